@@ -0,0 +1,275 @@
+//! # Derive macros for vulkan-rs
+//! Implementations of `vulkan_rs` traits generated from type definitions.
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+use syn::{
+    parse_macro_input, Data, DeriveInput, Error, Expr, ExprLit, Fields, Ident, Index, Lit, Member,
+    Type,
+};
+
+/// Implements `vulkan_rs::pipeline::Vertex` for a `#[repr(C)]` struct, with one vertex
+/// attribute per field at consecutive locations.
+///
+/// Formats follow from the field types: scalars and arrays of up to four `f32`, `f64`, `u32`,
+/// `i32`, `u16`, `i16`, `u8` or `i8` map to the matching `Sfloat`, `Uint` or `Sint` format, and
+/// arrays of such arrays are matrices taking one location per column. Fields accept these
+/// attributes:
+/// - `#[vertex(normalized)]` reads 8 and 16-bit integers as `Unorm` or `Snorm` floats.
+/// - `#[vertex(format = R8g8b8a8Srgb)]` uses the given `Format` variant instead.
+/// - `#[vertex(skip)]` leaves the field out, such as padding.
+#[proc_macro_derive(Vertex, attributes(vertex))]
+pub fn derive_vertex(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_vertex(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Implements `vulkan_rs::resource::Pod` for a `#[repr(C)]` struct whose fields all
+/// implement `Pod`. Compilation fails if the struct has padding between or after its fields.
+#[proc_macro_derive(Pod)]
+pub fn derive_pod(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_pod(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+struct FieldOptions {
+    normalized: bool,
+    format: Option<Ident>,
+    skip: bool,
+}
+
+fn expand_vertex(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    if !is_repr_c(input) {
+        return Err(Error::new(
+            input.ident.span(),
+            "`Vertex` can only be derived for `#[repr(C)]` structs",
+        ));
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(Error::new(
+                input.ident.span(),
+                "`Vertex` can only be derived for structs",
+            ))
+        }
+    };
+    let members: Vec<(Member, &syn::Field)> = match fields {
+        Fields::Named(fields) => fields
+            .named
+            .iter()
+            .map(|field| (Member::Named(field.ident.clone().unwrap()), field))
+            .collect(),
+        Fields::Unnamed(fields) => fields
+            .unnamed
+            .iter()
+            .enumerate()
+            .map(|(i, field)| (Member::Unnamed(Index::from(i)), field))
+            .collect(),
+        Fields::Unit => Vec::new(),
+    };
+
+    let mut attributes = Vec::new();
+    for (member, field) in members {
+        let options = field_options(field)?;
+        if options.skip {
+            continue;
+        }
+
+        // Matrices are arrays of columns, each column being one attribute.
+        let (column, columns) = match array(&field.ty)? {
+            Some((element, len)) if array(element)?.is_some() => (element, len),
+            _ => (&field.ty, 1),
+        };
+        let format = match options.format {
+            Some(format) => format,
+            None => column_format(column, options.normalized)?,
+        };
+
+        for i in 0..columns {
+            attributes.push(quote! {
+                (
+                    ::vulkan_rs::format::Format::#format,
+                    (::core::mem::offset_of!(Self, #member)
+                        + #i * ::core::mem::size_of::<#column>()) as u32,
+                )
+            });
+        }
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::vulkan_rs::pipeline::Vertex for #ident #ty_generics #where_clause {
+            fn attribute_descriptions(
+                binding: u32,
+                first_location: u32,
+            ) -> ::std::vec::Vec<::vulkan_rs::pipeline::VertexInputAttributeDescription> {
+                ::vulkan_rs::pipeline::VertexInputAttributeDescription::consecutive(
+                    binding,
+                    first_location,
+                    &[#(#attributes),*],
+                )
+            }
+        }
+    })
+}
+
+fn expand_pod(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    if !is_repr_c(input) {
+        return Err(Error::new(
+            input.ident.span(),
+            "`Pod` can only be derived for `#[repr(C)]` structs",
+        ));
+    }
+    if !input.generics.params.is_empty() {
+        return Err(Error::new(
+            input.generics.span(),
+            "`Pod` cannot be derived for generic structs",
+        ));
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(Error::new(
+                input.ident.span(),
+                "`Pod` can only be derived for structs",
+            ))
+        }
+    };
+    let types: Vec<&Type> = fields.iter().map(|field| &field.ty).collect();
+    let ident = &input.ident;
+    let message = format!("`{}` has padding and cannot be `Pod`", ident);
+
+    // NOTE: The bounds on concrete field types are checked when the impl is compiled, which
+    // rejects fields that are not `Pod` themselves.
+    Ok(quote! {
+        const _: () = assert!(
+            ::std::mem::size_of::<#ident>() == 0 #(+ ::std::mem::size_of::<#types>())*,
+            #message,
+        );
+
+        unsafe impl ::vulkan_rs::resource::Pod for #ident
+        where
+            #(#types: ::vulkan_rs::resource::Pod,)*
+        {
+        }
+    })
+}
+
+fn is_repr_c(input: &DeriveInput) -> bool {
+    input.attrs.iter().any(|attr| {
+        let mut repr_c = false;
+        if attr.path().is_ident("repr") {
+            let _ = attr.parse_nested_meta(|meta| {
+                repr_c |= meta.path.is_ident("C");
+                Ok(())
+            });
+        }
+        repr_c
+    })
+}
+
+fn field_options(field: &syn::Field) -> syn::Result<FieldOptions> {
+    let mut options = FieldOptions {
+        normalized: false,
+        format: None,
+        skip: false,
+    };
+
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("vertex"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("normalized") {
+                options.normalized = true;
+            } else if meta.path.is_ident("skip") {
+                options.skip = true;
+            } else if meta.path.is_ident("format") {
+                options.format = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error("expected `normalized`, `format` or `skip`"));
+            }
+            Ok(())
+        })?;
+    }
+
+    Ok(options)
+}
+
+/// Element type and length of an array type.
+fn array(ty: &Type) -> syn::Result<Option<(&Type, usize)>> {
+    let array = match ty {
+        Type::Array(array) => array,
+        _ => return Ok(None),
+    };
+    match &array.len {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(len), ..
+        }) => Ok(Some((&array.elem, len.base10_parse()?))),
+        len => Err(Error::new(len.span(), "array length must be a literal")),
+    }
+}
+
+/// Format of a scalar or an array of up to four scalars.
+fn column_format(ty: &Type, normalized: bool) -> syn::Result<Ident> {
+    let (scalar, count) = match array(ty)? {
+        Some((element, len)) => (element, len),
+        None => (ty, 1),
+    };
+    let unsupported = || {
+        Error::new(
+            ty.span(),
+            "unsupported vertex attribute type, use `#[vertex(format = ...)]`",
+        )
+    };
+    if !(1..=4).contains(&count) {
+        return Err(unsupported());
+    }
+
+    let scalar = match scalar {
+        Type::Path(path) => path.path.get_ident().ok_or_else(unsupported)?.to_string(),
+        _ => return Err(unsupported()),
+    };
+    let (width, suffix) = match (scalar.as_str(), normalized) {
+        ("f32", false) => (32, "Sfloat"),
+        ("f64", false) => (64, "Sfloat"),
+        ("u32", false) => (32, "Uint"),
+        ("i32", false) => (32, "Sint"),
+        ("u16", false) => (16, "Uint"),
+        ("i16", false) => (16, "Sint"),
+        ("u8", false) => (8, "Uint"),
+        ("i8", false) => (8, "Sint"),
+        ("u16", true) => (16, "Unorm"),
+        ("i16", true) => (16, "Snorm"),
+        ("u8", true) => (8, "Unorm"),
+        ("i8", true) => (8, "Snorm"),
+        (_, true) => {
+            return Err(Error::new(
+                ty.span(),
+                "only 8 and 16-bit integers can be normalized",
+            ))
+        }
+        _ => return Err(unsupported()),
+    };
+
+    let components: String = ["R", "g", "b", "a"][..count]
+        .iter()
+        .map(|component| format!("{}{}", component, width))
+        .collect();
+    Ok(format_ident!(
+        "{}{}",
+        components,
+        suffix,
+        span = Span::call_site()
+    ))
+}
@@ -1,17 +1,21 @@
-use vulkan_rs::core::Rect2D;
 use vulkan_rs::device::PhysicalDeviceType;
 use vulkan_rs::format::Format;
 use vulkan_rs::init::{ApiVersion, ApplicationInfo, Instance};
 use vulkan_rs::pipeline::{
-    ColorComponentFlags, CullMode, FrontFace,
-    PipelineColorBlendAttachmentState, PipelineInputAssemblyStateCreateInfo,
+    ColorComponentFlags, CullMode, DynamicState, FrontFace, GraphicsPipelineBuilder,
+    PipelineColorBlendAttachmentState, PipelineColorBlendStateCreateInfo,
+    PipelineDynamicStateCreateInfo, PipelineInputAssemblyStateCreateInfo, PipelineLayoutBuilder,
     PipelineMultisampleStateCreateInfo, PipelineRasterizationStateCreateInfo,
     PipelineShaderStageCreateInfo, PipelineVertexInputStateCreateInfo,
-    PipelineViewportStateCreateInfo, PrimitiveTopology, ShaderStage, Viewport,
+    PipelineViewportStateCreateInfo, PrimitiveTopology, ShaderStage,
+};
+use vulkan_rs::render_pass::{
+    AttachmentDescription, AttachmentLoadOp, AttachmentReference, AttachmentStoreOp,
+    RenderPassBuilder, SubpassDescription,
 };
 use vulkan_rs::resource::{
-    ImageAspectFlagsBuilder, ImageSubresourceRange, ImageUsageFlagsBuilder, ImageViewBuilder,
-    ImageViewType, SharingMode,
+    ImageAspectFlagsBuilder, ImageLayout, ImageSubresourceRange, ImageUsageFlagsBuilder,
+    ImageViewBuilder, ImageViewType, SharingMode,
 };
 use vulkan_rs::wsi::{ColorSpaceKhr, CompositeAlphaKhr, PresentModeKhr, SwapchainBuilderKhr};
 use vulkan_rs::wsi::{
@@ -53,8 +57,12 @@ fn main() {
         })
         .expect("Could not find suitable GPU.");
 
-    let device =
-        physical_device.create_device(&[0], &[&[1.0f32]], Some(&[KHR_SWAPCHAIN_EXTENSION_NAME]));
+    let device = physical_device.create_device(
+        &[0],
+        &[&[1.0f32]],
+        Some(&[KHR_SWAPCHAIN_EXTENSION_NAME]),
+        None,
+    );
 
     let _queue = device.get_queue(0, 0).unwrap();
 
@@ -140,38 +148,32 @@ fn main() {
         })
         .collect();
 
-    let vert = std::fs::read("examples/vert.spv").unwrap();
-    let frag = std::fs::read("examples/frag.spv").unwrap();
+    let vert = vulkan_rs::include_spirv!("vert.spv");
+    let frag = vulkan_rs::include_spirv!("frag.spv");
 
-    let vert_module = device.create_shader_module(&vert).unwrap();
-    let frag_module = device.create_shader_module(&frag).unwrap();
+    let vert_module = device.create_shader_module(vert).unwrap();
+    let frag_module = device.create_shader_module(frag).unwrap();
 
     let vert_shader_stage = PipelineShaderStageCreateInfo::new(&vert_module);
     let frag_shader_stage =
         PipelineShaderStageCreateInfo::new(&frag_module).with_stage(ShaderStage::Fragment);
 
-    let _shader_stages = [vert_shader_stage, frag_shader_stage];
-    let _vertex_input_info = PipelineVertexInputStateCreateInfo::default();
-    let _input_assembly =
+    let shader_stages = [vert_shader_stage, frag_shader_stage];
+    let vertex_input_info = PipelineVertexInputStateCreateInfo::default();
+    let input_assembly =
         PipelineInputAssemblyStateCreateInfo::new().with_topology(PrimitiveTopology::TriangleList);
 
-    let viewport = Viewport::new(
-        0f32,
-        0f32,
-        surface_capabilities.current_extent.width() as f32,
-        surface_capabilities.current_extent.height() as f32,
-        0f32,
-        1f32,
-    );
-    let scissor = Rect2D::new((0, 0).into(), surface_capabilities.current_extent);
-    let _viewport_state = PipelineViewportStateCreateInfo::new()
-        .with_viewports(std::array::from_ref(&viewport))
-        .with_scissors(std::array::from_ref(&scissor));
-    let _rasterizer = PipelineRasterizationStateCreateInfo::new()
+    // NOTE: Viewport and scissor are set when recording, so the pipeline survives window resizes.
+    let viewport_state = PipelineViewportStateCreateInfo::new()
+        .with_viewport_count(1)
+        .with_scissor_count(1);
+    let dynamic_state = PipelineDynamicStateCreateInfo::new()
+        .with_dynamic_states(&[DynamicState::Viewport, DynamicState::Scissor]);
+    let rasterizer = PipelineRasterizationStateCreateInfo::new()
         .with_line_width(1f32)
         .with_cull_mode(CullMode::Back)
         .with_front_face(FrontFace::Clockwise);
-    let _multisampling = PipelineMultisampleStateCreateInfo::new();
+    let multisampling = PipelineMultisampleStateCreateInfo::new();
     let color_blend_attachment = PipelineColorBlendAttachmentState::new().with_color_write_mask(
         ColorComponentFlags::new()
             .with_red(true)
@@ -179,7 +181,37 @@ fn main() {
             .with_blue(true)
             .with_alpha(true),
     );
-    dbg!(color_blend_attachment);
+    let color_blending = PipelineColorBlendStateCreateInfo::new()
+        .with_attachments(std::array::from_ref(&color_blend_attachment));
+
+    let color_attachment_ref = AttachmentReference::new(0, ImageLayout::ColorAttachmentOptimal);
+    let render_pass = RenderPassBuilder::new()
+        .with_attachment(
+            AttachmentDescription::new(surface_format.format)
+                .with_load_op(AttachmentLoadOp::Clear)
+                .with_store_op(AttachmentStoreOp::Store)
+                .with_final_layout(ImageLayout::PresentSrcKhr),
+        )
+        .with_subpass(
+            SubpassDescription::new()
+                .with_color_attachments(std::array::from_ref(&color_attachment_ref)),
+        )
+        .build(&device)
+        .unwrap();
+
+    let pipeline_layout = PipelineLayoutBuilder::new().build(&device).unwrap();
+
+    let _pipeline = GraphicsPipelineBuilder::new(&shader_stages, &pipeline_layout)
+        .with_render_pass(&render_pass, 0)
+        .with_vertex_input_state(vertex_input_info)
+        .with_input_assembly_state(input_assembly)
+        .with_viewport_state(viewport_state)
+        .with_rasterization_state(rasterizer)
+        .with_multisample_state(multisampling)
+        .with_color_blend_state(color_blending)
+        .with_dynamic_state(dynamic_state)
+        .build(&device)
+        .unwrap();
 
     event_loop.run_return(|event, _, control_flow| {
         *control_flow = ControlFlow::Poll;
@@ -1,11 +1,22 @@
 use std::marker::PhantomData;
 use std::ptr::NonNull;
 
+use crate::core::{Error, Extent3D, Offset3D, Rect2D, Result};
+use crate::descriptor::DescriptorSet;
 use crate::device::Device;
 use crate::ffi;
+use crate::pipeline::{
+    CullMode, FrontFace, Pipeline, PipelineBindPoint, PipelineLayout, PrimitiveTopology,
+    ShaderStageFlags, StencilFace, Viewport,
+};
+use crate::query::QueryPool;
+use crate::render_pass::RenderingInfo;
+use crate::resource::{Buffer, BufferUsage, Image, ImageLayout, ImageSubresourceLayers, Pod};
+use crate::sync::{DependencyInfo, Event, ImageMemoryBarrier, PipelineStage, PipelineStageFlags};
 
 pub struct CommandBuffer<'a> {
-    handle: NonNull<ffi::VkCommandBuffer_T>,
+    pub(crate) handle: NonNull<ffi::VkCommandBuffer_T>,
+    device: &'a Device<'a>,
     _marker: PhantomData<(ffi::VkCommandBuffer_T, &'a CommandPool<'a>)>,
 }
 
@@ -19,6 +30,37 @@ pub struct CommandPool<'a> {
     pub(crate) _marker: PhantomData<ffi::VkCommandPool_T>,
 }
 
+/// A region copied by [`CommandBuffer::copy_buffer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BufferCopy {
+    src_offset: u64,
+    dst_offset: u64,
+    size: u64,
+}
+
+/// A region copied by [`CommandBuffer::copy_buffer_to_image`].
+#[derive(Debug, Clone, Copy)]
+pub struct BufferImageCopy {
+    buffer_offset: u64,
+    buffer_row_length: u32,
+    buffer_image_height: u32,
+    image_subresource: ImageSubresourceLayers,
+    image_offset: Offset3D,
+    image_extent: Extent3D,
+}
+
+pub enum CommandBufferUsage {
+    OneTimeSubmit,
+    RenderPassContinue,
+    SimultaneousUse,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CommandBufferUsageFlags(u32);
+
+#[derive(Default)]
+pub struct CommandBufferUsageFlagsBuilder(u32);
+
 impl<'a> CommandPool<'a> {
     pub fn allocate_command_buffers(&self, buffer_count: usize) -> Vec<CommandBuffer<'_>> {
         let create_info = ffi::CommandBufferAllocateInfo {
@@ -48,6 +90,7 @@ impl<'a> CommandPool<'a> {
                 .into_iter()
                 .map(|buffer| CommandBuffer {
                     handle: unsafe { NonNull::new_unchecked(buffer) },
+                    device: self.device,
                     _marker: PhantomData,
                 })
                 .collect()
@@ -72,3 +115,981 @@ impl<'a> Drop for CommandPool<'a> {
         }
     }
 }
+
+impl<'a> CommandBuffer<'a> {
+    /// Starts recording the command buffer.
+    ///
+    /// # Errors
+    ///
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    pub fn begin(&mut self, flags: CommandBufferUsageFlags) -> Result<()> {
+        let begin_info = ffi::CommandBufferBeginInfo {
+            s_type: ffi::StructureType::CommandBufferBeginInfo,
+            p_next: std::ptr::null(),
+            flags: flags.0,
+            p_inheritance_info: std::ptr::null(),
+        };
+
+        let result = unsafe {
+            (self.device.dispatch_loader.vk_begin_command_buffer)(self.handle.as_ptr(), &begin_info)
+        };
+
+        match result {
+            ffi::Result::Success => Ok(()),
+            ffi::Result::ErrorOutOfHostMemory => Err(Error::OutOfHostMemory),
+            ffi::Result::ErrorOutOfDeviceMemory => Err(Error::OutOfDeviceMemory),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Finishes recording the command buffer.
+    ///
+    /// # Errors
+    ///
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    pub fn end(&mut self) -> Result<()> {
+        let result =
+            unsafe { (self.device.dispatch_loader.vk_end_command_buffer)(self.handle.as_ptr()) };
+
+        match result {
+            ffi::Result::Success => Ok(()),
+            ffi::Result::ErrorOutOfHostMemory => Err(Error::OutOfHostMemory),
+            ffi::Result::ErrorOutOfDeviceMemory => Err(Error::OutOfDeviceMemory),
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn bind_pipeline(&mut self, pipeline: &Pipeline) {
+        unsafe {
+            (self.device.dispatch_loader.vk_cmd_bind_pipeline)(
+                self.handle.as_ptr(),
+                pipeline.bind_point().into(),
+                #[cfg(target_pointer_width = "64")]
+                pipeline.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                pipeline.handle.get(),
+            );
+        }
+    }
+
+    /// Binds `sets` to consecutive set numbers of `layout` starting at `first_set`.
+    ///
+    /// The sets stay borrowed for as long as the command buffer, since the recorded commands
+    /// read them when the command buffer is executed.
+    ///
+    /// # Errors
+    /// The errors of [`PipelineLayout::validate_descriptor_sets`].
+    pub fn bind_descriptor_sets<'r: 'a>(
+        &mut self,
+        bind_point: PipelineBindPoint,
+        layout: &PipelineLayout,
+        first_set: u32,
+        sets: &[&'r DescriptorSet<'r>],
+        dynamic_offsets: &[u32],
+    ) -> Result<()> {
+        layout.validate_descriptor_sets(first_set, sets, dynamic_offsets)?;
+
+        #[cfg(target_pointer_width = "64")]
+        let handles: Vec<_> = sets.iter().map(|set| set.handle.as_ptr()).collect();
+        #[cfg(not(target_pointer_width = "64"))]
+        let handles: Vec<_> = sets.iter().map(|set| set.handle.get()).collect();
+        unsafe {
+            (self.device.dispatch_loader.vk_cmd_bind_descriptor_sets)(
+                self.handle.as_ptr(),
+                bind_point.into(),
+                #[cfg(target_pointer_width = "64")]
+                layout.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                layout.handle.get(),
+                first_set,
+                handles.len() as u32,
+                handles.as_ptr(),
+                dynamic_offsets.len() as u32,
+                dynamic_offsets.as_ptr(),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Updates the push constants of `stages` at `offset` with the bytes of `value`.
+    ///
+    /// # Errors
+    /// The errors of [`PipelineLayout::validate_push_constants`] for the size of `T`.
+    pub fn push_constants<T: Pod>(
+        &mut self,
+        layout: &PipelineLayout,
+        stages: ShaderStageFlags,
+        offset: u32,
+        value: &T,
+    ) -> Result<()> {
+        let size = std::mem::size_of::<T>() as u32;
+        layout.validate_push_constants(stages, offset, size)?;
+
+        unsafe {
+            (self.device.dispatch_loader.vk_cmd_push_constants)(
+                self.handle.as_ptr(),
+                #[cfg(target_pointer_width = "64")]
+                layout.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                layout.handle.get(),
+                stages.0,
+                offset,
+                size,
+                (value as *const T).cast(),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Copies `regions` from `src` to `dst`.
+    ///
+    /// # Errors
+    /// - [`MemoryRangeOutOfBounds`](Error::MemoryRangeOutOfBounds) if a region is empty or
+    ///   extends past the end of either buffer.
+    pub fn copy_buffer(
+        &mut self,
+        src: &Buffer,
+        dst: &Buffer,
+        regions: &[BufferCopy],
+    ) -> Result<()> {
+        for region in regions {
+            for (offset, buffer) in [(region.src_offset, src), (region.dst_offset, dst)] {
+                if region.size == 0
+                    || offset
+                        .checked_add(region.size)
+                        .is_none_or(|end| end > buffer.size())
+                {
+                    return Err(Error::MemoryRangeOutOfBounds {
+                        offset,
+                        size: region.size,
+                    });
+                }
+            }
+        }
+
+        let regions: Vec<ffi::BufferCopy> = regions.iter().map(|&region| region.into()).collect();
+
+        unsafe {
+            (self.device.dispatch_loader.vk_cmd_copy_buffer)(
+                self.handle.as_ptr(),
+                #[cfg(target_pointer_width = "64")]
+                src.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                src.handle.get(),
+                #[cfg(target_pointer_width = "64")]
+                dst.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                dst.handle.get(),
+                regions.len() as u32,
+                regions.as_ptr(),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Copies `regions` from `src` to `dst`, which has to be in `dst_layout`, either
+    /// [`TransferDstOptimal`](ImageLayout::TransferDstOptimal) or
+    /// [`General`](ImageLayout::General).
+    pub fn copy_buffer_to_image(
+        &mut self,
+        src: &Buffer,
+        dst: &Image,
+        dst_layout: ImageLayout,
+        regions: &[BufferImageCopy],
+    ) {
+        let regions: Vec<ffi::BufferImageCopy> =
+            regions.iter().map(|&region| region.into()).collect();
+
+        unsafe {
+            (self.device.dispatch_loader.vk_cmd_copy_buffer_to_image)(
+                self.handle.as_ptr(),
+                #[cfg(target_pointer_width = "64")]
+                src.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                src.handle.get(),
+                #[cfg(target_pointer_width = "64")]
+                dst.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                dst.handle.get(),
+                dst_layout.into(),
+                regions.len() as u32,
+                regions.as_ptr(),
+            );
+        }
+    }
+
+    /// Records the barriers of `dependency_info`, with the stages of all barriers combined.
+    /// Without synchronization2, [`ImageLayout::ReadOnlyOptimalKhr`] and
+    /// [`ImageLayout::AttachmentOptimalKhr`] are replaced by the layouts of the aspects of
+    /// each barrier, which descriptors and render passes then have to use too. Updates the
+    /// tracked layouts of images, see [`Image::track_layouts`].
+    pub fn pipeline_barrier(&mut self, dependency_info: &DependencyInfo) {
+        let memory_barriers: Vec<ffi::MemoryBarrier> = dependency_info
+            .memory_barriers()
+            .iter()
+            .map(|barrier| barrier.into())
+            .collect();
+        let buffer_barriers: Vec<ffi::BufferMemoryBarrier> = dependency_info
+            .buffer_barriers()
+            .iter()
+            .map(|barrier| barrier.into())
+            .collect();
+        let image_barriers: Vec<ffi::ImageMemoryBarrier> = dependency_info
+            .image_barriers()
+            .iter()
+            .map(|barrier| barrier.to_legacy(self.device.enabled_features()))
+            .collect();
+        record_transitions(dependency_info);
+        let (src_stage_mask, dst_stage_mask) =
+            dependency_info.combined_stages(self.device.enabled_features());
+
+        unsafe {
+            (self.device.dispatch_loader.vk_cmd_pipeline_barrier)(
+                self.handle.as_ptr(),
+                src_stage_mask,
+                dst_stage_mask,
+                dependency_info.dependency_flags(),
+                memory_barriers.len() as u32,
+                memory_barriers.as_ptr(),
+                buffer_barriers.len() as u32,
+                buffer_barriers.as_ptr(),
+                image_barriers.len() as u32,
+                image_barriers.as_ptr(),
+            );
+        }
+    }
+
+    /// Records the barriers of `dependency_info` with their own stages and accesses if the
+    /// device supports synchronization2, otherwise lowers them to
+    /// [`pipeline_barrier`](Self::pipeline_barrier). Updates the tracked layouts of images.
+    pub fn pipeline_barrier2(&mut self, dependency_info: &DependencyInfo) {
+        let vk_cmd_pipeline_barrier2 = match self.device.dispatch_loader.vk_cmd_pipeline_barrier2 {
+            Some(vk_cmd_pipeline_barrier2) if self.device.supports_synchronization2() => {
+                vk_cmd_pipeline_barrier2
+            }
+            _ => return self.pipeline_barrier(dependency_info),
+        };
+
+        record_transitions(dependency_info);
+        let barriers = Barriers2::new(dependency_info);
+        unsafe {
+            vk_cmd_pipeline_barrier2(self.handle.as_ptr(), &barriers.dependency_info());
+        }
+    }
+
+    /// Sets `event` once the source scopes of `dependency_info` have completed. The
+    /// matching [`wait_events2`](Self::wait_events2) must use the same barriers. Without
+    /// synchronization2 only the combined source stages are used.
+    pub fn set_event2(&mut self, event: &Event, dependency_info: &DependencyInfo) {
+        match self.device.dispatch_loader.vk_cmd_set_event2 {
+            Some(vk_cmd_set_event2) if self.device.supports_synchronization2() => {
+                let barriers = Barriers2::new(dependency_info);
+                unsafe {
+                    vk_cmd_set_event2(
+                        self.handle.as_ptr(),
+                        #[cfg(target_pointer_width = "64")]
+                        event.handle.as_ptr(),
+                        #[cfg(not(target_pointer_width = "64"))]
+                        event.handle.get(),
+                        &barriers.dependency_info(),
+                    );
+                }
+            }
+            _ => {
+                let (src_stage_mask, _) =
+                    dependency_info.combined_stages(self.device.enabled_features());
+                unsafe {
+                    (self.device.dispatch_loader.vk_cmd_set_event)(
+                        self.handle.as_ptr(),
+                        #[cfg(target_pointer_width = "64")]
+                        event.handle.as_ptr(),
+                        #[cfg(not(target_pointer_width = "64"))]
+                        event.handle.get(),
+                        src_stage_mask,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Resets `event` once the commands before it have completed `stages`.
+    pub fn reset_event2(&mut self, event: &Event, stages: PipelineStageFlags) {
+        match self.device.dispatch_loader.vk_cmd_reset_event2 {
+            Some(vk_cmd_reset_event2) if self.device.supports_synchronization2() => unsafe {
+                vk_cmd_reset_event2(
+                    self.handle.as_ptr(),
+                    #[cfg(target_pointer_width = "64")]
+                    event.handle.as_ptr(),
+                    #[cfg(not(target_pointer_width = "64"))]
+                    event.handle.get(),
+                    stages.0,
+                );
+            },
+            _ => unsafe {
+                (self.device.dispatch_loader.vk_cmd_reset_event)(
+                    self.handle.as_ptr(),
+                    #[cfg(target_pointer_width = "64")]
+                    event.handle.as_ptr(),
+                    #[cfg(not(target_pointer_width = "64"))]
+                    event.handle.get(),
+                    stages.to_legacy(self.device.enabled_features()),
+                );
+            },
+        }
+    }
+
+    /// Waits for each event to be set, then executes the barriers it was set with. Without
+    /// synchronization2 the stages of all events are combined into one wait. Updates the
+    /// tracked layouts of images.
+    pub fn wait_events2(&mut self, events: &[(&Event, &DependencyInfo)]) {
+        #[cfg(target_pointer_width = "64")]
+        let handles: Vec<_> = events
+            .iter()
+            .map(|(event, _)| event.handle.as_ptr())
+            .collect();
+        #[cfg(not(target_pointer_width = "64"))]
+        let handles: Vec<_> = events.iter().map(|(event, _)| event.handle.get()).collect();
+        for (_, dependency_info) in events {
+            record_transitions(dependency_info);
+        }
+
+        match self.device.dispatch_loader.vk_cmd_wait_events2 {
+            Some(vk_cmd_wait_events2) if self.device.supports_synchronization2() => {
+                let barriers: Vec<Barriers2> = events
+                    .iter()
+                    .map(|(_, dependency_info)| Barriers2::new(dependency_info))
+                    .collect();
+                let dependency_infos: Vec<ffi::DependencyInfo> = barriers
+                    .iter()
+                    .map(|barriers| barriers.dependency_info())
+                    .collect();
+
+                unsafe {
+                    vk_cmd_wait_events2(
+                        self.handle.as_ptr(),
+                        handles.len() as u32,
+                        handles.as_ptr(),
+                        dependency_infos.as_ptr(),
+                    );
+                }
+            }
+            _ => {
+                let features = self.device.enabled_features();
+                let (src_stage_mask, dst_stage_mask) =
+                    events
+                        .iter()
+                        .fold((0, 0), |(src, dst), (_, dependency_info)| {
+                            let (event_src, event_dst) = dependency_info.combined_stages(features);
+                            (src | event_src, dst | event_dst)
+                        });
+                let memory_barriers: Vec<ffi::MemoryBarrier> = events
+                    .iter()
+                    .flat_map(|(_, dependency_info)| dependency_info.memory_barriers())
+                    .map(|barrier| barrier.into())
+                    .collect();
+                let buffer_barriers: Vec<ffi::BufferMemoryBarrier> = events
+                    .iter()
+                    .flat_map(|(_, dependency_info)| dependency_info.buffer_barriers())
+                    .map(|barrier| barrier.into())
+                    .collect();
+                let image_barriers: Vec<ffi::ImageMemoryBarrier> = events
+                    .iter()
+                    .flat_map(|(_, dependency_info)| dependency_info.image_barriers())
+                    .map(|barrier| barrier.to_legacy(features))
+                    .collect();
+
+                unsafe {
+                    (self.device.dispatch_loader.vk_cmd_wait_events)(
+                        self.handle.as_ptr(),
+                        handles.len() as u32,
+                        handles.as_ptr(),
+                        src_stage_mask,
+                        dst_stage_mask,
+                        memory_barriers.len() as u32,
+                        memory_barriers.as_ptr(),
+                        buffer_barriers.len() as u32,
+                        buffer_barriers.as_ptr(),
+                        image_barriers.len() as u32,
+                        image_barriers.as_ptr(),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Resets `query_count` queries starting at `first_query`, which must be done before they
+    /// are used again.
+    ///
+    /// # Errors
+    /// - [`QueryOutOfBounds`](Error::QueryOutOfBounds) if the queries extend past the end of
+    ///   the pool.
+    pub fn reset_query_pool(
+        &mut self,
+        query_pool: &QueryPool,
+        first_query: u32,
+        query_count: u32,
+    ) -> Result<()> {
+        query_pool.check_range(first_query, query_count)?;
+
+        unsafe {
+            (self.device.dispatch_loader.vk_cmd_reset_query_pool)(
+                self.handle.as_ptr(),
+                #[cfg(target_pointer_width = "64")]
+                query_pool.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                query_pool.handle.get(),
+                first_query,
+                query_count,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Writes the device time to `query` once all previous commands have completed `stage`.
+    /// Without synchronization2 a stage only known to it is replaced by the latest legacy
+    /// stage containing it.
+    ///
+    /// # Errors
+    /// - [`QueryOutOfBounds`](Error::QueryOutOfBounds) if `query` is out of bounds of the
+    ///   pool.
+    pub fn write_timestamp2(
+        &mut self,
+        stage: PipelineStage,
+        query_pool: &QueryPool,
+        query: u32,
+    ) -> Result<()> {
+        query_pool.check_range(query, 1)?;
+
+        let stages = PipelineStageFlags::from(stage);
+        match self.device.dispatch_loader.vk_cmd_write_timestamp2 {
+            Some(vk_cmd_write_timestamp2) if self.device.supports_synchronization2() => unsafe {
+                vk_cmd_write_timestamp2(
+                    self.handle.as_ptr(),
+                    stages.0,
+                    #[cfg(target_pointer_width = "64")]
+                    query_pool.handle.as_ptr(),
+                    #[cfg(not(target_pointer_width = "64"))]
+                    query_pool.handle.get(),
+                    query,
+                );
+            },
+            _ => {
+                let legacy = stages.to_legacy(self.device.enabled_features());
+                let latest_stage = if legacy == 0 {
+                    ffi::PipelineStageFlagBits::TopOfPipeBit as u32
+                } else {
+                    1 << (31 - legacy.leading_zeros())
+                };
+
+                unsafe {
+                    (self.device.dispatch_loader.vk_cmd_write_timestamp)(
+                        self.handle.as_ptr(),
+                        latest_stage,
+                        #[cfg(target_pointer_width = "64")]
+                        query_pool.handle.as_ptr(),
+                        #[cfg(not(target_pointer_width = "64"))]
+                        query_pool.handle.get(),
+                        query,
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Transitions all subresources of `image` from `old_layout` to `new_layout`, waiting for
+    /// the stages and accesses that typically use an image in the old layout and blocking
+    /// those that use it in the new one.
+    pub fn transition_image(
+        &mut self,
+        image: &Image,
+        old_layout: ImageLayout,
+        new_layout: ImageLayout,
+    ) {
+        self.pipeline_barrier2(
+            &DependencyInfo::new()
+                .with_image_barrier(ImageMemoryBarrier::new(image, old_layout, new_layout)),
+        );
+    }
+
+    /// Like [`transition_image`](Self::transition_image), from the tracked layout of the
+    /// image.
+    ///
+    /// # Errors
+    /// - [`ImageLayoutNotTracked`](Error::ImageLayoutNotTracked) if the layouts of the image
+    ///   are not tracked or differ between its subresources.
+    pub fn transition_image_to(&mut self, image: &Image, new_layout: ImageLayout) -> Result<()> {
+        let old_layout = image
+            .layout(&image.subresource_range())
+            .ok_or(Error::ImageLayoutNotTracked)?;
+
+        self.transition_image(image, old_layout, new_layout);
+        Ok(())
+    }
+
+    /// Dispatches `group_count_x * group_count_y * group_count_z` local workgroups of the bound
+    /// compute pipeline.
+    pub fn dispatch(&mut self, group_count_x: u32, group_count_y: u32, group_count_z: u32) {
+        unsafe {
+            (self.device.dispatch_loader.vk_cmd_dispatch)(
+                self.handle.as_ptr(),
+                group_count_x,
+                group_count_y,
+                group_count_z,
+            );
+        }
+    }
+
+    /// Dispatches with workgroup counts read from `buffer` at `offset`, laid out like
+    /// `VkDispatchIndirectCommand`.
+    ///
+    /// # Errors
+    /// - [`MissingBufferUsage`](Error::MissingBufferUsage) if the buffer lacks the
+    ///   [`IndirectBuffer`](BufferUsage::IndirectBuffer) usage.
+    /// - [`MisalignedMemoryOffset`](Error::MisalignedMemoryOffset) if `offset` is not a
+    ///   multiple of 4.
+    /// - [`MemoryRangeOutOfBounds`](Error::MemoryRangeOutOfBounds) if the command extends
+    ///   past the end of the buffer.
+    pub fn dispatch_indirect(&mut self, buffer: &Buffer, offset: u64) -> Result<()> {
+        const COMMAND_SIZE: u64 = 3 * std::mem::size_of::<u32>() as u64;
+
+        if !buffer.usage().contains(BufferUsage::IndirectBuffer) {
+            return Err(Error::MissingBufferUsage(BufferUsage::IndirectBuffer));
+        }
+        if !offset.is_multiple_of(4) {
+            return Err(Error::MisalignedMemoryOffset {
+                offset,
+                alignment: 4,
+            });
+        }
+        if offset
+            .checked_add(COMMAND_SIZE)
+            .is_none_or(|end| end > buffer.size())
+        {
+            return Err(Error::MemoryRangeOutOfBounds {
+                offset,
+                size: COMMAND_SIZE,
+            });
+        }
+
+        unsafe {
+            (self.device.dispatch_loader.vk_cmd_dispatch_indirect)(
+                self.handle.as_ptr(),
+                #[cfg(target_pointer_width = "64")]
+                buffer.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                buffer.handle.get(),
+                offset,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Dispatches workgroups with non-zero base values for the workgroup ID. The bound pipeline
+    /// must be created with the dispatch base flag.
+    ///
+    /// Returns [`None`] if neither Vulkan 1.1 nor `"VK_KHR_device_group"` is available.
+    pub fn dispatch_base(&mut self, base_group: [u32; 3], group_count: [u32; 3]) -> Option<()> {
+        let vk_cmd_dispatch_base = self.device.dispatch_loader.vk_cmd_dispatch_base?;
+
+        unsafe {
+            vk_cmd_dispatch_base(
+                self.handle.as_ptr(),
+                base_group[0],
+                base_group[1],
+                base_group[2],
+                group_count[0],
+                group_count[1],
+                group_count[2],
+            );
+        }
+
+        Some(())
+    }
+
+    /// Sets the viewports starting at `first_viewport` for pipelines with dynamic
+    /// [`Viewport`](crate::pipeline::DynamicState::Viewport) state.
+    pub fn set_viewport(&mut self, first_viewport: u32, viewports: &[Viewport]) {
+        let viewports: Vec<ffi::Viewport> =
+            viewports.iter().map(|viewport| viewport.into()).collect();
+
+        unsafe {
+            (self.device.dispatch_loader.vk_cmd_set_viewport)(
+                self.handle.as_ptr(),
+                first_viewport,
+                viewports.len() as u32,
+                viewports.as_ptr(),
+            );
+        }
+    }
+
+    /// Sets the scissor rectangles starting at `first_scissor` for pipelines with dynamic
+    /// [`Scissor`](crate::pipeline::DynamicState::Scissor) state.
+    pub fn set_scissor(&mut self, first_scissor: u32, scissors: &[Rect2D]) {
+        let scissors: Vec<ffi::Rect2D> = scissors.iter().map(|&scissor| scissor.into()).collect();
+
+        unsafe {
+            (self.device.dispatch_loader.vk_cmd_set_scissor)(
+                self.handle.as_ptr(),
+                first_scissor,
+                scissors.len() as u32,
+                scissors.as_ptr(),
+            );
+        }
+    }
+
+    pub fn set_line_width(&mut self, line_width: f32) {
+        unsafe {
+            (self.device.dispatch_loader.vk_cmd_set_line_width)(self.handle.as_ptr(), line_width);
+        }
+    }
+
+    pub fn set_depth_bias(
+        &mut self,
+        depth_bias_constant_factor: f32,
+        depth_bias_clamp: f32,
+        depth_bias_slope_factor: f32,
+    ) {
+        unsafe {
+            (self.device.dispatch_loader.vk_cmd_set_depth_bias)(
+                self.handle.as_ptr(),
+                depth_bias_constant_factor,
+                depth_bias_clamp,
+                depth_bias_slope_factor,
+            );
+        }
+    }
+
+    /// Sets the RGBA constants used by the `Constant*` blend factors.
+    pub fn set_blend_constants(&mut self, blend_constants: [f32; 4]) {
+        unsafe {
+            (self.device.dispatch_loader.vk_cmd_set_blend_constants)(
+                self.handle.as_ptr(),
+                blend_constants.as_ptr(),
+            );
+        }
+    }
+
+    pub fn set_depth_bounds(&mut self, min_depth_bounds: f32, max_depth_bounds: f32) {
+        unsafe {
+            (self.device.dispatch_loader.vk_cmd_set_depth_bounds)(
+                self.handle.as_ptr(),
+                min_depth_bounds,
+                max_depth_bounds,
+            );
+        }
+    }
+
+    pub fn set_stencil_compare_mask(&mut self, face: StencilFace, compare_mask: u32) {
+        unsafe {
+            (self.device.dispatch_loader.vk_cmd_set_stencil_compare_mask)(
+                self.handle.as_ptr(),
+                ffi::StencilFaceFlagBits::from(face) as u32,
+                compare_mask,
+            );
+        }
+    }
+
+    pub fn set_stencil_write_mask(&mut self, face: StencilFace, write_mask: u32) {
+        unsafe {
+            (self.device.dispatch_loader.vk_cmd_set_stencil_write_mask)(
+                self.handle.as_ptr(),
+                ffi::StencilFaceFlagBits::from(face) as u32,
+                write_mask,
+            );
+        }
+    }
+
+    pub fn set_stencil_reference(&mut self, face: StencilFace, reference: u32) {
+        unsafe {
+            (self.device.dispatch_loader.vk_cmd_set_stencil_reference)(
+                self.handle.as_ptr(),
+                ffi::StencilFaceFlagBits::from(face) as u32,
+                reference,
+            );
+        }
+    }
+
+    /// # Errors
+    /// - [`FeatureNotEnabled`](Error::FeatureNotEnabled) if the device does not
+    ///   [support extended dynamic state](Device::supports_extended_dynamic_state).
+    pub fn set_cull_mode(&mut self, cull_mode: CullMode) -> Result<()> {
+        let vk_cmd_set_cull_mode =
+            self.extended_dynamic_state(self.device.dispatch_loader.vk_cmd_set_cull_mode)?;
+
+        unsafe {
+            vk_cmd_set_cull_mode(
+                self.handle.as_ptr(),
+                ffi::CullModeFlagBits::from(cull_mode) as u32,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// # Errors
+    /// - [`FeatureNotEnabled`](Error::FeatureNotEnabled) if the device does not
+    ///   [support extended dynamic state](Device::supports_extended_dynamic_state).
+    pub fn set_front_face(&mut self, front_face: FrontFace) -> Result<()> {
+        let vk_cmd_set_front_face =
+            self.extended_dynamic_state(self.device.dispatch_loader.vk_cmd_set_front_face)?;
+
+        unsafe {
+            vk_cmd_set_front_face(self.handle.as_ptr(), front_face.into());
+        }
+
+        Ok(())
+    }
+
+    /// # Errors
+    /// - [`FeatureNotEnabled`](Error::FeatureNotEnabled) if the device does not
+    ///   [support extended dynamic state](Device::supports_extended_dynamic_state).
+    pub fn set_primitive_topology(&mut self, primitive_topology: PrimitiveTopology) -> Result<()> {
+        let vk_cmd_set_primitive_topology =
+            self.extended_dynamic_state(self.device.dispatch_loader.vk_cmd_set_primitive_topology)?;
+
+        unsafe {
+            vk_cmd_set_primitive_topology(self.handle.as_ptr(), primitive_topology.into());
+        }
+
+        Ok(())
+    }
+
+    /// # Errors
+    /// - [`FeatureNotEnabled`](Error::FeatureNotEnabled) if the device does not
+    ///   [support extended dynamic state](Device::supports_extended_dynamic_state).
+    pub fn set_depth_test_enable(&mut self, depth_test_enable: bool) -> Result<()> {
+        let vk_cmd_set_depth_test_enable =
+            self.extended_dynamic_state(self.device.dispatch_loader.vk_cmd_set_depth_test_enable)?;
+
+        unsafe {
+            vk_cmd_set_depth_test_enable(self.handle.as_ptr(), depth_test_enable.into());
+        }
+
+        Ok(())
+    }
+
+    /// # Errors
+    /// - [`FeatureNotEnabled`](Error::FeatureNotEnabled) if the device does not
+    ///   [support extended dynamic state](Device::supports_extended_dynamic_state).
+    pub fn set_depth_write_enable(&mut self, depth_write_enable: bool) -> Result<()> {
+        let vk_cmd_set_depth_write_enable =
+            self.extended_dynamic_state(self.device.dispatch_loader.vk_cmd_set_depth_write_enable)?;
+
+        unsafe {
+            vk_cmd_set_depth_write_enable(self.handle.as_ptr(), depth_write_enable.into());
+        }
+
+        Ok(())
+    }
+
+    /// `command` of extended dynamic state, if the device supports it.
+    fn extended_dynamic_state<F>(&self, command: Option<F>) -> Result<F> {
+        command
+            .filter(|_| self.device.supports_extended_dynamic_state())
+            .ok_or(Error::FeatureNotEnabled("extendedDynamicState"))
+    }
+
+    /// Begins a dynamic rendering pass, which has to be ended with
+    /// [`end_rendering`](Self::end_rendering). Returns [`None`] if the `dynamicRendering`
+    /// feature was not enabled on the device.
+    pub fn begin_rendering(&mut self, rendering_info: &RenderingInfo) -> Option<()> {
+        if !self.device.supports_dynamic_rendering() {
+            return None;
+        }
+        let vk_cmd_begin_rendering = self.device.dispatch_loader.vk_cmd_begin_rendering?;
+
+        let mut attachments = Vec::new();
+        let rendering_info = rendering_info.to_ffi(&mut attachments);
+
+        unsafe {
+            vk_cmd_begin_rendering(self.handle.as_ptr(), &rendering_info);
+        }
+
+        Some(())
+    }
+
+    /// Returns [`None`] if the `dynamicRendering` feature was not enabled on the device.
+    pub fn end_rendering(&mut self) -> Option<()> {
+        if !self.device.supports_dynamic_rendering() {
+            return None;
+        }
+        let vk_cmd_end_rendering = self.device.dispatch_loader.vk_cmd_end_rendering?;
+
+        unsafe {
+            vk_cmd_end_rendering(self.handle.as_ptr());
+        }
+
+        Some(())
+    }
+}
+
+impl BufferCopy {
+    pub fn new(src_offset: u64, dst_offset: u64, size: u64) -> Self {
+        Self {
+            src_offset,
+            dst_offset,
+            size,
+        }
+    }
+}
+
+impl From<BufferCopy> for ffi::BufferCopy {
+    fn from(region: BufferCopy) -> Self {
+        Self {
+            src_offset: region.src_offset,
+            dst_offset: region.dst_offset,
+            size: region.size,
+        }
+    }
+}
+
+impl BufferImageCopy {
+    /// Region whose texels are tightly packed in the buffer starting at `buffer_offset`.
+    pub fn new(
+        buffer_offset: u64,
+        image_subresource: ImageSubresourceLayers,
+        image_offset: Offset3D,
+        image_extent: Extent3D,
+    ) -> Self {
+        Self {
+            buffer_offset,
+            buffer_row_length: 0,
+            buffer_image_height: 0,
+            image_subresource,
+            image_offset,
+            image_extent,
+        }
+    }
+
+    /// Row length and image height in texels of the data in the buffer, if it is not tightly
+    /// packed. Zero means the size of the image extent.
+    pub fn with_buffer_layout(mut self, buffer_row_length: u32, buffer_image_height: u32) -> Self {
+        self.buffer_row_length = buffer_row_length;
+        self.buffer_image_height = buffer_image_height;
+        self
+    }
+}
+
+impl From<BufferImageCopy> for ffi::BufferImageCopy {
+    fn from(region: BufferImageCopy) -> Self {
+        Self {
+            buffer_offset: region.buffer_offset,
+            buffer_row_length: region.buffer_row_length,
+            buffer_image_height: region.buffer_image_height,
+            image_subresource: region.image_subresource.into(),
+            image_offset: region.image_offset.into(),
+            image_extent: region.image_extent.into(),
+        }
+    }
+}
+
+impl CommandBufferUsageFlags {
+    pub fn contains(&self, flag: CommandBufferUsage) -> bool {
+        let flag = ffi::CommandBufferUsageFlagBits::from(flag);
+
+        self.0 & flag as u32 != 0
+    }
+}
+
+impl From<CommandBufferUsage> for ffi::CommandBufferUsageFlagBits {
+    fn from(usage: CommandBufferUsage) -> Self {
+        match usage {
+            CommandBufferUsage::OneTimeSubmit => Self::OneTimeSubmitBit,
+            CommandBufferUsage::RenderPassContinue => Self::RenderPassContinueBit,
+            CommandBufferUsage::SimultaneousUse => Self::SimultaneousUseBit,
+        }
+    }
+}
+
+impl CommandBufferUsageFlagsBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn one_time_submit(&mut self, one_time_submit: bool) -> &mut Self {
+        if one_time_submit {
+            self.0 |= ffi::CommandBufferUsageFlagBits::OneTimeSubmitBit as u32;
+        }
+        self
+    }
+
+    pub fn render_pass_continue(&mut self, render_pass_continue: bool) -> &mut Self {
+        if render_pass_continue {
+            self.0 |= ffi::CommandBufferUsageFlagBits::RenderPassContinueBit as u32;
+        }
+        self
+    }
+
+    pub fn simultaneous_use(&mut self, simultaneous_use: bool) -> &mut Self {
+        if simultaneous_use {
+            self.0 |= ffi::CommandBufferUsageFlagBits::SimultaneousUseBit as u32;
+        }
+        self
+    }
+
+    pub fn build(&self) -> CommandBufferUsageFlags {
+        CommandBufferUsageFlags(self.0)
+    }
+}
+
+/// Updates the tracked layouts of the images transitioned by `dependency_info`.
+fn record_transitions(dependency_info: &DependencyInfo) {
+    for barrier in dependency_info.image_barriers() {
+        barrier.record_transition();
+    }
+}
+
+/// Synchronization2 barriers of a [`DependencyInfo`], kept alive while a
+/// `VkDependencyInfo` points to them.
+struct Barriers2 {
+    dependency_flags: u32,
+    memory_barriers: Vec<ffi::MemoryBarrier2>,
+    buffer_barriers: Vec<ffi::BufferMemoryBarrier2>,
+    image_barriers: Vec<ffi::ImageMemoryBarrier2>,
+}
+
+impl Barriers2 {
+    fn new(dependency_info: &DependencyInfo) -> Self {
+        Self {
+            dependency_flags: dependency_info.dependency_flags(),
+            memory_barriers: dependency_info
+                .memory_barriers()
+                .iter()
+                .map(|barrier| barrier.into())
+                .collect(),
+            buffer_barriers: dependency_info
+                .buffer_barriers()
+                .iter()
+                .map(|barrier| barrier.into())
+                .collect(),
+            image_barriers: dependency_info
+                .image_barriers()
+                .iter()
+                .map(|barrier| barrier.into())
+                .collect(),
+        }
+    }
+
+    fn dependency_info(&self) -> ffi::DependencyInfo {
+        ffi::DependencyInfo {
+            s_type: ffi::StructureType::DependencyInfo,
+            p_next: std::ptr::null(),
+            dependency_flags: self.dependency_flags,
+            memory_barrier_count: self.memory_barriers.len() as u32,
+            p_memory_barriers: self.memory_barriers.as_ptr(),
+            buffer_memory_barrier_count: self.buffer_barriers.len() as u32,
+            p_buffer_memory_barriers: self.buffer_barriers.as_ptr(),
+            image_memory_barrier_count: self.image_barriers.len() as u32,
+            p_image_memory_barriers: self.image_barriers.as_ptr(),
+        }
+    }
+}
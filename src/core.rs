@@ -1,4 +1,8 @@
 use crate::ffi;
+use crate::format::Format;
+use crate::pipeline::SampleCount;
+use crate::resource::{BufferUsage, ImageUsage};
+use crate::shaders::SpirvError;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -8,7 +12,7 @@ pub struct Extent2D {
     height: u32,
 }
 
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct Extent3D {
     width: u32,
     height: u32,
@@ -62,11 +66,403 @@ pub enum Error {
     InvalidDrmFormatModifierPlaneLayoutExt,
     NotPermittedExt,
     FullScreenExclusiveModeLostExt,
+    // Errors below are reported by this crate before the driver is called.
+    /// A requested value is above one of the
+    /// [`PhysicalDeviceLimits`](crate::device::PhysicalDeviceLimits) or
+    /// [`ImageFormatProperties`](crate::device::ImageFormatProperties).
+    LimitExceeded {
+        limit: &'static str,
+        value: u64,
+        max: u64,
+    },
+    /// The same binding number was declared more than once in a descriptor set layout.
+    DuplicateBinding(u32),
+    /// Immutable samplers were given for a binding whose descriptor type does not take
+    /// samplers, or their number does not match the descriptor count.
+    InvalidImmutableSamplers(u32),
+    /// Push constant offset must be a multiple of 4 and size a non-zero multiple of 4.
+    InvalidPushConstantRange {
+        offset: u32,
+        size: u32,
+    },
+    /// Pushed constants are not covered by a push constant range for every given stage, or
+    /// overlap a range with stages that were not given.
+    UndeclaredPushConstants {
+        offset: u32,
+        size: u32,
+    },
+    /// Two push constant ranges of a pipeline layout include the same shader stage.
+    OverlappingPushConstantRanges,
+    /// An attachment reference points past the attachments of a render pass.
+    InvalidAttachmentReference(u32),
+    /// A subpass index is not lower than the number of subpasses of a render pass.
+    InvalidSubpass(u32),
+    /// The depth/stencil attachment has a format without the depth or stencil aspect it is
+    /// used for.
+    InvalidDepthStencilFormat(Format),
+    /// A specialization constant is mapped more than once or lies outside the specialization
+    /// data.
+    InvalidSpecializationConstant(u32),
+    /// Shader code is not well-formed SPIR-V.
+    InvalidSpirv(SpirvError),
+    /// Shader stages declare different descriptor types for the same set and binding.
+    IncompatibleBinding {
+        set: u32,
+        binding: u32,
+    },
+    /// A vertex shader input location is not fed by any vertex attribute.
+    UnmatchedVertexInput(u32),
+    /// The format of a vertex attribute has another numeric type or width than the vertex
+    /// shader input at its location.
+    IncompatibleVertexFormat {
+        location: u32,
+        format: Format,
+    },
+    /// A fragment shader input location is not written by the previous stage with a matching
+    /// type.
+    UnmatchedFragmentInput(u32),
+    /// A fragment shader output location has no color blend attachment.
+    UnmatchedFragmentOutput(u32),
+    /// A resource is bound to memory a second time.
+    MemoryAlreadyBound,
+    /// A resource is bound to memory of a type outside its memory requirements.
+    IncompatibleMemoryType(u32),
+    /// A resource is bound at a memory offset that is not a multiple of its required alignment.
+    MisalignedMemoryOffset {
+        offset: u64,
+        alignment: u64,
+    },
+    /// A range of memory is empty or extends past the end of its allocation or mapping.
+    MemoryRangeOutOfBounds {
+        offset: u64,
+        size: u64,
+    },
+    /// A memory type index is not lower than the number of memory types of the device.
+    InvalidMemoryType(u32),
+    /// Memory is mapped that was not allocated from a host visible memory type.
+    MemoryNotHostVisible,
+    /// An image extent has a zero dimension or more dimensions than its image type.
+    InvalidImageExtent(Extent3D),
+    /// A cube compatible image is not a square 2D image with at least 6 array layers.
+    InvalidCubeCompatibleImage,
+    /// The sample count is not supported for the format and usage of an image.
+    UnsupportedSampleCount(SampleCount),
+    /// No memory type allowed by the memory requirements has the required properties.
+    NoSuitableMemoryType,
+    /// An allocation strategy cannot place an allocation even in an empty memory block.
+    UnsupportedAllocation {
+        size: u64,
+        alignment: u64,
+    },
+    /// A device feature is used that was not enabled when the device was created.
+    FeatureNotEnabled(&'static str),
+    /// A sampler parameter is out of range or not allowed with the other parameters.
+    InvalidSamplerParameter(&'static str),
+    /// A binding number is not declared by the descriptor set layout.
+    InvalidDescriptorBinding(u32),
+    /// A descriptor does not match the type declared for its binding, or its resource lacks
+    /// the usage that type requires.
+    IncompatibleDescriptor {
+        binding: u32,
+        array_element: u32,
+    },
+    /// An array element is not lower than the descriptor count of its binding.
+    DescriptorOutOfBounds {
+        binding: u32,
+        array_element: u32,
+    },
+    /// The layout of an image is inferred, but its layouts are not tracked or differ between
+    /// the subresources.
+    ImageLayoutNotTracked,
+    /// A range of queries extends past the end of the query pool.
+    QueryOutOfBounds {
+        first_query: u32,
+        query_count: u32,
+    },
+    /// A render graph pass writes through a read-only access, or uses an access its kind of
+    /// pass does not support.
+    InvalidPassAccess {
+        pass: u32,
+    },
+    /// A render graph pass reads a resource created by the graph before any pass writes it.
+    UninitializedGraphResource {
+        pass: u32,
+    },
+    /// A resource of a render graph is recorded without an image or buffer bound to it.
+    UnboundGraphResource,
+    /// Memory requirements require a dedicated allocation, but no resource was given to
+    /// allocate it for.
+    DedicatedResourceRequired,
+    /// A graphics pipeline lacks a state its render target or rasterization requires.
+    MissingPipelineState(&'static str),
+    /// A subpass has resolve attachments, but not one for each color attachment.
+    ResolveAttachmentCountMismatch {
+        color_attachments: u32,
+        resolve_attachments: u32,
+    },
+    /// An image or image view lacks a usage the operation requires.
+    MissingImageUsage(ImageUsage),
+    /// A buffer or buffer view lacks a usage the operation requires.
+    MissingBufferUsage(BufferUsage),
+    /// A mip level or range of array layers lies outside the image.
+    ImageSubresourceOutOfRange {
+        mip_level: u32,
+        base_array_layer: u32,
+        layer_count: u32,
+    },
+    /// Image data does not have the size of the region it is copied to.
+    ImageDataSizeMismatch {
+        size: u64,
+        expected: u64,
+    },
+    /// A buffer parameter is out of range, such as a zero size or an empty usage.
+    InvalidBufferParameter(&'static str),
+    /// Concurrent sharing names fewer than two queue families, or one of them twice.
+    InvalidSharingMode,
+    /// An image parameter is out of range or not allowed with the other parameters.
+    InvalidImageParameter(&'static str),
+    /// The entry point name of a shader stage contains a NUL byte.
+    InvalidEntryPointName,
+    /// A descriptor set number is not declared by the pipeline layout, or the set bound to it
+    /// has a different layout.
+    IncompatibleDescriptorSet(u32),
+    /// The number of dynamic offsets differs from the number of dynamic descriptors of the
+    /// bound sets.
+    DynamicOffsetCountMismatch {
+        expected: u32,
+        actual: u32,
+    },
+    /// The same subresource is uploaded with two different final layouts.
+    ConflictingImageLayouts {
+        mip_level: u32,
+        array_layer: u32,
+    },
+    /// No shader stage declares a binding at the set and binding number.
+    UndeclaredBinding {
+        set: u32,
+        binding: u32,
+    },
+    /// A compiled render graph is recorded with another graph than it was compiled from, or
+    /// passes were added to the graph after compiling it.
+    CompiledGraphMismatch,
 }
 
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "vulkan run-time error")
+        match self {
+            Self::LimitExceeded { limit, value, max } => {
+                write!(
+                    f,
+                    "{} exceeded: {} requested, maximum is {}",
+                    limit, value, max
+                )
+            }
+            Self::DuplicateBinding(binding) => {
+                write!(f, "binding {} is declared more than once", binding)
+            }
+            Self::InvalidImmutableSamplers(binding) => {
+                write!(f, "invalid immutable samplers for binding {}", binding)
+            }
+            Self::InvalidPushConstantRange { offset, size } => write!(
+                f,
+                "invalid push constant range at offset {} with size {}",
+                offset, size
+            ),
+            Self::UndeclaredPushConstants { offset, size } => write!(
+                f,
+                "push constants at offset {} with size {} do not match the declared ranges",
+                offset, size
+            ),
+            Self::OverlappingPushConstantRanges => {
+                write!(f, "push constant ranges share a shader stage")
+            }
+            Self::InvalidAttachmentReference(attachment) => {
+                write!(f, "attachment {} does not exist", attachment)
+            }
+            Self::InvalidSubpass(subpass) => write!(f, "subpass {} does not exist", subpass),
+            Self::InvalidDepthStencilFormat(format) => write!(
+                f,
+                "{:?} lacks the aspect required of the depth/stencil attachment",
+                format
+            ),
+            Self::InvalidSpecializationConstant(constant_id) => write!(
+                f,
+                "specialization constant {} is duplicated or out of bounds",
+                constant_id
+            ),
+            Self::InvalidSpirv(error) => error.fmt(f),
+            Self::IncompatibleBinding { set, binding } => write!(
+                f,
+                "shader stages declare different descriptor types for set {} binding {}",
+                set, binding
+            ),
+            Self::UnmatchedVertexInput(location) => write!(
+                f,
+                "vertex shader input at location {} has no vertex attribute",
+                location
+            ),
+            Self::IncompatibleVertexFormat { location, format } => write!(
+                f,
+                "vertex attribute format {:?} does not match the vertex shader input at location {}",
+                format, location
+            ),
+            Self::UnmatchedFragmentInput(location) => write!(
+                f,
+                "fragment shader input at location {} is not written by the previous stage",
+                location
+            ),
+            Self::UnmatchedFragmentOutput(location) => write!(
+                f,
+                "fragment shader output at location {} has no color blend attachment",
+                location
+            ),
+            Self::MemoryAlreadyBound => write!(f, "resource is already bound to memory"),
+            Self::IncompatibleMemoryType(index) => write!(
+                f,
+                "memory type {} is not allowed by the memory requirements",
+                index
+            ),
+            Self::MisalignedMemoryOffset { offset, alignment } => write!(
+                f,
+                "memory offset {} is not a multiple of the required alignment {}",
+                offset, alignment
+            ),
+            Self::MemoryRangeOutOfBounds { offset, size } => write!(
+                f,
+                "memory range at offset {} with size {} is out of bounds",
+                offset, size
+            ),
+            Self::InvalidMemoryType(index) => write!(f, "memory type {} does not exist", index),
+            Self::MemoryNotHostVisible => write!(f, "memory is not host visible"),
+            Self::InvalidImageExtent(extent) => write!(f, "invalid image extent {:?}", extent),
+            Self::InvalidCubeCompatibleImage => write!(
+                f,
+                "cube compatible image is not square, 2D or has fewer than 6 array layers"
+            ),
+            Self::UnsupportedSampleCount(samples) => {
+                write!(f, "sample count {:?} is not supported for the image", samples)
+            }
+            Self::NoSuitableMemoryType => {
+                write!(f, "no memory type has the required properties")
+            }
+            Self::UnsupportedAllocation { size, alignment } => write!(
+                f,
+                "allocation strategy cannot place {} bytes aligned to {} in a memory block",
+                size, alignment
+            ),
+            Self::FeatureNotEnabled(feature) => write!(f, "feature {} is not enabled", feature),
+            Self::InvalidSamplerParameter(parameter) => {
+                write!(f, "invalid sampler parameter {}", parameter)
+            }
+            Self::InvalidDescriptorBinding(binding) => write!(
+                f,
+                "binding {} is not declared by the descriptor set layout",
+                binding
+            ),
+            Self::IncompatibleDescriptor {
+                binding,
+                array_element,
+            } => write!(
+                f,
+                "descriptor {} of binding {} does not match the declared descriptor type",
+                array_element, binding
+            ),
+            Self::DescriptorOutOfBounds {
+                binding,
+                array_element,
+            } => write!(
+                f,
+                "array element {} is out of bounds of binding {}",
+                array_element, binding
+            ),
+            Self::ImageLayoutNotTracked => {
+                write!(f, "image layouts are not tracked or differ between subresources")
+            }
+            Self::QueryOutOfBounds {
+                first_query,
+                query_count,
+            } => write!(
+                f,
+                "{} queries starting at {} are out of bounds of the query pool",
+                query_count, first_query
+            ),
+            Self::InvalidPassAccess { pass } => {
+                write!(f, "pass {} uses a resource through an invalid access", pass)
+            }
+            Self::UninitializedGraphResource { pass } => {
+                write!(f, "pass {} reads a resource before it is written", pass)
+            }
+            Self::UnboundGraphResource => {
+                write!(f, "render graph resource is not bound to an image or buffer")
+            }
+            Self::MissingImageUsage(usage) => write!(f, "image lacks usage {:?}", usage),
+            Self::MissingBufferUsage(usage) => write!(f, "buffer lacks usage {:?}", usage),
+            Self::ImageSubresourceOutOfRange {
+                mip_level,
+                base_array_layer,
+                layer_count,
+            } => write!(
+                f,
+                "mip level {} of {} array layers starting at {} is out of range of the image",
+                mip_level, layer_count, base_array_layer
+            ),
+            Self::ImageDataSizeMismatch { size, expected } => write!(
+                f,
+                "image data has {} bytes, the region needs {}",
+                size, expected
+            ),
+            Self::ResolveAttachmentCountMismatch {
+                color_attachments,
+                resolve_attachments,
+            } => write!(
+                f,
+                "{} resolve attachments given for {} color attachments",
+                resolve_attachments, color_attachments
+            ),
+            Self::MissingPipelineState(state) => {
+                write!(f, "graphics pipeline has no {} state", state)
+            }
+            Self::DedicatedResourceRequired => write!(
+                f,
+                "dedicated allocation is required, but no resource was given"
+            ),
+            Self::InvalidBufferParameter(parameter) => {
+                write!(f, "invalid buffer parameter {}", parameter)
+            }
+            Self::InvalidSharingMode => write!(
+                f,
+                "concurrent sharing needs at least two distinct queue families"
+            ),
+            Self::InvalidImageParameter(parameter) => {
+                write!(f, "invalid image parameter {}", parameter)
+            }
+            Self::InvalidEntryPointName => write!(f, "entry point name contains a NUL byte"),
+            Self::IncompatibleDescriptorSet(set) => {
+                write!(f, "descriptor set {} is incompatible with the pipeline layout", set)
+            }
+            Self::DynamicOffsetCountMismatch { expected, actual } => write!(
+                f,
+                "{} dynamic offsets given, the bound sets have {} dynamic descriptors",
+                actual, expected
+            ),
+            Self::ConflictingImageLayouts {
+                mip_level,
+                array_layer,
+            } => write!(
+                f,
+                "mip level {} array layer {} is given two different final layouts",
+                mip_level, array_layer
+            ),
+            Self::UndeclaredBinding { set, binding } => write!(
+                f,
+                "no shader stage declares binding {} of set {}",
+                binding, set
+            ),
+            Self::CompiledGraphMismatch => {
+                write!(f, "compiled render graph does not match the recorded graph")
+            }
+            _ => write!(f, "vulkan run-time error"),
+        }
     }
 }
 
@@ -110,6 +506,68 @@ impl From<ffi::Extent2D> for Extent2D {
     }
 }
 
+impl Extent3D {
+    pub fn new(width: u32, height: u32, depth: u32) -> Self {
+        Self {
+            width,
+            height,
+            depth,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+}
+
+impl From<(u32, u32, u32)> for Extent3D {
+    fn from((width, height, depth): (u32, u32, u32)) -> Self {
+        Self {
+            width,
+            height,
+            depth,
+        }
+    }
+}
+
+impl From<Extent2D> for Extent3D {
+    fn from(extent: Extent2D) -> Self {
+        Self {
+            width: extent.width,
+            height: extent.height,
+            depth: 1,
+        }
+    }
+}
+
+impl From<Extent3D> for ffi::Extent3D {
+    fn from(extent: Extent3D) -> Self {
+        Self {
+            width: extent.width,
+            height: extent.height,
+            depth: extent.depth,
+        }
+    }
+}
+
+impl From<ffi::Extent3D> for Extent3D {
+    fn from(extent: ffi::Extent3D) -> Self {
+        Self {
+            width: extent.width,
+            height: extent.height,
+            depth: extent.depth,
+        }
+    }
+}
+
 impl Offset2D {
     pub fn new(x: i32, y: i32) -> Self {
         Self { x, y }
@@ -140,6 +598,40 @@ impl From<ffi::Offset2D> for Offset2D {
     }
 }
 
+impl Offset3D {
+    pub fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn x(&self) -> i32 {
+        self.x
+    }
+
+    pub fn y(&self) -> i32 {
+        self.y
+    }
+
+    pub fn z(&self) -> i32 {
+        self.z
+    }
+}
+
+impl From<(i32, i32, i32)> for Offset3D {
+    fn from((x, y, z): (i32, i32, i32)) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl From<Offset3D> for ffi::Offset3D {
+    fn from(offset: Offset3D) -> Self {
+        Self {
+            x: offset.x,
+            y: offset.y,
+            z: offset.z,
+        }
+    }
+}
+
 impl Rect2D {
     pub fn new(offset: Offset2D, extent: Extent2D) -> Self {
         Self { offset, extent }
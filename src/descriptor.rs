@@ -0,0 +1,1224 @@
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+#[cfg(not(target_pointer_width = "64"))]
+use std::num::NonZeroU64;
+#[cfg(target_pointer_width = "64")]
+use std::ptr::NonNull;
+
+use crate::core::{Error, Result};
+use crate::device::Device;
+use crate::ffi;
+use crate::pipeline::ShaderStageFlags;
+use crate::resource::{
+    Buffer, BufferUsage, BufferView, ImageLayout, ImageUsage, ImageView, WHOLE_SIZE,
+};
+use crate::sampler::Sampler;
+
+pub mod allocator;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DescriptorType {
+    Sampler,
+    CombinedImageSampler,
+    SampledImage,
+    StorageImage,
+    UniformTexelBuffer,
+    StorageTexelBuffer,
+    UniformBuffer,
+    StorageBuffer,
+    UniformBufferDynamic,
+    StorageBufferDynamic,
+    InputAttachment,
+    InlineUniformBlockExt,
+    AccelerationStructureKhr,
+    AccelerationStructureNv,
+    MutableValve,
+}
+
+pub enum DescriptorSetLayoutCreate {
+    PushDescriptorKhr,
+    UpdateAfterBindPool,
+    HostOnlyPoolValve,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DescriptorSetLayoutCreateFlags(u32);
+
+#[derive(Default)]
+pub struct DescriptorSetLayoutCreateFlagsBuilder(u32);
+
+pub enum DescriptorBinding {
+    UpdateAfterBind,
+    UpdateUnusedWhilePending,
+    PartiallyBound,
+    VariableDescriptorCount,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DescriptorBindingFlags(u32);
+
+#[derive(Default)]
+pub struct DescriptorBindingFlagsBuilder(u32);
+
+/// A single binding of a [`DescriptorSetLayout`].
+#[derive(Debug)]
+pub struct DescriptorSetLayoutBinding<'a> {
+    binding: u32,
+    descriptor_type: DescriptorType,
+    descriptor_count: u32,
+    stage_flags: ShaderStageFlags,
+    immutable_samplers: Option<&'a [&'a Sampler<'a>]>,
+    binding_flags: DescriptorBindingFlags,
+}
+
+pub struct DescriptorSetLayout<'a> {
+    #[cfg(target_pointer_width = "64")]
+    pub(crate) handle: NonNull<ffi::VkDescriptorSetLayout_T>,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub(crate) handle: NonZeroU64,
+    pub(crate) device: &'a Device<'a>,
+    pub(crate) bindings: Vec<LayoutBinding>,
+    #[cfg(target_pointer_width = "64")]
+    _marker: PhantomData<ffi::VkDescriptorSetLayout_T>,
+}
+
+/// Binding declaration kept by a [`DescriptorSetLayout`] once it is created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct LayoutBinding {
+    pub(crate) binding: u32,
+    pub(crate) descriptor_type: DescriptorType,
+    pub(crate) descriptor_count: u32,
+    pub(crate) stage_flags: ShaderStageFlags,
+}
+
+#[derive(Debug, Default)]
+pub struct DescriptorSetLayoutBuilder<'a> {
+    flags: DescriptorSetLayoutCreateFlags,
+    bindings: Vec<DescriptorSetLayoutBinding<'a>>,
+}
+
+pub enum DescriptorPoolCreate {
+    FreeDescriptorSet,
+    UpdateAfterBind,
+    HostOnlyValve,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DescriptorPoolCreateFlags(u32);
+
+#[derive(Default)]
+pub struct DescriptorPoolCreateFlagsBuilder(u32);
+
+/// Number of descriptors of one type that a [`DescriptorPool`] can hold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DescriptorPoolSize {
+    pub descriptor_type: DescriptorType,
+    pub descriptor_count: u32,
+}
+
+pub struct DescriptorPool<'a> {
+    #[cfg(target_pointer_width = "64")]
+    pub(crate) handle: NonNull<ffi::VkDescriptorPool_T>,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub(crate) handle: NonZeroU64,
+    pub(crate) device: &'a Device<'a>,
+    flags: DescriptorPoolCreateFlags,
+    #[cfg(target_pointer_width = "64")]
+    _marker: PhantomData<ffi::VkDescriptorPool_T>,
+}
+
+#[derive(Debug, Default)]
+pub struct DescriptorPoolBuilder {
+    flags: DescriptorPoolCreateFlags,
+    max_sets: u32,
+    pool_sizes: Vec<DescriptorPoolSize>,
+}
+
+/// A descriptor set allocated from a [`DescriptorPool`].
+///
+/// The pool, the layout and every resource written to the set stay borrowed for as long as
+/// the set exists. Resetting the pool therefore requires all of its sets to be dropped first.
+pub struct DescriptorSet<'r> {
+    #[cfg(target_pointer_width = "64")]
+    pub(crate) handle: NonNull<ffi::VkDescriptorSet_T>,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub(crate) handle: NonZeroU64,
+    device: &'r Device<'r>,
+    #[cfg(target_pointer_width = "64")]
+    pool: NonNull<ffi::VkDescriptorPool_T>,
+    #[cfg(not(target_pointer_width = "64"))]
+    pool: NonZeroU64,
+    /// Whether the pool was created with [`DescriptorPoolCreate::FreeDescriptorSet`].
+    freeable: bool,
+    layout: &'r DescriptorSetLayout<'r>,
+    #[cfg(target_pointer_width = "64")]
+    _marker: PhantomData<ffi::VkDescriptorSet_T>,
+}
+
+/// Resource written to a single descriptor.
+#[derive(Debug, Clone, Copy)]
+pub enum Descriptor<'r> {
+    /// For [`Sampler`](DescriptorType::Sampler) bindings.
+    Sampler(&'r Sampler<'r>),
+    /// For [`SampledImage`](DescriptorType::SampledImage),
+    /// [`StorageImage`](DescriptorType::StorageImage) and
+    /// [`InputAttachment`](DescriptorType::InputAttachment) bindings, with the layout the
+    /// image is in when the set is used.
+    Image {
+        view: &'r ImageView<'r>,
+        layout: ImageLayout,
+    },
+    /// For [`CombinedImageSampler`](DescriptorType::CombinedImageSampler) bindings.
+    CombinedImageSampler {
+        view: &'r ImageView<'r>,
+        layout: ImageLayout,
+        sampler: &'r Sampler<'r>,
+    },
+    /// For uniform and storage buffer bindings, including the dynamic ones. `range` can be
+    /// [`WHOLE_SIZE`].
+    Buffer {
+        buffer: &'r Buffer<'r>,
+        offset: u64,
+        range: u64,
+    },
+    /// For [`UniformTexelBuffer`](DescriptorType::UniformTexelBuffer) and
+    /// [`StorageTexelBuffer`](DescriptorType::StorageTexelBuffer) bindings.
+    TexelBuffer(&'r BufferView<'r>),
+}
+
+/// Descriptors written to consecutive array elements of one binding of a [`DescriptorSet`].
+#[derive(Debug, Clone)]
+pub struct DescriptorWrite<'r> {
+    binding: u32,
+    array_element: u32,
+    descriptor_type: DescriptorType,
+    descriptors: Vec<Descriptor<'r>>,
+}
+
+/// Descriptors copied from consecutive array elements of a binding of another
+/// [`DescriptorSet`].
+#[derive(Debug, Clone, Copy)]
+pub struct DescriptorCopy<'s, 'r> {
+    src_set: &'s DescriptorSet<'r>,
+    src_binding: u32,
+    src_array_element: u32,
+    dst_binding: u32,
+    dst_array_element: u32,
+    descriptor_count: u32,
+}
+
+impl From<DescriptorType> for ffi::DescriptorType {
+    fn from(descriptor_type: DescriptorType) -> Self {
+        match descriptor_type {
+            DescriptorType::Sampler => Self::Sampler,
+            DescriptorType::CombinedImageSampler => Self::CombinedImageSampler,
+            DescriptorType::SampledImage => Self::SampledImage,
+            DescriptorType::StorageImage => Self::StorageImage,
+            DescriptorType::UniformTexelBuffer => Self::UniformTexelBuffer,
+            DescriptorType::StorageTexelBuffer => Self::StorageTexelBuffer,
+            DescriptorType::UniformBuffer => Self::UniformBuffer,
+            DescriptorType::StorageBuffer => Self::StorageBuffer,
+            DescriptorType::UniformBufferDynamic => Self::UniformBufferDynamic,
+            DescriptorType::StorageBufferDynamic => Self::StorageBufferDynamic,
+            DescriptorType::InputAttachment => Self::InputAttachment,
+            DescriptorType::InlineUniformBlockExt => Self::InlineUniformBlockExt,
+            DescriptorType::AccelerationStructureKhr => Self::AccelerationStructureKhr,
+            DescriptorType::AccelerationStructureNv => Self::AccelerationStructureNv,
+            DescriptorType::MutableValve => Self::MutableValve,
+        }
+    }
+}
+
+impl DescriptorSetLayoutCreateFlags {
+    pub fn contains(&self, flag: DescriptorSetLayoutCreate) -> bool {
+        let flag = ffi::DescriptorSetLayoutCreateFlagBits::from(flag);
+
+        self.0 & flag as u32 != 0
+    }
+}
+
+impl From<DescriptorSetLayoutCreate> for ffi::DescriptorSetLayoutCreateFlagBits {
+    fn from(flag: DescriptorSetLayoutCreate) -> Self {
+        match flag {
+            DescriptorSetLayoutCreate::PushDescriptorKhr => Self::PushDescriptorBitKhr,
+            DescriptorSetLayoutCreate::UpdateAfterBindPool => Self::UpdateAfterBindPoolBit,
+            DescriptorSetLayoutCreate::HostOnlyPoolValve => Self::HostOnlyPoolBitValve,
+        }
+    }
+}
+
+impl DescriptorSetLayoutCreateFlagsBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push_descriptor_khr(&mut self, push_descriptor_khr: bool) -> &mut Self {
+        if push_descriptor_khr {
+            self.0 |= ffi::DescriptorSetLayoutCreateFlagBits::PushDescriptorBitKhr as u32;
+        }
+        self
+    }
+
+    pub fn update_after_bind_pool(&mut self, update_after_bind_pool: bool) -> &mut Self {
+        if update_after_bind_pool {
+            self.0 |= ffi::DescriptorSetLayoutCreateFlagBits::UpdateAfterBindPoolBit as u32;
+        }
+        self
+    }
+
+    pub fn host_only_pool_valve(&mut self, host_only_pool_valve: bool) -> &mut Self {
+        if host_only_pool_valve {
+            self.0 |= ffi::DescriptorSetLayoutCreateFlagBits::HostOnlyPoolBitValve as u32;
+        }
+        self
+    }
+
+    pub fn build(&self) -> DescriptorSetLayoutCreateFlags {
+        DescriptorSetLayoutCreateFlags(self.0)
+    }
+}
+
+impl DescriptorBindingFlags {
+    pub fn contains(&self, flag: DescriptorBinding) -> bool {
+        let flag = ffi::DescriptorBindingFlagBits::from(flag);
+
+        self.0 & flag as u32 != 0
+    }
+}
+
+impl From<DescriptorBinding> for ffi::DescriptorBindingFlagBits {
+    fn from(flag: DescriptorBinding) -> Self {
+        match flag {
+            DescriptorBinding::UpdateAfterBind => Self::UpdateAfterBindBit,
+            DescriptorBinding::UpdateUnusedWhilePending => Self::UpdateUnusedWhilePendingBit,
+            DescriptorBinding::PartiallyBound => Self::PartiallyBoundBit,
+            DescriptorBinding::VariableDescriptorCount => Self::VariableDescriptorCountBit,
+        }
+    }
+}
+
+impl DescriptorBindingFlagsBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update_after_bind(&mut self, update_after_bind: bool) -> &mut Self {
+        if update_after_bind {
+            self.0 |= ffi::DescriptorBindingFlagBits::UpdateAfterBindBit as u32;
+        }
+        self
+    }
+
+    pub fn update_unused_while_pending(&mut self, update_unused_while_pending: bool) -> &mut Self {
+        if update_unused_while_pending {
+            self.0 |= ffi::DescriptorBindingFlagBits::UpdateUnusedWhilePendingBit as u32;
+        }
+        self
+    }
+
+    pub fn partially_bound(&mut self, partially_bound: bool) -> &mut Self {
+        if partially_bound {
+            self.0 |= ffi::DescriptorBindingFlagBits::PartiallyBoundBit as u32;
+        }
+        self
+    }
+
+    pub fn variable_descriptor_count(&mut self, variable_descriptor_count: bool) -> &mut Self {
+        if variable_descriptor_count {
+            self.0 |= ffi::DescriptorBindingFlagBits::VariableDescriptorCountBit as u32;
+        }
+        self
+    }
+
+    pub fn build(&self) -> DescriptorBindingFlags {
+        DescriptorBindingFlags(self.0)
+    }
+}
+
+impl<'a> DescriptorSetLayoutBinding<'a> {
+    pub fn new(
+        binding: u32,
+        descriptor_type: DescriptorType,
+        descriptor_count: u32,
+        stage_flags: ShaderStageFlags,
+    ) -> Self {
+        Self {
+            binding,
+            descriptor_type,
+            descriptor_count,
+            stage_flags,
+            immutable_samplers: None,
+            binding_flags: Default::default(),
+        }
+    }
+
+    /// Samplers that are baked into the layout. Only valid for
+    /// [`Sampler`](DescriptorType::Sampler) and
+    /// [`CombinedImageSampler`](DescriptorType::CombinedImageSampler) bindings, and there must
+    /// be exactly one sampler per descriptor.
+    pub fn with_immutable_samplers(mut self, immutable_samplers: &'a [&'a Sampler<'a>]) -> Self {
+        self.immutable_samplers = Some(immutable_samplers);
+        self
+    }
+
+    /// Per-binding flags. Requires Vulkan 1.2 or `"VK_EXT_descriptor_indexing"`.
+    pub fn with_binding_flags(mut self, binding_flags: DescriptorBindingFlags) -> Self {
+        self.binding_flags = binding_flags;
+        self
+    }
+}
+
+impl<'a> DescriptorSetLayout<'a> {
+    /// Number of the bindings declared by the layout.
+    pub fn binding_count(&self) -> usize {
+        self.bindings.len()
+    }
+
+    /// Descriptor type and count declared for `binding`.
+    pub fn binding(&self, binding: u32) -> Option<(DescriptorType, u32)> {
+        self.bindings
+            .iter()
+            .find(|b| b.binding == binding)
+            .map(|b| (b.descriptor_type, b.descriptor_count))
+    }
+}
+
+impl<'a> Drop for DescriptorSetLayout<'a> {
+    fn drop(&mut self) {
+        println!("Dropped DescriptorSetLayout");
+        unsafe {
+            (self.device.dispatch_loader.vk_destroy_descriptor_set_layout)(
+                self.device.handle.as_ptr(),
+                #[cfg(target_pointer_width = "64")]
+                self.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                self.handle.get(),
+                std::ptr::null(),
+            );
+        }
+    }
+}
+
+impl<'a> std::fmt::Debug for DescriptorSetLayout<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DescriptorSetLayout")
+            .field("handle", &self.handle)
+            .field("bindings", &self.bindings)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> DescriptorSetLayoutBuilder<'a> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_flags(&mut self, flags: DescriptorSetLayoutCreateFlags) -> &mut Self {
+        self.flags = flags;
+        self
+    }
+
+    pub fn with_binding(&mut self, binding: DescriptorSetLayoutBinding<'a>) -> &mut Self {
+        self.bindings.push(binding);
+        self
+    }
+
+    /// # Errors
+    ///
+    /// - [`DuplicateBinding`](Error::DuplicateBinding) if a binding number is used twice.
+    /// - [`InvalidImmutableSamplers`](Error::InvalidImmutableSamplers) if immutable samplers
+    ///   are given for a binding that cannot use them, or their count does not match.
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    pub fn build(&self, device: &'a Device) -> Result<DescriptorSetLayout<'a>> {
+        for (i, binding) in self.bindings.iter().enumerate() {
+            if self.bindings[..i]
+                .iter()
+                .any(|other| other.binding == binding.binding)
+            {
+                return Err(Error::DuplicateBinding(binding.binding));
+            }
+
+            if let Some(samplers) = binding.immutable_samplers {
+                let takes_samplers = matches!(
+                    binding.descriptor_type,
+                    DescriptorType::Sampler | DescriptorType::CombinedImageSampler
+                );
+                if !takes_samplers || samplers.len() != binding.descriptor_count as usize {
+                    return Err(Error::InvalidImmutableSamplers(binding.binding));
+                }
+            }
+        }
+
+        let immutable_samplers: Vec<Option<Vec<_>>> = self
+            .bindings
+            .iter()
+            .map(|binding| {
+                binding.immutable_samplers.map(|samplers| {
+                    samplers
+                        .iter()
+                        .map(|sampler| {
+                            #[cfg(target_pointer_width = "64")]
+                            let handle = sampler.handle.as_ptr();
+                            #[cfg(not(target_pointer_width = "64"))]
+                            let handle = sampler.handle.get();
+                            handle
+                        })
+                        .collect()
+                })
+            })
+            .collect();
+
+        let bindings: Vec<ffi::DescriptorSetLayoutBinding> = self
+            .bindings
+            .iter()
+            .zip(immutable_samplers.iter())
+            .map(|(binding, samplers)| ffi::DescriptorSetLayoutBinding {
+                binding: binding.binding,
+                descriptor_type: binding.descriptor_type.into(),
+                descriptor_count: binding.descriptor_count,
+                stage_flags: binding.stage_flags.0,
+                p_immutable_samplers: samplers
+                    .as_ref()
+                    .map_or(std::ptr::null(), |samplers| samplers.as_ptr()),
+            })
+            .collect();
+
+        let binding_flags: Vec<ffi::DescriptorBindingFlags> = self
+            .bindings
+            .iter()
+            .map(|binding| binding.binding_flags.0)
+            .collect();
+        let binding_flags_info = ffi::DescriptorSetLayoutBindingFlagsCreateInfo {
+            s_type: ffi::StructureType::DescriptorSetLayoutBindingFlagsCreateInfo,
+            p_next: std::ptr::null(),
+            binding_count: binding_flags.len() as u32,
+            p_binding_flags: binding_flags.as_ptr(),
+        };
+
+        // NOTE: The binding flags structure is only chained when it is actually used, so that
+        // layouts without flags also work on implementations without descriptor indexing.
+        let p_next = if binding_flags.iter().any(|&flags| flags != 0) {
+            (&binding_flags_info as *const ffi::DescriptorSetLayoutBindingFlagsCreateInfo).cast()
+        } else {
+            std::ptr::null()
+        };
+
+        let create_info = ffi::DescriptorSetLayoutCreateInfo {
+            s_type: ffi::StructureType::DescriptorSetLayoutCreateInfo,
+            p_next,
+            flags: self.flags.0,
+            binding_count: bindings.len() as u32,
+            p_bindings: bindings.as_ptr(),
+        };
+
+        let mut handle = MaybeUninit::uninit();
+        let result = unsafe {
+            (device.dispatch_loader.vk_create_descriptor_set_layout)(
+                device.handle.as_ptr(),
+                &create_info,
+                std::ptr::null(),
+                handle.as_mut_ptr(),
+            )
+        };
+
+        match result {
+            ffi::Result::Success => Ok(DescriptorSetLayout {
+                #[cfg(target_pointer_width = "64")]
+                handle: unsafe { NonNull::new_unchecked(handle.assume_init()) },
+                #[cfg(not(target_pointer_width = "64"))]
+                handle: unsafe { NonZeroU64::new_unchecked(handle.assume_init()) },
+                device,
+                bindings: self
+                    .bindings
+                    .iter()
+                    .map(|binding| LayoutBinding {
+                        binding: binding.binding,
+                        descriptor_type: binding.descriptor_type,
+                        descriptor_count: binding.descriptor_count,
+                        stage_flags: binding.stage_flags,
+                    })
+                    .collect(),
+                #[cfg(target_pointer_width = "64")]
+                _marker: PhantomData,
+            }),
+            ffi::Result::ErrorOutOfHostMemory => Err(Error::OutOfHostMemory),
+            ffi::Result::ErrorOutOfDeviceMemory => Err(Error::OutOfDeviceMemory),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl DescriptorPoolCreateFlags {
+    pub fn contains(&self, flag: DescriptorPoolCreate) -> bool {
+        let flag = ffi::DescriptorPoolCreateFlagBits::from(flag);
+
+        self.0 & flag as u32 != 0
+    }
+}
+
+impl From<DescriptorPoolCreate> for ffi::DescriptorPoolCreateFlagBits {
+    fn from(flag: DescriptorPoolCreate) -> Self {
+        match flag {
+            DescriptorPoolCreate::FreeDescriptorSet => Self::FreeDescriptorSetBit,
+            DescriptorPoolCreate::UpdateAfterBind => Self::UpdateAfterBindBit,
+            DescriptorPoolCreate::HostOnlyValve => Self::HostOnlyBitValve,
+        }
+    }
+}
+
+impl DescriptorPoolCreateFlagsBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Lets descriptor sets be freed individually, which happens when they are dropped.
+    pub fn free_descriptor_set(&mut self, free_descriptor_set: bool) -> &mut Self {
+        if free_descriptor_set {
+            self.0 |= ffi::DescriptorPoolCreateFlagBits::FreeDescriptorSetBit as u32;
+        }
+        self
+    }
+
+    pub fn update_after_bind(&mut self, update_after_bind: bool) -> &mut Self {
+        if update_after_bind {
+            self.0 |= ffi::DescriptorPoolCreateFlagBits::UpdateAfterBindBit as u32;
+        }
+        self
+    }
+
+    pub fn host_only_valve(&mut self, host_only_valve: bool) -> &mut Self {
+        if host_only_valve {
+            self.0 |= ffi::DescriptorPoolCreateFlagBits::HostOnlyBitValve as u32;
+        }
+        self
+    }
+
+    pub fn build(&self) -> DescriptorPoolCreateFlags {
+        DescriptorPoolCreateFlags(self.0)
+    }
+}
+
+impl DescriptorPoolSize {
+    pub fn new(descriptor_type: DescriptorType, descriptor_count: u32) -> Self {
+        Self {
+            descriptor_type,
+            descriptor_count,
+        }
+    }
+}
+
+impl From<DescriptorPoolSize> for ffi::DescriptorPoolSize {
+    fn from(pool_size: DescriptorPoolSize) -> Self {
+        Self {
+            ty: pool_size.descriptor_type.into(),
+            descriptor_count: pool_size.descriptor_count,
+        }
+    }
+}
+
+impl DescriptorPoolBuilder {
+    pub fn new(max_sets: u32) -> Self {
+        Self {
+            max_sets,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_flags(&mut self, flags: DescriptorPoolCreateFlags) -> &mut Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Adds room for `descriptor_count` descriptors of `descriptor_type`, shared by all sets
+    /// of the pool.
+    pub fn with_pool_size(
+        &mut self,
+        descriptor_type: DescriptorType,
+        descriptor_count: u32,
+    ) -> &mut Self {
+        self.pool_sizes
+            .push(DescriptorPoolSize::new(descriptor_type, descriptor_count));
+        self
+    }
+
+    /// # Errors
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    /// - [`Fragmentation`](Error::Fragmentation)
+    pub fn build<'a>(&self, device: &'a Device) -> Result<DescriptorPool<'a>> {
+        let pool_sizes: Vec<ffi::DescriptorPoolSize> = self
+            .pool_sizes
+            .iter()
+            .map(|&pool_size| pool_size.into())
+            .collect();
+
+        let create_info = ffi::DescriptorPoolCreateInfo {
+            s_type: ffi::StructureType::DescriptorPoolCreateInfo,
+            p_next: std::ptr::null(),
+            flags: self.flags.0,
+            max_sets: self.max_sets,
+            pool_size_count: pool_sizes.len() as u32,
+            p_pool_sizes: pool_sizes.as_ptr(),
+        };
+
+        let mut handle = MaybeUninit::uninit();
+        let result = unsafe {
+            (device.dispatch_loader.vk_create_descriptor_pool)(
+                device.handle.as_ptr(),
+                &create_info,
+                std::ptr::null(),
+                handle.as_mut_ptr(),
+            )
+        };
+
+        match result {
+            ffi::Result::Success => Ok(DescriptorPool {
+                #[cfg(target_pointer_width = "64")]
+                handle: unsafe { NonNull::new_unchecked(handle.assume_init()) },
+                #[cfg(not(target_pointer_width = "64"))]
+                handle: unsafe { NonZeroU64::new_unchecked(handle.assume_init()) },
+                device,
+                flags: self.flags,
+                #[cfg(target_pointer_width = "64")]
+                _marker: PhantomData,
+            }),
+            ffi::Result::ErrorOutOfHostMemory => Err(Error::OutOfHostMemory),
+            ffi::Result::ErrorOutOfDeviceMemory => Err(Error::OutOfDeviceMemory),
+            ffi::Result::ErrorFragmentation => Err(Error::Fragmentation),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<'a> DescriptorPool<'a> {
+    pub fn flags(&self) -> DescriptorPoolCreateFlags {
+        self.flags
+    }
+
+    /// Allocates one descriptor set per layout.
+    ///
+    /// # Errors
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    /// - [`FragmentedPool`](Error::FragmentedPool)
+    /// - [`OutOfPoolMemory`](Error::OutOfPoolMemory)
+    pub fn allocate<'r>(
+        &'r self,
+        layouts: &[&'r DescriptorSetLayout<'r>],
+    ) -> Result<Vec<DescriptorSet<'r>>> {
+        self.allocate_sets(self.device, layouts)
+    }
+
+    /// Like [`allocate`](Self::allocate), but the sets do not borrow the pool. The caller has
+    /// to keep the pool alive and not reset it while the sets exist.
+    pub(crate) fn allocate_sets<'r>(
+        &self,
+        device: &'r Device<'r>,
+        layouts: &[&'r DescriptorSetLayout<'r>],
+    ) -> Result<Vec<DescriptorSet<'r>>> {
+        if layouts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let set_layouts: Vec<_> = layouts
+            .iter()
+            .map(|layout| {
+                #[cfg(target_pointer_width = "64")]
+                let handle = layout.handle.as_ptr();
+                #[cfg(not(target_pointer_width = "64"))]
+                let handle = layout.handle.get();
+                handle
+            })
+            .collect();
+
+        let allocate_info = ffi::DescriptorSetAllocateInfo {
+            s_type: ffi::StructureType::DescriptorSetAllocateInfo,
+            p_next: std::ptr::null(),
+            #[cfg(target_pointer_width = "64")]
+            descriptor_pool: self.handle.as_ptr(),
+            #[cfg(not(target_pointer_width = "64"))]
+            descriptor_pool: self.handle.get(),
+            descriptor_set_count: set_layouts.len() as u32,
+            p_set_layouts: set_layouts.as_ptr(),
+        };
+
+        let mut handles = Vec::with_capacity(set_layouts.len());
+        let result = unsafe {
+            (self.device.dispatch_loader.vk_allocate_descriptor_sets)(
+                self.device.handle.as_ptr(),
+                &allocate_info,
+                handles.as_mut_ptr(),
+            )
+        };
+
+        match result {
+            ffi::Result::Success => {
+                unsafe { handles.set_len(set_layouts.len()) };
+
+                Ok(handles
+                    .into_iter()
+                    .zip(layouts)
+                    .map(|(handle, &layout)| DescriptorSet {
+                        #[cfg(target_pointer_width = "64")]
+                        handle: unsafe { NonNull::new_unchecked(handle) },
+                        #[cfg(not(target_pointer_width = "64"))]
+                        handle: unsafe { NonZeroU64::new_unchecked(handle) },
+                        device,
+                        pool: self.handle,
+                        freeable: self.flags.contains(DescriptorPoolCreate::FreeDescriptorSet),
+                        layout,
+                        #[cfg(target_pointer_width = "64")]
+                        _marker: PhantomData,
+                    })
+                    .collect())
+            }
+            ffi::Result::ErrorOutOfHostMemory => Err(Error::OutOfHostMemory),
+            ffi::Result::ErrorOutOfDeviceMemory => Err(Error::OutOfDeviceMemory),
+            ffi::Result::ErrorFragmentedPool => Err(Error::FragmentedPool),
+            ffi::Result::ErrorOutOfPoolMemory => Err(Error::OutOfPoolMemory),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns all descriptor sets to the pool. The sets borrow the pool, so none of them can
+    /// still exist.
+    pub fn reset(&mut self) {
+        unsafe {
+            (self.device.dispatch_loader.vk_reset_descriptor_pool)(
+                self.device.handle.as_ptr(),
+                #[cfg(target_pointer_width = "64")]
+                self.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                self.handle.get(),
+                0,
+            );
+        }
+    }
+}
+
+impl<'a> Drop for DescriptorPool<'a> {
+    fn drop(&mut self) {
+        println!("Dropped DescriptorPool");
+        unsafe {
+            (self.device.dispatch_loader.vk_destroy_descriptor_pool)(
+                self.device.handle.as_ptr(),
+                #[cfg(target_pointer_width = "64")]
+                self.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                self.handle.get(),
+                std::ptr::null(),
+            );
+        }
+    }
+}
+
+impl<'a> std::fmt::Debug for DescriptorPool<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DescriptorPool")
+            .field("handle", &self.handle)
+            .field("flags", &self.flags)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'r> DescriptorSet<'r> {
+    pub fn layout(&self) -> &'r DescriptorSetLayout<'r> {
+        self.layout
+    }
+
+    /// Writes descriptors to the set and copies descriptors from other sets into it.
+    ///
+    /// # Errors
+    /// - [`InvalidDescriptorBinding`](Error::InvalidDescriptorBinding) if a binding is not
+    ///   declared by the layout of its set.
+    /// - [`IncompatibleDescriptor`](Error::IncompatibleDescriptor) if a write has another
+    ///   descriptor type than its binding, a descriptor does not fit that type, a buffer or
+    ///   image view lacks the matching usage, or a copy is between bindings of different types.
+    /// - [`DescriptorOutOfBounds`](Error::DescriptorOutOfBounds) if the descriptors run past
+    ///   the descriptor count of a binding.
+    /// - [`MemoryRangeOutOfBounds`](Error::MemoryRangeOutOfBounds) if a buffer range is empty
+    ///   or extends past the end of its buffer.
+    /// - [`MisalignedMemoryOffset`](Error::MisalignedMemoryOffset) if a buffer offset is not a
+    ///   multiple of `minUniformBufferOffsetAlignment` or `minStorageBufferOffsetAlignment`.
+    /// - [`LimitExceeded`](Error::LimitExceeded) if a buffer range is above
+    ///   `maxUniformBufferRange` or `maxStorageBufferRange`.
+    pub fn update(
+        &mut self,
+        writes: &[DescriptorWrite<'r>],
+        copies: &[DescriptorCopy<'_, 'r>],
+    ) -> Result<()> {
+        for write in writes {
+            self.validate_write(write)?;
+        }
+        for copy in copies {
+            self.validate_copy(copy)?;
+        }
+
+        #[cfg(target_pointer_width = "64")]
+        let dst_set = self.handle.as_ptr();
+        #[cfg(not(target_pointer_width = "64"))]
+        let dst_set = self.handle.get();
+
+        // NOTE: The infos are collected per write first, so that the pointers taken below stay
+        // valid until the descriptors are updated.
+        let image_infos: Vec<Vec<ffi::DescriptorImageInfo>> = writes
+            .iter()
+            .map(|write| write.descriptors.iter().filter_map(image_info).collect())
+            .collect();
+        let buffer_infos: Vec<Vec<ffi::DescriptorBufferInfo>> = writes
+            .iter()
+            .map(|write| write.descriptors.iter().filter_map(buffer_info).collect())
+            .collect();
+        let texel_buffer_views: Vec<Vec<_>> = writes
+            .iter()
+            .map(|write| {
+                write
+                    .descriptors
+                    .iter()
+                    .filter_map(|descriptor| match descriptor {
+                        Descriptor::TexelBuffer(view) => {
+                            #[cfg(target_pointer_width = "64")]
+                            let handle = view.handle.as_ptr();
+                            #[cfg(not(target_pointer_width = "64"))]
+                            let handle = view.handle.get();
+                            Some(handle)
+                        }
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let descriptor_writes: Vec<ffi::WriteDescriptorSet> = writes
+            .iter()
+            .enumerate()
+            .filter(|(_, write)| !write.descriptors.is_empty())
+            .map(|(i, write)| ffi::WriteDescriptorSet {
+                s_type: ffi::StructureType::WriteDescriptorSet,
+                p_next: std::ptr::null(),
+                dst_set,
+                dst_binding: write.binding,
+                dst_array_element: write.array_element,
+                descriptor_count: write.descriptors.len() as u32,
+                descriptor_type: write.descriptor_type.into(),
+                p_image_info: image_infos[i].as_ptr(),
+                p_buffer_info: buffer_infos[i].as_ptr(),
+                p_texel_buffer_view: texel_buffer_views[i].as_ptr(),
+            })
+            .collect();
+
+        let descriptor_copies: Vec<ffi::CopyDescriptorSet> = copies
+            .iter()
+            .filter(|copy| copy.descriptor_count > 0)
+            .map(|copy| ffi::CopyDescriptorSet {
+                s_type: ffi::StructureType::CopyDescriptorSet,
+                p_next: std::ptr::null(),
+                #[cfg(target_pointer_width = "64")]
+                src_set: copy.src_set.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                src_set: copy.src_set.handle.get(),
+                src_binding: copy.src_binding,
+                src_array_element: copy.src_array_element,
+                dst_set,
+                dst_binding: copy.dst_binding,
+                dst_array_element: copy.dst_array_element,
+                descriptor_count: copy.descriptor_count,
+            })
+            .collect();
+
+        unsafe {
+            (self.device.dispatch_loader.vk_update_descriptor_sets)(
+                self.device.handle.as_ptr(),
+                descriptor_writes.len() as u32,
+                descriptor_writes.as_ptr(),
+                descriptor_copies.len() as u32,
+                descriptor_copies.as_ptr(),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn validate_write(&self, write: &DescriptorWrite) -> Result<()> {
+        let binding = layout_binding(self.layout, write.binding)?;
+        validate_range(binding, write.array_element, write.descriptors.len() as u32)?;
+
+        let limits = self.device.limits();
+        for (i, descriptor) in write.descriptors.iter().enumerate() {
+            let array_element = write.array_element + i as u32;
+            let incompatible = Error::IncompatibleDescriptor {
+                binding: write.binding,
+                array_element,
+            };
+            if write.descriptor_type != binding.descriptor_type
+                || !accepts(write.descriptor_type, descriptor)
+            {
+                return Err(incompatible);
+            }
+
+            if let Descriptor::Buffer {
+                buffer,
+                offset,
+                range,
+            } = *descriptor
+            {
+                let uniform = matches!(
+                    write.descriptor_type,
+                    DescriptorType::UniformBuffer | DescriptorType::UniformBufferDynamic
+                );
+                let (limit, max_range, alignment) = if uniform {
+                    (
+                        "maxUniformBufferRange",
+                        limits.max_uniform_buffer_range,
+                        limits.min_uniform_buffer_offset_alignment,
+                    )
+                } else {
+                    (
+                        "maxStorageBufferRange",
+                        limits.max_storage_buffer_range,
+                        limits.min_storage_buffer_offset_alignment,
+                    )
+                };
+
+                let range = if range == WHOLE_SIZE {
+                    buffer.size().saturating_sub(offset)
+                } else {
+                    range
+                };
+                if range == 0
+                    || offset
+                        .checked_add(range)
+                        .is_none_or(|end| end > buffer.size())
+                {
+                    return Err(Error::MemoryRangeOutOfBounds {
+                        offset,
+                        size: range,
+                    });
+                }
+                if !offset.is_multiple_of(alignment) {
+                    return Err(Error::MisalignedMemoryOffset { offset, alignment });
+                }
+                if range > max_range.into() {
+                    return Err(Error::LimitExceeded {
+                        limit,
+                        value: range,
+                        max: max_range.into(),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_copy(&self, copy: &DescriptorCopy) -> Result<()> {
+        let src = layout_binding(copy.src_set.layout, copy.src_binding)?;
+        let dst = layout_binding(self.layout, copy.dst_binding)?;
+        validate_range(src, copy.src_array_element, copy.descriptor_count)?;
+        validate_range(dst, copy.dst_array_element, copy.descriptor_count)?;
+
+        if src.descriptor_type != dst.descriptor_type {
+            return Err(Error::IncompatibleDescriptor {
+                binding: copy.dst_binding,
+                array_element: copy.dst_array_element,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl<'r> Drop for DescriptorSet<'r> {
+    fn drop(&mut self) {
+        // NOTE: Sets of pools without the free flag are returned when the pool is reset or
+        // destroyed.
+        if !self.freeable {
+            return;
+        }
+
+        println!("Dropped DescriptorSet");
+        #[cfg(target_pointer_width = "64")]
+        let handle = self.handle.as_ptr();
+        #[cfg(not(target_pointer_width = "64"))]
+        let handle = self.handle.get();
+        unsafe {
+            (self.device.dispatch_loader.vk_free_descriptor_sets)(
+                self.device.handle.as_ptr(),
+                #[cfg(target_pointer_width = "64")]
+                self.pool.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                self.pool.get(),
+                1,
+                &handle,
+            );
+        }
+    }
+}
+
+impl<'r> std::fmt::Debug for DescriptorSet<'r> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DescriptorSet")
+            .field("handle", &self.handle)
+            .field("layout", &self.layout)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'r> DescriptorWrite<'r> {
+    /// Write starting at array element 0 of `binding`, which must be declared with
+    /// `descriptor_type`.
+    pub fn new(binding: u32, descriptor_type: DescriptorType) -> Self {
+        Self {
+            binding,
+            array_element: 0,
+            descriptor_type,
+            descriptors: Vec::new(),
+        }
+    }
+
+    pub fn with_array_element(mut self, array_element: u32) -> Self {
+        self.array_element = array_element;
+        self
+    }
+
+    pub fn with_descriptor(mut self, descriptor: Descriptor<'r>) -> Self {
+        self.descriptors.push(descriptor);
+        self
+    }
+
+    pub fn with_descriptors(
+        mut self,
+        descriptors: impl IntoIterator<Item = Descriptor<'r>>,
+    ) -> Self {
+        self.descriptors.extend(descriptors);
+        self
+    }
+}
+
+impl<'s, 'r> DescriptorCopy<'s, 'r> {
+    /// Copies `descriptor_count` descriptors starting at array element 0 of both bindings.
+    pub fn new(
+        src_set: &'s DescriptorSet<'r>,
+        src_binding: u32,
+        dst_binding: u32,
+        descriptor_count: u32,
+    ) -> Self {
+        Self {
+            src_set,
+            src_binding,
+            src_array_element: 0,
+            dst_binding,
+            dst_array_element: 0,
+            descriptor_count,
+        }
+    }
+
+    pub fn with_src_array_element(mut self, src_array_element: u32) -> Self {
+        self.src_array_element = src_array_element;
+        self
+    }
+
+    pub fn with_dst_array_element(mut self, dst_array_element: u32) -> Self {
+        self.dst_array_element = dst_array_element;
+        self
+    }
+}
+
+fn layout_binding(layout: &DescriptorSetLayout, binding: u32) -> Result<LayoutBinding> {
+    layout
+        .bindings
+        .iter()
+        .find(|b| b.binding == binding)
+        .copied()
+        .ok_or(Error::InvalidDescriptorBinding(binding))
+}
+
+fn validate_range(binding: LayoutBinding, array_element: u32, count: u32) -> Result<()> {
+    if array_element
+        .checked_add(count)
+        .is_none_or(|end| end > binding.descriptor_count)
+    {
+        return Err(Error::DescriptorOutOfBounds {
+            binding: binding.binding,
+            array_element: array_element.max(binding.descriptor_count),
+        });
+    }
+
+    Ok(())
+}
+
+/// Whether `descriptor` can be written to a binding of `descriptor_type`.
+fn accepts(descriptor_type: DescriptorType, descriptor: &Descriptor) -> bool {
+    match (descriptor_type, descriptor) {
+        (DescriptorType::Sampler, Descriptor::Sampler(_)) => true,
+        (DescriptorType::CombinedImageSampler, Descriptor::CombinedImageSampler { view, .. })
+        | (DescriptorType::SampledImage, Descriptor::Image { view, .. }) => {
+            view.usage().contains(ImageUsage::Sampled)
+        }
+        (DescriptorType::StorageImage, Descriptor::Image { view, .. }) => {
+            view.usage().contains(ImageUsage::Storage)
+        }
+        (DescriptorType::InputAttachment, Descriptor::Image { view, .. }) => {
+            view.usage().contains(ImageUsage::InputAttachment)
+        }
+        (
+            DescriptorType::UniformBuffer | DescriptorType::UniformBufferDynamic,
+            Descriptor::Buffer { buffer, .. },
+        ) => buffer.usage().contains(BufferUsage::UniformBuffer),
+        (
+            DescriptorType::StorageBuffer | DescriptorType::StorageBufferDynamic,
+            Descriptor::Buffer { buffer, .. },
+        ) => buffer.usage().contains(BufferUsage::StorageBuffer),
+        (DescriptorType::UniformTexelBuffer, Descriptor::TexelBuffer(view)) => {
+            view.usage().contains(BufferUsage::UniformTexelBuffer)
+        }
+        (DescriptorType::StorageTexelBuffer, Descriptor::TexelBuffer(view)) => {
+            view.usage().contains(BufferUsage::StorageTexelBuffer)
+        }
+        _ => false,
+    }
+}
+
+fn image_info(descriptor: &Descriptor) -> Option<ffi::DescriptorImageInfo> {
+    let (sampler, view, layout) = match *descriptor {
+        Descriptor::Sampler(sampler) => (Some(sampler), None, ImageLayout::Undefined),
+        Descriptor::Image { view, layout } => (None, Some(view), layout),
+        Descriptor::CombinedImageSampler {
+            view,
+            layout,
+            sampler,
+        } => (Some(sampler), Some(view), layout),
+        _ => return None,
+    };
+
+    Some(ffi::DescriptorImageInfo {
+        #[cfg(target_pointer_width = "64")]
+        sampler: sampler.map_or(std::ptr::null_mut(), |sampler| sampler.handle.as_ptr()),
+        #[cfg(not(target_pointer_width = "64"))]
+        sampler: sampler.map_or(0, |sampler| sampler.handle.get()),
+        #[cfg(target_pointer_width = "64")]
+        image_view: view.map_or(std::ptr::null_mut(), |view| view.handle.as_ptr()),
+        #[cfg(not(target_pointer_width = "64"))]
+        image_view: view.map_or(0, |view| view.handle.get()),
+        image_layout: layout.into(),
+    })
+}
+
+fn buffer_info(descriptor: &Descriptor) -> Option<ffi::DescriptorBufferInfo> {
+    match *descriptor {
+        Descriptor::Buffer {
+            buffer,
+            offset,
+            range,
+        } => Some(ffi::DescriptorBufferInfo {
+            #[cfg(target_pointer_width = "64")]
+            buffer: buffer.handle.as_ptr(),
+            #[cfg(not(target_pointer_width = "64"))]
+            buffer: buffer.handle.get(),
+            offset,
+            range,
+        }),
+        _ => None,
+    }
+}
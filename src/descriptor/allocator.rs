@@ -0,0 +1,332 @@
+//! # Descriptor allocation
+//! A [`DescriptorAllocator`] hands out descriptor sets from a list of pools and creates
+//! another pool whenever the current one runs out of sets or descriptors, so
+//! [`OutOfPoolMemory`](Error::OutOfPoolMemory) and [`FragmentedPool`](Error::FragmentedPool)
+//! never reach the caller. Keeping one allocator per frame in flight and resetting it at the
+//! start of the frame returns all sets of that frame at once.
+//!
+//! A [`DescriptorSetLayoutCache`] creates each distinct layout only once per device.
+//!
+//! The sizes of the pools and the identity of cached layouts are computed by
+//! [`DescriptorPoolSizing`] and [`DescriptorSetLayoutKey`], which do not need a device.
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::core::{Error, Result};
+use crate::descriptor::{
+    DescriptorBindingFlags, DescriptorPool, DescriptorPoolBuilder, DescriptorPoolCreateFlags,
+    DescriptorPoolSize, DescriptorSet, DescriptorSetLayout, DescriptorSetLayoutBuilder,
+    DescriptorType,
+};
+use crate::device::Device;
+use crate::pipeline::ShaderStageFlags;
+
+const DEFAULT_SETS_PER_POOL: u32 = 64;
+const MAX_SETS_PER_POOL: u32 = 4096;
+
+/// Descriptor allocator that grows by creating new pools.
+pub struct DescriptorAllocator<'a> {
+    device: &'a Device<'a>,
+    flags: DescriptorPoolCreateFlags,
+    sizing: DescriptorPoolSizing,
+    pools: RefCell<Pools<'a>>,
+}
+
+/// Number of sets and descriptors of the pools of a [`DescriptorAllocator`]. Pools start at
+/// a number of sets and double up to 4096 sets, and reserve the same descriptors per set.
+#[derive(Debug, Clone)]
+pub struct DescriptorPoolSizing {
+    /// Descriptors reserved per set, multiplied by the number of sets of a pool.
+    pool_sizes: Vec<DescriptorPoolSize>,
+    sets_per_pool: u32,
+}
+
+#[derive(Default)]
+struct Pools<'a> {
+    current: Option<DescriptorPool<'a>>,
+    /// Pools that failed an allocation and are not used until the next reset.
+    full: Vec<DescriptorPool<'a>>,
+    /// Pools that were reset and are reused before new ones are created.
+    free: Vec<DescriptorPool<'a>>,
+    created: u32,
+}
+
+/// Cache that deduplicates descriptor set layouts by their flags and bindings.
+pub struct DescriptorSetLayoutCache<'a> {
+    device: &'a Device<'a>,
+    layouts: HashMap<DescriptorSetLayoutKey, Rc<DescriptorSetLayout<'a>>>,
+}
+
+/// Identity of a descriptor set layout in a [`DescriptorSetLayoutCache`]. Builders with
+/// equal keys create interchangeable layouts.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct DescriptorSetLayoutKey {
+    flags: u32,
+    /// Sorted by binding number, so the order the bindings were added in does not matter.
+    bindings: Vec<BindingKey>,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct BindingKey {
+    binding: u32,
+    descriptor_type: DescriptorType,
+    descriptor_count: u32,
+    stage_flags: ShaderStageFlags,
+    binding_flags: DescriptorBindingFlags,
+    immutable_samplers: Option<Vec<u64>>,
+}
+
+impl<'a> DescriptorAllocator<'a> {
+    /// Allocator whose pools start at 64 sets and double up to 4096 sets, with room for a
+    /// few descriptors of each common type per set.
+    pub fn new(device: &'a Device<'a>) -> Self {
+        Self {
+            device,
+            flags: Default::default(),
+            sizing: DescriptorPoolSizing::new(),
+            pools: Default::default(),
+        }
+    }
+
+    /// Replaces the descriptors reserved per set, see
+    /// [`DescriptorPoolSizing::with_pool_sizes`].
+    pub fn with_pool_sizes(mut self, pool_sizes: &[DescriptorPoolSize]) -> Self {
+        self.sizing = self.sizing.with_pool_sizes(pool_sizes);
+        self
+    }
+
+    /// Number of sets of the first pool.
+    pub fn with_sets_per_pool(mut self, sets_per_pool: u32) -> Self {
+        self.sizing = self.sizing.with_sets_per_pool(sets_per_pool);
+        self
+    }
+
+    /// Flags of the pools. With [`FreeDescriptorSet`](super::DescriptorPoolCreate::FreeDescriptorSet)
+    /// dropped sets are freed individually, but pools that ran full are only used again
+    /// after a reset.
+    pub fn with_flags(mut self, flags: DescriptorPoolCreateFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Allocates a descriptor set, creating a new pool if the current one is exhausted.
+    ///
+    /// # Errors
+    /// - [`OutOfPoolMemory`](Error::OutOfPoolMemory) if the set does not fit even in a new
+    ///   pool, since the layout needs more descriptors than a pool reserves.
+    /// - The errors of [`DescriptorPool::allocate`] and [`DescriptorPoolBuilder::build`].
+    pub fn allocate<'r>(
+        &'r self,
+        layout: &'r DescriptorSetLayout<'r>,
+    ) -> Result<DescriptorSet<'r>> {
+        let mut pools = self.pools.borrow_mut();
+
+        if let Some(pool) = &pools.current {
+            match pool.allocate_sets(self.device, &[layout]) {
+                Ok(mut sets) => return Ok(sets.remove(0)),
+                Err(Error::OutOfPoolMemory | Error::FragmentedPool) => {
+                    let full = pools.current.take().unwrap();
+                    pools.full.push(full);
+                }
+                Err(error) => return Err(error),
+            }
+        }
+
+        let pool = match pools.free.pop() {
+            Some(pool) => pool,
+            None => self.create_pool(&mut pools)?,
+        };
+        let mut sets = pool.allocate_sets(self.device, &[layout])?;
+        pools.current = Some(pool);
+
+        Ok(sets.remove(0))
+    }
+
+    /// Returns every set to its pool. The sets borrow the allocator, so none of them can
+    /// still exist.
+    pub fn reset(&mut self) {
+        let pools = self.pools.get_mut();
+        let used = pools.current.take().into_iter().chain(pools.full.drain(..));
+        let reset: Vec<_> = used
+            .map(|mut pool| {
+                pool.reset();
+                pool
+            })
+            .collect();
+        pools.free.extend(reset);
+    }
+
+    /// Number of pools created so far.
+    pub fn pool_count(&self) -> usize {
+        let pools = self.pools.borrow();
+        pools.current.iter().count() + pools.full.len() + pools.free.len()
+    }
+
+    fn create_pool(&self, pools: &mut Pools<'a>) -> Result<DescriptorPool<'a>> {
+        let sets = self.sizing.sets(pools.created);
+        pools.created += 1;
+
+        let mut builder = DescriptorPoolBuilder::new(sets);
+        builder.with_flags(self.flags);
+        for pool_size in self.sizing.pool_sizes(sets) {
+            builder.with_pool_size(pool_size.descriptor_type, pool_size.descriptor_count);
+        }
+
+        builder.build(self.device)
+    }
+}
+
+impl DescriptorPoolSizing {
+    /// Pools starting at 64 sets, with room for a few descriptors of each common type per
+    /// set.
+    pub fn new() -> Self {
+        Self {
+            pool_sizes: vec![
+                DescriptorPoolSize::new(DescriptorType::Sampler, 1),
+                DescriptorPoolSize::new(DescriptorType::CombinedImageSampler, 4),
+                DescriptorPoolSize::new(DescriptorType::SampledImage, 4),
+                DescriptorPoolSize::new(DescriptorType::StorageImage, 1),
+                DescriptorPoolSize::new(DescriptorType::UniformTexelBuffer, 1),
+                DescriptorPoolSize::new(DescriptorType::StorageTexelBuffer, 1),
+                DescriptorPoolSize::new(DescriptorType::UniformBuffer, 2),
+                DescriptorPoolSize::new(DescriptorType::StorageBuffer, 2),
+                DescriptorPoolSize::new(DescriptorType::UniformBufferDynamic, 1),
+                DescriptorPoolSize::new(DescriptorType::StorageBufferDynamic, 1),
+                DescriptorPoolSize::new(DescriptorType::InputAttachment, 1),
+            ],
+            sets_per_pool: DEFAULT_SETS_PER_POOL,
+        }
+    }
+
+    /// Replaces the descriptors reserved per set. The descriptor count of each pool size is
+    /// multiplied by the number of sets of a pool.
+    pub fn with_pool_sizes(mut self, pool_sizes: &[DescriptorPoolSize]) -> Self {
+        self.pool_sizes = pool_sizes.to_vec();
+        self
+    }
+
+    /// Number of sets of the first pool, at least one. First pools above 4096 sets do not
+    /// grow.
+    pub fn with_sets_per_pool(mut self, sets_per_pool: u32) -> Self {
+        self.sets_per_pool = sets_per_pool.max(1);
+        self
+    }
+
+    /// Number of sets of the pool created after `created` others.
+    pub fn sets(&self, created: u32) -> u32 {
+        let grown = self
+            .sets_per_pool
+            .saturating_mul(2u32.saturating_pow(created))
+            .min(MAX_SETS_PER_POOL);
+
+        grown.max(self.sets_per_pool)
+    }
+
+    /// Descriptors reserved by a pool of `sets` sets.
+    pub fn pool_sizes(&self, sets: u32) -> Vec<DescriptorPoolSize> {
+        self.pool_sizes
+            .iter()
+            .map(|pool_size| {
+                DescriptorPoolSize::new(
+                    pool_size.descriptor_type,
+                    pool_size.descriptor_count.saturating_mul(sets),
+                )
+            })
+            .collect()
+    }
+}
+
+impl Default for DescriptorPoolSizing {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> std::fmt::Debug for DescriptorAllocator<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DescriptorAllocator")
+            .field("flags", &self.flags)
+            .field("sizing", &self.sizing)
+            .field("pool_count", &self.pool_count())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> DescriptorSetLayoutCache<'a> {
+    pub fn new(device: &'a Device<'a>) -> Self {
+        Self {
+            device,
+            layouts: HashMap::new(),
+        }
+    }
+
+    /// Returns the layout created for identical flags and bindings, or creates it.
+    ///
+    /// # Errors
+    /// The errors of [`DescriptorSetLayoutBuilder::build`].
+    pub fn get(
+        &mut self,
+        builder: &DescriptorSetLayoutBuilder<'a>,
+    ) -> Result<Rc<DescriptorSetLayout<'a>>> {
+        let key = DescriptorSetLayoutKey::from(builder);
+        if let Some(layout) = self.layouts.get(&key) {
+            return Ok(layout.clone());
+        }
+
+        let layout = Rc::new(builder.build(self.device)?);
+        self.layouts.insert(key, layout.clone());
+
+        Ok(layout)
+    }
+
+    pub fn len(&self) -> usize {
+        self.layouts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.layouts.is_empty()
+    }
+}
+
+impl<'a> std::fmt::Debug for DescriptorSetLayoutCache<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DescriptorSetLayoutCache")
+            .field("len", &self.layouts.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl From<&DescriptorSetLayoutBuilder<'_>> for DescriptorSetLayoutKey {
+    fn from(builder: &DescriptorSetLayoutBuilder) -> Self {
+        let mut bindings: Vec<BindingKey> = builder
+            .bindings
+            .iter()
+            .map(|binding| BindingKey {
+                binding: binding.binding,
+                descriptor_type: binding.descriptor_type,
+                descriptor_count: binding.descriptor_count,
+                stage_flags: binding.stage_flags,
+                binding_flags: binding.binding_flags,
+                immutable_samplers: binding.immutable_samplers.map(|samplers| {
+                    samplers
+                        .iter()
+                        .map(|sampler| {
+                            #[cfg(target_pointer_width = "64")]
+                            let handle = sampler.handle.as_ptr() as u64;
+                            #[cfg(not(target_pointer_width = "64"))]
+                            let handle = sampler.handle.get();
+                            handle
+                        })
+                        .collect()
+                }),
+            })
+            .collect();
+        bindings.sort_by_key(|binding| binding.binding);
+
+        Self {
+            flags: builder.flags.0,
+            bindings,
+        }
+    }
+}
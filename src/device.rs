@@ -1,20 +1,32 @@
-use std::ffi::{CStr, CString};
+use std::ffi::{c_void, CStr, CString};
 use std::marker::PhantomData;
 use std::mem::MaybeUninit;
 use std::ptr::NonNull;
 
-use crate::command_buffer::CommandPool;
-use crate::core::{Error, Result};
+use crate::command_buffer::{CommandBuffer, CommandPool};
+use crate::core::{Error, Extent3D, Result};
 use crate::ffi;
+use crate::format::Format;
 use crate::init::{ApiVersion, DispatchLoaderDevice, DispatchLoaderPhysicalDevice, Instance};
-use crate::resource::{Image, ImageUsageFlags};
-use crate::shaders::ShaderModule;
-use crate::sync::Semaphore;
+use crate::pipeline::SampleCount;
+use crate::query::{QueryPool, QueryType};
+use crate::resource::{
+    DedicatedResource, DeviceMemory, Image, ImageCreateFlags, ImageTiling, ImageType,
+    ImageUsageFlags, MemoryPropertyFlags,
+};
+use crate::shaders::{spirv_words, ShaderModule, ShaderReflection};
+use crate::sync::{Event, Fence, PipelineStageFlags, Semaphore};
 use crate::wsi::{
     CompositeAlphaFlagsKhr, PresentModeKhr, SurfaceCapabilitiesKhr, SurfaceFormatKhr, SurfaceKhr,
     SurfaceTransformFlagsKhr, SwapchainKhr,
 };
 
+/// Extensions of features that are core in Vulkan 1.2 and 1.3.
+const SAMPLER_MIRROR_CLAMP_TO_EDGE_EXTENSION: &str = "VK_KHR_sampler_mirror_clamp_to_edge";
+const SYNCHRONIZATION2_EXTENSION: &str = "VK_KHR_synchronization2";
+const DYNAMIC_RENDERING_EXTENSION: &str = "VK_KHR_dynamic_rendering";
+const EXTENDED_DYNAMIC_STATE_EXTENSION: &str = "VK_EXT_extended_dynamic_state";
+
 pub struct PhysicalDevice<'a> {
     pub(crate) handle: NonNull<ffi::VkPhysicalDevice_T>,
     pub(crate) dispatch_loader: DispatchLoaderPhysicalDevice,
@@ -24,13 +36,27 @@ pub struct PhysicalDevice<'a> {
 pub struct Device<'a> {
     pub(super) handle: NonNull<ffi::VkDevice_T>,
     pub(super) dispatch_loader: DispatchLoaderDevice,
+    physical_device: PhysicalDevice<'a>,
+    properties: PhysicalDeviceProperties,
+    memory_properties: PhysicalDeviceMemoryProperties,
+    enabled_features: PhysicalDeviceFeatures,
     _marker: PhantomData<(ffi::VkDevice_T, &'a Instance)>,
 }
 
-#[derive(Debug)]
 pub struct Queue<'a> {
     handle: NonNull<ffi::VkQueue_T>,
-    _marker: PhantomData<(ffi::VkQueue_T, &'a Device<'a>)>,
+    device: &'a Device<'a>,
+    family_index: u32,
+    _marker: PhantomData<ffi::VkQueue_T>,
+}
+
+/// A batch of command buffers for [`Queue::submit`], with the semaphores waited on before
+/// they execute and signaled once they complete.
+#[derive(Default)]
+pub struct SubmitInfo<'s> {
+    wait_semaphores: Vec<(&'s Semaphore<'s>, PipelineStageFlags)>,
+    command_buffers: Vec<&'s CommandBuffer<'s>>,
+    signal_semaphores: Vec<&'s Semaphore<'s>>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -44,8 +70,224 @@ pub enum PhysicalDeviceType {
 
 pub struct PhysicalDeviceProperties {
     pub api_version: ApiVersion,
+    pub driver_version: u32,
+    pub vendor_id: u32,
+    pub device_id: u32,
     pub device_type: PhysicalDeviceType,
     pub device_name: String,
+    /// Identifies the implementation whose pipeline cache data is compatible with this device.
+    pub pipeline_cache_uuid: [u8; 16],
+    pub limits: PhysicalDeviceLimits,
+}
+
+/// Optional features of a physical device. Only features enabled in
+/// [`PhysicalDevice::create_device`] can be used by the device.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PhysicalDeviceFeatures {
+    pub robust_buffer_access: bool,
+    pub full_draw_index_uint32: bool,
+    pub image_cube_array: bool,
+    pub independent_blend: bool,
+    pub geometry_shader: bool,
+    pub tessellation_shader: bool,
+    pub sample_rate_shading: bool,
+    pub dual_src_blend: bool,
+    pub logic_op: bool,
+    pub multi_draw_indirect: bool,
+    pub draw_indirect_first_instance: bool,
+    pub depth_clamp: bool,
+    pub depth_bias_clamp: bool,
+    pub fill_mode_non_solid: bool,
+    pub depth_bounds: bool,
+    pub wide_lines: bool,
+    pub large_points: bool,
+    pub alpha_to_one: bool,
+    pub multi_viewport: bool,
+    pub sampler_anisotropy: bool,
+    pub texture_compression_etc2: bool,
+    pub texture_compression_astc_ldr: bool,
+    pub texture_compression_bc: bool,
+    pub occlusion_query_precise: bool,
+    pub pipeline_statistics_query: bool,
+    pub vertex_pipeline_stores_and_atomics: bool,
+    pub fragment_stores_and_atomics: bool,
+    pub shader_tessellation_and_geometry_point_size: bool,
+    pub shader_image_gather_extended: bool,
+    pub shader_storage_image_extended_formats: bool,
+    pub shader_storage_image_multisample: bool,
+    pub shader_storage_image_read_without_format: bool,
+    pub shader_storage_image_write_without_format: bool,
+    pub shader_uniform_buffer_array_dynamic_indexing: bool,
+    pub shader_sampled_image_array_dynamic_indexing: bool,
+    pub shader_storage_buffer_array_dynamic_indexing: bool,
+    pub shader_storage_image_array_dynamic_indexing: bool,
+    pub shader_clip_distance: bool,
+    pub shader_cull_distance: bool,
+    pub shader_float64: bool,
+    pub shader_int64: bool,
+    pub shader_int16: bool,
+    pub shader_resource_residency: bool,
+    pub shader_resource_min_lod: bool,
+    pub sparse_binding: bool,
+    pub sparse_residency_buffer: bool,
+    pub sparse_residency_image_2d: bool,
+    pub sparse_residency_image_3d: bool,
+    pub sparse_residency2_samples: bool,
+    pub sparse_residency4_samples: bool,
+    pub sparse_residency8_samples: bool,
+    pub sparse_residency16_samples: bool,
+    pub sparse_residency_aliased: bool,
+    pub variable_multisample_rate: bool,
+    pub inherited_queries: bool,
+    /// `synchronization2` of Vulkan 1.3 or `"VK_KHR_synchronization2"`.
+    pub synchronization2: bool,
+    /// `dynamicRendering` of Vulkan 1.3 or `"VK_KHR_dynamic_rendering"`.
+    pub dynamic_rendering: bool,
+    /// `samplerMirrorClampToEdge` of Vulkan 1.2 or `"VK_KHR_sampler_mirror_clamp_to_edge"`.
+    pub sampler_mirror_clamp_to_edge: bool,
+    /// Core in Vulkan 1.3, otherwise `extendedDynamicState` of
+    /// `"VK_EXT_extended_dynamic_state"`.
+    pub extended_dynamic_state: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct PhysicalDeviceLimits {
+    pub max_image_dimension_1d: u32,
+    pub max_image_dimension_2d: u32,
+    pub max_image_dimension_3d: u32,
+    pub max_image_dimension_cube: u32,
+    pub max_image_array_layers: u32,
+    pub max_texel_buffer_elements: u32,
+    pub max_uniform_buffer_range: u32,
+    pub max_storage_buffer_range: u32,
+    pub max_push_constants_size: u32,
+    pub max_memory_allocation_count: u32,
+    pub max_sampler_allocation_count: u32,
+    pub buffer_image_granularity: u64,
+    pub sparse_address_space_size: u64,
+    pub max_bound_descriptor_sets: u32,
+    pub max_per_stage_descriptor_samplers: u32,
+    pub max_per_stage_descriptor_uniform_buffers: u32,
+    pub max_per_stage_descriptor_storage_buffers: u32,
+    pub max_per_stage_descriptor_sampled_images: u32,
+    pub max_per_stage_descriptor_storage_images: u32,
+    pub max_per_stage_descriptor_input_attachments: u32,
+    pub max_per_stage_resources: u32,
+    pub max_descriptor_set_samplers: u32,
+    pub max_descriptor_set_uniform_buffers: u32,
+    pub max_descriptor_set_uniform_buffers_dynamic: u32,
+    pub max_descriptor_set_storage_buffers: u32,
+    pub max_descriptor_set_storage_buffers_dynamic: u32,
+    pub max_descriptor_set_sampled_images: u32,
+    pub max_descriptor_set_storage_images: u32,
+    pub max_descriptor_set_input_attachments: u32,
+    pub max_vertex_input_attributes: u32,
+    pub max_vertex_input_bindings: u32,
+    pub max_vertex_input_attribute_offset: u32,
+    pub max_vertex_input_binding_stride: u32,
+    pub max_vertex_output_components: u32,
+    pub max_tessellation_generation_level: u32,
+    pub max_tessellation_patch_size: u32,
+    pub max_tessellation_control_per_vertex_input_components: u32,
+    pub max_tessellation_control_per_vertex_output_components: u32,
+    pub max_tessellation_control_per_patch_output_components: u32,
+    pub max_tessellation_control_total_output_components: u32,
+    pub max_tessellation_evaluation_input_components: u32,
+    pub max_tessellation_evaluation_output_components: u32,
+    pub max_geometry_shader_invocations: u32,
+    pub max_geometry_input_components: u32,
+    pub max_geometry_output_components: u32,
+    pub max_geometry_output_vertices: u32,
+    pub max_geometry_total_output_components: u32,
+    pub max_fragment_input_components: u32,
+    pub max_fragment_output_attachments: u32,
+    pub max_fragment_dual_src_attachments: u32,
+    pub max_fragment_combined_output_resources: u32,
+    pub max_compute_shared_memory_size: u32,
+    pub max_compute_work_group_count: [u32; 3],
+    pub max_compute_work_group_invocations: u32,
+    pub max_compute_work_group_size: [u32; 3],
+    pub sub_pixel_precision_bits: u32,
+    pub sub_texel_precision_bits: u32,
+    pub mipmap_precision_bits: u32,
+    pub max_draw_indexed_index_value: u32,
+    pub max_draw_indirect_count: u32,
+    pub max_sampler_lod_bias: f32,
+    pub max_sampler_anisotropy: f32,
+    pub max_viewports: u32,
+    pub max_viewport_dimensions: [u32; 2],
+    pub viewport_bounds_range: [f32; 2],
+    pub viewport_sub_pixel_bits: u32,
+    pub min_memory_map_alignment: usize,
+    pub min_texel_buffer_offset_alignment: u64,
+    pub min_uniform_buffer_offset_alignment: u64,
+    pub min_storage_buffer_offset_alignment: u64,
+    pub min_texel_offset: i32,
+    pub max_texel_offset: u32,
+    pub min_texel_gather_offset: i32,
+    pub max_texel_gather_offset: u32,
+    pub min_interpolation_offset: f32,
+    pub max_interpolation_offset: f32,
+    pub sub_pixel_interpolation_offset_bits: u32,
+    pub max_framebuffer_width: u32,
+    pub max_framebuffer_height: u32,
+    pub max_framebuffer_layers: u32,
+    pub framebuffer_color_sample_counts: u32,
+    pub framebuffer_depth_sample_counts: u32,
+    pub framebuffer_stencil_sample_counts: u32,
+    pub framebuffer_no_attachments_sample_counts: u32,
+    pub max_color_attachments: u32,
+    pub sampled_image_color_sample_counts: u32,
+    pub sampled_image_integer_sample_counts: u32,
+    pub sampled_image_depth_sample_counts: u32,
+    pub sampled_image_stencil_sample_counts: u32,
+    pub storage_image_sample_counts: u32,
+    pub max_sample_mask_words: u32,
+    pub timestamp_compute_and_graphics: bool,
+    pub timestamp_period: f32,
+    pub max_clip_distances: u32,
+    pub max_cull_distances: u32,
+    pub max_combined_clip_and_cull_distances: u32,
+    pub discrete_queue_priorities: u32,
+    pub point_size_range: [f32; 2],
+    pub line_width_range: [f32; 2],
+    pub point_size_granularity: f32,
+    pub line_width_granularity: f32,
+    pub strict_lines: bool,
+    pub standard_sample_locations: bool,
+    pub optimal_buffer_copy_offset_alignment: u64,
+    pub optimal_buffer_copy_row_pitch_alignment: u64,
+    pub non_coherent_atom_size: u64,
+}
+
+/// Limits of images with a given format, type, tiling, usage and create flags.
+#[derive(Debug, Clone, Copy)]
+pub struct ImageFormatProperties {
+    pub max_extent: Extent3D,
+    pub max_mip_levels: u32,
+    pub max_array_layers: u32,
+    pub sample_counts: u32,
+    pub max_resource_size: u64,
+}
+
+/// Memory types and heaps of a physical device.
+#[derive(Debug, Clone, Default)]
+pub struct PhysicalDeviceMemoryProperties {
+    pub memory_types: Vec<MemoryType>,
+    pub memory_heaps: Vec<MemoryHeap>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MemoryType {
+    pub property_flags: MemoryPropertyFlags,
+    /// Index into [`PhysicalDeviceMemoryProperties::memory_heaps`].
+    pub heap_index: u32,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MemoryHeap {
+    pub size: u64,
+    pub device_local: bool,
 }
 
 pub struct QueueFamilyProperties {
@@ -80,8 +322,244 @@ impl<'a> PhysicalDevice<'a> {
 
         PhysicalDeviceProperties {
             api_version: ApiVersion::from(props.api_version),
+            driver_version: props.driver_version,
+            vendor_id: props.vendor_id,
+            device_id: props.device_id,
             device_type,
             device_name,
+            pipeline_cache_uuid: props.pipeline_cache_uuid,
+            limits: props.limits.into(),
+        }
+    }
+
+    /// Names of the extensions the device supports.
+    ///
+    /// # Errors
+    ///
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    pub fn extension_names(&self) -> Result<Vec<String>> {
+        let vk_enumerate_device_extension_properties = self
+            .dispatch_loader
+            .vk_enumerate_device_extension_properties;
+        loop {
+            let mut property_count = 0;
+            let result = unsafe {
+                vk_enumerate_device_extension_properties(
+                    self.handle.as_ptr(),
+                    std::ptr::null(),
+                    &mut property_count,
+                    std::ptr::null_mut(),
+                )
+            };
+            match result {
+                ffi::Result::Success => (),
+                ffi::Result::ErrorOutOfHostMemory => return Err(Error::OutOfHostMemory),
+                ffi::Result::ErrorOutOfDeviceMemory => return Err(Error::OutOfDeviceMemory),
+                _ => unreachable!(),
+            }
+
+            let mut properties = Vec::with_capacity(property_count as usize);
+            let result = unsafe {
+                vk_enumerate_device_extension_properties(
+                    self.handle.as_ptr(),
+                    std::ptr::null(),
+                    &mut property_count,
+                    properties.as_mut_ptr(),
+                )
+            };
+            match result {
+                ffi::Result::Success => {
+                    unsafe { properties.set_len(property_count as usize) };
+                    return Ok(properties
+                        .iter()
+                        .map(|properties: &ffi::ExtensionProperties| {
+                            let name =
+                                unsafe { CStr::from_ptr(properties.extension_name.as_ptr()) };
+                            name.to_string_lossy().into_owned()
+                        })
+                        .collect());
+                }
+                ffi::Result::Incomplete => continue,
+                ffi::Result::ErrorOutOfHostMemory => return Err(Error::OutOfHostMemory),
+                ffi::Result::ErrorOutOfDeviceMemory => return Err(Error::OutOfDeviceMemory),
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    /// Features of the device. Synchronization2, dynamic rendering and extended dynamic state
+    /// are reported for Vulkan 1.3 devices and for devices with their extension, the mirror
+    /// clamp to edge address mode for Vulkan 1.2 devices and for devices with its extension.
+    pub fn features(&self) -> PhysicalDeviceFeatures {
+        let api_version = self.properties().api_version;
+        let vulkan12 = api_version >= ApiVersion::V1_2;
+        let vulkan13 = api_version >= ApiVersion::V1_3;
+        let extension_names = if vulkan13 {
+            Vec::new()
+        } else {
+            self.extension_names().unwrap_or_default()
+        };
+        let supports =
+            |extension: &str| vulkan13 || extension_names.iter().any(|name| name == extension);
+        // The extension has no feature struct, the address mode comes with it.
+        let mirror_clamp_to_edge_extension =
+            !vulkan12 && supports(SAMPLER_MIRROR_CLAMP_TO_EDGE_EXTENSION);
+
+        if let Some(vk_get_physical_device_features2) =
+            self.dispatch_loader.vk_get_physical_device_features2
+        {
+            let mut p_next: *mut c_void = std::ptr::null_mut();
+            let mut synchronization2 = ffi::PhysicalDeviceSynchronization2Features {
+                s_type: ffi::StructureType::PhysicalDeviceSynchronization2Features,
+                p_next: std::ptr::null_mut(),
+                synchronization2: 0,
+            };
+            if supports(SYNCHRONIZATION2_EXTENSION) {
+                synchronization2.p_next = p_next;
+                p_next = (&mut synchronization2
+                    as *mut ffi::PhysicalDeviceSynchronization2Features)
+                    .cast();
+            }
+            let mut dynamic_rendering = ffi::PhysicalDeviceDynamicRenderingFeatures {
+                s_type: ffi::StructureType::PhysicalDeviceDynamicRenderingFeatures,
+                p_next: std::ptr::null_mut(),
+                dynamic_rendering: 0,
+            };
+            if supports(DYNAMIC_RENDERING_EXTENSION) {
+                dynamic_rendering.p_next = p_next;
+                p_next = (&mut dynamic_rendering
+                    as *mut ffi::PhysicalDeviceDynamicRenderingFeatures)
+                    .cast();
+            }
+            let mut extended_dynamic_state = ffi::PhysicalDeviceExtendedDynamicStateFeaturesExt {
+                s_type: ffi::StructureType::PhysicalDeviceExtendedDynamicStateFeaturesExt,
+                p_next: std::ptr::null_mut(),
+                extended_dynamic_state: 0,
+            };
+            if !vulkan13 && supports(EXTENDED_DYNAMIC_STATE_EXTENSION) {
+                extended_dynamic_state.p_next = p_next;
+                p_next = (&mut extended_dynamic_state
+                    as *mut ffi::PhysicalDeviceExtendedDynamicStateFeaturesExt)
+                    .cast();
+            }
+            let mut vulkan12_features = ffi::PhysicalDeviceVulkan12Features::default();
+            if vulkan12 {
+                vulkan12_features.p_next = p_next;
+                p_next =
+                    (&mut vulkan12_features as *mut ffi::PhysicalDeviceVulkan12Features).cast();
+            }
+
+            if !p_next.is_null() {
+                let mut features2 = ffi::PhysicalDeviceFeatures2 {
+                    s_type: ffi::StructureType::PhysicalDeviceFeatures2,
+                    p_next,
+                    features: Default::default(),
+                };
+                unsafe { vk_get_physical_device_features2(self.handle.as_ptr(), &mut features2) };
+
+                return PhysicalDeviceFeatures {
+                    synchronization2: synchronization2.synchronization2 != 0,
+                    dynamic_rendering: dynamic_rendering.dynamic_rendering != 0,
+                    sampler_mirror_clamp_to_edge: mirror_clamp_to_edge_extension
+                        || vulkan12_features.sampler_mirror_clamp_to_edge != 0,
+                    extended_dynamic_state: vulkan13
+                        || extended_dynamic_state.extended_dynamic_state != 0,
+                    ..(&features2.features).into()
+                };
+            }
+        }
+
+        let mut features = MaybeUninit::uninit();
+        unsafe {
+            (self.dispatch_loader.vk_get_physical_device_features)(
+                self.handle.as_ptr(),
+                features.as_mut_ptr(),
+            )
+        };
+
+        PhysicalDeviceFeatures {
+            sampler_mirror_clamp_to_edge: mirror_clamp_to_edge_extension,
+            ..(&unsafe { features.assume_init() }).into()
+        }
+    }
+
+    pub fn memory_properties(&self) -> PhysicalDeviceMemoryProperties {
+        let mut props = MaybeUninit::uninit();
+        unsafe {
+            (self
+                .dispatch_loader
+                .vk_get_physical_device_memory_properties)(
+                self.handle.as_ptr(), props.as_mut_ptr()
+            )
+        };
+        let props = unsafe { props.assume_init() };
+
+        PhysicalDeviceMemoryProperties {
+            memory_types: props.memory_types[..props.memory_type_count as usize]
+                .iter()
+                .map(|memory_type| MemoryType {
+                    property_flags: MemoryPropertyFlags(memory_type.property_flags),
+                    heap_index: memory_type.heap_index,
+                })
+                .collect(),
+            memory_heaps: props.memory_heaps[..props.memory_heap_count as usize]
+                .iter()
+                .map(|memory_heap| MemoryHeap {
+                    size: memory_heap.size,
+                    device_local: memory_heap.flags
+                        & ffi::MemoryHeapFlagBits::DeviceLocalBit as u32
+                        != 0,
+                })
+                .collect(),
+        }
+    }
+
+    /// Limits of images created with the given parameters.
+    ///
+    /// # Errors
+    /// - [`FormatNotSupported`](Error::FormatNotSupported) if no image can be created with
+    ///   them.
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    pub fn image_format_properties(
+        &self,
+        format: Format,
+        image_type: ImageType,
+        tiling: ImageTiling,
+        usage: ImageUsageFlags,
+        flags: ImageCreateFlags,
+    ) -> Result<ImageFormatProperties> {
+        let mut props = MaybeUninit::uninit();
+        let result = unsafe {
+            (self
+                .dispatch_loader
+                .vk_get_physical_device_image_format_properties)(
+                self.handle.as_ptr(),
+                format.into(),
+                image_type.into(),
+                tiling.into(),
+                usage.0,
+                flags.0,
+                props.as_mut_ptr(),
+            )
+        };
+
+        match result {
+            ffi::Result::Success => {
+                let props = unsafe { props.assume_init() };
+                Ok(ImageFormatProperties {
+                    max_extent: props.max_extent.into(),
+                    max_mip_levels: props.max_mip_levels,
+                    max_array_layers: props.max_array_layers,
+                    sample_counts: props.sample_counts,
+                    max_resource_size: props.max_resource_size,
+                })
+            }
+            ffi::Result::ErrorOutOfHostMemory => Err(Error::OutOfHostMemory),
+            ffi::Result::ErrorOutOfDeviceMemory => Err(Error::OutOfDeviceMemory),
+            ffi::Result::ErrorFormatNotSupported => Err(Error::FormatNotSupported),
+            _ => unreachable!(),
         }
     }
 
@@ -119,11 +597,17 @@ impl<'a> PhysicalDevice<'a> {
             .collect()
     }
 
+    /// Creates a logical device with the queues of `queue_family_indices`, the
+    /// `extensions` and the `enabled_features`. Enabling synchronization2 or dynamic rendering
+    /// on a device older than Vulkan 1.3, or the mirror clamp to edge address mode on a device
+    /// older than Vulkan 1.2, also enables its extension, whose dependencies have to be given
+    /// in `extensions`.
     pub fn create_device(
         &self,
         queue_family_indices: &[usize],
         priorities: &[&[f32]],
         extensions: Option<&[&str]>,
+        enabled_features: Option<&PhysicalDeviceFeatures>,
     ) -> Device<'a> {
         let queue_create_infos: Vec<ffi::DeviceQueueCreateInfo> = queue_family_indices
             .iter()
@@ -138,23 +622,97 @@ impl<'a> PhysicalDevice<'a> {
             })
             .collect();
 
-        let extensions_c: Option<Vec<CString>> = extensions.map(|e| {
-            e.iter()
-                .map(|name| CString::new(name.as_bytes()).unwrap())
-                .collect()
-        });
-        let extension_ptrs: Option<Vec<*const i8>> = extensions_c
-            .as_ref()
-            .map(|e| e.iter().map(|name| name.as_ptr()).collect());
+        let enabled_features = enabled_features.copied().unwrap_or_default();
+        let api_version = self.properties().api_version;
+        let vulkan12 = api_version >= ApiVersion::V1_2;
+        let mut extensions = extensions.unwrap_or_default().to_vec();
+        let promoted = [
+            (
+                ApiVersion::V1_2,
+                enabled_features.sampler_mirror_clamp_to_edge,
+                SAMPLER_MIRROR_CLAMP_TO_EDGE_EXTENSION,
+            ),
+            (
+                ApiVersion::V1_3,
+                enabled_features.synchronization2,
+                SYNCHRONIZATION2_EXTENSION,
+            ),
+            (
+                ApiVersion::V1_3,
+                enabled_features.dynamic_rendering,
+                DYNAMIC_RENDERING_EXTENSION,
+            ),
+            (
+                ApiVersion::V1_3,
+                enabled_features.extended_dynamic_state,
+                EXTENDED_DYNAMIC_STATE_EXTENSION,
+            ),
+        ];
+        for (core_version, enabled, extension) in promoted {
+            if api_version < core_version && enabled && !extensions.contains(&extension) {
+                extensions.push(extension);
+            }
+        }
+
+        let extensions_c: Vec<CString> = extensions
+            .iter()
+            .map(|name| CString::new(name.as_bytes()).unwrap())
+            .collect();
+        let extension_ptrs: Vec<*const i8> =
+            extensions_c.iter().map(|name| name.as_ptr()).collect();
+
+        let enabled_extension_count = extension_ptrs.len() as u32;
+        let pp_enabled_extension_names = if extension_ptrs.is_empty() {
+            std::ptr::null()
+        } else {
+            extension_ptrs.as_ptr()
+        };
 
-        let enabled_extension_count = extension_ptrs.as_ref().map_or(0, |ptrs| ptrs.len() as u32);
-        let pp_enabled_extension_names = extension_ptrs
-            .as_ref()
-            .map_or(std::ptr::null(), |ptrs| ptrs.as_ptr());
+        let features = ffi::PhysicalDeviceFeatures::from(&enabled_features);
+        let mut p_next: *mut c_void = std::ptr::null_mut();
+        let mut synchronization2 = ffi::PhysicalDeviceSynchronization2Features {
+            s_type: ffi::StructureType::PhysicalDeviceSynchronization2Features,
+            p_next: std::ptr::null_mut(),
+            synchronization2: 1,
+        };
+        if enabled_features.synchronization2 {
+            synchronization2.p_next = p_next;
+            p_next =
+                (&mut synchronization2 as *mut ffi::PhysicalDeviceSynchronization2Features).cast();
+        }
+        let mut dynamic_rendering = ffi::PhysicalDeviceDynamicRenderingFeatures {
+            s_type: ffi::StructureType::PhysicalDeviceDynamicRenderingFeatures,
+            p_next: std::ptr::null_mut(),
+            dynamic_rendering: 1,
+        };
+        if enabled_features.dynamic_rendering {
+            dynamic_rendering.p_next = p_next;
+            p_next =
+                (&mut dynamic_rendering as *mut ffi::PhysicalDeviceDynamicRenderingFeatures).cast();
+        }
+        let mut extended_dynamic_state = ffi::PhysicalDeviceExtendedDynamicStateFeaturesExt {
+            s_type: ffi::StructureType::PhysicalDeviceExtendedDynamicStateFeaturesExt,
+            p_next: std::ptr::null_mut(),
+            extended_dynamic_state: 1,
+        };
+        if api_version < ApiVersion::V1_3 && enabled_features.extended_dynamic_state {
+            extended_dynamic_state.p_next = p_next;
+            p_next = (&mut extended_dynamic_state
+                as *mut ffi::PhysicalDeviceExtendedDynamicStateFeaturesExt)
+                .cast();
+        }
+        let mut vulkan12_features = ffi::PhysicalDeviceVulkan12Features {
+            sampler_mirror_clamp_to_edge: 1,
+            ..Default::default()
+        };
+        if vulkan12 && enabled_features.sampler_mirror_clamp_to_edge {
+            vulkan12_features.p_next = p_next;
+            p_next = (&mut vulkan12_features as *mut ffi::PhysicalDeviceVulkan12Features).cast();
+        }
 
         let create_info = ffi::DeviceCreateInfo {
             s_type: ffi::StructureType::DeviceCreateInfo,
-            p_next: std::ptr::null(),
+            p_next,
             flags: 0,
             queue_create_info_count: queue_create_infos.len() as u32,
             p_queue_create_infos: queue_create_infos.as_ptr(),
@@ -162,7 +720,7 @@ impl<'a> PhysicalDevice<'a> {
             pp_enabled_layer_names: std::ptr::null(),
             enabled_extension_count,
             pp_enabled_extension_names,
-            p_enabled_features: std::ptr::null(),
+            p_enabled_features: &features,
         };
 
         let mut device_handle = MaybeUninit::uninit();
@@ -185,6 +743,14 @@ impl<'a> PhysicalDevice<'a> {
                         device_handle,
                     )
                 },
+                physical_device: PhysicalDevice {
+                    handle: self.handle,
+                    dispatch_loader: self.dispatch_loader.clone(),
+                    _marker: PhantomData,
+                },
+                properties: self.properties(),
+                memory_properties: self.memory_properties(),
+                enabled_features,
                 _marker: PhantomData,
             }
         } else {
@@ -376,6 +942,53 @@ impl<'a> PhysicalDevice<'a> {
 }
 
 impl<'a> Device<'a> {
+    /// The physical device this device was created from.
+    pub fn physical_device(&self) -> &PhysicalDevice<'a> {
+        &self.physical_device
+    }
+
+    /// Properties of the physical device this device was created from.
+    pub fn properties(&self) -> &PhysicalDeviceProperties {
+        &self.properties
+    }
+
+    /// Limits of the physical device this device was created from.
+    pub fn limits(&self) -> &PhysicalDeviceLimits {
+        &self.properties.limits
+    }
+
+    /// Memory types and heaps of the physical device this device was created from.
+    pub fn memory_properties(&self) -> &PhysicalDeviceMemoryProperties {
+        &self.memory_properties
+    }
+
+    /// Features enabled when the device was created.
+    pub fn enabled_features(&self) -> &PhysicalDeviceFeatures {
+        &self.enabled_features
+    }
+
+    /// Whether synchronization2 commands are used. Otherwise barriers, events, timestamps and
+    /// submissions are lowered to their legacy equivalents.
+    pub fn supports_synchronization2(&self) -> bool {
+        self.enabled_features.synchronization2
+            && self.dispatch_loader.vk_cmd_pipeline_barrier2.is_some()
+            && self.dispatch_loader.vk_queue_submit2.is_some()
+    }
+
+    /// Whether dynamic rendering was enabled, which pipelines without a render pass and
+    /// [`begin_rendering`](crate::command_buffer::CommandBuffer::begin_rendering) need.
+    pub fn supports_dynamic_rendering(&self) -> bool {
+        self.enabled_features.dynamic_rendering
+            && self.dispatch_loader.vk_cmd_begin_rendering.is_some()
+    }
+
+    /// Whether extended dynamic state was enabled, which the `Ext` variants of
+    /// [`DynamicState`](crate::pipeline::DynamicState) and their commands need.
+    pub fn supports_extended_dynamic_state(&self) -> bool {
+        self.enabled_features.extended_dynamic_state
+            && self.dispatch_loader.vk_cmd_set_cull_mode.is_some()
+    }
+
     pub fn get_queue(&self, queue_family_index: usize, queue_index: usize) -> Option<Queue<'_>> {
         let mut handle = MaybeUninit::uninit();
         unsafe {
@@ -389,6 +1002,8 @@ impl<'a> Device<'a> {
 
         NonNull::new(unsafe { handle.assume_init() }).map(|handle| Queue {
             handle,
+            device: self,
+            family_index: queue_family_index as u32,
             _marker: PhantomData,
         })
     }
@@ -459,6 +1074,136 @@ impl<'a> Device<'a> {
         }
     }
 
+    /// Creates a fence, optionally in the signaled state.
+    ///
+    /// # Errors
+    ///
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    pub fn create_fence(&self, signaled: bool) -> Result<Fence<'_>> {
+        let create_info = ffi::FenceCreateInfo {
+            s_type: ffi::StructureType::FenceCreateInfo,
+            p_next: std::ptr::null(),
+            flags: if signaled {
+                ffi::FenceCreateFlagBits::SignaledBit as u32
+            } else {
+                0
+            },
+        };
+
+        let mut handle = MaybeUninit::uninit();
+        let result = unsafe {
+            (self.dispatch_loader.vk_create_fence)(
+                self.handle.as_ptr(),
+                &create_info,
+                std::ptr::null(),
+                handle.as_mut_ptr(),
+            )
+        };
+
+        match result {
+            ffi::Result::Success => Ok(Fence {
+                #[cfg(target_pointer_width = "64")]
+                handle: unsafe { NonNull::new_unchecked(handle.assume_init()) },
+                #[cfg(not(target_pointer_width = "64"))]
+                handle: unsafe { NonZeroU64::new_unchecked(handle.assume_init()) },
+                device: self,
+                #[cfg(target_pointer_width = "64")]
+                _marker: PhantomData,
+            }),
+            ffi::Result::ErrorOutOfHostMemory => Err(Error::OutOfHostMemory),
+            ffi::Result::ErrorOutOfDeviceMemory => Err(Error::OutOfDeviceMemory),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Creates an event in the reset state.
+    ///
+    /// # Errors
+    ///
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    pub fn create_event(&self) -> Result<Event<'_>> {
+        let create_info = ffi::EventCreateInfo {
+            s_type: ffi::StructureType::EventCreateInfo,
+            p_next: std::ptr::null(),
+            flags: 0,
+        };
+
+        let mut handle = MaybeUninit::uninit();
+        let result = unsafe {
+            (self.dispatch_loader.vk_create_event)(
+                self.handle.as_ptr(),
+                &create_info,
+                std::ptr::null(),
+                handle.as_mut_ptr(),
+            )
+        };
+
+        match result {
+            ffi::Result::Success => Ok(Event {
+                #[cfg(target_pointer_width = "64")]
+                handle: unsafe { NonNull::new_unchecked(handle.assume_init()) },
+                #[cfg(not(target_pointer_width = "64"))]
+                handle: unsafe { NonZeroU64::new_unchecked(handle.assume_init()) },
+                device: self,
+                #[cfg(target_pointer_width = "64")]
+                _marker: PhantomData,
+            }),
+            ffi::Result::ErrorOutOfHostMemory => Err(Error::OutOfHostMemory),
+            ffi::Result::ErrorOutOfDeviceMemory => Err(Error::OutOfDeviceMemory),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Creates a pool of `query_count` queries of `query_type`.
+    ///
+    /// # Errors
+    ///
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    pub fn create_query_pool(
+        &self,
+        query_type: QueryType,
+        query_count: u32,
+    ) -> Result<QueryPool<'_>> {
+        let create_info = ffi::QueryPoolCreateInfo {
+            s_type: ffi::StructureType::QueryPoolCreateInfo,
+            p_next: std::ptr::null(),
+            flags: 0,
+            query_type: query_type.into(),
+            query_count,
+            pipeline_statistics: 0,
+        };
+
+        let mut handle = MaybeUninit::uninit();
+        let result = unsafe {
+            (self.dispatch_loader.vk_create_query_pool)(
+                self.handle.as_ptr(),
+                &create_info,
+                std::ptr::null(),
+                handle.as_mut_ptr(),
+            )
+        };
+
+        match result {
+            ffi::Result::Success => Ok(QueryPool {
+                #[cfg(target_pointer_width = "64")]
+                handle: unsafe { NonNull::new_unchecked(handle.assume_init()) },
+                #[cfg(not(target_pointer_width = "64"))]
+                handle: unsafe { NonZeroU64::new_unchecked(handle.assume_init()) },
+                device: self,
+                query_type,
+                query_count,
+                #[cfg(target_pointer_width = "64")]
+                _marker: PhantomData,
+            }),
+            ffi::Result::ErrorOutOfHostMemory => Err(Error::OutOfHostMemory),
+            ffi::Result::ErrorOutOfDeviceMemory => Err(Error::OutOfDeviceMemory),
+            _ => unreachable!(),
+        }
+    }
+
     pub fn get_swapchain_images_khr<'b: 'a, 'c: 'b>(
         &'b self,
         swapchain: &'c SwapchainKhr<'b>,
@@ -507,6 +1252,18 @@ impl<'a> Device<'a> {
                                     device: self,
                                     #[cfg(target_pointer_width = "64")]
                                     _marker: PhantomData,
+                                    owned: false,
+                                    memory_bound: true,
+                                    format: swapchain.image_format,
+                                    extent: Extent3D::new(
+                                        swapchain.image_extent.width(),
+                                        swapchain.image_extent.height(),
+                                        1,
+                                    ),
+                                    mip_levels: 1,
+                                    array_layers: swapchain.image_array_layers,
+                                    usage: swapchain.image_usage,
+                                    layouts: None,
                                 })
                                 .collect())
                         }
@@ -524,13 +1281,32 @@ impl<'a> Device<'a> {
         })
     }
 
+    /// Creates a shader module from SPIR-V `code`, whose interface is available through
+    /// [`ShaderModule::reflection`].
+    ///
+    /// Code that is misaligned or in the opposite byte order is copied, see
+    /// [`spirv_words`]. Use [`include_spirv!`](crate::include_spirv) to embed code that is
+    /// always aligned.
+    ///
+    /// # Errors
+    ///
+    /// Besides the errors returned by the driver:
+    /// - [`InvalidSpirv`](Error::InvalidSpirv) if the code is not well-formed SPIR-V.
+    ///
+    /// Driver errors:
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    /// - [`InvalidShaderNv`](Error::InvalidShaderNv)
     pub fn create_shader_module(&self, code: &[u8]) -> Result<ShaderModule<'_>> {
+        let code = spirv_words(code)?;
+        let reflection = ShaderReflection::from_words(&code)?;
+
         let create_info = ffi::ShaderModuleCreateInfo {
             s_type: ffi::StructureType::ShaderModuleCreateInfo,
             p_next: std::ptr::null(),
             flags: 0,
-            code_size: code.len(),
-            p_code: code.as_ptr().cast(),
+            code_size: code.len() * 4,
+            p_code: code.as_ptr(),
         };
 
         let mut p_shader_module = MaybeUninit::uninit();
@@ -551,6 +1327,7 @@ impl<'a> Device<'a> {
                 #[cfg(not(target_pointer_width = "64"))]
                 handle: unsafe { NonZeroU64::new_unchecked(p_shader_module.assume_init()) },
                 device: self,
+                reflection,
                 #[cfg(target_pointer_width = "64")]
                 _marker: PhantomData,
             }),
@@ -560,6 +1337,415 @@ impl<'a> Device<'a> {
             _ => unreachable!(),
         }
     }
+
+    /// Allocates `size` bytes of the memory type at `memory_type_index` in
+    /// [`memory_properties`](Self::memory_properties). The memory is freed when dropped.
+    ///
+    /// # Errors
+    /// - [`MemoryRangeOutOfBounds`](Error::MemoryRangeOutOfBounds) if `size` is zero.
+    /// - [`InvalidMemoryType`](Error::InvalidMemoryType) if there is no memory type at
+    ///   `memory_type_index`.
+    ///
+    /// Driver errors:
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    /// - [`TooManyObjects`](Error::TooManyObjects)
+    /// - [`InvalidExternalHandle`](Error::InvalidExternalHandle)
+    /// - [`InvalidOpaqueCaptureAddress`](Error::InvalidOpaqueCaptureAddress)
+    pub fn allocate_memory(&self, size: u64, memory_type_index: u32) -> Result<DeviceMemory<'_>> {
+        self.allocate(size, memory_type_index, None)
+    }
+
+    /// Allocates `size` bytes of memory that only `resource` is bound to, as preferred or
+    /// required by its [`MemoryRequirements`](crate::resource::MemoryRequirements). Needs
+    /// Vulkan 1.1 or `"VK_KHR_dedicated_allocation"`.
+    ///
+    /// # Errors
+    /// The same as [`allocate_memory`](Self::allocate_memory).
+    pub fn allocate_dedicated_memory(
+        &self,
+        size: u64,
+        memory_type_index: u32,
+        resource: DedicatedResource,
+    ) -> Result<DeviceMemory<'_>> {
+        let dedicated_info = match resource {
+            DedicatedResource::Buffer(buffer) => ffi::MemoryDedicatedAllocateInfo {
+                s_type: ffi::StructureType::MemoryDedicatedAllocateInfo,
+                p_next: std::ptr::null(),
+                #[cfg(target_pointer_width = "64")]
+                image: std::ptr::null_mut(),
+                #[cfg(not(target_pointer_width = "64"))]
+                image: 0,
+                #[cfg(target_pointer_width = "64")]
+                buffer: buffer.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                buffer: buffer.handle.get(),
+            },
+            DedicatedResource::Image(image) => ffi::MemoryDedicatedAllocateInfo {
+                s_type: ffi::StructureType::MemoryDedicatedAllocateInfo,
+                p_next: std::ptr::null(),
+                #[cfg(target_pointer_width = "64")]
+                image: image.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                image: image.handle.get(),
+                #[cfg(target_pointer_width = "64")]
+                buffer: std::ptr::null_mut(),
+                #[cfg(not(target_pointer_width = "64"))]
+                buffer: 0,
+            },
+        };
+
+        self.allocate(size, memory_type_index, Some(&dedicated_info))
+    }
+
+    fn allocate(
+        &self,
+        size: u64,
+        memory_type_index: u32,
+        dedicated_info: Option<&ffi::MemoryDedicatedAllocateInfo>,
+    ) -> Result<DeviceMemory<'_>> {
+        if size == 0 {
+            return Err(Error::MemoryRangeOutOfBounds { offset: 0, size });
+        }
+        let property_flags = match self
+            .memory_properties
+            .memory_types
+            .get(memory_type_index as usize)
+        {
+            Some(memory_type) => memory_type.property_flags,
+            None => return Err(Error::InvalidMemoryType(memory_type_index)),
+        };
+
+        let allocate_info = ffi::MemoryAllocateInfo {
+            s_type: ffi::StructureType::MemoryAllocateInfo,
+            p_next: dedicated_info.map_or(std::ptr::null(), |info| {
+                (info as *const ffi::MemoryDedicatedAllocateInfo).cast()
+            }),
+            allocation_size: size,
+            memory_type_index,
+        };
+
+        let mut p_memory = MaybeUninit::uninit();
+
+        let result = unsafe {
+            (self.dispatch_loader.vk_allocate_memory)(
+                self.handle.as_ptr(),
+                &allocate_info,
+                std::ptr::null(),
+                p_memory.as_mut_ptr(),
+            )
+        };
+
+        match result {
+            ffi::Result::Success => Ok(DeviceMemory {
+                #[cfg(target_pointer_width = "64")]
+                handle: unsafe { NonNull::new_unchecked(p_memory.assume_init()) },
+                #[cfg(not(target_pointer_width = "64"))]
+                handle: unsafe { NonZeroU64::new_unchecked(p_memory.assume_init()) },
+                device: self,
+                size,
+                memory_type_index,
+                property_flags,
+                #[cfg(target_pointer_width = "64")]
+                _marker: PhantomData,
+            }),
+            ffi::Result::ErrorOutOfHostMemory => Err(Error::OutOfHostMemory),
+            ffi::Result::ErrorOutOfDeviceMemory => Err(Error::OutOfDeviceMemory),
+            ffi::Result::ErrorTooManyObjects => Err(Error::TooManyObjects),
+            ffi::Result::ErrorInvalidExternalHandle => Err(Error::InvalidExternalHandle),
+            ffi::Result::ErrorInvalidOpaqueCaptureAddress => {
+                Err(Error::InvalidOpaqueCaptureAddress)
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<'a> Queue<'a> {
+    /// Index of the queue family the queue belongs to.
+    pub fn family_index(&self) -> u32 {
+        self.family_index
+    }
+
+    /// Submits batches of command buffers for execution. `fence` is signaled once all of them
+    /// have completed.
+    ///
+    /// # Errors
+    ///
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    /// - [`DeviceLost`](Error::DeviceLost)
+    pub fn submit(&self, submits: &[SubmitInfo], fence: Option<&Fence>) -> Result<()> {
+        let wait_semaphores: Vec<Vec<_>> = submits
+            .iter()
+            .map(|submit| {
+                submit
+                    .wait_semaphores
+                    .iter()
+                    .map(|(semaphore, _)| {
+                        #[cfg(target_pointer_width = "64")]
+                        let handle = semaphore.handle.as_ptr();
+                        #[cfg(not(target_pointer_width = "64"))]
+                        let handle = semaphore.handle.get();
+                        handle
+                    })
+                    .collect()
+            })
+            .collect();
+        let wait_stages: Vec<Vec<_>> = submits
+            .iter()
+            .map(|submit| {
+                submit
+                    .wait_semaphores
+                    .iter()
+                    .map(|(_, stages)| stages.to_legacy_wait_stages(&self.device.enabled_features))
+                    .collect()
+            })
+            .collect();
+        let command_buffers: Vec<Vec<_>> = submits
+            .iter()
+            .map(|submit| {
+                submit
+                    .command_buffers
+                    .iter()
+                    .map(|command_buffer| command_buffer.handle.as_ptr())
+                    .collect()
+            })
+            .collect();
+        let signal_semaphores: Vec<Vec<_>> = submits
+            .iter()
+            .map(|submit| {
+                submit
+                    .signal_semaphores
+                    .iter()
+                    .map(|semaphore| {
+                        #[cfg(target_pointer_width = "64")]
+                        let handle = semaphore.handle.as_ptr();
+                        #[cfg(not(target_pointer_width = "64"))]
+                        let handle = semaphore.handle.get();
+                        handle
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let submits: Vec<ffi::SubmitInfo> = (0..submits.len())
+            .map(|i| ffi::SubmitInfo {
+                s_type: ffi::StructureType::SubmitInfo,
+                p_next: std::ptr::null(),
+                wait_semaphore_count: wait_semaphores[i].len() as u32,
+                p_wait_semaphores: wait_semaphores[i].as_ptr(),
+                p_wait_dst_stage_mask: wait_stages[i].as_ptr(),
+                command_buffer_count: command_buffers[i].len() as u32,
+                p_command_buffers: command_buffers[i].as_ptr(),
+                signal_semaphore_count: signal_semaphores[i].len() as u32,
+                p_signal_semaphores: signal_semaphores[i].as_ptr(),
+            })
+            .collect();
+
+        let result = unsafe {
+            (self.device.dispatch_loader.vk_queue_submit)(
+                self.handle.as_ptr(),
+                submits.len() as u32,
+                submits.as_ptr(),
+                #[cfg(target_pointer_width = "64")]
+                fence.map_or(std::ptr::null_mut(), |fence| fence.handle.as_ptr()),
+                #[cfg(not(target_pointer_width = "64"))]
+                fence.map_or(0, |fence| fence.handle.get()),
+            )
+        };
+
+        match result {
+            ffi::Result::Success => Ok(()),
+            ffi::Result::ErrorOutOfHostMemory => Err(Error::OutOfHostMemory),
+            ffi::Result::ErrorOutOfDeviceMemory => Err(Error::OutOfDeviceMemory),
+            ffi::Result::ErrorDeviceLost => Err(Error::DeviceLost),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Submits batches of command buffers like [`submit`](Self::submit), but with
+    /// synchronization2 so that wait stages keep their full precision. Signal semaphores are
+    /// signaled after all commands. Falls back to [`submit`](Self::submit) if the device does
+    /// not support synchronization2.
+    ///
+    /// # Errors
+    ///
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    /// - [`DeviceLost`](Error::DeviceLost)
+    pub fn submit2(&self, submits: &[SubmitInfo], fence: Option<&Fence>) -> Result<()> {
+        let vk_queue_submit2 = match self.device.dispatch_loader.vk_queue_submit2 {
+            Some(vk_queue_submit2) if self.device.supports_synchronization2() => vk_queue_submit2,
+            _ => return self.submit(submits, fence),
+        };
+
+        let semaphore_info = |semaphore: &Semaphore, stages: u64| ffi::SemaphoreSubmitInfo {
+            s_type: ffi::StructureType::SemaphoreSubmitInfo,
+            p_next: std::ptr::null(),
+            #[cfg(target_pointer_width = "64")]
+            semaphore: semaphore.handle.as_ptr(),
+            #[cfg(not(target_pointer_width = "64"))]
+            semaphore: semaphore.handle.get(),
+            value: 0,
+            stage_mask: stages,
+            device_index: 0,
+        };
+        let wait_semaphores: Vec<Vec<_>> = submits
+            .iter()
+            .map(|submit| {
+                submit
+                    .wait_semaphores
+                    .iter()
+                    .map(|(semaphore, stages)| semaphore_info(semaphore, stages.0))
+                    .collect()
+            })
+            .collect();
+        let command_buffers: Vec<Vec<_>> = submits
+            .iter()
+            .map(|submit| {
+                submit
+                    .command_buffers
+                    .iter()
+                    .map(|command_buffer| ffi::CommandBufferSubmitInfo {
+                        s_type: ffi::StructureType::CommandBufferSubmitInfo,
+                        p_next: std::ptr::null(),
+                        command_buffer: command_buffer.handle.as_ptr(),
+                        device_mask: 0,
+                    })
+                    .collect()
+            })
+            .collect();
+        let signal_semaphores: Vec<Vec<_>> = submits
+            .iter()
+            .map(|submit| {
+                submit
+                    .signal_semaphores
+                    .iter()
+                    .map(|semaphore| {
+                        semaphore_info(
+                            semaphore,
+                            ffi::PipelineStageFlagBits2::AllCommandsBit as u64,
+                        )
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let submits: Vec<ffi::SubmitInfo2> = (0..submits.len())
+            .map(|i| ffi::SubmitInfo2 {
+                s_type: ffi::StructureType::SubmitInfo2,
+                p_next: std::ptr::null(),
+                flags: 0,
+                wait_semaphore_info_count: wait_semaphores[i].len() as u32,
+                p_wait_semaphore_infos: wait_semaphores[i].as_ptr(),
+                command_buffer_info_count: command_buffers[i].len() as u32,
+                p_command_buffer_infos: command_buffers[i].as_ptr(),
+                signal_semaphore_info_count: signal_semaphores[i].len() as u32,
+                p_signal_semaphore_infos: signal_semaphores[i].as_ptr(),
+            })
+            .collect();
+
+        let result = unsafe {
+            vk_queue_submit2(
+                self.handle.as_ptr(),
+                submits.len() as u32,
+                submits.as_ptr(),
+                #[cfg(target_pointer_width = "64")]
+                fence.map_or(std::ptr::null_mut(), |fence| fence.handle.as_ptr()),
+                #[cfg(not(target_pointer_width = "64"))]
+                fence.map_or(0, |fence| fence.handle.get()),
+            )
+        };
+
+        match result {
+            ffi::Result::Success => Ok(()),
+            ffi::Result::ErrorOutOfHostMemory => Err(Error::OutOfHostMemory),
+            ffi::Result::ErrorOutOfDeviceMemory => Err(Error::OutOfDeviceMemory),
+            ffi::Result::ErrorDeviceLost => Err(Error::DeviceLost),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Waits for all work submitted to the queue to complete.
+    ///
+    /// # Errors
+    ///
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    /// - [`DeviceLost`](Error::DeviceLost)
+    pub fn wait_idle(&self) -> Result<()> {
+        let result =
+            unsafe { (self.device.dispatch_loader.vk_queue_wait_idle)(self.handle.as_ptr()) };
+
+        match result {
+            ffi::Result::Success => Ok(()),
+            ffi::Result::ErrorOutOfHostMemory => Err(Error::OutOfHostMemory),
+            ffi::Result::ErrorOutOfDeviceMemory => Err(Error::OutOfDeviceMemory),
+            ffi::Result::ErrorDeviceLost => Err(Error::DeviceLost),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<'a> std::fmt::Debug for Queue<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Queue")
+            .field("handle", &self.handle)
+            .field("family_index", &self.family_index)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'s> SubmitInfo<'s> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Waits on `semaphore` before the commands reach `stages`.
+    pub fn with_wait_semaphore(
+        mut self,
+        semaphore: &'s Semaphore<'s>,
+        stages: PipelineStageFlags,
+    ) -> Self {
+        self.wait_semaphores.push((semaphore, stages));
+        self
+    }
+
+    pub fn with_command_buffer(mut self, command_buffer: &'s CommandBuffer<'s>) -> Self {
+        self.command_buffers.push(command_buffer);
+        self
+    }
+
+    pub fn with_signal_semaphore(mut self, semaphore: &'s Semaphore<'s>) -> Self {
+        self.signal_semaphores.push(semaphore);
+        self
+    }
+}
+
+impl ImageFormatProperties {
+    pub fn supports_samples(&self, samples: SampleCount) -> bool {
+        self.sample_counts & ffi::SampleCountFlagBits::from(samples) as u32 != 0
+    }
+}
+
+impl PhysicalDeviceMemoryProperties {
+    /// Index of the first memory type allowed by `memory_type_bits` that has all of
+    /// `properties`.
+    pub fn find_memory_type(
+        &self,
+        memory_type_bits: u32,
+        properties: MemoryPropertyFlags,
+    ) -> Option<u32> {
+        self.memory_types
+            .iter()
+            .enumerate()
+            .position(|(index, memory_type)| {
+                memory_type_bits & (1 << index) != 0
+                    && memory_type.property_flags.0 & properties.0 == properties.0
+            })
+            .map(|index| index as u32)
+    }
 }
 
 impl<'a> Drop for Device<'a> {
@@ -569,6 +1755,131 @@ impl<'a> Drop for Device<'a> {
     }
 }
 
+impl From<ffi::PhysicalDeviceLimits> for PhysicalDeviceLimits {
+    fn from(limits: ffi::PhysicalDeviceLimits) -> Self {
+        Self {
+            max_image_dimension_1d: limits.max_image_dimension_1d,
+            max_image_dimension_2d: limits.max_image_dimension_2d,
+            max_image_dimension_3d: limits.max_image_dimension_3d,
+            max_image_dimension_cube: limits.max_image_dimension_cube,
+            max_image_array_layers: limits.max_image_array_layers,
+            max_texel_buffer_elements: limits.max_texel_buffer_elements,
+            max_uniform_buffer_range: limits.max_uniform_buffer_range,
+            max_storage_buffer_range: limits.max_storage_buffer_range,
+            max_push_constants_size: limits.max_push_constants_size,
+            max_memory_allocation_count: limits.max_memory_allocation_count,
+            max_sampler_allocation_count: limits.max_sampler_allocation_count,
+            buffer_image_granularity: limits.buffer_image_granularity,
+            sparse_address_space_size: limits.sparse_address_space_size,
+            max_bound_descriptor_sets: limits.max_bound_descriptor_sets,
+            max_per_stage_descriptor_samplers: limits.max_per_stage_descriptor_samplers,
+            max_per_stage_descriptor_uniform_buffers: limits
+                .max_per_stage_descriptor_uniform_buffers,
+            max_per_stage_descriptor_storage_buffers: limits
+                .max_per_stage_descriptor_storage_buffers,
+            max_per_stage_descriptor_sampled_images: limits.max_per_stage_descriptor_sampled_images,
+            max_per_stage_descriptor_storage_images: limits.max_per_stage_descriptor_storage_images,
+            max_per_stage_descriptor_input_attachments: limits
+                .max_per_stage_descriptor_input_attachments,
+            max_per_stage_resources: limits.max_per_stage_resources,
+            max_descriptor_set_samplers: limits.max_descriptor_set_samplers,
+            max_descriptor_set_uniform_buffers: limits.max_descriptor_set_uniform_buffers,
+            max_descriptor_set_uniform_buffers_dynamic: limits
+                .max_descriptor_set_uniform_buffers_dynamic,
+            max_descriptor_set_storage_buffers: limits.max_descriptor_set_storage_buffers,
+            max_descriptor_set_storage_buffers_dynamic: limits
+                .max_descriptor_set_storage_buffers_dynamic,
+            max_descriptor_set_sampled_images: limits.max_descriptor_set_sampled_images,
+            max_descriptor_set_storage_images: limits.max_descriptor_set_storage_images,
+            max_descriptor_set_input_attachments: limits.max_descriptor_set_input_attachments,
+            max_vertex_input_attributes: limits.max_vertex_input_attributes,
+            max_vertex_input_bindings: limits.max_vertex_input_bindings,
+            max_vertex_input_attribute_offset: limits.max_vertex_input_attribute_offset,
+            max_vertex_input_binding_stride: limits.max_vertex_input_binding_stride,
+            max_vertex_output_components: limits.max_vertex_output_components,
+            max_tessellation_generation_level: limits.max_tessellation_generation_level,
+            max_tessellation_patch_size: limits.max_tessellation_patch_size,
+            max_tessellation_control_per_vertex_input_components: limits
+                .max_tessellation_control_per_vertex_input_components,
+            max_tessellation_control_per_vertex_output_components: limits
+                .max_tessellation_control_per_vertex_output_components,
+            max_tessellation_control_per_patch_output_components: limits
+                .max_tessellation_control_per_patch_output_components,
+            max_tessellation_control_total_output_components: limits
+                .max_tessellation_control_total_output_components,
+            max_tessellation_evaluation_input_components: limits
+                .max_tessellation_evaluation_input_components,
+            max_tessellation_evaluation_output_components: limits
+                .max_tessellation_evaluation_output_components,
+            max_geometry_shader_invocations: limits.max_geometry_shader_invocations,
+            max_geometry_input_components: limits.max_geometry_input_components,
+            max_geometry_output_components: limits.max_geometry_output_components,
+            max_geometry_output_vertices: limits.max_geometry_output_vertices,
+            max_geometry_total_output_components: limits.max_geometry_total_output_components,
+            max_fragment_input_components: limits.max_fragment_input_components,
+            max_fragment_output_attachments: limits.max_fragment_output_attachments,
+            max_fragment_dual_src_attachments: limits.max_fragment_dual_src_attachments,
+            max_fragment_combined_output_resources: limits.max_fragment_combined_output_resources,
+            max_compute_shared_memory_size: limits.max_compute_shared_memory_size,
+            max_compute_work_group_count: limits.max_compute_work_group_count,
+            max_compute_work_group_invocations: limits.max_compute_work_group_invocations,
+            max_compute_work_group_size: limits.max_compute_work_group_size,
+            sub_pixel_precision_bits: limits.sub_pixel_precision_bits,
+            sub_texel_precision_bits: limits.sub_texel_precision_bits,
+            mipmap_precision_bits: limits.mipmap_precision_bits,
+            max_draw_indexed_index_value: limits.max_draw_indexed_index_value,
+            max_draw_indirect_count: limits.max_draw_indirect_count,
+            max_sampler_lod_bias: limits.max_sampler_lod_bias,
+            max_sampler_anisotropy: limits.max_sampler_anisotropy,
+            max_viewports: limits.max_viewports,
+            max_viewport_dimensions: limits.max_viewport_dimensions,
+            viewport_bounds_range: limits.viewport_bounds_range,
+            viewport_sub_pixel_bits: limits.viewport_sub_pixel_bits,
+            min_memory_map_alignment: limits.min_memory_map_alignment,
+            min_texel_buffer_offset_alignment: limits.min_texel_buffer_offset_alignment,
+            min_uniform_buffer_offset_alignment: limits.min_uniform_buffer_offset_alignment,
+            min_storage_buffer_offset_alignment: limits.min_storage_buffer_offset_alignment,
+            min_texel_offset: limits.min_texel_offset,
+            max_texel_offset: limits.max_texel_offset,
+            min_texel_gather_offset: limits.min_texel_gather_offset,
+            max_texel_gather_offset: limits.max_texel_gather_offset,
+            min_interpolation_offset: limits.min_interpolation_offset,
+            max_interpolation_offset: limits.max_interpolation_offset,
+            sub_pixel_interpolation_offset_bits: limits.sub_pixel_interpolation_offset_bits,
+            max_framebuffer_width: limits.max_framebuffer_width,
+            max_framebuffer_height: limits.max_framebuffer_height,
+            max_framebuffer_layers: limits.max_framebuffer_layers,
+            framebuffer_color_sample_counts: limits.framebuffer_color_sample_counts,
+            framebuffer_depth_sample_counts: limits.framebuffer_depth_sample_counts,
+            framebuffer_stencil_sample_counts: limits.framebuffer_stencil_sample_counts,
+            framebuffer_no_attachments_sample_counts: limits
+                .framebuffer_no_attachments_sample_counts,
+            max_color_attachments: limits.max_color_attachments,
+            sampled_image_color_sample_counts: limits.sampled_image_color_sample_counts,
+            sampled_image_integer_sample_counts: limits.sampled_image_integer_sample_counts,
+            sampled_image_depth_sample_counts: limits.sampled_image_depth_sample_counts,
+            sampled_image_stencil_sample_counts: limits.sampled_image_stencil_sample_counts,
+            storage_image_sample_counts: limits.storage_image_sample_counts,
+            max_sample_mask_words: limits.max_sample_mask_words,
+            timestamp_compute_and_graphics: limits.timestamp_compute_and_graphics != 0,
+            timestamp_period: limits.timestamp_period,
+            max_clip_distances: limits.max_clip_distances,
+            max_cull_distances: limits.max_cull_distances,
+            max_combined_clip_and_cull_distances: limits.max_combined_clip_and_cull_distances,
+            discrete_queue_priorities: limits.discrete_queue_priorities,
+            point_size_range: limits.point_size_range,
+            line_width_range: limits.line_width_range,
+            point_size_granularity: limits.point_size_granularity,
+            line_width_granularity: limits.line_width_granularity,
+            strict_lines: limits.strict_lines != 0,
+            standard_sample_locations: limits.standard_sample_locations != 0,
+            optimal_buffer_copy_offset_alignment: limits.optimal_buffer_copy_offset_alignment,
+            optimal_buffer_copy_row_pitch_alignment: limits.optimal_buffer_copy_row_pitch_alignment,
+            non_coherent_atom_size: limits.non_coherent_atom_size,
+        }
+    }
+}
+
 impl QueueFamilyProperties {
     pub fn supports_graphics(&self) -> bool {
         self.queue_flags & ffi::QueueFlagBits::GraphicsBit as u32 != 0
@@ -586,3 +1897,162 @@ impl QueueFamilyProperties {
         self.queue_flags & ffi::QueueFlagBits::SparseBindingBit as u32 != 0
     }
 }
+
+impl From<&ffi::PhysicalDeviceFeatures> for PhysicalDeviceFeatures {
+    fn from(features: &ffi::PhysicalDeviceFeatures) -> Self {
+        Self {
+            robust_buffer_access: features.robust_buffer_access != 0,
+            full_draw_index_uint32: features.full_draw_index_uint32 != 0,
+            image_cube_array: features.image_cube_array != 0,
+            independent_blend: features.independent_blend != 0,
+            geometry_shader: features.geometry_shader != 0,
+            tessellation_shader: features.tessellation_shader != 0,
+            sample_rate_shading: features.sample_rate_shading != 0,
+            dual_src_blend: features.dual_src_blend != 0,
+            logic_op: features.logic_op != 0,
+            multi_draw_indirect: features.multi_draw_indirect != 0,
+            draw_indirect_first_instance: features.draw_indirect_first_instance != 0,
+            depth_clamp: features.depth_clamp != 0,
+            depth_bias_clamp: features.depth_bias_clamp != 0,
+            fill_mode_non_solid: features.fill_mode_non_solid != 0,
+            depth_bounds: features.depth_bounds != 0,
+            wide_lines: features.wide_lines != 0,
+            large_points: features.large_points != 0,
+            alpha_to_one: features.alpha_to_one != 0,
+            multi_viewport: features.multi_viewport != 0,
+            sampler_anisotropy: features.sampler_anisotropy != 0,
+            texture_compression_etc2: features.texture_compression_etc2 != 0,
+            texture_compression_astc_ldr: features.texture_compression_astc_ldr != 0,
+            texture_compression_bc: features.texture_compression_bc != 0,
+            occlusion_query_precise: features.occlusion_query_precise != 0,
+            pipeline_statistics_query: features.pipeline_statistics_query != 0,
+            vertex_pipeline_stores_and_atomics: features.vertex_pipeline_stores_and_atomics != 0,
+            fragment_stores_and_atomics: features.fragment_stores_and_atomics != 0,
+            shader_tessellation_and_geometry_point_size: features
+                .shader_tessellation_and_geometry_point_size
+                != 0,
+            shader_image_gather_extended: features.shader_image_gather_extended != 0,
+            shader_storage_image_extended_formats: features.shader_storage_image_extended_formats
+                != 0,
+            shader_storage_image_multisample: features.shader_storage_image_multisample != 0,
+            shader_storage_image_read_without_format: features
+                .shader_storage_image_read_without_format
+                != 0,
+            shader_storage_image_write_without_format: features
+                .shader_storage_image_write_without_format
+                != 0,
+            shader_uniform_buffer_array_dynamic_indexing: features
+                .shader_uniform_buffer_array_dynamic_indexing
+                != 0,
+            shader_sampled_image_array_dynamic_indexing: features
+                .shader_sampled_image_array_dynamic_indexing
+                != 0,
+            shader_storage_buffer_array_dynamic_indexing: features
+                .shader_storage_buffer_array_dynamic_indexing
+                != 0,
+            shader_storage_image_array_dynamic_indexing: features
+                .shader_storage_image_array_dynamic_indexing
+                != 0,
+            shader_clip_distance: features.shader_clip_distance != 0,
+            shader_cull_distance: features.shader_cull_distance != 0,
+            shader_float64: features.shader_float64 != 0,
+            shader_int64: features.shader_int64 != 0,
+            shader_int16: features.shader_int16 != 0,
+            shader_resource_residency: features.shader_resource_residency != 0,
+            shader_resource_min_lod: features.shader_resource_min_lod != 0,
+            sparse_binding: features.sparse_binding != 0,
+            sparse_residency_buffer: features.sparse_residency_buffer != 0,
+            sparse_residency_image_2d: features.sparse_residency_image_2d != 0,
+            sparse_residency_image_3d: features.sparse_residency_image_3d != 0,
+            sparse_residency2_samples: features.sparse_residency2_samples != 0,
+            sparse_residency4_samples: features.sparse_residency4_samples != 0,
+            sparse_residency8_samples: features.sparse_residency8_samples != 0,
+            sparse_residency16_samples: features.sparse_residency16_samples != 0,
+            sparse_residency_aliased: features.sparse_residency_aliased != 0,
+            variable_multisample_rate: features.variable_multisample_rate != 0,
+            inherited_queries: features.inherited_queries != 0,
+            synchronization2: false,
+            dynamic_rendering: false,
+            sampler_mirror_clamp_to_edge: false,
+            extended_dynamic_state: false,
+        }
+    }
+}
+
+impl From<&PhysicalDeviceFeatures> for ffi::PhysicalDeviceFeatures {
+    fn from(features: &PhysicalDeviceFeatures) -> Self {
+        Self {
+            robust_buffer_access: features.robust_buffer_access.into(),
+            full_draw_index_uint32: features.full_draw_index_uint32.into(),
+            image_cube_array: features.image_cube_array.into(),
+            independent_blend: features.independent_blend.into(),
+            geometry_shader: features.geometry_shader.into(),
+            tessellation_shader: features.tessellation_shader.into(),
+            sample_rate_shading: features.sample_rate_shading.into(),
+            dual_src_blend: features.dual_src_blend.into(),
+            logic_op: features.logic_op.into(),
+            multi_draw_indirect: features.multi_draw_indirect.into(),
+            draw_indirect_first_instance: features.draw_indirect_first_instance.into(),
+            depth_clamp: features.depth_clamp.into(),
+            depth_bias_clamp: features.depth_bias_clamp.into(),
+            fill_mode_non_solid: features.fill_mode_non_solid.into(),
+            depth_bounds: features.depth_bounds.into(),
+            wide_lines: features.wide_lines.into(),
+            large_points: features.large_points.into(),
+            alpha_to_one: features.alpha_to_one.into(),
+            multi_viewport: features.multi_viewport.into(),
+            sampler_anisotropy: features.sampler_anisotropy.into(),
+            texture_compression_etc2: features.texture_compression_etc2.into(),
+            texture_compression_astc_ldr: features.texture_compression_astc_ldr.into(),
+            texture_compression_bc: features.texture_compression_bc.into(),
+            occlusion_query_precise: features.occlusion_query_precise.into(),
+            pipeline_statistics_query: features.pipeline_statistics_query.into(),
+            vertex_pipeline_stores_and_atomics: features.vertex_pipeline_stores_and_atomics.into(),
+            fragment_stores_and_atomics: features.fragment_stores_and_atomics.into(),
+            shader_tessellation_and_geometry_point_size: features
+                .shader_tessellation_and_geometry_point_size
+                .into(),
+            shader_image_gather_extended: features.shader_image_gather_extended.into(),
+            shader_storage_image_extended_formats: features
+                .shader_storage_image_extended_formats
+                .into(),
+            shader_storage_image_multisample: features.shader_storage_image_multisample.into(),
+            shader_storage_image_read_without_format: features
+                .shader_storage_image_read_without_format
+                .into(),
+            shader_storage_image_write_without_format: features
+                .shader_storage_image_write_without_format
+                .into(),
+            shader_uniform_buffer_array_dynamic_indexing: features
+                .shader_uniform_buffer_array_dynamic_indexing
+                .into(),
+            shader_sampled_image_array_dynamic_indexing: features
+                .shader_sampled_image_array_dynamic_indexing
+                .into(),
+            shader_storage_buffer_array_dynamic_indexing: features
+                .shader_storage_buffer_array_dynamic_indexing
+                .into(),
+            shader_storage_image_array_dynamic_indexing: features
+                .shader_storage_image_array_dynamic_indexing
+                .into(),
+            shader_clip_distance: features.shader_clip_distance.into(),
+            shader_cull_distance: features.shader_cull_distance.into(),
+            shader_float64: features.shader_float64.into(),
+            shader_int64: features.shader_int64.into(),
+            shader_int16: features.shader_int16.into(),
+            shader_resource_residency: features.shader_resource_residency.into(),
+            shader_resource_min_lod: features.shader_resource_min_lod.into(),
+            sparse_binding: features.sparse_binding.into(),
+            sparse_residency_buffer: features.sparse_residency_buffer.into(),
+            sparse_residency_image_2d: features.sparse_residency_image_2d.into(),
+            sparse_residency_image_3d: features.sparse_residency_image_3d.into(),
+            sparse_residency2_samples: features.sparse_residency2_samples.into(),
+            sparse_residency4_samples: features.sparse_residency4_samples.into(),
+            sparse_residency8_samples: features.sparse_residency8_samples.into(),
+            sparse_residency16_samples: features.sparse_residency16_samples.into(),
+            sparse_residency_aliased: features.sparse_residency_aliased.into(),
+            variable_multisample_rate: features.variable_multisample_rate.into(),
+            inherited_queries: features.inherited_queries.into(),
+        }
+    }
+}
@@ -1,4 +1,6 @@
 #![allow(dead_code, non_camel_case_types)]
+// Variants keep the `Bit` and vendor suffixes of the Vulkan enumerants they mirror.
+#![allow(clippy::enum_variant_names)]
 
 use std::ffi::c_void;
 use std::marker::{PhantomData, PhantomPinned};
@@ -27,7 +29,10 @@ macro_rules! vk_define_non_dispatchable_handle {
 pub type Bool32 = u32;
 pub type DeviceAddress = u64;
 pub type DeviceSize = u64;
+
+pub const QUEUE_FAMILY_IGNORED: u32 = !0;
 pub type Flags = u32;
+pub type Flags64 = u64;
 pub type SampleMask = u32;
 vk_define_non_dispatchable_handle!(VkBuffer_T);
 vk_define_non_dispatchable_handle!(VkImage_T);
@@ -108,12 +113,68 @@ pub enum StructureType {
     InstanceCreateInfo = 1,
     DeviceQueueCreateInfo = 2,
     DeviceCreateInfo = 3,
+    SubmitInfo = 4,
+    MemoryAllocateInfo = 5,
+    MappedMemoryRange = 6,
+    FenceCreateInfo = 8,
+    EventCreateInfo = 10,
+    QueryPoolCreateInfo = 11,
     SemaphoreCreateInfo = 9,
+    BufferCreateInfo = 12,
+    BufferViewCreateInfo = 13,
+    ImageCreateInfo = 14,
     ImageViewCreateInfo = 15,
     ShaderModuleCreateInfo = 16,
+    PipelineCacheCreateInfo = 17,
+    PipelineShaderStageCreateInfo = 18,
+    PipelineVertexInputStateCreateInfo = 19,
+    PipelineInputAssemblyStateCreateInfo = 20,
+    PipelineTessellationStateCreateInfo = 21,
+    PipelineViewportStateCreateInfo = 22,
+    PipelineRasterizationStateCreateInfo = 23,
+    PipelineMultisampleStateCreateInfo = 24,
+    PipelineDepthStencilStateCreateInfo = 25,
+    PipelineColorBlendStateCreateInfo = 26,
+    PipelineDynamicStateCreateInfo = 27,
+    GraphicsPipelineCreateInfo = 28,
+    ComputePipelineCreateInfo = 29,
+    PipelineLayoutCreateInfo = 30,
+    SamplerCreateInfo = 31,
+    DescriptorSetLayoutCreateInfo = 32,
+    DescriptorPoolCreateInfo = 33,
+    DescriptorSetAllocateInfo = 34,
+    WriteDescriptorSet = 35,
+    CopyDescriptorSet = 36,
+    RenderPassCreateInfo = 38,
     CommandPoolCreateInfo = 39,
     CommandBufferAllocateInfo = 40,
+    BufferMemoryBarrier = 44,
+    ImageMemoryBarrier = 45,
+    MemoryBarrier = 46,
+    CommandBufferInheritanceInfo = 41,
+    CommandBufferBeginInfo = 42,
+    PhysicalDeviceVulkan12Features = 51,
     SwapchainCreateInfoKhr = 1000001000,
+    RenderingInfo = 1000044000,
+    RenderingAttachmentInfo = 1000044001,
+    PipelineRenderingCreateInfo = 1000044002,
+    PhysicalDeviceDynamicRenderingFeatures = 1000044003,
+    PhysicalDeviceExtendedDynamicStateFeaturesExt = 1000267000,
+    MemoryDedicatedRequirements = 1000127000,
+    MemoryDedicatedAllocateInfo = 1000127001,
+    BufferMemoryRequirementsInfo2 = 1000146000,
+    ImageMemoryRequirementsInfo2 = 1000146001,
+    MemoryRequirements2 = 1000146003,
+    DescriptorSetLayoutBindingFlagsCreateInfo = 1000161000,
+    PhysicalDeviceFeatures2 = 1000059000,
+    MemoryBarrier2 = 1000314000,
+    BufferMemoryBarrier2 = 1000314001,
+    ImageMemoryBarrier2 = 1000314002,
+    DependencyInfo = 1000314003,
+    SubmitInfo2 = 1000314004,
+    SemaphoreSubmitInfo = 1000314005,
+    CommandBufferSubmitInfo = 1000314006,
+    PhysicalDeviceSynchronization2Features = 1000314007,
     XlibSurfaceCreateInfoKhr = 1000004000,
     XcbSurfaceCreateInfoKhr = 1000005000,
     Win32SurfaceCreateInfoKhr = 1000009000,
@@ -150,6 +211,113 @@ pub enum CommandBufferLevel {
     Secondary = 1,
 }
 
+#[repr(i32)]
+pub enum CommandBufferUsageFlagBits {
+    OneTimeSubmitBit = 0x00000001,
+    RenderPassContinueBit = 0x00000002,
+    SimultaneousUseBit = 0x00000004,
+}
+pub type CommandBufferUsageFlags = Flags;
+
+#[repr(i32)]
+pub enum PipelineCacheCreateFlagBits {
+    ExternallySynchronizedBitExt = 0x00000001,
+}
+pub type PipelineCacheCreateFlags = Flags;
+
+#[repr(i32)]
+pub enum PipelineCacheHeaderVersion {
+    One = 1,
+}
+
+#[repr(i32)]
+pub enum ImageLayout {
+    Undefined = 0,
+    General = 1,
+    ColorAttachmentOptimal = 2,
+    DepthStencilAttachmentOptimal = 3,
+    DepthStencilReadOnlyOptimal = 4,
+    ShaderReadOnlyOptimal = 5,
+    TransferSrcOptimal = 6,
+    TransferDstOptimal = 7,
+    Preinitialized = 8,
+    DepthReadOnlyStencilAttachmentOptimal = 1000117000,
+    DepthAttachmentStencilReadOnlyOptimal = 1000117001,
+    DepthAttachmentOptimal = 1000241000,
+    DepthReadOnlyOptimal = 1000241001,
+    StencilAttachmentOptimal = 1000241002,
+    StencilReadOnlyOptimal = 1000241003,
+    PresentSrcKhr = 1000001002,
+    SharedPresentKhr = 1000111000,
+    ReadOnlyOptimalKhr = 1000314000,
+    AttachmentOptimalKhr = 1000314001,
+}
+
+#[repr(i32)]
+pub enum AttachmentLoadOp {
+    Load = 0,
+    Clear = 1,
+    DontCare = 2,
+    NoneExt = 1000400000,
+}
+
+#[repr(i32)]
+pub enum AttachmentStoreOp {
+    Store = 0,
+    DontCare = 1,
+    NoneKhr = 1000301000,
+}
+
+#[repr(i32)]
+pub enum AttachmentDescriptionFlagBits {
+    MayAliasBit = 0x00000001,
+}
+pub type AttachmentDescriptionFlags = Flags;
+pub type SubpassDescriptionFlags = Flags;
+pub type RenderPassCreateFlags = Flags;
+pub type PipelineStageFlags = Flags;
+pub type PipelineStageFlags2 = Flags64;
+pub type AccessFlags2 = Flags64;
+pub type QueryPoolCreateFlags = Flags;
+pub type QueryResultFlags = Flags;
+pub type EventCreateFlags = Flags;
+pub type SubmitFlags = Flags;
+pub type AccessFlags = Flags;
+pub type DependencyFlags = Flags;
+
+#[repr(i32)]
+pub enum RenderingFlagBits {
+    ContentsSecondaryCommandBuffersBit = 0x00000001,
+    SuspendingBit = 0x00000002,
+    ResumingBit = 0x00000004,
+}
+pub type RenderingFlags = Flags;
+
+#[repr(i32)]
+pub enum ResolveModeFlagBits {
+    None = 0,
+    SampleZeroBit = 0x00000001,
+    AverageBit = 0x00000002,
+    MinBit = 0x00000004,
+    MaxBit = 0x00000008,
+}
+
+#[repr(i32)]
+pub enum StencilFaceFlagBits {
+    FrontBit = 0x00000001,
+    BackBit = 0x00000002,
+    FrontAndBack = 0x00000003,
+}
+pub type StencilFaceFlags = Flags;
+
+#[repr(i32)]
+pub enum PipelineBindPoint {
+    Graphics = 0,
+    Compute = 1,
+    RayTracingKhr = 1000165000,
+    SubpassShadingHuawei = 1000369003,
+}
+
 #[repr(i32)]
 pub enum ImageAspectFlagBits {
     ColorBit = 0x00000001,
@@ -174,9 +342,9 @@ pub struct Extent2D {
 
 #[repr(C)]
 pub struct Extent3D {
-    width: u32,
-    height: u32,
-    depth: u32,
+    pub width: u32,
+    pub height: u32,
+    pub depth: u32,
 }
 
 #[repr(C)]
@@ -187,9 +355,9 @@ pub struct Offset2D {
 
 #[repr(C)]
 pub struct Offset3D {
-    x: i32,
-    y: i32,
-    z: i32,
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
 }
 
 #[repr(C)]
@@ -270,6 +438,12 @@ pub type PFN_vkEnumeratePhysicalDevices = unsafe extern "system" fn(
     pPhysicalDeviceCount: *mut u32,
     pPhysicalDevices: *mut *mut VkPhysicalDevice_T,
 ) -> self::Result;
+pub type PFN_vkEnumerateDeviceExtensionProperties = unsafe extern "system" fn(
+    physical_device: *mut VkPhysicalDevice_T,
+    p_layer_name: *const i8,
+    p_property_count: *mut u32,
+    p_properties: *mut ExtensionProperties,
+) -> self::Result;
 pub type PFN_vkGetPhysicalDeviceProperties = unsafe extern "system" fn(
     physical_device: *mut VkPhysicalDevice_T,
     p_properties: *mut PhysicalDeviceProperties,
@@ -279,6 +453,27 @@ pub type PFN_vkGetPhysicalDeviceQueueFamilyProperties = unsafe extern "system" f
     p_queue_family_property_count: *mut u32,
     p_queue_family_properties: *mut QueueFamilyProperties,
 );
+pub type PFN_vkGetPhysicalDeviceMemoryProperties = unsafe extern "system" fn(
+    physical_device: *mut VkPhysicalDevice_T,
+    p_memory_properties: *mut PhysicalDeviceMemoryProperties,
+);
+pub type PFN_vkGetPhysicalDeviceFeatures = unsafe extern "system" fn(
+    physical_device: *mut VkPhysicalDevice_T,
+    p_features: *mut PhysicalDeviceFeatures,
+);
+pub type PFN_vkGetPhysicalDeviceFeatures2 = unsafe extern "system" fn(
+    physical_device: *mut VkPhysicalDevice_T,
+    p_features: *mut PhysicalDeviceFeatures2,
+);
+pub type PFN_vkGetPhysicalDeviceImageFormatProperties = unsafe extern "system" fn(
+    physical_device: *mut VkPhysicalDevice_T,
+    format: Format,
+    image_type: ImageType,
+    tiling: ImageTiling,
+    usage: ImageUsageFlags,
+    flags: ImageCreateFlags,
+    p_image_format_properties: *mut ImageFormatProperties,
+) -> self::Result;
 pub type PFN_vkCreateDevice = unsafe extern "system" fn(
     physical_device: *mut VkPhysicalDevice_T,
     p_create_info: *const DeviceCreateInfo,
@@ -371,6 +566,38 @@ pub type PFN_vkGetSwapchainImagesKHR = unsafe extern "system" fn(
     #[cfg(target_pointer_width = "64")] p_swapchain_images: *mut *mut VkImage_T,
     #[cfg(not(target_pointer_width = "64"))] p_swapchain_images: *mut u64,
 ) -> self::Result;
+pub type PFN_vkCreateImage = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    p_create_info: *const ImageCreateInfo,
+    p_allocator: *const AllocationCallbacks,
+    #[cfg(target_pointer_width = "64")] p_image: *mut *mut VkImage_T,
+    #[cfg(not(target_pointer_width = "64"))] p_image: *mut u64,
+) -> self::Result;
+pub type PFN_vkDestroyImage = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    #[cfg(target_pointer_width = "64")] image: *mut VkImage_T,
+    #[cfg(not(target_pointer_width = "64"))] image: u64,
+    p_allocator: *const AllocationCallbacks,
+);
+pub type PFN_vkGetImageMemoryRequirements = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    #[cfg(target_pointer_width = "64")] image: *mut VkImage_T,
+    #[cfg(not(target_pointer_width = "64"))] image: u64,
+    p_memory_requirements: *mut MemoryRequirements,
+);
+pub type PFN_vkGetImageMemoryRequirements2 = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    p_info: *const ImageMemoryRequirementsInfo2,
+    p_memory_requirements: *mut MemoryRequirements2,
+);
+pub type PFN_vkBindImageMemory = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    #[cfg(target_pointer_width = "64")] image: *mut VkImage_T,
+    #[cfg(not(target_pointer_width = "64"))] image: u64,
+    #[cfg(target_pointer_width = "64")] memory: *mut VkDeviceMemory_T,
+    #[cfg(not(target_pointer_width = "64"))] memory: u64,
+    memory_offset: DeviceSize,
+) -> self::Result;
 pub type PFN_vkCreateImageView = unsafe extern "system" fn(
     device: *mut VkDevice_T,
     p_create_info: *const ImageViewCreateInfo,
@@ -397,6 +624,532 @@ pub type PFN_vkDestroyShaderModule = unsafe extern "system" fn(
     #[cfg(not(target_pointer_width = "64"))] shader_module: u64,
     p_allocator: *const AllocationCallbacks,
 );
+pub type PFN_vkCreateDescriptorSetLayout = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    p_create_info: *const DescriptorSetLayoutCreateInfo,
+    p_allocator: *const AllocationCallbacks,
+    #[cfg(target_pointer_width = "64")] p_set_layout: *mut *mut VkDescriptorSetLayout_T,
+    #[cfg(not(target_pointer_width = "64"))] p_set_layout: *mut u64,
+) -> self::Result;
+pub type PFN_vkDestroyDescriptorSetLayout = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    #[cfg(target_pointer_width = "64")] descriptor_set_layout: *mut VkDescriptorSetLayout_T,
+    #[cfg(not(target_pointer_width = "64"))] descriptor_set_layout: u64,
+    p_allocator: *const AllocationCallbacks,
+);
+pub type PFN_vkCreateDescriptorPool = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    p_create_info: *const DescriptorPoolCreateInfo,
+    p_allocator: *const AllocationCallbacks,
+    #[cfg(target_pointer_width = "64")] p_descriptor_pool: *mut *mut VkDescriptorPool_T,
+    #[cfg(not(target_pointer_width = "64"))] p_descriptor_pool: *mut u64,
+) -> self::Result;
+pub type PFN_vkDestroyDescriptorPool = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    #[cfg(target_pointer_width = "64")] descriptor_pool: *mut VkDescriptorPool_T,
+    #[cfg(not(target_pointer_width = "64"))] descriptor_pool: u64,
+    p_allocator: *const AllocationCallbacks,
+);
+pub type PFN_vkResetDescriptorPool = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    #[cfg(target_pointer_width = "64")] descriptor_pool: *mut VkDescriptorPool_T,
+    #[cfg(not(target_pointer_width = "64"))] descriptor_pool: u64,
+    flags: DescriptorPoolResetFlags,
+) -> self::Result;
+pub type PFN_vkAllocateDescriptorSets = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    p_allocate_info: *const DescriptorSetAllocateInfo,
+    #[cfg(target_pointer_width = "64")] p_descriptor_sets: *mut *mut VkDescriptorSet_T,
+    #[cfg(not(target_pointer_width = "64"))] p_descriptor_sets: *mut u64,
+) -> self::Result;
+pub type PFN_vkFreeDescriptorSets = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    #[cfg(target_pointer_width = "64")] descriptor_pool: *mut VkDescriptorPool_T,
+    #[cfg(not(target_pointer_width = "64"))] descriptor_pool: u64,
+    descriptor_set_count: u32,
+    #[cfg(target_pointer_width = "64")] p_descriptor_sets: *const *mut VkDescriptorSet_T,
+    #[cfg(not(target_pointer_width = "64"))] p_descriptor_sets: *const u64,
+) -> self::Result;
+pub type PFN_vkUpdateDescriptorSets = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    descriptor_write_count: u32,
+    p_descriptor_writes: *const WriteDescriptorSet,
+    descriptor_copy_count: u32,
+    p_descriptor_copies: *const CopyDescriptorSet,
+);
+pub type PFN_vkCreateBufferView = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    p_create_info: *const BufferViewCreateInfo,
+    p_allocator: *const AllocationCallbacks,
+    #[cfg(target_pointer_width = "64")] p_view: *mut *mut VkBufferView_T,
+    #[cfg(not(target_pointer_width = "64"))] p_view: *mut u64,
+) -> self::Result;
+pub type PFN_vkDestroyBufferView = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    #[cfg(target_pointer_width = "64")] buffer_view: *mut VkBufferView_T,
+    #[cfg(not(target_pointer_width = "64"))] buffer_view: u64,
+    p_allocator: *const AllocationCallbacks,
+);
+pub type PFN_vkCreatePipelineLayout = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    p_create_info: *const PipelineLayoutCreateInfo,
+    p_allocator: *const AllocationCallbacks,
+    #[cfg(target_pointer_width = "64")] p_pipeline_layout: *mut *mut VkPipelineLayout_T,
+    #[cfg(not(target_pointer_width = "64"))] p_pipeline_layout: *mut u64,
+) -> self::Result;
+pub type PFN_vkDestroyPipelineLayout = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    #[cfg(target_pointer_width = "64")] pipeline_layout: *mut VkPipelineLayout_T,
+    #[cfg(not(target_pointer_width = "64"))] pipeline_layout: u64,
+    p_allocator: *const AllocationCallbacks,
+);
+pub type PFN_vkCreateComputePipelines = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    #[cfg(target_pointer_width = "64")] pipeline_cache: *mut VkPipelineCache_T,
+    #[cfg(not(target_pointer_width = "64"))] pipeline_cache: u64,
+    create_info_count: u32,
+    p_create_infos: *const ComputePipelineCreateInfo,
+    p_allocator: *const AllocationCallbacks,
+    #[cfg(target_pointer_width = "64")] p_pipelines: *mut *mut VkPipeline_T,
+    #[cfg(not(target_pointer_width = "64"))] p_pipelines: *mut u64,
+) -> self::Result;
+pub type PFN_vkCreatePipelineCache = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    p_create_info: *const PipelineCacheCreateInfo,
+    p_allocator: *const AllocationCallbacks,
+    #[cfg(target_pointer_width = "64")] p_pipeline_cache: *mut *mut VkPipelineCache_T,
+    #[cfg(not(target_pointer_width = "64"))] p_pipeline_cache: *mut u64,
+) -> self::Result;
+pub type PFN_vkDestroyPipelineCache = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    #[cfg(target_pointer_width = "64")] pipeline_cache: *mut VkPipelineCache_T,
+    #[cfg(not(target_pointer_width = "64"))] pipeline_cache: u64,
+    p_allocator: *const AllocationCallbacks,
+);
+pub type PFN_vkGetPipelineCacheData = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    #[cfg(target_pointer_width = "64")] pipeline_cache: *mut VkPipelineCache_T,
+    #[cfg(not(target_pointer_width = "64"))] pipeline_cache: u64,
+    p_data_size: *mut usize,
+    p_data: *mut c_void,
+) -> self::Result;
+pub type PFN_vkMergePipelineCaches = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    #[cfg(target_pointer_width = "64")] dst_cache: *mut VkPipelineCache_T,
+    #[cfg(not(target_pointer_width = "64"))] dst_cache: u64,
+    src_cache_count: u32,
+    #[cfg(target_pointer_width = "64")] p_src_caches: *const *mut VkPipelineCache_T,
+    #[cfg(not(target_pointer_width = "64"))] p_src_caches: *const u64,
+) -> self::Result;
+pub type PFN_vkDestroyPipeline = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    #[cfg(target_pointer_width = "64")] pipeline: *mut VkPipeline_T,
+    #[cfg(not(target_pointer_width = "64"))] pipeline: u64,
+    p_allocator: *const AllocationCallbacks,
+);
+pub type PFN_vkDestroyBuffer = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    #[cfg(target_pointer_width = "64")] buffer: *mut VkBuffer_T,
+    #[cfg(not(target_pointer_width = "64"))] buffer: u64,
+    p_allocator: *const AllocationCallbacks,
+);
+pub type PFN_vkCreateBuffer = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    p_create_info: *const BufferCreateInfo,
+    p_allocator: *const AllocationCallbacks,
+    #[cfg(target_pointer_width = "64")] p_buffer: *mut *mut VkBuffer_T,
+    #[cfg(not(target_pointer_width = "64"))] p_buffer: *mut u64,
+) -> self::Result;
+pub type PFN_vkGetBufferMemoryRequirements = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    #[cfg(target_pointer_width = "64")] buffer: *mut VkBuffer_T,
+    #[cfg(not(target_pointer_width = "64"))] buffer: u64,
+    p_memory_requirements: *mut MemoryRequirements,
+);
+pub type PFN_vkGetBufferMemoryRequirements2 = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    p_info: *const BufferMemoryRequirementsInfo2,
+    p_memory_requirements: *mut MemoryRequirements2,
+);
+pub type PFN_vkBindBufferMemory = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    #[cfg(target_pointer_width = "64")] buffer: *mut VkBuffer_T,
+    #[cfg(not(target_pointer_width = "64"))] buffer: u64,
+    #[cfg(target_pointer_width = "64")] memory: *mut VkDeviceMemory_T,
+    #[cfg(not(target_pointer_width = "64"))] memory: u64,
+    memory_offset: DeviceSize,
+) -> self::Result;
+pub type PFN_vkFreeMemory = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    #[cfg(target_pointer_width = "64")] memory: *mut VkDeviceMemory_T,
+    #[cfg(not(target_pointer_width = "64"))] memory: u64,
+    p_allocator: *const AllocationCallbacks,
+);
+pub type PFN_vkAllocateMemory = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    p_allocate_info: *const MemoryAllocateInfo,
+    p_allocator: *const AllocationCallbacks,
+    #[cfg(target_pointer_width = "64")] p_memory: *mut *mut VkDeviceMemory_T,
+    #[cfg(not(target_pointer_width = "64"))] p_memory: *mut u64,
+) -> self::Result;
+pub type PFN_vkMapMemory = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    #[cfg(target_pointer_width = "64")] memory: *mut VkDeviceMemory_T,
+    #[cfg(not(target_pointer_width = "64"))] memory: u64,
+    offset: DeviceSize,
+    size: DeviceSize,
+    flags: MemoryMapFlags,
+    pp_data: *mut *mut c_void,
+) -> self::Result;
+pub type PFN_vkUnmapMemory = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    #[cfg(target_pointer_width = "64")] memory: *mut VkDeviceMemory_T,
+    #[cfg(not(target_pointer_width = "64"))] memory: u64,
+);
+pub type PFN_vkFlushMappedMemoryRanges = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    memory_range_count: u32,
+    p_memory_ranges: *const MappedMemoryRange,
+) -> self::Result;
+pub type PFN_vkInvalidateMappedMemoryRanges = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    memory_range_count: u32,
+    p_memory_ranges: *const MappedMemoryRange,
+) -> self::Result;
+pub type PFN_vkBeginCommandBuffer = unsafe extern "system" fn(
+    command_buffer: *mut VkCommandBuffer_T,
+    p_begin_info: *const CommandBufferBeginInfo,
+) -> self::Result;
+pub type PFN_vkEndCommandBuffer =
+    unsafe extern "system" fn(command_buffer: *mut VkCommandBuffer_T) -> self::Result;
+pub type PFN_vkCmdBindPipeline = unsafe extern "system" fn(
+    command_buffer: *mut VkCommandBuffer_T,
+    pipeline_bind_point: PipelineBindPoint,
+    #[cfg(target_pointer_width = "64")] pipeline: *mut VkPipeline_T,
+    #[cfg(not(target_pointer_width = "64"))] pipeline: u64,
+);
+pub type PFN_vkCmdBindDescriptorSets = unsafe extern "system" fn(
+    command_buffer: *mut VkCommandBuffer_T,
+    pipeline_bind_point: PipelineBindPoint,
+    #[cfg(target_pointer_width = "64")] layout: *mut VkPipelineLayout_T,
+    #[cfg(not(target_pointer_width = "64"))] layout: u64,
+    first_set: u32,
+    descriptor_set_count: u32,
+    #[cfg(target_pointer_width = "64")] p_descriptor_sets: *const *mut VkDescriptorSet_T,
+    #[cfg(not(target_pointer_width = "64"))] p_descriptor_sets: *const u64,
+    dynamic_offset_count: u32,
+    p_dynamic_offsets: *const u32,
+);
+pub type PFN_vkCmdPushConstants = unsafe extern "system" fn(
+    command_buffer: *mut VkCommandBuffer_T,
+    #[cfg(target_pointer_width = "64")] layout: *mut VkPipelineLayout_T,
+    #[cfg(not(target_pointer_width = "64"))] layout: u64,
+    stage_flags: ShaderStageFlags,
+    offset: u32,
+    size: u32,
+    p_values: *const c_void,
+);
+pub type PFN_vkCreateFence = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    p_create_info: *const FenceCreateInfo,
+    p_allocator: *const AllocationCallbacks,
+    #[cfg(target_pointer_width = "64")] p_fence: *mut *mut VkFence_T,
+    #[cfg(not(target_pointer_width = "64"))] p_fence: *mut u64,
+) -> self::Result;
+pub type PFN_vkDestroyFence = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    #[cfg(target_pointer_width = "64")] fence: *mut VkFence_T,
+    #[cfg(not(target_pointer_width = "64"))] fence: u64,
+    p_allocator: *const AllocationCallbacks,
+);
+pub type PFN_vkResetFences = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    fence_count: u32,
+    #[cfg(target_pointer_width = "64")] p_fences: *const *mut VkFence_T,
+    #[cfg(not(target_pointer_width = "64"))] p_fences: *const u64,
+) -> self::Result;
+pub type PFN_vkGetFenceStatus = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    #[cfg(target_pointer_width = "64")] fence: *mut VkFence_T,
+    #[cfg(not(target_pointer_width = "64"))] fence: u64,
+) -> self::Result;
+pub type PFN_vkWaitForFences = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    fence_count: u32,
+    #[cfg(target_pointer_width = "64")] p_fences: *const *mut VkFence_T,
+    #[cfg(not(target_pointer_width = "64"))] p_fences: *const u64,
+    wait_all: Bool32,
+    timeout: u64,
+) -> self::Result;
+pub type PFN_vkQueueSubmit = unsafe extern "system" fn(
+    queue: *mut VkQueue_T,
+    submit_count: u32,
+    p_submits: *const SubmitInfo,
+    #[cfg(target_pointer_width = "64")] fence: *mut VkFence_T,
+    #[cfg(not(target_pointer_width = "64"))] fence: u64,
+) -> self::Result;
+pub type PFN_vkQueueWaitIdle = unsafe extern "system" fn(queue: *mut VkQueue_T) -> self::Result;
+pub type PFN_vkCmdCopyBuffer = unsafe extern "system" fn(
+    command_buffer: *mut VkCommandBuffer_T,
+    #[cfg(target_pointer_width = "64")] src_buffer: *mut VkBuffer_T,
+    #[cfg(not(target_pointer_width = "64"))] src_buffer: u64,
+    #[cfg(target_pointer_width = "64")] dst_buffer: *mut VkBuffer_T,
+    #[cfg(not(target_pointer_width = "64"))] dst_buffer: u64,
+    region_count: u32,
+    p_regions: *const BufferCopy,
+);
+pub type PFN_vkCmdCopyBufferToImage = unsafe extern "system" fn(
+    command_buffer: *mut VkCommandBuffer_T,
+    #[cfg(target_pointer_width = "64")] src_buffer: *mut VkBuffer_T,
+    #[cfg(not(target_pointer_width = "64"))] src_buffer: u64,
+    #[cfg(target_pointer_width = "64")] dst_image: *mut VkImage_T,
+    #[cfg(not(target_pointer_width = "64"))] dst_image: u64,
+    dst_image_layout: ImageLayout,
+    region_count: u32,
+    p_regions: *const BufferImageCopy,
+);
+pub type PFN_vkCmdPipelineBarrier2 = unsafe extern "system" fn(
+    command_buffer: *mut VkCommandBuffer_T,
+    p_dependency_info: *const DependencyInfo,
+);
+pub type PFN_vkCreateEvent = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    p_create_info: *const EventCreateInfo,
+    p_allocator: *const AllocationCallbacks,
+    #[cfg(target_pointer_width = "64")] p_event: *mut *mut VkEvent_T,
+    #[cfg(not(target_pointer_width = "64"))] p_event: *mut u64,
+) -> self::Result;
+pub type PFN_vkDestroyEvent = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    #[cfg(target_pointer_width = "64")] event: *mut VkEvent_T,
+    #[cfg(not(target_pointer_width = "64"))] event: u64,
+    p_allocator: *const AllocationCallbacks,
+);
+pub type PFN_vkGetEventStatus = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    #[cfg(target_pointer_width = "64")] event: *mut VkEvent_T,
+    #[cfg(not(target_pointer_width = "64"))] event: u64,
+) -> self::Result;
+pub type PFN_vkCmdSetEvent = unsafe extern "system" fn(
+    command_buffer: *mut VkCommandBuffer_T,
+    #[cfg(target_pointer_width = "64")] event: *mut VkEvent_T,
+    #[cfg(not(target_pointer_width = "64"))] event: u64,
+    stage_mask: PipelineStageFlags,
+);
+pub type PFN_vkCmdResetEvent = PFN_vkCmdSetEvent;
+pub type PFN_vkCmdWaitEvents = unsafe extern "system" fn(
+    command_buffer: *mut VkCommandBuffer_T,
+    event_count: u32,
+    #[cfg(target_pointer_width = "64")] p_events: *const *mut VkEvent_T,
+    #[cfg(not(target_pointer_width = "64"))] p_events: *const u64,
+    src_stage_mask: PipelineStageFlags,
+    dst_stage_mask: PipelineStageFlags,
+    memory_barrier_count: u32,
+    p_memory_barriers: *const MemoryBarrier,
+    buffer_memory_barrier_count: u32,
+    p_buffer_memory_barriers: *const BufferMemoryBarrier,
+    image_memory_barrier_count: u32,
+    p_image_memory_barriers: *const ImageMemoryBarrier,
+);
+pub type PFN_vkCmdSetEvent2 = unsafe extern "system" fn(
+    command_buffer: *mut VkCommandBuffer_T,
+    #[cfg(target_pointer_width = "64")] event: *mut VkEvent_T,
+    #[cfg(not(target_pointer_width = "64"))] event: u64,
+    p_dependency_info: *const DependencyInfo,
+);
+pub type PFN_vkCmdResetEvent2 = unsafe extern "system" fn(
+    command_buffer: *mut VkCommandBuffer_T,
+    #[cfg(target_pointer_width = "64")] event: *mut VkEvent_T,
+    #[cfg(not(target_pointer_width = "64"))] event: u64,
+    stage_mask: PipelineStageFlags2,
+);
+pub type PFN_vkCmdWaitEvents2 = unsafe extern "system" fn(
+    command_buffer: *mut VkCommandBuffer_T,
+    event_count: u32,
+    #[cfg(target_pointer_width = "64")] p_events: *const *mut VkEvent_T,
+    #[cfg(not(target_pointer_width = "64"))] p_events: *const u64,
+    p_dependency_infos: *const DependencyInfo,
+);
+pub type PFN_vkCreateQueryPool = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    p_create_info: *const QueryPoolCreateInfo,
+    p_allocator: *const AllocationCallbacks,
+    #[cfg(target_pointer_width = "64")] p_query_pool: *mut *mut VkQueryPool_T,
+    #[cfg(not(target_pointer_width = "64"))] p_query_pool: *mut u64,
+) -> self::Result;
+pub type PFN_vkDestroyQueryPool = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    #[cfg(target_pointer_width = "64")] query_pool: *mut VkQueryPool_T,
+    #[cfg(not(target_pointer_width = "64"))] query_pool: u64,
+    p_allocator: *const AllocationCallbacks,
+);
+pub type PFN_vkGetQueryPoolResults = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    #[cfg(target_pointer_width = "64")] query_pool: *mut VkQueryPool_T,
+    #[cfg(not(target_pointer_width = "64"))] query_pool: u64,
+    first_query: u32,
+    query_count: u32,
+    data_size: usize,
+    p_data: *mut c_void,
+    stride: DeviceSize,
+    flags: QueryResultFlags,
+) -> self::Result;
+pub type PFN_vkCmdResetQueryPool = unsafe extern "system" fn(
+    command_buffer: *mut VkCommandBuffer_T,
+    #[cfg(target_pointer_width = "64")] query_pool: *mut VkQueryPool_T,
+    #[cfg(not(target_pointer_width = "64"))] query_pool: u64,
+    first_query: u32,
+    query_count: u32,
+);
+pub type PFN_vkCmdWriteTimestamp = unsafe extern "system" fn(
+    command_buffer: *mut VkCommandBuffer_T,
+    pipeline_stage: PipelineStageFlags,
+    #[cfg(target_pointer_width = "64")] query_pool: *mut VkQueryPool_T,
+    #[cfg(not(target_pointer_width = "64"))] query_pool: u64,
+    query: u32,
+);
+pub type PFN_vkCmdWriteTimestamp2 = unsafe extern "system" fn(
+    command_buffer: *mut VkCommandBuffer_T,
+    stage: PipelineStageFlags2,
+    #[cfg(target_pointer_width = "64")] query_pool: *mut VkQueryPool_T,
+    #[cfg(not(target_pointer_width = "64"))] query_pool: u64,
+    query: u32,
+);
+pub type PFN_vkQueueSubmit2 = unsafe extern "system" fn(
+    queue: *mut VkQueue_T,
+    submit_count: u32,
+    p_submits: *const SubmitInfo2,
+    #[cfg(target_pointer_width = "64")] fence: *mut VkFence_T,
+    #[cfg(not(target_pointer_width = "64"))] fence: u64,
+) -> self::Result;
+pub type PFN_vkCmdPipelineBarrier = unsafe extern "system" fn(
+    command_buffer: *mut VkCommandBuffer_T,
+    src_stage_mask: PipelineStageFlags,
+    dst_stage_mask: PipelineStageFlags,
+    dependency_flags: DependencyFlags,
+    memory_barrier_count: u32,
+    p_memory_barriers: *const MemoryBarrier,
+    buffer_memory_barrier_count: u32,
+    p_buffer_memory_barriers: *const BufferMemoryBarrier,
+    image_memory_barrier_count: u32,
+    p_image_memory_barriers: *const ImageMemoryBarrier,
+);
+pub type PFN_vkCmdDispatch = unsafe extern "system" fn(
+    command_buffer: *mut VkCommandBuffer_T,
+    group_count_x: u32,
+    group_count_y: u32,
+    group_count_z: u32,
+);
+pub type PFN_vkCmdDispatchIndirect = unsafe extern "system" fn(
+    command_buffer: *mut VkCommandBuffer_T,
+    #[cfg(target_pointer_width = "64")] buffer: *mut VkBuffer_T,
+    #[cfg(not(target_pointer_width = "64"))] buffer: u64,
+    offset: DeviceSize,
+);
+pub type PFN_vkCmdDispatchBase = unsafe extern "system" fn(
+    command_buffer: *mut VkCommandBuffer_T,
+    base_group_x: u32,
+    base_group_y: u32,
+    base_group_z: u32,
+    group_count_x: u32,
+    group_count_y: u32,
+    group_count_z: u32,
+);
+pub type PFN_vkCmdSetViewport = unsafe extern "system" fn(
+    command_buffer: *mut VkCommandBuffer_T,
+    first_viewport: u32,
+    viewport_count: u32,
+    p_viewports: *const Viewport,
+);
+pub type PFN_vkCmdSetScissor = unsafe extern "system" fn(
+    command_buffer: *mut VkCommandBuffer_T,
+    first_scissor: u32,
+    scissor_count: u32,
+    p_scissors: *const Rect2D,
+);
+pub type PFN_vkCmdSetLineWidth =
+    unsafe extern "system" fn(command_buffer: *mut VkCommandBuffer_T, line_width: f32);
+pub type PFN_vkCmdSetDepthBias = unsafe extern "system" fn(
+    command_buffer: *mut VkCommandBuffer_T,
+    depth_bias_constant_factor: f32,
+    depth_bias_clamp: f32,
+    depth_bias_slope_factor: f32,
+);
+pub type PFN_vkCmdSetBlendConstants =
+    unsafe extern "system" fn(command_buffer: *mut VkCommandBuffer_T, blend_constants: *const f32);
+pub type PFN_vkCmdSetDepthBounds = unsafe extern "system" fn(
+    command_buffer: *mut VkCommandBuffer_T,
+    min_depth_bounds: f32,
+    max_depth_bounds: f32,
+);
+pub type PFN_vkCmdSetStencilCompareMask = unsafe extern "system" fn(
+    command_buffer: *mut VkCommandBuffer_T,
+    face_mask: StencilFaceFlags,
+    compare_mask: u32,
+);
+pub type PFN_vkCmdSetStencilWriteMask = unsafe extern "system" fn(
+    command_buffer: *mut VkCommandBuffer_T,
+    face_mask: StencilFaceFlags,
+    write_mask: u32,
+);
+pub type PFN_vkCmdSetStencilReference = unsafe extern "system" fn(
+    command_buffer: *mut VkCommandBuffer_T,
+    face_mask: StencilFaceFlags,
+    reference: u32,
+);
+pub type PFN_vkCmdSetCullMode =
+    unsafe extern "system" fn(command_buffer: *mut VkCommandBuffer_T, cull_mode: CullModeFlags);
+pub type PFN_vkCmdSetFrontFace =
+    unsafe extern "system" fn(command_buffer: *mut VkCommandBuffer_T, front_face: FrontFace);
+pub type PFN_vkCmdSetPrimitiveTopology = unsafe extern "system" fn(
+    command_buffer: *mut VkCommandBuffer_T,
+    primitive_topology: PrimitiveTopology,
+);
+pub type PFN_vkCmdSetDepthTestEnable =
+    unsafe extern "system" fn(command_buffer: *mut VkCommandBuffer_T, depth_test_enable: Bool32);
+pub type PFN_vkCmdSetDepthWriteEnable =
+    unsafe extern "system" fn(command_buffer: *mut VkCommandBuffer_T, depth_write_enable: Bool32);
+pub type PFN_vkCreateGraphicsPipelines = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    #[cfg(target_pointer_width = "64")] pipeline_cache: *mut VkPipelineCache_T,
+    #[cfg(not(target_pointer_width = "64"))] pipeline_cache: u64,
+    create_info_count: u32,
+    p_create_infos: *const GraphicsPipelineCreateInfo,
+    p_allocator: *const AllocationCallbacks,
+    #[cfg(target_pointer_width = "64")] p_pipelines: *mut *mut VkPipeline_T,
+    #[cfg(not(target_pointer_width = "64"))] p_pipelines: *mut u64,
+) -> self::Result;
+pub type PFN_vkCreateRenderPass = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    p_create_info: *const RenderPassCreateInfo,
+    p_allocator: *const AllocationCallbacks,
+    #[cfg(target_pointer_width = "64")] p_render_pass: *mut *mut VkRenderPass_T,
+    #[cfg(not(target_pointer_width = "64"))] p_render_pass: *mut u64,
+) -> self::Result;
+pub type PFN_vkDestroyRenderPass = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    #[cfg(target_pointer_width = "64")] render_pass: *mut VkRenderPass_T,
+    #[cfg(not(target_pointer_width = "64"))] render_pass: u64,
+    p_allocator: *const AllocationCallbacks,
+);
+pub type PFN_vkCmdBeginRendering = unsafe extern "system" fn(
+    command_buffer: *mut VkCommandBuffer_T,
+    p_rendering_info: *const RenderingInfo,
+);
+pub type PFN_vkCmdEndRendering = unsafe extern "system" fn(command_buffer: *mut VkCommandBuffer_T);
+pub type PFN_vkCreateSampler = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    p_create_info: *const SamplerCreateInfo,
+    p_allocator: *const AllocationCallbacks,
+    #[cfg(target_pointer_width = "64")] p_sampler: *mut *mut VkSampler_T,
+    #[cfg(not(target_pointer_width = "64"))] p_sampler: *mut u64,
+) -> self::Result;
+pub type PFN_vkDestroySampler = unsafe extern "system" fn(
+    device: *mut VkDevice_T,
+    #[cfg(target_pointer_width = "64")] sampler: *mut VkSampler_T,
+    #[cfg(not(target_pointer_width = "64"))] sampler: u64,
+    p_allocator: *const AllocationCallbacks,
+);
 
 type InstanceCreateFlags = Flags;
 type DeviceQueueCreateFlags = Flags;
@@ -446,172 +1199,173 @@ pub struct InstanceCreateInfo {
 }
 
 #[repr(C)]
+#[derive(Default)]
 pub struct PhysicalDeviceFeatures {
-    robust_buffer_access: Bool32,
-    full_draw_index_uint32: Bool32,
-    image_cube_array: Bool32,
-    independent_blend: Bool32,
-    geometry_shader: Bool32,
-    tessellation_shader: Bool32,
-    sample_rate_shading: Bool32,
-    dual_src_blend: Bool32,
-    logic_op: Bool32,
-    multi_draw_indirect: Bool32,
-    draw_indirect_first_instance: Bool32,
-    depth_clamp: Bool32,
-    depth_bias_clamp: Bool32,
-    fill_mode_non_solid: Bool32,
-    depth_bounds: Bool32,
-    wide_lines: Bool32,
-    large_points: Bool32,
-    alpha_to_one: Bool32,
-    multi_viewport: Bool32,
-    sampler_anisotropy: Bool32,
-    texture_compression_etc2: Bool32,
-    texture_compression_astc_ldr: Bool32,
-    texture_compression_bc: Bool32,
-    occlusion_query_precise: Bool32,
-    pipeline_statistics_query: Bool32,
-    vertex_pipeline_stores_and_atomics: Bool32,
-    fragment_stores_and_atomics: Bool32,
-    shader_tessellation_and_geometry_point_size: Bool32,
-    shader_image_gather_extended: Bool32,
-    shader_storage_image_extended_formats: Bool32,
-    shader_storage_image_multisample: Bool32,
-    shader_storage_image_read_without_format: Bool32,
-    shader_storage_image_write_without_format: Bool32,
-    shader_uniform_buffer_array_dynamic_indexing: Bool32,
-    shader_sampled_image_array_dynamic_indexing: Bool32,
-    shader_storage_buffer_array_dynamic_indexing: Bool32,
-    shader_storage_image_array_dynamic_indexing: Bool32,
-    shader_clip_distance: Bool32,
-    shader_cull_distance: Bool32,
-    shader_float64: Bool32,
-    shader_int64: Bool32,
-    shader_int16: Bool32,
-    shader_resource_residency: Bool32,
-    shader_resource_min_lod: Bool32,
-    sparse_binding: Bool32,
-    sparse_residency_buffer: Bool32,
-    sparse_residency_image_2d: Bool32,
-    sparse_residency_image_3d: Bool32,
-    sparse_residency2_samples: Bool32,
-    sparse_residency4_samples: Bool32,
-    sparse_residency8_samples: Bool32,
-    sparse_residency16_samples: Bool32,
-    sparse_residency_aliased: Bool32,
-    variable_multisample_rate: Bool32,
-    inherited_queries: Bool32,
+    pub robust_buffer_access: Bool32,
+    pub full_draw_index_uint32: Bool32,
+    pub image_cube_array: Bool32,
+    pub independent_blend: Bool32,
+    pub geometry_shader: Bool32,
+    pub tessellation_shader: Bool32,
+    pub sample_rate_shading: Bool32,
+    pub dual_src_blend: Bool32,
+    pub logic_op: Bool32,
+    pub multi_draw_indirect: Bool32,
+    pub draw_indirect_first_instance: Bool32,
+    pub depth_clamp: Bool32,
+    pub depth_bias_clamp: Bool32,
+    pub fill_mode_non_solid: Bool32,
+    pub depth_bounds: Bool32,
+    pub wide_lines: Bool32,
+    pub large_points: Bool32,
+    pub alpha_to_one: Bool32,
+    pub multi_viewport: Bool32,
+    pub sampler_anisotropy: Bool32,
+    pub texture_compression_etc2: Bool32,
+    pub texture_compression_astc_ldr: Bool32,
+    pub texture_compression_bc: Bool32,
+    pub occlusion_query_precise: Bool32,
+    pub pipeline_statistics_query: Bool32,
+    pub vertex_pipeline_stores_and_atomics: Bool32,
+    pub fragment_stores_and_atomics: Bool32,
+    pub shader_tessellation_and_geometry_point_size: Bool32,
+    pub shader_image_gather_extended: Bool32,
+    pub shader_storage_image_extended_formats: Bool32,
+    pub shader_storage_image_multisample: Bool32,
+    pub shader_storage_image_read_without_format: Bool32,
+    pub shader_storage_image_write_without_format: Bool32,
+    pub shader_uniform_buffer_array_dynamic_indexing: Bool32,
+    pub shader_sampled_image_array_dynamic_indexing: Bool32,
+    pub shader_storage_buffer_array_dynamic_indexing: Bool32,
+    pub shader_storage_image_array_dynamic_indexing: Bool32,
+    pub shader_clip_distance: Bool32,
+    pub shader_cull_distance: Bool32,
+    pub shader_float64: Bool32,
+    pub shader_int64: Bool32,
+    pub shader_int16: Bool32,
+    pub shader_resource_residency: Bool32,
+    pub shader_resource_min_lod: Bool32,
+    pub sparse_binding: Bool32,
+    pub sparse_residency_buffer: Bool32,
+    pub sparse_residency_image_2d: Bool32,
+    pub sparse_residency_image_3d: Bool32,
+    pub sparse_residency2_samples: Bool32,
+    pub sparse_residency4_samples: Bool32,
+    pub sparse_residency8_samples: Bool32,
+    pub sparse_residency16_samples: Bool32,
+    pub sparse_residency_aliased: Bool32,
+    pub variable_multisample_rate: Bool32,
+    pub inherited_queries: Bool32,
 }
 
 #[repr(C)]
 pub struct PhysicalDeviceLimits {
-    max_image_dimension_1d: u32,
-    max_image_dimension_2d: u32,
-    max_image_dimension_3d: u32,
-    max_image_dimension_cube: u32,
-    max_image_array_layers: u32,
-    max_texel_buffer_elements: u32,
-    max_uniform_buffer_range: u32,
-    max_storage_buffer_range: u32,
-    max_push_constants_size: u32,
-    max_memory_allocation_count: u32,
-    max_sampler_allocation_count: u32,
-    buffer_image_granularity: DeviceSize,
-    sparse_address_space_size: DeviceSize,
-    max_bound_descriptor_sets: u32,
-    max_per_stage_descriptor_samplers: u32,
-    max_per_stage_descriptor_uniform_buffers: u32,
-    max_per_stage_descriptor_storage_buffers: u32,
-    max_per_stage_descriptor_sampled_images: u32,
-    max_per_stage_descriptor_storage_images: u32,
-    max_per_stage_descriptor_input_attachments: u32,
-    max_per_stage_resources: u32,
-    max_descriptor_set_samplers: u32,
-    max_descriptor_set_uniform_buffers: u32,
-    max_descriptor_set_uniform_buffers_dynamic: u32,
-    max_descriptor_set_storage_buffers: u32,
-    max_descriptor_set_storage_buffers_dynamic: u32,
-    max_descriptor_set_sampled_images: u32,
-    max_descriptor_set_storage_images: u32,
-    max_descriptor_set_input_attachments: u32,
-    max_vertex_input_attributes: u32,
-    max_vertex_input_bindings: u32,
-    max_vertex_input_attribute_offset: u32,
-    max_vertex_input_binding_stride: u32,
-    max_vertex_output_components: u32,
-    max_tessellation_generation_level: u32,
-    max_tessellation_patch_size: u32,
-    max_tessellation_control_per_vertex_input_components: u32,
-    max_tessellation_control_per_vertex_output_components: u32,
-    max_tessellation_control_per_patch_output_components: u32,
-    max_tessellation_control_total_output_components: u32,
-    max_tessellation_evaluation_input_components: u32,
-    max_tessellation_evaluation_output_components: u32,
-    max_geometry_shader_invocations: u32,
-    max_geometry_input_components: u32,
-    max_geometry_output_components: u32,
-    max_geometry_output_vertices: u32,
-    max_geometry_total_output_components: u32,
-    max_fragment_input_components: u32,
-    max_fragment_output_attachments: u32,
-    max_fragment_dual_src_attachments: u32,
-    max_fragment_combined_output_resources: u32,
-    max_compute_shared_memory_size: u32,
-    max_compute_work_group_count: [u32; 3],
-    max_compute_work_group_invocations: u32,
-    max_compute_work_group_size: [u32; 3],
-    sub_pixel_precision_bits: u32,
-    sub_texel_precision_bits: u32,
-    mipmap_precision_bits: u32,
-    max_draw_indexed_index_value: u32,
-    max_draw_indirect_count: u32,
-    max_sampler_lod_bias: f32,
-    max_sampler_anisotropy: f32,
-    max_viewports: u32,
-    max_viewport_dimensions: [u32; 2],
-    viewport_bounds_range: [f32; 2],
-    viewport_sub_pixel_bits: u32,
-    min_memory_map_alignment: usize,
-    min_texel_buffer_offset_alignment: DeviceSize,
-    min_uniform_buffer_offset_alignment: DeviceSize,
-    min_storage_buffer_offset_alignment: DeviceSize,
-    min_texel_offset: i32,
-    max_texel_offset: u32,
-    min_texel_gather_offset: i32,
-    max_texel_gather_offset: u32,
-    min_interpolation_offset: f32,
-    max_interpolation_offset: f32,
-    sub_pixel_interpolation_offset_bits: u32,
-    max_framebuffer_width: u32,
-    max_framebuffer_height: u32,
-    max_framebuffer_layers: u32,
-    framebuffer_color_sample_counts: SampleCountFlags,
-    framebuffer_depth_sample_counts: SampleCountFlags,
-    framebuffer_stencil_sample_counts: SampleCountFlags,
-    framebuffer_no_attachments_sample_counts: SampleCountFlags,
-    max_color_attachments: u32,
-    sampled_image_color_sample_counts: SampleCountFlags,
-    sampled_image_integer_sample_counts: SampleCountFlags,
-    sampled_image_depth_sample_counts: SampleCountFlags,
-    sampled_image_stencil_sample_counts: SampleCountFlags,
-    storage_image_sample_counts: SampleCountFlags,
-    max_sample_mask_words: u32,
-    timestamp_compute_and_graphics: Bool32,
-    timestamp_period: f32,
-    max_clip_distances: u32,
-    max_cull_distances: u32,
-    max_combined_clip_and_cull_distances: u32,
-    discrete_queue_priorities: u32,
-    point_size_range: [f32; 2],
-    line_width_range: [f32; 2],
-    point_size_granularity: f32,
-    line_width_granularity: f32,
-    strict_lines: Bool32,
-    standard_sample_locations: Bool32,
-    optimal_buffer_copy_offset_alignment: DeviceSize,
-    optimal_buffer_copy_row_pitch_alignment: DeviceSize,
-    non_coherent_atom_size: DeviceSize,
+    pub max_image_dimension_1d: u32,
+    pub max_image_dimension_2d: u32,
+    pub max_image_dimension_3d: u32,
+    pub max_image_dimension_cube: u32,
+    pub max_image_array_layers: u32,
+    pub max_texel_buffer_elements: u32,
+    pub max_uniform_buffer_range: u32,
+    pub max_storage_buffer_range: u32,
+    pub max_push_constants_size: u32,
+    pub max_memory_allocation_count: u32,
+    pub max_sampler_allocation_count: u32,
+    pub buffer_image_granularity: DeviceSize,
+    pub sparse_address_space_size: DeviceSize,
+    pub max_bound_descriptor_sets: u32,
+    pub max_per_stage_descriptor_samplers: u32,
+    pub max_per_stage_descriptor_uniform_buffers: u32,
+    pub max_per_stage_descriptor_storage_buffers: u32,
+    pub max_per_stage_descriptor_sampled_images: u32,
+    pub max_per_stage_descriptor_storage_images: u32,
+    pub max_per_stage_descriptor_input_attachments: u32,
+    pub max_per_stage_resources: u32,
+    pub max_descriptor_set_samplers: u32,
+    pub max_descriptor_set_uniform_buffers: u32,
+    pub max_descriptor_set_uniform_buffers_dynamic: u32,
+    pub max_descriptor_set_storage_buffers: u32,
+    pub max_descriptor_set_storage_buffers_dynamic: u32,
+    pub max_descriptor_set_sampled_images: u32,
+    pub max_descriptor_set_storage_images: u32,
+    pub max_descriptor_set_input_attachments: u32,
+    pub max_vertex_input_attributes: u32,
+    pub max_vertex_input_bindings: u32,
+    pub max_vertex_input_attribute_offset: u32,
+    pub max_vertex_input_binding_stride: u32,
+    pub max_vertex_output_components: u32,
+    pub max_tessellation_generation_level: u32,
+    pub max_tessellation_patch_size: u32,
+    pub max_tessellation_control_per_vertex_input_components: u32,
+    pub max_tessellation_control_per_vertex_output_components: u32,
+    pub max_tessellation_control_per_patch_output_components: u32,
+    pub max_tessellation_control_total_output_components: u32,
+    pub max_tessellation_evaluation_input_components: u32,
+    pub max_tessellation_evaluation_output_components: u32,
+    pub max_geometry_shader_invocations: u32,
+    pub max_geometry_input_components: u32,
+    pub max_geometry_output_components: u32,
+    pub max_geometry_output_vertices: u32,
+    pub max_geometry_total_output_components: u32,
+    pub max_fragment_input_components: u32,
+    pub max_fragment_output_attachments: u32,
+    pub max_fragment_dual_src_attachments: u32,
+    pub max_fragment_combined_output_resources: u32,
+    pub max_compute_shared_memory_size: u32,
+    pub max_compute_work_group_count: [u32; 3],
+    pub max_compute_work_group_invocations: u32,
+    pub max_compute_work_group_size: [u32; 3],
+    pub sub_pixel_precision_bits: u32,
+    pub sub_texel_precision_bits: u32,
+    pub mipmap_precision_bits: u32,
+    pub max_draw_indexed_index_value: u32,
+    pub max_draw_indirect_count: u32,
+    pub max_sampler_lod_bias: f32,
+    pub max_sampler_anisotropy: f32,
+    pub max_viewports: u32,
+    pub max_viewport_dimensions: [u32; 2],
+    pub viewport_bounds_range: [f32; 2],
+    pub viewport_sub_pixel_bits: u32,
+    pub min_memory_map_alignment: usize,
+    pub min_texel_buffer_offset_alignment: DeviceSize,
+    pub min_uniform_buffer_offset_alignment: DeviceSize,
+    pub min_storage_buffer_offset_alignment: DeviceSize,
+    pub min_texel_offset: i32,
+    pub max_texel_offset: u32,
+    pub min_texel_gather_offset: i32,
+    pub max_texel_gather_offset: u32,
+    pub min_interpolation_offset: f32,
+    pub max_interpolation_offset: f32,
+    pub sub_pixel_interpolation_offset_bits: u32,
+    pub max_framebuffer_width: u32,
+    pub max_framebuffer_height: u32,
+    pub max_framebuffer_layers: u32,
+    pub framebuffer_color_sample_counts: SampleCountFlags,
+    pub framebuffer_depth_sample_counts: SampleCountFlags,
+    pub framebuffer_stencil_sample_counts: SampleCountFlags,
+    pub framebuffer_no_attachments_sample_counts: SampleCountFlags,
+    pub max_color_attachments: u32,
+    pub sampled_image_color_sample_counts: SampleCountFlags,
+    pub sampled_image_integer_sample_counts: SampleCountFlags,
+    pub sampled_image_depth_sample_counts: SampleCountFlags,
+    pub sampled_image_stencil_sample_counts: SampleCountFlags,
+    pub storage_image_sample_counts: SampleCountFlags,
+    pub max_sample_mask_words: u32,
+    pub timestamp_compute_and_graphics: Bool32,
+    pub timestamp_period: f32,
+    pub max_clip_distances: u32,
+    pub max_cull_distances: u32,
+    pub max_combined_clip_and_cull_distances: u32,
+    pub discrete_queue_priorities: u32,
+    pub point_size_range: [f32; 2],
+    pub line_width_range: [f32; 2],
+    pub point_size_granularity: f32,
+    pub line_width_granularity: f32,
+    pub strict_lines: Bool32,
+    pub standard_sample_locations: Bool32,
+    pub optimal_buffer_copy_offset_alignment: DeviceSize,
+    pub optimal_buffer_copy_row_pitch_alignment: DeviceSize,
+    pub non_coherent_atom_size: DeviceSize,
 }
 
 #[repr(C)]
@@ -623,17 +1377,45 @@ pub struct PhysicalDeviceSparseProperties {
     residency_non_resident_strict: Bool32,
 }
 
+#[repr(C)]
+pub struct ExtensionProperties {
+    pub extension_name: [i8; 256],
+    pub spec_version: u32,
+}
+
 #[repr(C)]
 pub struct PhysicalDeviceProperties {
     pub api_version: u32,
-    driver_version: u32,
-    vendor_id: u32,
-    device_id: u32,
+    pub driver_version: u32,
+    pub vendor_id: u32,
+    pub device_id: u32,
     pub device_type: PhysicalDeviceType,
     pub device_name: [i8; 256],
-    pipeline_cache_uuid: [u8; 16],
-    limits: PhysicalDeviceLimits,
-    sparse_properties: PhysicalDeviceSparseProperties,
+    pub pipeline_cache_uuid: [u8; 16],
+    pub limits: PhysicalDeviceLimits,
+    pub sparse_properties: PhysicalDeviceSparseProperties,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct MemoryType {
+    pub property_flags: MemoryPropertyFlags,
+    pub heap_index: u32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct MemoryHeap {
+    pub size: DeviceSize,
+    pub flags: MemoryHeapFlags,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceMemoryProperties {
+    pub memory_type_count: u32,
+    pub memory_types: [MemoryType; 32],
+    pub memory_heap_count: u32,
+    pub memory_heaps: [MemoryHeap; 16],
 }
 
 #[repr(C)]
@@ -688,6 +1470,32 @@ pub struct CommandBufferAllocateInfo {
     pub command_buffer_count: u32,
 }
 
+#[repr(C)]
+pub struct CommandBufferInheritanceInfo {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    #[cfg(target_pointer_width = "64")]
+    pub render_pass: *mut VkRenderPass_T,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub render_pass: u64,
+    pub subpass: u32,
+    #[cfg(target_pointer_width = "64")]
+    pub framebuffer: *mut VkFramebuffer_T,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub framebuffer: u64,
+    pub occlusion_query_enable: Bool32,
+    pub query_flags: Flags,
+    pub pipeline_statistics: Flags,
+}
+
+#[repr(C)]
+pub struct CommandBufferBeginInfo {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub flags: CommandBufferUsageFlags,
+    pub p_inheritance_info: *const CommandBufferInheritanceInfo,
+}
+
 #[repr(C)]
 pub struct SemaphoreCreateInfo {
     pub s_type: StructureType,
@@ -1364,10 +2172,92 @@ pub enum SampleCountFlagBits {
     ThirtyTwoBit = 0x00000020,
     SixtyFourBit = 0x00000040,
 }
-type SampleCountFlags = Flags;
+pub type SampleCountFlags = Flags;
 
 #[repr(i32)]
-pub enum ImageUsageFlagBits {
+pub enum ImageType {
+    OneD = 0,
+    TwoD = 1,
+    ThreeD = 2,
+}
+
+pub type SamplerCreateFlags = Flags;
+
+#[repr(i32)]
+pub enum Filter {
+    Nearest = 0,
+    Linear = 1,
+}
+
+#[repr(i32)]
+pub enum SamplerMipmapMode {
+    Nearest = 0,
+    Linear = 1,
+}
+
+#[repr(i32)]
+pub enum SamplerAddressMode {
+    Repeat = 0,
+    MirroredRepeat = 1,
+    ClampToEdge = 2,
+    ClampToBorder = 3,
+    MirrorClampToEdge = 4,
+}
+
+#[repr(i32)]
+pub enum BorderColor {
+    FloatTransparentBlack = 0,
+    IntTransparentBlack = 1,
+    FloatOpaqueBlack = 2,
+    IntOpaqueBlack = 3,
+    FloatOpaqueWhite = 4,
+    IntOpaqueWhite = 5,
+}
+
+#[repr(C)]
+pub struct SamplerCreateInfo {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub flags: SamplerCreateFlags,
+    pub mag_filter: Filter,
+    pub min_filter: Filter,
+    pub mipmap_mode: SamplerMipmapMode,
+    pub address_mode_u: SamplerAddressMode,
+    pub address_mode_v: SamplerAddressMode,
+    pub address_mode_w: SamplerAddressMode,
+    pub mip_lod_bias: f32,
+    pub anisotropy_enable: Bool32,
+    pub max_anisotropy: f32,
+    pub compare_enable: Bool32,
+    pub compare_op: CompareOp,
+    pub min_lod: f32,
+    pub max_lod: f32,
+    pub border_color: BorderColor,
+    pub unnormalized_coordinates: Bool32,
+}
+
+#[repr(i32)]
+pub enum ImageTiling {
+    Optimal = 0,
+    Linear = 1,
+}
+
+#[repr(i32)]
+pub enum ImageCreateFlagBits {
+    SparseBindingBit = 0x00000001,
+    SparseResidencyBit = 0x00000002,
+    SparseAliasedBit = 0x00000004,
+    MutableFormatBit = 0x00000008,
+    CubeCompatibleBit = 0x00000010,
+    TwoDArrayCompatibleBit = 0x00000020,
+    BlockTexelViewCompatibleBit = 0x00000080,
+    ExtendedUsageBit = 0x00000100,
+    ProtectedBit = 0x00000800,
+}
+pub type ImageCreateFlags = Flags;
+
+#[repr(i32)]
+pub enum ImageUsageFlagBits {
     TransferSrcBit = 0x00000001,
     TransferDstBit = 0x00000002,
     SampledBit = 0x00000004,
@@ -1381,6 +2271,50 @@ pub enum ImageUsageFlagBits {
 }
 pub type ImageUsageFlags = Flags;
 
+#[repr(i32)]
+pub enum BufferCreateFlagBits {
+    SparseBindingBit = 0x00000001,
+    SparseResidencyBit = 0x00000002,
+    SparseAliasedBit = 0x00000004,
+    ProtectedBit = 0x00000008,
+    DeviceAddressCaptureReplayBit = 0x00000010,
+}
+pub type BufferCreateFlags = Flags;
+
+#[repr(i32)]
+pub enum BufferUsageFlagBits {
+    TransferSrcBit = 0x00000001,
+    TransferDstBit = 0x00000002,
+    UniformTexelBufferBit = 0x00000004,
+    StorageTexelBufferBit = 0x00000008,
+    UniformBufferBit = 0x00000010,
+    StorageBufferBit = 0x00000020,
+    IndexBufferBit = 0x00000040,
+    VertexBufferBit = 0x00000080,
+    IndirectBufferBit = 0x00000100,
+    ShaderDeviceAddressBit = 0x00020000,
+}
+pub type BufferUsageFlags = Flags;
+
+#[repr(i32)]
+pub enum MemoryPropertyFlagBits {
+    DeviceLocalBit = 0x00000001,
+    HostVisibleBit = 0x00000002,
+    HostCoherentBit = 0x00000004,
+    HostCachedBit = 0x00000008,
+    LazilyAllocatedBit = 0x00000010,
+    ProtectedBit = 0x00000020,
+}
+pub type MemoryPropertyFlags = Flags;
+
+#[repr(i32)]
+pub enum MemoryHeapFlagBits {
+    DeviceLocalBit = 0x00000001,
+    MultiInstanceBit = 0x00000002,
+}
+pub type MemoryHeapFlags = Flags;
+pub type MemoryMapFlags = Flags;
+
 #[repr(i32)]
 pub enum QueueFlagBits {
     GraphicsBit = 0x00000001,
@@ -1406,7 +2340,7 @@ pub enum ColorComponentFlagBits {
     Blue = 0x00000004,
     Alpha = 0x00000008,
 }
-type ColorComponentFlags = Flags;
+pub type ColorComponentFlags = Flags;
 
 #[repr(i32)]
 pub enum PipeLineCreateFlagBits {
@@ -1468,18 +2402,54 @@ pub enum CullModeFlagBits {
     BackBit = 0x00000002,
     FrontAndBack = 0x00000003,
 }
-type CullModeFlags = Flags;
-type PipelineVertexInputStateCreateFlags = Flags;
-type PipelineInputAssemblyStateCreateFlags = Flags;
-type PipelineTessellationStateCreateFlags = Flags;
-type PipelineViewportStateCreateFlags = Flags;
-type PipelineRasterizationStateCreateFlags = Flags;
-type PipelineMultisampleStateCreateFlags = Flags;
-type PipelineDepthStencilStateCreateFlags = Flags;
-type PipelineColorBlendStateCreateFlags = Flags;
-type PipelineDynamicStateCreateFlags = Flags;
+pub type CullModeFlags = Flags;
+pub type PipelineVertexInputStateCreateFlags = Flags;
+pub type PipelineInputAssemblyStateCreateFlags = Flags;
+pub type PipelineTessellationStateCreateFlags = Flags;
+pub type PipelineViewportStateCreateFlags = Flags;
+pub type PipelineRasterizationStateCreateFlags = Flags;
+pub type PipelineMultisampleStateCreateFlags = Flags;
+pub type PipelineDepthStencilStateCreateFlags = Flags;
+pub type PipelineColorBlendStateCreateFlags = Flags;
+pub type PipelineDynamicStateCreateFlags = Flags;
 type PipelineLayoutCreateFlags = Flags;
-type ShaderStageFlags = Flags;
+pub type ShaderStageFlags = Flags;
+
+#[repr(i32)]
+pub enum DescriptorType {
+    Sampler = 0,
+    CombinedImageSampler = 1,
+    SampledImage = 2,
+    StorageImage = 3,
+    UniformTexelBuffer = 4,
+    StorageTexelBuffer = 5,
+    UniformBuffer = 6,
+    StorageBuffer = 7,
+    UniformBufferDynamic = 8,
+    StorageBufferDynamic = 9,
+    InputAttachment = 10,
+    InlineUniformBlockExt = 1000138000,
+    AccelerationStructureKhr = 1000150000,
+    AccelerationStructureNv = 1000165000,
+    MutableValve = 1000351000,
+}
+
+#[repr(i32)]
+pub enum DescriptorSetLayoutCreateFlagBits {
+    PushDescriptorBitKhr = 0x00000001,
+    UpdateAfterBindPoolBit = 0x00000002,
+    HostOnlyPoolBitValve = 0x00000004,
+}
+pub type DescriptorSetLayoutCreateFlags = Flags;
+
+#[repr(i32)]
+pub enum DescriptorBindingFlagBits {
+    UpdateAfterBindBit = 0x00000001,
+    UpdateUnusedWhilePendingBit = 0x00000002,
+    PartiallyBoundBit = 0x00000004,
+    VariableDescriptorCountBit = 0x00000008,
+}
+pub type DescriptorBindingFlags = Flags;
 
 #[repr(C)]
 pub struct SurfaceCapabilitiesKhr {
@@ -1555,6 +2525,44 @@ pub struct ComponentMapping {
     pub a: ComponentSwizzle,
 }
 
+#[repr(C)]
+pub struct ImageCreateInfo {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub flags: ImageCreateFlags,
+    pub image_type: ImageType,
+    pub format: Format,
+    pub extent: Extent3D,
+    pub mip_levels: u32,
+    pub array_layers: u32,
+    pub samples: SampleCountFlagBits,
+    pub tiling: ImageTiling,
+    pub usage: ImageUsageFlags,
+    pub sharing_mode: SharingMode,
+    pub queue_family_index_count: u32,
+    pub p_queue_family_indices: *const u32,
+    pub initial_layout: ImageLayout,
+}
+
+#[repr(C)]
+pub struct ImageFormatProperties {
+    pub max_extent: Extent3D,
+    pub max_mip_levels: u32,
+    pub max_array_layers: u32,
+    pub sample_counts: SampleCountFlags,
+    pub max_resource_size: DeviceSize,
+}
+
+#[repr(C)]
+pub struct ImageMemoryRequirementsInfo2 {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    #[cfg(target_pointer_width = "64")]
+    pub image: *mut VkImage_T,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub image: u64,
+}
+
 #[repr(C)]
 pub struct ImageViewCreateInfo {
     pub s_type: StructureType,
@@ -1570,6 +2578,84 @@ pub struct ImageViewCreateInfo {
     pub subresource_range: ImageSubresourceRange,
 }
 
+#[repr(C)]
+pub struct BufferCreateInfo {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub flags: BufferCreateFlags,
+    pub size: DeviceSize,
+    pub usage: BufferUsageFlags,
+    pub sharing_mode: SharingMode,
+    pub queue_family_index_count: u32,
+    pub p_queue_family_indices: *const u32,
+}
+
+#[repr(C)]
+pub struct MemoryAllocateInfo {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub allocation_size: DeviceSize,
+    pub memory_type_index: u32,
+}
+
+#[repr(C)]
+pub struct MemoryDedicatedAllocateInfo {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    #[cfg(target_pointer_width = "64")]
+    pub image: *mut VkImage_T,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub image: u64,
+    #[cfg(target_pointer_width = "64")]
+    pub buffer: *mut VkBuffer_T,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub buffer: u64,
+}
+
+#[repr(C)]
+pub struct MappedMemoryRange {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    #[cfg(target_pointer_width = "64")]
+    pub memory: *mut VkDeviceMemory_T,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub memory: u64,
+    pub offset: DeviceSize,
+    pub size: DeviceSize,
+}
+
+#[repr(C)]
+pub struct MemoryRequirements {
+    pub size: DeviceSize,
+    pub alignment: DeviceSize,
+    pub memory_type_bits: u32,
+}
+
+#[repr(C)]
+pub struct BufferMemoryRequirementsInfo2 {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    #[cfg(target_pointer_width = "64")]
+    pub buffer: *mut VkBuffer_T,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub buffer: u64,
+}
+
+#[repr(C)]
+pub struct MemoryRequirements2 {
+    pub s_type: StructureType,
+    pub p_next: *mut c_void,
+    pub memory_requirements: MemoryRequirements,
+}
+
+#[repr(C)]
+pub struct MemoryDedicatedRequirements {
+    pub s_type: StructureType,
+    pub p_next: *mut c_void,
+    pub prefers_dedicated_allocation: Bool32,
+    pub requires_dedicated_allocation: Bool32,
+}
+
 #[repr(C)]
 pub struct ShaderModuleCreateInfo {
     pub s_type: StructureType,
@@ -1580,221 +2666,1003 @@ pub struct ShaderModuleCreateInfo {
 }
 
 #[repr(C)]
+#[derive(Clone, Copy)]
 pub struct SpecializationMapEntry {
-    constant_id: u32,
-    offset: u32,
-    size: usize,
+    pub constant_id: u32,
+    pub offset: u32,
+    pub size: usize,
 }
 
 #[repr(C)]
 pub struct SpecializationInfo {
-    map_entry_count: u32,
-    p_map_entries: *const SpecializationMapEntry,
-    data_size: usize,
-    p_data: *const c_void,
+    pub map_entry_count: u32,
+    pub p_map_entries: *const SpecializationMapEntry,
+    pub data_size: usize,
+    pub p_data: *const c_void,
 }
 
 #[repr(C)]
 pub struct PipelineShaderStageCreateInfo {
-    s_type: StructureType,
-    p_next: *const c_void,
-    flags: PipelineShaderStageCreateFlags,
-    stage: ShaderStageFlagBits,
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub flags: PipelineShaderStageCreateFlags,
+    pub stage: ShaderStageFlagBits,
     #[cfg(target_pointer_width = "64")]
-    module: *mut VkShaderModule_T,
+    pub module: *mut VkShaderModule_T,
     #[cfg(not(target_pointer_width = "64"))]
-    module: u64,
-    p_name: *const i8,
-    p_specialization_info: *const SpecializationInfo,
+    pub module: u64,
+    pub p_name: *const i8,
+    pub p_specialization_info: *const SpecializationInfo,
 }
 
 #[repr(C)]
 pub struct VertexInputBindingDescription {
-    binding: u32,
-    stride: u32,
-    input_rate: VertexInputRate,
+    pub binding: u32,
+    pub stride: u32,
+    pub input_rate: VertexInputRate,
 }
 
 #[repr(C)]
 pub struct VertexInputAttributeDescription {
-    location: u32,
-    binding: u32,
-    format: Format,
-    offset: u32,
+    pub location: u32,
+    pub binding: u32,
+    pub format: Format,
+    pub offset: u32,
 }
 
 #[repr(C)]
 pub struct PipelineVertexInputStateCreateInfo {
-    s_type: StructureType,
-    p_next: *const c_void,
-    flags: PipelineVertexInputStateCreateFlags,
-    vertex_binding_description_count: u32,
-    p_vertex_binding_descriptions: *const VertexInputBindingDescription,
-    vertex_attribute_description_count: u32,
-    p_vertex_attribute_descriptions: *const VertexInputAttributeDescription,
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub flags: PipelineVertexInputStateCreateFlags,
+    pub vertex_binding_description_count: u32,
+    pub p_vertex_binding_descriptions: *const VertexInputBindingDescription,
+    pub vertex_attribute_description_count: u32,
+    pub p_vertex_attribute_descriptions: *const VertexInputAttributeDescription,
 }
 
 #[repr(C)]
 pub struct PipelineInputAssemblyStateCreateInfo {
-    s_type: StructureType,
-    p_next: *const c_void,
-    flags: PipelineInputAssemblyStateCreateFlags,
-    topology: PrimitiveTopology,
-    primitive_restart_enable: Bool32,
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub flags: PipelineInputAssemblyStateCreateFlags,
+    pub topology: PrimitiveTopology,
+    pub primitive_restart_enable: Bool32,
 }
 
 #[repr(C)]
 pub struct PipelineTessellationStateCreateInfo {
-    s_type: StructureType,
-    p_next: *const c_void,
-    flags: PipelineTessellationStateCreateFlags,
-    patch_control_points: u32,
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub flags: PipelineTessellationStateCreateFlags,
+    pub patch_control_points: u32,
 }
 
 #[repr(C)]
 pub struct Viewport {
-    x: f32,
-    y: f32,
-    width: f32,
-    height: f32,
-    min_depth: f32,
-    max_depth: f32,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub min_depth: f32,
+    pub max_depth: f32,
 }
 
 #[repr(C)]
 pub struct PipelineViewportStateCreateInfo {
-    s_type: StructureType,
-    p_next: *const c_void,
-    flags: PipelineViewportStateCreateFlags,
-    viewport_count: u32,
-    p_viewports: *const Viewport,
-    scissor_count: u32,
-    p_scissors: *const Rect2D,
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub flags: PipelineViewportStateCreateFlags,
+    pub viewport_count: u32,
+    pub p_viewports: *const Viewport,
+    pub scissor_count: u32,
+    pub p_scissors: *const Rect2D,
 }
 
 #[repr(C)]
 pub struct PipelineRasterizationStateCreateInfo {
-    s_type: StructureType,
-    p_next: *const c_void,
-    flags: PipelineRasterizationStateCreateFlags,
-    depth_clamp_enable: Bool32,
-    rasterizer_discard_enable: Bool32,
-    polygon_mode: PolygonMode,
-    cull_mode: CullModeFlags,
-    front_face: FrontFace,
-    depth_bias_enable: Bool32,
-    depth_bias_constant_factor: f32,
-    depth_bias_clamp: f32,
-    depth_bias_slope_factor: f32,
-    line_width: f32,
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub flags: PipelineRasterizationStateCreateFlags,
+    pub depth_clamp_enable: Bool32,
+    pub rasterizer_discard_enable: Bool32,
+    pub polygon_mode: PolygonMode,
+    pub cull_mode: CullModeFlags,
+    pub front_face: FrontFace,
+    pub depth_bias_enable: Bool32,
+    pub depth_bias_constant_factor: f32,
+    pub depth_bias_clamp: f32,
+    pub depth_bias_slope_factor: f32,
+    pub line_width: f32,
 }
 
 #[repr(C)]
 pub struct PipelineMultisampleStateCreateInfo {
-    s_type: StructureType,
-    p_next: *const c_void,
-    flags: PipelineMultisampleStateCreateFlags,
-    rasterization_samples: SampleCountFlagBits,
-    sample_shading_enable: Bool32,
-    min_sample_shading: f32,
-    p_sample_mask: *const SampleMask,
-    alpha_to_coverage_enable: Bool32,
-    alpha_to_one_enable: Bool32,
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub flags: PipelineMultisampleStateCreateFlags,
+    pub rasterization_samples: SampleCountFlagBits,
+    pub sample_shading_enable: Bool32,
+    pub min_sample_shading: f32,
+    pub p_sample_mask: *const SampleMask,
+    pub alpha_to_coverage_enable: Bool32,
+    pub alpha_to_one_enable: Bool32,
 }
 
 #[repr(C)]
 pub struct StencilOpState {
-    fail_op: StencilOp,
-    pass_op: StencilOp,
-    depth_fail_op: StencilOp,
-    compare_op: CompareOp,
-    compare_mask: u32,
-    write_mask: u32,
-    reference: u32,
+    pub fail_op: StencilOp,
+    pub pass_op: StencilOp,
+    pub depth_fail_op: StencilOp,
+    pub compare_op: CompareOp,
+    pub compare_mask: u32,
+    pub write_mask: u32,
+    pub reference: u32,
 }
 
 #[repr(C)]
 pub struct PipelineDepthStencilStateCreateInfo {
-    s_type: StructureType,
-    p_next: *const c_void,
-    flags: PipelineDepthStencilStateCreateFlags,
-    depth_test_enable: Bool32,
-    depth_write_enable: Bool32,
-    depth_compare_op: CompareOp,
-    depth_bounds_test_enable: Bool32,
-    stencil_test_enable: Bool32,
-    front: StencilOpState,
-    back: StencilOpState,
-    min_depth_bounds: f32,
-    max_depth_bounds: f32,
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub flags: PipelineDepthStencilStateCreateFlags,
+    pub depth_test_enable: Bool32,
+    pub depth_write_enable: Bool32,
+    pub depth_compare_op: CompareOp,
+    pub depth_bounds_test_enable: Bool32,
+    pub stencil_test_enable: Bool32,
+    pub front: StencilOpState,
+    pub back: StencilOpState,
+    pub min_depth_bounds: f32,
+    pub max_depth_bounds: f32,
 }
 
 #[repr(C)]
 pub struct PipelineColorBlendAttachmentState {
-    blend_enable: Bool32,
-    src_color_blend_factor: BlendFactor,
-    dst_color_blend_factor: BlendFactor,
-    color_blend_op: BlendOp,
-    src_alpha_blend_factor: BlendFactor,
-    dst_alpha_blend_factor: BlendFactor,
-    alpha_blend_op: BlendOp,
-    color_write_mask: ColorComponentFlags,
+    pub blend_enable: Bool32,
+    pub src_color_blend_factor: BlendFactor,
+    pub dst_color_blend_factor: BlendFactor,
+    pub color_blend_op: BlendOp,
+    pub src_alpha_blend_factor: BlendFactor,
+    pub dst_alpha_blend_factor: BlendFactor,
+    pub alpha_blend_op: BlendOp,
+    pub color_write_mask: ColorComponentFlags,
 }
 
 #[repr(C)]
 pub struct PipelineColorBlendStateCreateInfo {
-    s_type: StructureType,
-    p_next: *const c_void,
-    flags: PipelineColorBlendStateCreateFlags,
-    logic_op_enable: Bool32,
-    logic_op: LogicOp,
-    attachment_count: u32,
-    p_attachments: *const PipelineColorBlendAttachmentState,
-    blend_constants: [f32; 4],
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub flags: PipelineColorBlendStateCreateFlags,
+    pub logic_op_enable: Bool32,
+    pub logic_op: LogicOp,
+    pub attachment_count: u32,
+    pub p_attachments: *const PipelineColorBlendAttachmentState,
+    pub blend_constants: [f32; 4],
 }
 
 #[repr(C)]
 pub struct PipelineDynamicStateCreateInfo {
-    s_type: StructureType,
-    p_next: *const c_void,
-    flags: PipelineDynamicStateCreateFlags,
-    dynamic_state_count: u32,
-    p_dynamic_states: *const DynamicState,
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub flags: PipelineDynamicStateCreateFlags,
+    pub dynamic_state_count: u32,
+    pub p_dynamic_states: *const DynamicState,
 }
 
 #[repr(C)]
 pub struct GraphicsPipelineCreateInfo {
-    s_type: StructureType,
-    p_next: *const c_void,
-    flags: PipelineCreateFlags,
-    stage_count: u32,
-    p_stages: *const PipelineShaderStageCreateInfo,
-    p_vertex_input_state: *const PipelineVertexInputStateCreateInfo,
-    p_input_assembly_state: *const PipelineInputAssemblyStateCreateInfo,
-    p_tessellation_state: *const PipelineTessellationStateCreateInfo,
-    p_viewport_state: *const PipelineViewportStateCreateInfo,
-    p_rasterization_state: *const PipelineRasterizationStateCreateInfo,
-    p_multisample_state: *const PipelineMultisampleStateCreateInfo,
-    p_depth_stencil_state: *const PipelineDepthStencilStateCreateInfo,
-    p_color_blend_state: *const PipelineColorBlendStateCreateInfo,
-    p_dynamic_state: *const PipelineDynamicStateCreateInfo,
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub flags: PipelineCreateFlags,
+    pub stage_count: u32,
+    pub p_stages: *const PipelineShaderStageCreateInfo,
+    pub p_vertex_input_state: *const PipelineVertexInputStateCreateInfo,
+    pub p_input_assembly_state: *const PipelineInputAssemblyStateCreateInfo,
+    pub p_tessellation_state: *const PipelineTessellationStateCreateInfo,
+    pub p_viewport_state: *const PipelineViewportStateCreateInfo,
+    pub p_rasterization_state: *const PipelineRasterizationStateCreateInfo,
+    pub p_multisample_state: *const PipelineMultisampleStateCreateInfo,
+    pub p_depth_stencil_state: *const PipelineDepthStencilStateCreateInfo,
+    pub p_color_blend_state: *const PipelineColorBlendStateCreateInfo,
+    pub p_dynamic_state: *const PipelineDynamicStateCreateInfo,
+
+    #[cfg(target_pointer_width = "64")]
+    pub layout: *mut VkPipelineLayout_T,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub layout: u64,
+
+    #[cfg(target_pointer_width = "64")]
+    pub render_pass: *mut VkRenderPass_T,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub render_pass: u64,
+
+    pub subpass: u32,
 
     #[cfg(target_pointer_width = "64")]
-    layout: *mut VkPipelineLayout_T,
+    pub base_pipeline_handle: *mut VkPipeline_T,
     #[cfg(not(target_pointer_width = "64"))]
-    layout: u64,
+    pub base_pipeline_handle: u64,
+
+    pub base_pipeline_index: i32,
+}
 
+#[repr(C)]
+pub struct DescriptorSetLayoutBinding {
+    pub binding: u32,
+    pub descriptor_type: DescriptorType,
+    pub descriptor_count: u32,
+    pub stage_flags: ShaderStageFlags,
     #[cfg(target_pointer_width = "64")]
-    render_pass: *mut VkRenderPass_T,
+    pub p_immutable_samplers: *const *mut VkSampler_T,
     #[cfg(not(target_pointer_width = "64"))]
-    render_pass: u64,
+    pub p_immutable_samplers: *const u64,
+}
+
+#[repr(C)]
+pub struct DescriptorSetLayoutCreateInfo {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub flags: DescriptorSetLayoutCreateFlags,
+    pub binding_count: u32,
+    pub p_bindings: *const DescriptorSetLayoutBinding,
+}
+
+#[repr(i32)]
+pub enum DescriptorPoolCreateFlagBits {
+    FreeDescriptorSetBit = 0x00000001,
+    UpdateAfterBindBit = 0x00000002,
+    HostOnlyBitValve = 0x00000004,
+}
+pub type DescriptorPoolCreateFlags = Flags;
+pub type DescriptorPoolResetFlags = Flags;
+pub type BufferViewCreateFlags = Flags;
+
+#[repr(C)]
+pub struct DescriptorPoolSize {
+    pub ty: DescriptorType,
+    pub descriptor_count: u32,
+}
 
-    subpass: u32,
+#[repr(C)]
+pub struct DescriptorPoolCreateInfo {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub flags: DescriptorPoolCreateFlags,
+    pub max_sets: u32,
+    pub pool_size_count: u32,
+    pub p_pool_sizes: *const DescriptorPoolSize,
+}
 
+#[repr(C)]
+pub struct DescriptorSetAllocateInfo {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    #[cfg(target_pointer_width = "64")]
+    pub descriptor_pool: *mut VkDescriptorPool_T,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub descriptor_pool: u64,
+    pub descriptor_set_count: u32,
     #[cfg(target_pointer_width = "64")]
-    base_pipeline_handle: *mut VkPipeline_T,
+    pub p_set_layouts: *const *mut VkDescriptorSetLayout_T,
     #[cfg(not(target_pointer_width = "64"))]
-    base_pipeline_handle: u64,
+    pub p_set_layouts: *const u64,
+}
 
-    base_pipeline_index: i32,
+#[repr(C)]
+pub struct DescriptorImageInfo {
+    #[cfg(target_pointer_width = "64")]
+    pub sampler: *mut VkSampler_T,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub sampler: u64,
+    #[cfg(target_pointer_width = "64")]
+    pub image_view: *mut VkImageView_T,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub image_view: u64,
+    pub image_layout: ImageLayout,
+}
+
+#[repr(C)]
+pub struct DescriptorBufferInfo {
+    #[cfg(target_pointer_width = "64")]
+    pub buffer: *mut VkBuffer_T,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub buffer: u64,
+    pub offset: DeviceSize,
+    pub range: DeviceSize,
+}
+
+#[repr(C)]
+pub struct WriteDescriptorSet {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    #[cfg(target_pointer_width = "64")]
+    pub dst_set: *mut VkDescriptorSet_T,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub dst_set: u64,
+    pub dst_binding: u32,
+    pub dst_array_element: u32,
+    pub descriptor_count: u32,
+    pub descriptor_type: DescriptorType,
+    pub p_image_info: *const DescriptorImageInfo,
+    pub p_buffer_info: *const DescriptorBufferInfo,
+    #[cfg(target_pointer_width = "64")]
+    pub p_texel_buffer_view: *const *mut VkBufferView_T,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub p_texel_buffer_view: *const u64,
+}
+
+#[repr(C)]
+pub struct CopyDescriptorSet {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    #[cfg(target_pointer_width = "64")]
+    pub src_set: *mut VkDescriptorSet_T,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub src_set: u64,
+    pub src_binding: u32,
+    pub src_array_element: u32,
+    #[cfg(target_pointer_width = "64")]
+    pub dst_set: *mut VkDescriptorSet_T,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub dst_set: u64,
+    pub dst_binding: u32,
+    pub dst_array_element: u32,
+    pub descriptor_count: u32,
+}
+
+#[repr(C)]
+pub struct BufferViewCreateInfo {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub flags: BufferViewCreateFlags,
+    #[cfg(target_pointer_width = "64")]
+    pub buffer: *mut VkBuffer_T,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub buffer: u64,
+    pub format: Format,
+    pub offset: DeviceSize,
+    pub range: DeviceSize,
+}
+
+#[repr(i32)]
+pub enum FenceCreateFlagBits {
+    SignaledBit = 0x00000001,
+}
+pub type FenceCreateFlags = Flags;
+
+#[repr(C)]
+pub struct FenceCreateInfo {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub flags: FenceCreateFlags,
+}
+
+#[repr(C)]
+pub struct SubmitInfo {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub wait_semaphore_count: u32,
+    #[cfg(target_pointer_width = "64")]
+    pub p_wait_semaphores: *const *mut VkSemaphore_T,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub p_wait_semaphores: *const u64,
+    pub p_wait_dst_stage_mask: *const PipelineStageFlags,
+    pub command_buffer_count: u32,
+    pub p_command_buffers: *const *mut VkCommandBuffer_T,
+    pub signal_semaphore_count: u32,
+    #[cfg(target_pointer_width = "64")]
+    pub p_signal_semaphores: *const *mut VkSemaphore_T,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub p_signal_semaphores: *const u64,
+}
+
+#[repr(u32)]
+pub enum PipelineStageFlagBits {
+    TopOfPipeBit = 0x00000001,
+    DrawIndirectBit = 0x00000002,
+    VertexInputBit = 0x00000004,
+    VertexShaderBit = 0x00000008,
+    TessellationControlShaderBit = 0x00000010,
+    TessellationEvaluationShaderBit = 0x00000020,
+    GeometryShaderBit = 0x00000040,
+    FragmentShaderBit = 0x00000080,
+    EarlyFragmentTestsBit = 0x00000100,
+    LateFragmentTestsBit = 0x00000200,
+    ColorAttachmentOutputBit = 0x00000400,
+    ComputeShaderBit = 0x00000800,
+    TransferBit = 0x00001000,
+    BottomOfPipeBit = 0x00002000,
+    HostBit = 0x00004000,
+    AllGraphicsBit = 0x00008000,
+    AllCommandsBit = 0x00010000,
+}
+
+#[repr(u32)]
+pub enum AccessFlagBits {
+    IndirectCommandReadBit = 0x00000001,
+    IndexReadBit = 0x00000002,
+    VertexAttributeReadBit = 0x00000004,
+    UniformReadBit = 0x00000008,
+    InputAttachmentReadBit = 0x00000010,
+    ShaderReadBit = 0x00000020,
+    ShaderWriteBit = 0x00000040,
+    ColorAttachmentReadBit = 0x00000080,
+    ColorAttachmentWriteBit = 0x00000100,
+    DepthStencilAttachmentReadBit = 0x00000200,
+    DepthStencilAttachmentWriteBit = 0x00000400,
+    TransferReadBit = 0x00000800,
+    TransferWriteBit = 0x00001000,
+    HostReadBit = 0x00002000,
+    HostWriteBit = 0x00004000,
+    MemoryReadBit = 0x00008000,
+    MemoryWriteBit = 0x00010000,
+}
+
+#[repr(u32)]
+pub enum DependencyFlagBits {
+    ByRegionBit = 0x00000001,
+    ViewLocalBit = 0x00000002,
+    DeviceGroupBit = 0x00000004,
+}
+
+#[repr(C)]
+pub struct MemoryBarrier {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub src_access_mask: AccessFlags,
+    pub dst_access_mask: AccessFlags,
+}
+
+#[repr(C)]
+pub struct BufferMemoryBarrier {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub src_access_mask: AccessFlags,
+    pub dst_access_mask: AccessFlags,
+    pub src_queue_family_index: u32,
+    pub dst_queue_family_index: u32,
+    #[cfg(target_pointer_width = "64")]
+    pub buffer: *mut VkBuffer_T,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub buffer: u64,
+    pub offset: DeviceSize,
+    pub size: DeviceSize,
+}
+
+#[repr(C)]
+pub struct ImageMemoryBarrier {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub src_access_mask: AccessFlags,
+    pub dst_access_mask: AccessFlags,
+    pub old_layout: ImageLayout,
+    pub new_layout: ImageLayout,
+    pub src_queue_family_index: u32,
+    pub dst_queue_family_index: u32,
+    #[cfg(target_pointer_width = "64")]
+    pub image: *mut VkImage_T,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub image: u64,
+    pub subresource_range: ImageSubresourceRange,
+}
+
+#[repr(C)]
+pub struct BufferCopy {
+    pub src_offset: DeviceSize,
+    pub dst_offset: DeviceSize,
+    pub size: DeviceSize,
+}
+
+#[repr(C)]
+pub struct ImageSubresourceLayers {
+    pub aspect_mask: ImageAspectFlags,
+    pub mip_level: u32,
+    pub base_array_layer: u32,
+    pub layer_count: u32,
+}
+
+#[repr(C)]
+pub struct BufferImageCopy {
+    pub buffer_offset: DeviceSize,
+    pub buffer_row_length: u32,
+    pub buffer_image_height: u32,
+    pub image_subresource: ImageSubresourceLayers,
+    pub image_offset: Offset3D,
+    pub image_extent: Extent3D,
+}
+
+#[repr(C)]
+pub struct DescriptorSetLayoutBindingFlagsCreateInfo {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub binding_count: u32,
+    pub p_binding_flags: *const DescriptorBindingFlags,
+}
+
+#[repr(C)]
+pub struct PushConstantRange {
+    pub stage_flags: ShaderStageFlags,
+    pub offset: u32,
+    pub size: u32,
+}
+
+#[repr(C)]
+pub struct PipelineLayoutCreateInfo {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub flags: PipelineLayoutCreateFlags,
+    pub set_layout_count: u32,
+    #[cfg(target_pointer_width = "64")]
+    pub p_set_layouts: *const *mut VkDescriptorSetLayout_T,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub p_set_layouts: *const u64,
+    pub push_constant_range_count: u32,
+    pub p_push_constant_ranges: *const PushConstantRange,
+}
+
+#[repr(C)]
+pub struct ComputePipelineCreateInfo {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub flags: PipelineCreateFlags,
+    pub stage: PipelineShaderStageCreateInfo,
+    #[cfg(target_pointer_width = "64")]
+    pub layout: *mut VkPipelineLayout_T,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub layout: u64,
+    #[cfg(target_pointer_width = "64")]
+    pub base_pipeline_handle: *mut VkPipeline_T,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub base_pipeline_handle: u64,
+    pub base_pipeline_index: i32,
+}
+
+#[repr(C)]
+pub struct PipelineCacheCreateInfo {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub flags: PipelineCacheCreateFlags,
+    pub initial_data_size: usize,
+    pub p_initial_data: *const c_void,
+}
+
+#[repr(C)]
+pub struct AttachmentDescription {
+    pub flags: AttachmentDescriptionFlags,
+    pub format: Format,
+    pub samples: SampleCountFlagBits,
+    pub load_op: AttachmentLoadOp,
+    pub store_op: AttachmentStoreOp,
+    pub stencil_load_op: AttachmentLoadOp,
+    pub stencil_store_op: AttachmentStoreOp,
+    pub initial_layout: ImageLayout,
+    pub final_layout: ImageLayout,
+}
+
+#[repr(C)]
+pub struct AttachmentReference {
+    pub attachment: u32,
+    pub layout: ImageLayout,
+}
+
+#[repr(C)]
+pub struct SubpassDescription {
+    pub flags: SubpassDescriptionFlags,
+    pub pipeline_bind_point: PipelineBindPoint,
+    pub input_attachment_count: u32,
+    pub p_input_attachments: *const AttachmentReference,
+    pub color_attachment_count: u32,
+    pub p_color_attachments: *const AttachmentReference,
+    pub p_resolve_attachments: *const AttachmentReference,
+    pub p_depth_stencil_attachment: *const AttachmentReference,
+    pub preserve_attachment_count: u32,
+    pub p_preserve_attachments: *const u32,
+}
+
+#[repr(C)]
+pub struct SubpassDependency {
+    pub src_subpass: u32,
+    pub dst_subpass: u32,
+    pub src_stage_mask: PipelineStageFlags,
+    pub dst_stage_mask: PipelineStageFlags,
+    pub src_access_mask: AccessFlags,
+    pub dst_access_mask: AccessFlags,
+    pub dependency_flags: DependencyFlags,
+}
+
+#[repr(C)]
+pub struct RenderPassCreateInfo {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub flags: RenderPassCreateFlags,
+    pub attachment_count: u32,
+    pub p_attachments: *const AttachmentDescription,
+    pub subpass_count: u32,
+    pub p_subpasses: *const SubpassDescription,
+    pub dependency_count: u32,
+    pub p_dependencies: *const SubpassDependency,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub union ClearColorValue {
+    pub float32: [f32; 4],
+    pub int32: [i32; 4],
+    pub uint32: [u32; 4],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct ClearDepthStencilValue {
+    pub depth: f32,
+    pub stencil: u32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub union ClearValue {
+    pub color: ClearColorValue,
+    pub depth_stencil: ClearDepthStencilValue,
+}
+
+#[repr(C)]
+pub struct RenderingAttachmentInfo {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    #[cfg(target_pointer_width = "64")]
+    pub image_view: *mut VkImageView_T,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub image_view: u64,
+    pub image_layout: ImageLayout,
+    pub resolve_mode: ResolveModeFlagBits,
+    #[cfg(target_pointer_width = "64")]
+    pub resolve_image_view: *mut VkImageView_T,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub resolve_image_view: u64,
+    pub resolve_image_layout: ImageLayout,
+    pub load_op: AttachmentLoadOp,
+    pub store_op: AttachmentStoreOp,
+    pub clear_value: ClearValue,
+}
+
+#[repr(C)]
+pub struct RenderingInfo {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub flags: RenderingFlags,
+    pub render_area: Rect2D,
+    pub layer_count: u32,
+    pub view_mask: u32,
+    pub color_attachment_count: u32,
+    pub p_color_attachments: *const RenderingAttachmentInfo,
+    pub p_depth_attachment: *const RenderingAttachmentInfo,
+    pub p_stencil_attachment: *const RenderingAttachmentInfo,
+}
+
+#[repr(C)]
+pub struct PipelineRenderingCreateInfo {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub view_mask: u32,
+    pub color_attachment_count: u32,
+    pub p_color_attachment_formats: *const Format,
+    pub depth_attachment_format: Format,
+    pub stencil_attachment_format: Format,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceFeatures2 {
+    pub s_type: StructureType,
+    pub p_next: *mut c_void,
+    pub features: PhysicalDeviceFeatures,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceDynamicRenderingFeatures {
+    pub s_type: StructureType,
+    pub p_next: *mut c_void,
+    pub dynamic_rendering: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceExtendedDynamicStateFeaturesExt {
+    pub s_type: StructureType,
+    pub p_next: *mut c_void,
+    pub extended_dynamic_state: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceVulkan12Features {
+    pub s_type: StructureType,
+    pub p_next: *mut c_void,
+    pub sampler_mirror_clamp_to_edge: Bool32,
+    pub draw_indirect_count: Bool32,
+    pub storage_buffer8_bit_access: Bool32,
+    pub uniform_and_storage_buffer8_bit_access: Bool32,
+    pub storage_push_constant8: Bool32,
+    pub shader_buffer_int64_atomics: Bool32,
+    pub shader_shared_int64_atomics: Bool32,
+    pub shader_float16: Bool32,
+    pub shader_int8: Bool32,
+    pub descriptor_indexing: Bool32,
+    pub shader_input_attachment_array_dynamic_indexing: Bool32,
+    pub shader_uniform_texel_buffer_array_dynamic_indexing: Bool32,
+    pub shader_storage_texel_buffer_array_dynamic_indexing: Bool32,
+    pub shader_uniform_buffer_array_non_uniform_indexing: Bool32,
+    pub shader_sampled_image_array_non_uniform_indexing: Bool32,
+    pub shader_storage_buffer_array_non_uniform_indexing: Bool32,
+    pub shader_storage_image_array_non_uniform_indexing: Bool32,
+    pub shader_input_attachment_array_non_uniform_indexing: Bool32,
+    pub shader_uniform_texel_buffer_array_non_uniform_indexing: Bool32,
+    pub shader_storage_texel_buffer_array_non_uniform_indexing: Bool32,
+    pub descriptor_binding_uniform_buffer_update_after_bind: Bool32,
+    pub descriptor_binding_sampled_image_update_after_bind: Bool32,
+    pub descriptor_binding_storage_image_update_after_bind: Bool32,
+    pub descriptor_binding_storage_buffer_update_after_bind: Bool32,
+    pub descriptor_binding_uniform_texel_buffer_update_after_bind: Bool32,
+    pub descriptor_binding_storage_texel_buffer_update_after_bind: Bool32,
+    pub descriptor_binding_update_unused_while_pending: Bool32,
+    pub descriptor_binding_partially_bound: Bool32,
+    pub descriptor_binding_variable_descriptor_count: Bool32,
+    pub runtime_descriptor_array: Bool32,
+    pub sampler_filter_minmax: Bool32,
+    pub scalar_block_layout: Bool32,
+    pub imageless_framebuffer: Bool32,
+    pub uniform_buffer_standard_layout: Bool32,
+    pub shader_subgroup_extended_types: Bool32,
+    pub separate_depth_stencil_layouts: Bool32,
+    pub host_query_reset: Bool32,
+    pub timeline_semaphore: Bool32,
+    pub buffer_device_address: Bool32,
+    pub buffer_device_address_capture_replay: Bool32,
+    pub buffer_device_address_multi_device: Bool32,
+    pub vulkan_memory_model: Bool32,
+    pub vulkan_memory_model_device_scope: Bool32,
+    pub vulkan_memory_model_availability_visibility_chains: Bool32,
+    pub shader_output_viewport_index: Bool32,
+    pub shader_output_layer: Bool32,
+    pub subgroup_broadcast_dynamic_id: Bool32,
+}
+
+impl Default for PhysicalDeviceVulkan12Features {
+    fn default() -> Self {
+        Self {
+            s_type: StructureType::PhysicalDeviceVulkan12Features,
+            p_next: std::ptr::null_mut(),
+            sampler_mirror_clamp_to_edge: 0,
+            draw_indirect_count: 0,
+            storage_buffer8_bit_access: 0,
+            uniform_and_storage_buffer8_bit_access: 0,
+            storage_push_constant8: 0,
+            shader_buffer_int64_atomics: 0,
+            shader_shared_int64_atomics: 0,
+            shader_float16: 0,
+            shader_int8: 0,
+            descriptor_indexing: 0,
+            shader_input_attachment_array_dynamic_indexing: 0,
+            shader_uniform_texel_buffer_array_dynamic_indexing: 0,
+            shader_storage_texel_buffer_array_dynamic_indexing: 0,
+            shader_uniform_buffer_array_non_uniform_indexing: 0,
+            shader_sampled_image_array_non_uniform_indexing: 0,
+            shader_storage_buffer_array_non_uniform_indexing: 0,
+            shader_storage_image_array_non_uniform_indexing: 0,
+            shader_input_attachment_array_non_uniform_indexing: 0,
+            shader_uniform_texel_buffer_array_non_uniform_indexing: 0,
+            shader_storage_texel_buffer_array_non_uniform_indexing: 0,
+            descriptor_binding_uniform_buffer_update_after_bind: 0,
+            descriptor_binding_sampled_image_update_after_bind: 0,
+            descriptor_binding_storage_image_update_after_bind: 0,
+            descriptor_binding_storage_buffer_update_after_bind: 0,
+            descriptor_binding_uniform_texel_buffer_update_after_bind: 0,
+            descriptor_binding_storage_texel_buffer_update_after_bind: 0,
+            descriptor_binding_update_unused_while_pending: 0,
+            descriptor_binding_partially_bound: 0,
+            descriptor_binding_variable_descriptor_count: 0,
+            runtime_descriptor_array: 0,
+            sampler_filter_minmax: 0,
+            scalar_block_layout: 0,
+            imageless_framebuffer: 0,
+            uniform_buffer_standard_layout: 0,
+            shader_subgroup_extended_types: 0,
+            separate_depth_stencil_layouts: 0,
+            host_query_reset: 0,
+            timeline_semaphore: 0,
+            buffer_device_address: 0,
+            buffer_device_address_capture_replay: 0,
+            buffer_device_address_multi_device: 0,
+            vulkan_memory_model: 0,
+            vulkan_memory_model_device_scope: 0,
+            vulkan_memory_model_availability_visibility_chains: 0,
+            shader_output_viewport_index: 0,
+            shader_output_layer: 0,
+            subgroup_broadcast_dynamic_id: 0,
+        }
+    }
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceSynchronization2Features {
+    pub s_type: StructureType,
+    pub p_next: *mut c_void,
+    pub synchronization2: Bool32,
+}
+
+#[repr(u64)]
+pub enum PipelineStageFlagBits2 {
+    None = 0,
+    TopOfPipeBit = 0x00000001,
+    DrawIndirectBit = 0x00000002,
+    VertexInputBit = 0x00000004,
+    VertexShaderBit = 0x00000008,
+    TessellationControlShaderBit = 0x00000010,
+    TessellationEvaluationShaderBit = 0x00000020,
+    GeometryShaderBit = 0x00000040,
+    FragmentShaderBit = 0x00000080,
+    EarlyFragmentTestsBit = 0x00000100,
+    LateFragmentTestsBit = 0x00000200,
+    ColorAttachmentOutputBit = 0x00000400,
+    ComputeShaderBit = 0x00000800,
+    AllTransferBit = 0x00001000,
+    BottomOfPipeBit = 0x00002000,
+    HostBit = 0x00004000,
+    AllGraphicsBit = 0x00008000,
+    AllCommandsBit = 0x00010000,
+    CopyBit = 0x100000000,
+    ResolveBit = 0x200000000,
+    BlitBit = 0x400000000,
+    ClearBit = 0x800000000,
+    IndexInputBit = 0x1000000000,
+    VertexAttributeInputBit = 0x2000000000,
+    PreRasterizationShadersBit = 0x4000000000,
+}
+
+#[repr(u64)]
+pub enum AccessFlagBits2 {
+    None = 0,
+    IndirectCommandReadBit = 0x00000001,
+    IndexReadBit = 0x00000002,
+    VertexAttributeReadBit = 0x00000004,
+    UniformReadBit = 0x00000008,
+    InputAttachmentReadBit = 0x00000010,
+    ShaderReadBit = 0x00000020,
+    ShaderWriteBit = 0x00000040,
+    ColorAttachmentReadBit = 0x00000080,
+    ColorAttachmentWriteBit = 0x00000100,
+    DepthStencilAttachmentReadBit = 0x00000200,
+    DepthStencilAttachmentWriteBit = 0x00000400,
+    TransferReadBit = 0x00000800,
+    TransferWriteBit = 0x00001000,
+    HostReadBit = 0x00002000,
+    HostWriteBit = 0x00004000,
+    MemoryReadBit = 0x00008000,
+    MemoryWriteBit = 0x00010000,
+    ShaderSampledReadBit = 0x100000000,
+    ShaderStorageReadBit = 0x200000000,
+    ShaderStorageWriteBit = 0x400000000,
+}
+
+#[repr(C)]
+pub struct MemoryBarrier2 {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub src_stage_mask: PipelineStageFlags2,
+    pub src_access_mask: AccessFlags2,
+    pub dst_stage_mask: PipelineStageFlags2,
+    pub dst_access_mask: AccessFlags2,
+}
+
+#[repr(C)]
+pub struct BufferMemoryBarrier2 {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub src_stage_mask: PipelineStageFlags2,
+    pub src_access_mask: AccessFlags2,
+    pub dst_stage_mask: PipelineStageFlags2,
+    pub dst_access_mask: AccessFlags2,
+    pub src_queue_family_index: u32,
+    pub dst_queue_family_index: u32,
+    #[cfg(target_pointer_width = "64")]
+    pub buffer: *mut VkBuffer_T,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub buffer: u64,
+    pub offset: DeviceSize,
+    pub size: DeviceSize,
+}
+
+#[repr(C)]
+pub struct ImageMemoryBarrier2 {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub src_stage_mask: PipelineStageFlags2,
+    pub src_access_mask: AccessFlags2,
+    pub dst_stage_mask: PipelineStageFlags2,
+    pub dst_access_mask: AccessFlags2,
+    pub old_layout: ImageLayout,
+    pub new_layout: ImageLayout,
+    pub src_queue_family_index: u32,
+    pub dst_queue_family_index: u32,
+    #[cfg(target_pointer_width = "64")]
+    pub image: *mut VkImage_T,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub image: u64,
+    pub subresource_range: ImageSubresourceRange,
+}
+
+#[repr(C)]
+pub struct DependencyInfo {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub dependency_flags: DependencyFlags,
+    pub memory_barrier_count: u32,
+    pub p_memory_barriers: *const MemoryBarrier2,
+    pub buffer_memory_barrier_count: u32,
+    pub p_buffer_memory_barriers: *const BufferMemoryBarrier2,
+    pub image_memory_barrier_count: u32,
+    pub p_image_memory_barriers: *const ImageMemoryBarrier2,
+}
+
+#[repr(C)]
+pub struct SemaphoreSubmitInfo {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    #[cfg(target_pointer_width = "64")]
+    pub semaphore: *mut VkSemaphore_T,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub semaphore: u64,
+    pub value: u64,
+    pub stage_mask: PipelineStageFlags2,
+    pub device_index: u32,
+}
+
+#[repr(C)]
+pub struct CommandBufferSubmitInfo {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub command_buffer: *mut VkCommandBuffer_T,
+    pub device_mask: u32,
+}
+
+#[repr(C)]
+pub struct SubmitInfo2 {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub flags: SubmitFlags,
+    pub wait_semaphore_info_count: u32,
+    pub p_wait_semaphore_infos: *const SemaphoreSubmitInfo,
+    pub command_buffer_info_count: u32,
+    pub p_command_buffer_infos: *const CommandBufferSubmitInfo,
+    pub signal_semaphore_info_count: u32,
+    pub p_signal_semaphore_infos: *const SemaphoreSubmitInfo,
+}
+
+#[repr(C)]
+pub struct EventCreateInfo {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub flags: EventCreateFlags,
+}
+
+#[repr(i32)]
+pub enum QueryType {
+    Occlusion = 0,
+    PipelineStatistics = 1,
+    Timestamp = 2,
+}
+
+#[repr(i32)]
+pub enum QueryResultFlagBits {
+    ResultBit64 = 0x00000001,
+    WaitBit = 0x00000002,
+    WithAvailabilityBit = 0x00000004,
+    PartialBit = 0x00000008,
+}
+
+#[repr(C)]
+pub struct QueryPoolCreateInfo {
+    pub s_type: StructureType,
+    pub p_next: *const c_void,
+    pub flags: QueryPoolCreateFlags,
+    pub query_type: QueryType,
+    pub query_count: u32,
+    pub pipeline_statistics: Flags,
 }
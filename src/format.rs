@@ -1,4 +1,5 @@
 use crate::ffi;
+use crate::shaders::NumericType;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Format {
@@ -251,6 +252,430 @@ pub enum Format {
     A4b4g4r4UnormPack16Ext,
 }
 
+impl Format {
+    /// Whether images of this format have a depth aspect.
+    pub fn has_depth(&self) -> bool {
+        matches!(
+            self,
+            Self::D16Unorm
+                | Self::X8D24UnormPack32
+                | Self::D32Sfloat
+                | Self::D16UnormS8Uint
+                | Self::D24UnormS8Uint
+                | Self::D32SfloatS8Uint
+        )
+    }
+
+    /// Whether images of this format have a stencil aspect.
+    pub fn has_stencil(&self) -> bool {
+        matches!(
+            self,
+            Self::S8Uint | Self::D16UnormS8Uint | Self::D24UnormS8Uint | Self::D32SfloatS8Uint
+        )
+    }
+
+    /// Numeric type that shaders read the color components of this format as, [`None`] for
+    /// depth/stencil formats.
+    pub fn numeric_type(&self) -> Option<NumericType> {
+        match self {
+            Self::Undefined => None,
+            _ if self.has_depth() || self.has_stencil() => None,
+            Self::R8Uint
+            | Self::R8g8Uint
+            | Self::R8g8b8Uint
+            | Self::B8g8r8Uint
+            | Self::R8g8b8a8Uint
+            | Self::B8g8r8a8Uint
+            | Self::A8b8g8r8UintPack32
+            | Self::A2r10g10b10UintPack32
+            | Self::A2b10g10r10UintPack32
+            | Self::R16Uint
+            | Self::R16g16Uint
+            | Self::R16g16b16Uint
+            | Self::R16g16b16a16Uint
+            | Self::R32Uint
+            | Self::R32g32Uint
+            | Self::R32g32b32Uint
+            | Self::R32g32b32a32Uint
+            | Self::R64Uint
+            | Self::R64g64Uint
+            | Self::R64g64b64Uint
+            | Self::R64g64b64a64Uint => Some(NumericType::Uint),
+            Self::R8Sint
+            | Self::R8g8Sint
+            | Self::R8g8b8Sint
+            | Self::B8g8r8Sint
+            | Self::R8g8b8a8Sint
+            | Self::B8g8r8a8Sint
+            | Self::A8b8g8r8SintPack32
+            | Self::A2r10g10b10SintPack32
+            | Self::A2b10g10r10SintPack32
+            | Self::R16Sint
+            | Self::R16g16Sint
+            | Self::R16g16b16Sint
+            | Self::R16g16b16a16Sint
+            | Self::R32Sint
+            | Self::R32g32Sint
+            | Self::R32g32b32Sint
+            | Self::R32g32b32a32Sint
+            | Self::R64Sint
+            | Self::R64g64Sint
+            | Self::R64g64b64Sint
+            | Self::R64g64b64a64Sint => Some(NumericType::Sint),
+            _ => Some(NumericType::Float),
+        }
+    }
+
+    /// Size in bytes of a texel block, the smallest unit of data of an image of this format.
+    /// [`None`] for [`Undefined`](Self::Undefined) and multi-planar formats, whose planes are
+    /// copied separately.
+    pub fn texel_block_size(&self) -> Option<u32> {
+        match self {
+            Self::R4g4UnormPack8
+            | Self::R8Unorm
+            | Self::R8Snorm
+            | Self::R8Uscaled
+            | Self::R8Sscaled
+            | Self::R8Uint
+            | Self::R8Sint
+            | Self::R8Srgb
+            | Self::S8Uint => Some(1),
+            Self::R4g4b4a4UnormPack16
+            | Self::B4g4r4a4UnormPack16
+            | Self::R5g6b5UnormPack16
+            | Self::B5g6r5UnormPack16
+            | Self::R5g5b5a1UnormPack16
+            | Self::B5g5r5a1UnormPack16
+            | Self::A1r5g5b5UnormPack16
+            | Self::R8g8Unorm
+            | Self::R8g8Snorm
+            | Self::R8g8Uscaled
+            | Self::R8g8Sscaled
+            | Self::R8g8Uint
+            | Self::R8g8Sint
+            | Self::R8g8Srgb
+            | Self::R16Unorm
+            | Self::R16Snorm
+            | Self::R16Uscaled
+            | Self::R16Sscaled
+            | Self::R16Uint
+            | Self::R16Sint
+            | Self::R16Sfloat
+            | Self::D16Unorm
+            | Self::R10x6UnormPack16
+            | Self::R12x4UnormPack16
+            | Self::A4r4g4b4UnormPack16Ext
+            | Self::A4b4g4r4UnormPack16Ext => Some(2),
+            Self::R8g8b8Unorm
+            | Self::R8g8b8Snorm
+            | Self::R8g8b8Uscaled
+            | Self::R8g8b8Sscaled
+            | Self::R8g8b8Uint
+            | Self::R8g8b8Sint
+            | Self::R8g8b8Srgb
+            | Self::B8g8r8Unorm
+            | Self::B8g8r8Snorm
+            | Self::B8g8r8Uscaled
+            | Self::B8g8r8Sscaled
+            | Self::B8g8r8Uint
+            | Self::B8g8r8Sint
+            | Self::B8g8r8Srgb
+            | Self::D16UnormS8Uint => Some(3),
+            Self::R8g8b8a8Unorm
+            | Self::R8g8b8a8Snorm
+            | Self::R8g8b8a8Uscaled
+            | Self::R8g8b8a8Sscaled
+            | Self::R8g8b8a8Uint
+            | Self::R8g8b8a8Sint
+            | Self::R8g8b8a8Srgb
+            | Self::B8g8r8a8Unorm
+            | Self::B8g8r8a8Snorm
+            | Self::B8g8r8a8Uscaled
+            | Self::B8g8r8a8Sscaled
+            | Self::B8g8r8a8Uint
+            | Self::B8g8r8a8Sint
+            | Self::B8g8r8a8Srgb
+            | Self::A8b8g8r8UnormPack32
+            | Self::A8b8g8r8SnormPack32
+            | Self::A8b8g8r8UscaledPack32
+            | Self::A8b8g8r8SscaledPack32
+            | Self::A8b8g8r8UintPack32
+            | Self::A8b8g8r8SintPack32
+            | Self::A8b8g8r8SrgbPack32
+            | Self::A2r10g10b10UnormPack32
+            | Self::A2r10g10b10SnormPack32
+            | Self::A2r10g10b10UscaledPack32
+            | Self::A2r10g10b10SscaledPack32
+            | Self::A2r10g10b10UintPack32
+            | Self::A2r10g10b10SintPack32
+            | Self::A2b10g10r10UnormPack32
+            | Self::A2b10g10r10SnormPack32
+            | Self::A2b10g10r10UscaledPack32
+            | Self::A2b10g10r10SscaledPack32
+            | Self::A2b10g10r10UintPack32
+            | Self::A2b10g10r10SintPack32
+            | Self::R16g16Unorm
+            | Self::R16g16Snorm
+            | Self::R16g16Uscaled
+            | Self::R16g16Sscaled
+            | Self::R16g16Uint
+            | Self::R16g16Sint
+            | Self::R16g16Sfloat
+            | Self::R32Uint
+            | Self::R32Sint
+            | Self::R32Sfloat
+            | Self::B10g11r11UfloatPack32
+            | Self::E5b9g9r9UfloatPack32
+            | Self::X8D24UnormPack32
+            | Self::D32Sfloat
+            | Self::D24UnormS8Uint
+            | Self::G8b8g8r8_422Unorm
+            | Self::B8g8r8g8_422Unorm
+            | Self::R10x6g10x6Unorm2pack16
+            | Self::R12x4g12x4Unorm2pack16 => Some(4),
+            Self::D32SfloatS8Uint => Some(5),
+            Self::R16g16b16Unorm
+            | Self::R16g16b16Snorm
+            | Self::R16g16b16Uscaled
+            | Self::R16g16b16Sscaled
+            | Self::R16g16b16Uint
+            | Self::R16g16b16Sint
+            | Self::R16g16b16Sfloat => Some(6),
+            Self::R16g16b16a16Unorm
+            | Self::R16g16b16a16Snorm
+            | Self::R16g16b16a16Uscaled
+            | Self::R16g16b16a16Sscaled
+            | Self::R16g16b16a16Uint
+            | Self::R16g16b16a16Sint
+            | Self::R16g16b16a16Sfloat
+            | Self::R32g32Uint
+            | Self::R32g32Sint
+            | Self::R32g32Sfloat
+            | Self::R64Uint
+            | Self::R64Sint
+            | Self::R64Sfloat
+            | Self::Bc1RgbUnormBlock
+            | Self::Bc1RgbSrgbBlock
+            | Self::Bc1RgbaUnormBlock
+            | Self::Bc1RgbaSrgbBlock
+            | Self::Bc4UnormBlock
+            | Self::Bc4SnormBlock
+            | Self::Etc2R8g8b8UnormBlock
+            | Self::Etc2R8g8b8SrgbBlock
+            | Self::Etc2R8g8b8a1UnormBlock
+            | Self::Etc2R8g8b8a1SrgbBlock
+            | Self::EacR11UnormBlock
+            | Self::EacR11SnormBlock
+            | Self::R10x6g10x6b10x6a10x6Unorm4pack16
+            | Self::G10x6b10x6g10x6r10x6_422Unorm4pack16
+            | Self::B10x6g10x6r10x6g10x6_422Unorm4pack16
+            | Self::R12x4g12x4b12x4a12x4Unorm4pack16
+            | Self::G12x4b12x4g12x4r12x4_422Unorm4pack16
+            | Self::B12x4g12x4r12x4g12x4_422Unorm4pack16
+            | Self::G16b16g16r16_422Unorm
+            | Self::B16g16r16g16_422Unorm
+            | Self::Pvrtc1_2bppUnormBlockImg
+            | Self::Pvrtc1_4bppUnormBlockImg
+            | Self::Pvrtc2_2bppUnormBlockImg
+            | Self::Pvrtc2_4bppUnormBlockImg
+            | Self::Pvrtc1_2bppSrgbBlockImg
+            | Self::Pvrtc1_4bppSrgbBlockImg
+            | Self::Pvrtc2_2bppSrgbBlockImg
+            | Self::Pvrtc2_4bppSrgbBlockImg => Some(8),
+            Self::R32g32b32Uint | Self::R32g32b32Sint | Self::R32g32b32Sfloat => Some(12),
+            Self::R32g32b32a32Uint
+            | Self::R32g32b32a32Sint
+            | Self::R32g32b32a32Sfloat
+            | Self::R64g64Uint
+            | Self::R64g64Sint
+            | Self::R64g64Sfloat
+            | Self::Bc2UnormBlock
+            | Self::Bc2SrgbBlock
+            | Self::Bc3UnormBlock
+            | Self::Bc3SrgbBlock
+            | Self::Bc5UnormBlock
+            | Self::Bc5SnormBlock
+            | Self::Bc6hUfloatBlock
+            | Self::Bc6hSfloatBlock
+            | Self::Bc7UnormBlock
+            | Self::Bc7SrgbBlock
+            | Self::Etc2R8g8b8a8UnormBlock
+            | Self::Etc2R8g8b8a8SrgbBlock
+            | Self::EacR11g11UnormBlock
+            | Self::EacR11g11SnormBlock
+            | Self::Astc4x4UnormBlock
+            | Self::Astc4x4SrgbBlock
+            | Self::Astc5x4UnormBlock
+            | Self::Astc5x4SrgbBlock
+            | Self::Astc5x5UnormBlock
+            | Self::Astc5x5SrgbBlock
+            | Self::Astc6x5UnormBlock
+            | Self::Astc6x5SrgbBlock
+            | Self::Astc6x6UnormBlock
+            | Self::Astc6x6SrgbBlock
+            | Self::Astc8x5UnormBlock
+            | Self::Astc8x5SrgbBlock
+            | Self::Astc8x6UnormBlock
+            | Self::Astc8x6SrgbBlock
+            | Self::Astc8x8UnormBlock
+            | Self::Astc8x8SrgbBlock
+            | Self::Astc10x5UnormBlock
+            | Self::Astc10x5SrgbBlock
+            | Self::Astc10x6UnormBlock
+            | Self::Astc10x6SrgbBlock
+            | Self::Astc10x8UnormBlock
+            | Self::Astc10x8SrgbBlock
+            | Self::Astc10x10UnormBlock
+            | Self::Astc10x10SrgbBlock
+            | Self::Astc12x10UnormBlock
+            | Self::Astc12x10SrgbBlock
+            | Self::Astc12x12UnormBlock
+            | Self::Astc12x12SrgbBlock
+            | Self::Astc4x4SfloatBlockExt
+            | Self::Astc5x4SfloatBlockExt
+            | Self::Astc5x5SfloatBlockExt
+            | Self::Astc6x5SfloatBlockExt
+            | Self::Astc6x6SfloatBlockExt
+            | Self::Astc8x5SfloatBlockExt
+            | Self::Astc8x6SfloatBlockExt
+            | Self::Astc8x8SfloatBlockExt
+            | Self::Astc10x5SfloatBlockExt
+            | Self::Astc10x6SfloatBlockExt
+            | Self::Astc10x8SfloatBlockExt
+            | Self::Astc10x10SfloatBlockExt
+            | Self::Astc12x10SfloatBlockExt
+            | Self::Astc12x12SfloatBlockExt => Some(16),
+            Self::R64g64b64Uint | Self::R64g64b64Sint | Self::R64g64b64Sfloat => Some(24),
+            Self::R64g64b64a64Uint | Self::R64g64b64a64Sint | Self::R64g64b64a64Sfloat => Some(32),
+            _ => None,
+        }
+    }
+
+    /// Width and height in texels of a texel block, which is larger than one texel for
+    /// compressed and subsampled formats.
+    pub fn texel_block_extent(&self) -> (u32, u32) {
+        match self {
+            Self::G8b8g8r8_422Unorm
+            | Self::B8g8r8g8_422Unorm
+            | Self::G10x6b10x6g10x6r10x6_422Unorm4pack16
+            | Self::B10x6g10x6r10x6g10x6_422Unorm4pack16
+            | Self::G12x4b12x4g12x4r12x4_422Unorm4pack16
+            | Self::B12x4g12x4r12x4g12x4_422Unorm4pack16
+            | Self::G16b16g16r16_422Unorm
+            | Self::B16g16r16g16_422Unorm => (2, 1),
+            Self::Bc1RgbUnormBlock
+            | Self::Bc1RgbSrgbBlock
+            | Self::Bc1RgbaUnormBlock
+            | Self::Bc1RgbaSrgbBlock
+            | Self::Bc2UnormBlock
+            | Self::Bc2SrgbBlock
+            | Self::Bc3UnormBlock
+            | Self::Bc3SrgbBlock
+            | Self::Bc4UnormBlock
+            | Self::Bc4SnormBlock
+            | Self::Bc5UnormBlock
+            | Self::Bc5SnormBlock
+            | Self::Bc6hUfloatBlock
+            | Self::Bc6hSfloatBlock
+            | Self::Bc7UnormBlock
+            | Self::Bc7SrgbBlock
+            | Self::Etc2R8g8b8UnormBlock
+            | Self::Etc2R8g8b8SrgbBlock
+            | Self::Etc2R8g8b8a1UnormBlock
+            | Self::Etc2R8g8b8a1SrgbBlock
+            | Self::Etc2R8g8b8a8UnormBlock
+            | Self::Etc2R8g8b8a8SrgbBlock
+            | Self::EacR11UnormBlock
+            | Self::EacR11SnormBlock
+            | Self::EacR11g11UnormBlock
+            | Self::EacR11g11SnormBlock
+            | Self::Astc4x4UnormBlock
+            | Self::Astc4x4SrgbBlock
+            | Self::Pvrtc1_4bppUnormBlockImg
+            | Self::Pvrtc2_4bppUnormBlockImg
+            | Self::Pvrtc1_4bppSrgbBlockImg
+            | Self::Pvrtc2_4bppSrgbBlockImg
+            | Self::Astc4x4SfloatBlockExt => (4, 4),
+            Self::Astc5x4UnormBlock | Self::Astc5x4SrgbBlock | Self::Astc5x4SfloatBlockExt => {
+                (5, 4)
+            }
+            Self::Astc5x5UnormBlock | Self::Astc5x5SrgbBlock | Self::Astc5x5SfloatBlockExt => {
+                (5, 5)
+            }
+            Self::Astc6x5UnormBlock | Self::Astc6x5SrgbBlock | Self::Astc6x5SfloatBlockExt => {
+                (6, 5)
+            }
+            Self::Astc6x6UnormBlock | Self::Astc6x6SrgbBlock | Self::Astc6x6SfloatBlockExt => {
+                (6, 6)
+            }
+            Self::Pvrtc1_2bppUnormBlockImg
+            | Self::Pvrtc2_2bppUnormBlockImg
+            | Self::Pvrtc1_2bppSrgbBlockImg
+            | Self::Pvrtc2_2bppSrgbBlockImg => (8, 4),
+            Self::Astc8x5UnormBlock | Self::Astc8x5SrgbBlock | Self::Astc8x5SfloatBlockExt => {
+                (8, 5)
+            }
+            Self::Astc8x6UnormBlock | Self::Astc8x6SrgbBlock | Self::Astc8x6SfloatBlockExt => {
+                (8, 6)
+            }
+            Self::Astc8x8UnormBlock | Self::Astc8x8SrgbBlock | Self::Astc8x8SfloatBlockExt => {
+                (8, 8)
+            }
+            Self::Astc10x5UnormBlock | Self::Astc10x5SrgbBlock | Self::Astc10x5SfloatBlockExt => {
+                (10, 5)
+            }
+            Self::Astc10x6UnormBlock | Self::Astc10x6SrgbBlock | Self::Astc10x6SfloatBlockExt => {
+                (10, 6)
+            }
+            Self::Astc10x8UnormBlock | Self::Astc10x8SrgbBlock | Self::Astc10x8SfloatBlockExt => {
+                (10, 8)
+            }
+            Self::Astc10x10UnormBlock
+            | Self::Astc10x10SrgbBlock
+            | Self::Astc10x10SfloatBlockExt => (10, 10),
+            Self::Astc12x10UnormBlock
+            | Self::Astc12x10SrgbBlock
+            | Self::Astc12x10SfloatBlockExt => (12, 10),
+            Self::Astc12x12UnormBlock
+            | Self::Astc12x12SrgbBlock
+            | Self::Astc12x12SfloatBlockExt => (12, 12),
+            _ => (1, 1),
+        }
+    }
+
+    /// Number of vertex input locations taken by an attribute of this format.
+    pub(crate) fn location_count(&self) -> u32 {
+        match self {
+            Self::R64g64b64Uint
+            | Self::R64g64b64Sint
+            | Self::R64g64b64Sfloat
+            | Self::R64g64b64a64Uint
+            | Self::R64g64b64a64Sint
+            | Self::R64g64b64a64Sfloat => 2,
+            _ => 1,
+        }
+    }
+
+    /// Whether the components of this format are 64 bits wide.
+    pub(crate) fn is_64_bit(&self) -> bool {
+        matches!(
+            self,
+            Self::R64Uint
+                | Self::R64Sint
+                | Self::R64Sfloat
+                | Self::R64g64Uint
+                | Self::R64g64Sint
+                | Self::R64g64Sfloat
+                | Self::R64g64b64Uint
+                | Self::R64g64b64Sint
+                | Self::R64g64b64Sfloat
+                | Self::R64g64b64a64Uint
+                | Self::R64g64b64a64Sint
+                | Self::R64g64b64a64Sfloat
+        )
+    }
+}
+
 impl From<ffi::Format> for Format {
     fn from(format: ffi::Format) -> Self {
         match format {
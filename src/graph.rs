@@ -0,0 +1,1214 @@
+//! # Render graph
+//! A [`RenderGraph`] describes the passes of a frame and how each of them reads and writes
+//! images and buffers. [`compile`](RenderGraph::compile) turns the description into a
+//! [`CompiledGraph`] without touching the device:
+//! - Passes that write nothing an imported resource depends on are culled.
+//! - The remaining passes are ordered so that, where dependencies allow, a pass does not
+//!   directly follow a pass it depends on, which leaves the device work to overlap with the
+//!   barrier between them.
+//! - Each pass gets the barriers and image layout transitions its accesses need, and only
+//!   those: reads of the same data in the same layout share one barrier, and reads after
+//!   reads need none.
+//!
+//! [`record`](RenderGraph::record) records the compiled graph into a command buffer, with the
+//! images and buffers bound in [`GraphResources`].
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::command_buffer::CommandBuffer;
+use crate::core::{Error, Extent3D, Result};
+use crate::ffi;
+use crate::format::Format;
+use crate::pipeline::SampleCount;
+use crate::resource::{
+    Buffer, BufferBuilder, BufferUsage, BufferUsageFlags, Image, ImageBuilder, ImageLayout,
+    ImageType, ImageUsage, ImageUsageFlags,
+};
+use crate::sync::{
+    layout_dst_scope, layout_src_scope, Access, AccessFlags, BufferMemoryBarrier, DependencyInfo,
+    ImageMemoryBarrier, PipelineStage, PipelineStageFlags,
+};
+
+pub mod aliasing;
+
+/// An image of a [`RenderGraph`], either imported or created by the graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ImageHandle(usize);
+
+/// A buffer of a [`RenderGraph`], either imported or created by the graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BufferHandle(usize);
+
+/// An image or buffer of a [`RenderGraph`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResourceHandle {
+    Image(ImageHandle),
+    Buffer(BufferHandle),
+}
+
+/// Commands a pass records, which decide the shader stages of its shader accesses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PassKind {
+    Graphics,
+    Compute,
+    Transfer,
+}
+
+/// How a pass uses an image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageAccess {
+    ColorAttachment,
+    /// Depth and stencil tests. Reading only keeps the image in a read-only layout, so it can
+    /// be sampled at the same time.
+    DepthStencilAttachment,
+    /// Sampled in shaders, read only.
+    Sampled,
+    /// Storage image in shaders, in the general layout.
+    Storage,
+    /// Source or destination of copy, blit, resolve and clear commands.
+    Transfer,
+}
+
+/// How a pass uses a buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BufferAccess {
+    /// Vertex buffer, read only.
+    Vertex,
+    /// Index buffer, read only.
+    Index,
+    /// Parameters of indirect draws and dispatches, read only.
+    Indirect,
+    /// Uniform buffer in shaders, read only.
+    Uniform,
+    /// Storage buffer in shaders.
+    Storage,
+    /// Source or destination of copy and fill commands.
+    Transfer,
+}
+
+/// Description of an image created by a [`RenderGraph`]. 2D unless the depth of the extent
+/// is above 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageDesc {
+    format: Format,
+    extent: Extent3D,
+    mip_levels: u32,
+    array_layers: u32,
+    samples: SampleCount,
+}
+
+pub struct RenderGraph<'g> {
+    /// Identifies the graph in the graphs compiled from it.
+    id: u64,
+    images: Vec<GraphImage>,
+    buffers: Vec<GraphBuffer>,
+    passes: Vec<Pass<'g>>,
+}
+
+type RecordFn<'g> = Box<dyn FnMut(&mut CommandBuffer, &GraphResources) + 'g>;
+
+/// A pass of a [`RenderGraph`], with the resources it accesses and the commands it records.
+pub struct Pass<'g> {
+    name: String,
+    kind: PassKind,
+    image_accesses: Vec<(ImageHandle, ImageAccess, bool)>,
+    buffer_accesses: Vec<(BufferHandle, BufferAccess, bool)>,
+    record: Option<RecordFn<'g>>,
+}
+
+/// The real images and buffers behind the handles of a [`RenderGraph`].
+#[derive(Default)]
+pub struct GraphResources<'r> {
+    images: HashMap<ImageHandle, &'r Image<'r>>,
+    buffers: HashMap<BufferHandle, &'r Buffer<'r>>,
+}
+
+/// Passes of a [`RenderGraph`] in execution order, with the barriers recorded before each of
+/// them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompiledGraph {
+    graph_id: u64,
+    /// Number of passes of the graph when it was compiled, culled ones included.
+    pass_count: usize,
+    passes: Vec<CompiledPass>,
+    culled_passes: Vec<String>,
+    final_barriers: Barriers,
+    transients: Vec<TransientLifetime>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompiledPass {
+    index: usize,
+    name: String,
+    barriers: Barriers,
+}
+
+/// Passes using a resource created by the graph, as positions in
+/// [`CompiledGraph::passes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransientLifetime {
+    resource: ResourceHandle,
+    first_pass: usize,
+    last_pass: usize,
+    /// Scope of the first access.
+    first_stages: PipelineStageFlags,
+    first_access: AccessFlags,
+    /// Scope of the accesses the next user of the memory has to wait for.
+    last_stages: PipelineStageFlags,
+    last_access: AccessFlags,
+}
+
+/// Barriers recorded together in one pipeline barrier.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Barriers {
+    image_barriers: Vec<ImageBarrier>,
+    buffer_barriers: Vec<BufferBarrier>,
+}
+
+/// Dependency on all subresources of an image, with a layout transition if the layouts
+/// differ.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageBarrier {
+    image: ImageHandle,
+    old_layout: ImageLayout,
+    new_layout: ImageLayout,
+    src_stages: PipelineStageFlags,
+    src_access: AccessFlags,
+    dst_stages: PipelineStageFlags,
+    dst_access: AccessFlags,
+}
+
+/// Dependency on a whole buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BufferBarrier {
+    buffer: BufferHandle,
+    src_stages: PipelineStageFlags,
+    src_access: AccessFlags,
+    dst_stages: PipelineStageFlags,
+    dst_access: AccessFlags,
+}
+
+struct GraphImage {
+    /// `None` for imported images.
+    desc: Option<ImageDesc>,
+    initial_layout: ImageLayout,
+    final_layout: Option<ImageLayout>,
+}
+
+struct GraphBuffer {
+    /// `None` for imported buffers.
+    size: Option<u64>,
+}
+
+/// All accesses of one pass to one resource, merged.
+#[derive(Debug, Clone, Copy)]
+struct Use {
+    resource: ResourceHandle,
+    stages: PipelineStageFlags,
+    access: AccessFlags,
+    /// `None` for buffers.
+    layout: Option<ImageLayout>,
+    read: bool,
+    write: bool,
+}
+
+/// Synchronization state of a resource while the compiled passes are simulated.
+#[derive(Debug, Clone, Copy)]
+struct State {
+    layout: ImageLayout,
+    /// Whether a write or layout transition may still be in flight.
+    written: bool,
+    /// Stages of the last write or layout transition.
+    write_stages: PipelineStageFlags,
+    /// Writes that have to be made available.
+    write_access: AccessFlags,
+    /// Stages that read the resource since the last write.
+    read_stages: PipelineStageFlags,
+    /// Scope the last write is already visible to.
+    visible_stages: PipelineStageFlags,
+    visible_access: AccessFlags,
+}
+
+impl ImageDesc {
+    /// Image of a single mip level and array layer, with one sample.
+    pub fn new(format: Format, extent: impl Into<Extent3D>) -> Self {
+        Self {
+            format,
+            extent: extent.into(),
+            mip_levels: 1,
+            array_layers: 1,
+            samples: SampleCount::OneBit,
+        }
+    }
+
+    pub fn with_mip_levels(mut self, mip_levels: u32) -> Self {
+        self.mip_levels = mip_levels;
+        self
+    }
+
+    pub fn with_array_layers(mut self, array_layers: u32) -> Self {
+        self.array_layers = array_layers;
+        self
+    }
+
+    pub fn with_samples(mut self, samples: SampleCount) -> Self {
+        self.samples = samples;
+        self
+    }
+
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    pub fn extent(&self) -> Extent3D {
+        self.extent
+    }
+
+    pub fn mip_levels(&self) -> u32 {
+        self.mip_levels
+    }
+
+    pub fn array_layers(&self) -> u32 {
+        self.array_layers
+    }
+
+    pub fn samples(&self) -> SampleCount {
+        self.samples
+    }
+
+    /// Builder of an image matching the description.
+    pub fn builder(&self, usage: ImageUsageFlags) -> ImageBuilder<'static> {
+        let image_type = if self.extent.depth() > 1 {
+            ImageType::ThreeD
+        } else {
+            ImageType::TwoD
+        };
+
+        let mut builder = ImageBuilder::new(image_type, self.format, self.extent, usage);
+        builder
+            .with_mip_levels(self.mip_levels)
+            .with_array_layers(self.array_layers)
+            .with_samples(self.samples);
+        builder
+    }
+}
+
+impl<'g> RenderGraph<'g> {
+    pub fn new() -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+        Self {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            images: Vec::new(),
+            buffers: Vec::new(),
+            passes: Vec::new(),
+        }
+    }
+
+    /// Image created for the graph. Its contents are undefined before the first pass writing
+    /// it and are discarded after the last pass reading it.
+    pub fn create_image(&mut self, desc: ImageDesc) -> ImageHandle {
+        self.images.push(GraphImage {
+            desc: Some(desc),
+            initial_layout: ImageLayout::Undefined,
+            final_layout: None,
+        });
+        ImageHandle(self.images.len() - 1)
+    }
+
+    /// Image that lives outside the graph and is in `initial_layout` when the graph starts,
+    /// with the scope of [`layout_src_scope`] as the previous accesses. Passes writing it are
+    /// never culled.
+    pub fn import_image(&mut self, initial_layout: ImageLayout) -> ImageHandle {
+        self.images.push(GraphImage {
+            desc: None,
+            initial_layout,
+            final_layout: None,
+        });
+        ImageHandle(self.images.len() - 1)
+    }
+
+    /// Transitions an imported image to `final_layout` after the last pass, such as
+    /// [`ImageLayout::PresentSrcKhr`] for a swapchain image.
+    ///
+    /// # Panics
+    /// If `image` was created by the graph.
+    pub fn export_image(&mut self, image: ImageHandle, final_layout: ImageLayout) {
+        let graph_image = &mut self.images[image.0];
+        assert!(
+            graph_image.desc.is_none(),
+            "only imported images can be exported"
+        );
+        graph_image.final_layout = Some(final_layout);
+    }
+
+    /// Buffer of `size` bytes created for the graph. Its contents are undefined before the
+    /// first pass writing it and are discarded after the last pass reading it.
+    pub fn create_buffer(&mut self, size: u64) -> BufferHandle {
+        self.buffers.push(GraphBuffer { size: Some(size) });
+        BufferHandle(self.buffers.len() - 1)
+    }
+
+    /// Buffer that lives outside the graph. Accesses before the graph have to be synchronized
+    /// by the caller. Passes writing it are never culled.
+    pub fn import_buffer(&mut self) -> BufferHandle {
+        self.buffers.push(GraphBuffer { size: None });
+        BufferHandle(self.buffers.len() - 1)
+    }
+
+    /// Adds a pass. Passes see the writes of the passes added before them.
+    pub fn add_pass(&mut self, name: impl Into<String>, kind: PassKind) -> &mut Pass<'g> {
+        self.passes.push(Pass {
+            name: name.into(),
+            kind,
+            image_accesses: Vec::new(),
+            buffer_accesses: Vec::new(),
+            record: None,
+        });
+        self.passes.last_mut().unwrap()
+    }
+
+    /// Description of an image created by the graph, `None` if it was imported.
+    pub fn image_desc(&self, image: ImageHandle) -> Option<&ImageDesc> {
+        self.images[image.0].desc.as_ref()
+    }
+
+    /// Size of a buffer created by the graph, `None` if it was imported.
+    pub fn buffer_size(&self, buffer: BufferHandle) -> Option<u64> {
+        self.buffers[buffer.0].size
+    }
+
+    /// Usage an image needs for all accesses of the passes.
+    pub fn image_usage(&self, image: ImageHandle) -> ImageUsageFlags {
+        let usage = self
+            .passes
+            .iter()
+            .flat_map(|pass| &pass.image_accesses)
+            .filter(|(handle, _, _)| *handle == image)
+            .fold(0, |usage, &(_, access, write)| {
+                let flag = match access {
+                    ImageAccess::ColorAttachment => ImageUsage::ColorAttachment,
+                    ImageAccess::DepthStencilAttachment => ImageUsage::DepthStencilAttachment,
+                    ImageAccess::Sampled => ImageUsage::Sampled,
+                    ImageAccess::Storage => ImageUsage::Storage,
+                    ImageAccess::Transfer if write => ImageUsage::TransferDst,
+                    ImageAccess::Transfer => ImageUsage::TransferSrc,
+                };
+                usage | ffi::ImageUsageFlagBits::from(flag) as u32
+            });
+
+        ImageUsageFlags(usage)
+    }
+
+    /// Usage a buffer needs for all accesses of the passes.
+    pub fn buffer_usage(&self, buffer: BufferHandle) -> BufferUsageFlags {
+        let usage = self
+            .passes
+            .iter()
+            .flat_map(|pass| &pass.buffer_accesses)
+            .filter(|(handle, _, _)| *handle == buffer)
+            .fold(0, |usage, &(_, access, write)| {
+                let flag = match access {
+                    BufferAccess::Vertex => BufferUsage::VertexBuffer,
+                    BufferAccess::Index => BufferUsage::IndexBuffer,
+                    BufferAccess::Indirect => BufferUsage::IndirectBuffer,
+                    BufferAccess::Uniform => BufferUsage::UniformBuffer,
+                    BufferAccess::Storage => BufferUsage::StorageBuffer,
+                    BufferAccess::Transfer if write => BufferUsage::TransferDst,
+                    BufferAccess::Transfer => BufferUsage::TransferSrc,
+                };
+                usage | ffi::BufferUsageFlagBits::from(flag) as u32
+            });
+
+        BufferUsageFlags(usage)
+    }
+
+    /// Builder of an image matching an image created by the graph, with the usage of all its
+    /// accesses. `None` if the image was imported.
+    pub fn image_builder(&self, image: ImageHandle) -> Option<ImageBuilder<'static>> {
+        self.image_desc(image)
+            .map(|desc| desc.builder(self.image_usage(image)))
+    }
+
+    /// Builder of a buffer matching a buffer created by the graph, with the usage of all its
+    /// accesses. `None` if the buffer was imported.
+    pub fn buffer_builder(&self, buffer: BufferHandle) -> Option<BufferBuilder<'static>> {
+        self.buffer_size(buffer)
+            .map(|size| BufferBuilder::new(size, self.buffer_usage(buffer)))
+    }
+
+    /// Culls and orders the passes and derives the barriers before each of them.
+    ///
+    /// # Errors
+    /// - [`InvalidPassAccess`](Error::InvalidPassAccess) if a pass writes through a read-only
+    ///   access, or uses an access its kind does not support.
+    /// - [`UninitializedGraphResource`](Error::UninitializedGraphResource) if a pass reads a
+    ///   resource created by the graph before any pass writes it.
+    pub fn compile(&self) -> Result<CompiledGraph> {
+        let uses = self
+            .passes
+            .iter()
+            .enumerate()
+            .map(|(index, pass)| pass.uses(index))
+            .collect::<Result<Vec<_>>>()?;
+        let dependencies = self.dependencies(&uses)?;
+        let live = self.live_passes(&uses, &dependencies);
+        let order = schedule(&dependencies, &live);
+
+        let mut states: HashMap<ResourceHandle, State> = HashMap::new();
+        let mut transients: Vec<TransientLifetime> = Vec::new();
+        let passes = order
+            .into_iter()
+            .enumerate()
+            .map(|(position, index)| {
+                let mut barriers = Barriers::default();
+                for resource_use in &uses[index] {
+                    let state = states
+                        .entry(resource_use.resource)
+                        .or_insert_with(|| self.initial_state(resource_use.resource));
+                    state.access(resource_use, &mut barriers);
+
+                    if self.is_imported(resource_use.resource) {
+                        continue;
+                    }
+                    match transients
+                        .iter_mut()
+                        .find(|transient| transient.resource == resource_use.resource)
+                    {
+                        Some(transient) => transient.last_pass = position,
+                        None => transients.push(TransientLifetime {
+                            resource: resource_use.resource,
+                            first_pass: position,
+                            last_pass: position,
+                            first_stages: resource_use.stages,
+                            first_access: resource_use.access,
+                            last_stages: PipelineStageFlags::new(),
+                            last_access: AccessFlags::new(),
+                        }),
+                    }
+                }
+
+                CompiledPass {
+                    index,
+                    name: self.passes[index].name.clone(),
+                    barriers,
+                }
+            })
+            .collect();
+
+        let mut final_barriers = Barriers::default();
+        for (index, image) in self.images.iter().enumerate() {
+            let final_layout = match image.final_layout {
+                Some(final_layout) => final_layout,
+                None => continue,
+            };
+            let resource = ResourceHandle::Image(ImageHandle(index));
+            let state = states
+                .get(&resource)
+                .copied()
+                .unwrap_or_else(|| self.initial_state(resource));
+            if state.layout != final_layout {
+                let (dst_stages, dst_access) = layout_dst_scope(final_layout);
+                final_barriers.image_barriers.push(ImageBarrier {
+                    image: ImageHandle(index),
+                    old_layout: state.layout,
+                    new_layout: final_layout,
+                    src_stages: union_stages(state.write_stages, state.read_stages),
+                    src_access: state.write_access,
+                    dst_stages,
+                    dst_access,
+                });
+            }
+        }
+
+        Ok(CompiledGraph {
+            graph_id: self.id,
+            pass_count: self.passes.len(),
+            passes,
+            culled_passes: self
+                .passes
+                .iter()
+                .zip(&live)
+                .filter(|(_, live)| !**live)
+                .map(|(pass, _)| pass.name.clone())
+                .collect(),
+            final_barriers,
+            transients: transients
+                .into_iter()
+                .map(|transient| {
+                    let state = states[&transient.resource];
+                    TransientLifetime {
+                        last_stages: union_stages(state.write_stages, state.read_stages),
+                        last_access: state.write_access,
+                        ..transient
+                    }
+                })
+                .collect(),
+        })
+    }
+
+    /// Records the passes of `compiled`, which has to be compiled from this graph, each
+    /// preceded by its barriers, followed by the transitions of exported images.
+    ///
+    /// # Errors
+    /// - [`CompiledGraphMismatch`](Error::CompiledGraphMismatch) if `compiled` was compiled
+    ///   from another graph, or passes were added to this one since.
+    /// - [`UnboundGraphResource`](Error::UnboundGraphResource) if an image or buffer used by
+    ///   a recorded pass or exported is not bound in `resources`.
+    ///
+    /// Nothing is recorded on errors.
+    pub fn record(
+        &mut self,
+        compiled: &CompiledGraph,
+        command_buffer: &mut CommandBuffer,
+        resources: &GraphResources,
+    ) -> Result<()> {
+        if compiled.graph_id != self.id || compiled.pass_count != self.passes.len() {
+            return Err(Error::CompiledGraphMismatch);
+        }
+        let dependency_infos = compiled
+            .passes
+            .iter()
+            .map(|pass| {
+                self.passes[pass.index].check_bound(resources)?;
+                resources.dependency_info(&pass.barriers)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let final_dependency_info = resources.dependency_info(&compiled.final_barriers)?;
+
+        for (pass, dependency_info) in compiled.passes.iter().zip(&dependency_infos) {
+            if !dependency_info.is_empty() {
+                command_buffer.pipeline_barrier2(dependency_info);
+            }
+            if let Some(record) = &mut self.passes[pass.index].record {
+                record(command_buffer, resources);
+            }
+        }
+        if !final_dependency_info.is_empty() {
+            command_buffer.pipeline_barrier2(&final_dependency_info);
+        }
+
+        Ok(())
+    }
+
+    /// Passes each pass has to execute after, with whether the pass needs their results.
+    fn dependencies(&self, uses: &[Vec<Use>]) -> Result<Vec<Vec<(usize, bool)>>> {
+        #[derive(Default)]
+        struct Accessors {
+            writer: Option<usize>,
+            readers: Vec<usize>,
+        }
+
+        let mut accessors: HashMap<ResourceHandle, Accessors> = HashMap::new();
+        let mut dependencies = vec![Vec::new(); uses.len()];
+        for (index, pass_uses) in uses.iter().enumerate() {
+            for resource_use in pass_uses {
+                let resource_accessors = accessors.entry(resource_use.resource).or_default();
+                if let Some(writer) = resource_accessors.writer {
+                    dependencies[index].push((writer, true));
+                } else if resource_use.read && !self.is_imported(resource_use.resource) {
+                    return Err(Error::UninitializedGraphResource { pass: index as u32 });
+                }
+
+                if resource_use.write {
+                    dependencies[index].extend(
+                        resource_accessors
+                            .readers
+                            .drain(..)
+                            .filter(|&reader| reader != index)
+                            .map(|reader| (reader, false)),
+                    );
+                    resource_accessors.writer = Some(index);
+                } else {
+                    resource_accessors.readers.push(index);
+                }
+            }
+        }
+
+        Ok(dependencies)
+    }
+
+    /// Passes writing imported resources, and the passes whose results they need.
+    fn live_passes(&self, uses: &[Vec<Use>], dependencies: &[Vec<(usize, bool)>]) -> Vec<bool> {
+        let mut live = vec![false; uses.len()];
+        let mut stack: Vec<usize> = (0..uses.len())
+            .filter(|&index| {
+                uses[index].iter().any(|resource_use| {
+                    resource_use.write && self.is_imported(resource_use.resource)
+                })
+            })
+            .collect();
+
+        while let Some(index) = stack.pop() {
+            if !live[index] {
+                live[index] = true;
+                stack.extend(
+                    dependencies[index]
+                        .iter()
+                        .filter(|(_, needed)| *needed)
+                        .map(|(dependency, _)| *dependency),
+                );
+            }
+        }
+
+        live
+    }
+
+    fn is_imported(&self, resource: ResourceHandle) -> bool {
+        match resource {
+            ResourceHandle::Image(image) => self.images[image.0].desc.is_none(),
+            ResourceHandle::Buffer(buffer) => self.buffers[buffer.0].size.is_none(),
+        }
+    }
+
+    fn initial_state(&self, resource: ResourceHandle) -> State {
+        let layout = match resource {
+            ResourceHandle::Image(image) => self.images[image.0].initial_layout,
+            ResourceHandle::Buffer(_) => ImageLayout::Undefined,
+        };
+        let (write_stages, write_access) = match resource {
+            ResourceHandle::Image(_) if layout != ImageLayout::Undefined => {
+                layout_src_scope(layout)
+            }
+            _ => Default::default(),
+        };
+
+        State {
+            layout,
+            written: !write_access.is_empty(),
+            write_stages,
+            write_access,
+            read_stages: PipelineStageFlags::new(),
+            visible_stages: PipelineStageFlags::new(),
+            visible_access: AccessFlags::new(),
+        }
+    }
+}
+
+impl<'g> Default for RenderGraph<'g> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'g> Pass<'g> {
+    pub fn read_image(&mut self, image: ImageHandle, access: ImageAccess) -> &mut Self {
+        self.image_accesses.push((image, access, false));
+        self
+    }
+
+    pub fn write_image(&mut self, image: ImageHandle, access: ImageAccess) -> &mut Self {
+        self.image_accesses.push((image, access, true));
+        self
+    }
+
+    pub fn read_buffer(&mut self, buffer: BufferHandle, access: BufferAccess) -> &mut Self {
+        self.buffer_accesses.push((buffer, access, false));
+        self
+    }
+
+    pub fn write_buffer(&mut self, buffer: BufferHandle, access: BufferAccess) -> &mut Self {
+        self.buffer_accesses.push((buffer, access, true));
+        self
+    }
+
+    /// Commands of the pass, recorded after the barriers it needs. Render passes or dynamic
+    /// rendering are begun and ended by the pass itself.
+    pub fn with_record(
+        &mut self,
+        record: impl FnMut(&mut CommandBuffer, &GraphResources) + 'g,
+    ) -> &mut Self {
+        self.record = Some(Box::new(record));
+        self
+    }
+
+    /// Accesses of the pass, merged per resource. A resource used in different layouts by the
+    /// same pass is used in the general layout.
+    fn uses(&self, index: usize) -> Result<Vec<Use>> {
+        let invalid = Error::InvalidPassAccess { pass: index as u32 };
+        let image_uses = self.image_accesses.iter().map(|&(image, access, write)| {
+            let (stages, access, layout) = image_scope(self.kind, access, write).ok_or(invalid)?;
+            Ok(Use {
+                resource: ResourceHandle::Image(image),
+                stages,
+                access,
+                layout: Some(layout),
+                read: !write,
+                write,
+            })
+        });
+        let buffer_uses = self.buffer_accesses.iter().map(|&(buffer, access, write)| {
+            let (stages, access) = buffer_scope(self.kind, access, write).ok_or(invalid)?;
+            Ok(Use {
+                resource: ResourceHandle::Buffer(buffer),
+                stages,
+                access,
+                layout: None,
+                read: !write,
+                write,
+            })
+        });
+
+        let mut uses: Vec<Use> = Vec::new();
+        for resource_use in image_uses.chain(buffer_uses) {
+            let resource_use = resource_use?;
+            match uses
+                .iter_mut()
+                .find(|merged| merged.resource == resource_use.resource)
+            {
+                Some(merged) => {
+                    merged.stages = union_stages(merged.stages, resource_use.stages);
+                    merged.access = union_access(merged.access, resource_use.access);
+                    merged.read |= resource_use.read;
+                    merged.write |= resource_use.write;
+                    if merged.layout != resource_use.layout {
+                        merged.layout = Some(ImageLayout::General);
+                    }
+                }
+                None => uses.push(resource_use),
+            }
+        }
+
+        Ok(uses)
+    }
+
+    fn check_bound(&self, resources: &GraphResources) -> Result<()> {
+        let images_bound = self
+            .image_accesses
+            .iter()
+            .all(|(image, _, _)| resources.images.contains_key(image));
+        let buffers_bound = self
+            .buffer_accesses
+            .iter()
+            .all(|(buffer, _, _)| resources.buffers.contains_key(buffer));
+
+        if images_bound && buffers_bound {
+            Ok(())
+        } else {
+            Err(Error::UnboundGraphResource)
+        }
+    }
+}
+
+impl<'g> std::fmt::Debug for Pass<'g> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Pass")
+            .field("name", &self.name)
+            .field("kind", &self.kind)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'r> GraphResources<'r> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn bind_image(&mut self, handle: ImageHandle, image: &'r Image<'r>) -> &mut Self {
+        self.images.insert(handle, image);
+        self
+    }
+
+    pub fn bind_buffer(&mut self, handle: BufferHandle, buffer: &'r Buffer<'r>) -> &mut Self {
+        self.buffers.insert(handle, buffer);
+        self
+    }
+
+    pub fn image(&self, handle: ImageHandle) -> Option<&'r Image<'r>> {
+        self.images.get(&handle).copied()
+    }
+
+    pub fn buffer(&self, handle: BufferHandle) -> Option<&'r Buffer<'r>> {
+        self.buffers.get(&handle).copied()
+    }
+
+    fn dependency_info(&self, barriers: &Barriers) -> Result<DependencyInfo<'r>> {
+        let mut dependency_info = DependencyInfo::new();
+        for barrier in &barriers.image_barriers {
+            let image = self
+                .image(barrier.image)
+                .ok_or(Error::UnboundGraphResource)?;
+            dependency_info = dependency_info.with_image_barrier(
+                ImageMemoryBarrier::new(image, barrier.old_layout, barrier.new_layout)
+                    .with_src(barrier.src_stages, barrier.src_access)
+                    .with_dst(barrier.dst_stages, barrier.dst_access),
+            );
+        }
+        for barrier in &barriers.buffer_barriers {
+            let buffer = self
+                .buffer(barrier.buffer)
+                .ok_or(Error::UnboundGraphResource)?;
+            dependency_info = dependency_info.with_buffer_barrier(BufferMemoryBarrier::new(
+                buffer,
+                barrier.src_stages,
+                barrier.src_access,
+                barrier.dst_stages,
+                barrier.dst_access,
+            ));
+        }
+
+        Ok(dependency_info)
+    }
+}
+
+impl CompiledGraph {
+    /// Passes that are recorded, in execution order.
+    pub fn passes(&self) -> &[CompiledPass] {
+        &self.passes
+    }
+
+    /// Names of the passes that are not recorded because nothing uses their results.
+    pub fn culled_passes(&self) -> &[String] {
+        &self.culled_passes
+    }
+
+    /// Transitions of exported images to their final layouts, after the last pass.
+    pub fn final_barriers(&self) -> &Barriers {
+        &self.final_barriers
+    }
+
+    /// Lifetimes of the resources created by the graph that recorded passes use, in the
+    /// order of their first use.
+    pub fn transients(&self) -> &[TransientLifetime] {
+        &self.transients
+    }
+}
+
+impl TransientLifetime {
+    pub fn resource(&self) -> ResourceHandle {
+        self.resource
+    }
+
+    /// Position of the first pass using the resource.
+    pub fn first_pass(&self) -> usize {
+        self.first_pass
+    }
+
+    /// Position of the last pass using the resource.
+    pub fn last_pass(&self) -> usize {
+        self.last_pass
+    }
+
+    /// Whether the resource is in use at the same time as `other`.
+    pub fn overlaps(&self, other: &TransientLifetime) -> bool {
+        self.first_pass <= other.last_pass && other.first_pass <= self.last_pass
+    }
+}
+
+impl CompiledPass {
+    /// Index of the pass in the order the passes were added.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Barriers recorded before the pass.
+    pub fn barriers(&self) -> &Barriers {
+        &self.barriers
+    }
+}
+
+impl Barriers {
+    pub fn image_barriers(&self) -> &[ImageBarrier] {
+        &self.image_barriers
+    }
+
+    pub fn buffer_barriers(&self) -> &[BufferBarrier] {
+        &self.buffer_barriers
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.image_barriers.is_empty() && self.buffer_barriers.is_empty()
+    }
+}
+
+impl ImageBarrier {
+    pub fn image(&self) -> ImageHandle {
+        self.image
+    }
+
+    pub fn old_layout(&self) -> ImageLayout {
+        self.old_layout
+    }
+
+    pub fn new_layout(&self) -> ImageLayout {
+        self.new_layout
+    }
+
+    pub fn src_stages(&self) -> PipelineStageFlags {
+        self.src_stages
+    }
+
+    pub fn src_access(&self) -> AccessFlags {
+        self.src_access
+    }
+
+    pub fn dst_stages(&self) -> PipelineStageFlags {
+        self.dst_stages
+    }
+
+    pub fn dst_access(&self) -> AccessFlags {
+        self.dst_access
+    }
+}
+
+impl BufferBarrier {
+    pub fn buffer(&self) -> BufferHandle {
+        self.buffer
+    }
+
+    pub fn src_stages(&self) -> PipelineStageFlags {
+        self.src_stages
+    }
+
+    pub fn src_access(&self) -> AccessFlags {
+        self.src_access
+    }
+
+    pub fn dst_stages(&self) -> PipelineStageFlags {
+        self.dst_stages
+    }
+
+    pub fn dst_access(&self) -> AccessFlags {
+        self.dst_access
+    }
+}
+
+impl State {
+    /// Updates the state for `resource_use`, adding the barrier it needs to `barriers`.
+    fn access(&mut self, resource_use: &Use, barriers: &mut Barriers) {
+        let layout = resource_use.layout.unwrap_or(self.layout);
+        let transition = layout != self.layout;
+
+        if transition || resource_use.write {
+            // Writes and layout transitions wait for all previous accesses.
+            let src_stages = union_stages(self.write_stages, self.read_stages);
+            if transition || !src_stages.is_empty() {
+                self.push_barrier(resource_use, layout, src_stages, barriers);
+            }
+
+            self.layout = layout;
+            self.written = true;
+            self.write_stages = resource_use.stages;
+            self.write_access = resource_use.access.writes();
+            self.read_stages = PipelineStageFlags::new();
+            // The write is not visible to any later access yet, including reads of the
+            // same scope.
+            self.visible_stages = PipelineStageFlags::new();
+            self.visible_access = AccessFlags::new();
+        } else {
+            // Reads only wait for the last write, once per scope.
+            let visible = contains_stages(self.visible_stages, resource_use.stages)
+                && contains_access(self.visible_access, resource_use.access);
+            if self.written && !visible {
+                self.push_barrier(resource_use, layout, self.write_stages, barriers);
+                self.visible_stages = union_stages(self.visible_stages, resource_use.stages);
+                self.visible_access = union_access(self.visible_access, resource_use.access);
+            }
+
+            self.read_stages = union_stages(self.read_stages, resource_use.stages);
+        }
+    }
+
+    fn push_barrier(
+        &self,
+        resource_use: &Use,
+        new_layout: ImageLayout,
+        src_stages: PipelineStageFlags,
+        barriers: &mut Barriers,
+    ) {
+        match resource_use.resource {
+            ResourceHandle::Image(image) => barriers.image_barriers.push(ImageBarrier {
+                image,
+                old_layout: self.layout,
+                new_layout,
+                src_stages,
+                src_access: self.write_access,
+                dst_stages: resource_use.stages,
+                dst_access: resource_use.access,
+            }),
+            ResourceHandle::Buffer(buffer) => barriers.buffer_barriers.push(BufferBarrier {
+                buffer,
+                src_stages,
+                src_access: self.write_access,
+                dst_stages: resource_use.stages,
+                dst_access: resource_use.access,
+            }),
+        }
+    }
+}
+
+/// Topological order of the live passes. Among the passes whose dependencies have executed,
+/// prefers one that does not depend on the pass executed last, then the one added first.
+fn schedule(dependencies: &[Vec<(usize, bool)>], live: &[bool]) -> Vec<usize> {
+    let mut remaining: Vec<usize> = dependencies
+        .iter()
+        .map(|pass_dependencies| {
+            pass_dependencies
+                .iter()
+                .filter(|(dependency, _)| live[*dependency])
+                .count()
+        })
+        .collect();
+    let mut scheduled = vec![false; dependencies.len()];
+    let mut order: Vec<usize> = Vec::new();
+
+    loop {
+        let ready = || {
+            (0..dependencies.len())
+                .filter(|&index| live[index] && !scheduled[index] && remaining[index] == 0)
+        };
+        let independent = ready().find(|&index| {
+            !order.last().is_some_and(|&last| {
+                dependencies[index]
+                    .iter()
+                    .any(|(dependency, _)| *dependency == last)
+            })
+        });
+        let next = match independent.or_else(|| ready().next()) {
+            Some(next) => next,
+            None => break,
+        };
+
+        scheduled[next] = true;
+        order.push(next);
+        for (index, pass_dependencies) in dependencies.iter().enumerate() {
+            remaining[index] -= pass_dependencies
+                .iter()
+                .filter(|(dependency, _)| *dependency == next)
+                .count();
+        }
+    }
+
+    order
+}
+
+/// Stages and access of an image access, with the layout it needs. `None` if the kind of
+/// pass cannot use the access, or the access cannot write.
+fn image_scope(
+    kind: PassKind,
+    access: ImageAccess,
+    write: bool,
+) -> Option<(PipelineStageFlags, AccessFlags, ImageLayout)> {
+    use Access::*;
+
+    match (access, write) {
+        (ImageAccess::ColorAttachment, _) if kind == PassKind::Graphics => Some((
+            PipelineStage::ColorAttachmentOutput.into(),
+            if write {
+                ColorAttachmentWrite.into()
+            } else {
+                ColorAttachmentRead.into()
+            },
+            ImageLayout::ColorAttachmentOptimal,
+        )),
+        (ImageAccess::DepthStencilAttachment, _) if kind == PassKind::Graphics => Some((
+            PipelineStageFlags::new()
+                .with_stage(PipelineStage::EarlyFragmentTests)
+                .with_stage(PipelineStage::LateFragmentTests),
+            if write {
+                AccessFlags::new()
+                    .with_access(DepthStencilAttachmentRead)
+                    .with_access(DepthStencilAttachmentWrite)
+            } else {
+                DepthStencilAttachmentRead.into()
+            },
+            if write {
+                ImageLayout::DepthStencilAttachmentOptimal
+            } else {
+                ImageLayout::DepthStencilReadOnlyOptimal
+            },
+        )),
+        (ImageAccess::Sampled, false) => Some((
+            shader_stages(kind)?,
+            ShaderSampledRead.into(),
+            ImageLayout::ShaderReadOnlyOptimal,
+        )),
+        (ImageAccess::Storage, _) => Some((
+            shader_stages(kind)?,
+            if write {
+                ShaderStorageWrite.into()
+            } else {
+                ShaderStorageRead.into()
+            },
+            ImageLayout::General,
+        )),
+        (ImageAccess::Transfer, true) => Some((
+            PipelineStage::Transfer.into(),
+            TransferWrite.into(),
+            ImageLayout::TransferDstOptimal,
+        )),
+        (ImageAccess::Transfer, false) => Some((
+            PipelineStage::Transfer.into(),
+            TransferRead.into(),
+            ImageLayout::TransferSrcOptimal,
+        )),
+        _ => None,
+    }
+}
+
+/// Stages and access of a buffer access. `None` if the kind of pass cannot use the access, or
+/// the access cannot write.
+fn buffer_scope(
+    kind: PassKind,
+    access: BufferAccess,
+    write: bool,
+) -> Option<(PipelineStageFlags, AccessFlags)> {
+    use Access::*;
+
+    match (access, write) {
+        (BufferAccess::Vertex, false) if kind == PassKind::Graphics => Some((
+            PipelineStage::VertexAttributeInput.into(),
+            VertexAttributeRead.into(),
+        )),
+        (BufferAccess::Index, false) if kind == PassKind::Graphics => {
+            Some((PipelineStage::IndexInput.into(), IndexRead.into()))
+        }
+        (BufferAccess::Indirect, false) if kind != PassKind::Transfer => Some((
+            PipelineStage::DrawIndirect.into(),
+            IndirectCommandRead.into(),
+        )),
+        (BufferAccess::Uniform, false) => Some((shader_stages(kind)?, UniformRead.into())),
+        (BufferAccess::Storage, true) => Some((shader_stages(kind)?, ShaderStorageWrite.into())),
+        (BufferAccess::Storage, false) => Some((shader_stages(kind)?, ShaderStorageRead.into())),
+        (BufferAccess::Transfer, true) => {
+            Some((PipelineStage::Transfer.into(), TransferWrite.into()))
+        }
+        (BufferAccess::Transfer, false) => {
+            Some((PipelineStage::Transfer.into(), TransferRead.into()))
+        }
+        _ => None,
+    }
+}
+
+fn shader_stages(kind: PassKind) -> Option<PipelineStageFlags> {
+    match kind {
+        PassKind::Graphics => Some(
+            PipelineStageFlags::new()
+                .with_stage(PipelineStage::PreRasterizationShaders)
+                .with_stage(PipelineStage::FragmentShader),
+        ),
+        PassKind::Compute => Some(PipelineStage::ComputeShader.into()),
+        PassKind::Transfer => None,
+    }
+}
+
+fn union_stages(a: PipelineStageFlags, b: PipelineStageFlags) -> PipelineStageFlags {
+    PipelineStageFlags(a.0 | b.0)
+}
+
+fn union_access(a: AccessFlags, b: AccessFlags) -> AccessFlags {
+    AccessFlags(a.0 | b.0)
+}
+
+fn contains_stages(a: PipelineStageFlags, b: PipelineStageFlags) -> bool {
+    a.0 & b.0 == b.0
+}
+
+fn contains_access(a: AccessFlags, b: AccessFlags) -> bool {
+    a.0 & b.0 == b.0
+}
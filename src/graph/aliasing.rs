@@ -0,0 +1,407 @@
+//! # Transient memory aliasing
+//! Images and buffers created by a [`RenderGraph`] only live from the first to the last pass
+//! using them. An [`AliasingPlan`] places them in one heap per memory type, so that resources
+//! whose lifetimes do not overlap share memory, and
+//! [`CompiledGraph::with_aliasing`] adds the barriers that make each resource wait for the
+//! previous users of its memory.
+//!
+//! [`TransientResources`] creates the resources on a device and binds them following the
+//! plan.
+
+use crate::core::{Error, Result};
+use crate::device::Device;
+use crate::graph::{
+    BufferBarrier, BufferHandle, CompiledGraph, GraphResources, ImageHandle, RenderGraph,
+    ResourceHandle, TransientLifetime,
+};
+use crate::resource::{
+    Buffer, DeviceMemory, Image, MemoryPropertyFlagsBuilder, MemoryRequirements,
+};
+use crate::sync::{AccessFlags, PipelineStageFlags};
+
+/// Offsets of the transient resources of a [`CompiledGraph`] in per memory type heaps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AliasingPlan {
+    placements: Vec<Placement>,
+    heaps: Vec<Heap>,
+    unaliased_size: u64,
+}
+
+/// Memory range of one transient resource.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placement {
+    lifetime: TransientLifetime,
+    memory_type_index: u32,
+    offset: u64,
+    size: u64,
+}
+
+/// Memory all transient resources of one memory type are placed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Heap {
+    memory_type_index: u32,
+    size: u64,
+}
+
+/// Transient images and buffers of a [`CompiledGraph`], bound to aliased memory.
+pub struct TransientResources<'a> {
+    // Resources are dropped before the memory they are bound to.
+    images: Vec<(ImageHandle, Image<'a>)>,
+    buffers: Vec<(BufferHandle, Buffer<'a>)>,
+    memory: Vec<DeviceMemory<'a>>,
+    plan: AliasingPlan,
+}
+
+impl AliasingPlan {
+    /// Places the transient resources of `compiled`, with the memory requirements and memory
+    /// type index `memory` returns for each of them. Resources are placed largest first, at
+    /// the lowest offset not used by a resource whose lifetime overlaps. When a heap holds
+    /// both buffers and images, offsets are aligned to `buffer_image_granularity` so that
+    /// they never share a page.
+    pub fn new(
+        compiled: &CompiledGraph,
+        buffer_image_granularity: u64,
+        mut memory: impl FnMut(ResourceHandle) -> (MemoryRequirements, u32),
+    ) -> Self {
+        let requests: Vec<(TransientLifetime, MemoryRequirements, u32)> = compiled
+            .transients()
+            .iter()
+            .map(|lifetime| {
+                let (requirements, memory_type_index) = memory(lifetime.resource());
+                (*lifetime, requirements, memory_type_index)
+            })
+            .collect();
+
+        let mut order: Vec<usize> = (0..requests.len()).collect();
+        order.sort_by_key(|&index| std::cmp::Reverse(requests[index].1.size()));
+
+        let mut placements: Vec<Placement> = Vec::with_capacity(requests.len());
+        for index in order {
+            let (lifetime, requirements, memory_type_index) = requests[index];
+            let mixed = requests.iter().any(|(other, _, other_memory_type_index)| {
+                *other_memory_type_index == memory_type_index
+                    && is_image(other.resource()) != is_image(lifetime.resource())
+            });
+            let alignment = if mixed {
+                requirements.alignment().max(buffer_image_granularity)
+            } else {
+                requirements.alignment()
+            };
+
+            let mut occupied: Vec<(u64, u64)> = placements
+                .iter()
+                .filter(|placement| {
+                    placement.memory_type_index == memory_type_index
+                        && placement.lifetime.overlaps(&lifetime)
+                })
+                .map(|placement| (placement.offset, placement.offset + placement.size))
+                .collect();
+            occupied.sort_unstable();
+
+            let mut offset = 0;
+            for (start, end) in occupied {
+                if align_up(offset, alignment) + requirements.size() <= start {
+                    break;
+                }
+                offset = offset.max(end);
+            }
+
+            placements.push(Placement {
+                lifetime,
+                memory_type_index,
+                offset: align_up(offset, alignment),
+                size: requirements.size(),
+            });
+        }
+        placements.sort_by_key(|placement| placement.lifetime.first_pass());
+
+        let mut heaps: Vec<Heap> = Vec::new();
+        for placement in &placements {
+            let end = placement.offset + placement.size;
+            match heaps
+                .iter_mut()
+                .find(|heap| heap.memory_type_index == placement.memory_type_index)
+            {
+                Some(heap) => heap.size = heap.size.max(end),
+                None => heaps.push(Heap {
+                    memory_type_index: placement.memory_type_index,
+                    size: end,
+                }),
+            }
+        }
+
+        Self {
+            unaliased_size: placements.iter().map(|placement| placement.size).sum(),
+            placements,
+            heaps,
+        }
+    }
+
+    /// Placements in the order of the first use of their resources.
+    pub fn placements(&self) -> &[Placement] {
+        &self.placements
+    }
+
+    pub fn placement(&self, resource: ResourceHandle) -> Option<&Placement> {
+        self.placements
+            .iter()
+            .find(|placement| placement.lifetime.resource() == resource)
+    }
+
+    pub fn heaps(&self) -> &[Heap] {
+        &self.heaps
+    }
+
+    /// Memory needed by all heaps.
+    pub fn aliased_size(&self) -> u64 {
+        self.heaps.iter().map(|heap| heap.size).sum()
+    }
+
+    /// Memory the transient resources would need without aliasing.
+    pub fn unaliased_size(&self) -> u64 {
+        self.unaliased_size
+    }
+
+    /// Memory saved by aliasing.
+    pub fn saved_size(&self) -> u64 {
+        self.unaliased_size.saturating_sub(self.aliased_size())
+    }
+
+    /// Placements sharing memory with `placement` that are used before it.
+    fn previous_users<'p>(
+        &'p self,
+        placement: &'p Placement,
+    ) -> impl Iterator<Item = &'p Placement> {
+        self.placements.iter().filter(move |other| {
+            other.memory_type_index == placement.memory_type_index
+                && other.lifetime.last_pass() < placement.lifetime.first_pass()
+                && other.offset < placement.offset + placement.size
+                && placement.offset < other.offset + other.size
+        })
+    }
+}
+
+impl Placement {
+    pub fn resource(&self) -> ResourceHandle {
+        self.lifetime.resource()
+    }
+
+    pub fn memory_type_index(&self) -> u32 {
+        self.memory_type_index
+    }
+
+    /// Offset of the resource in the heap of its memory type.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+impl Heap {
+    pub fn memory_type_index(&self) -> u32 {
+        self.memory_type_index
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+impl CompiledGraph {
+    /// Makes the first access of each transient resource in `plan` wait for all accesses of
+    /// the resources previously placed in the same memory. For images, the wait is added to
+    /// the transition out of the undefined layout, for buffers a barrier is added.
+    pub fn with_aliasing(mut self, plan: &AliasingPlan) -> Self {
+        for placement in &plan.placements {
+            let (src_stages, src_access) = plan.previous_users(placement).fold(
+                (PipelineStageFlags::new(), AccessFlags::new()),
+                |(stages, access), previous| {
+                    (
+                        PipelineStageFlags(stages.0 | previous.lifetime.last_stages.0),
+                        AccessFlags(access.0 | previous.lifetime.last_access.0),
+                    )
+                },
+            );
+            if src_stages.is_empty() {
+                continue;
+            }
+
+            let lifetime = &placement.lifetime;
+            let barriers = &mut self.passes[lifetime.first_pass()].barriers;
+            match lifetime.resource() {
+                ResourceHandle::Image(image) => {
+                    // The first access of an image always transitions it from the undefined
+                    // layout.
+                    if let Some(barrier) = barriers
+                        .image_barriers
+                        .iter_mut()
+                        .find(|barrier| barrier.image == image)
+                    {
+                        barrier.src_stages =
+                            PipelineStageFlags(barrier.src_stages.0 | src_stages.0);
+                        barrier.src_access = AccessFlags(barrier.src_access.0 | src_access.0);
+                    }
+                }
+                ResourceHandle::Buffer(buffer) => {
+                    match barriers
+                        .buffer_barriers
+                        .iter_mut()
+                        .find(|barrier| barrier.buffer == buffer)
+                    {
+                        Some(barrier) => {
+                            barrier.src_stages =
+                                PipelineStageFlags(barrier.src_stages.0 | src_stages.0);
+                            barrier.src_access = AccessFlags(barrier.src_access.0 | src_access.0);
+                        }
+                        None => barriers.buffer_barriers.push(BufferBarrier {
+                            buffer,
+                            src_stages,
+                            src_access,
+                            dst_stages: lifetime.first_stages,
+                            dst_access: lifetime.first_access,
+                        }),
+                    }
+                }
+            }
+        }
+
+        self
+    }
+}
+
+impl<'a> TransientResources<'a> {
+    /// Creates the transient images and buffers of `compiled`, which has to be compiled from
+    /// `graph`, and binds them to device-local memory placed by an [`AliasingPlan`]. The
+    /// compiled graph recorded with them needs [`CompiledGraph::with_aliasing`] of
+    /// [`plan`](Self::plan).
+    ///
+    /// # Errors
+    /// - [`NoSuitableMemoryType`](Error::NoSuitableMemoryType) if a resource allows no
+    ///   memory type.
+    /// - The errors of [`ImageBuilder::build`](crate::resource::ImageBuilder::build),
+    ///   [`BufferBuilder::build`](crate::resource::BufferBuilder::build),
+    ///   [`Device::allocate_memory`] and [`Image::bind_memory`].
+    pub fn new(device: &'a Device, graph: &RenderGraph, compiled: &CompiledGraph) -> Result<Self> {
+        let mut images = Vec::new();
+        let mut buffers = Vec::new();
+        let mut requirements = Vec::new();
+        for lifetime in compiled.transients() {
+            let resource_requirements = match lifetime.resource() {
+                ResourceHandle::Image(image) => {
+                    let builder = graph
+                        .image_builder(image)
+                        .ok_or(Error::UnboundGraphResource)?;
+                    let created = builder.build(device)?;
+                    let resource_requirements = created.memory_requirements();
+                    images.push((image, created));
+                    resource_requirements
+                }
+                ResourceHandle::Buffer(buffer) => {
+                    let builder = graph
+                        .buffer_builder(buffer)
+                        .ok_or(Error::UnboundGraphResource)?;
+                    let created = builder.build(device)?;
+                    let resource_requirements = created.memory_requirements();
+                    buffers.push((buffer, created));
+                    resource_requirements
+                }
+            };
+
+            let memory_properties = device.memory_properties();
+            let memory_type_index = memory_properties
+                .find_memory_type(
+                    resource_requirements.memory_type_bits(),
+                    MemoryPropertyFlagsBuilder::new().device_local(true).build(),
+                )
+                .or_else(|| {
+                    memory_properties.find_memory_type(
+                        resource_requirements.memory_type_bits(),
+                        Default::default(),
+                    )
+                })
+                .ok_or(Error::NoSuitableMemoryType)?;
+            requirements.push((
+                lifetime.resource(),
+                resource_requirements,
+                memory_type_index,
+            ));
+        }
+
+        let plan = AliasingPlan::new(
+            compiled,
+            device.limits().buffer_image_granularity,
+            |resource| {
+                let (_, resource_requirements, memory_type_index) = requirements
+                    .iter()
+                    .find(|(other, _, _)| *other == resource)
+                    .unwrap();
+                (*resource_requirements, *memory_type_index)
+            },
+        );
+
+        let memory = plan
+            .heaps
+            .iter()
+            .map(|heap| device.allocate_memory(heap.size, heap.memory_type_index))
+            .collect::<Result<Vec<_>>>()?;
+        let heap_memory = |placement: &Placement| {
+            let index = plan
+                .heaps
+                .iter()
+                .position(|heap| heap.memory_type_index == placement.memory_type_index)
+                .unwrap();
+            &memory[index]
+        };
+        // SAFETY: The memory is kept with the resources, which are dropped before it.
+        for (handle, image) in &mut images {
+            let placement = plan.placement(ResourceHandle::Image(*handle)).unwrap();
+            unsafe { image.bind_memory(heap_memory(placement), placement.offset)? };
+        }
+        for (handle, buffer) in &mut buffers {
+            let placement = plan.placement(ResourceHandle::Buffer(*handle)).unwrap();
+            unsafe { buffer.bind_memory(heap_memory(placement), placement.offset)? };
+        }
+
+        Ok(Self {
+            images,
+            buffers,
+            memory,
+            plan,
+        })
+    }
+
+    pub fn plan(&self) -> &AliasingPlan {
+        &self.plan
+    }
+
+    /// Binds the transient images and buffers to their handles.
+    pub fn bind<'r>(&'r self, resources: &mut GraphResources<'r>) {
+        for (handle, image) in &self.images {
+            resources.bind_image(*handle, image);
+        }
+        for (handle, buffer) in &self.buffers {
+            resources.bind_buffer(*handle, buffer);
+        }
+    }
+}
+
+impl<'a> std::fmt::Debug for TransientResources<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TransientResources")
+            .field("memory", &self.memory)
+            .field("plan", &self.plan)
+            .finish_non_exhaustive()
+    }
+}
+
+fn is_image(resource: ResourceHandle) -> bool {
+    matches!(resource, ResourceHandle::Image(_))
+}
+
+fn align_up(offset: u64, alignment: u64) -> u64 {
+    let alignment = alignment.max(1);
+    offset.div_ceil(alignment) * alignment
+}
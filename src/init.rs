@@ -34,7 +34,7 @@ pub struct ApplicationInfo {
     pub api_version: ApiVersion,
 }
 
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct ApiVersion(u32);
 
 #[derive(Default)]
@@ -65,10 +65,19 @@ pub(crate) struct DispatchLoaderInstance {
     pub(crate) vk_destroy_surface_khr: Option<ffi::PFN_vkDestroySurfaceKHR>,
 }
 
+#[derive(Clone)]
 pub(crate) struct DispatchLoaderPhysicalDevice {
     pub(crate) vk_get_physical_device_properties: ffi::PFN_vkGetPhysicalDeviceProperties,
     pub(crate) vk_get_physical_device_queue_family_properties:
         ffi::PFN_vkGetPhysicalDeviceQueueFamilyProperties,
+    pub(crate) vk_get_physical_device_memory_properties:
+        ffi::PFN_vkGetPhysicalDeviceMemoryProperties,
+    pub(crate) vk_get_physical_device_image_format_properties:
+        ffi::PFN_vkGetPhysicalDeviceImageFormatProperties,
+    pub(crate) vk_get_physical_device_features: ffi::PFN_vkGetPhysicalDeviceFeatures,
+    pub(crate) vk_get_physical_device_features2: Option<ffi::PFN_vkGetPhysicalDeviceFeatures2>,
+    pub(crate) vk_enumerate_device_extension_properties:
+        ffi::PFN_vkEnumerateDeviceExtensionProperties,
     pub(crate) vk_create_device: ffi::PFN_vkCreateDevice,
     pub(crate) vk_get_device_proc_addr: ffi::PFN_vkGetDeviceProcAddr,
     pub(crate) vk_get_physical_device_surface_capabilities_khr:
@@ -94,6 +103,96 @@ pub(crate) struct DispatchLoaderDevice {
     pub(crate) vk_destroy_image_view: ffi::PFN_vkDestroyImageView,
     pub(crate) vk_create_shader_module: ffi::PFN_vkCreateShaderModule,
     pub(crate) vk_destroy_shader_module: ffi::PFN_vkDestroyShaderModule,
+    pub(crate) vk_create_descriptor_set_layout: ffi::PFN_vkCreateDescriptorSetLayout,
+    pub(crate) vk_destroy_descriptor_set_layout: ffi::PFN_vkDestroyDescriptorSetLayout,
+    pub(crate) vk_create_pipeline_layout: ffi::PFN_vkCreatePipelineLayout,
+    pub(crate) vk_destroy_pipeline_layout: ffi::PFN_vkDestroyPipelineLayout,
+    pub(crate) vk_destroy_sampler: ffi::PFN_vkDestroySampler,
+    pub(crate) vk_create_compute_pipelines: ffi::PFN_vkCreateComputePipelines,
+    pub(crate) vk_destroy_pipeline: ffi::PFN_vkDestroyPipeline,
+    pub(crate) vk_destroy_buffer: ffi::PFN_vkDestroyBuffer,
+    pub(crate) vk_begin_command_buffer: ffi::PFN_vkBeginCommandBuffer,
+    pub(crate) vk_end_command_buffer: ffi::PFN_vkEndCommandBuffer,
+    pub(crate) vk_cmd_bind_pipeline: ffi::PFN_vkCmdBindPipeline,
+    pub(crate) vk_cmd_bind_descriptor_sets: ffi::PFN_vkCmdBindDescriptorSets,
+    pub(crate) vk_cmd_dispatch: ffi::PFN_vkCmdDispatch,
+    pub(crate) vk_cmd_dispatch_indirect: ffi::PFN_vkCmdDispatchIndirect,
+    pub(crate) vk_cmd_dispatch_base: Option<ffi::PFN_vkCmdDispatchBase>,
+    pub(crate) vk_create_pipeline_cache: ffi::PFN_vkCreatePipelineCache,
+    pub(crate) vk_destroy_pipeline_cache: ffi::PFN_vkDestroyPipelineCache,
+    pub(crate) vk_get_pipeline_cache_data: ffi::PFN_vkGetPipelineCacheData,
+    pub(crate) vk_merge_pipeline_caches: ffi::PFN_vkMergePipelineCaches,
+    pub(crate) vk_cmd_set_viewport: ffi::PFN_vkCmdSetViewport,
+    pub(crate) vk_cmd_set_scissor: ffi::PFN_vkCmdSetScissor,
+    pub(crate) vk_cmd_set_line_width: ffi::PFN_vkCmdSetLineWidth,
+    pub(crate) vk_cmd_set_depth_bias: ffi::PFN_vkCmdSetDepthBias,
+    pub(crate) vk_cmd_set_blend_constants: ffi::PFN_vkCmdSetBlendConstants,
+    pub(crate) vk_cmd_set_depth_bounds: ffi::PFN_vkCmdSetDepthBounds,
+    pub(crate) vk_cmd_set_stencil_compare_mask: ffi::PFN_vkCmdSetStencilCompareMask,
+    pub(crate) vk_cmd_set_stencil_write_mask: ffi::PFN_vkCmdSetStencilWriteMask,
+    pub(crate) vk_cmd_set_stencil_reference: ffi::PFN_vkCmdSetStencilReference,
+    pub(crate) vk_cmd_set_cull_mode: Option<ffi::PFN_vkCmdSetCullMode>,
+    pub(crate) vk_cmd_set_front_face: Option<ffi::PFN_vkCmdSetFrontFace>,
+    pub(crate) vk_cmd_set_primitive_topology: Option<ffi::PFN_vkCmdSetPrimitiveTopology>,
+    pub(crate) vk_cmd_set_depth_test_enable: Option<ffi::PFN_vkCmdSetDepthTestEnable>,
+    pub(crate) vk_cmd_set_depth_write_enable: Option<ffi::PFN_vkCmdSetDepthWriteEnable>,
+    pub(crate) vk_create_graphics_pipelines: ffi::PFN_vkCreateGraphicsPipelines,
+    pub(crate) vk_create_render_pass: ffi::PFN_vkCreateRenderPass,
+    pub(crate) vk_destroy_render_pass: ffi::PFN_vkDestroyRenderPass,
+    pub(crate) vk_cmd_begin_rendering: Option<ffi::PFN_vkCmdBeginRendering>,
+    pub(crate) vk_cmd_end_rendering: Option<ffi::PFN_vkCmdEndRendering>,
+    pub(crate) vk_create_buffer: ffi::PFN_vkCreateBuffer,
+    pub(crate) vk_get_buffer_memory_requirements: ffi::PFN_vkGetBufferMemoryRequirements,
+    pub(crate) vk_get_buffer_memory_requirements2: Option<ffi::PFN_vkGetBufferMemoryRequirements2>,
+    pub(crate) vk_bind_buffer_memory: ffi::PFN_vkBindBufferMemory,
+    pub(crate) vk_free_memory: ffi::PFN_vkFreeMemory,
+    pub(crate) vk_allocate_memory: ffi::PFN_vkAllocateMemory,
+    pub(crate) vk_map_memory: ffi::PFN_vkMapMemory,
+    pub(crate) vk_unmap_memory: ffi::PFN_vkUnmapMemory,
+    pub(crate) vk_flush_mapped_memory_ranges: ffi::PFN_vkFlushMappedMemoryRanges,
+    pub(crate) vk_invalidate_mapped_memory_ranges: ffi::PFN_vkInvalidateMappedMemoryRanges,
+    pub(crate) vk_create_image: ffi::PFN_vkCreateImage,
+    pub(crate) vk_destroy_image: ffi::PFN_vkDestroyImage,
+    pub(crate) vk_get_image_memory_requirements: ffi::PFN_vkGetImageMemoryRequirements,
+    pub(crate) vk_get_image_memory_requirements2: Option<ffi::PFN_vkGetImageMemoryRequirements2>,
+    pub(crate) vk_bind_image_memory: ffi::PFN_vkBindImageMemory,
+    pub(crate) vk_create_sampler: ffi::PFN_vkCreateSampler,
+    pub(crate) vk_create_descriptor_pool: ffi::PFN_vkCreateDescriptorPool,
+    pub(crate) vk_destroy_descriptor_pool: ffi::PFN_vkDestroyDescriptorPool,
+    pub(crate) vk_reset_descriptor_pool: ffi::PFN_vkResetDescriptorPool,
+    pub(crate) vk_allocate_descriptor_sets: ffi::PFN_vkAllocateDescriptorSets,
+    pub(crate) vk_free_descriptor_sets: ffi::PFN_vkFreeDescriptorSets,
+    pub(crate) vk_update_descriptor_sets: ffi::PFN_vkUpdateDescriptorSets,
+    pub(crate) vk_create_buffer_view: ffi::PFN_vkCreateBufferView,
+    pub(crate) vk_destroy_buffer_view: ffi::PFN_vkDestroyBufferView,
+    pub(crate) vk_cmd_push_constants: ffi::PFN_vkCmdPushConstants,
+    pub(crate) vk_create_fence: ffi::PFN_vkCreateFence,
+    pub(crate) vk_destroy_fence: ffi::PFN_vkDestroyFence,
+    pub(crate) vk_reset_fences: ffi::PFN_vkResetFences,
+    pub(crate) vk_get_fence_status: ffi::PFN_vkGetFenceStatus,
+    pub(crate) vk_wait_for_fences: ffi::PFN_vkWaitForFences,
+    pub(crate) vk_queue_submit: ffi::PFN_vkQueueSubmit,
+    pub(crate) vk_queue_wait_idle: ffi::PFN_vkQueueWaitIdle,
+    pub(crate) vk_cmd_copy_buffer: ffi::PFN_vkCmdCopyBuffer,
+    pub(crate) vk_cmd_copy_buffer_to_image: ffi::PFN_vkCmdCopyBufferToImage,
+    pub(crate) vk_cmd_pipeline_barrier: ffi::PFN_vkCmdPipelineBarrier,
+    pub(crate) vk_create_event: ffi::PFN_vkCreateEvent,
+    pub(crate) vk_destroy_event: ffi::PFN_vkDestroyEvent,
+    pub(crate) vk_get_event_status: ffi::PFN_vkGetEventStatus,
+    pub(crate) vk_cmd_set_event: ffi::PFN_vkCmdSetEvent,
+    pub(crate) vk_cmd_reset_event: ffi::PFN_vkCmdResetEvent,
+    pub(crate) vk_cmd_wait_events: ffi::PFN_vkCmdWaitEvents,
+    pub(crate) vk_create_query_pool: ffi::PFN_vkCreateQueryPool,
+    pub(crate) vk_destroy_query_pool: ffi::PFN_vkDestroyQueryPool,
+    pub(crate) vk_get_query_pool_results: ffi::PFN_vkGetQueryPoolResults,
+    pub(crate) vk_cmd_reset_query_pool: ffi::PFN_vkCmdResetQueryPool,
+    pub(crate) vk_cmd_write_timestamp: ffi::PFN_vkCmdWriteTimestamp,
+    pub(crate) vk_cmd_pipeline_barrier2: Option<ffi::PFN_vkCmdPipelineBarrier2>,
+    pub(crate) vk_cmd_set_event2: Option<ffi::PFN_vkCmdSetEvent2>,
+    pub(crate) vk_cmd_reset_event2: Option<ffi::PFN_vkCmdResetEvent2>,
+    pub(crate) vk_cmd_wait_events2: Option<ffi::PFN_vkCmdWaitEvents2>,
+    pub(crate) vk_cmd_write_timestamp2: Option<ffi::PFN_vkCmdWriteTimestamp2>,
+    pub(crate) vk_queue_submit2: Option<ffi::PFN_vkQueueSubmit2>,
 }
 
 impl Instance {
@@ -440,6 +539,7 @@ impl ApiVersion {
     pub const V1_0: Self = Self::new(0, 1, 0, 0);
     pub const V1_1: Self = Self::new(0, 1, 1, 0);
     pub const V1_2: Self = Self::new(0, 1, 2, 0);
+    pub const V1_3: Self = Self::new(0, 1, 3, 0);
 
     pub const fn new(variant: u32, major: u32, minor: u32, patch: u32) -> Self {
         Self((variant << 29) | (major << 22) | (minor << 12) | patch)
@@ -477,16 +577,13 @@ impl DispatchLoaderInstance {
             vk_enumerate_instance_version: unsafe {
                 vk_get_instance_proc_addr(
                     std::ptr::null_mut(),
-                    "vkEnumerateInstanceVersion\0".as_ptr().cast(),
+                    c"vkEnumerateInstanceVersion".as_ptr().cast(),
                 )
                 .map(|pfn| std::mem::transmute(pfn))
             },
             vk_create_instance: unsafe {
-                vk_get_instance_proc_addr(
-                    std::ptr::null_mut(),
-                    "vkCreateInstance\0".as_ptr().cast(),
-                )
-                .map(|pfn| std::mem::transmute(pfn))
+                vk_get_instance_proc_addr(std::ptr::null_mut(), c"vkCreateInstance".as_ptr().cast())
+                    .map(|pfn| std::mem::transmute(pfn))
             },
             ..Default::default()
         }
@@ -495,10 +592,10 @@ impl DispatchLoaderInstance {
     unsafe fn load(&mut self, instance: *mut ffi::VkInstance_T) {
         let vk_get_instance_proc_addr = self.vk_get_instance_proc_addr.unwrap();
         self.vk_destroy_instance =
-            vk_get_instance_proc_addr(instance, "vkDestroyInstance\0".as_ptr().cast())
+            vk_get_instance_proc_addr(instance, c"vkDestroyInstance".as_ptr().cast())
                 .map(|pfn| std::mem::transmute(pfn));
         self.vk_enumerate_physical_devices =
-            vk_get_instance_proc_addr(instance, "vkEnumeratePhysicalDevices\0".as_ptr().cast())
+            vk_get_instance_proc_addr(instance, c"vkEnumeratePhysicalDevices".as_ptr().cast())
                 .map(|pfn| std::mem::transmute(pfn));
 
         #[cfg(any(
@@ -510,21 +607,21 @@ impl DispatchLoaderInstance {
         ))]
         {
             self.vk_create_xcb_surface_khr =
-                vk_get_instance_proc_addr(instance, "vkCreateXcbSurfaceKHR\0".as_ptr().cast())
+                vk_get_instance_proc_addr(instance, c"vkCreateXcbSurfaceKHR".as_ptr().cast())
                     .map(|pfn| std::mem::transmute(pfn));
             self.vk_create_xlib_surface_khr =
-                vk_get_instance_proc_addr(instance, "vkCreateXlibSurfaceKHR\0".as_ptr().cast())
+                vk_get_instance_proc_addr(instance, c"vkCreateXlibSurfaceKHR".as_ptr().cast())
                     .map(|pfn| std::mem::transmute(pfn));
         }
         #[cfg(target_os = "windows")]
         {
             self.vk_create_win32_surface_khr =
-                vk_get_instance_proc_addr(instance, "vkCreateWin32SurfaceKHR\0".as_ptr().cast())
+                vk_get_instance_proc_addr(instance, c"vkCreateWin32SurfaceKHR".as_ptr().cast())
                     .map(|pfn| std::mem::transmute(pfn));
         }
 
         self.vk_destroy_surface_khr =
-            vk_get_instance_proc_addr(instance, "vkDestroySurfaceKHR\0".as_ptr().cast())
+            vk_get_instance_proc_addr(instance, c"vkDestroySurfaceKHR".as_ptr().cast())
                 .map(|pfn| std::mem::transmute(pfn));
     }
 }
@@ -540,45 +637,76 @@ impl DispatchLoaderPhysicalDevice {
             Self {
                 vk_get_physical_device_properties: vk_get_instance_proc_addr(
                     instance.handle.as_ptr(),
-                    "vkGetPhysicalDeviceProperties\0".as_ptr().cast(),
+                    c"vkGetPhysicalDeviceProperties".as_ptr().cast(),
                 )
                 .map(|pfn| std::mem::transmute(pfn))
                 .unwrap(),
                 vk_get_physical_device_queue_family_properties: vk_get_instance_proc_addr(
                     instance.handle.as_ptr(),
-                    "vkGetPhysicalDeviceQueueFamilyProperties\0".as_ptr().cast(),
+                    c"vkGetPhysicalDeviceQueueFamilyProperties".as_ptr().cast(),
+                )
+                .map(|pfn| std::mem::transmute(pfn))
+                .unwrap(),
+                vk_get_physical_device_memory_properties: vk_get_instance_proc_addr(
+                    instance.handle.as_ptr(),
+                    c"vkGetPhysicalDeviceMemoryProperties".as_ptr().cast(),
+                )
+                .map(|pfn| std::mem::transmute(pfn))
+                .unwrap(),
+                vk_get_physical_device_image_format_properties: vk_get_instance_proc_addr(
+                    instance.handle.as_ptr(),
+                    c"vkGetPhysicalDeviceImageFormatProperties".as_ptr().cast(),
+                )
+                .map(|pfn| std::mem::transmute(pfn))
+                .unwrap(),
+                vk_get_physical_device_features: vk_get_instance_proc_addr(
+                    instance.handle.as_ptr(),
+                    c"vkGetPhysicalDeviceFeatures".as_ptr().cast(),
+                )
+                .map(|pfn| std::mem::transmute(pfn))
+                .unwrap(),
+                vk_get_physical_device_features2: vk_get_instance_proc_addr(
+                    instance.handle.as_ptr(),
+                    c"vkGetPhysicalDeviceFeatures2".as_ptr().cast(),
+                )
+                .or_else(|| {
+                    vk_get_instance_proc_addr(
+                        instance.handle.as_ptr(),
+                        c"vkGetPhysicalDeviceFeatures2KHR".as_ptr().cast(),
+                    )
+                })
+                .map(|pfn| std::mem::transmute(pfn)),
+                vk_enumerate_device_extension_properties: vk_get_instance_proc_addr(
+                    instance.handle.as_ptr(),
+                    c"vkEnumerateDeviceExtensionProperties".as_ptr().cast(),
                 )
                 .map(|pfn| std::mem::transmute(pfn))
                 .unwrap(),
                 vk_create_device: vk_get_instance_proc_addr(
                     instance.handle.as_ptr(),
-                    "vkCreateDevice\0".as_ptr().cast(),
+                    c"vkCreateDevice".as_ptr().cast(),
                 )
                 .map(|pfn| std::mem::transmute(pfn))
                 .unwrap(),
                 vk_get_device_proc_addr: vk_get_instance_proc_addr(
                     instance.handle.as_ptr(),
-                    "vkGetDeviceProcAddr\0".as_ptr().cast(),
+                    c"vkGetDeviceProcAddr".as_ptr().cast(),
                 )
                 .map(|pfn| std::mem::transmute(pfn))
                 .unwrap(),
                 vk_get_physical_device_surface_capabilities_khr: vk_get_instance_proc_addr(
                     instance.handle.as_ptr(),
-                    "vkGetPhysicalDeviceSurfaceCapabilitiesKHR\0"
-                        .as_ptr()
-                        .cast(),
+                    c"vkGetPhysicalDeviceSurfaceCapabilitiesKHR".as_ptr().cast(),
                 )
                 .map(|pfn| std::mem::transmute(pfn)),
                 vk_get_physical_device_surface_formats_khr: vk_get_instance_proc_addr(
                     instance.handle.as_ptr(),
-                    "vkGetPhysicalDeviceSurfaceFormatsKHR\0".as_ptr().cast(),
+                    c"vkGetPhysicalDeviceSurfaceFormatsKHR".as_ptr().cast(),
                 )
                 .map(|pfn| std::mem::transmute(pfn)),
                 vk_get_physical_device_surface_present_modes_khr: vk_get_instance_proc_addr(
                     instance.handle.as_ptr(),
-                    "vkGetPhysicalDeviceSurfacePresentModesKHR\0"
-                        .as_ptr()
-                        .cast(),
+                    c"vkGetPhysicalDeviceSurfacePresentModesKHR".as_ptr().cast(),
                 )
                 .map(|pfn| std::mem::transmute(pfn)),
             }
@@ -595,85 +723,666 @@ impl DispatchLoaderDevice {
         Self {
             vk_destroy_device: vk_get_device_proc_addr(
                 device_handle,
-                "vkDestroyDevice\0".as_ptr().cast(),
+                c"vkDestroyDevice".as_ptr().cast(),
             )
             .map(|pfn| std::mem::transmute(pfn))
             .unwrap(),
             vk_get_device_queue: vk_get_device_proc_addr(
                 device_handle,
-                "vkGetDeviceQueue\0".as_ptr().cast(),
+                c"vkGetDeviceQueue".as_ptr().cast(),
             )
             .map(|pfn| std::mem::transmute(pfn))
             .unwrap(),
             vk_create_command_pool: vk_get_device_proc_addr(
                 device_handle,
-                "vkCreateCommandPool\0".as_ptr().cast(),
+                c"vkCreateCommandPool".as_ptr().cast(),
             )
             .map(|pfn| std::mem::transmute(pfn))
             .unwrap(),
             vk_destroy_command_pool: vk_get_device_proc_addr(
                 device_handle,
-                "vkDestroyCommandPool\0".as_ptr().cast(),
+                c"vkDestroyCommandPool".as_ptr().cast(),
             )
             .map(|pfn| std::mem::transmute(pfn))
             .unwrap(),
             vk_allocate_command_buffers: vk_get_device_proc_addr(
                 device_handle,
-                "vkAllocateCommandBuffers\0".as_ptr().cast(),
+                c"vkAllocateCommandBuffers".as_ptr().cast(),
             )
             .map(|pfn| std::mem::transmute(pfn))
             .unwrap(),
             vk_create_semaphore: vk_get_device_proc_addr(
                 device_handle,
-                "vkCreateSemaphore\0".as_ptr().cast(),
+                c"vkCreateSemaphore".as_ptr().cast(),
             )
             .map(|pfn| std::mem::transmute(pfn))
             .unwrap(),
             vk_destroy_semaphore: vk_get_device_proc_addr(
                 device_handle,
-                "vkDestroySemaphore\0".as_ptr().cast(),
+                c"vkDestroySemaphore".as_ptr().cast(),
             )
             .map(|pfn| std::mem::transmute(pfn))
             .unwrap(),
             vk_create_swapchain_khr: vk_get_device_proc_addr(
                 device_handle,
-                "vkCreateSwapchainKHR\0".as_ptr().cast(),
+                c"vkCreateSwapchainKHR".as_ptr().cast(),
             )
             .map(|pfn| std::mem::transmute(pfn)),
             vk_destroy_swapchain_khr: vk_get_device_proc_addr(
                 device_handle,
-                "vkDestroySwapchainKHR\0".as_ptr().cast(),
+                c"vkDestroySwapchainKHR".as_ptr().cast(),
             )
             .map(|pfn| std::mem::transmute(pfn)),
             vk_get_swapchain_images_khr: vk_get_device_proc_addr(
                 device_handle,
-                "vkGetSwapchainImagesKHR\0".as_ptr().cast(),
+                c"vkGetSwapchainImagesKHR".as_ptr().cast(),
             )
             .map(|pfn| std::mem::transmute(pfn)),
             vk_create_image_view: vk_get_device_proc_addr(
                 device_handle,
-                "vkCreateImageView\0".as_ptr().cast(),
+                c"vkCreateImageView".as_ptr().cast(),
             )
             .map(|pfn| std::mem::transmute(pfn))
             .unwrap(),
             vk_destroy_image_view: vk_get_device_proc_addr(
                 device_handle,
-                "vkDestroyImageView\0".as_ptr().cast(),
+                c"vkDestroyImageView".as_ptr().cast(),
             )
             .map(|pfn| std::mem::transmute(pfn))
             .unwrap(),
             vk_create_shader_module: vk_get_device_proc_addr(
                 device_handle,
-                "vkCreateShaderModule\0".as_ptr().cast(),
+                c"vkCreateShaderModule".as_ptr().cast(),
             )
             .map(|pfn| std::mem::transmute(pfn))
             .unwrap(),
             vk_destroy_shader_module: vk_get_device_proc_addr(
                 device_handle,
-                "vkDestroyShaderModule\0".as_ptr().cast(),
+                c"vkDestroyShaderModule".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_create_descriptor_set_layout: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCreateDescriptorSetLayout".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_destroy_descriptor_set_layout: vk_get_device_proc_addr(
+                device_handle,
+                c"vkDestroyDescriptorSetLayout".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_create_pipeline_layout: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCreatePipelineLayout".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_destroy_pipeline_layout: vk_get_device_proc_addr(
+                device_handle,
+                c"vkDestroyPipelineLayout".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_destroy_sampler: vk_get_device_proc_addr(
+                device_handle,
+                c"vkDestroySampler".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_create_compute_pipelines: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCreateComputePipelines".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_destroy_pipeline: vk_get_device_proc_addr(
+                device_handle,
+                c"vkDestroyPipeline".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_destroy_buffer: vk_get_device_proc_addr(
+                device_handle,
+                c"vkDestroyBuffer".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_begin_command_buffer: vk_get_device_proc_addr(
+                device_handle,
+                c"vkBeginCommandBuffer".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_end_command_buffer: vk_get_device_proc_addr(
+                device_handle,
+                c"vkEndCommandBuffer".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_cmd_bind_pipeline: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdBindPipeline".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_cmd_bind_descriptor_sets: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdBindDescriptorSets".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_cmd_dispatch: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdDispatch".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_cmd_dispatch_indirect: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdDispatchIndirect".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_cmd_dispatch_base: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdDispatchBase".as_ptr().cast(),
+            )
+            .or_else(|| {
+                vk_get_device_proc_addr(device_handle, c"vkCmdDispatchBaseKHR".as_ptr().cast())
+            })
+            .map(|pfn| std::mem::transmute(pfn)),
+            vk_create_pipeline_cache: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCreatePipelineCache".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_destroy_pipeline_cache: vk_get_device_proc_addr(
+                device_handle,
+                c"vkDestroyPipelineCache".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_get_pipeline_cache_data: vk_get_device_proc_addr(
+                device_handle,
+                c"vkGetPipelineCacheData".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_merge_pipeline_caches: vk_get_device_proc_addr(
+                device_handle,
+                c"vkMergePipelineCaches".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_cmd_set_viewport: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdSetViewport".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_cmd_set_scissor: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdSetScissor".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_cmd_set_line_width: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdSetLineWidth".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_cmd_set_depth_bias: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdSetDepthBias".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_cmd_set_blend_constants: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdSetBlendConstants".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_cmd_set_depth_bounds: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdSetDepthBounds".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_cmd_set_stencil_compare_mask: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdSetStencilCompareMask".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_cmd_set_stencil_write_mask: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdSetStencilWriteMask".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_cmd_set_stencil_reference: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdSetStencilReference".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_cmd_set_cull_mode: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdSetCullMode".as_ptr().cast(),
+            )
+            .or_else(|| {
+                vk_get_device_proc_addr(device_handle, c"vkCmdSetCullModeEXT".as_ptr().cast())
+            })
+            .map(|pfn| std::mem::transmute(pfn)),
+            vk_cmd_set_front_face: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdSetFrontFace".as_ptr().cast(),
+            )
+            .or_else(|| {
+                vk_get_device_proc_addr(device_handle, c"vkCmdSetFrontFaceEXT".as_ptr().cast())
+            })
+            .map(|pfn| std::mem::transmute(pfn)),
+            vk_cmd_set_primitive_topology: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdSetPrimitiveTopology".as_ptr().cast(),
+            )
+            .or_else(|| {
+                vk_get_device_proc_addr(
+                    device_handle,
+                    c"vkCmdSetPrimitiveTopologyEXT".as_ptr().cast(),
+                )
+            })
+            .map(|pfn| std::mem::transmute(pfn)),
+            vk_cmd_set_depth_test_enable: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdSetDepthTestEnable".as_ptr().cast(),
+            )
+            .or_else(|| {
+                vk_get_device_proc_addr(
+                    device_handle,
+                    c"vkCmdSetDepthTestEnableEXT".as_ptr().cast(),
+                )
+            })
+            .map(|pfn| std::mem::transmute(pfn)),
+            vk_cmd_set_depth_write_enable: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdSetDepthWriteEnable".as_ptr().cast(),
+            )
+            .or_else(|| {
+                vk_get_device_proc_addr(
+                    device_handle,
+                    c"vkCmdSetDepthWriteEnableEXT".as_ptr().cast(),
+                )
+            })
+            .map(|pfn| std::mem::transmute(pfn)),
+            vk_create_graphics_pipelines: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCreateGraphicsPipelines".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_create_render_pass: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCreateRenderPass".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_destroy_render_pass: vk_get_device_proc_addr(
+                device_handle,
+                c"vkDestroyRenderPass".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_cmd_begin_rendering: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdBeginRendering".as_ptr().cast(),
+            )
+            .or_else(|| {
+                vk_get_device_proc_addr(device_handle, c"vkCmdBeginRenderingKHR".as_ptr().cast())
+            })
+            .map(|pfn| std::mem::transmute(pfn)),
+            vk_cmd_end_rendering: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdEndRendering".as_ptr().cast(),
+            )
+            .or_else(|| {
+                vk_get_device_proc_addr(device_handle, c"vkCmdEndRenderingKHR".as_ptr().cast())
+            })
+            .map(|pfn| std::mem::transmute(pfn)),
+            vk_create_buffer: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCreateBuffer".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_get_buffer_memory_requirements: vk_get_device_proc_addr(
+                device_handle,
+                c"vkGetBufferMemoryRequirements".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_get_buffer_memory_requirements2: vk_get_device_proc_addr(
+                device_handle,
+                c"vkGetBufferMemoryRequirements2".as_ptr().cast(),
+            )
+            .or_else(|| {
+                vk_get_device_proc_addr(
+                    device_handle,
+                    c"vkGetBufferMemoryRequirements2KHR".as_ptr().cast(),
+                )
+            })
+            .map(|pfn| std::mem::transmute(pfn)),
+            vk_bind_buffer_memory: vk_get_device_proc_addr(
+                device_handle,
+                c"vkBindBufferMemory".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_free_memory: vk_get_device_proc_addr(device_handle, c"vkFreeMemory".as_ptr().cast())
+                .map(|pfn| std::mem::transmute(pfn))
+                .unwrap(),
+            vk_allocate_memory: vk_get_device_proc_addr(
+                device_handle,
+                c"vkAllocateMemory".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_map_memory: vk_get_device_proc_addr(device_handle, c"vkMapMemory".as_ptr().cast())
+                .map(|pfn| std::mem::transmute(pfn))
+                .unwrap(),
+            vk_unmap_memory: vk_get_device_proc_addr(
+                device_handle,
+                c"vkUnmapMemory".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_flush_mapped_memory_ranges: vk_get_device_proc_addr(
+                device_handle,
+                c"vkFlushMappedMemoryRanges".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_invalidate_mapped_memory_ranges: vk_get_device_proc_addr(
+                device_handle,
+                c"vkInvalidateMappedMemoryRanges".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_create_image: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCreateImage".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_destroy_image: vk_get_device_proc_addr(
+                device_handle,
+                c"vkDestroyImage".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_get_image_memory_requirements: vk_get_device_proc_addr(
+                device_handle,
+                c"vkGetImageMemoryRequirements".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_get_image_memory_requirements2: vk_get_device_proc_addr(
+                device_handle,
+                c"vkGetImageMemoryRequirements2".as_ptr().cast(),
+            )
+            .or_else(|| {
+                vk_get_device_proc_addr(
+                    device_handle,
+                    c"vkGetImageMemoryRequirements2KHR".as_ptr().cast(),
+                )
+            })
+            .map(|pfn| std::mem::transmute(pfn)),
+            vk_bind_image_memory: vk_get_device_proc_addr(
+                device_handle,
+                c"vkBindImageMemory".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_create_sampler: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCreateSampler".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_create_descriptor_pool: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCreateDescriptorPool".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_destroy_descriptor_pool: vk_get_device_proc_addr(
+                device_handle,
+                c"vkDestroyDescriptorPool".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_reset_descriptor_pool: vk_get_device_proc_addr(
+                device_handle,
+                c"vkResetDescriptorPool".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_allocate_descriptor_sets: vk_get_device_proc_addr(
+                device_handle,
+                c"vkAllocateDescriptorSets".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_free_descriptor_sets: vk_get_device_proc_addr(
+                device_handle,
+                c"vkFreeDescriptorSets".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_update_descriptor_sets: vk_get_device_proc_addr(
+                device_handle,
+                c"vkUpdateDescriptorSets".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_create_buffer_view: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCreateBufferView".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_destroy_buffer_view: vk_get_device_proc_addr(
+                device_handle,
+                c"vkDestroyBufferView".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_cmd_push_constants: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdPushConstants".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_create_fence: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCreateFence".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_destroy_fence: vk_get_device_proc_addr(
+                device_handle,
+                c"vkDestroyFence".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_reset_fences: vk_get_device_proc_addr(
+                device_handle,
+                c"vkResetFences".as_ptr().cast(),
             )
             .map(|pfn| std::mem::transmute(pfn))
             .unwrap(),
+            vk_get_fence_status: vk_get_device_proc_addr(
+                device_handle,
+                c"vkGetFenceStatus".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_wait_for_fences: vk_get_device_proc_addr(
+                device_handle,
+                c"vkWaitForFences".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_queue_submit: vk_get_device_proc_addr(
+                device_handle,
+                c"vkQueueSubmit".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_queue_wait_idle: vk_get_device_proc_addr(
+                device_handle,
+                c"vkQueueWaitIdle".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_cmd_copy_buffer: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdCopyBuffer".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_cmd_copy_buffer_to_image: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdCopyBufferToImage".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_cmd_pipeline_barrier: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdPipelineBarrier".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_create_event: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCreateEvent".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_destroy_event: vk_get_device_proc_addr(
+                device_handle,
+                c"vkDestroyEvent".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_get_event_status: vk_get_device_proc_addr(
+                device_handle,
+                c"vkGetEventStatus".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_cmd_set_event: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdSetEvent".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_cmd_reset_event: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdResetEvent".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_cmd_wait_events: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdWaitEvents".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_create_query_pool: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCreateQueryPool".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_destroy_query_pool: vk_get_device_proc_addr(
+                device_handle,
+                c"vkDestroyQueryPool".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_get_query_pool_results: vk_get_device_proc_addr(
+                device_handle,
+                c"vkGetQueryPoolResults".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_cmd_reset_query_pool: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdResetQueryPool".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_cmd_write_timestamp: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdWriteTimestamp".as_ptr().cast(),
+            )
+            .map(|pfn| std::mem::transmute(pfn))
+            .unwrap(),
+            vk_cmd_pipeline_barrier2: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdPipelineBarrier2".as_ptr().cast(),
+            )
+            .or_else(|| {
+                vk_get_device_proc_addr(device_handle, c"vkCmdPipelineBarrier2KHR".as_ptr().cast())
+            })
+            .map(|pfn| std::mem::transmute(pfn)),
+            vk_cmd_set_event2: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdSetEvent2".as_ptr().cast(),
+            )
+            .or_else(|| {
+                vk_get_device_proc_addr(device_handle, c"vkCmdSetEvent2KHR".as_ptr().cast())
+            })
+            .map(|pfn| std::mem::transmute(pfn)),
+            vk_cmd_reset_event2: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdResetEvent2".as_ptr().cast(),
+            )
+            .or_else(|| {
+                vk_get_device_proc_addr(device_handle, c"vkCmdResetEvent2KHR".as_ptr().cast())
+            })
+            .map(|pfn| std::mem::transmute(pfn)),
+            vk_cmd_wait_events2: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdWaitEvents2".as_ptr().cast(),
+            )
+            .or_else(|| {
+                vk_get_device_proc_addr(device_handle, c"vkCmdWaitEvents2KHR".as_ptr().cast())
+            })
+            .map(|pfn| std::mem::transmute(pfn)),
+            vk_cmd_write_timestamp2: vk_get_device_proc_addr(
+                device_handle,
+                c"vkCmdWriteTimestamp2".as_ptr().cast(),
+            )
+            .or_else(|| {
+                vk_get_device_proc_addr(device_handle, c"vkCmdWriteTimestamp2KHR".as_ptr().cast())
+            })
+            .map(|pfn| std::mem::transmute(pfn)),
+            vk_queue_submit2: vk_get_device_proc_addr(
+                device_handle,
+                c"vkQueueSubmit2".as_ptr().cast(),
+            )
+            .or_else(|| {
+                vk_get_device_proc_addr(device_handle, c"vkQueueSubmit2KHR".as_ptr().cast())
+            })
+            .map(|pfn| std::mem::transmute(pfn)),
         }
     }
 }
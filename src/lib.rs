@@ -2,13 +2,22 @@
 //! Vulkan is a low-overhead, cross-platform API, open standard for 3D graphics and computing.
 pub mod command_buffer;
 pub mod core;
+/// Descriptors are the way shaders access resources such as buffers, images and samplers.
+pub mod descriptor;
 /// Devices and Queues are the primary objects used to interact with a Vulkan implementation.
 pub mod device;
 pub mod format;
+pub mod graph;
 /// An application must initialize Vulkan by creating [`Instance`](init::Instance) object.
 pub mod init;
 pub mod pipeline;
+/// Queries record statistics and timestamps of commands executed on the device.
+pub mod query;
+/// Render passes describe the attachments of a set of subpasses and how their contents are
+/// loaded and stored.
+pub mod render_pass;
 pub mod resource;
+pub mod sampler;
 pub mod shaders;
 pub mod sync;
 /// Window System Integration, between Vulkan and the various forms of displaying the results of
@@ -1,11 +1,28 @@
 #![warn(missing_debug_implementations)]
 
+use std::ffi::CString;
 use std::fmt;
-
-use crate::core::Rect2D;
+use std::io;
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+#[cfg(not(target_pointer_width = "64"))]
+use std::num::NonZeroU64;
+use std::path::Path;
+#[cfg(target_pointer_width = "64")]
+use std::ptr::NonNull;
+
+use crate::core::{Error, Rect2D, Result};
+use crate::descriptor::{
+    DescriptorSet, DescriptorSetLayout, DescriptorSetLayoutBinding, DescriptorSetLayoutBuilder,
+    DescriptorType, LayoutBinding,
+};
+use crate::device::{Device, PhysicalDeviceProperties};
 use crate::ffi;
 use crate::format::Format;
-use crate::shaders::ShaderModule;
+use crate::render_pass::RenderPass;
+use crate::resource::Pod;
+use crate::shaders::{ShaderModule, ShaderReflection};
+pub use vulkan_rs_derive::Vertex;
 
 pub type SampleMask = u32;
 
@@ -86,6 +103,14 @@ pub enum ShaderStage {
     SubpassShadingHuawei,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PipelineBindPoint {
+    Graphics,
+    Compute,
+    RayTracingKhr,
+    SubpassShadingHuawei,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CullMode {
     None,
@@ -94,6 +119,65 @@ pub enum CullMode {
     FrondAndBack,
 }
 
+/// Comparison of a new value against the one in the depth/stencil attachment, passing if
+/// `new <op> old` holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CompareOp {
+    #[default]
+    Never,
+    Less,
+    Equal,
+    LessOrEqual,
+    Greater,
+    NotEqual,
+    GreaterOrEqual,
+    Always,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StencilOp {
+    #[default]
+    Keep,
+    Zero,
+    Replace,
+    IncrementAndClamp,
+    DecrementAndClamp,
+    Invert,
+    IncrementAndWrap,
+    DecrementAndWrap,
+}
+
+/// Faces whose stencil state is updated by the stencil commands of a
+/// [`CommandBuffer`](crate::command_buffer::CommandBuffer).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StencilFace {
+    Front,
+    Back,
+    FrontAndBack,
+}
+
+/// Pipeline state that is not baked into the pipeline and has to be set with the matching
+/// command of a [`CommandBuffer`](crate::command_buffer::CommandBuffer) before drawing.
+///
+/// Variants ending with `Ext` require Vulkan 1.3 or `"VK_EXT_extended_dynamic_state"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DynamicState {
+    Viewport,
+    Scissor,
+    LineWidth,
+    DepthBias,
+    BlendConstants,
+    DepthBounds,
+    StencilCompareMask,
+    StencilWriteMask,
+    StencilReference,
+    CullModeExt,
+    FrontFaceExt,
+    PrimitiveTopologyExt,
+    DepthTestEnableExt,
+    DepthWriteEnableExt,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SampleCount {
     OneBit,
@@ -193,16 +277,53 @@ pub enum ColorComponent {
 #[derive(Default)]
 pub struct ColorComponentFlags(u32);
 
+/// Set of [`ShaderStage`]s.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ShaderStageFlags(pub(crate) u32);
+
 #[derive(Default)]
 pub struct PipelineShaderStageCreateFlags(u32);
 
+#[derive(Default, Clone, Copy)]
+pub struct PipelineCreateFlags(u32);
+
 #[derive(Debug)]
 pub struct PipelineShaderStageCreateInfo<'a> {
     flags: PipelineShaderStageCreateFlags,
     stage: ShaderStage,
     module: &'a ShaderModule<'a>,
     name: &'a str,
-    specialization_info: Option<()>, // TODO: SpecializationInfo
+    specialization_info: Option<&'a SpecializationInfo>,
+}
+
+/// Value of a specialization constant, sized as the matching SPIR-V scalar type.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpecializationConstant {
+    Bool(bool),
+    Int(i32),
+    Uint(u32),
+    Float(f32),
+    Double(f64),
+}
+
+/// Location of a specialization constant in the data of a [`SpecializationInfo`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpecializationMapEntry {
+    constant_id: u32,
+    offset: u32,
+    size: usize,
+}
+
+/// Values of the specialization constants of a shader stage, so that one shader module can be
+/// used for many pipeline variants.
+///
+/// Constants are either added one by one with [`with_constant`](Self::with_constant) or mapped
+/// into existing data with [`from_bytes`](Self::from_bytes) and
+/// [`from_struct`](Self::from_struct).
+#[derive(Debug, Default, Clone)]
+pub struct SpecializationInfo {
+    map_entries: Vec<SpecializationMapEntry>,
+    data: Vec<u8>,
 }
 
 #[derive(Default)]
@@ -216,16 +337,20 @@ pub struct PipelineRasterizationStateCreateFlags(u32);
 #[derive(Default)]
 pub struct PipelineMultisampleStateCreateFlags(u32);
 #[derive(Default)]
+pub struct PipelineDepthStencilStateCreateFlags(u32);
+#[derive(Default)]
 pub struct PipelineColorBlendStateCreateFlags(u32);
+#[derive(Default)]
+pub struct PipelineDynamicStateCreateFlags(u32);
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct VertexInputBindingDescription {
     binding: u32,
     stride: u32,
     input_rate: VertexInputRate,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct VertexInputAttributeDescription {
     location: u32,
     binding: u32,
@@ -233,6 +358,33 @@ pub struct VertexInputAttributeDescription {
     offset: u32,
 }
 
+/// Type whose values are read by vertex shaders from a vertex buffer, one attribute per field.
+///
+/// Usually implemented with `#[derive(Vertex)]`, which maps field types to formats.
+pub trait Vertex: Sized {
+    /// Attributes of the fields read from `binding`, starting at `first_location`.
+    fn attribute_descriptions(
+        binding: u32,
+        first_location: u32,
+    ) -> Vec<VertexInputAttributeDescription>;
+
+    /// Binding whose stride is the size of the type.
+    fn binding_description(
+        binding: u32,
+        input_rate: VertexInputRate,
+    ) -> VertexInputBindingDescription {
+        VertexInputBindingDescription::new(binding, std::mem::size_of::<Self>() as u32, input_rate)
+    }
+}
+
+/// Vertex bindings and attributes of [`Vertex`] types, one binding per type with attributes at
+/// consecutive locations.
+#[derive(Debug, Default, Clone)]
+pub struct VertexInputDescriptions {
+    bindings: Vec<VertexInputBindingDescription>,
+    attributes: Vec<VertexInputAttributeDescription>,
+}
+
 #[derive(Debug, Default)]
 pub struct PipelineVertexInputStateCreateInfo<'a> {
     flags: PipelineVertexInputStateCreateFlags,
@@ -259,10 +411,15 @@ pub struct Viewport {
     max_depth: f32,
 }
 
+/// When viewports or scissors are dynamic, only their number is used and the actual values are
+/// set with [`set_viewport`](crate::command_buffer::CommandBuffer::set_viewport) and
+/// [`set_scissor`](crate::command_buffer::CommandBuffer::set_scissor) instead.
 #[derive(Debug)]
 pub struct PipelineViewportStateCreateInfo<'a> {
     flags: PipelineViewportStateCreateFlags,
+    viewport_count: u32,
     viewports: Option<&'a [Viewport]>,
+    scissor_count: u32,
     scissors: Option<&'a [Rect2D]>,
 }
 
@@ -291,6 +448,32 @@ pub struct PipelineMultisampleStateCreateInfo<'a> {
     alpha_to_one_enable: bool,
 }
 
+/// Stencil test and update of one face.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct StencilOpState {
+    fail_op: StencilOp,
+    pass_op: StencilOp,
+    depth_fail_op: StencilOp,
+    compare_op: CompareOp,
+    compare_mask: u32,
+    write_mask: u32,
+    reference: u32,
+}
+
+#[derive(Debug)]
+pub struct PipelineDepthStencilStateCreateInfo {
+    flags: PipelineDepthStencilStateCreateFlags,
+    depth_test_enable: bool,
+    depth_write_enable: bool,
+    depth_compare_op: CompareOp,
+    depth_bounds_test_enable: bool,
+    stencil_test_enable: bool,
+    front: StencilOpState,
+    back: StencilOpState,
+    min_depth_bounds: f32,
+    max_depth_bounds: f32,
+}
+
 #[derive(Debug, Default)]
 pub struct PipelineColorBlendAttachmentState {
     blend_enable: bool,
@@ -312,6 +495,147 @@ pub struct PipelineColorBlendStateCreateInfo<'a> {
     blend_constants: [f64; 4],
 }
 
+#[derive(Debug, Default)]
+pub struct PipelineDynamicStateCreateInfo<'a> {
+    flags: PipelineDynamicStateCreateFlags,
+    dynamic_states: &'a [DynamicState],
+}
+
+/// Attachment formats of a graphics pipeline used with dynamic rendering instead of a
+/// [`RenderPass`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PipelineRenderingCreateInfo<'a> {
+    view_mask: u32,
+    color_attachment_formats: &'a [Format],
+    depth_attachment_format: Option<Format>,
+    stencil_attachment_format: Option<Format>,
+}
+
+/// Range of push constant memory accessible by a set of shader stages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PushConstantRange {
+    stage_flags: ShaderStageFlags,
+    offset: u32,
+    size: u32,
+}
+
+pub struct Pipeline<'a> {
+    #[cfg(target_pointer_width = "64")]
+    pub(crate) handle: NonNull<ffi::VkPipeline_T>,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub(crate) handle: NonZeroU64,
+    pub(crate) device: &'a Device<'a>,
+    bind_point: PipelineBindPoint,
+    #[cfg(target_pointer_width = "64")]
+    _marker: PhantomData<ffi::VkPipeline_T>,
+}
+
+/// Builder of a compute [`Pipeline`].
+#[derive(Debug)]
+pub struct ComputePipelineBuilder<'a> {
+    flags: PipelineCreateFlags,
+    stage: PipelineShaderStageCreateInfo<'a>,
+    layout: &'a PipelineLayout<'a>,
+    cache: Option<&'a PipelineCache<'a>>,
+}
+
+/// Builder of a graphics [`Pipeline`] used either in a subpass of a [`RenderPass`] or with
+/// dynamic rendering.
+///
+/// States that are not set use their defaults, except that viewport, depth/stencil and color
+/// blend states are left out of the pipeline. Without a render pass the pipeline targets
+/// dynamic rendering with the attachment formats of its [`PipelineRenderingCreateInfo`].
+#[derive(Debug)]
+pub struct GraphicsPipelineBuilder<'a> {
+    flags: PipelineCreateFlags,
+    stages: &'a [PipelineShaderStageCreateInfo<'a>],
+    vertex_input_state: PipelineVertexInputStateCreateInfo<'a>,
+    input_assembly_state: PipelineInputAssemblyStateCreateInfo,
+    viewport_state: Option<PipelineViewportStateCreateInfo<'a>>,
+    rasterization_state: PipelineRasterizationStateCreateInfo,
+    multisample_state: PipelineMultisampleStateCreateInfo<'a>,
+    depth_stencil_state: Option<PipelineDepthStencilStateCreateInfo>,
+    color_blend_state: Option<PipelineColorBlendStateCreateInfo<'a>>,
+    dynamic_state: PipelineDynamicStateCreateInfo<'a>,
+    layout: &'a PipelineLayout<'a>,
+    target: Option<RenderTarget<'a>>,
+    cache: Option<&'a PipelineCache<'a>>,
+}
+
+#[derive(Debug)]
+enum RenderTarget<'a> {
+    RenderPass(&'a RenderPass<'a>, u32),
+    Rendering(PipelineRenderingCreateInfo<'a>),
+}
+
+pub struct PipelineLayout<'a> {
+    #[cfg(target_pointer_width = "64")]
+    pub(crate) handle: NonNull<ffi::VkPipelineLayout_T>,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub(crate) handle: NonZeroU64,
+    pub(crate) device: &'a Device<'a>,
+    /// Bindings of each set layout, sorted by binding number.
+    set_layouts: Vec<Vec<LayoutBinding>>,
+    push_constant_ranges: Vec<PushConstantRange>,
+    #[cfg(target_pointer_width = "64")]
+    _marker: PhantomData<ffi::VkPipelineLayout_T>,
+}
+
+/// Builder of [`PipelineLayout`].
+///
+/// Descriptor set layouts are bound to consecutive set numbers in the order they are added.
+#[derive(Debug, Default)]
+pub struct PipelineLayoutBuilder<'a> {
+    set_layouts: Vec<&'a DescriptorSetLayout<'a>>,
+    push_constant_ranges: Vec<PushConstantRange>,
+}
+
+/// Descriptor set bindings and push constant ranges merged from the reflection of the shader
+/// stages of a pipeline, to build its layout from.
+///
+/// Stages that declare the same set and binding share it, with their stage flags combined.
+/// Push constant blocks become one range per stage. A range that lies within the range of
+/// another stage is merged into it, so both stages share the larger range.
+#[derive(Debug, Clone)]
+pub struct ReflectedLayoutBuilder {
+    sets: Vec<Vec<LayoutBinding>>,
+    push_constant_ranges: Vec<PushConstantRange>,
+}
+
+/// Descriptor set layouts and pipeline layout built by a [`ReflectedLayoutBuilder`].
+#[derive(Debug)]
+pub struct ReflectedLayout<'a> {
+    set_layouts: Vec<DescriptorSetLayout<'a>>,
+    pipeline_layout: PipelineLayout<'a>,
+}
+
+/// Pipeline cache lets the driver reuse the results of pipeline compilation between pipelines
+/// and, once saved to disk, between runs of the application.
+pub struct PipelineCache<'a> {
+    #[cfg(target_pointer_width = "64")]
+    pub(crate) handle: NonNull<ffi::VkPipelineCache_T>,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub(crate) handle: NonZeroU64,
+    pub(crate) device: &'a Device<'a>,
+    #[cfg(target_pointer_width = "64")]
+    _marker: PhantomData<ffi::VkPipelineCache_T>,
+}
+
+/// Builder of [`PipelineCache`].
+#[derive(Debug, Default)]
+pub struct PipelineCacheBuilder<'b> {
+    externally_synchronized: bool,
+    initial_data: Option<&'b [u8]>,
+}
+
+/// Header at the start of the data returned by [`PipelineCache::data`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PipelineCacheHeader {
+    pub vendor_id: u32,
+    pub device_id: u32,
+    pub pipeline_cache_uuid: [u8; 16],
+}
+
 impl Default for FrontFace {
     fn default() -> Self {
         Self::CounterClockwise
@@ -342,6 +666,295 @@ impl Default for ShaderStage {
     }
 }
 
+impl From<ShaderStage> for ffi::ShaderStageFlagBits {
+    fn from(stage: ShaderStage) -> Self {
+        match stage {
+            ShaderStage::Vertex => Self::VertexBit,
+            ShaderStage::TessellationControl => Self::TessellationControlBit,
+            ShaderStage::TessellationEvaluation => Self::TessellationEvaluationBit,
+            ShaderStage::Geometry => Self::GeometryBit,
+            ShaderStage::Fragment => Self::FragmentBit,
+            ShaderStage::Compute => Self::ComputeBit,
+            ShaderStage::AllGraphics => Self::AllGraphics,
+            ShaderStage::All => Self::All,
+            ShaderStage::RaygenKhr => Self::RaygenBitKhr,
+            ShaderStage::AnyHitKhr => Self::AnyHitBitKhr,
+            ShaderStage::ClosestHitKhr => Self::ClosestHitBitKhr,
+            ShaderStage::MissKhr => Self::MissBitKhr,
+            ShaderStage::IntersectionKhr => Self::IntersectionBitKhr,
+            ShaderStage::CallableKhr => Self::CallableBitKhr,
+            ShaderStage::TaskNv => Self::TaskBitNv,
+            ShaderStage::MeshNv => Self::MeshBitNv,
+            ShaderStage::SubpassShadingHuawei => Self::SubpassShadingBitHuawei,
+        }
+    }
+}
+
+impl From<PipelineBindPoint> for ffi::PipelineBindPoint {
+    fn from(bind_point: PipelineBindPoint) -> Self {
+        match bind_point {
+            PipelineBindPoint::Graphics => Self::Graphics,
+            PipelineBindPoint::Compute => Self::Compute,
+            PipelineBindPoint::RayTracingKhr => Self::RayTracingKhr,
+            PipelineBindPoint::SubpassShadingHuawei => Self::SubpassShadingHuawei,
+        }
+    }
+}
+
+impl From<FrontFace> for ffi::FrontFace {
+    fn from(front_face: FrontFace) -> Self {
+        match front_face {
+            FrontFace::CounterClockwise => Self::CounterClockwise,
+            FrontFace::Clockwise => Self::Clockwise,
+        }
+    }
+}
+
+impl From<PrimitiveTopology> for ffi::PrimitiveTopology {
+    fn from(topology: PrimitiveTopology) -> Self {
+        match topology {
+            PrimitiveTopology::PointList => Self::PointList,
+            PrimitiveTopology::LineList => Self::LineList,
+            PrimitiveTopology::LineStrip => Self::LineStrip,
+            PrimitiveTopology::TriangleList => Self::TriangleList,
+            PrimitiveTopology::TriangleStrip => Self::TriangleStrip,
+            PrimitiveTopology::TriangleFan => Self::TriangleFan,
+            PrimitiveTopology::LineListWithAdjacency => Self::LineListWithAdjacency,
+            PrimitiveTopology::LineStripWithAdjacency => Self::LineStripWithAdjacency,
+            PrimitiveTopology::TriangleListWithAdjacency => Self::TriangleListWithAdjacency,
+            PrimitiveTopology::TriangleStripWithAdjacency => Self::TriangleStripWithAdjacency,
+            PrimitiveTopology::PatchList => Self::PatchList,
+        }
+    }
+}
+
+impl From<CullMode> for ffi::CullModeFlagBits {
+    fn from(cull_mode: CullMode) -> Self {
+        match cull_mode {
+            CullMode::None => Self::None,
+            CullMode::Front => Self::FrontBit,
+            CullMode::Back => Self::BackBit,
+            CullMode::FrondAndBack => Self::FrontAndBack,
+        }
+    }
+}
+
+impl From<StencilFace> for ffi::StencilFaceFlagBits {
+    fn from(face: StencilFace) -> Self {
+        match face {
+            StencilFace::Front => Self::FrontBit,
+            StencilFace::Back => Self::BackBit,
+            StencilFace::FrontAndBack => Self::FrontAndBack,
+        }
+    }
+}
+
+impl DynamicState {
+    /// Whether the state needs Vulkan 1.3 or `"VK_EXT_extended_dynamic_state"`.
+    pub fn is_extended(&self) -> bool {
+        matches!(
+            self,
+            Self::CullModeExt
+                | Self::FrontFaceExt
+                | Self::PrimitiveTopologyExt
+                | Self::DepthTestEnableExt
+                | Self::DepthWriteEnableExt
+        )
+    }
+}
+
+impl From<DynamicState> for ffi::DynamicState {
+    fn from(state: DynamicState) -> Self {
+        match state {
+            DynamicState::Viewport => Self::Viewport,
+            DynamicState::Scissor => Self::Scissor,
+            DynamicState::LineWidth => Self::LineWidth,
+            DynamicState::DepthBias => Self::DepthBias,
+            DynamicState::BlendConstants => Self::BlendConstants,
+            DynamicState::DepthBounds => Self::DepthBounds,
+            DynamicState::StencilCompareMask => Self::StencilCompareMask,
+            DynamicState::StencilWriteMask => Self::StencilWriteMask,
+            DynamicState::StencilReference => Self::StencilReference,
+            DynamicState::CullModeExt => Self::CullModeExt,
+            DynamicState::FrontFaceExt => Self::FrontFaceExt,
+            DynamicState::PrimitiveTopologyExt => Self::PrimitiveTopologyExt,
+            DynamicState::DepthTestEnableExt => Self::DepthTestEnableExt,
+            DynamicState::DepthWriteEnableExt => Self::DepthWriteEnableExt,
+        }
+    }
+}
+
+impl From<PolygonMode> for ffi::PolygonMode {
+    fn from(polygon_mode: PolygonMode) -> Self {
+        match polygon_mode {
+            PolygonMode::Fill => Self::Fill,
+            PolygonMode::Line => Self::Line,
+            PolygonMode::Point => Self::Point,
+            PolygonMode::FillRectangleNv => Self::FillRectangleNv,
+        }
+    }
+}
+
+impl From<LogicOp> for ffi::LogicOp {
+    fn from(logic_op: LogicOp) -> Self {
+        match logic_op {
+            LogicOp::Clear => Self::Clear,
+            LogicOp::And => Self::And,
+            LogicOp::AndReverse => Self::AndReverse,
+            LogicOp::Copy => Self::Copy,
+            LogicOp::AndInverted => Self::AndInverted,
+            LogicOp::NoOp => Self::NoOp,
+            LogicOp::Xor => Self::Xor,
+            LogicOp::Or => Self::Or,
+            LogicOp::Nor => Self::Nor,
+            LogicOp::Equivalent => Self::Equivalent,
+            LogicOp::Invert => Self::Invert,
+            LogicOp::OrReverse => Self::OrReverse,
+            LogicOp::CopyInverted => Self::CopyInverted,
+            LogicOp::OrInverted => Self::OrInverted,
+            LogicOp::Nand => Self::Nand,
+            LogicOp::Set => Self::Set,
+        }
+    }
+}
+
+impl From<BlendFactor> for ffi::BlendFactor {
+    fn from(factor: BlendFactor) -> Self {
+        match factor {
+            BlendFactor::Zero => Self::Zero,
+            BlendFactor::One => Self::One,
+            BlendFactor::SrcColor => Self::SrcColor,
+            BlendFactor::OneMinusSrcColor => Self::OneMinusSrcColor,
+            BlendFactor::DstColor => Self::DstColor,
+            BlendFactor::OneMinusDstColor => Self::OneMinusDstColor,
+            BlendFactor::SrcAlpha => Self::SrcAlpha,
+            BlendFactor::OneMinusSrcAlpha => Self::OneMinusSrcAlpha,
+            BlendFactor::DstAlpha => Self::DstAlpha,
+            BlendFactor::OneMinusDstAlpha => Self::OneMinusDstAlpha,
+            BlendFactor::ConstantColor => Self::ConstantColor,
+            BlendFactor::OneMinusConstantColor => Self::OneMinusConstantColor,
+            BlendFactor::ConstantAlpha => Self::ConstantAlpha,
+            BlendFactor::OneMinusConstantAlpha => Self::OneMinusConstantAlpha,
+            BlendFactor::SrcAlphaSaturate => Self::SrcAlphaSaturate,
+            BlendFactor::Src1Color => Self::Src1Color,
+            BlendFactor::OneMinusSrc1Color => Self::OneMinusSrc1Color,
+            BlendFactor::Src1Alpha => Self::Src1Alpha,
+            BlendFactor::OneMinusSrc1Alpha => Self::OneMinusSrc1Alpha,
+        }
+    }
+}
+
+impl From<BlendOp> for ffi::BlendOp {
+    fn from(op: BlendOp) -> Self {
+        match op {
+            BlendOp::Add => Self::Add,
+            BlendOp::Subtract => Self::Subtract,
+            BlendOp::ReverseSubtract => Self::ReverseSubtract,
+            BlendOp::Min => Self::Min,
+            BlendOp::Max => Self::Max,
+            BlendOp::ZeroExt => Self::ZeroExt,
+            BlendOp::SrcExt => Self::SrcExt,
+            BlendOp::DstExt => Self::DstExt,
+            BlendOp::SrcOverExt => Self::SrcOverExt,
+            BlendOp::DstOverExt => Self::DstOverExt,
+            BlendOp::SrcInExt => Self::SrcInExt,
+            BlendOp::DstInExt => Self::DstInExt,
+            BlendOp::SrcOutExt => Self::SrcOutExt,
+            BlendOp::DstOutExt => Self::DstOutExt,
+            BlendOp::SrcAtopExt => Self::SrcAtopExt,
+            BlendOp::DstAtopExt => Self::DstAtopExt,
+            BlendOp::XorExt => Self::XorExt,
+            BlendOp::MultiplyExt => Self::MultiplyExt,
+            BlendOp::ScreenExt => Self::ScreenExt,
+            BlendOp::OverlayExt => Self::OverlayExt,
+            BlendOp::DarkenExt => Self::DarkenExt,
+            BlendOp::LightenExt => Self::LightenExt,
+            BlendOp::ColordodgeExt => Self::ColordodgeExt,
+            BlendOp::ColorburnExt => Self::ColorburnExt,
+            BlendOp::HardlightExt => Self::HardlightExt,
+            BlendOp::SoftlightExt => Self::SoftlightExt,
+            BlendOp::DifferenceExt => Self::DifferenceExt,
+            BlendOp::ExclusionExt => Self::ExclusionExt,
+            BlendOp::InvertExt => Self::InvertExt,
+            BlendOp::InvertRgbExt => Self::InvertRgbExt,
+            BlendOp::LineardodgeExt => Self::LineardodgeExt,
+            BlendOp::LinearburnExt => Self::LinearburnExt,
+            BlendOp::VividlightExt => Self::VividlightExt,
+            BlendOp::LinearlightExt => Self::LinearlightExt,
+            BlendOp::PinlightExt => Self::PinlightExt,
+            BlendOp::HardmixExt => Self::HardmixExt,
+            BlendOp::HslHueExt => Self::HslHueExt,
+            BlendOp::HslSaturationExt => Self::HslSaturationExt,
+            BlendOp::HslColorExt => Self::HslColorExt,
+            BlendOp::HslLuminosityExt => Self::HslLuminosityExt,
+            BlendOp::PlusExt => Self::PlusExt,
+            BlendOp::PlusClampedExt => Self::PlusClampedExt,
+            BlendOp::PlusClampedAlphaExt => Self::PlusClampedAlphaExt,
+            BlendOp::PlusDarkerExt => Self::PlusDarkerExt,
+            BlendOp::MinusExt => Self::MinusExt,
+            BlendOp::MinusClampedExt => Self::MinusClampedExt,
+            BlendOp::ContrastExt => Self::ContrastExt,
+            BlendOp::InvertOvgExt => Self::InvertOvgExt,
+            BlendOp::RedExt => Self::RedExt,
+            BlendOp::GreenExt => Self::GreenExt,
+            BlendOp::BlueExt => Self::BlueExt,
+        }
+    }
+}
+
+impl From<VertexInputRate> for ffi::VertexInputRate {
+    fn from(input_rate: VertexInputRate) -> Self {
+        match input_rate {
+            VertexInputRate::Vertex => Self::Vertex,
+            VertexInputRate::Instance => Self::Instance,
+        }
+    }
+}
+
+impl From<SampleCount> for ffi::SampleCountFlagBits {
+    fn from(samples: SampleCount) -> Self {
+        match samples {
+            SampleCount::OneBit => Self::OneBit,
+            SampleCount::TwoBit => Self::TwoBit,
+            SampleCount::FourBit => Self::FourBit,
+            SampleCount::EightBit => Self::EightBit,
+            SampleCount::SixteenBit => Self::SixteenBit,
+            SampleCount::ThirtytwoBit => Self::ThirtyTwoBit,
+            SampleCount::SixtyfourBit => Self::SixtyFourBit,
+        }
+    }
+}
+
+impl From<CompareOp> for ffi::CompareOp {
+    fn from(compare_op: CompareOp) -> Self {
+        match compare_op {
+            CompareOp::Never => Self::Never,
+            CompareOp::Less => Self::Less,
+            CompareOp::Equal => Self::Equal,
+            CompareOp::LessOrEqual => Self::LessOrEqual,
+            CompareOp::Greater => Self::Greater,
+            CompareOp::NotEqual => Self::NotEqual,
+            CompareOp::GreaterOrEqual => Self::GreaterOrEqual,
+            CompareOp::Always => Self::Always,
+        }
+    }
+}
+
+impl From<StencilOp> for ffi::StencilOp {
+    fn from(stencil_op: StencilOp) -> Self {
+        match stencil_op {
+            StencilOp::Keep => Self::Keep,
+            StencilOp::Zero => Self::Zero,
+            StencilOp::Replace => Self::Replace,
+            StencilOp::IncrementAndClamp => Self::IncrementAndClamp,
+            StencilOp::DecrementAndClamp => Self::DecrementAndClamp,
+            StencilOp::Invert => Self::Invert,
+            StencilOp::IncrementAndWrap => Self::IncrementAndWrap,
+            StencilOp::DecrementAndWrap => Self::DecrementAndWrap,
+        }
+    }
+}
+
 impl Default for CullMode {
     fn default() -> Self {
         Self::None
@@ -449,6 +1062,76 @@ impl fmt::Debug for ColorComponentFlags {
     }
 }
 
+impl ShaderStageFlags {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_stage(mut self, stage: ShaderStage) -> Self {
+        self.0 |= ffi::ShaderStageFlagBits::from(stage) as u32;
+        self
+    }
+
+    /// Returns `true` if every stage of `stage` is in the set.
+    pub fn contains(&self, stage: ShaderStage) -> bool {
+        let flag = ffi::ShaderStageFlagBits::from(stage) as u32;
+
+        self.0 & flag == flag
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl From<ShaderStage> for ShaderStageFlags {
+    fn from(stage: ShaderStage) -> Self {
+        Self(ffi::ShaderStageFlagBits::from(stage) as u32)
+    }
+}
+
+impl fmt::Debug for ShaderStageFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const STAGES: [(ShaderStage, &str); 15] = [
+            (ShaderStage::Vertex, "VERTEX"),
+            (ShaderStage::TessellationControl, "TESSELLATION_CONTROL"),
+            (
+                ShaderStage::TessellationEvaluation,
+                "TESSELLATION_EVALUATION",
+            ),
+            (ShaderStage::Geometry, "GEOMETRY"),
+            (ShaderStage::Fragment, "FRAGMENT"),
+            (ShaderStage::Compute, "COMPUTE"),
+            (ShaderStage::RaygenKhr, "RAYGEN_KHR"),
+            (ShaderStage::AnyHitKhr, "ANY_HIT_KHR"),
+            (ShaderStage::ClosestHitKhr, "CLOSEST_HIT_KHR"),
+            (ShaderStage::MissKhr, "MISS_KHR"),
+            (ShaderStage::IntersectionKhr, "INTERSECTION_KHR"),
+            (ShaderStage::CallableKhr, "CALLABLE_KHR"),
+            (ShaderStage::TaskNv, "TASK_NV"),
+            (ShaderStage::MeshNv, "MESH_NV"),
+            (ShaderStage::SubpassShadingHuawei, "SUBPASS_SHADING_HUAWEI"),
+        ];
+
+        if self.0 == 0 {
+            return f.write_str("()");
+        }
+
+        let mut first = true;
+        for (stage, name) in STAGES {
+            if self.contains(stage) {
+                if !first {
+                    f.write_str(" | ")?;
+                }
+                first = false;
+                f.write_str(name)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl fmt::Debug for PipelineShaderStageCreateFlags {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if self.0 == 0 {
@@ -485,138 +1168,594 @@ impl<'a> PipelineShaderStageCreateInfo<'a> {
         self
     }
 
-    fn _with_specialization_info(self, _specialization_info: Option<()>) -> Self {
-        todo!()
+    pub fn with_specialization_info(mut self, specialization_info: &'a SpecializationInfo) -> Self {
+        self.specialization_info = Some(specialization_info);
+        self
     }
-}
 
-impl fmt::Debug for PipelineVertexInputStateCreateFlags {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.0 == 0 {
-            f.write_str("()")
-        } else {
-            f.write_str("non-empty")
+    /// Entry point name as C string, which has to outlive the structure returned by
+    /// [`to_ffi`](Self::to_ffi).
+    pub(crate) fn name_c(&self) -> Result<CString> {
+        CString::new(self.name).map_err(|_| Error::InvalidEntryPointName)
+    }
+
+    /// Specialization info, which has to outlive the structure returned by
+    /// [`to_ffi`](Self::to_ffi).
+    pub(crate) fn specialization_info_ffi(&self) -> Option<SpecializationInfoFfi> {
+        self.specialization_info.map(SpecializationInfo::to_ffi)
+    }
+
+    pub(crate) fn to_ffi(
+        &self,
+        name: &CString,
+        specialization_info: Option<&SpecializationInfoFfi>,
+    ) -> ffi::PipelineShaderStageCreateInfo {
+        ffi::PipelineShaderStageCreateInfo {
+            s_type: ffi::StructureType::PipelineShaderStageCreateInfo,
+            p_next: std::ptr::null(),
+            flags: self.flags.0,
+            stage: self.stage.into(),
+            #[cfg(target_pointer_width = "64")]
+            module: self.module.handle.as_ptr(),
+            #[cfg(not(target_pointer_width = "64"))]
+            module: self.module.handle.get(),
+            p_name: name.as_ptr(),
+            p_specialization_info: specialization_info
+                .map_or(std::ptr::null(), |specialization_info| {
+                    &specialization_info.info
+                }),
         }
     }
 }
 
-impl fmt::Debug for PipelineInputAssemblyStateCreateFlags {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.0 == 0 {
-            f.write_str("()")
-        } else {
-            f.write_str("non-empty")
-        }
-    }
+/// [`ffi::SpecializationInfo`] together with the map entries it points to.
+pub(crate) struct SpecializationInfoFfi {
+    _map_entries: Vec<ffi::SpecializationMapEntry>,
+    info: ffi::SpecializationInfo,
 }
 
-impl fmt::Debug for PipelineViewportStateCreateFlags {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.0 == 0 {
-            f.write_str("()")
-        } else {
-            f.write_str("non-empty")
-        }
+impl From<bool> for SpecializationConstant {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
     }
 }
 
-impl fmt::Debug for PipelineRasterizationStateCreateFlags {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.0 == 0 {
-            f.write_str("()")
-        } else {
-            f.write_str("non-empty")
-        }
+impl From<i32> for SpecializationConstant {
+    fn from(value: i32) -> Self {
+        Self::Int(value)
     }
 }
 
-impl fmt::Debug for PipelineMultisampleStateCreateFlags {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str("()")
+impl From<u32> for SpecializationConstant {
+    fn from(value: u32) -> Self {
+        Self::Uint(value)
     }
 }
 
-impl fmt::Debug for PipelineColorBlendStateCreateFlags {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str("()")
+impl From<f32> for SpecializationConstant {
+    fn from(value: f32) -> Self {
+        Self::Float(value)
     }
 }
 
-impl<'a> PipelineVertexInputStateCreateInfo<'a> {
-    pub fn with_vertex_binding_descriptions(
-        mut self,
-        vertex_binding_descriptions: &'a [VertexInputBindingDescription],
-    ) -> Self {
-        self.vertex_binding_descriptions = Some(vertex_binding_descriptions);
-        self
+impl From<f64> for SpecializationConstant {
+    fn from(value: f64) -> Self {
+        Self::Double(value)
     }
+}
 
-    pub fn with_vertex_attribute_descriptions(
-        mut self,
-        vertex_attribute_descriptions: &'a [VertexInputAttributeDescription],
-    ) -> Self {
-        self.vertex_attribute_descriptions = Some(vertex_attribute_descriptions);
-        self
+impl SpecializationConstant {
+    /// Bytes of the value in native byte order, booleans being 32 bit wide like `VkBool32`.
+    fn to_ne_bytes(self) -> Vec<u8> {
+        match self {
+            Self::Bool(value) => (value as u32).to_ne_bytes().to_vec(),
+            Self::Int(value) => value.to_ne_bytes().to_vec(),
+            Self::Uint(value) => value.to_ne_bytes().to_vec(),
+            Self::Float(value) => value.to_ne_bytes().to_vec(),
+            Self::Double(value) => value.to_ne_bytes().to_vec(),
+        }
     }
 }
 
-impl PipelineInputAssemblyStateCreateInfo {
-    pub fn new() -> Self {
+impl SpecializationMapEntry {
+    /// Constant `constant_id` taking `size` bytes at `offset` in the specialization data.
+    pub fn new(constant_id: u32, offset: u32, size: usize) -> Self {
         Self {
-            flags: Default::default(),
-            topology: Default::default(),
-            primitive_restart_enable: Default::default(),
+            constant_id,
+            offset,
+            size,
         }
     }
 
-    pub fn with_topology(mut self, topology: PrimitiveTopology) -> Self {
-        self.topology = topology;
-        self
+    pub fn constant_id(&self) -> u32 {
+        self.constant_id
     }
 
-    pub fn with_primitive_restart_enable(mut self, primitive_restart_enable: bool) -> Self {
-        self.primitive_restart_enable = primitive_restart_enable;
-        self
+    pub fn offset(&self) -> u32 {
+        self.offset
     }
-}
 
-impl Default for PipelineInputAssemblyStateCreateInfo {
-    fn default() -> Self {
-        Self::new()
+    pub fn size(&self) -> usize {
+        self.size
     }
 }
 
-impl Viewport {
-    pub fn new(x: f32, y: f32, width: f32, height: f32, min_depth: f32, max_depth: f32) -> Self {
+impl From<SpecializationMapEntry> for ffi::SpecializationMapEntry {
+    fn from(entry: SpecializationMapEntry) -> Self {
         Self {
-            x,
-            y,
-            width,
-            height,
-            min_depth,
-            max_depth,
+            constant_id: entry.constant_id,
+            offset: entry.offset,
+            size: entry.size,
         }
     }
 }
 
-impl<'a> PipelineViewportStateCreateInfo<'a> {
+impl SpecializationInfo {
     pub fn new() -> Self {
-        Self {
-            flags: Default::default(),
-            viewports: Default::default(),
+        Default::default()
+    }
+
+    /// Sets the constant with the `constant_id` of the shader, replacing an earlier value of
+    /// it. A value of the same size is overwritten in place unless other constants share its
+    /// bytes, otherwise the data is rebuilt without the earlier value.
+    pub fn with_constant(
+        mut self,
+        constant_id: u32,
+        value: impl Into<SpecializationConstant>,
+    ) -> Self {
+        let bytes = value.into().to_ne_bytes();
+        let bytes_of = |entry: &SpecializationMapEntry| {
+            entry.offset as usize..entry.offset as usize + entry.size
+        };
+        if let Some(entry) = self
+            .map_entries
+            .iter()
+            .find(|entry| entry.constant_id == constant_id && entry.size == bytes.len())
+        {
+            let range = bytes_of(entry);
+            let shared = self.map_entries.iter().any(|other| {
+                other.constant_id != constant_id
+                    && bytes_of(other).start < range.end
+                    && range.start < bytes_of(other).end
+            });
+            if !shared {
+                self.data[range].copy_from_slice(&bytes);
+                return self;
+            }
+        }
+
+        let mut data = Vec::with_capacity(self.data.len() + bytes.len());
+        self.map_entries
+            .retain(|entry| entry.constant_id != constant_id);
+        for entry in &mut self.map_entries {
+            let offset = data.len() as u32;
+            data.extend_from_slice(&self.data[bytes_of(entry)]);
+            entry.offset = offset;
+        }
+        self.map_entries.push(SpecializationMapEntry::new(
+            constant_id,
+            data.len() as u32,
+            bytes.len(),
+        ));
+        data.extend(bytes);
+        self.data = data;
+        self
+    }
+
+    /// Maps constants into `data`, which is copied.
+    ///
+    /// # Errors
+    ///
+    /// [`InvalidSpecializationConstant`](Error::InvalidSpecializationConstant) if a constant is
+    /// mapped more than once or does not lie within `data`.
+    pub fn from_bytes(data: &[u8], map_entries: &[SpecializationMapEntry]) -> Result<Self> {
+        for (i, entry) in map_entries.iter().enumerate() {
+            let end = (entry.offset as usize).checked_add(entry.size);
+            if end.is_none_or(|end| end > data.len())
+                || map_entries[..i]
+                    .iter()
+                    .any(|other| other.constant_id == entry.constant_id)
+            {
+                return Err(Error::InvalidSpecializationConstant(entry.constant_id));
+            }
+        }
+
+        Ok(Self {
+            map_entries: map_entries.to_vec(),
+            data: data.to_vec(),
+        })
+    }
+
+    /// Maps constants into the fields of a `#[repr(C)]` struct, whose offsets are given by the
+    /// map entries.
+    ///
+    /// # Errors
+    ///
+    /// See [`from_bytes`](Self::from_bytes).
+    pub fn from_struct<T: Pod>(data: &T, map_entries: &[SpecializationMapEntry]) -> Result<Self> {
+        // SAFETY: `Pod` types have no padding, so all `size_of::<T>()` bytes are initialized.
+        let data =
+            unsafe { std::slice::from_raw_parts((data as *const T).cast::<u8>(), size_of::<T>()) };
+        Self::from_bytes(data, map_entries)
+    }
+
+    pub fn map_entries(&self) -> &[SpecializationMapEntry] {
+        &self.map_entries
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    fn to_ffi(&self) -> SpecializationInfoFfi {
+        let map_entries: Vec<ffi::SpecializationMapEntry> =
+            self.map_entries.iter().map(|&entry| entry.into()).collect();
+        let info = ffi::SpecializationInfo {
+            map_entry_count: map_entries.len() as u32,
+            p_map_entries: map_entries.as_ptr(),
+            data_size: self.data.len(),
+            p_data: self.data.as_ptr() as *const _,
+        };
+
+        SpecializationInfoFfi {
+            _map_entries: map_entries,
+            info,
+        }
+    }
+}
+
+impl PipelineCreateFlags {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_disable_optimization(mut self, disable_optimization: bool) -> Self {
+        if disable_optimization {
+            self.0 |= ffi::PipeLineCreateFlagBits::DisableOptimizationBit as u32;
+        } else {
+            self.0 &= !(ffi::PipeLineCreateFlagBits::DisableOptimizationBit as u32);
+        }
+        self
+    }
+
+    pub fn with_allow_derivatives(mut self, allow_derivatives: bool) -> Self {
+        if allow_derivatives {
+            self.0 |= ffi::PipeLineCreateFlagBits::AllowDerivativesBit as u32;
+        } else {
+            self.0 &= !(ffi::PipeLineCreateFlagBits::AllowDerivativesBit as u32);
+        }
+        self
+    }
+
+    pub fn with_derivative(mut self, derivative: bool) -> Self {
+        if derivative {
+            self.0 |= ffi::PipeLineCreateFlagBits::DerivativeBit as u32;
+        } else {
+            self.0 &= !(ffi::PipeLineCreateFlagBits::DerivativeBit as u32);
+        }
+        self
+    }
+
+    /// Allows non-zero base workgroups in
+    /// [`dispatch_base`](crate::command_buffer::CommandBuffer::dispatch_base).
+    pub fn with_dispatch_base(mut self, dispatch_base: bool) -> Self {
+        if dispatch_base {
+            self.0 |= ffi::PipeLineCreateFlagBits::DispatchBaseBit as u32;
+        } else {
+            self.0 &= !(ffi::PipeLineCreateFlagBits::DispatchBaseBit as u32);
+        }
+        self
+    }
+}
+
+impl fmt::Debug for PipelineCreateFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 == 0 {
+            f.write_str("()")
+        } else {
+            f.write_str("non-empty")
+        }
+    }
+}
+
+impl fmt::Debug for PipelineVertexInputStateCreateFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 == 0 {
+            f.write_str("()")
+        } else {
+            f.write_str("non-empty")
+        }
+    }
+}
+
+impl fmt::Debug for PipelineInputAssemblyStateCreateFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 == 0 {
+            f.write_str("()")
+        } else {
+            f.write_str("non-empty")
+        }
+    }
+}
+
+impl fmt::Debug for PipelineViewportStateCreateFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 == 0 {
+            f.write_str("()")
+        } else {
+            f.write_str("non-empty")
+        }
+    }
+}
+
+impl fmt::Debug for PipelineRasterizationStateCreateFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 == 0 {
+            f.write_str("()")
+        } else {
+            f.write_str("non-empty")
+        }
+    }
+}
+
+impl fmt::Debug for PipelineMultisampleStateCreateFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("()")
+    }
+}
+
+impl fmt::Debug for PipelineDepthStencilStateCreateFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 == 0 {
+            f.write_str("()")
+        } else {
+            f.write_str("non-empty")
+        }
+    }
+}
+
+impl fmt::Debug for PipelineColorBlendStateCreateFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("()")
+    }
+}
+
+impl fmt::Debug for PipelineDynamicStateCreateFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("()")
+    }
+}
+
+impl VertexInputBindingDescription {
+    pub fn new(binding: u32, stride: u32, input_rate: VertexInputRate) -> Self {
+        Self {
+            binding,
+            stride,
+            input_rate,
+        }
+    }
+
+    pub fn binding(&self) -> u32 {
+        self.binding
+    }
+
+    pub fn stride(&self) -> u32 {
+        self.stride
+    }
+
+    pub fn input_rate(&self) -> VertexInputRate {
+        self.input_rate
+    }
+}
+
+impl From<&VertexInputBindingDescription> for ffi::VertexInputBindingDescription {
+    fn from(description: &VertexInputBindingDescription) -> Self {
+        Self {
+            binding: description.binding,
+            stride: description.stride,
+            input_rate: description.input_rate.into(),
+        }
+    }
+}
+
+impl VertexInputAttributeDescription {
+    pub fn new(location: u32, binding: u32, format: Format, offset: u32) -> Self {
+        Self {
+            location,
+            binding,
+            format,
+            offset,
+        }
+    }
+
+    /// Attributes of `binding` with the formats and offsets of `fields`, at consecutive
+    /// locations starting at `first_location`. 64-bit formats with three or four components
+    /// take two locations.
+    pub fn consecutive(binding: u32, first_location: u32, fields: &[(Format, u32)]) -> Vec<Self> {
+        let mut location = first_location;
+        fields
+            .iter()
+            .map(|&(format, offset)| {
+                let attribute = Self::new(location, binding, format, offset);
+                location += format.location_count();
+                attribute
+            })
+            .collect()
+    }
+
+    pub fn location(&self) -> u32 {
+        self.location
+    }
+
+    pub fn binding(&self) -> u32 {
+        self.binding
+    }
+
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+}
+
+impl VertexInputDescriptions {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Adds a binding for `V`, with the next binding number, and its attributes at the
+    /// locations following those of the previous types.
+    pub fn with_vertex<V: Vertex>(mut self, input_rate: VertexInputRate) -> Self {
+        let binding = self.bindings.len() as u32;
+        let first_location = self
+            .attributes
+            .iter()
+            .map(|attribute| attribute.location + attribute.format.location_count())
+            .max()
+            .unwrap_or_default();
+
+        self.bindings
+            .push(V::binding_description(binding, input_rate));
+        self.attributes
+            .extend(V::attribute_descriptions(binding, first_location));
+        self
+    }
+
+    pub fn bindings(&self) -> &[VertexInputBindingDescription] {
+        &self.bindings
+    }
+
+    pub fn attributes(&self) -> &[VertexInputAttributeDescription] {
+        &self.attributes
+    }
+
+    /// Vertex input state reading the bindings and attributes.
+    pub fn state(&self) -> PipelineVertexInputStateCreateInfo<'_> {
+        PipelineVertexInputStateCreateInfo::default()
+            .with_vertex_binding_descriptions(&self.bindings)
+            .with_vertex_attribute_descriptions(&self.attributes)
+    }
+}
+
+impl From<&VertexInputAttributeDescription> for ffi::VertexInputAttributeDescription {
+    fn from(description: &VertexInputAttributeDescription) -> Self {
+        Self {
+            location: description.location,
+            binding: description.binding,
+            format: description.format.into(),
+            offset: description.offset,
+        }
+    }
+}
+
+impl<'a> PipelineVertexInputStateCreateInfo<'a> {
+    pub fn with_vertex_binding_descriptions(
+        mut self,
+        vertex_binding_descriptions: &'a [VertexInputBindingDescription],
+    ) -> Self {
+        self.vertex_binding_descriptions = Some(vertex_binding_descriptions);
+        self
+    }
+
+    pub fn with_vertex_attribute_descriptions(
+        mut self,
+        vertex_attribute_descriptions: &'a [VertexInputAttributeDescription],
+    ) -> Self {
+        self.vertex_attribute_descriptions = Some(vertex_attribute_descriptions);
+        self
+    }
+}
+
+impl PipelineInputAssemblyStateCreateInfo {
+    pub fn new() -> Self {
+        Self {
+            flags: Default::default(),
+            topology: Default::default(),
+            primitive_restart_enable: Default::default(),
+        }
+    }
+
+    pub fn with_topology(mut self, topology: PrimitiveTopology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    pub fn with_primitive_restart_enable(mut self, primitive_restart_enable: bool) -> Self {
+        self.primitive_restart_enable = primitive_restart_enable;
+        self
+    }
+}
+
+impl Default for PipelineInputAssemblyStateCreateInfo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Viewport {
+    pub fn new(x: f32, y: f32, width: f32, height: f32, min_depth: f32, max_depth: f32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+            min_depth,
+            max_depth,
+        }
+    }
+}
+
+impl From<&Viewport> for ffi::Viewport {
+    fn from(viewport: &Viewport) -> Self {
+        Self {
+            x: viewport.x,
+            y: viewport.y,
+            width: viewport.width,
+            height: viewport.height,
+            min_depth: viewport.min_depth,
+            max_depth: viewport.max_depth,
+        }
+    }
+}
+
+impl<'a> PipelineViewportStateCreateInfo<'a> {
+    pub fn new() -> Self {
+        Self {
+            flags: Default::default(),
+            viewport_count: 0,
+            viewports: Default::default(),
+            scissor_count: 0,
             scissors: Default::default(),
         }
     }
 
     pub fn with_viewports(mut self, viewports: &'a [Viewport]) -> Self {
+        self.viewport_count = viewports.len() as u32;
         self.viewports = Some(viewports);
         self
     }
 
     pub fn with_scissors(mut self, scissors: &'a [Rect2D]) -> Self {
+        self.scissor_count = scissors.len() as u32;
         self.scissors = Some(scissors);
         self
     }
+
+    /// Number of viewports used by the pipeline when [`DynamicState::Viewport`] is dynamic.
+    pub fn with_viewport_count(mut self, viewport_count: u32) -> Self {
+        self.viewport_count = viewport_count;
+        self.viewports = None;
+        self
+    }
+
+    /// Number of scissors used by the pipeline when [`DynamicState::Scissor`] is dynamic.
+    pub fn with_scissor_count(mut self, scissor_count: u32) -> Self {
+        self.scissor_count = scissor_count;
+        self.scissors = None;
+        self
+    }
 }
 
 impl Default for PipelineViewportStateCreateInfo<'_> {
@@ -712,18 +1851,147 @@ impl<'a> PipelineMultisampleStateCreateInfo<'a> {
     }
 }
 
-impl PipelineColorBlendAttachmentState {
+impl StencilOpState {
     pub fn new() -> Self {
         Default::default()
     }
 
-    pub fn with_blend_enable(mut self, blend_enable: bool) -> Self {
-        self.blend_enable = blend_enable;
+    /// Action performed on samples that fail the stencil test.
+    pub fn with_fail_op(mut self, fail_op: StencilOp) -> Self {
+        self.fail_op = fail_op;
         self
     }
 
-    pub fn with_src_color_blend_factor(mut self, src_color_blend_factor: BlendFactor) -> Self {
-        self.src_color_blend_factor = src_color_blend_factor;
+    /// Action performed on samples that pass both the depth and stencil tests.
+    pub fn with_pass_op(mut self, pass_op: StencilOp) -> Self {
+        self.pass_op = pass_op;
+        self
+    }
+
+    /// Action performed on samples that pass the stencil test and fail the depth test.
+    pub fn with_depth_fail_op(mut self, depth_fail_op: StencilOp) -> Self {
+        self.depth_fail_op = depth_fail_op;
+        self
+    }
+
+    pub fn with_compare_op(mut self, compare_op: CompareOp) -> Self {
+        self.compare_op = compare_op;
+        self
+    }
+
+    pub fn with_compare_mask(mut self, compare_mask: u32) -> Self {
+        self.compare_mask = compare_mask;
+        self
+    }
+
+    pub fn with_write_mask(mut self, write_mask: u32) -> Self {
+        self.write_mask = write_mask;
+        self
+    }
+
+    pub fn with_reference(mut self, reference: u32) -> Self {
+        self.reference = reference;
+        self
+    }
+}
+
+impl From<StencilOpState> for ffi::StencilOpState {
+    fn from(state: StencilOpState) -> Self {
+        Self {
+            fail_op: state.fail_op.into(),
+            pass_op: state.pass_op.into(),
+            depth_fail_op: state.depth_fail_op.into(),
+            compare_op: state.compare_op.into(),
+            compare_mask: state.compare_mask,
+            write_mask: state.write_mask,
+            reference: state.reference,
+        }
+    }
+}
+
+impl PipelineDepthStencilStateCreateInfo {
+    pub fn new() -> Self {
+        Self {
+            flags: Default::default(),
+            depth_test_enable: false,
+            depth_write_enable: false,
+            depth_compare_op: CompareOp::Less,
+            depth_bounds_test_enable: false,
+            stencil_test_enable: false,
+            front: Default::default(),
+            back: Default::default(),
+            min_depth_bounds: 0.0,
+            max_depth_bounds: 1.0,
+        }
+    }
+
+    pub fn with_depth_test_enable(mut self, depth_test_enable: bool) -> Self {
+        self.depth_test_enable = depth_test_enable;
+        self
+    }
+
+    pub fn with_depth_write_enable(mut self, depth_write_enable: bool) -> Self {
+        self.depth_write_enable = depth_write_enable;
+        self
+    }
+
+    pub fn with_depth_compare_op(mut self, depth_compare_op: CompareOp) -> Self {
+        self.depth_compare_op = depth_compare_op;
+        self
+    }
+
+    pub fn with_depth_bounds_test_enable(mut self, depth_bounds_test_enable: bool) -> Self {
+        self.depth_bounds_test_enable = depth_bounds_test_enable;
+        self
+    }
+
+    /// Samples with a depth attachment value outside `min_depth_bounds..=max_depth_bounds` are
+    /// discarded when the depth bounds test is enabled.
+    pub fn with_depth_bounds(mut self, min_depth_bounds: f32, max_depth_bounds: f32) -> Self {
+        self.min_depth_bounds = min_depth_bounds;
+        self.max_depth_bounds = max_depth_bounds;
+        self
+    }
+
+    pub fn with_stencil_test_enable(mut self, stencil_test_enable: bool) -> Self {
+        self.stencil_test_enable = stencil_test_enable;
+        self
+    }
+
+    pub fn with_front(mut self, front: StencilOpState) -> Self {
+        self.front = front;
+        self
+    }
+
+    pub fn with_back(mut self, back: StencilOpState) -> Self {
+        self.back = back;
+        self
+    }
+
+    /// Whether the state reads or writes the depth aspect of the attachment.
+    fn uses_depth(&self) -> bool {
+        self.depth_test_enable || self.depth_write_enable || self.depth_bounds_test_enable
+    }
+}
+
+impl Default for PipelineDepthStencilStateCreateInfo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PipelineColorBlendAttachmentState {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_blend_enable(mut self, blend_enable: bool) -> Self {
+        self.blend_enable = blend_enable;
+        self
+    }
+
+    pub fn with_src_color_blend_factor(mut self, src_color_blend_factor: BlendFactor) -> Self {
+        self.src_color_blend_factor = src_color_blend_factor;
         self
     }
 
@@ -758,6 +2026,21 @@ impl PipelineColorBlendAttachmentState {
     }
 }
 
+impl From<&PipelineColorBlendAttachmentState> for ffi::PipelineColorBlendAttachmentState {
+    fn from(state: &PipelineColorBlendAttachmentState) -> Self {
+        Self {
+            blend_enable: state.blend_enable.into(),
+            src_color_blend_factor: state.src_color_blend_factor.into(),
+            dst_color_blend_factor: state.dst_color_blend_factor.into(),
+            color_blend_op: state.color_blend_op.into(),
+            src_alpha_blend_factor: state.src_alpha_blend_factor.into(),
+            dst_alpha_blend_factor: state.dst_alpha_blend_factor.into(),
+            alpha_blend_op: state.alpha_blend_op.into(),
+            color_write_mask: state.color_write_mask.0,
+        }
+    }
+}
+
 impl<'a> PipelineColorBlendStateCreateInfo<'a> {
     pub fn new() -> Self {
         Default::default()
@@ -783,3 +2066,1574 @@ impl<'a> PipelineColorBlendStateCreateInfo<'a> {
         self
     }
 }
+
+impl<'a> PipelineDynamicStateCreateInfo<'a> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_dynamic_states(mut self, dynamic_states: &'a [DynamicState]) -> Self {
+        self.dynamic_states = dynamic_states;
+        self
+    }
+}
+
+impl<'a> PipelineRenderingCreateInfo<'a> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Enables multiview rendering into the views whose bits are set.
+    pub fn with_view_mask(mut self, view_mask: u32) -> Self {
+        self.view_mask = view_mask;
+        self
+    }
+
+    pub fn with_color_attachment_formats(mut self, color_attachment_formats: &'a [Format]) -> Self {
+        self.color_attachment_formats = color_attachment_formats;
+        self
+    }
+
+    pub fn with_depth_attachment_format(mut self, depth_attachment_format: Format) -> Self {
+        self.depth_attachment_format = Some(depth_attachment_format);
+        self
+    }
+
+    pub fn with_stencil_attachment_format(mut self, stencil_attachment_format: Format) -> Self {
+        self.stencil_attachment_format = Some(stencil_attachment_format);
+        self
+    }
+}
+
+impl<'a> Pipeline<'a> {
+    pub fn bind_point(&self) -> PipelineBindPoint {
+        self.bind_point
+    }
+}
+
+impl<'a> Drop for Pipeline<'a> {
+    fn drop(&mut self) {
+        println!("Dropped Pipeline");
+        unsafe {
+            (self.device.dispatch_loader.vk_destroy_pipeline)(
+                self.device.handle.as_ptr(),
+                #[cfg(target_pointer_width = "64")]
+                self.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                self.handle.get(),
+                std::ptr::null(),
+            );
+        }
+    }
+}
+
+impl<'a> fmt::Debug for Pipeline<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pipeline")
+            .field("handle", &self.handle)
+            .field("bind_point", &self.bind_point)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> ComputePipelineBuilder<'a> {
+    /// The stage of `stage` is always set to [`Compute`](ShaderStage::Compute).
+    pub fn new(stage: PipelineShaderStageCreateInfo<'a>, layout: &'a PipelineLayout<'a>) -> Self {
+        Self {
+            flags: Default::default(),
+            stage: stage.with_stage(ShaderStage::Compute),
+            layout,
+            cache: None,
+        }
+    }
+
+    pub fn with_flags(&mut self, flags: PipelineCreateFlags) -> &mut Self {
+        self.flags = flags;
+        self
+    }
+
+    pub fn with_cache(&mut self, cache: &'a PipelineCache<'a>) -> &mut Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// # Errors
+    /// - [`InvalidEntryPointName`](Error::InvalidEntryPointName) if the entry point name
+    ///   contains a NUL byte.
+    ///
+    /// Driver errors:
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    /// - [`InvalidShaderNv`](Error::InvalidShaderNv)
+    pub fn build(&self, device: &'a Device) -> Result<Pipeline<'a>> {
+        let name = self.stage.name_c()?;
+        let specialization_info = self.stage.specialization_info_ffi();
+        let create_info = ffi::ComputePipelineCreateInfo {
+            s_type: ffi::StructureType::ComputePipelineCreateInfo,
+            p_next: std::ptr::null(),
+            flags: self.flags.0,
+            stage: self.stage.to_ffi(&name, specialization_info.as_ref()),
+            #[cfg(target_pointer_width = "64")]
+            layout: self.layout.handle.as_ptr(),
+            #[cfg(not(target_pointer_width = "64"))]
+            layout: self.layout.handle.get(),
+            #[cfg(target_pointer_width = "64")]
+            base_pipeline_handle: std::ptr::null_mut(),
+            #[cfg(not(target_pointer_width = "64"))]
+            base_pipeline_handle: 0,
+            base_pipeline_index: -1,
+        };
+
+        let mut handle = MaybeUninit::uninit();
+        let result = unsafe {
+            (device.dispatch_loader.vk_create_compute_pipelines)(
+                device.handle.as_ptr(),
+                cache_handle(self.cache),
+                1,
+                &create_info,
+                std::ptr::null(),
+                handle.as_mut_ptr(),
+            )
+        };
+
+        match result {
+            ffi::Result::Success => Ok(Pipeline {
+                #[cfg(target_pointer_width = "64")]
+                handle: unsafe { NonNull::new_unchecked(handle.assume_init()) },
+                #[cfg(not(target_pointer_width = "64"))]
+                handle: unsafe { NonZeroU64::new_unchecked(handle.assume_init()) },
+                device,
+                bind_point: PipelineBindPoint::Compute,
+                #[cfg(target_pointer_width = "64")]
+                _marker: PhantomData,
+            }),
+            ffi::Result::ErrorOutOfHostMemory => Err(Error::OutOfHostMemory),
+            ffi::Result::ErrorOutOfDeviceMemory => Err(Error::OutOfDeviceMemory),
+            ffi::Result::ErrorInvalidShaderNv => Err(Error::InvalidShaderNv),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<'a> GraphicsPipelineBuilder<'a> {
+    pub fn new(
+        stages: &'a [PipelineShaderStageCreateInfo<'a>],
+        layout: &'a PipelineLayout<'a>,
+    ) -> Self {
+        Self {
+            flags: Default::default(),
+            stages,
+            vertex_input_state: Default::default(),
+            input_assembly_state: Default::default(),
+            viewport_state: None,
+            rasterization_state: PipelineRasterizationStateCreateInfo::new().with_line_width(1.0),
+            multisample_state: Default::default(),
+            depth_stencil_state: None,
+            color_blend_state: None,
+            dynamic_state: Default::default(),
+            layout,
+            target: None,
+            cache: None,
+        }
+    }
+
+    pub fn with_flags(&mut self, flags: PipelineCreateFlags) -> &mut Self {
+        self.flags = flags;
+        self
+    }
+
+    pub fn with_vertex_input_state(
+        &mut self,
+        vertex_input_state: PipelineVertexInputStateCreateInfo<'a>,
+    ) -> &mut Self {
+        self.vertex_input_state = vertex_input_state;
+        self
+    }
+
+    pub fn with_input_assembly_state(
+        &mut self,
+        input_assembly_state: PipelineInputAssemblyStateCreateInfo,
+    ) -> &mut Self {
+        self.input_assembly_state = input_assembly_state;
+        self
+    }
+
+    pub fn with_viewport_state(
+        &mut self,
+        viewport_state: PipelineViewportStateCreateInfo<'a>,
+    ) -> &mut Self {
+        self.viewport_state = Some(viewport_state);
+        self
+    }
+
+    pub fn with_rasterization_state(
+        &mut self,
+        rasterization_state: PipelineRasterizationStateCreateInfo,
+    ) -> &mut Self {
+        self.rasterization_state = rasterization_state;
+        self
+    }
+
+    pub fn with_multisample_state(
+        &mut self,
+        multisample_state: PipelineMultisampleStateCreateInfo<'a>,
+    ) -> &mut Self {
+        self.multisample_state = multisample_state;
+        self
+    }
+
+    pub fn with_depth_stencil_state(
+        &mut self,
+        depth_stencil_state: PipelineDepthStencilStateCreateInfo,
+    ) -> &mut Self {
+        self.depth_stencil_state = Some(depth_stencil_state);
+        self
+    }
+
+    pub fn with_color_blend_state(
+        &mut self,
+        color_blend_state: PipelineColorBlendStateCreateInfo<'a>,
+    ) -> &mut Self {
+        self.color_blend_state = Some(color_blend_state);
+        self
+    }
+
+    pub fn with_dynamic_state(
+        &mut self,
+        dynamic_state: PipelineDynamicStateCreateInfo<'a>,
+    ) -> &mut Self {
+        self.dynamic_state = dynamic_state;
+        self
+    }
+
+    /// Uses the pipeline in `subpass` of `render_pass`, replacing any
+    /// [`with_rendering`](Self::with_rendering).
+    pub fn with_render_pass(&mut self, render_pass: &'a RenderPass<'a>, subpass: u32) -> &mut Self {
+        self.target = Some(RenderTarget::RenderPass(render_pass, subpass));
+        self
+    }
+
+    /// Uses the pipeline with dynamic rendering, replacing any
+    /// [`with_render_pass`](Self::with_render_pass).
+    pub fn with_rendering(&mut self, rendering: PipelineRenderingCreateInfo<'a>) -> &mut Self {
+        self.target = Some(RenderTarget::Rendering(rendering));
+        self
+    }
+
+    pub fn with_cache(&mut self, cache: &'a PipelineCache<'a>) -> &mut Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// # Errors
+    ///
+    /// Besides the errors returned by the driver:
+    /// - [`MissingPipelineState`](Error::MissingPipelineState) if neither
+    ///   [`with_render_pass`](Self::with_render_pass) nor
+    ///   [`with_rendering`](Self::with_rendering) was called.
+    /// - [`FeatureNotEnabled`](Error::FeatureNotEnabled) if the pipeline is used with dynamic
+    ///   rendering without the `dynamicRendering` feature, or has an `Ext` dynamic state while
+    ///   the device does not
+    ///   [support extended dynamic state](Device::supports_extended_dynamic_state).
+    /// - [`InvalidSubpass`](Error::InvalidSubpass) if the render pass has no such subpass.
+    /// - [`MissingPipelineState`](Error::MissingPipelineState) if rasterizer discard is disabled
+    ///   and the viewport state is not set, the depth/stencil state is not set while there is a
+    ///   depth/stencil attachment, or the color blend state is not set while there are color
+    ///   attachments.
+    /// - [`InvalidDepthStencilFormat`](Error::InvalidDepthStencilFormat) if the depth/stencil
+    ///   state tests or writes depth while the depth/stencil attachment of the subpass has no
+    ///   depth aspect, or enables the stencil test while it has no stencil aspect. With dynamic
+    ///   rendering, also if the depth attachment format has no depth aspect, the stencil
+    ///   attachment format has no stencil aspect, or both are given but differ.
+    ///
+    /// The interfaces of the shader stages, as reflected from their modules, are checked too:
+    /// - [`UnmatchedVertexInput`](Error::UnmatchedVertexInput) if a vertex shader input has no
+    ///   vertex attribute at its location.
+    /// - [`IncompatibleVertexFormat`](Error::IncompatibleVertexFormat) if the attribute format
+    ///   has another numeric type or component width than the input.
+    /// - [`UnmatchedFragmentInput`](Error::UnmatchedFragmentInput) if a location of a fragment
+    ///   shader input is not written by an output of the last stage before rasterization with
+    ///   the same type and at least as many components.
+    /// - [`UnmatchedFragmentOutput`](Error::UnmatchedFragmentOutput) if a fragment shader
+    ///   output location is not below the number of color blend attachments.
+    /// - [`InvalidEntryPointName`](Error::InvalidEntryPointName) if an entry point name
+    ///   contains a NUL byte.
+    ///
+    /// Driver errors:
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    /// - [`InvalidShaderNv`](Error::InvalidShaderNv)
+    pub fn build(&self, device: &'a Device) -> Result<Pipeline<'a>> {
+        let target = self.validate(device)?;
+
+        let names = self
+            .stages
+            .iter()
+            .map(PipelineShaderStageCreateInfo::name_c)
+            .collect::<Result<Vec<_>>>()?;
+        let specialization_infos: Vec<_> = self
+            .stages
+            .iter()
+            .map(|stage| stage.specialization_info_ffi())
+            .collect();
+        let stages: Vec<_> = self
+            .stages
+            .iter()
+            .zip(&names)
+            .zip(&specialization_infos)
+            .map(|((stage, name), specialization_info)| {
+                stage.to_ffi(name, specialization_info.as_ref())
+            })
+            .collect();
+
+        let vertex_input = &self.vertex_input_state;
+        let vertex_bindings: Vec<ffi::VertexInputBindingDescription> = vertex_input
+            .vertex_binding_descriptions
+            .unwrap_or_default()
+            .iter()
+            .map(|description| description.into())
+            .collect();
+        let vertex_attributes: Vec<ffi::VertexInputAttributeDescription> = vertex_input
+            .vertex_attribute_descriptions
+            .unwrap_or_default()
+            .iter()
+            .map(|description| description.into())
+            .collect();
+        let vertex_input_state = ffi::PipelineVertexInputStateCreateInfo {
+            s_type: ffi::StructureType::PipelineVertexInputStateCreateInfo,
+            p_next: std::ptr::null(),
+            flags: vertex_input.flags.0,
+            vertex_binding_description_count: vertex_bindings.len() as u32,
+            p_vertex_binding_descriptions: vertex_bindings.as_ptr(),
+            vertex_attribute_description_count: vertex_attributes.len() as u32,
+            p_vertex_attribute_descriptions: vertex_attributes.as_ptr(),
+        };
+
+        let input_assembly = &self.input_assembly_state;
+        let input_assembly_state = ffi::PipelineInputAssemblyStateCreateInfo {
+            s_type: ffi::StructureType::PipelineInputAssemblyStateCreateInfo,
+            p_next: std::ptr::null(),
+            flags: input_assembly.flags.0,
+            topology: input_assembly.topology.into(),
+            primitive_restart_enable: input_assembly.primitive_restart_enable.into(),
+        };
+
+        let viewports: Vec<ffi::Viewport> = self
+            .viewport_state
+            .as_ref()
+            .and_then(|viewport| viewport.viewports)
+            .unwrap_or_default()
+            .iter()
+            .map(|viewport| viewport.into())
+            .collect();
+        let scissors: Vec<ffi::Rect2D> = self
+            .viewport_state
+            .as_ref()
+            .and_then(|viewport| viewport.scissors)
+            .unwrap_or_default()
+            .iter()
+            .map(|&scissor| scissor.into())
+            .collect();
+        let viewport_state =
+            self.viewport_state
+                .as_ref()
+                .map(|viewport| ffi::PipelineViewportStateCreateInfo {
+                    s_type: ffi::StructureType::PipelineViewportStateCreateInfo,
+                    p_next: std::ptr::null(),
+                    flags: viewport.flags.0,
+                    viewport_count: viewport.viewport_count,
+                    p_viewports: match viewport.viewports {
+                        Some(_) => viewports.as_ptr(),
+                        None => std::ptr::null(),
+                    },
+                    scissor_count: viewport.scissor_count,
+                    p_scissors: match viewport.scissors {
+                        Some(_) => scissors.as_ptr(),
+                        None => std::ptr::null(),
+                    },
+                });
+
+        let rasterization = &self.rasterization_state;
+        let rasterization_state = ffi::PipelineRasterizationStateCreateInfo {
+            s_type: ffi::StructureType::PipelineRasterizationStateCreateInfo,
+            p_next: std::ptr::null(),
+            flags: rasterization.flags.0,
+            depth_clamp_enable: rasterization.depth_clamp_enable.into(),
+            rasterizer_discard_enable: rasterization.rasterizer_discard_enable.into(),
+            polygon_mode: rasterization.polygon_mode.into(),
+            cull_mode: ffi::CullModeFlagBits::from(rasterization.cull_mode) as u32,
+            front_face: rasterization.front_face.into(),
+            depth_bias_enable: rasterization.depth_bias_enable.into(),
+            depth_bias_constant_factor: rasterization.depth_bias_constant_factor,
+            depth_bias_clamp: rasterization.depth_bias_clamp,
+            depth_bias_slope_factor: rasterization.depth_bias_slope_factor,
+            line_width: rasterization.line_width,
+        };
+
+        let multisample = &self.multisample_state;
+        let multisample_state = ffi::PipelineMultisampleStateCreateInfo {
+            s_type: ffi::StructureType::PipelineMultisampleStateCreateInfo,
+            p_next: std::ptr::null(),
+            flags: multisample.flags.0,
+            rasterization_samples: multisample.rasterization_samples.into(),
+            sample_shading_enable: multisample.min_sample_mask.is_some().into(),
+            min_sample_shading: multisample.min_sample_mask.unwrap_or_default(),
+            p_sample_mask: if multisample.sample_mask.is_empty() {
+                std::ptr::null()
+            } else {
+                multisample.sample_mask.as_ptr()
+            },
+            alpha_to_coverage_enable: multisample.alpha_to_coverage_enable.into(),
+            alpha_to_one_enable: multisample.alpha_to_one_enable.into(),
+        };
+
+        let depth_stencil_state = self.depth_stencil_state.as_ref().map(|depth_stencil| {
+            ffi::PipelineDepthStencilStateCreateInfo {
+                s_type: ffi::StructureType::PipelineDepthStencilStateCreateInfo,
+                p_next: std::ptr::null(),
+                flags: depth_stencil.flags.0,
+                depth_test_enable: depth_stencil.depth_test_enable.into(),
+                depth_write_enable: depth_stencil.depth_write_enable.into(),
+                depth_compare_op: depth_stencil.depth_compare_op.into(),
+                depth_bounds_test_enable: depth_stencil.depth_bounds_test_enable.into(),
+                stencil_test_enable: depth_stencil.stencil_test_enable.into(),
+                front: depth_stencil.front.into(),
+                back: depth_stencil.back.into(),
+                min_depth_bounds: depth_stencil.min_depth_bounds,
+                max_depth_bounds: depth_stencil.max_depth_bounds,
+            }
+        });
+
+        let color_blend_attachments: Vec<ffi::PipelineColorBlendAttachmentState> = self
+            .color_blend_state
+            .as_ref()
+            .and_then(|color_blend| color_blend.attachments)
+            .unwrap_or_default()
+            .iter()
+            .map(|attachment| attachment.into())
+            .collect();
+        let color_blend_state = self.color_blend_state.as_ref().map(|color_blend| {
+            ffi::PipelineColorBlendStateCreateInfo {
+                s_type: ffi::StructureType::PipelineColorBlendStateCreateInfo,
+                p_next: std::ptr::null(),
+                flags: color_blend._flags.0,
+                logic_op_enable: color_blend.logic_op_enable.into(),
+                logic_op: color_blend.logic_op.into(),
+                attachment_count: color_blend_attachments.len() as u32,
+                p_attachments: color_blend_attachments.as_ptr(),
+                blend_constants: color_blend.blend_constants.map(|constant| constant as f32),
+            }
+        });
+
+        let dynamic_states: Vec<ffi::DynamicState> = self
+            .dynamic_state
+            .dynamic_states
+            .iter()
+            .map(|&state| state.into())
+            .collect();
+        let dynamic_state = ffi::PipelineDynamicStateCreateInfo {
+            s_type: ffi::StructureType::PipelineDynamicStateCreateInfo,
+            p_next: std::ptr::null(),
+            flags: self.dynamic_state.flags.0,
+            dynamic_state_count: dynamic_states.len() as u32,
+            p_dynamic_states: dynamic_states.as_ptr(),
+        };
+
+        let color_attachment_formats: Vec<ffi::Format> = match target {
+            RenderTarget::RenderPass(..) => Vec::new(),
+            RenderTarget::Rendering(rendering) => rendering
+                .color_attachment_formats
+                .iter()
+                .map(|&format| format.into())
+                .collect(),
+        };
+        let rendering_create_info = match target {
+            RenderTarget::RenderPass(..) => None,
+            RenderTarget::Rendering(rendering) => Some(ffi::PipelineRenderingCreateInfo {
+                s_type: ffi::StructureType::PipelineRenderingCreateInfo,
+                p_next: std::ptr::null(),
+                view_mask: rendering.view_mask,
+                color_attachment_count: color_attachment_formats.len() as u32,
+                p_color_attachment_formats: color_attachment_formats.as_ptr(),
+                depth_attachment_format: rendering
+                    .depth_attachment_format
+                    .unwrap_or(Format::Undefined)
+                    .into(),
+                stencil_attachment_format: rendering
+                    .stencil_attachment_format
+                    .unwrap_or(Format::Undefined)
+                    .into(),
+            }),
+        };
+        let (render_pass, subpass) = match *target {
+            #[cfg(target_pointer_width = "64")]
+            RenderTarget::RenderPass(render_pass, subpass) => {
+                (render_pass.handle.as_ptr(), subpass)
+            }
+            #[cfg(not(target_pointer_width = "64"))]
+            RenderTarget::RenderPass(render_pass, subpass) => (render_pass.handle.get(), subpass),
+            #[cfg(target_pointer_width = "64")]
+            RenderTarget::Rendering(_) => (std::ptr::null_mut(), 0),
+            #[cfg(not(target_pointer_width = "64"))]
+            RenderTarget::Rendering(_) => (0, 0),
+        };
+
+        let create_info = ffi::GraphicsPipelineCreateInfo {
+            s_type: ffi::StructureType::GraphicsPipelineCreateInfo,
+            p_next: rendering_create_info
+                .as_ref()
+                .map_or(std::ptr::null(), |info| info as *const _ as *const _),
+            flags: self.flags.0,
+            stage_count: stages.len() as u32,
+            p_stages: stages.as_ptr(),
+            p_vertex_input_state: &vertex_input_state,
+            p_input_assembly_state: &input_assembly_state,
+            p_tessellation_state: std::ptr::null(),
+            p_viewport_state: viewport_state
+                .as_ref()
+                .map_or(std::ptr::null(), |state| state),
+            p_rasterization_state: &rasterization_state,
+            p_multisample_state: &multisample_state,
+            p_depth_stencil_state: depth_stencil_state
+                .as_ref()
+                .map_or(std::ptr::null(), |state| state),
+            p_color_blend_state: color_blend_state
+                .as_ref()
+                .map_or(std::ptr::null(), |state| state),
+            p_dynamic_state: if dynamic_states.is_empty() {
+                std::ptr::null()
+            } else {
+                &dynamic_state
+            },
+            #[cfg(target_pointer_width = "64")]
+            layout: self.layout.handle.as_ptr(),
+            #[cfg(not(target_pointer_width = "64"))]
+            layout: self.layout.handle.get(),
+            render_pass,
+            subpass,
+            #[cfg(target_pointer_width = "64")]
+            base_pipeline_handle: std::ptr::null_mut(),
+            #[cfg(not(target_pointer_width = "64"))]
+            base_pipeline_handle: 0,
+            base_pipeline_index: -1,
+        };
+
+        let mut handle = MaybeUninit::uninit();
+        let result = unsafe {
+            (device.dispatch_loader.vk_create_graphics_pipelines)(
+                device.handle.as_ptr(),
+                cache_handle(self.cache),
+                1,
+                &create_info,
+                std::ptr::null(),
+                handle.as_mut_ptr(),
+            )
+        };
+
+        match result {
+            ffi::Result::Success => Ok(Pipeline {
+                #[cfg(target_pointer_width = "64")]
+                handle: unsafe { NonNull::new_unchecked(handle.assume_init()) },
+                #[cfg(not(target_pointer_width = "64"))]
+                handle: unsafe { NonZeroU64::new_unchecked(handle.assume_init()) },
+                device,
+                bind_point: PipelineBindPoint::Graphics,
+                #[cfg(target_pointer_width = "64")]
+                _marker: PhantomData,
+            }),
+            ffi::Result::ErrorOutOfHostMemory => Err(Error::OutOfHostMemory),
+            ffi::Result::ErrorOutOfDeviceMemory => Err(Error::OutOfDeviceMemory),
+            ffi::Result::ErrorInvalidShaderNv => Err(Error::InvalidShaderNv),
+            _ => unreachable!(),
+        }
+    }
+
+    fn validate(&self, device: &Device) -> Result<&RenderTarget<'a>> {
+        let target = self
+            .target
+            .as_ref()
+            .ok_or(Error::MissingPipelineState("render target"))?;
+        let (color_attachment_count, depth_format, stencil_format) = match *target {
+            RenderTarget::RenderPass(render_pass, subpass) => {
+                let attachments = render_pass
+                    .subpass(subpass)
+                    .ok_or(Error::InvalidSubpass(subpass))?;
+                let format = attachments.depth_stencil_format;
+                (attachments.color_attachment_count, format, format)
+            }
+            RenderTarget::Rendering(ref rendering) => {
+                if !device.supports_dynamic_rendering() {
+                    return Err(Error::FeatureNotEnabled("dynamicRendering"));
+                }
+                let depth_format = rendering.depth_attachment_format;
+                let stencil_format = rendering.stencil_attachment_format;
+                if let Some(format) = depth_format.filter(|format| !format.has_depth()) {
+                    return Err(Error::InvalidDepthStencilFormat(format));
+                }
+                if let Some(format) = stencil_format.filter(|format| !format.has_stencil()) {
+                    return Err(Error::InvalidDepthStencilFormat(format));
+                }
+                if let (Some(depth), Some(stencil)) = (depth_format, stencil_format) {
+                    if depth != stencil {
+                        return Err(Error::InvalidDepthStencilFormat(stencil));
+                    }
+                }
+                (
+                    rendering.color_attachment_formats.len() as u32,
+                    depth_format,
+                    stencil_format,
+                )
+            }
+        };
+        if self
+            .dynamic_state
+            .dynamic_states
+            .iter()
+            .any(DynamicState::is_extended)
+            && !device.supports_extended_dynamic_state()
+        {
+            return Err(Error::FeatureNotEnabled("extendedDynamicState"));
+        }
+
+        // Without rasterizer discard, the driver reads the states the attachments need.
+        if !self.rasterization_state.rasterizer_discard_enable {
+            if self.viewport_state.is_none() {
+                return Err(Error::MissingPipelineState("viewport"));
+            }
+            if self.depth_stencil_state.is_none()
+                && (depth_format.is_some() || stencil_format.is_some())
+            {
+                return Err(Error::MissingPipelineState("depth/stencil"));
+            }
+            if self.color_blend_state.is_none() && color_attachment_count > 0 {
+                return Err(Error::MissingPipelineState("color blend"));
+            }
+        }
+
+        if let Some(depth_stencil) = &self.depth_stencil_state {
+            if let Some(format) =
+                depth_format.filter(|format| depth_stencil.uses_depth() && !format.has_depth())
+            {
+                return Err(Error::InvalidDepthStencilFormat(format));
+            }
+            if let Some(format) = stencil_format
+                .filter(|format| depth_stencil.stencil_test_enable && !format.has_stencil())
+            {
+                return Err(Error::InvalidDepthStencilFormat(format));
+            }
+        }
+
+        self.validate_interfaces()?;
+
+        Ok(target)
+    }
+
+    fn validate_interfaces(&self) -> Result<()> {
+        let entry_point = |stage: ShaderStage| {
+            self.stages
+                .iter()
+                .find(|info| info.stage == stage)
+                .and_then(|info| info.module.reflection().entry_point(info.name))
+        };
+
+        if let Some(vertex) = entry_point(ShaderStage::Vertex) {
+            vertex.validate_vertex_attributes(
+                self.vertex_input_state
+                    .vertex_attribute_descriptions
+                    .unwrap_or_default(),
+            )?;
+        }
+
+        let fragment = match entry_point(ShaderStage::Fragment) {
+            Some(fragment) => fragment,
+            None => return Ok(()),
+        };
+
+        let last_stage = [
+            ShaderStage::MeshNv,
+            ShaderStage::Geometry,
+            ShaderStage::TessellationEvaluation,
+            ShaderStage::Vertex,
+        ]
+        .into_iter()
+        .find_map(entry_point);
+        if let Some(last_stage) = last_stage {
+            fragment.validate_inputs_from(last_stage)?;
+        }
+
+        if let Some(color_blend) = &self.color_blend_state {
+            fragment.validate_color_attachments(
+                color_blend.attachments.unwrap_or_default().len() as u32,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ReflectedLayoutBuilder {
+    /// Merges the interfaces of the shader modules of `stages`.
+    ///
+    /// # Errors
+    ///
+    /// [`IncompatibleBinding`](Error::IncompatibleBinding) if two stages declare different
+    /// descriptor types for the same set and binding.
+    pub fn new(stages: &[PipelineShaderStageCreateInfo]) -> Result<Self> {
+        let reflections: Vec<_> = stages
+            .iter()
+            .map(|stage| (stage.stage, stage.module.reflection()))
+            .collect();
+
+        Self::from_reflections(&reflections)
+    }
+
+    /// Merges the interfaces of shader modules reflected without a device, each used by the
+    /// stage it is paired with.
+    ///
+    /// # Errors
+    ///
+    /// [`IncompatibleBinding`](Error::IncompatibleBinding) if two stages declare different
+    /// descriptor types for the same set and binding.
+    pub fn from_reflections(stages: &[(ShaderStage, &ShaderReflection)]) -> Result<Self> {
+        let mut sets: Vec<Vec<LayoutBinding>> = Vec::new();
+        let mut push_constant_ranges: Vec<PushConstantRange> = Vec::new();
+
+        for &(stage, reflection) in stages {
+            let stage_flags = ShaderStageFlags::from(stage);
+
+            for declared in reflection.descriptor_bindings() {
+                let set = declared.set() as usize;
+                if sets.len() <= set {
+                    sets.resize_with(set + 1, Vec::new);
+                }
+
+                match sets[set]
+                    .iter_mut()
+                    .find(|binding| binding.binding == declared.binding())
+                {
+                    Some(binding) if binding.descriptor_type != declared.descriptor_type() => {
+                        return Err(Error::IncompatibleBinding {
+                            set: declared.set(),
+                            binding: declared.binding(),
+                        });
+                    }
+                    Some(binding) => {
+                        binding.descriptor_count =
+                            binding.descriptor_count.max(declared.descriptor_count());
+                        binding.stage_flags.0 |= stage_flags.0;
+                    }
+                    None => sets[set].push(LayoutBinding {
+                        binding: declared.binding(),
+                        descriptor_type: declared.descriptor_type(),
+                        descriptor_count: declared.descriptor_count(),
+                        stage_flags,
+                    }),
+                }
+            }
+
+            if let Some(block) = reflection.push_constant_block() {
+                let offset = block
+                    .members()
+                    .iter()
+                    .map(|member| member.offset())
+                    .min()
+                    .unwrap_or_default()
+                    & !3;
+                let size = block.size().saturating_sub(offset);
+
+                if size == 0 {
+                    continue;
+                }
+                let covers = |outer: (u32, u32), inner: (u32, u32)| {
+                    outer.0 <= inner.0 && inner.0 + inner.1 <= outer.0 + outer.1
+                };
+                if let Some(range) = push_constant_ranges
+                    .iter_mut()
+                    .find(|range| covers((range.offset, range.size), (offset, size)))
+                {
+                    range.stage_flags.0 |= stage_flags.0;
+                } else {
+                    let mut merged = PushConstantRange::new(stage_flags, offset, size);
+                    push_constant_ranges.retain(|range| {
+                        let covered = covers((offset, size), (range.offset, range.size));
+                        if covered {
+                            merged.stage_flags.0 |= range.stage_flags.0;
+                        }
+                        !covered
+                    });
+                    push_constant_ranges.push(merged);
+                }
+            }
+        }
+
+        Ok(Self {
+            sets,
+            push_constant_ranges,
+        })
+    }
+
+    /// Sets the number of descriptors of a binding, which is required for runtime-sized
+    /// arrays that are otherwise given no descriptors.
+    ///
+    /// # Errors
+    ///
+    /// [`UndeclaredBinding`](Error::UndeclaredBinding) if no stage declares `binding` of `set`.
+    pub fn with_descriptor_count(
+        &mut self,
+        set: u32,
+        binding: u32,
+        count: u32,
+    ) -> Result<&mut Self> {
+        let declared = self
+            .sets
+            .get_mut(set as usize)
+            .and_then(|bindings| bindings.iter_mut().find(|b| b.binding == binding))
+            .ok_or(Error::UndeclaredBinding { set, binding })?;
+        declared.descriptor_count = count;
+        Ok(self)
+    }
+
+    /// Number of descriptor set layouts, including empty ones for sets that no stage uses
+    /// below the highest set number.
+    pub fn set_count(&self) -> usize {
+        self.sets.len()
+    }
+
+    /// Descriptor type, count and stages of `binding` of `set`, or [`None`] if no stage
+    /// declares it.
+    pub fn binding(
+        &self,
+        set: u32,
+        binding: u32,
+    ) -> Option<(DescriptorType, u32, ShaderStageFlags)> {
+        self.sets
+            .get(set as usize)?
+            .iter()
+            .find(|b| b.binding == binding)
+            .map(|b| (b.descriptor_type, b.descriptor_count, b.stage_flags))
+    }
+
+    pub fn push_constant_ranges(&self) -> &[PushConstantRange] {
+        &self.push_constant_ranges
+    }
+
+    /// # Errors
+    ///
+    /// See [`DescriptorSetLayoutBuilder::build`] and [`PipelineLayoutBuilder::build`].
+    pub fn build<'a>(&self, device: &'a Device) -> Result<ReflectedLayout<'a>> {
+        let set_layouts = self
+            .sets
+            .iter()
+            .map(|bindings| {
+                let mut builder = DescriptorSetLayoutBuilder::new();
+                for binding in bindings {
+                    builder.with_binding(DescriptorSetLayoutBinding::new(
+                        binding.binding,
+                        binding.descriptor_type,
+                        binding.descriptor_count,
+                        binding.stage_flags,
+                    ));
+                }
+                builder.build(device)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let set_layout_refs: Vec<_> = set_layouts.iter().collect();
+        let mut builder = PipelineLayoutBuilder::new();
+        builder.with_set_layouts(&set_layout_refs);
+        for &range in &self.push_constant_ranges {
+            builder.with_push_constant_range(range);
+        }
+        let pipeline_layout = builder.build(device)?;
+
+        Ok(ReflectedLayout {
+            set_layouts,
+            pipeline_layout,
+        })
+    }
+}
+
+impl<'a> ReflectedLayout<'a> {
+    /// Set layouts in set number order, to allocate descriptor sets from.
+    pub fn set_layouts(&self) -> &[DescriptorSetLayout<'a>] {
+        &self.set_layouts
+    }
+
+    pub fn pipeline_layout(&self) -> &PipelineLayout<'a> {
+        &self.pipeline_layout
+    }
+}
+
+impl PushConstantRange {
+    pub fn new(stage_flags: ShaderStageFlags, offset: u32, size: u32) -> Self {
+        Self {
+            stage_flags,
+            offset,
+            size,
+        }
+    }
+
+    /// Range holding a `T` at `offset`, such as a `#[repr(C)]` struct with `#[derive(Pod)]`.
+    pub fn of<T: Pod>(stage_flags: ShaderStageFlags, offset: u32) -> Self {
+        Self::new(stage_flags, offset, std::mem::size_of::<T>() as u32)
+    }
+
+    pub fn stage_flags(&self) -> ShaderStageFlags {
+        self.stage_flags
+    }
+
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+}
+
+impl From<PushConstantRange> for ffi::PushConstantRange {
+    fn from(range: PushConstantRange) -> Self {
+        Self {
+            stage_flags: range.stage_flags.0,
+            offset: range.offset,
+            size: range.size,
+        }
+    }
+}
+
+impl<'a> PipelineLayout<'a> {
+    pub fn push_constant_ranges(&self) -> &[PushConstantRange] {
+        &self.push_constant_ranges
+    }
+
+    /// Checks that `size` bytes at `offset` can be pushed for `stages`: both must be multiples
+    /// of 4, every stage must have a push constant range covering all bytes, and every range
+    /// overlapping the bytes must only contain stages of `stages`.
+    ///
+    /// # Errors
+    /// - [`InvalidPushConstantRange`](Error::InvalidPushConstantRange) if the offset or size
+    ///   is not a multiple of 4 or the size is 0.
+    /// - [`UndeclaredPushConstants`](Error::UndeclaredPushConstants) if the bytes are not
+    ///   covered by the ranges of the layout as described above.
+    pub fn validate_push_constants(
+        &self,
+        stages: ShaderStageFlags,
+        offset: u32,
+        size: u32,
+    ) -> Result<()> {
+        if size == 0 || !offset.is_multiple_of(4) || !size.is_multiple_of(4) {
+            return Err(Error::InvalidPushConstantRange { offset, size });
+        }
+        let end = offset as u64 + size as u64;
+
+        // Each stage is in at most one range, so it has to cover all bytes on its own.
+        let mut covered = 0;
+        for range in &self.push_constant_ranges {
+            let range_end = range.offset as u64 + range.size as u64;
+            if range.offset as u64 >= end || offset as u64 >= range_end {
+                continue;
+            }
+            if range.stage_flags.0 & !stages.0 != 0 {
+                return Err(Error::UndeclaredPushConstants { offset, size });
+            }
+            if range.offset <= offset && end <= range_end {
+                covered |= range.stage_flags.0;
+            }
+        }
+        if stages.0 == 0 || stages.0 & !covered != 0 {
+            return Err(Error::UndeclaredPushConstants { offset, size });
+        }
+
+        Ok(())
+    }
+
+    /// Checks that `sets` can be bound to consecutive set numbers starting at `first_set`
+    /// with `dynamic_offsets`, one per dynamic descriptor of the sets in set, binding and
+    /// array element order.
+    ///
+    /// # Errors
+    /// - [`IncompatibleDescriptorSet`](Error::IncompatibleDescriptorSet) if a set number is
+    ///   not declared by the layout, or the set bound to it was allocated with a set layout
+    ///   that declares other bindings.
+    /// - [`DynamicOffsetCountMismatch`](Error::DynamicOffsetCountMismatch) if there are not
+    ///   exactly as many dynamic offsets as dynamic descriptors.
+    /// - [`MisalignedMemoryOffset`](Error::MisalignedMemoryOffset) if a dynamic offset is not
+    ///   a multiple of `minUniformBufferOffsetAlignment` or `minStorageBufferOffsetAlignment`.
+    pub fn validate_descriptor_sets(
+        &self,
+        first_set: u32,
+        sets: &[&DescriptorSet],
+        dynamic_offsets: &[u32],
+    ) -> Result<()> {
+        let mut dynamic_types = Vec::new();
+        for (set, descriptor_set) in (first_set..).zip(sets) {
+            let declared = self
+                .set_layouts
+                .get(set as usize)
+                .ok_or(Error::IncompatibleDescriptorSet(set))?;
+            if *declared != sorted_bindings(descriptor_set.layout()) {
+                return Err(Error::IncompatibleDescriptorSet(set));
+            }
+
+            for binding in declared {
+                if matches!(
+                    binding.descriptor_type,
+                    DescriptorType::UniformBufferDynamic | DescriptorType::StorageBufferDynamic
+                ) {
+                    dynamic_types.extend(std::iter::repeat_n(
+                        binding.descriptor_type,
+                        binding.descriptor_count as usize,
+                    ));
+                }
+            }
+        }
+
+        if dynamic_types.len() != dynamic_offsets.len() {
+            return Err(Error::DynamicOffsetCountMismatch {
+                expected: dynamic_types.len() as u32,
+                actual: dynamic_offsets.len() as u32,
+            });
+        }
+        let limits = self.device.limits();
+        for (descriptor_type, &offset) in dynamic_types.into_iter().zip(dynamic_offsets) {
+            let alignment = if descriptor_type == DescriptorType::UniformBufferDynamic {
+                limits.min_uniform_buffer_offset_alignment
+            } else {
+                limits.min_storage_buffer_offset_alignment
+            };
+            if !u64::from(offset).is_multiple_of(alignment) {
+                return Err(Error::MisalignedMemoryOffset {
+                    offset: offset.into(),
+                    alignment,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn sorted_bindings(layout: &DescriptorSetLayout) -> Vec<LayoutBinding> {
+    let mut bindings = layout.bindings.clone();
+    bindings.sort_by_key(|binding| binding.binding);
+    bindings
+}
+
+impl<'a> Drop for PipelineLayout<'a> {
+    fn drop(&mut self) {
+        println!("Dropped PipelineLayout");
+        unsafe {
+            (self.device.dispatch_loader.vk_destroy_pipeline_layout)(
+                self.device.handle.as_ptr(),
+                #[cfg(target_pointer_width = "64")]
+                self.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                self.handle.get(),
+                std::ptr::null(),
+            );
+        }
+    }
+}
+
+impl<'a> fmt::Debug for PipelineLayout<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PipelineLayout")
+            .field("handle", &self.handle)
+            .field("push_constant_ranges", &self.push_constant_ranges)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'b> PipelineLayoutBuilder<'b> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_set_layouts(&mut self, set_layouts: &[&'b DescriptorSetLayout<'b>]) -> &mut Self {
+        self.set_layouts.extend_from_slice(set_layouts);
+        self
+    }
+
+    pub fn with_push_constant_range(&mut self, range: PushConstantRange) -> &mut Self {
+        self.push_constant_ranges.push(range);
+        self
+    }
+
+    /// # Errors
+    ///
+    /// Besides the errors returned by the driver, the layout is checked against the
+    /// [`PhysicalDeviceLimits`](crate::device::PhysicalDeviceLimits) of `device` first.
+    /// - [`LimitExceeded`](Error::LimitExceeded) if there are more set layouts than
+    ///   `maxBoundDescriptorSets`, a push constant range ends past `maxPushConstantsSize`, or
+    ///   the set layouts declare more descriptors of a kind than the matching
+    ///   `maxDescriptorSet*` limit.
+    /// - [`InvalidPushConstantRange`](Error::InvalidPushConstantRange) if a range is not
+    ///   aligned to 4 bytes, is empty or has no shader stages.
+    /// - [`OverlappingPushConstantRanges`](Error::OverlappingPushConstantRanges) if two ranges
+    ///   include the same shader stage.
+    pub fn build<'a>(&self, device: &'a Device) -> Result<PipelineLayout<'a>> {
+        self.validate(device)?;
+
+        let set_layouts: Vec<_> = self
+            .set_layouts
+            .iter()
+            .map(|layout| {
+                #[cfg(target_pointer_width = "64")]
+                let handle = layout.handle.as_ptr();
+                #[cfg(not(target_pointer_width = "64"))]
+                let handle = layout.handle.get();
+                handle
+            })
+            .collect();
+        let push_constant_ranges: Vec<ffi::PushConstantRange> = self
+            .push_constant_ranges
+            .iter()
+            .map(|&range| range.into())
+            .collect();
+
+        let create_info = ffi::PipelineLayoutCreateInfo {
+            s_type: ffi::StructureType::PipelineLayoutCreateInfo,
+            p_next: std::ptr::null(),
+            flags: 0,
+            set_layout_count: set_layouts.len() as u32,
+            p_set_layouts: set_layouts.as_ptr(),
+            push_constant_range_count: push_constant_ranges.len() as u32,
+            p_push_constant_ranges: push_constant_ranges.as_ptr(),
+        };
+
+        let mut handle = MaybeUninit::uninit();
+        let result = unsafe {
+            (device.dispatch_loader.vk_create_pipeline_layout)(
+                device.handle.as_ptr(),
+                &create_info,
+                std::ptr::null(),
+                handle.as_mut_ptr(),
+            )
+        };
+
+        match result {
+            ffi::Result::Success => Ok(PipelineLayout {
+                #[cfg(target_pointer_width = "64")]
+                handle: unsafe { NonNull::new_unchecked(handle.assume_init()) },
+                #[cfg(not(target_pointer_width = "64"))]
+                handle: unsafe { NonZeroU64::new_unchecked(handle.assume_init()) },
+                device,
+                set_layouts: self
+                    .set_layouts
+                    .iter()
+                    .map(|layout| sorted_bindings(layout))
+                    .collect(),
+                push_constant_ranges: self.push_constant_ranges.clone(),
+                #[cfg(target_pointer_width = "64")]
+                _marker: PhantomData,
+            }),
+            ffi::Result::ErrorOutOfHostMemory => Err(Error::OutOfHostMemory),
+            ffi::Result::ErrorOutOfDeviceMemory => Err(Error::OutOfDeviceMemory),
+            _ => unreachable!(),
+        }
+    }
+
+    fn validate(&self, device: &Device) -> Result<()> {
+        let limits = device.limits();
+
+        if self.set_layouts.len() as u32 > limits.max_bound_descriptor_sets {
+            return Err(Error::LimitExceeded {
+                limit: "maxBoundDescriptorSets",
+                value: self.set_layouts.len() as u64,
+                max: limits.max_bound_descriptor_sets as u64,
+            });
+        }
+
+        let mut stages = 0;
+        for range in self.push_constant_ranges.iter() {
+            if range.offset % 4 != 0
+                || range.size == 0
+                || range.size % 4 != 0
+                || range.stage_flags.is_empty()
+            {
+                return Err(Error::InvalidPushConstantRange {
+                    offset: range.offset,
+                    size: range.size,
+                });
+            }
+            let end = range.offset as u64 + range.size as u64;
+            if end > limits.max_push_constants_size as u64 {
+                return Err(Error::LimitExceeded {
+                    limit: "maxPushConstantsSize",
+                    value: end,
+                    max: limits.max_push_constants_size as u64,
+                });
+            }
+            if stages & range.stage_flags.0 != 0 {
+                return Err(Error::OverlappingPushConstantRanges);
+            }
+            stages |= range.stage_flags.0;
+        }
+
+        let count = |types: &[DescriptorType]| -> u64 {
+            self.set_layouts
+                .iter()
+                .flat_map(|layout| layout.bindings.iter())
+                .filter(|binding| types.contains(&binding.descriptor_type))
+                .map(|binding| binding.descriptor_count as u64)
+                .sum()
+        };
+        let checks = [
+            (
+                "maxDescriptorSetSamplers",
+                limits.max_descriptor_set_samplers,
+                count(&[
+                    DescriptorType::Sampler,
+                    DescriptorType::CombinedImageSampler,
+                ]),
+            ),
+            (
+                "maxDescriptorSetUniformBuffers",
+                limits.max_descriptor_set_uniform_buffers,
+                count(&[
+                    DescriptorType::UniformBuffer,
+                    DescriptorType::UniformBufferDynamic,
+                ]),
+            ),
+            (
+                "maxDescriptorSetUniformBuffersDynamic",
+                limits.max_descriptor_set_uniform_buffers_dynamic,
+                count(&[DescriptorType::UniformBufferDynamic]),
+            ),
+            (
+                "maxDescriptorSetStorageBuffers",
+                limits.max_descriptor_set_storage_buffers,
+                count(&[
+                    DescriptorType::StorageBuffer,
+                    DescriptorType::StorageBufferDynamic,
+                ]),
+            ),
+            (
+                "maxDescriptorSetStorageBuffersDynamic",
+                limits.max_descriptor_set_storage_buffers_dynamic,
+                count(&[DescriptorType::StorageBufferDynamic]),
+            ),
+            (
+                "maxDescriptorSetSampledImages",
+                limits.max_descriptor_set_sampled_images,
+                count(&[
+                    DescriptorType::CombinedImageSampler,
+                    DescriptorType::SampledImage,
+                    DescriptorType::UniformTexelBuffer,
+                ]),
+            ),
+            (
+                "maxDescriptorSetStorageImages",
+                limits.max_descriptor_set_storage_images,
+                count(&[
+                    DescriptorType::StorageImage,
+                    DescriptorType::StorageTexelBuffer,
+                ]),
+            ),
+            (
+                "maxDescriptorSetInputAttachments",
+                limits.max_descriptor_set_input_attachments,
+                count(&[DescriptorType::InputAttachment]),
+            ),
+        ];
+
+        for (limit, max, value) in checks {
+            if value > max as u64 {
+                return Err(Error::LimitExceeded {
+                    limit,
+                    value,
+                    max: max as u64,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Handle of an optional pipeline cache as passed to the `vkCreate*Pipelines` functions.
+#[cfg(target_pointer_width = "64")]
+pub(crate) fn cache_handle(cache: Option<&PipelineCache>) -> *mut ffi::VkPipelineCache_T {
+    cache.map_or(std::ptr::null_mut(), |cache| cache.handle.as_ptr())
+}
+
+/// Handle of an optional pipeline cache as passed to the `vkCreate*Pipelines` functions.
+#[cfg(not(target_pointer_width = "64"))]
+pub(crate) fn cache_handle(cache: Option<&PipelineCache>) -> u64 {
+    cache.map_or(0, |cache| cache.handle.get())
+}
+
+impl<'a> PipelineCache<'a> {
+    /// Creates a pipeline cache from the file at `path` written by [`save`](Self::save).
+    ///
+    /// A missing or unreadable file, or data written by a different driver or device (see
+    /// [`PipelineCacheHeader::is_compatible`]), results in an empty cache.
+    ///
+    /// # Errors
+    ///
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    pub fn from_file<P: AsRef<Path>>(device: &'a Device, path: P) -> Result<Self> {
+        let data = std::fs::read(path).unwrap_or_default();
+
+        PipelineCacheBuilder::new()
+            .with_initial_data(&data)
+            .build(device)
+    }
+
+    /// Retrieves the data of the cache, starting with a [`PipelineCacheHeader`].
+    ///
+    /// # Errors
+    ///
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    pub fn data(&self) -> Result<Vec<u8>> {
+        loop {
+            let mut data_size = 0;
+            let result = unsafe {
+                (self.device.dispatch_loader.vk_get_pipeline_cache_data)(
+                    self.device.handle.as_ptr(),
+                    #[cfg(target_pointer_width = "64")]
+                    self.handle.as_ptr(),
+                    #[cfg(not(target_pointer_width = "64"))]
+                    self.handle.get(),
+                    &mut data_size,
+                    std::ptr::null_mut(),
+                )
+            };
+            match result {
+                ffi::Result::Success => (),
+                ffi::Result::ErrorOutOfHostMemory => return Err(Error::OutOfHostMemory),
+                ffi::Result::ErrorOutOfDeviceMemory => return Err(Error::OutOfDeviceMemory),
+                _ => unreachable!(),
+            }
+
+            let mut data: Vec<u8> = Vec::with_capacity(data_size);
+            let result = unsafe {
+                (self.device.dispatch_loader.vk_get_pipeline_cache_data)(
+                    self.device.handle.as_ptr(),
+                    #[cfg(target_pointer_width = "64")]
+                    self.handle.as_ptr(),
+                    #[cfg(not(target_pointer_width = "64"))]
+                    self.handle.get(),
+                    &mut data_size,
+                    data.as_mut_ptr().cast(),
+                )
+            };
+
+            // NOTE: The cache can grow between the two calls if pipelines are being created on
+            // another thread, in which case the data is truncated and has to be queried again.
+            match result {
+                ffi::Result::Success => {
+                    unsafe { data.set_len(data_size) };
+                    return Ok(data);
+                }
+                ffi::Result::Incomplete => continue,
+                ffi::Result::ErrorOutOfHostMemory => return Err(Error::OutOfHostMemory),
+                ffi::Result::ErrorOutOfDeviceMemory => return Err(Error::OutOfDeviceMemory),
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    /// Merges the contents of `src_caches` into this cache.
+    ///
+    /// # Errors
+    ///
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    pub fn merge(&self, src_caches: &[&PipelineCache]) -> Result<()> {
+        let src_caches: Vec<_> = src_caches
+            .iter()
+            .map(|cache| {
+                #[cfg(target_pointer_width = "64")]
+                let handle = cache.handle.as_ptr();
+                #[cfg(not(target_pointer_width = "64"))]
+                let handle = cache.handle.get();
+                handle
+            })
+            .collect();
+
+        let result = unsafe {
+            (self.device.dispatch_loader.vk_merge_pipeline_caches)(
+                self.device.handle.as_ptr(),
+                #[cfg(target_pointer_width = "64")]
+                self.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                self.handle.get(),
+                src_caches.len() as u32,
+                src_caches.as_ptr(),
+            )
+        };
+
+        match result {
+            ffi::Result::Success => Ok(()),
+            ffi::Result::ErrorOutOfHostMemory => Err(Error::OutOfHostMemory),
+            ffi::Result::ErrorOutOfDeviceMemory => Err(Error::OutOfDeviceMemory),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Writes the data of the cache to the file at `path`, to be loaded with
+    /// [`from_file`](Self::from_file) on the next run.
+    ///
+    /// The data is written to a temporary file next to `path` first and then renamed, so an
+    /// interrupted save never leaves a truncated cache behind.
+    ///
+    /// # Errors
+    ///
+    /// Returns the I/O error, or a [`core::Error`](Error) wrapped in [`io::Error`] if the data
+    /// could not be retrieved.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        let data = self.data().map_err(io::Error::other)?;
+
+        let mut tmp_path = path.as_os_str().to_owned();
+        tmp_path.push(".tmp");
+        std::fs::write(&tmp_path, data)?;
+        std::fs::rename(&tmp_path, path)
+    }
+}
+
+impl<'a> Drop for PipelineCache<'a> {
+    fn drop(&mut self) {
+        println!("Dropped PipelineCache");
+        unsafe {
+            (self.device.dispatch_loader.vk_destroy_pipeline_cache)(
+                self.device.handle.as_ptr(),
+                #[cfg(target_pointer_width = "64")]
+                self.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                self.handle.get(),
+                std::ptr::null(),
+            );
+        }
+    }
+}
+
+impl<'a> fmt::Debug for PipelineCache<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PipelineCache")
+            .field("handle", &self.handle)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'b> PipelineCacheBuilder<'b> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// The application promises to synchronize all access to the cache itself, which lets the
+    /// driver skip internal locking. Requires `"VK_EXT_pipeline_creation_cache_control"` or
+    /// Vulkan 1.3.
+    pub fn with_externally_synchronized(&mut self, externally_synchronized: bool) -> &mut Self {
+        self.externally_synchronized = externally_synchronized;
+        self
+    }
+
+    /// Data previously retrieved with [`PipelineCache::data`]. It is discarded on
+    /// [`build`](Self::build) if its header does not match the device.
+    pub fn with_initial_data(&mut self, initial_data: &'b [u8]) -> &mut Self {
+        self.initial_data = Some(initial_data);
+        self
+    }
+
+    /// # Errors
+    ///
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    pub fn build<'a>(&self, device: &'a Device) -> Result<PipelineCache<'a>> {
+        let initial_data = self
+            .initial_data
+            .filter(|data| {
+                PipelineCacheHeader::parse(data)
+                    .is_some_and(|header| header.is_compatible(device.properties()))
+            })
+            .unwrap_or_default();
+
+        let mut flags = 0;
+        if self.externally_synchronized {
+            flags |= ffi::PipelineCacheCreateFlagBits::ExternallySynchronizedBitExt as u32;
+        }
+
+        let create_info = ffi::PipelineCacheCreateInfo {
+            s_type: ffi::StructureType::PipelineCacheCreateInfo,
+            p_next: std::ptr::null(),
+            flags,
+            initial_data_size: initial_data.len(),
+            p_initial_data: initial_data.as_ptr().cast(),
+        };
+
+        let mut handle = MaybeUninit::uninit();
+        let result = unsafe {
+            (device.dispatch_loader.vk_create_pipeline_cache)(
+                device.handle.as_ptr(),
+                &create_info,
+                std::ptr::null(),
+                handle.as_mut_ptr(),
+            )
+        };
+
+        match result {
+            ffi::Result::Success => Ok(PipelineCache {
+                #[cfg(target_pointer_width = "64")]
+                handle: unsafe { NonNull::new_unchecked(handle.assume_init()) },
+                #[cfg(not(target_pointer_width = "64"))]
+                handle: unsafe { NonZeroU64::new_unchecked(handle.assume_init()) },
+                device,
+                #[cfg(target_pointer_width = "64")]
+                _marker: PhantomData,
+            }),
+            ffi::Result::ErrorOutOfHostMemory => Err(Error::OutOfHostMemory),
+            ffi::Result::ErrorOutOfDeviceMemory => Err(Error::OutOfDeviceMemory),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl PipelineCacheHeader {
+    /// Size of the header for `VK_PIPELINE_CACHE_HEADER_VERSION_ONE`.
+    const SIZE: usize = 32;
+
+    /// Reads the header at the start of `data`.
+    ///
+    /// Returns [`None`] if `data` is too short or the header version is unknown.
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        if data.len() < Self::SIZE {
+            return None;
+        }
+
+        // NOTE: Unlike other Vulkan structures, the header is always little-endian.
+        let read_u32 = |offset: usize| {
+            let mut bytes = [0; 4];
+            bytes.copy_from_slice(&data[offset..offset + 4]);
+            u32::from_le_bytes(bytes)
+        };
+
+        let header_size = read_u32(0) as usize;
+        let header_version = read_u32(4);
+        if header_size < Self::SIZE
+            || header_size > data.len()
+            || header_version != ffi::PipelineCacheHeaderVersion::One as u32
+        {
+            return None;
+        }
+
+        let mut pipeline_cache_uuid = [0; 16];
+        pipeline_cache_uuid.copy_from_slice(&data[16..32]);
+
+        Some(Self {
+            vendor_id: read_u32(8),
+            device_id: read_u32(12),
+            pipeline_cache_uuid,
+        })
+    }
+
+    /// Whether cache data with this header was written by the same driver and device.
+    pub fn is_compatible(&self, properties: &PhysicalDeviceProperties) -> bool {
+        self.vendor_id == properties.vendor_id
+            && self.device_id == properties.device_id
+            && self.pipeline_cache_uuid == properties.pipeline_cache_uuid
+    }
+}
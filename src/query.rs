@@ -0,0 +1,137 @@
+use std::marker::PhantomData;
+#[cfg(not(target_pointer_width = "64"))]
+use std::num::NonZeroU64;
+#[cfg(target_pointer_width = "64")]
+use std::ptr::NonNull;
+
+use crate::core::{Error, Result};
+use crate::device::Device;
+use crate::ffi;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryType {
+    /// Number of samples passing the per-fragment tests between the beginning and the end of
+    /// the query.
+    Occlusion,
+    /// Device time at which all previous commands completed a pipeline stage, in ticks of
+    /// [`timestamp_period`](crate::device::PhysicalDeviceLimits::timestamp_period)
+    /// nanoseconds.
+    Timestamp,
+}
+
+pub struct QueryPool<'a> {
+    #[cfg(target_pointer_width = "64")]
+    pub(crate) handle: NonNull<ffi::VkQueryPool_T>,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub(crate) handle: NonZeroU64,
+    pub(crate) device: &'a Device<'a>,
+    pub(crate) query_type: QueryType,
+    pub(crate) query_count: u32,
+    #[cfg(target_pointer_width = "64")]
+    pub(crate) _marker: PhantomData<ffi::VkQueryPool_T>,
+}
+
+impl<'a> QueryPool<'a> {
+    pub fn query_type(&self) -> QueryType {
+        self.query_type
+    }
+
+    pub fn query_count(&self) -> u32 {
+        self.query_count
+    }
+
+    /// Returns the 64-bit results of `query_count` queries starting at `first_query`. If `wait`
+    /// is `false` and a result is not available yet, returns `None` instead of blocking.
+    ///
+    /// # Errors
+    ///
+    /// - [`QueryOutOfBounds`](Error::QueryOutOfBounds) if the queries extend past the end of
+    ///   the pool.
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    /// - [`DeviceLost`](Error::DeviceLost)
+    pub fn get_results(
+        &self,
+        first_query: u32,
+        query_count: u32,
+        wait: bool,
+    ) -> Result<Option<Vec<u64>>> {
+        self.check_range(first_query, query_count)?;
+
+        let mut results = vec![0u64; query_count as usize];
+        let mut flags = ffi::QueryResultFlagBits::ResultBit64 as u32;
+        if wait {
+            flags |= ffi::QueryResultFlagBits::WaitBit as u32;
+        }
+
+        let result = unsafe {
+            (self.device.dispatch_loader.vk_get_query_pool_results)(
+                self.device.handle.as_ptr(),
+                #[cfg(target_pointer_width = "64")]
+                self.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                self.handle.get(),
+                first_query,
+                query_count,
+                std::mem::size_of_val(results.as_slice()),
+                results.as_mut_ptr().cast(),
+                std::mem::size_of::<u64>() as u64,
+                flags,
+            )
+        };
+
+        match result {
+            ffi::Result::Success => Ok(Some(results)),
+            ffi::Result::NotReady => Ok(None),
+            ffi::Result::ErrorOutOfHostMemory => Err(Error::OutOfHostMemory),
+            ffi::Result::ErrorOutOfDeviceMemory => Err(Error::OutOfDeviceMemory),
+            ffi::Result::ErrorDeviceLost => Err(Error::DeviceLost),
+            _ => unreachable!(),
+        }
+    }
+
+    pub(crate) fn check_range(&self, first_query: u32, query_count: u32) -> Result<()> {
+        match first_query.checked_add(query_count) {
+            Some(end) if end <= self.query_count => Ok(()),
+            _ => Err(Error::QueryOutOfBounds {
+                first_query,
+                query_count,
+            }),
+        }
+    }
+}
+
+impl<'a> std::fmt::Debug for QueryPool<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QueryPool")
+            .field("handle", &self.handle)
+            .field("query_type", &self.query_type)
+            .field("query_count", &self.query_count)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> Drop for QueryPool<'a> {
+    fn drop(&mut self) {
+        println!("Dropped QueryPool");
+        unsafe {
+            (self.device.dispatch_loader.vk_destroy_query_pool)(
+                self.device.handle.as_ptr(),
+                #[cfg(target_pointer_width = "64")]
+                self.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                self.handle.get(),
+                std::ptr::null(),
+            );
+        }
+    }
+}
+
+impl From<QueryType> for ffi::QueryType {
+    fn from(query_type: QueryType) -> Self {
+        match query_type {
+            QueryType::Occlusion => Self::Occlusion,
+            QueryType::Timestamp => Self::Timestamp,
+        }
+    }
+}
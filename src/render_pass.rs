@@ -0,0 +1,774 @@
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+#[cfg(not(target_pointer_width = "64"))]
+use std::num::NonZeroU64;
+#[cfg(target_pointer_width = "64")]
+use std::ptr::NonNull;
+
+use crate::core::{Error, Rect2D, Result};
+use crate::device::Device;
+use crate::ffi;
+use crate::format::Format;
+use crate::pipeline::{PipelineBindPoint, SampleCount};
+use crate::resource::{ImageLayout, ImageView};
+
+/// Attachment index meaning that the attachment reference is not used.
+pub const ATTACHMENT_UNUSED: u32 = !0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttachmentLoadOp {
+    Load,
+    Clear,
+    DontCare,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttachmentStoreOp {
+    Store,
+    DontCare,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct AttachmentDescription {
+    format: Format,
+    samples: SampleCount,
+    load_op: AttachmentLoadOp,
+    store_op: AttachmentStoreOp,
+    stencil_load_op: AttachmentLoadOp,
+    stencil_store_op: AttachmentStoreOp,
+    initial_layout: ImageLayout,
+    final_layout: ImageLayout,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AttachmentReference {
+    attachment: u32,
+    layout: ImageLayout,
+}
+
+#[derive(Debug, Default)]
+pub struct SubpassDescription<'a> {
+    input_attachments: &'a [AttachmentReference],
+    color_attachments: &'a [AttachmentReference],
+    resolve_attachments: Option<&'a [AttachmentReference]>,
+    depth_stencil_attachment: Option<AttachmentReference>,
+    preserve_attachments: &'a [u32],
+}
+
+/// Value an attachment is cleared to when loaded with [`AttachmentLoadOp::Clear`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClearValue {
+    Float([f32; 4]),
+    Int([i32; 4]),
+    Uint([u32; 4]),
+    DepthStencil { depth: f32, stencil: u32 },
+}
+
+/// How the samples of a multisampled attachment are combined when it is resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolveMode {
+    SampleZero,
+    Average,
+    Min,
+    Max,
+}
+
+#[derive(Default, Clone, Copy)]
+pub struct RenderingFlags(u32);
+
+/// Attachment of a dynamic rendering pass, see
+/// [`begin_rendering`](crate::command_buffer::CommandBuffer::begin_rendering).
+#[derive(Debug)]
+pub struct RenderingAttachmentInfo<'a> {
+    image_view: &'a ImageView<'a>,
+    image_layout: ImageLayout,
+    resolve: Option<(ResolveMode, &'a ImageView<'a>, ImageLayout)>,
+    load_op: AttachmentLoadOp,
+    store_op: AttachmentStoreOp,
+    clear_value: ClearValue,
+}
+
+/// Render area and attachments of a dynamic rendering pass.
+#[derive(Debug)]
+pub struct RenderingInfo<'a> {
+    flags: RenderingFlags,
+    render_area: Rect2D,
+    layer_count: u32,
+    view_mask: u32,
+    color_attachments: &'a [RenderingAttachmentInfo<'a>],
+    depth_attachment: Option<&'a RenderingAttachmentInfo<'a>>,
+    stencil_attachment: Option<&'a RenderingAttachmentInfo<'a>>,
+}
+
+pub struct RenderPass<'a> {
+    #[cfg(target_pointer_width = "64")]
+    pub(crate) handle: NonNull<ffi::VkRenderPass_T>,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub(crate) handle: NonZeroU64,
+    pub(crate) device: &'a Device<'a>,
+    subpasses: Vec<SubpassAttachments>,
+    #[cfg(target_pointer_width = "64")]
+    _marker: PhantomData<ffi::VkRenderPass_T>,
+}
+
+/// What a pipeline created for a subpass needs to know about its attachments.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SubpassAttachments {
+    pub(crate) color_attachment_count: u32,
+    pub(crate) depth_stencil_format: Option<Format>,
+}
+
+/// Builder of [`RenderPass`].
+#[derive(Debug, Default)]
+pub struct RenderPassBuilder<'b> {
+    attachments: Vec<AttachmentDescription>,
+    subpasses: Vec<SubpassDescription<'b>>,
+}
+
+impl From<AttachmentLoadOp> for ffi::AttachmentLoadOp {
+    fn from(load_op: AttachmentLoadOp) -> Self {
+        match load_op {
+            AttachmentLoadOp::Load => Self::Load,
+            AttachmentLoadOp::Clear => Self::Clear,
+            AttachmentLoadOp::DontCare => Self::DontCare,
+        }
+    }
+}
+
+impl From<AttachmentStoreOp> for ffi::AttachmentStoreOp {
+    fn from(store_op: AttachmentStoreOp) -> Self {
+        match store_op {
+            AttachmentStoreOp::Store => Self::Store,
+            AttachmentStoreOp::DontCare => Self::DontCare,
+        }
+    }
+}
+
+impl From<ClearValue> for ffi::ClearValue {
+    fn from(value: ClearValue) -> Self {
+        match value {
+            ClearValue::Float(float32) => Self {
+                color: ffi::ClearColorValue { float32 },
+            },
+            ClearValue::Int(int32) => Self {
+                color: ffi::ClearColorValue { int32 },
+            },
+            ClearValue::Uint(uint32) => Self {
+                color: ffi::ClearColorValue { uint32 },
+            },
+            ClearValue::DepthStencil { depth, stencil } => Self {
+                depth_stencil: ffi::ClearDepthStencilValue { depth, stencil },
+            },
+        }
+    }
+}
+
+impl Default for ClearValue {
+    fn default() -> Self {
+        Self::Float([0.0; 4])
+    }
+}
+
+impl From<ResolveMode> for ffi::ResolveModeFlagBits {
+    fn from(mode: ResolveMode) -> Self {
+        match mode {
+            ResolveMode::SampleZero => Self::SampleZeroBit,
+            ResolveMode::Average => Self::AverageBit,
+            ResolveMode::Min => Self::MinBit,
+            ResolveMode::Max => Self::MaxBit,
+        }
+    }
+}
+
+impl RenderingFlags {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Draws are recorded in secondary command buffers executed within the rendering pass.
+    pub fn with_contents_secondary_command_buffers(
+        mut self,
+        contents_secondary_command_buffers: bool,
+    ) -> Self {
+        if contents_secondary_command_buffers {
+            self.0 |= ffi::RenderingFlagBits::ContentsSecondaryCommandBuffersBit as u32;
+        } else {
+            self.0 &= !(ffi::RenderingFlagBits::ContentsSecondaryCommandBuffersBit as u32);
+        }
+        self
+    }
+
+    /// The rendering pass is suspended and resumed by a later one with the same attachments.
+    pub fn with_suspending(mut self, suspending: bool) -> Self {
+        if suspending {
+            self.0 |= ffi::RenderingFlagBits::SuspendingBit as u32;
+        } else {
+            self.0 &= !(ffi::RenderingFlagBits::SuspendingBit as u32);
+        }
+        self
+    }
+
+    /// The rendering pass resumes a previously suspended one.
+    pub fn with_resuming(mut self, resuming: bool) -> Self {
+        if resuming {
+            self.0 |= ffi::RenderingFlagBits::ResumingBit as u32;
+        } else {
+            self.0 &= !(ffi::RenderingFlagBits::ResumingBit as u32);
+        }
+        self
+    }
+}
+
+impl std::fmt::Debug for RenderingFlags {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut list = f.debug_list();
+        if self.0 & ffi::RenderingFlagBits::ContentsSecondaryCommandBuffersBit as u32 != 0 {
+            list.entry(&"ContentsSecondaryCommandBuffers");
+        }
+        if self.0 & ffi::RenderingFlagBits::SuspendingBit as u32 != 0 {
+            list.entry(&"Suspending");
+        }
+        if self.0 & ffi::RenderingFlagBits::ResumingBit as u32 != 0 {
+            list.entry(&"Resuming");
+        }
+        list.finish()
+    }
+}
+
+impl<'a> RenderingAttachmentInfo<'a> {
+    /// Attachment whose contents are neither loaded nor stored.
+    pub fn new(image_view: &'a ImageView<'a>, image_layout: ImageLayout) -> Self {
+        Self {
+            image_view,
+            image_layout,
+            resolve: None,
+            load_op: AttachmentLoadOp::DontCare,
+            store_op: AttachmentStoreOp::DontCare,
+            clear_value: Default::default(),
+        }
+    }
+
+    /// Resolves the multisampled attachment into `image_view` at the end of the rendering pass.
+    pub fn with_resolve(
+        mut self,
+        mode: ResolveMode,
+        image_view: &'a ImageView<'a>,
+        image_layout: ImageLayout,
+    ) -> Self {
+        self.resolve = Some((mode, image_view, image_layout));
+        self
+    }
+
+    pub fn with_load_op(mut self, load_op: AttachmentLoadOp) -> Self {
+        self.load_op = load_op;
+        self
+    }
+
+    pub fn with_store_op(mut self, store_op: AttachmentStoreOp) -> Self {
+        self.store_op = store_op;
+        self
+    }
+
+    pub fn with_clear_value(mut self, clear_value: ClearValue) -> Self {
+        self.clear_value = clear_value;
+        self
+    }
+}
+
+impl From<&RenderingAttachmentInfo<'_>> for ffi::RenderingAttachmentInfo {
+    fn from(info: &RenderingAttachmentInfo<'_>) -> Self {
+        let (resolve_mode, resolve_image_view, resolve_image_layout) = match info.resolve {
+            Some((mode, image_view, image_layout)) => {
+                #[cfg(target_pointer_width = "64")]
+                let handle = image_view.handle.as_ptr();
+                #[cfg(not(target_pointer_width = "64"))]
+                let handle = image_view.handle.get();
+                (mode.into(), handle, image_layout.into())
+            }
+            #[cfg(target_pointer_width = "64")]
+            None => (
+                ffi::ResolveModeFlagBits::None,
+                std::ptr::null_mut(),
+                ffi::ImageLayout::Undefined,
+            ),
+            #[cfg(not(target_pointer_width = "64"))]
+            None => (
+                ffi::ResolveModeFlagBits::None,
+                0,
+                ffi::ImageLayout::Undefined,
+            ),
+        };
+
+        Self {
+            s_type: ffi::StructureType::RenderingAttachmentInfo,
+            p_next: std::ptr::null(),
+            #[cfg(target_pointer_width = "64")]
+            image_view: info.image_view.handle.as_ptr(),
+            #[cfg(not(target_pointer_width = "64"))]
+            image_view: info.image_view.handle.get(),
+            image_layout: info.image_layout.into(),
+            resolve_mode,
+            resolve_image_view,
+            resolve_image_layout,
+            load_op: info.load_op.into(),
+            store_op: info.store_op.into(),
+            clear_value: info.clear_value.into(),
+        }
+    }
+}
+
+impl<'a> RenderingInfo<'a> {
+    pub fn new(render_area: Rect2D) -> Self {
+        Self {
+            flags: Default::default(),
+            render_area,
+            layer_count: 1,
+            view_mask: 0,
+            color_attachments: &[],
+            depth_attachment: None,
+            stencil_attachment: None,
+        }
+    }
+
+    pub fn with_flags(mut self, flags: RenderingFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    pub fn with_layer_count(mut self, layer_count: u32) -> Self {
+        self.layer_count = layer_count;
+        self
+    }
+
+    /// Enables multiview rendering into the views whose bits are set; `layer_count` is then
+    /// ignored.
+    pub fn with_view_mask(mut self, view_mask: u32) -> Self {
+        self.view_mask = view_mask;
+        self
+    }
+
+    /// Color attachments are bound to the fragment shader outputs in order.
+    pub fn with_color_attachments(
+        mut self,
+        color_attachments: &'a [RenderingAttachmentInfo<'a>],
+    ) -> Self {
+        self.color_attachments = color_attachments;
+        self
+    }
+
+    pub fn with_depth_attachment(
+        mut self,
+        depth_attachment: &'a RenderingAttachmentInfo<'a>,
+    ) -> Self {
+        self.depth_attachment = Some(depth_attachment);
+        self
+    }
+
+    /// For combined depth/stencil formats this is usually the same view as the depth
+    /// attachment.
+    pub fn with_stencil_attachment(
+        mut self,
+        stencil_attachment: &'a RenderingAttachmentInfo<'a>,
+    ) -> Self {
+        self.stencil_attachment = Some(stencil_attachment);
+        self
+    }
+
+    /// Converts to the ffi struct, with the attachments stored in `attachments` which has to
+    /// outlive the result.
+    pub(crate) fn to_ffi(
+        &self,
+        attachments: &mut Vec<ffi::RenderingAttachmentInfo>,
+    ) -> ffi::RenderingInfo {
+        let start = attachments.len();
+        let color_attachment_count = self.color_attachments.len();
+        attachments.extend(
+            self.color_attachments
+                .iter()
+                .map(|attachment| attachment.into()),
+        );
+        attachments.extend(self.depth_attachment.map(|attachment| attachment.into()));
+        attachments.extend(self.stencil_attachment.map(|attachment| attachment.into()));
+
+        let mut rest = attachments[start + color_attachment_count..].iter();
+        let p_depth_attachment = match self.depth_attachment {
+            Some(_) => rest.next().unwrap() as *const _,
+            None => std::ptr::null(),
+        };
+        let p_stencil_attachment = match self.stencil_attachment {
+            Some(_) => rest.next().unwrap() as *const _,
+            None => std::ptr::null(),
+        };
+
+        ffi::RenderingInfo {
+            s_type: ffi::StructureType::RenderingInfo,
+            p_next: std::ptr::null(),
+            flags: self.flags.0,
+            render_area: self.render_area.into(),
+            layer_count: self.layer_count,
+            view_mask: self.view_mask,
+            color_attachment_count: color_attachment_count as u32,
+            p_color_attachments: attachments[start..].as_ptr(),
+            p_depth_attachment,
+            p_stencil_attachment,
+        }
+    }
+}
+
+impl AttachmentDescription {
+    /// Single-sampled attachment whose contents are neither loaded nor stored.
+    pub fn new(format: Format) -> Self {
+        Self {
+            format,
+            samples: SampleCount::OneBit,
+            load_op: AttachmentLoadOp::DontCare,
+            store_op: AttachmentStoreOp::DontCare,
+            stencil_load_op: AttachmentLoadOp::DontCare,
+            stencil_store_op: AttachmentStoreOp::DontCare,
+            initial_layout: ImageLayout::Undefined,
+            final_layout: ImageLayout::General,
+        }
+    }
+
+    pub fn with_samples(mut self, samples: SampleCount) -> Self {
+        self.samples = samples;
+        self
+    }
+
+    pub fn with_load_op(mut self, load_op: AttachmentLoadOp) -> Self {
+        self.load_op = load_op;
+        self
+    }
+
+    pub fn with_store_op(mut self, store_op: AttachmentStoreOp) -> Self {
+        self.store_op = store_op;
+        self
+    }
+
+    pub fn with_stencil_load_op(mut self, stencil_load_op: AttachmentLoadOp) -> Self {
+        self.stencil_load_op = stencil_load_op;
+        self
+    }
+
+    pub fn with_stencil_store_op(mut self, stencil_store_op: AttachmentStoreOp) -> Self {
+        self.stencil_store_op = stencil_store_op;
+        self
+    }
+
+    pub fn with_initial_layout(mut self, initial_layout: ImageLayout) -> Self {
+        self.initial_layout = initial_layout;
+        self
+    }
+
+    pub fn with_final_layout(mut self, final_layout: ImageLayout) -> Self {
+        self.final_layout = final_layout;
+        self
+    }
+
+    pub fn format(&self) -> Format {
+        self.format
+    }
+}
+
+impl From<&AttachmentDescription> for ffi::AttachmentDescription {
+    fn from(description: &AttachmentDescription) -> Self {
+        Self {
+            flags: 0,
+            format: description.format.into(),
+            samples: description.samples.into(),
+            load_op: description.load_op.into(),
+            store_op: description.store_op.into(),
+            stencil_load_op: description.stencil_load_op.into(),
+            stencil_store_op: description.stencil_store_op.into(),
+            initial_layout: description.initial_layout.into(),
+            final_layout: description.final_layout.into(),
+        }
+    }
+}
+
+impl AttachmentReference {
+    /// `attachment` is an index into the attachments of the render pass or
+    /// [`ATTACHMENT_UNUSED`].
+    pub fn new(attachment: u32, layout: ImageLayout) -> Self {
+        Self { attachment, layout }
+    }
+
+    pub fn attachment(&self) -> u32 {
+        self.attachment
+    }
+
+    pub fn layout(&self) -> ImageLayout {
+        self.layout
+    }
+}
+
+impl From<&AttachmentReference> for ffi::AttachmentReference {
+    fn from(reference: &AttachmentReference) -> Self {
+        Self {
+            attachment: reference.attachment,
+            layout: reference.layout.into(),
+        }
+    }
+}
+
+impl<'a> SubpassDescription<'a> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_input_attachments(mut self, input_attachments: &'a [AttachmentReference]) -> Self {
+        self.input_attachments = input_attachments;
+        self
+    }
+
+    pub fn with_color_attachments(mut self, color_attachments: &'a [AttachmentReference]) -> Self {
+        self.color_attachments = color_attachments;
+        self
+    }
+
+    /// Multisampled color attachments are resolved into these, one for each color attachment.
+    pub fn with_resolve_attachments(
+        mut self,
+        resolve_attachments: &'a [AttachmentReference],
+    ) -> Self {
+        self.resolve_attachments = Some(resolve_attachments);
+        self
+    }
+
+    pub fn with_depth_stencil_attachment(
+        mut self,
+        depth_stencil_attachment: AttachmentReference,
+    ) -> Self {
+        self.depth_stencil_attachment = Some(depth_stencil_attachment);
+        self
+    }
+
+    /// Attachments not used by this subpass whose contents must be preserved through it.
+    pub fn with_preserve_attachments(mut self, preserve_attachments: &'a [u32]) -> Self {
+        self.preserve_attachments = preserve_attachments;
+        self
+    }
+}
+
+impl<'a> RenderPass<'a> {
+    pub fn subpass_count(&self) -> u32 {
+        self.subpasses.len() as u32
+    }
+
+    pub(crate) fn subpass(&self, subpass: u32) -> Option<SubpassAttachments> {
+        self.subpasses.get(subpass as usize).copied()
+    }
+}
+
+impl<'a> Drop for RenderPass<'a> {
+    fn drop(&mut self) {
+        println!("Dropped RenderPass");
+        unsafe {
+            (self.device.dispatch_loader.vk_destroy_render_pass)(
+                self.device.handle.as_ptr(),
+                #[cfg(target_pointer_width = "64")]
+                self.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                self.handle.get(),
+                std::ptr::null(),
+            );
+        }
+    }
+}
+
+impl<'a> std::fmt::Debug for RenderPass<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RenderPass")
+            .field("handle", &self.handle)
+            .field("subpasses", &self.subpasses)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'b> RenderPassBuilder<'b> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Attachments are numbered in the order they are added.
+    pub fn with_attachment(&mut self, attachment: AttachmentDescription) -> &mut Self {
+        self.attachments.push(attachment);
+        self
+    }
+
+    /// Subpasses are numbered in the order they are added.
+    pub fn with_subpass(&mut self, subpass: SubpassDescription<'b>) -> &mut Self {
+        self.subpasses.push(subpass);
+        self
+    }
+
+    /// # Errors
+    ///
+    /// Besides the errors returned by the driver, the errors of [`validate`](Self::validate).
+    pub fn build<'a>(&self, device: &'a Device) -> Result<RenderPass<'a>> {
+        let subpass_attachments = self.subpass_attachments()?;
+
+        let attachments: Vec<ffi::AttachmentDescription> = self
+            .attachments
+            .iter()
+            .map(|attachment| attachment.into())
+            .collect();
+
+        // NOTE: The references have to outlive the subpass descriptions pointing into them.
+        let references: Vec<_> = self
+            .subpasses
+            .iter()
+            .map(|subpass| {
+                let convert =
+                    |references: &[AttachmentReference]| -> Vec<ffi::AttachmentReference> {
+                        references
+                            .iter()
+                            .map(|reference| reference.into())
+                            .collect()
+                    };
+                (
+                    convert(subpass.input_attachments),
+                    convert(subpass.color_attachments),
+                    subpass.resolve_attachments.map(convert),
+                    subpass
+                        .depth_stencil_attachment
+                        .as_ref()
+                        .map(ffi::AttachmentReference::from),
+                )
+            })
+            .collect();
+
+        let subpasses: Vec<_> = self
+            .subpasses
+            .iter()
+            .zip(&references)
+            .map(
+                |(subpass, (input, color, resolve, depth_stencil))| ffi::SubpassDescription {
+                    flags: 0,
+                    pipeline_bind_point: PipelineBindPoint::Graphics.into(),
+                    input_attachment_count: input.len() as u32,
+                    p_input_attachments: input.as_ptr(),
+                    color_attachment_count: color.len() as u32,
+                    p_color_attachments: color.as_ptr(),
+                    p_resolve_attachments: resolve
+                        .as_ref()
+                        .map_or(std::ptr::null(), |resolve| resolve.as_ptr()),
+                    p_depth_stencil_attachment: depth_stencil
+                        .as_ref()
+                        .map_or(std::ptr::null(), |depth_stencil| depth_stencil),
+                    preserve_attachment_count: subpass.preserve_attachments.len() as u32,
+                    p_preserve_attachments: subpass.preserve_attachments.as_ptr(),
+                },
+            )
+            .collect();
+
+        let create_info = ffi::RenderPassCreateInfo {
+            s_type: ffi::StructureType::RenderPassCreateInfo,
+            p_next: std::ptr::null(),
+            flags: 0,
+            attachment_count: attachments.len() as u32,
+            p_attachments: attachments.as_ptr(),
+            subpass_count: subpasses.len() as u32,
+            p_subpasses: subpasses.as_ptr(),
+            dependency_count: 0,
+            p_dependencies: std::ptr::null(),
+        };
+
+        let mut handle = MaybeUninit::uninit();
+        let result = unsafe {
+            (device.dispatch_loader.vk_create_render_pass)(
+                device.handle.as_ptr(),
+                &create_info,
+                std::ptr::null(),
+                handle.as_mut_ptr(),
+            )
+        };
+
+        match result {
+            ffi::Result::Success => Ok(RenderPass {
+                #[cfg(target_pointer_width = "64")]
+                handle: unsafe { NonNull::new_unchecked(handle.assume_init()) },
+                #[cfg(not(target_pointer_width = "64"))]
+                handle: unsafe { NonZeroU64::new_unchecked(handle.assume_init()) },
+                device,
+                subpasses: subpass_attachments,
+                #[cfg(target_pointer_width = "64")]
+                _marker: PhantomData,
+            }),
+            ffi::Result::ErrorOutOfHostMemory => Err(Error::OutOfHostMemory),
+            ffi::Result::ErrorOutOfDeviceMemory => Err(Error::OutOfDeviceMemory),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Checks the attachment references of the subpasses, without a device.
+    ///
+    /// # Errors
+    /// - [`InvalidAttachmentReference`](Error::InvalidAttachmentReference) if a subpass refers
+    ///   to an attachment that was not added, or preserves [`ATTACHMENT_UNUSED`].
+    /// - [`InvalidDepthStencilFormat`](Error::InvalidDepthStencilFormat) if the depth/stencil
+    ///   attachment of a subpass has neither a depth nor a stencil aspect.
+    /// - [`ResolveAttachmentCountMismatch`](Error::ResolveAttachmentCountMismatch) if a subpass
+    ///   has resolve attachments, but not as many as color attachments.
+    pub fn validate(&self) -> Result<()> {
+        self.subpass_attachments().map(|_| ())
+    }
+
+    fn subpass_attachments(&self) -> Result<Vec<SubpassAttachments>> {
+        let check = |attachment: u32| {
+            if attachment == ATTACHMENT_UNUSED || (attachment as usize) < self.attachments.len() {
+                Ok(())
+            } else {
+                Err(Error::InvalidAttachmentReference(attachment))
+            }
+        };
+
+        self.subpasses
+            .iter()
+            .map(|subpass| {
+                if let Some(resolve_attachments) = subpass.resolve_attachments {
+                    if resolve_attachments.len() != subpass.color_attachments.len() {
+                        return Err(Error::ResolveAttachmentCountMismatch {
+                            color_attachments: subpass.color_attachments.len() as u32,
+                            resolve_attachments: resolve_attachments.len() as u32,
+                        });
+                    }
+                }
+                subpass
+                    .input_attachments
+                    .iter()
+                    .chain(subpass.color_attachments)
+                    .chain(subpass.resolve_attachments.unwrap_or_default())
+                    .try_for_each(|reference| check(reference.attachment))?;
+                // Unlike the other references, preserved attachments cannot be unused.
+                subpass
+                    .preserve_attachments
+                    .iter()
+                    .try_for_each(|&attachment| {
+                        if attachment == ATTACHMENT_UNUSED {
+                            Err(Error::InvalidAttachmentReference(attachment))
+                        } else {
+                            check(attachment)
+                        }
+                    })?;
+
+                let depth_stencil_format = match subpass.depth_stencil_attachment {
+                    Some(reference) if reference.attachment != ATTACHMENT_UNUSED => {
+                        check(reference.attachment)?;
+                        let format = self.attachments[reference.attachment as usize].format;
+                        if !format.has_depth() && !format.has_stencil() {
+                            return Err(Error::InvalidDepthStencilFormat(format));
+                        }
+                        Some(format)
+                    }
+                    _ => None,
+                };
+
+                Ok(SubpassAttachments {
+                    color_attachment_count: subpass.color_attachments.len() as u32,
+                    depth_stencil_format,
+                })
+            })
+            .collect()
+    }
+}
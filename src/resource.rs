@@ -1,11 +1,24 @@
+use std::cell::Cell;
 use std::marker::PhantomData;
 use std::mem::MaybeUninit;
 use std::ptr::NonNull;
 
-use crate::core::{Error, Result};
+use crate::core::{Error, Extent3D, Result};
 use crate::device::Device;
 use crate::ffi;
 use crate::format::Format;
+use crate::pipeline::SampleCount;
+pub use vulkan_rs_derive::Pod;
+
+pub mod allocator;
+pub mod upload;
+
+/// Size of a buffer range that extends to the end of the buffer.
+pub const WHOLE_SIZE: u64 = !0;
+/// Level count of a subresource range that extends to the last mip level of the image.
+pub const REMAINING_MIP_LEVELS: u32 = !0;
+/// Layer count of a subresource range that extends to the last array layer of the image.
+pub const REMAINING_ARRAY_LAYERS: u32 = !0;
 
 pub struct Image<'a> {
     #[cfg(target_pointer_width = "64")]
@@ -15,18 +28,148 @@ pub struct Image<'a> {
     pub(crate) device: &'a Device<'a>,
     #[cfg(target_pointer_width = "64")]
     pub(crate) _marker: PhantomData<ffi::VkImage_T>,
+    /// Swapchain images belong to the swapchain and are not destroyed with the wrapper.
+    pub(crate) owned: bool,
+    pub(crate) memory_bound: bool,
+    pub(crate) format: Format,
+    pub(crate) extent: Extent3D,
+    pub(crate) mip_levels: u32,
+    pub(crate) array_layers: u32,
+    pub(crate) usage: ImageUsageFlags,
+    /// Layout of every subresource, in recording order, if tracked.
+    pub(crate) layouts: Option<LayoutTracker>,
+}
+
+/// Layout of every aspect, mip level and array layer of an image, in recording order. The
+/// depth and stencil aspects of depth/stencil formats are tracked separately, other formats
+/// track all their aspects as one.
+#[derive(Debug)]
+pub struct LayoutTracker {
+    mip_levels: u32,
+    array_layers: u32,
+    separate_stencil: bool,
+    layouts: Box<[Cell<ImageLayout>]>,
 }
 
 pub struct ImageView<'a> {
     #[cfg(target_pointer_width = "64")]
-    handle: NonNull<ffi::VkImageView_T>,
+    pub(crate) handle: NonNull<ffi::VkImageView_T>,
     #[cfg(not(target_pointer_width = "64"))]
-    handle: NonZeroU64,
+    pub(crate) handle: NonZeroU64,
     device: &'a Device<'a>,
+    usage: ImageUsageFlags,
     #[cfg(target_pointer_width = "64")]
     _marker: PhantomData<ffi::VkImageView_T>,
 }
 
+pub struct Buffer<'a> {
+    #[cfg(target_pointer_width = "64")]
+    pub(crate) handle: NonNull<ffi::VkBuffer_T>,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub(crate) handle: NonZeroU64,
+    pub(crate) device: &'a Device<'a>,
+    #[cfg(target_pointer_width = "64")]
+    pub(crate) _marker: PhantomData<ffi::VkBuffer_T>,
+    size: u64,
+    usage: BufferUsageFlags,
+    memory_bound: bool,
+}
+
+/// Formatted view of a range of a [`Buffer`], used by texel buffer descriptors.
+pub struct BufferView<'a> {
+    #[cfg(target_pointer_width = "64")]
+    pub(crate) handle: NonNull<ffi::VkBufferView_T>,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub(crate) handle: NonZeroU64,
+    device: &'a Device<'a>,
+    usage: BufferUsageFlags,
+    #[cfg(target_pointer_width = "64")]
+    _marker: PhantomData<(ffi::VkBufferView_T, &'a Buffer<'a>)>,
+    #[cfg(not(target_pointer_width = "64"))]
+    _marker: PhantomData<&'a Buffer<'a>>,
+}
+
+/// A `VkDeviceMemory` allocation that resources are bound to.
+pub struct DeviceMemory<'a> {
+    #[cfg(target_pointer_width = "64")]
+    pub(crate) handle: NonNull<ffi::VkDeviceMemory_T>,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub(crate) handle: NonZeroU64,
+    pub(crate) device: &'a Device<'a>,
+    pub(crate) size: u64,
+    pub(crate) memory_type_index: u32,
+    pub(crate) property_flags: MemoryPropertyFlags,
+    #[cfg(target_pointer_width = "64")]
+    pub(crate) _marker: PhantomData<ffi::VkDeviceMemory_T>,
+}
+
+/// Host access to a mapped range of [`DeviceMemory`], unmapped when dropped.
+///
+/// Dereferences to the mapped bytes.
+pub struct MappedMemory<'m, 'a> {
+    memory: &'m mut DeviceMemory<'a>,
+    ptr: NonNull<u8>,
+    offset: u64,
+    size: usize,
+    /// Range of the allocation mapped by the driver, widened to whole non-coherent atoms so
+    /// that flushed and invalidated ranges stay inside it.
+    mapped_range: (u64, u64),
+}
+
+/// Types that can be copied to and from device memory as plain bytes.
+///
+/// # Safety
+/// The type must have no padding, no pointers or references, and every bit pattern must be a
+/// valid value of it. For structs this means `#[repr(C)]` with fields that are themselves
+/// [`Pod`] and no gaps between them, which `#[derive(Pod)]` checks.
+pub unsafe trait Pod: Copy + 'static {}
+
+pub struct BufferBuilder<'a> {
+    flags: BufferCreateFlags,
+    size: u64,
+    usage: BufferUsageFlags,
+    sharing_mode: SharingMode<'a>,
+}
+
+/// Size, alignment and allowed memory types of the memory a resource needs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryRequirements {
+    size: u64,
+    alignment: u64,
+    memory_type_bits: u32,
+    prefers_dedicated_allocation: bool,
+    requires_dedicated_allocation: bool,
+}
+
+/// Resource that a dedicated memory allocation is made for.
+#[derive(Debug, Clone, Copy)]
+pub enum DedicatedResource<'r> {
+    Buffer(&'r Buffer<'r>),
+    Image(&'r Image<'r>),
+}
+
+pub struct ImageBuilder<'a> {
+    flags: ImageCreateFlags,
+    image_type: ImageType,
+    format: Format,
+    extent: Extent3D,
+    mip_levels: u32,
+    array_layers: u32,
+    samples: SampleCount,
+    tiling: ImageTiling,
+    usage: ImageUsageFlags,
+    sharing_mode: SharingMode<'a>,
+    initial_layout: ImageLayout,
+    layout_tracking: bool,
+}
+
+pub struct BufferViewBuilder<'a> {
+    buffer: &'a Buffer<'a>,
+    format: Format,
+    offset: u64,
+    range: u64,
+}
+
 pub struct ImageViewBuilder<'a> {
     flags: ImageViewCreateFlags,
     image: &'a Image<'a>,
@@ -36,6 +179,20 @@ pub struct ImageViewBuilder<'a> {
     subresource_range: ImageSubresourceRange,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageType {
+    OneD,
+    TwoD,
+    ThreeD,
+}
+
+/// Arrangement of the texels of an image in memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageTiling {
+    Optimal,
+    Linear,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ImageViewType {
     OneD,
@@ -61,11 +218,36 @@ pub enum ImageAspect {
     MemoryPlane2Ext,
     MemoryPlane3Ext,
 }
-#[derive(Default)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct ImageAspectFlags(u32);
 #[derive(Default)]
 pub struct ImageAspectFlagsBuilder(u32);
 
+/// Layout of the memory of an image, which restricts the operations the image can be used in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ImageLayout {
+    #[default]
+    Undefined,
+    General,
+    ColorAttachmentOptimal,
+    DepthStencilAttachmentOptimal,
+    DepthStencilReadOnlyOptimal,
+    ShaderReadOnlyOptimal,
+    TransferSrcOptimal,
+    TransferDstOptimal,
+    Preinitialized,
+    DepthReadOnlyStencilAttachmentOptimal,
+    DepthAttachmentStencilReadOnlyOptimal,
+    DepthAttachmentOptimal,
+    DepthReadOnlyOptimal,
+    StencilAttachmentOptimal,
+    StencilReadOnlyOptimal,
+    PresentSrcKhr,
+    SharedPresentKhr,
+    ReadOnlyOptimalKhr,
+    AttachmentOptimalKhr,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ComponentSwizzle {
     Identity,
@@ -82,6 +264,23 @@ pub enum SharingMode<'a> {
     Concurrent(&'a [u32]),
 }
 
+impl<'a> SharingMode<'a> {
+    fn validate(&self) -> Result<()> {
+        if let SharingMode::Concurrent(queue_family_indices) = self {
+            let distinct = queue_family_indices
+                .iter()
+                .enumerate()
+                .all(|(i, index)| !queue_family_indices[..i].contains(index));
+            if queue_family_indices.len() < 2 || !distinct {
+                return Err(Error::InvalidSharingMode);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ImageSubresourceRange {
     aspect_mask: ImageAspectFlags,
     base_mip_level: u32,
@@ -90,6 +289,16 @@ pub struct ImageSubresourceRange {
     layer_count: u32,
 }
 
+/// A mip level and range of array layers of an image, the target of copy commands.
+#[derive(Debug, Clone, Copy)]
+pub struct ImageSubresourceLayers {
+    aspect_mask: ImageAspectFlags,
+    mip_level: u32,
+    base_array_layer: u32,
+    layer_count: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ImageUsage {
     TransferSrc,
     TransferDst,
@@ -103,12 +312,82 @@ pub enum ImageUsage {
     FragmentDensityMapExt,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Copy)]
 pub struct ImageUsageFlags(pub(crate) u32);
 
 #[derive(Default)]
 pub struct ImageUsageFlagsBuilder(u32);
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BufferUsage {
+    TransferSrc,
+    TransferDst,
+    UniformTexelBuffer,
+    StorageTexelBuffer,
+    UniformBuffer,
+    StorageBuffer,
+    IndexBuffer,
+    VertexBuffer,
+    IndirectBuffer,
+    ShaderDeviceAddress,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BufferUsageFlags(pub(crate) u32);
+
+#[derive(Default)]
+pub struct BufferUsageFlagsBuilder(u32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BufferCreate {
+    SparseBinding,
+    SparseResidency,
+    SparseAliased,
+    Protected,
+    DeviceAddressCaptureReplay,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BufferCreateFlags(u32);
+
+#[derive(Default)]
+pub struct BufferCreateFlagsBuilder(u32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryProperty {
+    DeviceLocal,
+    HostVisible,
+    HostCoherent,
+    HostCached,
+    LazilyAllocated,
+    Protected,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryPropertyFlags(pub(crate) u32);
+
+#[derive(Default)]
+pub struct MemoryPropertyFlagsBuilder(u32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageCreate {
+    SparseBinding,
+    SparseResidency,
+    SparseAliased,
+    MutableFormat,
+    CubeCompatible,
+    TwoDArrayCompatible,
+    BlockTexelViewCompatible,
+    ExtendedUsage,
+    Protected,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ImageCreateFlags(pub(crate) u32);
+
+#[derive(Default)]
+pub struct ImageCreateFlagsBuilder(u32);
+
 pub enum ImageViewCreate {
     FragmentDensityMapDynamicExt,
     FragmentDensityMapDeferredExt,
@@ -128,6 +407,21 @@ pub struct ComponentMapping {
     alpha: ComponentSwizzle,
 }
 
+impl<'a> std::fmt::Debug for ImageView<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ImageView")
+            .field("handle", &self.handle)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> ImageView<'a> {
+    /// Usage of the viewed image.
+    pub fn usage(&self) -> ImageUsageFlags {
+        self.usage
+    }
+}
+
 impl<'a> Drop for ImageView<'a> {
     fn drop(&mut self) {
         println!("Dropped ImageView");
@@ -135,11 +429,1393 @@ impl<'a> Drop for ImageView<'a> {
             (self.device.dispatch_loader.vk_destroy_image_view)(
                 self.device.handle.as_ptr(),
                 #[cfg(target_pointer_width = "64")]
-                self.handle.as_ptr(),
+                self.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                self.handle.get(),
+                std::ptr::null(),
+            );
+        }
+    }
+}
+
+impl<'a> BufferView<'a> {
+    /// Usage of the viewed buffer.
+    pub fn usage(&self) -> BufferUsageFlags {
+        self.usage
+    }
+}
+
+impl<'a> std::fmt::Debug for BufferView<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BufferView")
+            .field("handle", &self.handle)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> Drop for BufferView<'a> {
+    fn drop(&mut self) {
+        println!("Dropped BufferView");
+        unsafe {
+            (self.device.dispatch_loader.vk_destroy_buffer_view)(
+                self.device.handle.as_ptr(),
+                #[cfg(target_pointer_width = "64")]
+                self.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                self.handle.get(),
+                std::ptr::null(),
+            );
+        }
+    }
+}
+
+impl<'a> Drop for Image<'a> {
+    fn drop(&mut self) {
+        if !self.owned {
+            return;
+        }
+
+        println!("Dropped Image");
+        unsafe {
+            (self.device.dispatch_loader.vk_destroy_image)(
+                self.device.handle.as_ptr(),
+                #[cfg(target_pointer_width = "64")]
+                self.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                self.handle.get(),
+                std::ptr::null(),
+            );
+        }
+    }
+}
+
+impl<'a> Drop for Buffer<'a> {
+    fn drop(&mut self) {
+        println!("Dropped Buffer");
+        unsafe {
+            (self.device.dispatch_loader.vk_destroy_buffer)(
+                self.device.handle.as_ptr(),
+                #[cfg(target_pointer_width = "64")]
+                self.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                self.handle.get(),
+                std::ptr::null(),
+            );
+        }
+    }
+}
+
+impl<'a> Image<'a> {
+    /// Whether the image belongs to a swapchain, which destroys it instead of the wrapper.
+    pub fn is_swapchain_image(&self) -> bool {
+        !self.owned
+    }
+
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    pub fn extent(&self) -> Extent3D {
+        self.extent
+    }
+
+    pub fn mip_levels(&self) -> u32 {
+        self.mip_levels
+    }
+
+    pub fn array_layers(&self) -> u32 {
+        self.array_layers
+    }
+
+    pub fn usage(&self) -> ImageUsageFlags {
+        self.usage
+    }
+
+    /// Range of all aspects, mip levels and array layers of the image.
+    pub fn subresource_range(&self) -> ImageSubresourceRange {
+        let mut aspect_mask = ImageAspectFlagsBuilder::new();
+        if self.format.has_depth() || self.format.has_stencil() {
+            aspect_mask
+                .depth(self.format.has_depth())
+                .stencil(self.format.has_stencil());
+        } else {
+            aspect_mask.color(true);
+        }
+
+        ImageSubresourceRange::new(
+            aspect_mask.build(),
+            0,
+            self.mip_levels,
+            0,
+            self.array_layers,
+        )
+    }
+
+    /// Starts tracking the layout of each mip level and array layer, and of the depth and
+    /// stencil aspects separately, which are all in `current_layout` now. Barriers recorded with
+    /// [`CommandBuffer::pipeline_barrier`](crate::command_buffer::CommandBuffer::pipeline_barrier)
+    /// update the tracked layouts in recording order, so command buffers have to be submitted
+    /// in the order they were recorded in. In debug builds, a barrier whose old layout does not
+    /// match the tracked layout panics.
+    pub fn track_layouts(&mut self, current_layout: ImageLayout) {
+        self.layouts = Some(LayoutTracker::new(
+            self.format,
+            self.mip_levels,
+            self.array_layers,
+            current_layout,
+        ));
+    }
+
+    pub fn is_layout_tracked(&self) -> bool {
+        self.layouts.is_some()
+    }
+
+    /// Tracked layout of the subresources of `range`, or [`None`] if layouts are not tracked or
+    /// differ within the range.
+    pub fn layout(&self, range: &ImageSubresourceRange) -> Option<ImageLayout> {
+        self.layouts.as_ref()?.layout(range)
+    }
+
+    /// Records a transition in the tracked layouts, see [`LayoutTracker::record_transition`].
+    pub(crate) fn record_transition(
+        &self,
+        range: &ImageSubresourceRange,
+        old_layout: ImageLayout,
+        new_layout: ImageLayout,
+        ownership_transfer: bool,
+    ) {
+        if let Some(layouts) = self.layouts.as_ref() {
+            layouts.record_transition(range, old_layout, new_layout, ownership_transfer);
+        }
+    }
+
+    /// Memory requirements of the image. Whether a dedicated allocation is preferred is only
+    /// reported with Vulkan 1.1 or `"VK_KHR_get_memory_requirements2"` and
+    /// `"VK_KHR_dedicated_allocation"`.
+    pub fn memory_requirements(&self) -> MemoryRequirements {
+        if let Some(vk_get_image_memory_requirements2) = self
+            .device
+            .dispatch_loader
+            .vk_get_image_memory_requirements2
+        {
+            let info = ffi::ImageMemoryRequirementsInfo2 {
+                s_type: ffi::StructureType::ImageMemoryRequirementsInfo2,
+                p_next: std::ptr::null(),
+                #[cfg(target_pointer_width = "64")]
+                image: self.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                image: self.handle.get(),
+            };
+            let mut dedicated = ffi::MemoryDedicatedRequirements {
+                s_type: ffi::StructureType::MemoryDedicatedRequirements,
+                p_next: std::ptr::null_mut(),
+                prefers_dedicated_allocation: 0,
+                requires_dedicated_allocation: 0,
+            };
+            let mut requirements = ffi::MemoryRequirements2 {
+                s_type: ffi::StructureType::MemoryRequirements2,
+                p_next: (&mut dedicated as *mut ffi::MemoryDedicatedRequirements).cast(),
+                memory_requirements: ffi::MemoryRequirements {
+                    size: 0,
+                    alignment: 0,
+                    memory_type_bits: 0,
+                },
+            };
+            unsafe {
+                vk_get_image_memory_requirements2(
+                    self.device.handle.as_ptr(),
+                    &info,
+                    &mut requirements,
+                );
+            }
+
+            return MemoryRequirements::from(requirements.memory_requirements)
+                .with_prefers_dedicated_allocation(dedicated.prefers_dedicated_allocation != 0)
+                .with_requires_dedicated_allocation(dedicated.requires_dedicated_allocation != 0);
+        }
+
+        let mut requirements = MaybeUninit::uninit();
+        unsafe {
+            (self.device.dispatch_loader.vk_get_image_memory_requirements)(
+                self.device.handle.as_ptr(),
+                #[cfg(target_pointer_width = "64")]
+                self.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                self.handle.get(),
+                requirements.as_mut_ptr(),
+            );
+        }
+
+        unsafe { requirements.assume_init() }.into()
+    }
+
+    /// Binds `memory` to the image, starting `offset` bytes into the allocation. An image can
+    /// only be bound once, and swapchain images are bound by the swapchain.
+    ///
+    /// # Errors
+    /// The same as [`Buffer::bind_memory`].
+    ///
+    /// # Safety
+    ///
+    /// The same as [`Buffer::bind_memory`].
+    pub unsafe fn bind_memory(&mut self, memory: &DeviceMemory, offset: u64) -> Result<()> {
+        if self.memory_bound {
+            return Err(Error::MemoryAlreadyBound);
+        }
+        validate_memory_binding(self.memory_requirements(), memory, offset)?;
+
+        let result = unsafe {
+            (self.device.dispatch_loader.vk_bind_image_memory)(
+                self.device.handle.as_ptr(),
+                #[cfg(target_pointer_width = "64")]
+                self.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                self.handle.get(),
+                #[cfg(target_pointer_width = "64")]
+                memory.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                memory.handle.get(),
+                offset,
+            )
+        };
+
+        match result {
+            ffi::Result::Success => {
+                self.memory_bound = true;
+                Ok(())
+            }
+            ffi::Result::ErrorOutOfHostMemory => Err(Error::OutOfHostMemory),
+            ffi::Result::ErrorOutOfDeviceMemory => Err(Error::OutOfDeviceMemory),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<'a> std::fmt::Debug for Image<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Image")
+            .field("handle", &self.handle)
+            .field("format", &self.format)
+            .field("extent", &self.extent)
+            .field("owned", &self.owned)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> Buffer<'a> {
+    /// Size in bytes the buffer was created with.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn usage(&self) -> BufferUsageFlags {
+        self.usage
+    }
+
+    /// Memory requirements of the buffer. Whether a dedicated allocation is preferred is only
+    /// reported with Vulkan 1.1 or `"VK_KHR_get_memory_requirements2"` and
+    /// `"VK_KHR_dedicated_allocation"`.
+    pub fn memory_requirements(&self) -> MemoryRequirements {
+        if let Some(vk_get_buffer_memory_requirements2) = self
+            .device
+            .dispatch_loader
+            .vk_get_buffer_memory_requirements2
+        {
+            let info = ffi::BufferMemoryRequirementsInfo2 {
+                s_type: ffi::StructureType::BufferMemoryRequirementsInfo2,
+                p_next: std::ptr::null(),
+                #[cfg(target_pointer_width = "64")]
+                buffer: self.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                buffer: self.handle.get(),
+            };
+            let mut dedicated = ffi::MemoryDedicatedRequirements {
+                s_type: ffi::StructureType::MemoryDedicatedRequirements,
+                p_next: std::ptr::null_mut(),
+                prefers_dedicated_allocation: 0,
+                requires_dedicated_allocation: 0,
+            };
+            let mut requirements = ffi::MemoryRequirements2 {
+                s_type: ffi::StructureType::MemoryRequirements2,
+                p_next: (&mut dedicated as *mut ffi::MemoryDedicatedRequirements).cast(),
+                memory_requirements: ffi::MemoryRequirements {
+                    size: 0,
+                    alignment: 0,
+                    memory_type_bits: 0,
+                },
+            };
+            unsafe {
+                vk_get_buffer_memory_requirements2(
+                    self.device.handle.as_ptr(),
+                    &info,
+                    &mut requirements,
+                );
+            }
+
+            return MemoryRequirements::from(requirements.memory_requirements)
+                .with_prefers_dedicated_allocation(dedicated.prefers_dedicated_allocation != 0)
+                .with_requires_dedicated_allocation(dedicated.requires_dedicated_allocation != 0);
+        }
+
+        let mut requirements = MaybeUninit::uninit();
+        unsafe {
+            (self
+                .device
+                .dispatch_loader
+                .vk_get_buffer_memory_requirements)(
+                self.device.handle.as_ptr(),
+                #[cfg(target_pointer_width = "64")]
+                self.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                self.handle.get(),
+                requirements.as_mut_ptr(),
+            );
+        }
+
+        unsafe { requirements.assume_init() }.into()
+    }
+
+    /// Binds `memory` to the buffer, starting `offset` bytes into the allocation. A buffer can
+    /// only be bound once.
+    ///
+    /// # Errors
+    /// - [`MemoryAlreadyBound`](Error::MemoryAlreadyBound) if the buffer is already bound.
+    /// - [`IncompatibleMemoryType`](Error::IncompatibleMemoryType) if the memory type is not
+    ///   one of [`MemoryRequirements::memory_type_bits`].
+    /// - [`MisalignedMemoryOffset`](Error::MisalignedMemoryOffset) if `offset` is not a
+    ///   multiple of [`MemoryRequirements::alignment`].
+    /// - [`MemoryRangeOutOfBounds`](Error::MemoryRangeOutOfBounds) if the buffer does not fit
+    ///   in the allocation after `offset`.
+    ///
+    /// Driver errors:
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    /// - [`InvalidOpaqueCaptureAddress`](Error::InvalidOpaqueCaptureAddress)
+    ///
+    /// # Safety
+    ///
+    /// `memory` is not borrowed beyond the call, so it must not be dropped before the buffer,
+    /// nor while the device still uses the buffer.
+    pub unsafe fn bind_memory(&mut self, memory: &DeviceMemory, offset: u64) -> Result<()> {
+        if self.memory_bound {
+            return Err(Error::MemoryAlreadyBound);
+        }
+
+        validate_memory_binding(self.memory_requirements(), memory, offset)?;
+
+        let result = unsafe {
+            (self.device.dispatch_loader.vk_bind_buffer_memory)(
+                self.device.handle.as_ptr(),
+                #[cfg(target_pointer_width = "64")]
+                self.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                self.handle.get(),
+                #[cfg(target_pointer_width = "64")]
+                memory.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                memory.handle.get(),
+                offset,
+            )
+        };
+
+        match result {
+            ffi::Result::Success => {
+                self.memory_bound = true;
+                Ok(())
+            }
+            ffi::Result::ErrorOutOfHostMemory => Err(Error::OutOfHostMemory),
+            ffi::Result::ErrorOutOfDeviceMemory => Err(Error::OutOfDeviceMemory),
+            ffi::Result::ErrorInvalidOpaqueCaptureAddress => {
+                Err(Error::InvalidOpaqueCaptureAddress)
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl LayoutTracker {
+    /// Tracks all subresources of an image of `format` as being in `layout`.
+    pub fn new(format: Format, mip_levels: u32, array_layers: u32, layout: ImageLayout) -> Self {
+        let separate_stencil = format.has_depth() && format.has_stencil();
+        let aspects = if separate_stencil { 2 } else { 1 };
+
+        Self {
+            mip_levels,
+            array_layers,
+            separate_stencil,
+            layouts: (0..aspects * mip_levels * array_layers)
+                .map(|_| Cell::new(layout))
+                .collect(),
+        }
+    }
+
+    /// Tracked layout of the subresources of `range`, or [`None`] if they differ within the
+    /// range or it is empty.
+    pub fn layout(&self, range: &ImageSubresourceRange) -> Option<ImageLayout> {
+        let mut layouts = self
+            .subresource_indices(range)
+            .map(|index| self.layouts[index].get());
+        let layout = layouts.next()?;
+
+        layouts.all(|other| other == layout).then_some(layout)
+    }
+
+    /// Records the transition of the subresources of `range` from `old_layout` to
+    /// `new_layout`. A transition from [`Undefined`](ImageLayout::Undefined) matches any
+    /// tracked layout, and so does one to the tracked layout when it acquires the image from
+    /// another queue family, whose release already transitioned it.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, if `old_layout` does not match the tracked layout of a subresource.
+    pub fn record_transition(
+        &self,
+        range: &ImageSubresourceRange,
+        old_layout: ImageLayout,
+        new_layout: ImageLayout,
+        ownership_transfer: bool,
+    ) {
+        let subresources = (self.mip_levels * self.array_layers) as usize;
+        for index in self.subresource_indices(range) {
+            let tracked = self.layouts[index].get();
+            debug_assert!(
+                old_layout == ImageLayout::Undefined
+                    || tracked == old_layout
+                    || (ownership_transfer && tracked == new_layout),
+                "image layout mismatch: mip level {} of array layer {}{} is in {:?}, but the \
+                 barrier transitions it from {:?}",
+                (index % subresources) as u32 % self.mip_levels,
+                (index % subresources) as u32 / self.mip_levels,
+                match (self.separate_stencil, index >= subresources) {
+                    (false, _) => "",
+                    (true, false) => " (depth)",
+                    (true, true) => " (stencil)",
+                },
+                tracked,
+                old_layout,
+            );
+            self.layouts[index].set(new_layout);
+        }
+    }
+
+    /// Indices into the tracked layouts of the subresources of `range`.
+    fn subresource_indices(&self, range: &ImageSubresourceRange) -> impl Iterator<Item = usize> {
+        let mip_levels = self.mip_levels;
+        let array_layers = self.array_layers;
+        let levels = range.base_mip_level
+            ..resolve_count(range.base_mip_level, range.level_count, mip_levels);
+        let layers = range.base_array_layer
+            ..resolve_count(range.base_array_layer, range.layer_count, array_layers);
+        let aspects: &[u32] = if !self.separate_stencil {
+            &[0]
+        } else {
+            match (
+                range.aspect_mask.contains(ImageAspect::Depth),
+                range.aspect_mask.contains(ImageAspect::Stencil),
+            ) {
+                (true, true) => &[0, 1],
+                (true, false) => &[0],
+                (false, true) => &[1],
+                (false, false) => &[],
+            }
+        };
+
+        aspects.iter().flat_map(move |&aspect| {
+            let levels = levels.clone();
+            layers.clone().flat_map(move |layer| {
+                levels.clone().map(move |level| {
+                    ((aspect * array_layers + layer) * mip_levels + level) as usize
+                })
+            })
+        })
+    }
+}
+
+/// End of a range of mip levels or array layers, clamped to `total`.
+fn resolve_count(base: u32, count: u32, total: u32) -> u32 {
+    base.saturating_add(count).min(total)
+}
+
+fn validate_memory_binding(
+    requirements: MemoryRequirements,
+    memory: &DeviceMemory,
+    offset: u64,
+) -> Result<()> {
+    if requirements.memory_type_bits & (1 << memory.memory_type_index) == 0 {
+        return Err(Error::IncompatibleMemoryType(memory.memory_type_index));
+    }
+    if !offset.is_multiple_of(requirements.alignment) {
+        return Err(Error::MisalignedMemoryOffset {
+            offset,
+            alignment: requirements.alignment,
+        });
+    }
+    if offset
+        .checked_add(requirements.size)
+        .is_none_or(|end| end > memory.size)
+    {
+        return Err(Error::MemoryRangeOutOfBounds {
+            offset,
+            size: requirements.size,
+        });
+    }
+
+    Ok(())
+}
+
+impl<'a> std::fmt::Debug for Buffer<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Buffer")
+            .field("handle", &self.handle)
+            .field("size", &self.size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> DeviceMemory<'a> {
+    /// Size in bytes of the allocation.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn memory_type_index(&self) -> u32 {
+        self.memory_type_index
+    }
+
+    /// Properties of the memory type the memory was allocated from.
+    pub fn property_flags(&self) -> MemoryPropertyFlags {
+        self.property_flags
+    }
+
+    /// Maps `size` bytes starting `offset` bytes into the allocation for host access. Only one
+    /// range can be mapped at a time. Memory that is not host coherent is mapped in whole
+    /// [`non_coherent_atom_size`](crate::device::PhysicalDeviceLimits::non_coherent_atom_size)
+    /// blocks around the range, so that any part of it can be flushed and invalidated.
+    ///
+    /// # Errors
+    /// - [`MemoryNotHostVisible`](Error::MemoryNotHostVisible) if the memory type is not
+    ///   host visible.
+    /// - [`MemoryRangeOutOfBounds`](Error::MemoryRangeOutOfBounds) if the range is empty or
+    ///   extends past the allocation.
+    ///
+    /// Driver errors:
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    /// - [`MemoryMapFailed`](Error::MemoryMapFailed)
+    pub fn map(&mut self, offset: u64, size: u64) -> Result<MappedMemory<'_, 'a>> {
+        if !self.property_flags.contains(MemoryProperty::HostVisible) {
+            return Err(Error::MemoryNotHostVisible);
+        }
+        if size == 0 || offset.checked_add(size).is_none_or(|end| end > self.size) {
+            return Err(Error::MemoryRangeOutOfBounds { offset, size });
+        }
+
+        let mapped_range = if self.property_flags.contains(MemoryProperty::HostCoherent) {
+            (offset, offset + size)
+        } else {
+            let atom_size = self.device.limits().non_coherent_atom_size.max(1);
+            let end = (offset + size)
+                .div_ceil(atom_size)
+                .saturating_mul(atom_size)
+                .min(self.size);
+            (offset - offset % atom_size, end)
+        };
+        let mut pp_data = MaybeUninit::uninit();
+
+        let result = unsafe {
+            (self.device.dispatch_loader.vk_map_memory)(
+                self.device.handle.as_ptr(),
+                #[cfg(target_pointer_width = "64")]
+                self.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                self.handle.get(),
+                mapped_range.0,
+                mapped_range.1 - mapped_range.0,
+                0,
+                pp_data.as_mut_ptr(),
+            )
+        };
+
+        match result {
+            ffi::Result::Success => Ok(MappedMemory {
+                ptr: unsafe {
+                    NonNull::new_unchecked(
+                        pp_data
+                            .assume_init()
+                            .cast::<u8>()
+                            .add((offset - mapped_range.0) as usize),
+                    )
+                },
+                memory: self,
+                offset,
+                size: size as usize,
+                mapped_range,
+            }),
+            ffi::Result::ErrorOutOfHostMemory => Err(Error::OutOfHostMemory),
+            ffi::Result::ErrorOutOfDeviceMemory => Err(Error::OutOfDeviceMemory),
+            ffi::Result::ErrorMemoryMapFailed => Err(Error::MemoryMapFailed),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<'m, 'a> MappedMemory<'m, 'a> {
+    /// Offset of the mapped range from the start of the allocation.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// The mapped bytes as a slice of `T`, leaving out trailing bytes that do not fill a
+    /// whole `T`.
+    ///
+    /// # Errors
+    /// - [`MisalignedMemoryOffset`](Error::MisalignedMemoryOffset) if the mapping does not
+    ///   start at an address aligned for `T`.
+    pub fn as_typed<T: Pod>(&self) -> Result<&[T]> {
+        let len = self.typed_len::<T>()?;
+        Ok(unsafe { std::slice::from_raw_parts(self.ptr.as_ptr().cast(), len) })
+    }
+
+    /// Mutable version of [`as_typed`](Self::as_typed).
+    ///
+    /// # Errors
+    /// - [`MisalignedMemoryOffset`](Error::MisalignedMemoryOffset) if the mapping does not
+    ///   start at an address aligned for `T`.
+    pub fn as_typed_mut<T: Pod>(&mut self) -> Result<&mut [T]> {
+        let len = self.typed_len::<T>()?;
+        Ok(unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr().cast(), len) })
+    }
+
+    /// Copies `data` into the mapping, starting `offset` bytes into it.
+    ///
+    /// # Errors
+    /// - [`MemoryRangeOutOfBounds`](Error::MemoryRangeOutOfBounds) if `data` does not fit in
+    ///   the mapping after `offset`.
+    pub fn write<T: Pod>(&mut self, offset: usize, data: &[T]) -> Result<()> {
+        let size = std::mem::size_of_val(data);
+        let range = match offset.checked_add(size) {
+            Some(end) if end <= self.size => offset..end,
+            _ => {
+                return Err(Error::MemoryRangeOutOfBounds {
+                    offset: offset as u64,
+                    size: size as u64,
+                })
+            }
+        };
+
+        let bytes = unsafe { std::slice::from_raw_parts(data.as_ptr().cast::<u8>(), size) };
+        self[range].copy_from_slice(bytes);
+        Ok(())
+    }
+
+    /// Makes host writes to `size` bytes starting `offset` bytes into the mapping visible to
+    /// the device. The range is widened to multiples of
+    /// [`non_coherent_atom_size`](crate::device::PhysicalDeviceLimits::non_coherent_atom_size),
+    /// and nothing is done for host coherent memory or if `size` is 0.
+    ///
+    /// # Errors
+    /// - [`MemoryRangeOutOfBounds`](Error::MemoryRangeOutOfBounds) if the range extends past
+    ///   the mapping.
+    ///
+    /// Driver errors:
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    pub fn flush(&self, offset: u64, size: u64) -> Result<()> {
+        match self.atom_range(offset, size)? {
+            Some(range) => unsafe {
+                self.call_ranges(
+                    self.memory
+                        .device
+                        .dispatch_loader
+                        .vk_flush_mapped_memory_ranges,
+                    &range,
+                )
+            },
+            None => Ok(()),
+        }
+    }
+
+    /// Makes device writes to `size` bytes starting `offset` bytes into the mapping visible to
+    /// the host. The range is widened like in [`flush`](Self::flush), and nothing is done for
+    /// host coherent memory or if `size` is 0.
+    ///
+    /// # Errors
+    /// - [`MemoryRangeOutOfBounds`](Error::MemoryRangeOutOfBounds) if the range extends past
+    ///   the mapping.
+    ///
+    /// Driver errors:
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    pub fn invalidate(&mut self, offset: u64, size: u64) -> Result<()> {
+        match self.atom_range(offset, size)? {
+            Some(range) => unsafe {
+                self.call_ranges(
+                    self.memory
+                        .device
+                        .dispatch_loader
+                        .vk_invalidate_mapped_memory_ranges,
+                    &range,
+                )
+            },
+            None => Ok(()),
+        }
+    }
+
+    /// Unmaps the memory, the same as dropping the guard.
+    pub fn unmap(self) {}
+
+    fn typed_len<T: Pod>(&self) -> Result<usize> {
+        let alignment = std::mem::align_of::<T>();
+        if !(self.ptr.as_ptr() as usize).is_multiple_of(alignment) {
+            return Err(Error::MisalignedMemoryOffset {
+                offset: self.offset,
+                alignment: alignment as u64,
+            });
+        }
+
+        Ok(self.size.checked_div(std::mem::size_of::<T>()).unwrap_or(0))
+    }
+
+    /// Range of the allocation to flush or invalidate, or [`None`] for coherent memory and
+    /// empty ranges.
+    fn atom_range(&self, offset: u64, size: u64) -> Result<Option<ffi::MappedMemoryRange>> {
+        if offset
+            .checked_add(size)
+            .is_none_or(|end| end > self.size as u64)
+        {
+            return Err(Error::MemoryRangeOutOfBounds { offset, size });
+        }
+        if size == 0
+            || self
+                .memory
+                .property_flags
+                .contains(MemoryProperty::HostCoherent)
+        {
+            return Ok(None);
+        }
+
+        let atom_size = self.memory.device.limits().non_coherent_atom_size.max(1);
+        let start = self.offset + offset;
+        let start = (start - start % atom_size).max(self.mapped_range.0);
+        let end = (self.offset + offset + size)
+            .div_ceil(atom_size)
+            .saturating_mul(atom_size)
+            .min(self.mapped_range.1);
+
+        Ok(Some(ffi::MappedMemoryRange {
+            s_type: ffi::StructureType::MappedMemoryRange,
+            p_next: std::ptr::null(),
+            #[cfg(target_pointer_width = "64")]
+            memory: self.memory.handle.as_ptr(),
+            #[cfg(not(target_pointer_width = "64"))]
+            memory: self.memory.handle.get(),
+            offset: start,
+            size: end - start,
+        }))
+    }
+
+    unsafe fn call_ranges(
+        &self,
+        pfn: ffi::PFN_vkFlushMappedMemoryRanges,
+        range: &ffi::MappedMemoryRange,
+    ) -> Result<()> {
+        match pfn(self.memory.device.handle.as_ptr(), 1, range) {
+            ffi::Result::Success => Ok(()),
+            ffi::Result::ErrorOutOfHostMemory => Err(Error::OutOfHostMemory),
+            ffi::Result::ErrorOutOfDeviceMemory => Err(Error::OutOfDeviceMemory),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<'m, 'a> std::ops::Deref for MappedMemory<'m, 'a> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.size) }
+    }
+}
+
+impl<'m, 'a> std::ops::DerefMut for MappedMemory<'m, 'a> {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.size) }
+    }
+}
+
+impl<'m, 'a> std::fmt::Debug for MappedMemory<'m, 'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MappedMemory")
+            .field("memory", &self.memory)
+            .field("offset", &self.offset)
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
+impl<'m, 'a> Drop for MappedMemory<'m, 'a> {
+    fn drop(&mut self) {
+        unsafe {
+            (self.memory.device.dispatch_loader.vk_unmap_memory)(
+                self.memory.device.handle.as_ptr(),
+                #[cfg(target_pointer_width = "64")]
+                self.memory.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                self.memory.handle.get(),
+            );
+        }
+    }
+}
+
+macro_rules! impl_pod {
+    ($($ty:ty),*) => {
+        $(unsafe impl Pod for $ty {})*
+    };
+}
+
+impl_pod!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize, f32, f64);
+
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
+impl<'a> std::fmt::Debug for DeviceMemory<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeviceMemory")
+            .field("handle", &self.handle)
+            .field("size", &self.size)
+            .field("memory_type_index", &self.memory_type_index)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> Drop for DeviceMemory<'a> {
+    fn drop(&mut self) {
+        println!("Dropped DeviceMemory");
+        unsafe {
+            (self.device.dispatch_loader.vk_free_memory)(
+                self.device.handle.as_ptr(),
+                #[cfg(target_pointer_width = "64")]
+                self.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                self.handle.get(),
+                std::ptr::null(),
+            );
+        }
+    }
+}
+
+impl<'b> BufferBuilder<'b> {
+    pub fn new(size: u64, usage: BufferUsageFlags) -> Self {
+        Self {
+            flags: Default::default(),
+            size,
+            usage,
+            sharing_mode: SharingMode::Exclusive,
+        }
+    }
+
+    pub fn with_sharing_mode(&mut self, sharing_mode: SharingMode<'b>) -> &mut Self {
+        self.sharing_mode = sharing_mode;
+        self
+    }
+
+    pub fn with_flags(&mut self, flags: BufferCreateFlags) -> &mut Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Creates the buffer, which has no memory until [`Buffer::bind_memory`] is called.
+    ///
+    /// # Errors
+    /// - [`InvalidBufferParameter`](Error::InvalidBufferParameter) if the size is zero or the
+    ///   usage is empty.
+    /// - [`InvalidSharingMode`](Error::InvalidSharingMode) if concurrent sharing names fewer
+    ///   than two queue families or one of them twice.
+    ///
+    /// Driver errors:
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    /// - [`InvalidOpaqueCaptureAddress`](Error::InvalidOpaqueCaptureAddress)
+    pub fn build<'a>(&self, device: &'a Device) -> Result<Buffer<'a>> {
+        if self.size == 0 {
+            return Err(Error::InvalidBufferParameter("size"));
+        }
+        if self.usage.0 == 0 {
+            return Err(Error::InvalidBufferParameter("usage"));
+        }
+        self.sharing_mode.validate()?;
+
+        let create_info = ffi::BufferCreateInfo {
+            s_type: ffi::StructureType::BufferCreateInfo,
+            p_next: std::ptr::null(),
+            flags: self.flags.0,
+            size: self.size,
+            usage: self.usage.0,
+            sharing_mode: (&self.sharing_mode).into(),
+            queue_family_index_count: match self.sharing_mode {
+                SharingMode::Exclusive => 0,
+                SharingMode::Concurrent(s) => s.len() as u32,
+            },
+            p_queue_family_indices: match self.sharing_mode {
+                SharingMode::Exclusive => std::ptr::null(),
+                SharingMode::Concurrent(s) => s.as_ptr(),
+            },
+        };
+
+        let mut p_buffer = MaybeUninit::uninit();
+
+        let result = unsafe {
+            (device.dispatch_loader.vk_create_buffer)(
+                device.handle.as_ptr(),
+                &create_info,
+                std::ptr::null(),
+                p_buffer.as_mut_ptr(),
+            )
+        };
+
+        match result {
+            ffi::Result::Success => Ok(Buffer {
+                #[cfg(target_pointer_width = "64")]
+                handle: unsafe { NonNull::new_unchecked(p_buffer.assume_init()) },
+                #[cfg(not(target_pointer_width = "64"))]
+                handle: unsafe { NonZeroU64::new_unchecked(p_buffer.assume_init()) },
+                device,
+                #[cfg(target_pointer_width = "64")]
+                _marker: PhantomData,
+                size: self.size,
+                usage: self.usage,
+                memory_bound: false,
+            }),
+            ffi::Result::ErrorOutOfHostMemory => Err(Error::OutOfHostMemory),
+            ffi::Result::ErrorOutOfDeviceMemory => Err(Error::OutOfDeviceMemory),
+            ffi::Result::ErrorInvalidOpaqueCaptureAddress => {
+                Err(Error::InvalidOpaqueCaptureAddress)
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<'b> ImageBuilder<'b> {
+    /// Image of a single mip level and array layer, with one sample, optimal tiling and an
+    /// undefined initial layout.
+    pub fn new(
+        image_type: ImageType,
+        format: Format,
+        extent: impl Into<Extent3D>,
+        usage: ImageUsageFlags,
+    ) -> Self {
+        Self {
+            flags: Default::default(),
+            image_type,
+            format,
+            extent: extent.into(),
+            mip_levels: 1,
+            array_layers: 1,
+            samples: SampleCount::OneBit,
+            tiling: ImageTiling::Optimal,
+            usage,
+            sharing_mode: SharingMode::Exclusive,
+            initial_layout: ImageLayout::Undefined,
+            layout_tracking: false,
+        }
+    }
+
+    /// Tracks the layouts of the image starting from the initial layout, see
+    /// [`Image::track_layouts`].
+    pub fn with_layout_tracking(&mut self, layout_tracking: bool) -> &mut Self {
+        self.layout_tracking = layout_tracking;
+        self
+    }
+
+    pub fn with_mip_levels(&mut self, mip_levels: u32) -> &mut Self {
+        self.mip_levels = mip_levels;
+        self
+    }
+
+    pub fn with_array_layers(&mut self, array_layers: u32) -> &mut Self {
+        self.array_layers = array_layers;
+        self
+    }
+
+    pub fn with_samples(&mut self, samples: SampleCount) -> &mut Self {
+        self.samples = samples;
+        self
+    }
+
+    pub fn with_tiling(&mut self, tiling: ImageTiling) -> &mut Self {
+        self.tiling = tiling;
+        self
+    }
+
+    pub fn with_sharing_mode(&mut self, sharing_mode: SharingMode<'b>) -> &mut Self {
+        self.sharing_mode = sharing_mode;
+        self
+    }
+
+    /// Either [`ImageLayout::Undefined`] or [`ImageLayout::Preinitialized`].
+    pub fn with_initial_layout(&mut self, initial_layout: ImageLayout) -> &mut Self {
+        self.initial_layout = initial_layout;
+        self
+    }
+
+    pub fn with_flags(&mut self, flags: ImageCreateFlags) -> &mut Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Creates the image, which has no memory until [`Image::bind_memory`] is called.
+    ///
+    /// # Errors
+    /// - [`InvalidImageExtent`](Error::InvalidImageExtent) if a dimension is zero or the image
+    ///   type has fewer dimensions than the extent.
+    /// - [`InvalidImageParameter`](Error::InvalidImageParameter) if the usage is empty, the
+    ///   mip levels or array layers are zero, a 3D image has more than one array layer, the
+    ///   initial layout is neither undefined nor preinitialized, or a multisampled image is
+    ///   not a 2D image with optimal tiling and a single mip level.
+    /// - [`InvalidSharingMode`](Error::InvalidSharingMode) if concurrent sharing names fewer
+    ///   than two queue families or one of them twice.
+    /// - [`InvalidCubeCompatibleImage`](Error::InvalidCubeCompatibleImage) if a cube
+    ///   compatible image is not square and 2D or has fewer than 6 array layers, or is
+    ///   multisampled.
+    /// - [`FormatNotSupported`](Error::FormatNotSupported) if the format does not support the
+    ///   image type, tiling, usage and flags.
+    /// - [`LimitExceeded`](Error::LimitExceeded) if the extent, mip levels or array layers are
+    ///   above the [`ImageFormatProperties`](crate::device::ImageFormatProperties).
+    /// - [`UnsupportedSampleCount`](Error::UnsupportedSampleCount) if the sample count is not
+    ///   in the [`ImageFormatProperties`](crate::device::ImageFormatProperties).
+    ///
+    /// Driver errors:
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    pub fn build<'a>(&self, device: &'a Device) -> Result<Image<'a>> {
+        self.validate(device)?;
+
+        let create_info = ffi::ImageCreateInfo {
+            s_type: ffi::StructureType::ImageCreateInfo,
+            p_next: std::ptr::null(),
+            flags: self.flags.0,
+            image_type: self.image_type.into(),
+            format: self.format.into(),
+            extent: self.extent.into(),
+            mip_levels: self.mip_levels,
+            array_layers: self.array_layers,
+            samples: self.samples.into(),
+            tiling: self.tiling.into(),
+            usage: self.usage.0,
+            sharing_mode: (&self.sharing_mode).into(),
+            queue_family_index_count: match self.sharing_mode {
+                SharingMode::Exclusive => 0,
+                SharingMode::Concurrent(s) => s.len() as u32,
+            },
+            p_queue_family_indices: match self.sharing_mode {
+                SharingMode::Exclusive => std::ptr::null(),
+                SharingMode::Concurrent(s) => s.as_ptr(),
+            },
+            initial_layout: self.initial_layout.into(),
+        };
+
+        let mut p_image = MaybeUninit::uninit();
+
+        let result = unsafe {
+            (device.dispatch_loader.vk_create_image)(
+                device.handle.as_ptr(),
+                &create_info,
+                std::ptr::null(),
+                p_image.as_mut_ptr(),
+            )
+        };
+
+        match result {
+            ffi::Result::Success => Ok(Image {
+                #[cfg(target_pointer_width = "64")]
+                handle: unsafe { NonNull::new_unchecked(p_image.assume_init()) },
+                #[cfg(not(target_pointer_width = "64"))]
+                handle: unsafe { NonZeroU64::new_unchecked(p_image.assume_init()) },
+                device,
+                #[cfg(target_pointer_width = "64")]
+                _marker: PhantomData,
+                owned: true,
+                memory_bound: false,
+                format: self.format,
+                extent: self.extent,
+                mip_levels: self.mip_levels,
+                array_layers: self.array_layers,
+                usage: self.usage,
+                layouts: self.layout_tracking.then(|| {
+                    LayoutTracker::new(
+                        self.format,
+                        self.mip_levels,
+                        self.array_layers,
+                        self.initial_layout,
+                    )
+                }),
+            }),
+            ffi::Result::ErrorOutOfHostMemory => Err(Error::OutOfHostMemory),
+            ffi::Result::ErrorOutOfDeviceMemory => Err(Error::OutOfDeviceMemory),
+            _ => unreachable!(),
+        }
+    }
+
+    fn validate(&self, device: &Device) -> Result<()> {
+        let extent = self.extent;
+        let dimensions = match self.image_type {
+            ImageType::OneD => 1,
+            ImageType::TwoD => 2,
+            ImageType::ThreeD => 3,
+        };
+        if extent.width() == 0
+            || extent.height() == 0
+            || extent.depth() == 0
+            || (dimensions < 2 && extent.height() != 1)
+            || (dimensions < 3 && extent.depth() != 1)
+        {
+            return Err(Error::InvalidImageExtent(extent));
+        }
+        if self.usage.0 == 0 {
+            return Err(Error::InvalidImageParameter("usage"));
+        }
+        if self.mip_levels == 0 {
+            return Err(Error::InvalidImageParameter("mipLevels"));
+        }
+        if self.array_layers == 0 || (dimensions == 3 && self.array_layers != 1) {
+            return Err(Error::InvalidImageParameter("arrayLayers"));
+        }
+        if !matches!(
+            self.initial_layout,
+            ImageLayout::Undefined | ImageLayout::Preinitialized
+        ) {
+            return Err(Error::InvalidImageParameter("initialLayout"));
+        }
+        let multisampled = self.samples != SampleCount::OneBit;
+        if multisampled
+            && (self.image_type != ImageType::TwoD
+                || self.tiling != ImageTiling::Optimal
+                || self.mip_levels != 1)
+        {
+            return Err(Error::InvalidImageParameter("samples"));
+        }
+        self.sharing_mode.validate()?;
+        if self.flags.contains(ImageCreate::CubeCompatible)
+            && (self.image_type != ImageType::TwoD
+                || extent.width() != extent.height()
+                || self.array_layers < 6
+                || multisampled)
+        {
+            return Err(Error::InvalidCubeCompatibleImage);
+        }
+
+        let properties = device.physical_device().image_format_properties(
+            self.format,
+            self.image_type,
+            self.tiling,
+            self.usage,
+            self.flags,
+        )?;
+
+        let max_extent = properties.max_extent;
+        let max_dimension = extent.width().max(extent.height()).max(extent.depth());
+        let limits = [
+            ("maxExtent.width", extent.width(), max_extent.width()),
+            ("maxExtent.height", extent.height(), max_extent.height()),
+            ("maxExtent.depth", extent.depth(), max_extent.depth()),
+            ("maxMipLevels", self.mip_levels, properties.max_mip_levels),
+            // A full mip chain ends at a 1x1x1 level.
+            (
+                "mipLevels",
+                self.mip_levels,
+                32 - max_dimension.leading_zeros(),
+            ),
+            (
+                "maxArrayLayers",
+                self.array_layers,
+                properties.max_array_layers,
+            ),
+        ];
+        for (limit, value, max) in limits {
+            if value > max {
+                return Err(Error::LimitExceeded {
+                    limit,
+                    value: value.into(),
+                    max: max.into(),
+                });
+            }
+        }
+
+        if !properties.supports_samples(self.samples) {
+            return Err(Error::UnsupportedSampleCount(self.samples));
+        }
+
+        Ok(())
+    }
+}
+
+impl MemoryRequirements {
+    pub fn new(size: u64, alignment: u64, memory_type_bits: u32) -> Self {
+        Self {
+            size,
+            alignment,
+            memory_type_bits,
+            prefers_dedicated_allocation: false,
+            requires_dedicated_allocation: false,
+        }
+    }
+
+    pub fn with_prefers_dedicated_allocation(mut self, prefers_dedicated_allocation: bool) -> Self {
+        self.prefers_dedicated_allocation = prefers_dedicated_allocation;
+        self
+    }
+
+    pub fn with_requires_dedicated_allocation(
+        mut self,
+        requires_dedicated_allocation: bool,
+    ) -> Self {
+        self.requires_dedicated_allocation = requires_dedicated_allocation;
+        self
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn alignment(&self) -> u64 {
+        self.alignment
+    }
+
+    /// Bit `i` is set if the memory type at index `i` can hold the resource.
+    pub fn memory_type_bits(&self) -> u32 {
+        self.memory_type_bits
+    }
+
+    /// The driver reports better performance if the resource has memory of its own.
+    pub fn prefers_dedicated_allocation(&self) -> bool {
+        self.prefers_dedicated_allocation || self.requires_dedicated_allocation
+    }
+
+    /// The resource must have memory of its own, allocated for it with a [`DedicatedResource`].
+    pub fn requires_dedicated_allocation(&self) -> bool {
+        self.requires_dedicated_allocation
+    }
+}
+
+impl From<ffi::MemoryRequirements> for MemoryRequirements {
+    fn from(requirements: ffi::MemoryRequirements) -> Self {
+        Self::new(
+            requirements.size,
+            requirements.alignment,
+            requirements.memory_type_bits,
+        )
+    }
+}
+
+impl<'a> BufferViewBuilder<'a> {
+    /// View of `range` bytes of `buffer` starting at `offset`, or of the rest of the buffer
+    /// if `range` is [`WHOLE_SIZE`].
+    pub fn new(buffer: &'a Buffer<'a>, format: Format, offset: u64, range: u64) -> Self {
+        Self {
+            buffer,
+            format,
+            offset,
+            range,
+        }
+    }
+
+    /// # Errors
+    /// - [`MissingBufferUsage`](Error::MissingBufferUsage) if the buffer has neither the
+    ///   [`UniformTexelBuffer`](BufferUsage::UniformTexelBuffer) nor the
+    ///   [`StorageTexelBuffer`](BufferUsage::StorageTexelBuffer) usage.
+    /// - [`InvalidBufferParameter`](Error::InvalidBufferParameter) if the format has no texel
+    ///   block size, or the range is not a multiple of it.
+    /// - [`MemoryRangeOutOfBounds`](Error::MemoryRangeOutOfBounds) if the range is empty or
+    ///   extends past the end of the buffer.
+    /// - [`MisalignedMemoryOffset`](Error::MisalignedMemoryOffset) if the offset is not a
+    ///   multiple of `minTexelBufferOffsetAlignment`.
+    /// - [`LimitExceeded`](Error::LimitExceeded) if the view has more texels than
+    ///   `maxTexelBufferElements`.
+    ///
+    /// Driver errors:
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    pub fn build(self, device: &'a Device) -> Result<BufferView<'a>> {
+        let usage = self.buffer.usage();
+        if !usage.contains(BufferUsage::UniformTexelBuffer)
+            && !usage.contains(BufferUsage::StorageTexelBuffer)
+        {
+            return Err(Error::MissingBufferUsage(BufferUsage::UniformTexelBuffer));
+        }
+        let texel_size = self
+            .format
+            .texel_block_size()
+            .ok_or(Error::InvalidBufferParameter("format"))?;
+        // Only an explicit range has to be a whole number of texels, the rest of the buffer
+        // is rounded down.
+        if self.range != WHOLE_SIZE && !self.range.is_multiple_of(texel_size.into()) {
+            return Err(Error::InvalidBufferParameter("range"));
+        }
+        let size = self.buffer.size();
+        let range = if self.range == WHOLE_SIZE {
+            size.saturating_sub(self.offset)
+        } else {
+            self.range
+        };
+        if range == 0 || self.offset.checked_add(range).is_none_or(|end| end > size) {
+            return Err(Error::MemoryRangeOutOfBounds {
+                offset: self.offset,
+                size: range,
+            });
+        }
+        let alignment = device.limits().min_texel_buffer_offset_alignment;
+        if !self.offset.is_multiple_of(alignment) {
+            return Err(Error::MisalignedMemoryOffset {
+                offset: self.offset,
+                alignment,
+            });
+        }
+        let elements = range / u64::from(texel_size);
+        let max_elements = device.limits().max_texel_buffer_elements;
+        if elements > u64::from(max_elements) {
+            return Err(Error::LimitExceeded {
+                limit: "maxTexelBufferElements",
+                value: elements,
+                max: max_elements.into(),
+            });
+        }
+
+        let create_info = ffi::BufferViewCreateInfo {
+            s_type: ffi::StructureType::BufferViewCreateInfo,
+            p_next: std::ptr::null(),
+            flags: 0,
+            #[cfg(target_pointer_width = "64")]
+            buffer: self.buffer.handle.as_ptr(),
+            #[cfg(not(target_pointer_width = "64"))]
+            buffer: self.buffer.handle.get(),
+            format: self.format.into(),
+            offset: self.offset,
+            range: self.range,
+        };
+
+        let mut p_view = MaybeUninit::uninit();
+
+        let result = unsafe {
+            (device.dispatch_loader.vk_create_buffer_view)(
+                device.handle.as_ptr(),
+                &create_info,
+                std::ptr::null(),
+                p_view.as_mut_ptr(),
+            )
+        };
+
+        match result {
+            ffi::Result::Success => Ok(BufferView {
+                #[cfg(target_pointer_width = "64")]
+                handle: unsafe { NonNull::new_unchecked(p_view.assume_init()) },
                 #[cfg(not(target_pointer_width = "64"))]
-                self.handle.get(),
-                std::ptr::null(),
-            );
+                handle: unsafe { NonZeroU64::new_unchecked(p_view.assume_init()) },
+                device,
+                usage: self.buffer.usage(),
+                _marker: PhantomData,
+            }),
+            ffi::Result::ErrorOutOfHostMemory => Err(Error::OutOfHostMemory),
+            ffi::Result::ErrorOutOfDeviceMemory => Err(Error::OutOfDeviceMemory),
+            _ => unreachable!(),
         }
     }
 }
@@ -203,6 +1879,7 @@ impl<'a> ImageViewBuilder<'a> {
                 #[cfg(not(target_pointer_width = "64"))]
                 handle: unsafe { NonZeroU64::new_unchecked(p_view.assume_init()) },
                 device,
+                usage: self.image.usage(),
                 #[cfg(target_pointer_width = "64")]
                 _marker: PhantomData,
             }),
@@ -213,6 +1890,25 @@ impl<'a> ImageViewBuilder<'a> {
     }
 }
 
+impl From<ImageType> for ffi::ImageType {
+    fn from(image_type: ImageType) -> Self {
+        match image_type {
+            ImageType::OneD => Self::OneD,
+            ImageType::TwoD => Self::TwoD,
+            ImageType::ThreeD => Self::ThreeD,
+        }
+    }
+}
+
+impl From<ImageTiling> for ffi::ImageTiling {
+    fn from(tiling: ImageTiling) -> Self {
+        match tiling {
+            ImageTiling::Optimal => Self::Optimal,
+            ImageTiling::Linear => Self::Linear,
+        }
+    }
+}
+
 impl From<ImageViewType> for ffi::ImageViewType {
     fn from(view_type: ImageViewType) -> Self {
         match view_type {
@@ -227,6 +1923,32 @@ impl From<ImageViewType> for ffi::ImageViewType {
     }
 }
 
+impl ImageAspectFlags {
+    pub fn contains(&self, flag: ImageAspect) -> bool {
+        let flag = ffi::ImageAspectFlagBits::from(flag);
+
+        self.0 & flag as u32 != 0
+    }
+}
+
+impl From<ImageAspect> for ffi::ImageAspectFlagBits {
+    fn from(aspect: ImageAspect) -> Self {
+        match aspect {
+            ImageAspect::Color => Self::ColorBit,
+            ImageAspect::Depth => Self::DepthBit,
+            ImageAspect::Stencil => Self::StencilBit,
+            ImageAspect::Metadata => Self::MetadataBit,
+            ImageAspect::Plane0 => Self::Plane0Bit,
+            ImageAspect::Plane1 => Self::Plane1Bit,
+            ImageAspect::Plane2 => Self::Plane2Bit,
+            ImageAspect::MemoryPlane0Ext => Self::MemoryPlane0BitExt,
+            ImageAspect::MemoryPlane1Ext => Self::MemoryPlane1BitExt,
+            ImageAspect::MemoryPlane2Ext => Self::MemoryPlane2BitExt,
+            ImageAspect::MemoryPlane3Ext => Self::MemoryPlane3BitExt,
+        }
+    }
+}
+
 impl ImageAspectFlagsBuilder {
     pub fn new() -> Self {
         Default::default()
@@ -314,6 +2036,52 @@ impl ImageAspectFlagsBuilder {
     }
 }
 
+impl From<ImageLayout> for ffi::ImageLayout {
+    fn from(layout: ImageLayout) -> Self {
+        match layout {
+            ImageLayout::Undefined => Self::Undefined,
+            ImageLayout::General => Self::General,
+            ImageLayout::ColorAttachmentOptimal => Self::ColorAttachmentOptimal,
+            ImageLayout::DepthStencilAttachmentOptimal => Self::DepthStencilAttachmentOptimal,
+            ImageLayout::DepthStencilReadOnlyOptimal => Self::DepthStencilReadOnlyOptimal,
+            ImageLayout::ShaderReadOnlyOptimal => Self::ShaderReadOnlyOptimal,
+            ImageLayout::TransferSrcOptimal => Self::TransferSrcOptimal,
+            ImageLayout::TransferDstOptimal => Self::TransferDstOptimal,
+            ImageLayout::Preinitialized => Self::Preinitialized,
+            ImageLayout::DepthReadOnlyStencilAttachmentOptimal => {
+                Self::DepthReadOnlyStencilAttachmentOptimal
+            }
+            ImageLayout::DepthAttachmentStencilReadOnlyOptimal => {
+                Self::DepthAttachmentStencilReadOnlyOptimal
+            }
+            ImageLayout::DepthAttachmentOptimal => Self::DepthAttachmentOptimal,
+            ImageLayout::DepthReadOnlyOptimal => Self::DepthReadOnlyOptimal,
+            ImageLayout::StencilAttachmentOptimal => Self::StencilAttachmentOptimal,
+            ImageLayout::StencilReadOnlyOptimal => Self::StencilReadOnlyOptimal,
+            ImageLayout::PresentSrcKhr => Self::PresentSrcKhr,
+            ImageLayout::SharedPresentKhr => Self::SharedPresentKhr,
+            ImageLayout::ReadOnlyOptimalKhr => Self::ReadOnlyOptimalKhr,
+            ImageLayout::AttachmentOptimalKhr => Self::AttachmentOptimalKhr,
+        }
+    }
+}
+
+impl ImageLayout {
+    /// The layout without synchronization2, which replaces the generic read-only and
+    /// attachment layouts by those of the depth/stencil or color aspects of `aspect_mask`.
+    pub fn to_legacy(self, aspect_mask: ImageAspectFlags) -> Self {
+        let depth_stencil =
+            aspect_mask.contains(ImageAspect::Depth) || aspect_mask.contains(ImageAspect::Stencil);
+        match self {
+            Self::ReadOnlyOptimalKhr if depth_stencil => Self::DepthStencilReadOnlyOptimal,
+            Self::ReadOnlyOptimalKhr => Self::ShaderReadOnlyOptimal,
+            Self::AttachmentOptimalKhr if depth_stencil => Self::DepthStencilAttachmentOptimal,
+            Self::AttachmentOptimalKhr => Self::ColorAttachmentOptimal,
+            layout => layout,
+        }
+    }
+}
+
 impl Default for ComponentSwizzle {
     fn default() -> Self {
         Self::Identity
@@ -336,6 +2104,26 @@ impl ImageSubresourceRange {
             layer_count,
         }
     }
+
+    pub fn aspect_mask(&self) -> ImageAspectFlags {
+        self.aspect_mask
+    }
+
+    pub fn base_mip_level(&self) -> u32 {
+        self.base_mip_level
+    }
+
+    pub fn level_count(&self) -> u32 {
+        self.level_count
+    }
+
+    pub fn base_array_layer(&self) -> u32 {
+        self.base_array_layer
+    }
+
+    pub fn layer_count(&self) -> u32 {
+        self.layer_count
+    }
 }
 
 impl From<ImageSubresourceRange> for ffi::ImageSubresourceRange {
@@ -350,6 +2138,33 @@ impl From<ImageSubresourceRange> for ffi::ImageSubresourceRange {
     }
 }
 
+impl ImageSubresourceLayers {
+    pub fn new(
+        aspect_mask: ImageAspectFlags,
+        mip_level: u32,
+        base_array_layer: u32,
+        layer_count: u32,
+    ) -> Self {
+        Self {
+            aspect_mask,
+            mip_level,
+            base_array_layer,
+            layer_count,
+        }
+    }
+}
+
+impl From<ImageSubresourceLayers> for ffi::ImageSubresourceLayers {
+    fn from(subresource_layers: ImageSubresourceLayers) -> Self {
+        Self {
+            aspect_mask: subresource_layers.aspect_mask.0,
+            mip_level: subresource_layers.mip_level,
+            base_array_layer: subresource_layers.base_array_layer,
+            layer_count: subresource_layers.layer_count,
+        }
+    }
+}
+
 impl From<&SharingMode<'_>> for ffi::SharingMode {
     fn from(sharing_mode: &SharingMode) -> Self {
         match sharing_mode {
@@ -518,3 +2333,346 @@ impl ImageUsageFlagsBuilder {
         ImageUsageFlags(self.0)
     }
 }
+
+impl BufferUsageFlags {
+    pub fn contains(&self, flag: BufferUsage) -> bool {
+        let flag = ffi::BufferUsageFlagBits::from(flag);
+
+        self.0 & flag as u32 != 0
+    }
+}
+
+impl From<BufferUsage> for ffi::BufferUsageFlagBits {
+    fn from(buffer_usage: BufferUsage) -> Self {
+        match buffer_usage {
+            BufferUsage::TransferSrc => Self::TransferSrcBit,
+            BufferUsage::TransferDst => Self::TransferDstBit,
+            BufferUsage::UniformTexelBuffer => Self::UniformTexelBufferBit,
+            BufferUsage::StorageTexelBuffer => Self::StorageTexelBufferBit,
+            BufferUsage::UniformBuffer => Self::UniformBufferBit,
+            BufferUsage::StorageBuffer => Self::StorageBufferBit,
+            BufferUsage::IndexBuffer => Self::IndexBufferBit,
+            BufferUsage::VertexBuffer => Self::VertexBufferBit,
+            BufferUsage::IndirectBuffer => Self::IndirectBufferBit,
+            BufferUsage::ShaderDeviceAddress => Self::ShaderDeviceAddressBit,
+        }
+    }
+}
+
+impl BufferUsageFlagsBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn transfer_src(&mut self, transfer_src: bool) -> &mut Self {
+        if transfer_src {
+            self.0 |= ffi::BufferUsageFlagBits::TransferSrcBit as u32;
+        }
+        self
+    }
+
+    pub fn transfer_dst(&mut self, transfer_dst: bool) -> &mut Self {
+        if transfer_dst {
+            self.0 |= ffi::BufferUsageFlagBits::TransferDstBit as u32;
+        }
+        self
+    }
+
+    pub fn uniform_texel_buffer(&mut self, uniform_texel_buffer: bool) -> &mut Self {
+        if uniform_texel_buffer {
+            self.0 |= ffi::BufferUsageFlagBits::UniformTexelBufferBit as u32;
+        }
+        self
+    }
+
+    pub fn storage_texel_buffer(&mut self, storage_texel_buffer: bool) -> &mut Self {
+        if storage_texel_buffer {
+            self.0 |= ffi::BufferUsageFlagBits::StorageTexelBufferBit as u32;
+        }
+        self
+    }
+
+    pub fn uniform_buffer(&mut self, uniform_buffer: bool) -> &mut Self {
+        if uniform_buffer {
+            self.0 |= ffi::BufferUsageFlagBits::UniformBufferBit as u32;
+        }
+        self
+    }
+
+    pub fn storage_buffer(&mut self, storage_buffer: bool) -> &mut Self {
+        if storage_buffer {
+            self.0 |= ffi::BufferUsageFlagBits::StorageBufferBit as u32;
+        }
+        self
+    }
+
+    pub fn index_buffer(&mut self, index_buffer: bool) -> &mut Self {
+        if index_buffer {
+            self.0 |= ffi::BufferUsageFlagBits::IndexBufferBit as u32;
+        }
+        self
+    }
+
+    pub fn vertex_buffer(&mut self, vertex_buffer: bool) -> &mut Self {
+        if vertex_buffer {
+            self.0 |= ffi::BufferUsageFlagBits::VertexBufferBit as u32;
+        }
+        self
+    }
+
+    pub fn indirect_buffer(&mut self, indirect_buffer: bool) -> &mut Self {
+        if indirect_buffer {
+            self.0 |= ffi::BufferUsageFlagBits::IndirectBufferBit as u32;
+        }
+        self
+    }
+
+    pub fn shader_device_address(&mut self, shader_device_address: bool) -> &mut Self {
+        if shader_device_address {
+            self.0 |= ffi::BufferUsageFlagBits::ShaderDeviceAddressBit as u32;
+        }
+        self
+    }
+
+    pub fn build(&self) -> BufferUsageFlags {
+        BufferUsageFlags(self.0)
+    }
+}
+
+impl BufferCreateFlags {
+    pub fn contains(&self, flag: BufferCreate) -> bool {
+        let flag = ffi::BufferCreateFlagBits::from(flag);
+
+        self.0 & flag as u32 != 0
+    }
+}
+
+impl From<BufferCreate> for ffi::BufferCreateFlagBits {
+    fn from(buffer_create: BufferCreate) -> Self {
+        match buffer_create {
+            BufferCreate::SparseBinding => Self::SparseBindingBit,
+            BufferCreate::SparseResidency => Self::SparseResidencyBit,
+            BufferCreate::SparseAliased => Self::SparseAliasedBit,
+            BufferCreate::Protected => Self::ProtectedBit,
+            BufferCreate::DeviceAddressCaptureReplay => Self::DeviceAddressCaptureReplayBit,
+        }
+    }
+}
+
+impl BufferCreateFlagsBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn sparse_binding(&mut self, sparse_binding: bool) -> &mut Self {
+        if sparse_binding {
+            self.0 |= ffi::BufferCreateFlagBits::SparseBindingBit as u32;
+        }
+        self
+    }
+
+    pub fn sparse_residency(&mut self, sparse_residency: bool) -> &mut Self {
+        if sparse_residency {
+            self.0 |= ffi::BufferCreateFlagBits::SparseResidencyBit as u32;
+        }
+        self
+    }
+
+    pub fn sparse_aliased(&mut self, sparse_aliased: bool) -> &mut Self {
+        if sparse_aliased {
+            self.0 |= ffi::BufferCreateFlagBits::SparseAliasedBit as u32;
+        }
+        self
+    }
+
+    pub fn protected(&mut self, protected: bool) -> &mut Self {
+        if protected {
+            self.0 |= ffi::BufferCreateFlagBits::ProtectedBit as u32;
+        }
+        self
+    }
+
+    pub fn device_address_capture_replay(
+        &mut self,
+        device_address_capture_replay: bool,
+    ) -> &mut Self {
+        if device_address_capture_replay {
+            self.0 |= ffi::BufferCreateFlagBits::DeviceAddressCaptureReplayBit as u32;
+        }
+        self
+    }
+
+    pub fn build(&self) -> BufferCreateFlags {
+        BufferCreateFlags(self.0)
+    }
+}
+
+impl MemoryPropertyFlags {
+    pub fn contains(&self, flag: MemoryProperty) -> bool {
+        let flag = ffi::MemoryPropertyFlagBits::from(flag);
+
+        self.0 & flag as u32 != 0
+    }
+}
+
+impl From<MemoryProperty> for ffi::MemoryPropertyFlagBits {
+    fn from(memory_property: MemoryProperty) -> Self {
+        match memory_property {
+            MemoryProperty::DeviceLocal => Self::DeviceLocalBit,
+            MemoryProperty::HostVisible => Self::HostVisibleBit,
+            MemoryProperty::HostCoherent => Self::HostCoherentBit,
+            MemoryProperty::HostCached => Self::HostCachedBit,
+            MemoryProperty::LazilyAllocated => Self::LazilyAllocatedBit,
+            MemoryProperty::Protected => Self::ProtectedBit,
+        }
+    }
+}
+
+impl MemoryPropertyFlagsBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn device_local(&mut self, device_local: bool) -> &mut Self {
+        if device_local {
+            self.0 |= ffi::MemoryPropertyFlagBits::DeviceLocalBit as u32;
+        }
+        self
+    }
+
+    pub fn host_visible(&mut self, host_visible: bool) -> &mut Self {
+        if host_visible {
+            self.0 |= ffi::MemoryPropertyFlagBits::HostVisibleBit as u32;
+        }
+        self
+    }
+
+    pub fn host_coherent(&mut self, host_coherent: bool) -> &mut Self {
+        if host_coherent {
+            self.0 |= ffi::MemoryPropertyFlagBits::HostCoherentBit as u32;
+        }
+        self
+    }
+
+    pub fn host_cached(&mut self, host_cached: bool) -> &mut Self {
+        if host_cached {
+            self.0 |= ffi::MemoryPropertyFlagBits::HostCachedBit as u32;
+        }
+        self
+    }
+
+    pub fn lazily_allocated(&mut self, lazily_allocated: bool) -> &mut Self {
+        if lazily_allocated {
+            self.0 |= ffi::MemoryPropertyFlagBits::LazilyAllocatedBit as u32;
+        }
+        self
+    }
+
+    pub fn protected(&mut self, protected: bool) -> &mut Self {
+        if protected {
+            self.0 |= ffi::MemoryPropertyFlagBits::ProtectedBit as u32;
+        }
+        self
+    }
+
+    pub fn build(&self) -> MemoryPropertyFlags {
+        MemoryPropertyFlags(self.0)
+    }
+}
+
+impl ImageCreateFlags {
+    pub fn contains(&self, flag: ImageCreate) -> bool {
+        let flag = ffi::ImageCreateFlagBits::from(flag);
+
+        self.0 & flag as u32 != 0
+    }
+}
+
+impl From<ImageCreate> for ffi::ImageCreateFlagBits {
+    fn from(image_create: ImageCreate) -> Self {
+        match image_create {
+            ImageCreate::SparseBinding => Self::SparseBindingBit,
+            ImageCreate::SparseResidency => Self::SparseResidencyBit,
+            ImageCreate::SparseAliased => Self::SparseAliasedBit,
+            ImageCreate::MutableFormat => Self::MutableFormatBit,
+            ImageCreate::CubeCompatible => Self::CubeCompatibleBit,
+            ImageCreate::TwoDArrayCompatible => Self::TwoDArrayCompatibleBit,
+            ImageCreate::BlockTexelViewCompatible => Self::BlockTexelViewCompatibleBit,
+            ImageCreate::ExtendedUsage => Self::ExtendedUsageBit,
+            ImageCreate::Protected => Self::ProtectedBit,
+        }
+    }
+}
+
+impl ImageCreateFlagsBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn sparse_binding(&mut self, sparse_binding: bool) -> &mut Self {
+        if sparse_binding {
+            self.0 |= ffi::ImageCreateFlagBits::SparseBindingBit as u32;
+        }
+        self
+    }
+
+    pub fn sparse_residency(&mut self, sparse_residency: bool) -> &mut Self {
+        if sparse_residency {
+            self.0 |= ffi::ImageCreateFlagBits::SparseResidencyBit as u32;
+        }
+        self
+    }
+
+    pub fn sparse_aliased(&mut self, sparse_aliased: bool) -> &mut Self {
+        if sparse_aliased {
+            self.0 |= ffi::ImageCreateFlagBits::SparseAliasedBit as u32;
+        }
+        self
+    }
+
+    pub fn mutable_format(&mut self, mutable_format: bool) -> &mut Self {
+        if mutable_format {
+            self.0 |= ffi::ImageCreateFlagBits::MutableFormatBit as u32;
+        }
+        self
+    }
+
+    pub fn cube_compatible(&mut self, cube_compatible: bool) -> &mut Self {
+        if cube_compatible {
+            self.0 |= ffi::ImageCreateFlagBits::CubeCompatibleBit as u32;
+        }
+        self
+    }
+
+    pub fn two_d_array_compatible(&mut self, two_d_array_compatible: bool) -> &mut Self {
+        if two_d_array_compatible {
+            self.0 |= ffi::ImageCreateFlagBits::TwoDArrayCompatibleBit as u32;
+        }
+        self
+    }
+
+    pub fn block_texel_view_compatible(&mut self, block_texel_view_compatible: bool) -> &mut Self {
+        if block_texel_view_compatible {
+            self.0 |= ffi::ImageCreateFlagBits::BlockTexelViewCompatibleBit as u32;
+        }
+        self
+    }
+
+    pub fn extended_usage(&mut self, extended_usage: bool) -> &mut Self {
+        if extended_usage {
+            self.0 |= ffi::ImageCreateFlagBits::ExtendedUsageBit as u32;
+        }
+        self
+    }
+
+    pub fn protected(&mut self, protected: bool) -> &mut Self {
+        if protected {
+            self.0 |= ffi::ImageCreateFlagBits::ProtectedBit as u32;
+        }
+        self
+    }
+
+    pub fn build(&self) -> ImageCreateFlags {
+        ImageCreateFlags(self.0)
+    }
+}
@@ -0,0 +1,985 @@
+//! # Memory sub-allocation
+//! Drivers limit the number of `VkDeviceMemory` objects to
+//! [`max_memory_allocation_count`](crate::device::PhysicalDeviceLimits::max_memory_allocation_count),
+//! so an [`Allocator`] allocates large blocks per memory type and places resources inside them
+//! with an [`AllocationStrategy`]:
+//! - [`Tlsf`] for resources of any size and lifetime.
+//! - [`Linear`] for resources that are all freed together, such as per-frame data.
+//! - [`Pool`] for resources of one fixed size.
+//!
+//! Linear resources (buffers and linearly tiled images) and optimal resources (optimally tiled
+//! images) are kept in separate blocks when
+//! [`buffer_image_granularity`](crate::device::PhysicalDeviceLimits::buffer_image_granularity)
+//! is above 1, so they never share a page.
+use std::collections::HashMap;
+
+use crate::core::{Error, Result};
+use crate::device::{Device, PhysicalDeviceMemoryProperties};
+use crate::resource::{DedicatedResource, DeviceMemory, MemoryPropertyFlags, MemoryRequirements};
+
+/// Source of the memory blocks divided by an [`Allocator`]. Blocks are freed when dropped.
+pub trait MemoryBackend {
+    type Memory;
+
+    /// Allocates `size` bytes of the memory type at `memory_type_index`. `dedicated` is only
+    /// given for resources whose requirements prefer a dedicated allocation.
+    ///
+    /// # Errors
+    /// [`OutOfDeviceMemory`](Error::OutOfDeviceMemory) makes the allocator retry with a
+    /// smaller block or another memory type, other errors are returned to the caller.
+    fn allocate(
+        &mut self,
+        size: u64,
+        memory_type_index: u32,
+        dedicated: Option<DedicatedResource>,
+    ) -> Result<Self::Memory>;
+}
+
+/// Placement of allocations inside one memory block.
+pub trait AllocationStrategy {
+    /// Offset of `size` free bytes aligned to `alignment`, or [`None`] if they do not fit in
+    /// the block or `alignment` is not a power of two.
+    fn allocate(&mut self, size: u64, alignment: u64) -> Option<u64>;
+
+    /// Frees the allocation at `offset`.
+    fn free(&mut self, offset: u64);
+
+    /// Frees every allocation of the block.
+    fn reset(&mut self);
+}
+
+/// Whether a resource is accessed linearly or with an implementation-defined tiling, which
+/// decides which resources may be neighbors within a page of `buffer_image_granularity`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceKind {
+    /// Buffers and images with linear tiling.
+    Linear,
+    /// Images with optimal tiling.
+    Optimal,
+}
+
+pub struct AllocatorBuilder<S> {
+    memory_properties: PhysicalDeviceMemoryProperties,
+    buffer_image_granularity: u64,
+    max_memory_allocation_count: u32,
+    block_size: u64,
+    new_strategy: Box<dyn Fn(u64) -> S>,
+}
+
+pub struct Allocator<B: MemoryBackend, S = Tlsf> {
+    backend: B,
+    memory_properties: PhysicalDeviceMemoryProperties,
+    buffer_image_granularity: u64,
+    max_memory_allocation_count: u32,
+    block_size: u64,
+    new_strategy: Box<dyn Fn(u64) -> S>,
+    blocks: Vec<Option<Block<B::Memory, S>>>,
+    /// Number of resets, which invalidate the allocations handed out before them.
+    generation: u64,
+}
+
+struct Block<M, S> {
+    memory: M,
+    memory_type_index: u32,
+    size: u64,
+    /// [`None`] for dedicated allocations.
+    strategy: Option<S>,
+    /// [`None`] if linear and optimal resources can share the block.
+    kind: Option<ResourceKind>,
+    allocation_count: usize,
+    allocated_bytes: u64,
+}
+
+pub struct AllocationRequest<'r> {
+    requirements: MemoryRequirements,
+    required_properties: MemoryPropertyFlags,
+    preferred_properties: MemoryPropertyFlags,
+    kind: ResourceKind,
+    dedicated_resource: Option<DedicatedResource<'r>>,
+}
+
+/// A range of memory handed out by an [`Allocator`], which has to be given back to
+/// [`Allocator::free`] of the same allocator unless it was invalidated by
+/// [`Allocator::reset`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct Allocation {
+    block: usize,
+    generation: u64,
+    memory_type_index: u32,
+    offset: u64,
+    size: u64,
+    dedicated: bool,
+}
+
+/// Memory use of an [`Allocator`], either in total or for one memory type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocatorStatistics {
+    /// Number of memory objects, dedicated allocations included.
+    pub block_count: usize,
+    pub dedicated_allocation_count: usize,
+    pub allocation_count: usize,
+    /// Size of all memory objects.
+    pub block_bytes: u64,
+    /// Size of all allocations, which is at most `block_bytes`.
+    pub allocated_bytes: u64,
+}
+
+/// Two-level segregated fit: free ranges are kept in size classes, so finding one that fits
+/// and merging neighbors when freeing takes constant time.
+pub struct Tlsf {
+    capacity: u64,
+    blocks: Vec<TlsfBlock>,
+    unused_blocks: Vec<usize>,
+    fl_bitmap: u64,
+    sl_bitmaps: [u32; TLSF_FL_COUNT],
+    free_heads: Vec<Option<usize>>,
+    allocated: HashMap<u64, usize>,
+}
+
+#[derive(Clone, Copy)]
+struct TlsfBlock {
+    offset: u64,
+    size: u64,
+    is_free: bool,
+    prev_physical: Option<usize>,
+    next_physical: Option<usize>,
+    prev_free: Option<usize>,
+    next_free: Option<usize>,
+}
+
+/// Bump allocation. Memory is only reused once every allocation of the block is freed or the
+/// allocator is [`reset`](Allocator::reset).
+pub struct Linear {
+    capacity: u64,
+    head: u64,
+    allocation_count: usize,
+}
+
+/// Fixed-size slots. Allocations larger than the slot size or with an alignment that does not
+/// divide it do not fit.
+pub struct Pool {
+    slot_size: u64,
+    slot_count: u64,
+    next_unused: u64,
+    free_slots: Vec<u64>,
+}
+
+const DEFAULT_BLOCK_SIZE: u64 = 256 * 1024 * 1024;
+
+const TLSF_SL_LOG2: u32 = 5;
+const TLSF_SL_COUNT: usize = 1 << TLSF_SL_LOG2;
+const TLSF_FL_COUNT: usize = 64 - TLSF_SL_LOG2 as usize + 1;
+
+impl<'a> MemoryBackend for &'a Device<'a> {
+    type Memory = DeviceMemory<'a>;
+
+    fn allocate(
+        &mut self,
+        size: u64,
+        memory_type_index: u32,
+        dedicated: Option<DedicatedResource>,
+    ) -> Result<DeviceMemory<'a>> {
+        let device: &'a Device<'a> = self;
+        match dedicated {
+            Some(resource) => device.allocate_dedicated_memory(size, memory_type_index, resource),
+            None => device.allocate_memory(size, memory_type_index),
+        }
+    }
+}
+
+impl AllocatorBuilder<Tlsf> {
+    /// Allocator for the memory types in `memory_properties`, using [`Tlsf`] in blocks of
+    /// 256 MiB by default.
+    pub fn new(memory_properties: PhysicalDeviceMemoryProperties) -> Self {
+        Self {
+            memory_properties,
+            buffer_image_granularity: 1,
+            max_memory_allocation_count: u32::MAX,
+            block_size: DEFAULT_BLOCK_SIZE,
+            new_strategy: Box::new(Tlsf::new),
+        }
+    }
+
+    /// Allocator for the memory types and limits of `device`.
+    pub fn for_device(device: &Device) -> Self {
+        Self::new(device.memory_properties().clone())
+            .with_buffer_image_granularity(device.limits().buffer_image_granularity)
+            .with_max_memory_allocation_count(device.limits().max_memory_allocation_count)
+    }
+}
+
+impl<S: AllocationStrategy> AllocatorBuilder<S> {
+    pub fn with_buffer_image_granularity(mut self, buffer_image_granularity: u64) -> Self {
+        self.buffer_image_granularity = buffer_image_granularity;
+        self
+    }
+
+    pub fn with_max_memory_allocation_count(mut self, max_memory_allocation_count: u32) -> Self {
+        self.max_memory_allocation_count = max_memory_allocation_count;
+        self
+    }
+
+    /// Size of the blocks resources are placed in. Heaps of at most 1 GiB use blocks of an
+    /// eighth of their size if that is smaller, and resources larger than half a block get
+    /// dedicated allocations.
+    pub fn with_block_size(mut self, block_size: u64) -> Self {
+        self.block_size = block_size;
+        self
+    }
+
+    /// Places allocations with the strategy `new_strategy` returns for a block of the given
+    /// size.
+    pub fn with_strategy<T: AllocationStrategy>(
+        self,
+        new_strategy: impl Fn(u64) -> T + 'static,
+    ) -> AllocatorBuilder<T> {
+        AllocatorBuilder {
+            memory_properties: self.memory_properties,
+            buffer_image_granularity: self.buffer_image_granularity,
+            max_memory_allocation_count: self.max_memory_allocation_count,
+            block_size: self.block_size,
+            new_strategy: Box::new(new_strategy),
+        }
+    }
+
+    pub fn build<B: MemoryBackend>(self, backend: B) -> Allocator<B, S> {
+        Allocator {
+            backend,
+            memory_properties: self.memory_properties,
+            buffer_image_granularity: self.buffer_image_granularity,
+            max_memory_allocation_count: self.max_memory_allocation_count,
+            block_size: self.block_size,
+            new_strategy: self.new_strategy,
+            blocks: Vec::new(),
+            generation: 0,
+        }
+    }
+}
+
+impl<B: MemoryBackend, S: AllocationStrategy> Allocator<B, S> {
+    /// Allocates memory for a resource from the memory type that has the required properties
+    /// and most of the preferred ones. Memory types with fewer preferred properties are tried
+    /// when the device runs out of memory.
+    ///
+    /// # Errors
+    /// - [`UnsupportedAllocation`](Error::UnsupportedAllocation) if the required alignment is
+    ///   not a power of two.
+    /// - [`DedicatedResourceRequired`](Error::DedicatedResourceRequired) if the requirements
+    ///   require a dedicated allocation and the request has no dedicated resource.
+    /// - [`NoSuitableMemoryType`](Error::NoSuitableMemoryType) if no memory type allowed by
+    ///   the requirements has the required properties.
+    /// - [`TooManyObjects`](Error::TooManyObjects) if a new block would exceed the maximum
+    ///   number of memory allocations.
+    /// - [`UnsupportedAllocation`](Error::UnsupportedAllocation) if the strategy cannot place
+    ///   the allocation even in an empty block.
+    ///
+    /// Driver errors:
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    pub fn allocate(&mut self, request: &AllocationRequest) -> Result<Allocation> {
+        let requirements = &request.requirements;
+        if !requirements.alignment().is_power_of_two() {
+            return Err(Error::UnsupportedAllocation {
+                size: requirements.size(),
+                alignment: requirements.alignment(),
+            });
+        }
+        if requirements.requires_dedicated_allocation() && request.dedicated_resource.is_none() {
+            return Err(Error::DedicatedResourceRequired);
+        }
+
+        let mut result = Err(Error::NoSuitableMemoryType);
+        for memory_type_index in self.memory_type_candidates(request) {
+            let requirements = &request.requirements;
+            result = if requirements.prefers_dedicated_allocation()
+                || requirements.size() > self.block_size(memory_type_index) / 2
+            {
+                self.allocate_dedicated(memory_type_index, request)
+            } else {
+                self.allocate_in_block(memory_type_index, request)
+            };
+
+            if !matches!(result, Err(Error::OutOfDeviceMemory)) {
+                break;
+            }
+        }
+
+        result
+    }
+
+    /// Frees `allocation`. Empty blocks are released, except for one per memory type that is
+    /// kept for later allocations. Allocations invalidated by [`reset`](Self::reset) were
+    /// already freed, and nothing is done for them.
+    pub fn free(&mut self, allocation: Allocation) {
+        if allocation.generation != self.generation {
+            return;
+        }
+        let block = self.blocks[allocation.block]
+            .as_mut()
+            .expect("allocation is not from this allocator");
+        if let Some(strategy) = &mut block.strategy {
+            strategy.free(allocation.offset);
+        }
+        block.allocation_count -= 1;
+        block.allocated_bytes -= allocation.size;
+
+        if block.allocation_count > 0 {
+            return;
+        }
+        let (memory_type_index, kind) = (block.memory_type_index, block.kind);
+        let keep = !allocation.dedicated
+            && !self.blocks.iter().enumerate().any(|(index, other)| {
+                index != allocation.block
+                    && other.as_ref().is_some_and(|other| {
+                        other.strategy.is_some()
+                            && other.memory_type_index == memory_type_index
+                            && other.kind == kind
+                            && other.allocation_count == 0
+                    })
+            });
+        if !keep {
+            self.blocks[allocation.block] = None;
+        }
+    }
+
+    /// Frees every allocation at once, such as at the start of a frame with the [`Linear`]
+    /// strategy. Dedicated allocations are released, blocks are kept. The allocations handed
+    /// out so far are invalidated: [`free`](Self::free) ignores them and
+    /// [`memory`](Self::memory) panics for them.
+    pub fn reset(&mut self) {
+        self.generation += 1;
+        for slot in &mut self.blocks {
+            let dedicated = match slot {
+                Some(block) => match &mut block.strategy {
+                    Some(strategy) => {
+                        strategy.reset();
+                        block.allocation_count = 0;
+                        block.allocated_bytes = 0;
+                        false
+                    }
+                    None => true,
+                },
+                None => false,
+            };
+            if dedicated {
+                *slot = None;
+            }
+        }
+    }
+
+    /// Memory object the allocation is placed in, at [`Allocation::offset`].
+    ///
+    /// # Panics
+    /// If the allocation is from another allocator or was invalidated by
+    /// [`reset`](Self::reset).
+    pub fn memory(&self, allocation: &Allocation) -> &B::Memory {
+        self.check_generation(allocation);
+        &self.blocks[allocation.block]
+            .as_ref()
+            .expect("allocation is not from this allocator")
+            .memory
+    }
+
+    /// Mutable version of [`memory`](Self::memory), to map the memory.
+    ///
+    /// # Panics
+    /// The same as [`memory`](Self::memory).
+    pub fn memory_mut(&mut self, allocation: &Allocation) -> &mut B::Memory {
+        self.check_generation(allocation);
+        &mut self.blocks[allocation.block]
+            .as_mut()
+            .expect("allocation is not from this allocator")
+            .memory
+    }
+
+    pub fn statistics(&self) -> AllocatorStatistics {
+        self.collect_statistics(|_| true)
+    }
+
+    pub fn memory_type_statistics(&self, memory_type_index: u32) -> AllocatorStatistics {
+        self.collect_statistics(|block| block.memory_type_index == memory_type_index)
+    }
+
+    fn collect_statistics(
+        &self,
+        filter: impl Fn(&Block<B::Memory, S>) -> bool,
+    ) -> AllocatorStatistics {
+        self.blocks
+            .iter()
+            .flatten()
+            .filter(|block| filter(block))
+            .fold(AllocatorStatistics::default(), |mut statistics, block| {
+                statistics.block_count += 1;
+                if block.strategy.is_none() {
+                    statistics.dedicated_allocation_count += 1;
+                }
+                statistics.allocation_count += block.allocation_count;
+                statistics.block_bytes += block.size;
+                statistics.allocated_bytes += block.allocated_bytes;
+                statistics
+            })
+    }
+
+    fn check_generation(&self, allocation: &Allocation) {
+        assert_eq!(
+            allocation.generation, self.generation,
+            "allocation was invalidated by a reset"
+        );
+    }
+
+    /// Indices of the memory types that can hold the allocation, most preferred first.
+    fn memory_type_candidates(&self, request: &AllocationRequest) -> Vec<u32> {
+        let required = request.required_properties.0;
+        let preferred = request.preferred_properties.0;
+
+        let mut candidates: Vec<(u32, u32)> = self
+            .memory_properties
+            .memory_types
+            .iter()
+            .enumerate()
+            .filter(|(index, memory_type)| {
+                request.requirements.memory_type_bits() & (1 << index) != 0
+                    && memory_type.property_flags.0 & required == required
+            })
+            .map(|(index, memory_type)| {
+                (
+                    index as u32,
+                    (memory_type.property_flags.0 & preferred).count_ones(),
+                )
+            })
+            .collect();
+        candidates.sort_by_key(|&(_, preferred_count)| std::cmp::Reverse(preferred_count));
+
+        candidates.into_iter().map(|(index, _)| index).collect()
+    }
+
+    fn block_size(&self, memory_type_index: u32) -> u64 {
+        let heap_index = self.memory_properties.memory_types[memory_type_index as usize].heap_index;
+        let heap_size = self.memory_properties.memory_heaps[heap_index as usize].size;
+
+        if heap_size <= 1024 * 1024 * 1024 {
+            self.block_size.min(heap_size / 8)
+        } else {
+            self.block_size
+        }
+    }
+
+    fn allocate_dedicated(
+        &mut self,
+        memory_type_index: u32,
+        request: &AllocationRequest,
+    ) -> Result<Allocation> {
+        let size = request.requirements.size();
+        let dedicated = request
+            .dedicated_resource
+            .filter(|_| request.requirements.prefers_dedicated_allocation());
+        let memory = self.allocate_memory(size, memory_type_index, dedicated)?;
+
+        let block = self.insert_block(Block {
+            memory,
+            memory_type_index,
+            size,
+            strategy: None,
+            kind: None,
+            allocation_count: 1,
+            allocated_bytes: size,
+        });
+
+        Ok(Allocation {
+            block,
+            generation: self.generation,
+            memory_type_index,
+            offset: 0,
+            size,
+            dedicated: true,
+        })
+    }
+
+    fn allocate_in_block(
+        &mut self,
+        memory_type_index: u32,
+        request: &AllocationRequest,
+    ) -> Result<Allocation> {
+        let size = request.requirements.size();
+        let alignment = request.requirements.alignment();
+        let kind = if self.buffer_image_granularity > 1 {
+            Some(request.kind)
+        } else {
+            None
+        };
+
+        for (index, slot) in self.blocks.iter_mut().enumerate() {
+            let block = match slot {
+                Some(block)
+                    if block.memory_type_index == memory_type_index && block.kind == kind =>
+                {
+                    block
+                }
+                _ => continue,
+            };
+            let offset = match &mut block.strategy {
+                Some(strategy) => strategy.allocate(size, alignment),
+                None => None,
+            };
+            if let Some(offset) = offset {
+                block.allocation_count += 1;
+                block.allocated_bytes += size;
+                return Ok(Allocation {
+                    block: index,
+                    generation: self.generation,
+                    memory_type_index,
+                    offset,
+                    size,
+                    dedicated: false,
+                });
+            }
+        }
+
+        // Smaller blocks are tried when the device is low on memory.
+        let mut block_size = self.block_size(memory_type_index).max(size);
+        let memory = loop {
+            match self.allocate_memory(block_size, memory_type_index, None) {
+                Err(Error::OutOfDeviceMemory) if block_size / 2 >= size => block_size /= 2,
+                result => break result?,
+            }
+        };
+
+        let mut strategy = (self.new_strategy)(block_size);
+        let offset = match strategy.allocate(size, alignment) {
+            Some(offset) => offset,
+            None => return Err(Error::UnsupportedAllocation { size, alignment }),
+        };
+        let block = self.insert_block(Block {
+            memory,
+            memory_type_index,
+            size: block_size,
+            strategy: Some(strategy),
+            kind,
+            allocation_count: 1,
+            allocated_bytes: size,
+        });
+
+        Ok(Allocation {
+            block,
+            generation: self.generation,
+            memory_type_index,
+            offset,
+            size,
+            dedicated: false,
+        })
+    }
+
+    fn allocate_memory(
+        &mut self,
+        size: u64,
+        memory_type_index: u32,
+        dedicated: Option<DedicatedResource>,
+    ) -> Result<B::Memory> {
+        if self.blocks.iter().flatten().count() >= self.max_memory_allocation_count as usize {
+            return Err(Error::TooManyObjects);
+        }
+
+        self.backend.allocate(size, memory_type_index, dedicated)
+    }
+
+    fn insert_block(&mut self, block: Block<B::Memory, S>) -> usize {
+        match self.blocks.iter().position(Option::is_none) {
+            Some(index) => {
+                self.blocks[index] = Some(block);
+                index
+            }
+            None => {
+                self.blocks.push(Some(block));
+                self.blocks.len() - 1
+            }
+        }
+    }
+}
+
+impl<'r> AllocationRequest<'r> {
+    /// Request for memory that satisfies `requirements`, without required or preferred
+    /// memory properties, for a [`ResourceKind::Linear`] resource.
+    pub fn new(requirements: MemoryRequirements) -> Self {
+        Self {
+            requirements,
+            required_properties: Default::default(),
+            preferred_properties: Default::default(),
+            kind: ResourceKind::Linear,
+            dedicated_resource: None,
+        }
+    }
+
+    pub fn with_required_properties(mut self, required_properties: MemoryPropertyFlags) -> Self {
+        self.required_properties = required_properties;
+        self
+    }
+
+    pub fn with_preferred_properties(mut self, preferred_properties: MemoryPropertyFlags) -> Self {
+        self.preferred_properties = preferred_properties;
+        self
+    }
+
+    pub fn with_kind(mut self, kind: ResourceKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Resource to make a dedicated allocation for if its requirements prefer one.
+    pub fn with_dedicated_resource(mut self, dedicated_resource: DedicatedResource<'r>) -> Self {
+        self.dedicated_resource = Some(dedicated_resource);
+        self
+    }
+}
+
+impl Allocation {
+    pub fn memory_type_index(&self) -> u32 {
+        self.memory_type_index
+    }
+
+    /// Offset of the allocation in its memory object.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Whether the allocation has a memory object of its own.
+    pub fn is_dedicated(&self) -> bool {
+        self.dedicated
+    }
+}
+
+impl Tlsf {
+    pub fn new(capacity: u64) -> Self {
+        let mut tlsf = Self {
+            capacity,
+            blocks: Vec::new(),
+            unused_blocks: Vec::new(),
+            fl_bitmap: 0,
+            sl_bitmaps: [0; TLSF_FL_COUNT],
+            free_heads: vec![None; TLSF_FL_COUNT * TLSF_SL_COUNT],
+            allocated: HashMap::new(),
+        };
+        tlsf.reset();
+        tlsf
+    }
+
+    /// Size class of a free range of `size` bytes.
+    fn mapping(size: u64) -> (usize, usize) {
+        if size < TLSF_SL_COUNT as u64 {
+            (0, size as usize)
+        } else {
+            let fl = 63 - size.leading_zeros();
+            let sl = (size >> (fl - TLSF_SL_LOG2)) as usize - TLSF_SL_COUNT;
+            ((fl - TLSF_SL_LOG2 + 1) as usize, sl)
+        }
+    }
+
+    /// Lowest size class whose free ranges are all at least `size` bytes.
+    fn search_mapping(size: u64) -> Option<(usize, usize)> {
+        if size < TLSF_SL_COUNT as u64 {
+            return Some(Self::mapping(size));
+        }
+
+        let fl = 63 - size.leading_zeros();
+        let rounded = size.checked_add((1 << (fl - TLSF_SL_LOG2)) - 1)?;
+        Some(Self::mapping(rounded))
+    }
+
+    fn find_free(&self, fl: usize, sl: usize) -> Option<usize> {
+        let mut sl_map = self.sl_bitmaps[fl] & (u32::MAX << sl);
+        let fl = if sl_map == 0 {
+            let fl_map = self.fl_bitmap & (u64::MAX << (fl + 1));
+            if fl_map == 0 {
+                return None;
+            }
+            let fl = fl_map.trailing_zeros() as usize;
+            sl_map = self.sl_bitmaps[fl];
+            fl
+        } else {
+            fl
+        };
+
+        self.free_heads[fl * TLSF_SL_COUNT + sl_map.trailing_zeros() as usize]
+    }
+
+    /// Walks the free lists for a range that fits once aligned, which the constant time
+    /// search misses when the padding for the alignment would push it into a larger class.
+    fn find_fitting(&self, size: u64, alignment: u64) -> Option<usize> {
+        let (fl, sl) = Self::search_mapping(size)?;
+
+        for head in &self.free_heads[fl * TLSF_SL_COUNT + sl..] {
+            let mut next = *head;
+            while let Some(index) = next {
+                let block = &self.blocks[index];
+                let offset = block.offset.next_multiple_of(alignment);
+                if offset + size <= block.offset + block.size {
+                    return Some(index);
+                }
+                next = block.next_free;
+            }
+        }
+
+        None
+    }
+
+    fn insert_free(&mut self, index: usize) {
+        let (fl, sl) = Self::mapping(self.blocks[index].size);
+        let list = fl * TLSF_SL_COUNT + sl;
+
+        let head = self.free_heads[list];
+        self.blocks[index].prev_free = None;
+        self.blocks[index].next_free = head;
+        if let Some(head) = head {
+            self.blocks[head].prev_free = Some(index);
+        }
+        self.free_heads[list] = Some(index);
+        self.sl_bitmaps[fl] |= 1 << sl;
+        self.fl_bitmap |= 1 << fl;
+    }
+
+    fn remove_free(&mut self, index: usize) {
+        let (fl, sl) = Self::mapping(self.blocks[index].size);
+        let list = fl * TLSF_SL_COUNT + sl;
+
+        let TlsfBlock {
+            prev_free,
+            next_free,
+            ..
+        } = self.blocks[index];
+        match prev_free {
+            Some(prev) => self.blocks[prev].next_free = next_free,
+            None => self.free_heads[list] = next_free,
+        }
+        if let Some(next) = next_free {
+            self.blocks[next].prev_free = prev_free;
+        }
+
+        if self.free_heads[list].is_none() {
+            self.sl_bitmaps[fl] &= !(1 << sl);
+            if self.sl_bitmaps[fl] == 0 {
+                self.fl_bitmap &= !(1 << fl);
+            }
+        }
+    }
+
+    fn push_block(&mut self, block: TlsfBlock) -> usize {
+        match self.unused_blocks.pop() {
+            Some(index) => {
+                self.blocks[index] = block;
+                index
+            }
+            None => {
+                self.blocks.push(block);
+                self.blocks.len() - 1
+            }
+        }
+    }
+
+    /// Splits the first `size` bytes of the range at `index` off into a free range before it.
+    fn split_front(&mut self, index: usize, size: u64) {
+        let block = self.blocks[index];
+        let front = self.push_block(TlsfBlock {
+            offset: block.offset,
+            size,
+            is_free: true,
+            prev_physical: block.prev_physical,
+            next_physical: Some(index),
+            prev_free: None,
+            next_free: None,
+        });
+        if let Some(prev) = block.prev_physical {
+            self.blocks[prev].next_physical = Some(front);
+        }
+        self.blocks[index].prev_physical = Some(front);
+        self.blocks[index].offset += size;
+        self.blocks[index].size -= size;
+        self.insert_free(front);
+    }
+
+    /// Splits the range at `index` after `size` bytes, the rest becoming a free range after it.
+    fn split_back(&mut self, index: usize, size: u64) {
+        let block = self.blocks[index];
+        let back = self.push_block(TlsfBlock {
+            offset: block.offset + size,
+            size: block.size - size,
+            is_free: true,
+            prev_physical: Some(index),
+            next_physical: block.next_physical,
+            prev_free: None,
+            next_free: None,
+        });
+        if let Some(next) = block.next_physical {
+            self.blocks[next].prev_physical = Some(back);
+        }
+        self.blocks[index].next_physical = Some(back);
+        self.blocks[index].size = size;
+        self.insert_free(back);
+    }
+
+    /// Merges the range at `next` into the range at `index` before it.
+    fn merge(&mut self, index: usize, next: usize) {
+        let next_block = self.blocks[next];
+        self.blocks[index].size += next_block.size;
+        self.blocks[index].next_physical = next_block.next_physical;
+        if let Some(after) = next_block.next_physical {
+            self.blocks[after].prev_physical = Some(index);
+        }
+        self.unused_blocks.push(next);
+    }
+}
+
+impl AllocationStrategy for Tlsf {
+    fn allocate(&mut self, size: u64, alignment: u64) -> Option<u64> {
+        if !alignment.is_power_of_two() {
+            return None;
+        }
+        let size = size.max(1);
+        let index = Self::search_mapping(size.checked_add(alignment - 1)?)
+            .and_then(|(fl, sl)| self.find_free(fl, sl))
+            .or_else(|| self.find_fitting(size, alignment))?;
+        self.remove_free(index);
+
+        let block = self.blocks[index];
+        let offset = block.offset.next_multiple_of(alignment);
+        if offset > block.offset {
+            self.split_front(index, offset - block.offset);
+        }
+        if self.blocks[index].size > size {
+            self.split_back(index, size);
+        }
+        self.blocks[index].is_free = false;
+        self.allocated.insert(offset, index);
+
+        Some(offset)
+    }
+
+    fn free(&mut self, offset: u64) {
+        let mut index = match self.allocated.remove(&offset) {
+            Some(index) => index,
+            None => return,
+        };
+        self.blocks[index].is_free = true;
+
+        if let Some(prev) = self.blocks[index].prev_physical {
+            if self.blocks[prev].is_free {
+                self.remove_free(prev);
+                self.merge(prev, index);
+                index = prev;
+            }
+        }
+        if let Some(next) = self.blocks[index].next_physical {
+            if self.blocks[next].is_free {
+                self.remove_free(next);
+                self.merge(index, next);
+            }
+        }
+
+        self.insert_free(index);
+    }
+
+    fn reset(&mut self) {
+        self.blocks.clear();
+        self.unused_blocks.clear();
+        self.fl_bitmap = 0;
+        self.sl_bitmaps = [0; TLSF_FL_COUNT];
+        self.free_heads.fill(None);
+        self.allocated.clear();
+
+        if self.capacity > 0 {
+            let index = self.push_block(TlsfBlock {
+                offset: 0,
+                size: self.capacity,
+                is_free: true,
+                prev_physical: None,
+                next_physical: None,
+                prev_free: None,
+                next_free: None,
+            });
+            self.insert_free(index);
+        }
+    }
+}
+
+impl Linear {
+    pub fn new(capacity: u64) -> Self {
+        Self {
+            capacity,
+            head: 0,
+            allocation_count: 0,
+        }
+    }
+}
+
+impl AllocationStrategy for Linear {
+    fn allocate(&mut self, size: u64, alignment: u64) -> Option<u64> {
+        if !alignment.is_power_of_two() {
+            return None;
+        }
+        let offset = self.head.next_multiple_of(alignment);
+        let end = offset.checked_add(size)?;
+        if end > self.capacity {
+            return None;
+        }
+
+        self.head = end;
+        self.allocation_count += 1;
+        Some(offset)
+    }
+
+    fn free(&mut self, _offset: u64) {
+        self.allocation_count = self.allocation_count.saturating_sub(1);
+        if self.allocation_count == 0 {
+            self.head = 0;
+        }
+    }
+
+    fn reset(&mut self) {
+        self.head = 0;
+        self.allocation_count = 0;
+    }
+}
+
+impl Pool {
+    pub fn new(capacity: u64, slot_size: u64) -> Self {
+        let slot_size = slot_size.max(1);
+        Self {
+            slot_size,
+            slot_count: capacity / slot_size,
+            next_unused: 0,
+            free_slots: Vec::new(),
+        }
+    }
+}
+
+impl AllocationStrategy for Pool {
+    fn allocate(&mut self, size: u64, alignment: u64) -> Option<u64> {
+        if !alignment.is_power_of_two() {
+            return None;
+        }
+        if size > self.slot_size || !self.slot_size.is_multiple_of(alignment) {
+            return None;
+        }
+
+        let slot = match self.free_slots.pop() {
+            Some(slot) => slot,
+            None if self.next_unused < self.slot_count => {
+                self.next_unused += 1;
+                self.next_unused - 1
+            }
+            None => return None,
+        };
+        Some(slot * self.slot_size)
+    }
+
+    fn free(&mut self, offset: u64) {
+        self.free_slots.push(offset / self.slot_size);
+    }
+
+    fn reset(&mut self) {
+        self.next_unused = 0;
+        self.free_slots.clear();
+    }
+}
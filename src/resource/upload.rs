@@ -0,0 +1,763 @@
+//! # Staging uploads
+//! Device local memory is usually not host visible, so data reaches it through a host visible
+//! staging buffer and a copy on a queue. An [`Uploader`] collects any number of buffer and
+//! image uploads, copies their data into one staging buffer and records all copies into one
+//! command buffer, submitted together by [`Uploader::submit`].
+//!
+//! When the uploads are consumed on another queue family than the transfer queue, for example
+//! a dedicated transfer queue feeding a graphics queue, the uploaded resources are released to
+//! that family after the copies and have to be acquired on it with
+//! [`UploadToken::record_acquire`] before use.
+use crate::command_buffer::{
+    BufferCopy, BufferImageCopy, CommandBuffer, CommandBufferUsageFlagsBuilder, CommandPool,
+};
+use crate::core::{Error, Extent3D, Offset3D, Result};
+use crate::device::{Device, Queue, SubmitInfo};
+use crate::format::Format;
+use crate::resource::{
+    Buffer, BufferBuilder, BufferUsage, BufferUsageFlagsBuilder, DeviceMemory, Image,
+    ImageAspectFlags, ImageAspectFlagsBuilder, ImageLayout, ImageSubresourceLayers,
+    ImageSubresourceRange, ImageUsage, MemoryPropertyFlagsBuilder, Pod,
+};
+use crate::sync::{
+    Access, AccessFlags, BufferMemoryBarrier, DependencyInfo, Fence, ImageMemoryBarrier,
+    PipelineStage,
+};
+
+/// Alignment of every upload within the staging buffer. Image copies need offsets that are
+/// multiples of the texel block size of the format and of 4, and 96 is a multiple of all
+/// texel block sizes of non-planar formats.
+const STAGING_ALIGNMENT: usize = 96;
+
+/// Collects uploads to buffers and images and submits them in one batch.
+pub struct Uploader<'a> {
+    device: &'a Device<'a>,
+    queue: &'a Queue<'a>,
+    dst_queue_family_index: u32,
+    data: Vec<u8>,
+    buffer_copies: Vec<BufferUpload<'a>>,
+    image_copies: Vec<ImageCopy<'a>>,
+    image_layouts: Vec<(&'a Image<'a>, SubresourceLayouts)>,
+}
+
+/// Final layouts of the uploaded subresources of one image, kept as disjoint ranges so that
+/// every subresource is transitioned by exactly one barrier.
+#[derive(Debug, Clone, Default)]
+pub struct SubresourceLayouts {
+    entries: Vec<LayerRun>,
+}
+
+/// Array layers `base_array_layer..end_array_layer` of one aspect and mip level.
+#[derive(Debug, Clone, Copy)]
+struct LayerRun {
+    aspect: u32,
+    mip_level: u32,
+    base_array_layer: u32,
+    end_array_layer: u32,
+    layout: ImageLayout,
+}
+
+/// Tightly packed texel data of a region of one mip level and a range of array layers of an
+/// image, uploaded with [`Uploader::upload_image`].
+#[derive(Debug, Clone, Copy)]
+pub struct ImageUploadRegion<'d> {
+    data: &'d [u8],
+    aspect_mask: ImageAspectFlags,
+    mip_level: u32,
+    base_array_layer: u32,
+    layer_count: u32,
+    offset: Offset3D,
+    extent: Extent3D,
+}
+
+/// Pending uploads of an [`Uploader::submit`]. Keeps the staging buffer alive until the copies
+/// have completed and waits for them when dropped.
+pub struct UploadToken<'a> {
+    fence: Fence<'a>,
+    src_queue_family_index: u32,
+    dst_queue_family_index: u32,
+    transfers: Vec<Transfer<'a>>,
+    _command_pool: Option<CommandPool<'a>>,
+    _staging: Option<(Buffer<'a>, DeviceMemory<'a>)>,
+}
+
+struct BufferUpload<'a> {
+    buffer: &'a Buffer<'a>,
+    staging_offset: u64,
+    offset: u64,
+    size: u64,
+}
+
+struct ImageCopy<'a> {
+    image: &'a Image<'a>,
+    region: BufferImageCopy,
+}
+
+/// Resource whose visibility, layout or ownership is changed after the copies.
+enum Transfer<'a> {
+    Buffer {
+        buffer: &'a Buffer<'a>,
+        offset: u64,
+        size: u64,
+    },
+    Image {
+        image: &'a Image<'a>,
+        subresource_range: ImageSubresourceRange,
+        final_layout: ImageLayout,
+    },
+}
+
+impl<'a> Uploader<'a> {
+    /// Uploader submitting to `queue`, whose family has to support transfers, for resources
+    /// used on queues of `dst_queue_family_index`.
+    pub fn new(device: &'a Device<'a>, queue: &'a Queue<'a>, dst_queue_family_index: u32) -> Self {
+        Self {
+            device,
+            queue,
+            dst_queue_family_index,
+            data: Vec::new(),
+            buffer_copies: Vec::new(),
+            image_copies: Vec::new(),
+            image_layouts: Vec::new(),
+        }
+    }
+
+    /// Number of bytes staged so far, including alignment padding.
+    pub fn staged_size(&self) -> u64 {
+        self.data.len() as u64
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buffer_copies.is_empty() && self.image_copies.is_empty()
+    }
+
+    /// Uploads `data` to `buffer`, starting `offset` bytes into it. The buffer needs the
+    /// [`TransferDst`](crate::resource::BufferUsage::TransferDst) usage.
+    ///
+    /// # Errors
+    /// - [`MissingBufferUsage`](Error::MissingBufferUsage) if the buffer lacks the
+    ///   [`TransferDst`](crate::resource::BufferUsage::TransferDst) usage.
+    /// - [`MemoryRangeOutOfBounds`](Error::MemoryRangeOutOfBounds) if `data` is empty or does
+    ///   not fit in the buffer after `offset`.
+    pub fn upload_buffer<T: Pod>(
+        &mut self,
+        buffer: &'a Buffer<'a>,
+        offset: u64,
+        data: &[T],
+    ) -> Result<()> {
+        if !buffer.usage().contains(BufferUsage::TransferDst) {
+            return Err(Error::MissingBufferUsage(BufferUsage::TransferDst));
+        }
+        let size = std::mem::size_of_val(data) as u64;
+        if size == 0
+            || offset
+                .checked_add(size)
+                .is_none_or(|end| end > buffer.size())
+        {
+            return Err(Error::MemoryRangeOutOfBounds { offset, size });
+        }
+
+        let bytes =
+            unsafe { std::slice::from_raw_parts(data.as_ptr().cast::<u8>(), size as usize) };
+        let staging_offset = self.stage(bytes);
+        self.buffer_copies.push(BufferUpload {
+            buffer,
+            staging_offset,
+            offset,
+            size,
+        });
+        Ok(())
+    }
+
+    /// Uploads `regions` to `image` and transitions their subresources to `final_layout`. The
+    /// image needs the [`TransferDst`](crate::resource::ImageUsage::TransferDst) usage. Previous
+    /// contents of the uploaded subresources are discarded.
+    ///
+    /// # Errors
+    /// - [`MissingImageUsage`](Error::MissingImageUsage) if the image lacks the
+    ///   [`TransferDst`](crate::resource::ImageUsage::TransferDst) usage.
+    /// - [`MemoryRangeOutOfBounds`](Error::MemoryRangeOutOfBounds) if a region has no data.
+    /// - [`ImageSubresourceOutOfRange`](Error::ImageSubresourceOutOfRange) if the mip level or
+    ///   array layers of a region are not in the image.
+    /// - [`InvalidImageExtent`](Error::InvalidImageExtent) if a region is empty or extends past
+    ///   its mip level.
+    /// - [`FormatNotSupported`](Error::FormatNotSupported) if the size of the data cannot be
+    ///   computed for the format and aspect of a region.
+    /// - [`ImageDataSizeMismatch`](Error::ImageDataSizeMismatch) if the data of a region is not
+    ///   the [`required_size`](ImageUploadRegion::required_size) of the region.
+    /// - [`ConflictingImageLayouts`](Error::ConflictingImageLayouts) if a subresource was
+    ///   already uploaded with another final layout.
+    pub fn upload_image(
+        &mut self,
+        image: &'a Image<'a>,
+        regions: &[ImageUploadRegion],
+        final_layout: ImageLayout,
+    ) -> Result<()> {
+        if !image.usage().contains(ImageUsage::TransferDst) {
+            return Err(Error::MissingImageUsage(ImageUsage::TransferDst));
+        }
+        for region in regions {
+            region.validate(image)?;
+        }
+        let index = self
+            .image_layouts
+            .iter()
+            .position(|(uploaded, _)| std::ptr::eq(*uploaded, image))
+            .unwrap_or_else(|| {
+                self.image_layouts.push((image, SubresourceLayouts::new()));
+                self.image_layouts.len() - 1
+            });
+        let mut layouts = self.image_layouts[index].1.clone();
+        for region in regions {
+            layouts.insert(region.subresource_range(), final_layout)?;
+        }
+        self.image_layouts[index].1 = layouts;
+
+        for region in regions {
+            let staging_offset = self.stage(region.data);
+            self.image_copies.push(ImageCopy {
+                image,
+                region: BufferImageCopy::new(
+                    staging_offset,
+                    ImageSubresourceLayers::new(
+                        region.aspect_mask,
+                        region.mip_level,
+                        region.base_array_layer,
+                        region.layer_count,
+                    ),
+                    region.offset,
+                    region.extent,
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    /// Copies the staged data into a staging buffer and submits all uploads in one command
+    /// buffer.
+    ///
+    /// # Errors
+    /// - [`NoSuitableMemoryType`](Error::NoSuitableMemoryType) if no memory type is host
+    ///   visible and host coherent.
+    ///
+    /// Driver errors of creating the staging buffer and fence, recording and submitting.
+    pub fn submit(self) -> Result<UploadToken<'a>> {
+        let src_queue_family_index = self.queue.family_index();
+        let dst_queue_family_index = self.dst_queue_family_index;
+
+        if self.is_empty() {
+            return Ok(UploadToken {
+                fence: self.device.create_fence(true)?,
+                src_queue_family_index,
+                dst_queue_family_index,
+                transfers: Vec::new(),
+                _command_pool: None,
+                _staging: None,
+            });
+        }
+
+        let (staging_buffer, staging_memory) = self.create_staging_buffer()?;
+
+        let mut transfers: Vec<Transfer<'a>> = Vec::new();
+        for upload in &self.buffer_copies {
+            transfers.push(Transfer::Buffer {
+                buffer: upload.buffer,
+                offset: upload.offset,
+                size: upload.size,
+            });
+        }
+        for (image, layouts) in &self.image_layouts {
+            for (subresource_range, final_layout) in layouts.ranges() {
+                transfers.push(Transfer::Image {
+                    image,
+                    subresource_range,
+                    final_layout,
+                });
+            }
+        }
+
+        let command_pool = self
+            .device
+            .create_command_pool(src_queue_family_index as usize);
+        let fence = self.device.create_fence(false)?;
+        {
+            let mut command_buffer = command_pool
+                .allocate_command_buffers(1)
+                .pop()
+                .expect("one command buffer was allocated");
+            command_buffer.begin(
+                CommandBufferUsageFlagsBuilder::new()
+                    .one_time_submit(true)
+                    .build(),
+            )?;
+
+            let to_transfer_dst =
+                transfers
+                    .iter()
+                    .fold(
+                        DependencyInfo::new(),
+                        |dependency_info, transfer| match transfer {
+                            Transfer::Image {
+                                image,
+                                subresource_range,
+                                ..
+                            } => dependency_info.with_image_barrier(
+                                ImageMemoryBarrier::new(
+                                    image,
+                                    ImageLayout::Undefined,
+                                    ImageLayout::TransferDstOptimal,
+                                )
+                                .with_subresource_range(*subresource_range),
+                            ),
+                            Transfer::Buffer { .. } => dependency_info,
+                        },
+                    );
+            if !to_transfer_dst.is_empty() {
+                command_buffer.pipeline_barrier2(&to_transfer_dst);
+            }
+
+            for upload in &self.buffer_copies {
+                command_buffer.copy_buffer(
+                    &staging_buffer,
+                    upload.buffer,
+                    &[BufferCopy::new(
+                        upload.staging_offset,
+                        upload.offset,
+                        upload.size,
+                    )],
+                )?;
+            }
+            for copy in &self.image_copies {
+                command_buffer.copy_buffer_to_image(
+                    &staging_buffer,
+                    copy.image,
+                    ImageLayout::TransferDstOptimal,
+                    &[copy.region],
+                );
+            }
+
+            command_buffer.pipeline_barrier2(&transfer_dependency(
+                &transfers,
+                (src_queue_family_index, dst_queue_family_index),
+                false,
+            ));
+
+            command_buffer.end()?;
+            self.queue.submit2(
+                &[SubmitInfo::new().with_command_buffer(&command_buffer)],
+                Some(&fence),
+            )?;
+        }
+
+        Ok(UploadToken {
+            fence,
+            src_queue_family_index,
+            dst_queue_family_index,
+            transfers,
+            _command_pool: Some(command_pool),
+            _staging: Some((staging_buffer, staging_memory)),
+        })
+    }
+
+    /// Appends `bytes` to the staged data and returns their offset in the staging buffer.
+    fn stage(&mut self, bytes: &[u8]) -> u64 {
+        let offset = self.data.len().next_multiple_of(STAGING_ALIGNMENT);
+        self.data.resize(offset, 0);
+        self.data.extend_from_slice(bytes);
+        offset as u64
+    }
+
+    fn create_staging_buffer(&self) -> Result<(Buffer<'a>, DeviceMemory<'a>)> {
+        let size = self.data.len() as u64;
+        let mut buffer = BufferBuilder::new(
+            size,
+            BufferUsageFlagsBuilder::new().transfer_src(true).build(),
+        )
+        .build(self.device)?;
+
+        let requirements = buffer.memory_requirements();
+        let memory_type_index = self
+            .device
+            .memory_properties()
+            .find_memory_type(
+                requirements.memory_type_bits(),
+                MemoryPropertyFlagsBuilder::new()
+                    .host_visible(true)
+                    .host_coherent(true)
+                    .build(),
+            )
+            .ok_or(Error::NoSuitableMemoryType)?;
+
+        let mut memory = self
+            .device
+            .allocate_memory(requirements.size(), memory_type_index)?;
+        memory.map(0, size)?.write(0, &self.data)?;
+        // SAFETY: The memory is returned with the buffer, which is dropped before it.
+        unsafe { buffer.bind_memory(&memory, 0)? };
+
+        Ok((buffer, memory))
+    }
+}
+
+impl<'a> std::fmt::Debug for Uploader<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Uploader")
+            .field("queue", self.queue)
+            .field("dst_queue_family_index", &self.dst_queue_family_index)
+            .field("staged_size", &self.data.len())
+            .field("buffer_uploads", &self.buffer_copies.len())
+            .field("image_uploads", &self.image_copies.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'d> ImageUploadRegion<'d> {
+    /// Region of the color aspect of mip level 0 and array layer 0 starting at the origin.
+    pub fn new(data: &'d [u8], extent: Extent3D) -> Self {
+        Self {
+            data,
+            aspect_mask: ImageAspectFlagsBuilder::new().color(true).build(),
+            mip_level: 0,
+            base_array_layer: 0,
+            layer_count: 1,
+            offset: Offset3D::default(),
+            extent,
+        }
+    }
+
+    pub fn with_aspect_mask(mut self, aspect_mask: ImageAspectFlags) -> Self {
+        self.aspect_mask = aspect_mask;
+        self
+    }
+
+    pub fn with_mip_level(mut self, mip_level: u32) -> Self {
+        self.mip_level = mip_level;
+        self
+    }
+
+    /// Array layers the data is uploaded to, one after the other in `data`.
+    pub fn with_array_layers(mut self, base_array_layer: u32, layer_count: u32) -> Self {
+        self.base_array_layer = base_array_layer;
+        self.layer_count = layer_count;
+        self
+    }
+
+    pub fn with_offset(mut self, offset: Offset3D) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Size in bytes of the tightly packed data of the region in an image of `format`, or
+    /// [`None`] if it cannot be computed for the format and aspect or does not fit in a `u64`.
+    /// Depth or stencil aspects copied alone take the size of that aspect.
+    pub fn required_size(&self, format: Format) -> Option<u64> {
+        let depth = ImageAspectFlagsBuilder::new().depth(true).build();
+        let stencil = ImageAspectFlagsBuilder::new().stencil(true).build();
+        let block_size = if self.aspect_mask == stencil && format.has_stencil() {
+            1
+        } else if self.aspect_mask == depth && format.has_depth() {
+            match format {
+                Format::D16Unorm | Format::D16UnormS8Uint => 2,
+                _ => 4,
+            }
+        } else if format.has_depth() && format.has_stencil() {
+            // Both aspects of a combined format cannot be copied together.
+            return None;
+        } else {
+            format.texel_block_size()?
+        };
+
+        let (block_width, block_height) = format.texel_block_extent();
+        let blocks_wide = self.extent.width().div_ceil(block_width) as u64;
+        let blocks_high = self.extent.height().div_ceil(block_height) as u64;
+        blocks_wide
+            .checked_mul(blocks_high)?
+            .checked_mul(self.extent.depth() as u64)?
+            .checked_mul(self.layer_count as u64)?
+            .checked_mul(block_size as u64)
+    }
+
+    fn subresource_range(&self) -> ImageSubresourceRange {
+        ImageSubresourceRange::new(
+            self.aspect_mask,
+            self.mip_level,
+            1,
+            self.base_array_layer,
+            self.layer_count,
+        )
+    }
+
+    fn validate(&self, image: &Image) -> Result<()> {
+        if self.data.is_empty() {
+            return Err(Error::MemoryRangeOutOfBounds { offset: 0, size: 0 });
+        }
+
+        let layers_in_range = self
+            .base_array_layer
+            .checked_add(self.layer_count)
+            .is_some_and(|end| end <= image.array_layers());
+        if self.mip_level >= image.mip_levels() || self.layer_count == 0 || !layers_in_range {
+            return Err(Error::ImageSubresourceOutOfRange {
+                mip_level: self.mip_level,
+                base_array_layer: self.base_array_layer,
+                layer_count: self.layer_count,
+            });
+        }
+
+        let image_extent = image.extent();
+        let mip_extent = |dimension: u32| (dimension >> self.mip_level).max(1) as i64;
+        let in_mip = |offset: i32, size: u32, dimension: u32| {
+            size != 0 && offset >= 0 && offset as i64 + size as i64 <= mip_extent(dimension)
+        };
+        if !in_mip(self.offset.x(), self.extent.width(), image_extent.width())
+            || !in_mip(self.offset.y(), self.extent.height(), image_extent.height())
+            || !in_mip(self.offset.z(), self.extent.depth(), image_extent.depth())
+        {
+            return Err(Error::InvalidImageExtent(self.extent));
+        }
+
+        let expected = self
+            .required_size(image.format())
+            .ok_or(Error::FormatNotSupported)?;
+        if self.data.len() as u64 != expected {
+            return Err(Error::ImageDataSizeMismatch {
+                size: self.data.len() as u64,
+                expected,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl SubresourceLayouts {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Records that the subresources of `range` end up in `layout`. Ranges overlapping or
+    /// adjoining ones with the same layout are merged with them.
+    ///
+    /// # Errors
+    /// - [`ConflictingImageLayouts`](Error::ConflictingImageLayouts) if a subresource of
+    ///   `range` was recorded with another layout. Nothing is recorded then.
+    pub fn insert(&mut self, range: ImageSubresourceRange, layout: ImageLayout) -> Result<()> {
+        let mut entries = self.entries.clone();
+        let aspects = (0..u32::BITS)
+            .map(|bit| 1 << bit)
+            .filter(|aspect| range.aspect_mask.0 & aspect != 0);
+        for aspect in aspects {
+            let mip_end = range.base_mip_level.saturating_add(range.level_count);
+            for mip_level in range.base_mip_level..mip_end {
+                let mut base = range.base_array_layer;
+                let mut end = base.saturating_add(range.layer_count);
+                let same_levels =
+                    |entry: &LayerRun| entry.aspect == aspect && entry.mip_level == mip_level;
+
+                if let Some(conflict) = entries.iter().find(|entry| {
+                    same_levels(entry)
+                        && entry.base_array_layer < end
+                        && base < entry.end_array_layer
+                        && entry.layout != layout
+                }) {
+                    return Err(Error::ConflictingImageLayouts {
+                        mip_level,
+                        array_layer: base.max(conflict.base_array_layer),
+                    });
+                }
+
+                entries.retain(|entry| {
+                    let merge = same_levels(entry)
+                        && entry.base_array_layer <= end
+                        && base <= entry.end_array_layer
+                        && entry.layout == layout;
+                    if merge {
+                        base = base.min(entry.base_array_layer);
+                        end = end.max(entry.end_array_layer);
+                    }
+                    !merge
+                });
+                entries.push(LayerRun {
+                    aspect,
+                    mip_level,
+                    base_array_layer: base,
+                    end_array_layer: end,
+                    layout,
+                });
+            }
+        }
+
+        self.entries = entries;
+        Ok(())
+    }
+
+    /// Disjoint ranges covering the recorded subresources with their layouts, ordered by mip
+    /// level and array layer. Aspects of the same layers and layout share a range.
+    pub fn ranges(&self) -> Vec<(ImageSubresourceRange, ImageLayout)> {
+        let mut runs: Vec<LayerRun> = Vec::new();
+        for entry in &self.entries {
+            match runs.iter_mut().find(|run| {
+                run.mip_level == entry.mip_level
+                    && run.base_array_layer == entry.base_array_layer
+                    && run.end_array_layer == entry.end_array_layer
+                    && run.layout == entry.layout
+            }) {
+                Some(run) => run.aspect |= entry.aspect,
+                None => runs.push(*entry),
+            }
+        }
+        runs.sort_by_key(|run| (run.mip_level, run.base_array_layer, run.aspect));
+
+        runs.into_iter()
+            .map(|run| {
+                (
+                    ImageSubresourceRange::new(
+                        ImageAspectFlags(run.aspect),
+                        run.mip_level,
+                        1,
+                        run.base_array_layer,
+                        run.end_array_layer - run.base_array_layer,
+                    ),
+                    run.layout,
+                )
+            })
+            .collect()
+    }
+}
+
+impl<'a> UploadToken<'a> {
+    /// Waits up to `timeout` nanoseconds for the uploads to complete. Returns `false` if the
+    /// timeout expired first.
+    ///
+    /// # Errors
+    /// The errors of [`Fence::wait`].
+    pub fn wait(&self, timeout: u64) -> Result<bool> {
+        self.fence.wait(timeout)
+    }
+
+    /// Returns `true` if the uploads have completed, without waiting.
+    ///
+    /// # Errors
+    /// The errors of [`Fence::is_signaled`].
+    pub fn is_complete(&self) -> Result<bool> {
+        self.fence.is_signaled()
+    }
+
+    /// Returns `true` if the uploaded resources were released to another queue family and
+    /// have to be acquired with [`record_acquire`](Self::record_acquire).
+    pub fn needs_acquire(&self) -> bool {
+        self.src_queue_family_index != self.dst_queue_family_index && !self.transfers.is_empty()
+    }
+
+    /// Records the acquisition of the uploaded resources by the destination queue family into
+    /// `command_buffer`, which has to be submitted to a queue of that family after the uploads
+    /// have completed. Does nothing if no ownership transfer is needed.
+    pub fn record_acquire(&self, command_buffer: &mut CommandBuffer) {
+        if !self.needs_acquire() {
+            return;
+        }
+
+        command_buffer.pipeline_barrier2(&transfer_dependency(
+            &self.transfers,
+            (self.src_queue_family_index, self.dst_queue_family_index),
+            true,
+        ));
+    }
+}
+
+impl<'a> std::fmt::Debug for UploadToken<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UploadToken")
+            .field("fence", &self.fence)
+            .field("src_queue_family_index", &self.src_queue_family_index)
+            .field("dst_queue_family_index", &self.dst_queue_family_index)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> Drop for UploadToken<'a> {
+    fn drop(&mut self) {
+        // The staging buffer and command buffer must outlive the copies.
+        let _ = self.fence.wait(u64::MAX);
+    }
+}
+
+/// Barriers of `transfers` after the copies, which transition images to their final layout.
+/// Without an ownership transfer they make the writes visible to all later commands, otherwise
+/// they are the release or, with `acquire`, the acquire half of the transfer.
+fn transfer_dependency<'r>(
+    transfers: &[Transfer<'r>],
+    (src_queue_family_index, dst_queue_family_index): (u32, u32),
+    acquire: bool,
+) -> DependencyInfo<'r> {
+    let ownership_transfer = src_queue_family_index != dst_queue_family_index;
+    let all_access = AccessFlags::new()
+        .with_access(Access::MemoryRead)
+        .with_access(Access::MemoryWrite);
+
+    transfers.iter().fold(
+        DependencyInfo::new(),
+        |dependency_info, transfer| match transfer {
+            Transfer::Buffer {
+                buffer,
+                offset,
+                size,
+            } => {
+                let (src_stage, src_access, dst_stage, dst_access) =
+                    match (ownership_transfer, acquire) {
+                        (true, true) => (
+                            PipelineStage::TopOfPipe,
+                            AccessFlags::new(),
+                            PipelineStage::AllCommands,
+                            all_access,
+                        ),
+                        (true, false) => (
+                            PipelineStage::Transfer,
+                            Access::TransferWrite.into(),
+                            PipelineStage::BottomOfPipe,
+                            AccessFlags::new(),
+                        ),
+                        (false, _) => (
+                            PipelineStage::Transfer,
+                            Access::TransferWrite.into(),
+                            PipelineStage::AllCommands,
+                            all_access,
+                        ),
+                    };
+                let mut barrier = BufferMemoryBarrier::new(
+                    buffer,
+                    src_stage.into(),
+                    src_access,
+                    dst_stage.into(),
+                    dst_access,
+                )
+                .with_range(*offset, *size);
+                if ownership_transfer {
+                    barrier = barrier
+                        .with_queue_family_transfer(src_queue_family_index, dst_queue_family_index);
+                }
+
+                dependency_info.with_buffer_barrier(barrier)
+            }
+            Transfer::Image {
+                image,
+                subresource_range,
+                final_layout,
+            } => {
+                let mut barrier =
+                    ImageMemoryBarrier::new(image, ImageLayout::TransferDstOptimal, *final_layout)
+                        .with_subresource_range(*subresource_range);
+                if ownership_transfer {
+                    barrier = if acquire {
+                        barrier.with_src(PipelineStage::TopOfPipe.into(), AccessFlags::new())
+                    } else {
+                        barrier.with_dst(PipelineStage::BottomOfPipe.into(), AccessFlags::new())
+                    }
+                    .with_queue_family_transfer(src_queue_family_index, dst_queue_family_index);
+                }
+
+                dependency_info.with_image_barrier(barrier)
+            }
+        },
+    )
+}
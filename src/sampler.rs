@@ -0,0 +1,456 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+#[cfg(not(target_pointer_width = "64"))]
+use std::num::NonZeroU64;
+#[cfg(target_pointer_width = "64")]
+use std::ptr::NonNull;
+use std::rc::Rc;
+
+use crate::core::{Error, Result};
+use crate::device::Device;
+use crate::ffi;
+use crate::pipeline::CompareOp;
+
+/// Value of [`SamplerBuilder::with_lod_range`] that does not clamp the maximum LOD.
+pub const LOD_CLAMP_NONE: f32 = 1000.0;
+
+pub struct Sampler<'a> {
+    #[cfg(target_pointer_width = "64")]
+    pub(crate) handle: NonNull<ffi::VkSampler_T>,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub(crate) handle: NonZeroU64,
+    pub(crate) device: &'a Device<'a>,
+    #[cfg(target_pointer_width = "64")]
+    pub(crate) _marker: PhantomData<ffi::VkSampler_T>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Filter {
+    Nearest,
+    Linear,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SamplerMipmapMode {
+    Nearest,
+    Linear,
+}
+
+/// Handling of texture coordinates outside of the image.
+///
+/// [`MirrorClampToEdge`](Self::MirrorClampToEdge) requires the
+/// [`sampler_mirror_clamp_to_edge`](crate::device::PhysicalDeviceFeatures::sampler_mirror_clamp_to_edge)
+/// feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SamplerAddressMode {
+    Repeat,
+    MirroredRepeat,
+    ClampToEdge,
+    ClampToBorder,
+    MirrorClampToEdge,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BorderColor {
+    FloatTransparentBlack,
+    IntTransparentBlack,
+    FloatOpaqueBlack,
+    IntOpaqueBlack,
+    FloatOpaqueWhite,
+    IntOpaqueWhite,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SamplerBuilder {
+    mag_filter: Filter,
+    min_filter: Filter,
+    mipmap_mode: SamplerMipmapMode,
+    address_mode_u: SamplerAddressMode,
+    address_mode_v: SamplerAddressMode,
+    address_mode_w: SamplerAddressMode,
+    mip_lod_bias: f32,
+    max_anisotropy: Option<f32>,
+    compare_op: Option<CompareOp>,
+    min_lod: f32,
+    max_lod: f32,
+    border_color: BorderColor,
+    unnormalized_coordinates: bool,
+}
+
+/// Deduplicates samplers of a [`Device`], since the number of samplers that can exist at
+/// the same time is limited by `maxSamplerAllocationCount`.
+pub struct SamplerCache<'a> {
+    device: &'a Device<'a>,
+    samplers: HashMap<SamplerKey, Rc<Sampler<'a>>>,
+}
+
+/// [`SamplerBuilder`] with the floating point parameters compared bitwise.
+#[derive(PartialEq, Eq, Hash)]
+struct SamplerKey {
+    mag_filter: Filter,
+    min_filter: Filter,
+    mipmap_mode: SamplerMipmapMode,
+    address_modes: [SamplerAddressMode; 3],
+    mip_lod_bias: u32,
+    max_anisotropy: Option<u32>,
+    compare_op: Option<CompareOp>,
+    min_lod: u32,
+    max_lod: u32,
+    border_color: BorderColor,
+    unnormalized_coordinates: bool,
+}
+
+impl<'a> Drop for Sampler<'a> {
+    fn drop(&mut self) {
+        println!("Dropped Sampler");
+        unsafe {
+            (self.device.dispatch_loader.vk_destroy_sampler)(
+                self.device.handle.as_ptr(),
+                #[cfg(target_pointer_width = "64")]
+                self.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                self.handle.get(),
+                std::ptr::null(),
+            );
+        }
+    }
+}
+
+impl<'a> std::fmt::Debug for Sampler<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Sampler")
+            .field("handle", &self.handle)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for SamplerBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SamplerBuilder {
+    /// Trilinear filtering with repeating address modes and no LOD clamping.
+    pub fn new() -> Self {
+        Self {
+            mag_filter: Filter::Linear,
+            min_filter: Filter::Linear,
+            mipmap_mode: SamplerMipmapMode::Linear,
+            address_mode_u: SamplerAddressMode::Repeat,
+            address_mode_v: SamplerAddressMode::Repeat,
+            address_mode_w: SamplerAddressMode::Repeat,
+            mip_lod_bias: 0.0,
+            max_anisotropy: None,
+            compare_op: None,
+            min_lod: 0.0,
+            max_lod: LOD_CLAMP_NONE,
+            border_color: BorderColor::FloatTransparentBlack,
+            unnormalized_coordinates: false,
+        }
+    }
+
+    pub fn with_filters(&mut self, mag_filter: Filter, min_filter: Filter) -> &mut Self {
+        self.mag_filter = mag_filter;
+        self.min_filter = min_filter;
+        self
+    }
+
+    pub fn with_mipmap_mode(&mut self, mipmap_mode: SamplerMipmapMode) -> &mut Self {
+        self.mipmap_mode = mipmap_mode;
+        self
+    }
+
+    /// Uses `address_mode` for all three texture coordinates.
+    pub fn with_address_mode(&mut self, address_mode: SamplerAddressMode) -> &mut Self {
+        self.with_address_modes(address_mode, address_mode, address_mode)
+    }
+
+    pub fn with_address_modes(
+        &mut self,
+        u: SamplerAddressMode,
+        v: SamplerAddressMode,
+        w: SamplerAddressMode,
+    ) -> &mut Self {
+        self.address_mode_u = u;
+        self.address_mode_v = v;
+        self.address_mode_w = w;
+        self
+    }
+
+    pub fn with_mip_lod_bias(&mut self, mip_lod_bias: f32) -> &mut Self {
+        self.mip_lod_bias = mip_lod_bias;
+        self
+    }
+
+    pub fn with_lod_range(&mut self, min_lod: f32, max_lod: f32) -> &mut Self {
+        self.min_lod = min_lod;
+        self.max_lod = max_lod;
+        self
+    }
+
+    /// Enables anisotropic filtering, which requires the `samplerAnisotropy` feature.
+    pub fn with_anisotropy(&mut self, max_anisotropy: f32) -> &mut Self {
+        self.max_anisotropy = Some(max_anisotropy);
+        self
+    }
+
+    /// Enables comparison of fetched texels against a reference value, as used for shadow
+    /// maps.
+    pub fn with_compare_op(&mut self, compare_op: CompareOp) -> &mut Self {
+        self.compare_op = Some(compare_op);
+        self
+    }
+
+    pub fn with_border_color(&mut self, border_color: BorderColor) -> &mut Self {
+        self.border_color = border_color;
+        self
+    }
+
+    /// Addresses texels by their coordinates instead of the `[0, 1]` range.
+    pub fn with_unnormalized_coordinates(&mut self, unnormalized_coordinates: bool) -> &mut Self {
+        self.unnormalized_coordinates = unnormalized_coordinates;
+        self
+    }
+
+    /// # Errors
+    /// - [`FeatureNotEnabled`](Error::FeatureNotEnabled) if anisotropy is used without the
+    ///   `samplerAnisotropy` feature, or an address mode is
+    ///   [`MirrorClampToEdge`](SamplerAddressMode::MirrorClampToEdge) without the
+    ///   `samplerMirrorClampToEdge` feature.
+    /// - [`InvalidSamplerParameter`](Error::InvalidSamplerParameter) if the anisotropy is not
+    ///   between 1 and `maxSamplerAnisotropy`, the LOD bias is above `maxSamplerLodBias`, the
+    ///   LOD range is empty, or unnormalized coordinates are used with filtering, mipmapping,
+    ///   repeating address modes, anisotropy or comparison.
+    ///
+    /// Driver errors:
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    pub fn build<'a>(&self, device: &'a Device) -> Result<Sampler<'a>> {
+        self.validate(device)?;
+
+        let create_info = ffi::SamplerCreateInfo {
+            s_type: ffi::StructureType::SamplerCreateInfo,
+            p_next: std::ptr::null(),
+            flags: 0,
+            mag_filter: self.mag_filter.into(),
+            min_filter: self.min_filter.into(),
+            mipmap_mode: self.mipmap_mode.into(),
+            address_mode_u: self.address_mode_u.into(),
+            address_mode_v: self.address_mode_v.into(),
+            address_mode_w: self.address_mode_w.into(),
+            mip_lod_bias: self.mip_lod_bias,
+            anisotropy_enable: self.max_anisotropy.is_some().into(),
+            max_anisotropy: self.max_anisotropy.unwrap_or(1.0),
+            compare_enable: self.compare_op.is_some().into(),
+            compare_op: self.compare_op.unwrap_or(CompareOp::Never).into(),
+            min_lod: self.min_lod,
+            max_lod: self.max_lod,
+            border_color: self.border_color.into(),
+            unnormalized_coordinates: self.unnormalized_coordinates.into(),
+        };
+
+        let mut p_sampler = MaybeUninit::uninit();
+
+        let result = unsafe {
+            (device.dispatch_loader.vk_create_sampler)(
+                device.handle.as_ptr(),
+                &create_info,
+                std::ptr::null(),
+                p_sampler.as_mut_ptr(),
+            )
+        };
+
+        match result {
+            ffi::Result::Success => Ok(Sampler {
+                #[cfg(target_pointer_width = "64")]
+                handle: unsafe { NonNull::new_unchecked(p_sampler.assume_init()) },
+                #[cfg(not(target_pointer_width = "64"))]
+                handle: unsafe { NonZeroU64::new_unchecked(p_sampler.assume_init()) },
+                device,
+                #[cfg(target_pointer_width = "64")]
+                _marker: PhantomData,
+            }),
+            ffi::Result::ErrorOutOfHostMemory => Err(Error::OutOfHostMemory),
+            ffi::Result::ErrorOutOfDeviceMemory => Err(Error::OutOfDeviceMemory),
+            _ => unreachable!(),
+        }
+    }
+
+    fn validate(&self, device: &Device) -> Result<()> {
+        let limits = device.limits();
+
+        if let Some(max_anisotropy) = self.max_anisotropy {
+            if !device.enabled_features().sampler_anisotropy {
+                return Err(Error::FeatureNotEnabled("samplerAnisotropy"));
+            }
+            if !(1.0..=limits.max_sampler_anisotropy).contains(&max_anisotropy) {
+                return Err(Error::InvalidSamplerParameter("maxAnisotropy"));
+            }
+        }
+        let address_modes = [
+            self.address_mode_u,
+            self.address_mode_v,
+            self.address_mode_w,
+        ];
+        if address_modes.contains(&SamplerAddressMode::MirrorClampToEdge)
+            && !device.enabled_features().sampler_mirror_clamp_to_edge
+        {
+            return Err(Error::FeatureNotEnabled("samplerMirrorClampToEdge"));
+        }
+        let max_lod_bias = limits.max_sampler_lod_bias;
+        if !(-max_lod_bias..=max_lod_bias).contains(&self.mip_lod_bias) {
+            return Err(Error::InvalidSamplerParameter("mipLodBias"));
+        }
+        if self.min_lod.is_nan() || self.max_lod.is_nan() || self.min_lod > self.max_lod {
+            return Err(Error::InvalidSamplerParameter("minLod"));
+        }
+
+        let clamped = |address_mode| {
+            matches!(
+                address_mode,
+                SamplerAddressMode::ClampToEdge | SamplerAddressMode::ClampToBorder
+            )
+        };
+        if self.unnormalized_coordinates
+            && (self.mag_filter != self.min_filter
+                || self.mipmap_mode != SamplerMipmapMode::Nearest
+                || self.min_lod != 0.0
+                || self.max_lod != 0.0
+                || !clamped(self.address_mode_u)
+                || !clamped(self.address_mode_v)
+                || self.max_anisotropy.is_some()
+                || self.compare_op.is_some())
+        {
+            return Err(Error::InvalidSamplerParameter("unnormalizedCoordinates"));
+        }
+
+        Ok(())
+    }
+
+    fn key(&self) -> SamplerKey {
+        SamplerKey {
+            mag_filter: self.mag_filter,
+            min_filter: self.min_filter,
+            mipmap_mode: self.mipmap_mode,
+            address_modes: [
+                self.address_mode_u,
+                self.address_mode_v,
+                self.address_mode_w,
+            ],
+            mip_lod_bias: self.mip_lod_bias.to_bits(),
+            max_anisotropy: self.max_anisotropy.map(f32::to_bits),
+            compare_op: self.compare_op,
+            min_lod: self.min_lod.to_bits(),
+            max_lod: self.max_lod.to_bits(),
+            border_color: self.border_color,
+            unnormalized_coordinates: self.unnormalized_coordinates,
+        }
+    }
+}
+
+impl<'a> SamplerCache<'a> {
+    pub fn new(device: &'a Device<'a>) -> Self {
+        Self {
+            device,
+            samplers: HashMap::new(),
+        }
+    }
+
+    /// Returns the sampler created for an identical description, or creates it. When the
+    /// cache holds `maxSamplerAllocationCount` samplers, the ones no longer used outside the
+    /// cache are destroyed first.
+    ///
+    /// # Errors
+    /// - [`LimitExceeded`](Error::LimitExceeded) if all cached samplers are still in use.
+    /// - The errors of [`SamplerBuilder::build`].
+    pub fn get(&mut self, builder: &SamplerBuilder) -> Result<Rc<Sampler<'a>>> {
+        let key = builder.key();
+        if let Some(sampler) = self.samplers.get(&key) {
+            return Ok(sampler.clone());
+        }
+
+        let max = self.device.limits().max_sampler_allocation_count;
+        if self.samplers.len() >= max as usize {
+            self.purge();
+        }
+        if self.samplers.len() >= max as usize {
+            return Err(Error::LimitExceeded {
+                limit: "maxSamplerAllocationCount",
+                value: self.samplers.len() as u64 + 1,
+                max: max.into(),
+            });
+        }
+
+        let sampler = Rc::new(builder.build(self.device)?);
+        self.samplers.insert(key, sampler.clone());
+
+        Ok(sampler)
+    }
+
+    /// Destroys the cached samplers that are not used outside the cache.
+    pub fn purge(&mut self) {
+        self.samplers
+            .retain(|_, sampler| Rc::strong_count(sampler) > 1);
+    }
+
+    pub fn len(&self) -> usize {
+        self.samplers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samplers.is_empty()
+    }
+}
+
+impl<'a> std::fmt::Debug for SamplerCache<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SamplerCache")
+            .field("len", &self.samplers.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl From<Filter> for ffi::Filter {
+    fn from(filter: Filter) -> Self {
+        match filter {
+            Filter::Nearest => Self::Nearest,
+            Filter::Linear => Self::Linear,
+        }
+    }
+}
+
+impl From<SamplerMipmapMode> for ffi::SamplerMipmapMode {
+    fn from(mipmap_mode: SamplerMipmapMode) -> Self {
+        match mipmap_mode {
+            SamplerMipmapMode::Nearest => Self::Nearest,
+            SamplerMipmapMode::Linear => Self::Linear,
+        }
+    }
+}
+
+impl From<SamplerAddressMode> for ffi::SamplerAddressMode {
+    fn from(address_mode: SamplerAddressMode) -> Self {
+        match address_mode {
+            SamplerAddressMode::Repeat => Self::Repeat,
+            SamplerAddressMode::MirroredRepeat => Self::MirroredRepeat,
+            SamplerAddressMode::ClampToEdge => Self::ClampToEdge,
+            SamplerAddressMode::ClampToBorder => Self::ClampToBorder,
+            SamplerAddressMode::MirrorClampToEdge => Self::MirrorClampToEdge,
+        }
+    }
+}
+
+impl From<BorderColor> for ffi::BorderColor {
+    fn from(border_color: BorderColor) -> Self {
+        match border_color {
+            BorderColor::FloatTransparentBlack => Self::FloatTransparentBlack,
+            BorderColor::IntTransparentBlack => Self::IntTransparentBlack,
+            BorderColor::FloatOpaqueBlack => Self::FloatOpaqueBlack,
+            BorderColor::IntOpaqueBlack => Self::IntOpaqueBlack,
+            BorderColor::FloatOpaqueWhite => Self::FloatOpaqueWhite,
+            BorderColor::IntOpaqueWhite => Self::IntOpaqueWhite,
+        }
+    }
+}
@@ -1,10 +1,16 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::marker::PhantomData;
 #[cfg(not(target_pointer_width = "64"))]
 use std::num::NonZeroU64;
+use std::ops::Range;
 use std::ptr::NonNull;
 
+use crate::core::{Error, Result};
+use crate::descriptor::DescriptorType;
 use crate::device::Device;
 use crate::ffi;
+use crate::pipeline::{ShaderStage, VertexInputAttributeDescription};
 
 pub struct ShaderModule<'a> {
     #[cfg(target_pointer_width = "64")]
@@ -12,10 +18,116 @@ pub struct ShaderModule<'a> {
     #[cfg(not(target_pointer_width = "64"))]
     pub(crate) handle: NonZeroU64,
     pub(crate) device: &'a Device<'a>,
+    pub(crate) reflection: ShaderReflection,
     #[cfg(target_pointer_width = "64")]
     pub(crate) _marker: PhantomData<ffi::VkShaderModule_T>,
 }
 
+/// Malformed SPIR-V found while parsing a shader module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpirvError {
+    /// The code is shorter than the SPIR-V header or not a whole number of words.
+    InvalidLength(usize),
+    /// The first word is not the SPIR-V magic number in either byte order.
+    InvalidMagicNumber(u32),
+    /// The header declares a SPIR-V version other than 1.x.
+    UnsupportedVersion(u32),
+    /// The instruction at the given word offset runs past the end of the code or has a word
+    /// count of zero.
+    TruncatedInstruction(usize),
+    /// The instruction at the given word offset lacks operands or refers to an unknown id.
+    InvalidInstruction { offset: usize, opcode: u16 },
+    /// The size, array length or location count of the type with the given id does not fit
+    /// in 32 bits.
+    TypeOverflow(u32),
+    /// The type with the given id nests deeper than [`MAX_TYPE_DEPTH`] or contains itself.
+    TypeTooDeep(u32),
+}
+
+/// Deepest nesting of arrays, structs, matrices and vectors reflection follows.
+pub const MAX_TYPE_DEPTH: u32 = 64;
+
+/// Interface of a SPIR-V module, as declared by its instructions.
+///
+/// Descriptor bindings, push constants and specialization constants are those declared by
+/// the module, whether or not every entry point uses them.
+#[derive(Debug, Clone, Default)]
+pub struct ShaderReflection {
+    entry_points: Vec<EntryPoint>,
+    descriptor_bindings: Vec<DescriptorBinding>,
+    push_constant_block: Option<PushConstantBlock>,
+    specialization_constants: Vec<SpecializationConstantInfo>,
+}
+
+/// Entry point of a shader module and the stage its execution model runs in.
+#[derive(Debug, Clone)]
+pub struct EntryPoint {
+    name: String,
+    stage: ShaderStage,
+    workgroup_size: Option<[u32; 3]>,
+    inputs: Vec<InterfaceVariable>,
+    outputs: Vec<InterfaceVariable>,
+}
+
+/// Resource variable declared with a descriptor set and binding number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DescriptorBinding {
+    set: u32,
+    binding: u32,
+    descriptor_type: DescriptorType,
+    descriptor_count: u32,
+    name: Option<String>,
+}
+
+/// Layout of the push constant block of a shader module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PushConstantBlock {
+    size: u32,
+    members: Vec<BlockMember>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockMember {
+    offset: u32,
+    size: u32,
+    name: Option<String>,
+}
+
+/// Specialization constant declared with a `SpecId`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpecializationConstantInfo {
+    constant_id: u32,
+    size: usize,
+    name: Option<String>,
+}
+
+/// Input or output variable of an entry point at a location.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InterfaceVariable {
+    location: u32,
+    component: u32,
+    location_count: u32,
+    numeric_type: NumericType,
+    width: u32,
+    component_count: u32,
+    name: Option<String>,
+}
+
+/// Numeric type of the components of an interface variable or format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NumericType {
+    Float,
+    Sint,
+    Uint,
+}
+
+impl<'a> ShaderModule<'a> {
+    /// Interface of the module, parsed from its code at creation.
+    pub fn reflection(&self) -> &ShaderReflection {
+        &self.reflection
+    }
+}
+
 impl<'a> Drop for ShaderModule<'a> {
     fn drop(&mut self) {
         println!("Dropped ShaderModule");
@@ -39,3 +151,1043 @@ impl<'a> std::fmt::Debug for ShaderModule<'a> {
             .finish_non_exhaustive()
     }
 }
+
+const MAGIC_NUMBER: u32 = 0x0723_0203;
+const HEADER_WORDS: usize = 5;
+
+mod op {
+    pub const NAME: u16 = 5;
+    pub const MEMBER_NAME: u16 = 6;
+    pub const ENTRY_POINT: u16 = 15;
+    pub const EXECUTION_MODE: u16 = 16;
+    pub const TYPE_BOOL: u16 = 20;
+    pub const TYPE_INT: u16 = 21;
+    pub const TYPE_FLOAT: u16 = 22;
+    pub const TYPE_VECTOR: u16 = 23;
+    pub const TYPE_MATRIX: u16 = 24;
+    pub const TYPE_IMAGE: u16 = 25;
+    pub const TYPE_SAMPLER: u16 = 26;
+    pub const TYPE_SAMPLED_IMAGE: u16 = 27;
+    pub const TYPE_ARRAY: u16 = 28;
+    pub const TYPE_RUNTIME_ARRAY: u16 = 29;
+    pub const TYPE_STRUCT: u16 = 30;
+    pub const TYPE_POINTER: u16 = 32;
+    pub const CONSTANT_TRUE: u16 = 41;
+    pub const CONSTANT_FALSE: u16 = 42;
+    pub const CONSTANT: u16 = 43;
+    pub const CONSTANT_COMPOSITE: u16 = 44;
+    pub const SPEC_CONSTANT_TRUE: u16 = 48;
+    pub const SPEC_CONSTANT_FALSE: u16 = 49;
+    pub const SPEC_CONSTANT: u16 = 50;
+    pub const SPEC_CONSTANT_COMPOSITE: u16 = 51;
+    pub const VARIABLE: u16 = 59;
+    pub const DECORATE: u16 = 71;
+    pub const MEMBER_DECORATE: u16 = 72;
+    pub const EXECUTION_MODE_ID: u16 = 331;
+    pub const TYPE_ACCELERATION_STRUCTURE: u16 = 5341;
+}
+
+mod decoration {
+    pub const SPEC_ID: u32 = 1;
+    pub const BUFFER_BLOCK: u32 = 3;
+    pub const ROW_MAJOR: u32 = 4;
+    pub const ARRAY_STRIDE: u32 = 6;
+    pub const MATRIX_STRIDE: u32 = 7;
+    pub const BUILT_IN: u32 = 11;
+    pub const PATCH: u32 = 15;
+    pub const LOCATION: u32 = 30;
+    pub const COMPONENT: u32 = 31;
+    pub const BINDING: u32 = 33;
+    pub const DESCRIPTOR_SET: u32 = 34;
+    pub const OFFSET: u32 = 35;
+}
+
+mod storage_class {
+    pub const UNIFORM_CONSTANT: u32 = 0;
+    pub const INPUT: u32 = 1;
+    pub const UNIFORM: u32 = 2;
+    pub const OUTPUT: u32 = 3;
+    pub const PUSH_CONSTANT: u32 = 9;
+    pub const STORAGE_BUFFER: u32 = 12;
+}
+
+const EXECUTION_MODE_LOCAL_SIZE: u32 = 17;
+const EXECUTION_MODE_LOCAL_SIZE_ID: u32 = 38;
+const BUILT_IN_WORKGROUP_SIZE: u32 = 25;
+const DIM_BUFFER: u32 = 5;
+const DIM_SUBPASS_DATA: u32 = 6;
+
+enum Type {
+    Bool,
+    Int { width: u32, signed: bool },
+    Float { width: u32 },
+    Vector { component: u32, count: u32 },
+    Matrix { column: u32, count: u32 },
+    Image { dim: u32, sampled: u32 },
+    Sampler,
+    SampledImage,
+    Array { element: u32, length: u32 },
+    RuntimeArray { element: u32 },
+    Struct { members: Vec<u32> },
+    Pointer { pointee: u32 },
+    AccelerationStructure,
+}
+
+#[derive(Default)]
+struct Decorations {
+    spec_id: Option<u32>,
+    buffer_block: bool,
+    array_stride: Option<u32>,
+    built_in: Option<u32>,
+    patch: bool,
+    location: Option<u32>,
+    component: Option<u32>,
+    binding: Option<u32>,
+    descriptor_set: Option<u32>,
+}
+
+#[derive(Default)]
+struct MemberDecorations {
+    offset: Option<u32>,
+    matrix_stride: Option<u32>,
+    row_major: bool,
+}
+
+/// Result of the first pass over a module, before the interface is resolved.
+#[derive(Default)]
+struct Module {
+    names: HashMap<u32, String>,
+    member_names: HashMap<(u32, u32), String>,
+    entry_points: Vec<(u32, u32, String, Vec<u32>)>,
+    local_sizes: HashMap<u32, [u32; 3]>,
+    local_size_ids: HashMap<u32, [u32; 3]>,
+    types: HashMap<u32, Type>,
+    constants: HashMap<u32, u64>,
+    composites: HashMap<u32, Vec<u32>>,
+    spec_constants: Vec<(u32, u32)>,
+    variables: Vec<(u32, u32, u32)>,
+    decorations: HashMap<u32, Decorations>,
+    member_decorations: HashMap<(u32, u32), MemberDecorations>,
+}
+
+/// Decodes a nul-terminated literal string, returning it and the number of words it takes.
+fn parse_string(words: &[u32]) -> Option<(String, usize)> {
+    let bytes: Vec<u8> = words.iter().flat_map(|word| word.to_le_bytes()).collect();
+    let len = bytes.iter().position(|&byte| byte == 0)?;
+    let string = String::from_utf8_lossy(&bytes[..len]).into_owned();
+    Some((string, len / 4 + 1))
+}
+
+fn stage_of(execution_model: u32) -> Option<ShaderStage> {
+    match execution_model {
+        0 => Some(ShaderStage::Vertex),
+        1 => Some(ShaderStage::TessellationControl),
+        2 => Some(ShaderStage::TessellationEvaluation),
+        3 => Some(ShaderStage::Geometry),
+        4 => Some(ShaderStage::Fragment),
+        5 => Some(ShaderStage::Compute),
+        5267 => Some(ShaderStage::TaskNv),
+        5268 => Some(ShaderStage::MeshNv),
+        5313 => Some(ShaderStage::RaygenKhr),
+        5314 => Some(ShaderStage::IntersectionKhr),
+        5315 => Some(ShaderStage::AnyHitKhr),
+        5316 => Some(ShaderStage::ClosestHitKhr),
+        5317 => Some(ShaderStage::MissKhr),
+        5318 => Some(ShaderStage::CallableKhr),
+        _ => None,
+    }
+}
+
+impl Module {
+    fn parse(words: &[u32]) -> Result<Self> {
+        let mut module = Self::default();
+        let mut offset = HEADER_WORDS;
+
+        while offset < words.len() {
+            let word_count = (words[offset] >> 16) as usize;
+            let opcode = words[offset] as u16;
+            if word_count == 0 || offset + word_count > words.len() {
+                return Err(Error::InvalidSpirv(SpirvError::TruncatedInstruction(
+                    offset,
+                )));
+            }
+
+            module
+                .parse_instruction(opcode, &words[offset + 1..offset + word_count])
+                .ok_or(Error::InvalidSpirv(SpirvError::InvalidInstruction {
+                    offset,
+                    opcode,
+                }))?;
+            offset += word_count;
+        }
+
+        Ok(module)
+    }
+
+    /// Records the instruction, returning [`None`] if operands are missing.
+    fn parse_instruction(&mut self, opcode: u16, operands: &[u32]) -> Option<()> {
+        let operand = |index: usize| operands.get(index).copied();
+
+        match opcode {
+            op::NAME => {
+                let (name, _) = parse_string(operands.get(1..)?)?;
+                self.names.insert(operand(0)?, name);
+            }
+            op::MEMBER_NAME => {
+                let (name, _) = parse_string(operands.get(2..)?)?;
+                self.member_names.insert((operand(0)?, operand(1)?), name);
+            }
+            op::ENTRY_POINT => {
+                let (name, len) = parse_string(operands.get(2..)?)?;
+                let interface = operands.get(2 + len..)?.to_vec();
+                self.entry_points
+                    .push((operand(0)?, operand(1)?, name, interface));
+            }
+            op::EXECUTION_MODE if operand(1)? == EXECUTION_MODE_LOCAL_SIZE => {
+                let size = [operand(2)?, operand(3)?, operand(4)?];
+                self.local_sizes.insert(operand(0)?, size);
+            }
+            op::EXECUTION_MODE_ID if operand(1)? == EXECUTION_MODE_LOCAL_SIZE_ID => {
+                let size = [operand(2)?, operand(3)?, operand(4)?];
+                self.local_size_ids.insert(operand(0)?, size);
+            }
+            op::TYPE_BOOL => {
+                self.types.insert(operand(0)?, Type::Bool);
+            }
+            op::TYPE_INT => {
+                let ty = Type::Int {
+                    width: operand(1)?,
+                    signed: operand(2)? != 0,
+                };
+                self.types.insert(operand(0)?, ty);
+            }
+            op::TYPE_FLOAT => {
+                let ty = Type::Float { width: operand(1)? };
+                self.types.insert(operand(0)?, ty);
+            }
+            op::TYPE_VECTOR => {
+                let ty = Type::Vector {
+                    component: operand(1)?,
+                    count: operand(2)?,
+                };
+                self.types.insert(operand(0)?, ty);
+            }
+            op::TYPE_MATRIX => {
+                let ty = Type::Matrix {
+                    column: operand(1)?,
+                    count: operand(2)?,
+                };
+                self.types.insert(operand(0)?, ty);
+            }
+            op::TYPE_IMAGE => {
+                let ty = Type::Image {
+                    dim: operand(2)?,
+                    sampled: operand(6)?,
+                };
+                self.types.insert(operand(0)?, ty);
+            }
+            op::TYPE_SAMPLER => {
+                self.types.insert(operand(0)?, Type::Sampler);
+            }
+            op::TYPE_SAMPLED_IMAGE => {
+                self.types.insert(operand(0)?, Type::SampledImage);
+            }
+            op::TYPE_ARRAY => {
+                let ty = Type::Array {
+                    element: operand(1)?,
+                    length: operand(2)?,
+                };
+                self.types.insert(operand(0)?, ty);
+            }
+            op::TYPE_RUNTIME_ARRAY => {
+                let ty = Type::RuntimeArray {
+                    element: operand(1)?,
+                };
+                self.types.insert(operand(0)?, ty);
+            }
+            op::TYPE_STRUCT => {
+                let ty = Type::Struct {
+                    members: operands.get(1..)?.to_vec(),
+                };
+                self.types.insert(operand(0)?, ty);
+            }
+            op::TYPE_POINTER => {
+                let ty = Type::Pointer {
+                    pointee: operand(2)?,
+                };
+                self.types.insert(operand(0)?, ty);
+            }
+            op::TYPE_ACCELERATION_STRUCTURE => {
+                self.types.insert(operand(0)?, Type::AccelerationStructure);
+            }
+            op::CONSTANT_TRUE | op::SPEC_CONSTANT_TRUE => {
+                self.constants.insert(operand(1)?, 1);
+                if opcode == op::SPEC_CONSTANT_TRUE {
+                    self.spec_constants.push((operand(0)?, operand(1)?));
+                }
+            }
+            op::CONSTANT_FALSE | op::SPEC_CONSTANT_FALSE => {
+                self.constants.insert(operand(1)?, 0);
+                if opcode == op::SPEC_CONSTANT_FALSE {
+                    self.spec_constants.push((operand(0)?, operand(1)?));
+                }
+            }
+            op::CONSTANT | op::SPEC_CONSTANT => {
+                let low = operand(2)? as u64;
+                let high = operand(3).unwrap_or_default() as u64;
+                self.constants.insert(operand(1)?, high << 32 | low);
+                if opcode == op::SPEC_CONSTANT {
+                    self.spec_constants.push((operand(0)?, operand(1)?));
+                }
+            }
+            op::CONSTANT_COMPOSITE | op::SPEC_CONSTANT_COMPOSITE => {
+                self.composites
+                    .insert(operand(1)?, operands.get(2..)?.to_vec());
+            }
+            op::VARIABLE => {
+                self.variables.push((operand(0)?, operand(1)?, operand(2)?));
+            }
+            op::DECORATE => {
+                let decorations = self.decorations.entry(operand(0)?).or_default();
+                match operand(1)? {
+                    decoration::SPEC_ID => decorations.spec_id = Some(operand(2)?),
+                    decoration::BUFFER_BLOCK => decorations.buffer_block = true,
+                    decoration::ARRAY_STRIDE => decorations.array_stride = Some(operand(2)?),
+                    decoration::BUILT_IN => decorations.built_in = Some(operand(2)?),
+                    decoration::PATCH => decorations.patch = true,
+                    decoration::LOCATION => decorations.location = Some(operand(2)?),
+                    decoration::COMPONENT => decorations.component = Some(operand(2)?),
+                    decoration::BINDING => decorations.binding = Some(operand(2)?),
+                    decoration::DESCRIPTOR_SET => decorations.descriptor_set = Some(operand(2)?),
+                    _ => (),
+                }
+            }
+            op::MEMBER_DECORATE => {
+                let decorations = self
+                    .member_decorations
+                    .entry((operand(0)?, operand(1)?))
+                    .or_default();
+                match operand(2)? {
+                    decoration::OFFSET => decorations.offset = Some(operand(3)?),
+                    decoration::MATRIX_STRIDE => decorations.matrix_stride = Some(operand(3)?),
+                    decoration::ROW_MAJOR => decorations.row_major = true,
+                    _ => (),
+                }
+            }
+            _ => (),
+        }
+
+        Some(())
+    }
+
+    fn decorations(&self, id: u32) -> Option<&Decorations> {
+        self.decorations.get(&id)
+    }
+
+    fn constant(&self, id: u32) -> Option<u32> {
+        self.constants.get(&id).map(|&value| value as u32)
+    }
+
+    /// Size in bytes of a type in a buffer block, using the strides of its decorations, or
+    /// [`None`] if the type has no size there.
+    fn size_of(
+        &self,
+        id: u32,
+        member: Option<&MemberDecorations>,
+        depth: u32,
+    ) -> Result<Option<u32>> {
+        if depth > MAX_TYPE_DEPTH {
+            return Err(Error::InvalidSpirv(SpirvError::TypeTooDeep(id)));
+        }
+        let overflow = || Error::InvalidSpirv(SpirvError::TypeOverflow(id));
+        let size = |id, member| self.size_of(id, member, depth + 1);
+
+        let ty = match self.types.get(&id) {
+            Some(ty) => ty,
+            None => return Ok(None),
+        };
+        let size = match *ty {
+            Type::Bool => 4,
+            Type::Int { width, .. } | Type::Float { width } => width / 8,
+            Type::Vector { component, count } => match size(component, None)? {
+                Some(size) => size.checked_mul(count).ok_or_else(overflow)?,
+                None => return Ok(None),
+            },
+            Type::Matrix { column, count } => {
+                let rows = match self.types.get(&column) {
+                    Some(Type::Vector { count, .. }) => *count,
+                    _ => return Ok(None),
+                };
+                let (stride, count) = match member.and_then(|member| member.matrix_stride) {
+                    Some(stride) if member.is_some_and(|member| member.row_major) => (stride, rows),
+                    Some(stride) => (stride, count),
+                    None => match size(column, None)? {
+                        Some(size) => (size, count),
+                        None => return Ok(None),
+                    },
+                };
+                stride.checked_mul(count).ok_or_else(overflow)?
+            }
+            Type::Array { element, length } => {
+                let stride = match self.decorations(id).and_then(|deco| deco.array_stride) {
+                    Some(stride) => stride,
+                    None => match size(element, member)? {
+                        Some(size) => size,
+                        None => return Ok(None),
+                    },
+                };
+                match self.constant(length) {
+                    Some(length) => stride.checked_mul(length).ok_or_else(overflow)?,
+                    None => return Ok(None),
+                }
+            }
+            Type::RuntimeArray { .. } => 0,
+            Type::Struct { ref members } => {
+                let mut struct_size = 0u32;
+                for (index, &member) in members.iter().enumerate() {
+                    let decorations = self.member_decorations.get(&(id, index as u32));
+                    let member_size = match size(member, decorations)? {
+                        Some(size) => size,
+                        None => return Ok(None),
+                    };
+                    let offset = match decorations.and_then(|deco| deco.offset) {
+                        Some(offset) => offset,
+                        None => struct_size,
+                    };
+                    let end = offset.checked_add(member_size).ok_or_else(overflow)?;
+                    struct_size = struct_size.max(end);
+                }
+                struct_size
+            }
+            _ => return Ok(None),
+        };
+
+        Ok(Some(size))
+    }
+
+    fn descriptor_binding(
+        &self,
+        id: u32,
+        storage_class: u32,
+        pointee: u32,
+    ) -> Result<Option<DescriptorBinding>> {
+        let overflow = || Error::InvalidSpirv(SpirvError::TypeOverflow(pointee));
+        let binding = match self.decorations(id).and_then(|deco| deco.binding) {
+            Some(binding) => binding,
+            None => return Ok(None),
+        };
+
+        let mut ty = pointee;
+        let mut descriptor_count = 1u32;
+        for depth in 0.. {
+            if depth > MAX_TYPE_DEPTH {
+                return Err(Error::InvalidSpirv(SpirvError::TypeTooDeep(pointee)));
+            }
+            match self.types.get(&ty) {
+                Some(&Type::Array { element, length }) => {
+                    let length = match self.constant(length) {
+                        Some(length) => length,
+                        None => return Ok(None),
+                    };
+                    descriptor_count = descriptor_count.checked_mul(length).ok_or_else(overflow)?;
+                    ty = element;
+                }
+                Some(&Type::RuntimeArray { element }) => {
+                    descriptor_count = 0;
+                    ty = element;
+                }
+                _ => break,
+            }
+        }
+
+        let descriptor_type = match self.types.get(&ty) {
+            Some(ty) => ty,
+            None => return Ok(None),
+        };
+        let descriptor_type = match *descriptor_type {
+            Type::Sampler => DescriptorType::Sampler,
+            Type::SampledImage => DescriptorType::CombinedImageSampler,
+            Type::Image { dim, .. } if dim == DIM_SUBPASS_DATA => DescriptorType::InputAttachment,
+            Type::Image { dim, sampled } if dim == DIM_BUFFER => match sampled {
+                2 => DescriptorType::StorageTexelBuffer,
+                _ => DescriptorType::UniformTexelBuffer,
+            },
+            Type::Image { sampled: 2, .. } => DescriptorType::StorageImage,
+            Type::Image { .. } => DescriptorType::SampledImage,
+            Type::Struct { .. } if storage_class == storage_class::STORAGE_BUFFER => {
+                DescriptorType::StorageBuffer
+            }
+            Type::Struct { .. } if self.decorations(ty).is_some_and(|deco| deco.buffer_block) => {
+                DescriptorType::StorageBuffer
+            }
+            Type::Struct { .. } => DescriptorType::UniformBuffer,
+            Type::AccelerationStructure => DescriptorType::AccelerationStructureKhr,
+            _ => return Ok(None),
+        };
+
+        Ok(Some(DescriptorBinding {
+            set: self
+                .decorations(id)
+                .and_then(|deco| deco.descriptor_set)
+                .unwrap_or_default(),
+            binding,
+            descriptor_type,
+            descriptor_count,
+            name: self.names.get(&id).cloned(),
+        }))
+    }
+
+    fn push_constant_block(&self, pointee: u32) -> Result<Option<PushConstantBlock>> {
+        let members = match self.types.get(&pointee) {
+            Some(Type::Struct { members }) => members,
+            _ => return Ok(None),
+        };
+
+        let mut block_members = Vec::with_capacity(members.len());
+        for (index, &member) in members.iter().enumerate() {
+            let key = (pointee, index as u32);
+            let decorations = self.member_decorations.get(&key);
+            let offset = decorations.and_then(|deco| deco.offset);
+            match (offset, self.size_of(member, decorations, 1)?) {
+                (Some(offset), Some(size)) => block_members.push(BlockMember {
+                    offset,
+                    size,
+                    name: self.member_names.get(&key).cloned(),
+                }),
+                _ => return Ok(None),
+            }
+        }
+
+        Ok(self
+            .size_of(pointee, None, 0)?
+            .map(|size| PushConstantBlock {
+                size,
+                members: block_members,
+            }))
+    }
+
+    fn interface_variable(&self, id: u32, stage: ShaderStage) -> Result<Option<InterfaceVariable>> {
+        let variable = self
+            .variables
+            .iter()
+            .find(|&&(_, variable, _)| variable == id);
+        let (&(pointer, _, storage_class), decorations) = match (variable, self.decorations(id)) {
+            (Some(variable), Some(decorations)) => (variable, decorations),
+            _ => return Ok(None),
+        };
+        let (location, mut ty) = match (decorations.location, self.types.get(&pointer)) {
+            (Some(location), Some(&Type::Pointer { pointee, .. })) => (location, pointee),
+            _ => return Ok(None),
+        };
+
+        // Per-vertex variables of these stages are arrays over the vertices of a primitive.
+        let arrayed = !decorations.patch
+            && match stage {
+                ShaderStage::TessellationControl => true,
+                ShaderStage::TessellationEvaluation | ShaderStage::Geometry => {
+                    storage_class == storage_class::INPUT
+                }
+                ShaderStage::MeshNv => storage_class == storage_class::OUTPUT,
+                _ => false,
+            };
+        if arrayed {
+            ty = match self.types.get(&ty) {
+                Some(&Type::Array { element, .. } | &Type::RuntimeArray { element }) => element,
+                _ => ty,
+            };
+        }
+
+        let overflow = || Error::InvalidSpirv(SpirvError::TypeOverflow(ty));
+        let mut location_count = 1u32;
+        let mut element = ty;
+        let mut depth = 0;
+        let (scalar, component_count) = loop {
+            if depth > MAX_TYPE_DEPTH {
+                return Err(Error::InvalidSpirv(SpirvError::TypeTooDeep(ty)));
+            }
+            depth += 1;
+            let count = match self.types.get(&element) {
+                Some(&Type::Array {
+                    element: inner,
+                    length,
+                }) => match self.constant(length) {
+                    Some(length) => {
+                        element = inner;
+                        length
+                    }
+                    None => return Ok(None),
+                },
+                Some(&Type::Matrix { column, count }) => {
+                    element = column;
+                    count
+                }
+                Some(&Type::Vector { component, count }) => break (component, count),
+                Some(_) => break (element, 1),
+                None => return Ok(None),
+            };
+            location_count = location_count.checked_mul(count).ok_or_else(overflow)?;
+        };
+
+        let (numeric_type, width) = match self.types.get(&scalar) {
+            Some(&Type::Float { width }) => (NumericType::Float, width),
+            Some(&Type::Int {
+                width,
+                signed: true,
+            }) => (NumericType::Sint, width),
+            Some(&Type::Int {
+                width,
+                signed: false,
+            }) => (NumericType::Uint, width),
+            _ => return Ok(None),
+        };
+        // 64-bit three and four component vectors take two locations.
+        if width == 64 && component_count > 2 {
+            location_count = location_count.checked_mul(2).ok_or_else(overflow)?;
+        }
+
+        Ok(Some(InterfaceVariable {
+            location,
+            component: decorations.component.unwrap_or_default(),
+            location_count,
+            numeric_type,
+            width,
+            component_count,
+            name: self.names.get(&id).cloned(),
+        }))
+    }
+
+    fn workgroup_size(&self, entry_point: u32) -> Option<[u32; 3]> {
+        // A `WorkgroupSize` built-in overrides the execution mode.
+        let built_in = self.composites.iter().find(|&(id, _)| {
+            self.decorations(*id)
+                .is_some_and(|deco| deco.built_in == Some(BUILT_IN_WORKGROUP_SIZE))
+        });
+        if let Some((_, components)) = built_in {
+            if let [x, y, z] = components[..] {
+                return Some([self.constant(x)?, self.constant(y)?, self.constant(z)?]);
+            }
+        }
+
+        if let Some(&size) = self.local_sizes.get(&entry_point) {
+            return Some(size);
+        }
+        let [x, y, z] = *self.local_size_ids.get(&entry_point)?;
+        Some([self.constant(x)?, self.constant(y)?, self.constant(z)?])
+    }
+}
+
+/// Embeds a SPIR-V file at compile time as a `&'static [u8]` aligned to 4 bytes, so that
+/// [`create_shader_module`](crate::device::Device::create_shader_module) can use it without
+/// copying. The path is resolved like [`include_bytes!`].
+#[macro_export]
+macro_rules! include_spirv {
+    ($path:expr) => {{
+        #[repr(C, align(4))]
+        struct Aligned<T: ?Sized>(T);
+
+        static ALIGNED: &Aligned<[u8]> = &Aligned(*include_bytes!($path));
+        &ALIGNED.0
+    }};
+}
+
+/// Checks the length and header of SPIR-V `code` and returns its words in native byte order.
+///
+/// The code is borrowed when it is 4-byte aligned and in native byte order, and copied into
+/// an aligned buffer otherwise.
+///
+/// # Errors
+///
+/// [`InvalidSpirv`](Error::InvalidSpirv) if the code is not a whole number of words, is
+/// shorter than the header, has no magic number or declares an unsupported version.
+pub fn spirv_words(code: &[u8]) -> Result<Cow<'_, [u32]>> {
+    if !code.len().is_multiple_of(4) || code.len() < HEADER_WORDS * 4 {
+        return Err(Error::InvalidSpirv(SpirvError::InvalidLength(code.len())));
+    }
+
+    let mut words = match unsafe { code.align_to::<u32>() } {
+        ([], words, []) => Cow::Borrowed(words),
+        _ => Cow::Owned(
+            code.chunks_exact(4)
+                .map(|word| u32::from_ne_bytes([word[0], word[1], word[2], word[3]]))
+                .collect(),
+        ),
+    };
+
+    if words[0] == MAGIC_NUMBER.swap_bytes() {
+        words
+            .to_mut()
+            .iter_mut()
+            .for_each(|word| *word = word.swap_bytes());
+    } else if words[0] != MAGIC_NUMBER {
+        return Err(Error::InvalidSpirv(SpirvError::InvalidMagicNumber(
+            words[0],
+        )));
+    }
+
+    // Version 1.x is encoded as 0x0001xx00.
+    let version = words[1];
+    if version & 0xffff_00ff != 0x0001_0000 {
+        return Err(Error::InvalidSpirv(SpirvError::UnsupportedVersion(version)));
+    }
+
+    Ok(words)
+}
+
+impl ShaderReflection {
+    /// Parses the interface of the SPIR-V `code`.
+    ///
+    /// # Errors
+    ///
+    /// [`InvalidSpirv`](Error::InvalidSpirv) if the code is not well-formed SPIR-V, or a type
+    /// overflows 32 bits or nests deeper than [`MAX_TYPE_DEPTH`].
+    pub fn new(code: &[u8]) -> Result<Self> {
+        Self::from_words(&spirv_words(code)?)
+    }
+
+    /// Parses the interface of SPIR-V code already checked by [`spirv_words`].
+    pub(crate) fn from_words(words: &[u32]) -> Result<Self> {
+        Self::from_module(&Module::parse(words)?)
+    }
+
+    fn from_module(module: &Module) -> Result<Self> {
+        let mut reflection = Self::default();
+
+        for &(pointer, id, storage_class) in &module.variables {
+            let pointee = match module.types.get(&pointer) {
+                Some(&Type::Pointer { pointee, .. }) => pointee,
+                _ => continue,
+            };
+
+            match storage_class {
+                storage_class::UNIFORM_CONSTANT
+                | storage_class::UNIFORM
+                | storage_class::STORAGE_BUFFER => {
+                    reflection
+                        .descriptor_bindings
+                        .extend(module.descriptor_binding(id, storage_class, pointee)?);
+                }
+                storage_class::PUSH_CONSTANT => {
+                    reflection.push_constant_block = module.push_constant_block(pointee)?;
+                }
+                _ => (),
+            }
+        }
+        reflection
+            .descriptor_bindings
+            .sort_by_key(|binding| (binding.set, binding.binding));
+
+        for &(type_id, id) in &module.spec_constants {
+            let constant_id = match module.decorations(id).and_then(|deco| deco.spec_id) {
+                Some(constant_id) => constant_id,
+                None => continue,
+            };
+            if let Some(size) = module.size_of(type_id, None, 0)? {
+                reflection
+                    .specialization_constants
+                    .push(SpecializationConstantInfo {
+                        constant_id,
+                        size: size as usize,
+                        name: module.names.get(&id).cloned(),
+                    });
+            }
+        }
+
+        for (execution_model, id, name, interface) in &module.entry_points {
+            let stage = match stage_of(*execution_model) {
+                Some(stage) => stage,
+                None => continue,
+            };
+
+            let mut entry_point = EntryPoint {
+                name: name.clone(),
+                stage,
+                workgroup_size: None,
+                inputs: Vec::new(),
+                outputs: Vec::new(),
+            };
+            if stage == ShaderStage::Compute {
+                entry_point.workgroup_size = module.workgroup_size(*id);
+            }
+
+            for &variable in interface {
+                let storage_class = module
+                    .variables
+                    .iter()
+                    .find(|&&(_, id, _)| id == variable)
+                    .map(|&(_, _, storage_class)| storage_class);
+                let interface_variable = match module.interface_variable(variable, stage)? {
+                    Some(interface_variable) => interface_variable,
+                    None => continue,
+                };
+                match storage_class {
+                    Some(storage_class::INPUT) => entry_point.inputs.push(interface_variable),
+                    Some(storage_class::OUTPUT) => entry_point.outputs.push(interface_variable),
+                    _ => (),
+                }
+            }
+            entry_point.inputs.sort_by_key(|input| input.location);
+            entry_point.outputs.sort_by_key(|output| output.location);
+
+            reflection.entry_points.push(entry_point);
+        }
+
+        Ok(reflection)
+    }
+
+    pub fn entry_points(&self) -> &[EntryPoint] {
+        &self.entry_points
+    }
+
+    pub fn entry_point(&self, name: &str) -> Option<&EntryPoint> {
+        self.entry_points
+            .iter()
+            .find(|entry_point| entry_point.name == name)
+    }
+
+    /// Descriptor bindings ordered by set and binding number.
+    pub fn descriptor_bindings(&self) -> &[DescriptorBinding] {
+        &self.descriptor_bindings
+    }
+
+    pub fn push_constant_block(&self) -> Option<&PushConstantBlock> {
+        self.push_constant_block.as_ref()
+    }
+
+    pub fn specialization_constants(&self) -> &[SpecializationConstantInfo] {
+        &self.specialization_constants
+    }
+}
+
+impl EntryPoint {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn stage(&self) -> ShaderStage {
+        self.stage
+    }
+
+    /// Local workgroup size of a compute entry point, with the default values of any
+    /// specialization constants it is made of.
+    pub fn workgroup_size(&self) -> Option<[u32; 3]> {
+        self.workgroup_size
+    }
+
+    /// Input variables with a location, ordered by location.
+    pub fn inputs(&self) -> &[InterfaceVariable] {
+        &self.inputs
+    }
+
+    /// Output variables with a location, ordered by location.
+    pub fn outputs(&self) -> &[InterfaceVariable] {
+        &self.outputs
+    }
+
+    /// Checks that every location of the inputs of a vertex entry point has an attribute with
+    /// the same numeric type and component width.
+    ///
+    /// # Errors
+    ///
+    /// - [`UnmatchedVertexInput`](Error::UnmatchedVertexInput) if an input location has no
+    ///   attribute.
+    /// - [`IncompatibleVertexFormat`](Error::IncompatibleVertexFormat) if the attribute format
+    ///   has another numeric type or component width than the input.
+    pub fn validate_vertex_attributes(
+        &self,
+        attributes: &[VertexInputAttributeDescription],
+    ) -> Result<()> {
+        for input in &self.inputs {
+            for location in input.locations() {
+                // Locations past the last one cannot have an attribute.
+                let location = u32::try_from(location)
+                    .map_err(|_| Error::UnmatchedVertexInput(input.location))?;
+                let format = attributes
+                    .iter()
+                    .find(|attribute| attribute.location() == location)
+                    .ok_or(Error::UnmatchedVertexInput(location))?
+                    .format();
+                if format.numeric_type() != Some(input.numeric_type())
+                    || format.is_64_bit() != (input.width() == 64)
+                {
+                    return Err(Error::IncompatibleVertexFormat { location, format });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that every location of the inputs of this entry point is written by an output
+    /// of `previous` with the same component, type and at least as many components.
+    ///
+    /// # Errors
+    ///
+    /// [`UnmatchedFragmentInput`](Error::UnmatchedFragmentInput) with the location of the
+    /// first input without a matching output.
+    pub fn validate_inputs_from(&self, previous: &EntryPoint) -> Result<()> {
+        for input in &self.inputs {
+            let matched = input.locations().all(|location| {
+                previous.outputs.iter().any(|output| {
+                    output.locations().contains(&location)
+                        && output.component == input.component
+                        && output.numeric_type == input.numeric_type
+                        && output.width == input.width
+                        && output.component_count >= input.component_count
+                })
+            });
+            if !matched {
+                return Err(Error::UnmatchedFragmentInput(input.location));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the outputs of a fragment entry point are below `attachment_count`.
+    ///
+    /// # Errors
+    ///
+    /// [`UnmatchedFragmentOutput`](Error::UnmatchedFragmentOutput) with the location of the
+    /// first output past the color attachments.
+    pub fn validate_color_attachments(&self, attachment_count: u32) -> Result<()> {
+        match self
+            .outputs
+            .iter()
+            .find(|output| output.locations().end > attachment_count as u64)
+        {
+            Some(output) => Err(Error::UnmatchedFragmentOutput(output.location)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl DescriptorBinding {
+    pub fn set(&self) -> u32 {
+        self.set
+    }
+
+    pub fn binding(&self) -> u32 {
+        self.binding
+    }
+
+    pub fn descriptor_type(&self) -> DescriptorType {
+        self.descriptor_type
+    }
+
+    /// Number of descriptors of an arrayed binding, zero for a runtime sized array.
+    pub fn descriptor_count(&self) -> u32 {
+        self.descriptor_count
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+}
+
+impl PushConstantBlock {
+    /// Size in bytes up to the end of the last member.
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    pub fn members(&self) -> &[BlockMember] {
+        &self.members
+    }
+}
+
+impl BlockMember {
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+}
+
+impl SpecializationConstantInfo {
+    pub fn constant_id(&self) -> u32 {
+        self.constant_id
+    }
+
+    /// Size in bytes of the constant, booleans being 4 bytes wide.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+}
+
+impl InterfaceVariable {
+    pub fn location(&self) -> u32 {
+        self.location
+    }
+
+    pub fn component(&self) -> u32 {
+        self.component
+    }
+
+    /// Number of consecutive locations taken by arrays, matrices and 64-bit vectors.
+    pub fn location_count(&self) -> u32 {
+        self.location_count
+    }
+
+    /// Locations taken by the variable, widened so that the end cannot overflow.
+    pub fn locations(&self) -> Range<u64> {
+        self.location as u64..self.location as u64 + self.location_count as u64
+    }
+
+    pub fn numeric_type(&self) -> NumericType {
+        self.numeric_type
+    }
+
+    /// Width in bits of a component.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn component_count(&self) -> u32 {
+        self.component_count
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+}
+
+impl std::fmt::Display for SpirvError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidLength(len) => write!(f, "invalid SPIR-V length of {} bytes", len),
+            Self::InvalidMagicNumber(magic) => {
+                write!(f, "invalid SPIR-V magic number {:#010x}", magic)
+            }
+            Self::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported SPIR-V version {}.{}",
+                version >> 16 & 0xff,
+                version >> 8 & 0xff
+            ),
+            Self::TruncatedInstruction(offset) => {
+                write!(f, "truncated SPIR-V instruction at word {}", offset)
+            }
+            Self::InvalidInstruction { offset, opcode } => write!(
+                f,
+                "invalid SPIR-V instruction with opcode {} at word {}",
+                opcode, offset
+            ),
+            Self::TypeOverflow(id) => write!(f, "SPIR-V type %{} overflows 32 bits", id),
+            Self::TypeTooDeep(id) => write!(
+                f,
+                "SPIR-V type %{} nests deeper than {} levels",
+                id, MAX_TYPE_DEPTH
+            ),
+        }
+    }
+}
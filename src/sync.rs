@@ -4,8 +4,10 @@ use std::num::NonZeroU64;
 #[cfg(target_pointer_width = "64")]
 use std::ptr::NonNull;
 
-use crate::device::Device;
+use crate::core::{Error, Result};
+use crate::device::{Device, PhysicalDeviceFeatures, QueueFamilyProperties};
 use crate::ffi;
+use crate::resource::{Buffer, Image, ImageLayout, ImageSubresourceRange, WHOLE_SIZE};
 
 pub struct Semaphore<'a> {
     #[cfg(target_pointer_width = "64")]
@@ -17,6 +19,136 @@ pub struct Semaphore<'a> {
     pub(crate) _marker: PhantomData<ffi::VkSemaphore_T>,
 }
 
+/// Synchronization primitive the host waits on for the completion of a queue submission.
+pub struct Fence<'a> {
+    #[cfg(target_pointer_width = "64")]
+    pub(crate) handle: NonNull<ffi::VkFence_T>,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub(crate) handle: NonZeroU64,
+    pub(crate) device: &'a Device<'a>,
+    #[cfg(target_pointer_width = "64")]
+    pub(crate) _marker: PhantomData<ffi::VkFence_T>,
+}
+
+/// Stage of the pipeline that commands pass through, used to scope execution dependencies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PipelineStage {
+    TopOfPipe,
+    DrawIndirect,
+    VertexInput,
+    VertexShader,
+    TessellationControlShader,
+    TessellationEvaluationShader,
+    GeometryShader,
+    FragmentShader,
+    EarlyFragmentTests,
+    LateFragmentTests,
+    ColorAttachmentOutput,
+    ComputeShader,
+    Transfer,
+    BottomOfPipe,
+    Host,
+    AllGraphics,
+    AllCommands,
+    // Stages below are only distinguished with synchronization2, legacy commands use the
+    // stages that contain them.
+    Copy,
+    Resolve,
+    Blit,
+    Clear,
+    IndexInput,
+    VertexAttributeInput,
+    PreRasterizationShaders,
+}
+
+/// Set of pipeline stages, in the 64-bit representation of synchronization2.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PipelineStageFlags(pub(crate) u64);
+
+/// Kind of memory access, used to scope memory dependencies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    IndirectCommandRead,
+    IndexRead,
+    VertexAttributeRead,
+    UniformRead,
+    InputAttachmentRead,
+    ShaderRead,
+    ShaderWrite,
+    ColorAttachmentRead,
+    ColorAttachmentWrite,
+    DepthStencilAttachmentRead,
+    DepthStencilAttachmentWrite,
+    TransferRead,
+    TransferWrite,
+    HostRead,
+    HostWrite,
+    MemoryRead,
+    MemoryWrite,
+    // Accesses below are only distinguished with synchronization2, legacy commands use the
+    // accesses that contain them.
+    ShaderSampledRead,
+    ShaderStorageRead,
+    ShaderStorageWrite,
+}
+
+/// Set of memory accesses, in the 64-bit representation of synchronization2.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AccessFlags(pub(crate) u64);
+
+/// Dependency between the commands before and after a barrier, covering all memory.
+///
+/// Each barrier has its own source and destination stages. Recorded with
+/// [`pipeline_barrier`](crate::command_buffer::CommandBuffer::pipeline_barrier), the stages of
+/// all barriers of a [`DependencyInfo`] are combined.
+#[derive(Debug, Clone, Copy)]
+pub struct MemoryBarrier {
+    src_stages: PipelineStageFlags,
+    src_access: AccessFlags,
+    dst_stages: PipelineStageFlags,
+    dst_access: AccessFlags,
+}
+
+/// Dependency on a range of a buffer, optionally transferring it between queue families.
+#[derive(Debug, Clone, Copy)]
+pub struct BufferMemoryBarrier<'r> {
+    buffer: &'r Buffer<'r>,
+    offset: u64,
+    size: u64,
+    src_stages: PipelineStageFlags,
+    src_access: AccessFlags,
+    dst_stages: PipelineStageFlags,
+    dst_access: AccessFlags,
+    src_queue_family_index: u32,
+    dst_queue_family_index: u32,
+}
+
+/// Dependency on subresources of an image that transitions their layout, optionally
+/// transferring them between queue families.
+#[derive(Debug, Clone, Copy)]
+pub struct ImageMemoryBarrier<'r> {
+    image: &'r Image<'r>,
+    subresource_range: ImageSubresourceRange,
+    old_layout: ImageLayout,
+    new_layout: ImageLayout,
+    src_stages: PipelineStageFlags,
+    src_access: AccessFlags,
+    dst_stages: PipelineStageFlags,
+    dst_access: AccessFlags,
+    src_queue_family_index: u32,
+    dst_queue_family_index: u32,
+}
+
+/// Barriers recorded together by one
+/// [`pipeline_barrier`](crate::command_buffer::CommandBuffer::pipeline_barrier).
+#[derive(Debug, Default)]
+pub struct DependencyInfo<'r> {
+    by_region: bool,
+    memory_barriers: Vec<MemoryBarrier>,
+    buffer_barriers: Vec<BufferMemoryBarrier<'r>>,
+    image_barriers: Vec<ImageMemoryBarrier<'r>>,
+}
+
 impl<'a> Drop for Semaphore<'a> {
     fn drop(&mut self) {
         println!("Dropped Semaphore");
@@ -32,3 +164,990 @@ impl<'a> Drop for Semaphore<'a> {
         }
     }
 }
+
+/// Synchronization primitive set and waited on by command buffers, at a finer granularity
+/// than a pipeline barrier.
+pub struct Event<'a> {
+    #[cfg(target_pointer_width = "64")]
+    pub(crate) handle: NonNull<ffi::VkEvent_T>,
+    #[cfg(not(target_pointer_width = "64"))]
+    pub(crate) handle: NonZeroU64,
+    pub(crate) device: &'a Device<'a>,
+    #[cfg(target_pointer_width = "64")]
+    pub(crate) _marker: PhantomData<ffi::VkEvent_T>,
+}
+
+impl<'a> Fence<'a> {
+    /// Waits up to `timeout` nanoseconds for the fence to be signaled. Returns `false` if the
+    /// timeout expired first.
+    ///
+    /// # Errors
+    ///
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    /// - [`DeviceLost`](Error::DeviceLost)
+    pub fn wait(&self, timeout: u64) -> Result<bool> {
+        #[cfg(target_pointer_width = "64")]
+        let fence = self.handle.as_ptr();
+        #[cfg(not(target_pointer_width = "64"))]
+        let fence = self.handle.get();
+
+        let result = unsafe {
+            (self.device.dispatch_loader.vk_wait_for_fences)(
+                self.device.handle.as_ptr(),
+                1,
+                &fence,
+                1,
+                timeout,
+            )
+        };
+
+        match result {
+            ffi::Result::Success => Ok(true),
+            ffi::Result::Timeout => Ok(false),
+            ffi::Result::ErrorOutOfHostMemory => Err(Error::OutOfHostMemory),
+            ffi::Result::ErrorOutOfDeviceMemory => Err(Error::OutOfDeviceMemory),
+            ffi::Result::ErrorDeviceLost => Err(Error::DeviceLost),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns `true` if the fence is signaled, without waiting.
+    ///
+    /// # Errors
+    ///
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    /// - [`DeviceLost`](Error::DeviceLost)
+    pub fn is_signaled(&self) -> Result<bool> {
+        let result = unsafe {
+            (self.device.dispatch_loader.vk_get_fence_status)(
+                self.device.handle.as_ptr(),
+                #[cfg(target_pointer_width = "64")]
+                self.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                self.handle.get(),
+            )
+        };
+
+        match result {
+            ffi::Result::Success => Ok(true),
+            ffi::Result::NotReady => Ok(false),
+            ffi::Result::ErrorOutOfHostMemory => Err(Error::OutOfHostMemory),
+            ffi::Result::ErrorOutOfDeviceMemory => Err(Error::OutOfDeviceMemory),
+            ffi::Result::ErrorDeviceLost => Err(Error::DeviceLost),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Sets the fence back to the unsignaled state. The fence must not be in use by a pending
+    /// submission.
+    ///
+    /// # Errors
+    ///
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    pub fn reset(&mut self) -> Result<()> {
+        #[cfg(target_pointer_width = "64")]
+        let fence = self.handle.as_ptr();
+        #[cfg(not(target_pointer_width = "64"))]
+        let fence = self.handle.get();
+
+        let result = unsafe {
+            (self.device.dispatch_loader.vk_reset_fences)(self.device.handle.as_ptr(), 1, &fence)
+        };
+
+        match result {
+            ffi::Result::Success => Ok(()),
+            ffi::Result::ErrorOutOfHostMemory => Err(Error::OutOfHostMemory),
+            ffi::Result::ErrorOutOfDeviceMemory => Err(Error::OutOfDeviceMemory),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<'a> Event<'a> {
+    /// Returns `true` if the event is set.
+    ///
+    /// # Errors
+    ///
+    /// - [`OutOfHostMemory`](Error::OutOfHostMemory)
+    /// - [`OutOfDeviceMemory`](Error::OutOfDeviceMemory)
+    /// - [`DeviceLost`](Error::DeviceLost)
+    pub fn is_set(&self) -> Result<bool> {
+        let result = unsafe {
+            (self.device.dispatch_loader.vk_get_event_status)(
+                self.device.handle.as_ptr(),
+                #[cfg(target_pointer_width = "64")]
+                self.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                self.handle.get(),
+            )
+        };
+
+        match result {
+            ffi::Result::EventSet => Ok(true),
+            ffi::Result::EventReset => Ok(false),
+            ffi::Result::ErrorOutOfHostMemory => Err(Error::OutOfHostMemory),
+            ffi::Result::ErrorOutOfDeviceMemory => Err(Error::OutOfDeviceMemory),
+            ffi::Result::ErrorDeviceLost => Err(Error::DeviceLost),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<'a> std::fmt::Debug for Event<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Event")
+            .field("handle", &self.handle)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> Drop for Event<'a> {
+    fn drop(&mut self) {
+        println!("Dropped Event");
+        unsafe {
+            (self.device.dispatch_loader.vk_destroy_event)(
+                self.device.handle.as_ptr(),
+                #[cfg(target_pointer_width = "64")]
+                self.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                self.handle.get(),
+                std::ptr::null(),
+            );
+        }
+    }
+}
+
+impl<'a> std::fmt::Debug for Fence<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Fence")
+            .field("handle", &self.handle)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> Drop for Fence<'a> {
+    fn drop(&mut self) {
+        println!("Dropped Fence");
+        unsafe {
+            (self.device.dispatch_loader.vk_destroy_fence)(
+                self.device.handle.as_ptr(),
+                #[cfg(target_pointer_width = "64")]
+                self.handle.as_ptr(),
+                #[cfg(not(target_pointer_width = "64"))]
+                self.handle.get(),
+                std::ptr::null(),
+            );
+        }
+    }
+}
+
+impl PipelineStageFlags {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_stage(mut self, stage: PipelineStage) -> Self {
+        self.0 |= ffi::PipelineStageFlagBits2::from(stage) as u64;
+        self
+    }
+
+    /// Returns `true` if `stage` is in the set.
+    pub fn contains(&self, stage: PipelineStage) -> bool {
+        let flag = ffi::PipelineStageFlagBits2::from(stage) as u64;
+
+        self.0 & flag == flag
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// The stages as legacy `VkPipelineStageFlags`, with stages only known to
+    /// synchronization2 replaced by the legacy stages containing them. Pre-rasterization
+    /// shaders include the tessellation and geometry stages only if those features are
+    /// enabled.
+    pub(crate) fn to_legacy(self, features: &PhysicalDeviceFeatures) -> u32 {
+        use ffi::PipelineStageFlagBits as Legacy;
+        use ffi::PipelineStageFlagBits2 as Bits;
+
+        const LEGACY: u64 = Bits::AllCommandsBit as u64 * 2 - 1;
+        const TRANSFER: u64 = Bits::CopyBit as u64
+            | Bits::ResolveBit as u64
+            | Bits::BlitBit as u64
+            | Bits::ClearBit as u64;
+        const VERTEX_INPUT: u64 = Bits::IndexInputBit as u64 | Bits::VertexAttributeInputBit as u64;
+
+        let mut legacy = (self.0 & LEGACY) as u32;
+        if self.0 & TRANSFER != 0 {
+            legacy |= Legacy::TransferBit as u32;
+        }
+        if self.0 & VERTEX_INPUT != 0 {
+            legacy |= Legacy::VertexInputBit as u32;
+        }
+        if self.0 & Bits::PreRasterizationShadersBit as u64 != 0 {
+            legacy |= Legacy::VertexShaderBit as u32;
+            if features.tessellation_shader {
+                legacy |= Legacy::TessellationControlShaderBit as u32
+                    | Legacy::TessellationEvaluationShaderBit as u32;
+            }
+            if features.geometry_shader {
+                legacy |= Legacy::GeometryShaderBit as u32;
+            }
+        }
+        legacy
+    }
+
+    /// The stages as the legacy wait stage mask of a semaphore in `vkQueueSubmit`, see
+    /// [`to_legacy`](Self::to_legacy). The mask cannot be empty there, so an empty set waits
+    /// before all commands.
+    pub fn to_legacy_wait_stages(self, features: &PhysicalDeviceFeatures) -> u32 {
+        match self.to_legacy(features) {
+            0 => ffi::PipelineStageFlagBits::AllCommandsBit as u32,
+            legacy => legacy,
+        }
+    }
+
+    /// The stages of the set that commands on a queue of `queue_family` can pass through.
+    /// Transfer stages are supported by every graphics, compute or transfer queue.
+    pub fn supported_by(self, queue_family: &QueueFamilyProperties) -> Self {
+        use ffi::PipelineStageFlagBits2 as Bits;
+
+        const GRAPHICS: u64 = Bits::VertexInputBit as u64
+            | Bits::VertexShaderBit as u64
+            | Bits::TessellationControlShaderBit as u64
+            | Bits::TessellationEvaluationShaderBit as u64
+            | Bits::GeometryShaderBit as u64
+            | Bits::FragmentShaderBit as u64
+            | Bits::EarlyFragmentTestsBit as u64
+            | Bits::LateFragmentTestsBit as u64
+            | Bits::ColorAttachmentOutputBit as u64
+            | Bits::AllGraphicsBit as u64
+            | Bits::ResolveBit as u64
+            | Bits::BlitBit as u64
+            | Bits::IndexInputBit as u64
+            | Bits::VertexAttributeInputBit as u64
+            | Bits::PreRasterizationShadersBit as u64;
+        const COMPUTE: u64 = Bits::ComputeShaderBit as u64;
+        const DISPATCH: u64 = Bits::DrawIndirectBit as u64;
+        const TRANSFER: u64 =
+            Bits::AllTransferBit as u64 | Bits::CopyBit as u64 | Bits::ClearBit as u64;
+
+        let graphics = queue_family.supports_graphics();
+        let compute = queue_family.supports_compute();
+        let mut unsupported = 0;
+        if !graphics {
+            unsupported |= GRAPHICS;
+        }
+        if !compute {
+            unsupported |= COMPUTE;
+        }
+        if !graphics && !compute {
+            unsupported |= DISPATCH;
+            if !queue_family.supports_transfer() {
+                unsupported |= TRANSFER;
+            }
+        }
+
+        Self(self.0 & !unsupported)
+    }
+}
+
+impl From<PipelineStage> for PipelineStageFlags {
+    fn from(stage: PipelineStage) -> Self {
+        Self(ffi::PipelineStageFlagBits2::from(stage) as u64)
+    }
+}
+
+impl From<PipelineStage> for ffi::PipelineStageFlagBits2 {
+    fn from(stage: PipelineStage) -> Self {
+        match stage {
+            PipelineStage::TopOfPipe => Self::TopOfPipeBit,
+            PipelineStage::DrawIndirect => Self::DrawIndirectBit,
+            PipelineStage::VertexInput => Self::VertexInputBit,
+            PipelineStage::VertexShader => Self::VertexShaderBit,
+            PipelineStage::TessellationControlShader => Self::TessellationControlShaderBit,
+            PipelineStage::TessellationEvaluationShader => Self::TessellationEvaluationShaderBit,
+            PipelineStage::GeometryShader => Self::GeometryShaderBit,
+            PipelineStage::FragmentShader => Self::FragmentShaderBit,
+            PipelineStage::EarlyFragmentTests => Self::EarlyFragmentTestsBit,
+            PipelineStage::LateFragmentTests => Self::LateFragmentTestsBit,
+            PipelineStage::ColorAttachmentOutput => Self::ColorAttachmentOutputBit,
+            PipelineStage::ComputeShader => Self::ComputeShaderBit,
+            PipelineStage::Transfer => Self::AllTransferBit,
+            PipelineStage::BottomOfPipe => Self::BottomOfPipeBit,
+            PipelineStage::Host => Self::HostBit,
+            PipelineStage::AllGraphics => Self::AllGraphicsBit,
+            PipelineStage::AllCommands => Self::AllCommandsBit,
+            PipelineStage::Copy => Self::CopyBit,
+            PipelineStage::Resolve => Self::ResolveBit,
+            PipelineStage::Blit => Self::BlitBit,
+            PipelineStage::Clear => Self::ClearBit,
+            PipelineStage::IndexInput => Self::IndexInputBit,
+            PipelineStage::VertexAttributeInput => Self::VertexAttributeInputBit,
+            PipelineStage::PreRasterizationShaders => Self::PreRasterizationShadersBit,
+        }
+    }
+}
+
+impl AccessFlags {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_access(mut self, access: Access) -> Self {
+        self.0 |= ffi::AccessFlagBits2::from(access) as u64;
+        self
+    }
+
+    /// Returns `true` if `access` is in the set.
+    pub fn contains(&self, access: Access) -> bool {
+        let flag = ffi::AccessFlagBits2::from(access) as u64;
+
+        self.0 & flag == flag
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// The write accesses of the set, the only ones a source access scope needs.
+    pub fn writes(&self) -> Self {
+        use ffi::AccessFlagBits2 as Bits;
+
+        const WRITES: u64 = Bits::ShaderWriteBit as u64
+            | Bits::ColorAttachmentWriteBit as u64
+            | Bits::DepthStencilAttachmentWriteBit as u64
+            | Bits::TransferWriteBit as u64
+            | Bits::HostWriteBit as u64
+            | Bits::MemoryWriteBit as u64
+            | Bits::ShaderStorageWriteBit as u64;
+
+        Self(self.0 & WRITES)
+    }
+
+    /// The accesses of the set that can be performed by at least one of `stages`.
+    pub fn supported_by(self, stages: PipelineStageFlags) -> Self {
+        use ffi::AccessFlagBits2 as Flags;
+        use ffi::PipelineStageFlagBits2 as Bits;
+
+        const ALL: u64 = Bits::AllCommandsBit as u64;
+        const GRAPHICS: u64 = ALL | Bits::AllGraphicsBit as u64;
+        const SHADERS: u64 = GRAPHICS
+            | Bits::VertexShaderBit as u64
+            | Bits::TessellationControlShaderBit as u64
+            | Bits::TessellationEvaluationShaderBit as u64
+            | Bits::GeometryShaderBit as u64
+            | Bits::FragmentShaderBit as u64
+            | Bits::ComputeShaderBit as u64
+            | Bits::PreRasterizationShadersBit as u64;
+        const FRAGMENT_TESTS: u64 =
+            GRAPHICS | Bits::EarlyFragmentTestsBit as u64 | Bits::LateFragmentTestsBit as u64;
+        const TRANSFER: u64 = GRAPHICS
+            | Bits::AllTransferBit as u64
+            | Bits::CopyBit as u64
+            | Bits::ResolveBit as u64
+            | Bits::BlitBit as u64
+            | Bits::ClearBit as u64;
+        const VERTEX_INPUT: u64 = GRAPHICS | Bits::VertexInputBit as u64;
+        const ACCESSES: [(u64, u64); 10] = [
+            (
+                Flags::IndirectCommandReadBit as u64,
+                GRAPHICS | Bits::DrawIndirectBit as u64,
+            ),
+            (
+                Flags::IndexReadBit as u64,
+                VERTEX_INPUT | Bits::IndexInputBit as u64,
+            ),
+            (
+                Flags::VertexAttributeReadBit as u64,
+                VERTEX_INPUT | Bits::VertexAttributeInputBit as u64,
+            ),
+            (
+                Flags::UniformReadBit as u64
+                    | Flags::ShaderReadBit as u64
+                    | Flags::ShaderWriteBit as u64
+                    | Flags::ShaderSampledReadBit as u64
+                    | Flags::ShaderStorageReadBit as u64
+                    | Flags::ShaderStorageWriteBit as u64,
+                SHADERS,
+            ),
+            (
+                Flags::InputAttachmentReadBit as u64,
+                GRAPHICS | Bits::FragmentShaderBit as u64,
+            ),
+            (
+                Flags::ColorAttachmentReadBit as u64 | Flags::ColorAttachmentWriteBit as u64,
+                GRAPHICS | Bits::ColorAttachmentOutputBit as u64,
+            ),
+            (
+                Flags::DepthStencilAttachmentReadBit as u64
+                    | Flags::DepthStencilAttachmentWriteBit as u64,
+                FRAGMENT_TESTS,
+            ),
+            (
+                Flags::TransferReadBit as u64 | Flags::TransferWriteBit as u64,
+                TRANSFER,
+            ),
+            (
+                Flags::HostReadBit as u64 | Flags::HostWriteBit as u64,
+                Bits::HostBit as u64,
+            ),
+            (
+                Flags::MemoryReadBit as u64 | Flags::MemoryWriteBit as u64,
+                u64::MAX,
+            ),
+        ];
+
+        let supported = ACCESSES
+            .iter()
+            .filter(|&&(_, access_stages)| stages.0 & access_stages != 0)
+            .fold(0, |supported, &(access, _)| supported | access);
+
+        Self(self.0 & supported)
+    }
+
+    /// The accesses as legacy `VkAccessFlags`, with accesses only known to synchronization2
+    /// replaced by the legacy accesses containing them.
+    pub(crate) fn to_legacy(self) -> u32 {
+        use ffi::AccessFlagBits as Legacy;
+        use ffi::AccessFlagBits2 as Bits;
+
+        const LEGACY: u64 = Bits::MemoryWriteBit as u64 * 2 - 1;
+        const SHADER_READ: u64 =
+            Bits::ShaderSampledReadBit as u64 | Bits::ShaderStorageReadBit as u64;
+
+        let mut legacy = (self.0 & LEGACY) as u32;
+        if self.0 & SHADER_READ != 0 {
+            legacy |= Legacy::ShaderReadBit as u32;
+        }
+        if self.0 & Bits::ShaderStorageWriteBit as u64 != 0 {
+            legacy |= Legacy::ShaderWriteBit as u32;
+        }
+        legacy
+    }
+}
+
+impl From<Access> for AccessFlags {
+    fn from(access: Access) -> Self {
+        Self(ffi::AccessFlagBits2::from(access) as u64)
+    }
+}
+
+impl From<Access> for ffi::AccessFlagBits2 {
+    fn from(access: Access) -> Self {
+        match access {
+            Access::IndirectCommandRead => Self::IndirectCommandReadBit,
+            Access::IndexRead => Self::IndexReadBit,
+            Access::VertexAttributeRead => Self::VertexAttributeReadBit,
+            Access::UniformRead => Self::UniformReadBit,
+            Access::InputAttachmentRead => Self::InputAttachmentReadBit,
+            Access::ShaderRead => Self::ShaderReadBit,
+            Access::ShaderWrite => Self::ShaderWriteBit,
+            Access::ColorAttachmentRead => Self::ColorAttachmentReadBit,
+            Access::ColorAttachmentWrite => Self::ColorAttachmentWriteBit,
+            Access::DepthStencilAttachmentRead => Self::DepthStencilAttachmentReadBit,
+            Access::DepthStencilAttachmentWrite => Self::DepthStencilAttachmentWriteBit,
+            Access::TransferRead => Self::TransferReadBit,
+            Access::TransferWrite => Self::TransferWriteBit,
+            Access::HostRead => Self::HostReadBit,
+            Access::HostWrite => Self::HostWriteBit,
+            Access::MemoryRead => Self::MemoryReadBit,
+            Access::MemoryWrite => Self::MemoryWriteBit,
+            Access::ShaderSampledRead => Self::ShaderSampledReadBit,
+            Access::ShaderStorageRead => Self::ShaderStorageReadBit,
+            Access::ShaderStorageWrite => Self::ShaderStorageWriteBit,
+        }
+    }
+}
+
+/// Stages and accesses that use an image in `layout`, for the destination scope of a
+/// transition to it. Layouts an image cannot be transitioned to give empty scopes.
+///
+/// The scope covers every queue the layout can be used on, so shader read layouts include
+/// graphics stages a compute queue does not support. Use [`layout_dst_scope_for_queue`] for
+/// barriers recorded on queues without graphics support.
+pub fn layout_dst_scope(layout: ImageLayout) -> (PipelineStageFlags, AccessFlags) {
+    use PipelineStage::*;
+
+    let (stages, access): (&[PipelineStage], &[Access]) = match layout {
+        ImageLayout::Undefined | ImageLayout::Preinitialized => (&[], &[]),
+        ImageLayout::General => (&[AllCommands], &[Access::MemoryRead, Access::MemoryWrite]),
+        ImageLayout::ColorAttachmentOptimal => (
+            &[ColorAttachmentOutput],
+            &[Access::ColorAttachmentRead, Access::ColorAttachmentWrite],
+        ),
+        ImageLayout::DepthStencilAttachmentOptimal
+        | ImageLayout::DepthReadOnlyStencilAttachmentOptimal
+        | ImageLayout::DepthAttachmentStencilReadOnlyOptimal
+        | ImageLayout::DepthAttachmentOptimal
+        | ImageLayout::StencilAttachmentOptimal => (
+            &[EarlyFragmentTests, LateFragmentTests],
+            &[
+                Access::DepthStencilAttachmentRead,
+                Access::DepthStencilAttachmentWrite,
+            ],
+        ),
+        ImageLayout::DepthStencilReadOnlyOptimal
+        | ImageLayout::DepthReadOnlyOptimal
+        | ImageLayout::StencilReadOnlyOptimal => (
+            &[EarlyFragmentTests, LateFragmentTests, FragmentShader],
+            &[Access::DepthStencilAttachmentRead, Access::ShaderRead],
+        ),
+        ImageLayout::ShaderReadOnlyOptimal => (
+            &[VertexShader, FragmentShader, ComputeShader],
+            &[Access::ShaderRead, Access::InputAttachmentRead],
+        ),
+        ImageLayout::TransferSrcOptimal => (&[Transfer], &[Access::TransferRead]),
+        ImageLayout::TransferDstOptimal => (&[Transfer], &[Access::TransferWrite]),
+        // Presentation is ordered by a semaphore, the barrier only has to finish the transition.
+        ImageLayout::PresentSrcKhr | ImageLayout::SharedPresentKhr => (&[BottomOfPipe], &[]),
+        ImageLayout::ReadOnlyOptimalKhr => (
+            &[
+                EarlyFragmentTests,
+                LateFragmentTests,
+                VertexShader,
+                FragmentShader,
+                ComputeShader,
+            ],
+            &[
+                Access::DepthStencilAttachmentRead,
+                Access::ShaderRead,
+                Access::InputAttachmentRead,
+            ],
+        ),
+        ImageLayout::AttachmentOptimalKhr => (
+            &[ColorAttachmentOutput, EarlyFragmentTests, LateFragmentTests],
+            &[
+                Access::ColorAttachmentRead,
+                Access::ColorAttachmentWrite,
+                Access::DepthStencilAttachmentRead,
+                Access::DepthStencilAttachmentWrite,
+            ],
+        ),
+    };
+
+    (
+        stages
+            .iter()
+            .fold(PipelineStageFlags::new(), |flags, &stage| {
+                flags.with_stage(stage)
+            }),
+        access.iter().fold(AccessFlags::new(), |flags, &access| {
+            flags.with_access(access)
+        }),
+    )
+}
+
+/// Stages and accesses that last used an image in `layout`, for the source scope of a
+/// transition from it. Only writes have to be made available.
+pub fn layout_src_scope(layout: ImageLayout) -> (PipelineStageFlags, AccessFlags) {
+    match layout {
+        ImageLayout::Undefined => (PipelineStage::TopOfPipe.into(), AccessFlags::new()),
+        ImageLayout::Preinitialized => (PipelineStage::Host.into(), Access::HostWrite.into()),
+        // An acquired swapchain image is ordered by a semaphore waited on at this stage.
+        ImageLayout::PresentSrcKhr | ImageLayout::SharedPresentKhr => (
+            PipelineStage::ColorAttachmentOutput.into(),
+            AccessFlags::new(),
+        ),
+        _ => {
+            let (stages, access) = layout_dst_scope(layout);
+            (stages, access.writes())
+        }
+    }
+}
+
+/// [`layout_dst_scope`] restricted to the stages supported by a queue of `queue_family` and
+/// the accesses they perform. Layouts no stage of the queue uses wait on all commands.
+pub fn layout_dst_scope_for_queue(
+    layout: ImageLayout,
+    queue_family: &QueueFamilyProperties,
+) -> (PipelineStageFlags, AccessFlags) {
+    let (stages, access) = layout_dst_scope(layout);
+    restrict_scope(stages, access, queue_family)
+}
+
+/// [`layout_src_scope`] restricted to the stages supported by a queue of `queue_family` and
+/// the accesses they perform. Layouts no stage of the queue uses wait on all commands.
+pub fn layout_src_scope_for_queue(
+    layout: ImageLayout,
+    queue_family: &QueueFamilyProperties,
+) -> (PipelineStageFlags, AccessFlags) {
+    let (stages, access) = layout_src_scope(layout);
+    restrict_scope(stages, access, queue_family)
+}
+
+fn restrict_scope(
+    stages: PipelineStageFlags,
+    access: AccessFlags,
+    queue_family: &QueueFamilyProperties,
+) -> (PipelineStageFlags, AccessFlags) {
+    let supported = stages.supported_by(queue_family);
+    if supported.is_empty() && !stages.is_empty() {
+        return (PipelineStage::AllCommands.into(), AccessFlags::new());
+    }
+
+    (supported, access.supported_by(supported))
+}
+
+impl MemoryBarrier {
+    pub fn new(
+        src_stages: PipelineStageFlags,
+        src_access: AccessFlags,
+        dst_stages: PipelineStageFlags,
+        dst_access: AccessFlags,
+    ) -> Self {
+        Self {
+            src_stages,
+            src_access,
+            dst_stages,
+            dst_access,
+        }
+    }
+}
+
+impl From<&MemoryBarrier> for ffi::MemoryBarrier {
+    fn from(barrier: &MemoryBarrier) -> Self {
+        Self {
+            s_type: ffi::StructureType::MemoryBarrier,
+            p_next: std::ptr::null(),
+            src_access_mask: barrier.src_access.to_legacy(),
+            dst_access_mask: barrier.dst_access.to_legacy(),
+        }
+    }
+}
+
+impl From<&MemoryBarrier> for ffi::MemoryBarrier2 {
+    fn from(barrier: &MemoryBarrier) -> Self {
+        Self {
+            s_type: ffi::StructureType::MemoryBarrier2,
+            p_next: std::ptr::null(),
+            src_stage_mask: barrier.src_stages.0,
+            src_access_mask: barrier.src_access.0,
+            dst_stage_mask: barrier.dst_stages.0,
+            dst_access_mask: barrier.dst_access.0,
+        }
+    }
+}
+
+impl<'r> BufferMemoryBarrier<'r> {
+    /// Barrier on the whole buffer.
+    pub fn new(
+        buffer: &'r Buffer<'r>,
+        src_stages: PipelineStageFlags,
+        src_access: AccessFlags,
+        dst_stages: PipelineStageFlags,
+        dst_access: AccessFlags,
+    ) -> Self {
+        Self {
+            buffer,
+            offset: 0,
+            size: WHOLE_SIZE,
+            src_stages,
+            src_access,
+            dst_stages,
+            dst_access,
+            src_queue_family_index: ffi::QUEUE_FAMILY_IGNORED,
+            dst_queue_family_index: ffi::QUEUE_FAMILY_IGNORED,
+        }
+    }
+
+    /// Limits the barrier to `size` bytes starting `offset` bytes into the buffer.
+    pub fn with_range(mut self, offset: u64, size: u64) -> Self {
+        self.offset = offset;
+        self.size = size;
+        self
+    }
+
+    /// Releases the buffer from `src_queue_family_index` when recorded on a queue of that
+    /// family, and acquires it for `dst_queue_family_index` when recorded on a queue of that
+    /// one. Both queues need a matching barrier.
+    pub fn with_queue_family_transfer(
+        mut self,
+        src_queue_family_index: u32,
+        dst_queue_family_index: u32,
+    ) -> Self {
+        self.src_queue_family_index = src_queue_family_index;
+        self.dst_queue_family_index = dst_queue_family_index;
+        self
+    }
+}
+
+impl From<&BufferMemoryBarrier<'_>> for ffi::BufferMemoryBarrier {
+    fn from(barrier: &BufferMemoryBarrier) -> Self {
+        Self {
+            s_type: ffi::StructureType::BufferMemoryBarrier,
+            p_next: std::ptr::null(),
+            src_access_mask: barrier.src_access.to_legacy(),
+            dst_access_mask: barrier.dst_access.to_legacy(),
+            src_queue_family_index: barrier.src_queue_family_index,
+            dst_queue_family_index: barrier.dst_queue_family_index,
+            #[cfg(target_pointer_width = "64")]
+            buffer: barrier.buffer.handle.as_ptr(),
+            #[cfg(not(target_pointer_width = "64"))]
+            buffer: barrier.buffer.handle.get(),
+            offset: barrier.offset,
+            size: barrier.size,
+        }
+    }
+}
+
+impl From<&BufferMemoryBarrier<'_>> for ffi::BufferMemoryBarrier2 {
+    fn from(barrier: &BufferMemoryBarrier) -> Self {
+        Self {
+            s_type: ffi::StructureType::BufferMemoryBarrier2,
+            p_next: std::ptr::null(),
+            src_stage_mask: barrier.src_stages.0,
+            src_access_mask: barrier.src_access.0,
+            dst_stage_mask: barrier.dst_stages.0,
+            dst_access_mask: barrier.dst_access.0,
+            src_queue_family_index: barrier.src_queue_family_index,
+            dst_queue_family_index: barrier.dst_queue_family_index,
+            #[cfg(target_pointer_width = "64")]
+            buffer: barrier.buffer.handle.as_ptr(),
+            #[cfg(not(target_pointer_width = "64"))]
+            buffer: barrier.buffer.handle.get(),
+            offset: barrier.offset,
+            size: barrier.size,
+        }
+    }
+}
+
+impl<'r> ImageMemoryBarrier<'r> {
+    /// Transition of all subresources of `image`, with the source and destination scopes of
+    /// [`layout_src_scope`] and [`layout_dst_scope`].
+    pub fn new(image: &'r Image<'r>, old_layout: ImageLayout, new_layout: ImageLayout) -> Self {
+        let (src_stages, src_access) = layout_src_scope(old_layout);
+        let (dst_stages, dst_access) = layout_dst_scope(new_layout);
+
+        Self {
+            image,
+            subresource_range: image.subresource_range(),
+            old_layout,
+            new_layout,
+            src_stages,
+            src_access,
+            dst_stages,
+            dst_access,
+            src_queue_family_index: ffi::QUEUE_FAMILY_IGNORED,
+            dst_queue_family_index: ffi::QUEUE_FAMILY_IGNORED,
+        }
+    }
+
+    /// Transition of all subresources of `image` recorded on a queue of `queue_family`, with
+    /// the source and destination scopes of [`layout_src_scope_for_queue`] and
+    /// [`layout_dst_scope_for_queue`].
+    pub fn for_queue(
+        image: &'r Image<'r>,
+        old_layout: ImageLayout,
+        new_layout: ImageLayout,
+        queue_family: &QueueFamilyProperties,
+    ) -> Self {
+        let (src_stages, src_access) = layout_src_scope_for_queue(old_layout, queue_family);
+        let (dst_stages, dst_access) = layout_dst_scope_for_queue(new_layout, queue_family);
+
+        Self {
+            src_stages,
+            src_access,
+            dst_stages,
+            dst_access,
+            ..Self::new(image, old_layout, new_layout)
+        }
+    }
+
+    pub fn with_subresource_range(mut self, subresource_range: ImageSubresourceRange) -> Self {
+        self.subresource_range = subresource_range;
+        self
+    }
+
+    /// Overrides the source scope inferred from the old layout.
+    pub fn with_src(mut self, src_stages: PipelineStageFlags, src_access: AccessFlags) -> Self {
+        self.src_stages = src_stages;
+        self.src_access = src_access;
+        self
+    }
+
+    /// Overrides the destination scope inferred from the new layout.
+    pub fn with_dst(mut self, dst_stages: PipelineStageFlags, dst_access: AccessFlags) -> Self {
+        self.dst_stages = dst_stages;
+        self.dst_access = dst_access;
+        self
+    }
+
+    /// See [`BufferMemoryBarrier::with_queue_family_transfer`].
+    pub fn with_queue_family_transfer(
+        mut self,
+        src_queue_family_index: u32,
+        dst_queue_family_index: u32,
+    ) -> Self {
+        self.src_queue_family_index = src_queue_family_index;
+        self.dst_queue_family_index = dst_queue_family_index;
+        self
+    }
+
+    pub fn old_layout(&self) -> ImageLayout {
+        self.old_layout
+    }
+
+    pub fn new_layout(&self) -> ImageLayout {
+        self.new_layout
+    }
+
+    pub(crate) fn is_queue_family_transfer(&self) -> bool {
+        self.src_queue_family_index != self.dst_queue_family_index
+    }
+
+    /// Updates the tracked layouts of the image, see [`Image::track_layouts`].
+    pub(crate) fn record_transition(&self) {
+        self.image.record_transition(
+            &self.subresource_range,
+            self.old_layout,
+            self.new_layout,
+            self.is_queue_family_transfer(),
+        );
+    }
+
+    /// The barrier for legacy commands. Unless synchronization2 is enabled, the layouts only
+    /// known to it are replaced, see [`ImageLayout::to_legacy`].
+    pub(crate) fn to_legacy(self, features: &PhysicalDeviceFeatures) -> ffi::ImageMemoryBarrier {
+        let layout = |layout: ImageLayout| {
+            if features.synchronization2 {
+                layout
+            } else {
+                layout.to_legacy(self.subresource_range.aspect_mask())
+            }
+        };
+
+        ffi::ImageMemoryBarrier {
+            s_type: ffi::StructureType::ImageMemoryBarrier,
+            p_next: std::ptr::null(),
+            src_access_mask: self.src_access.to_legacy(),
+            dst_access_mask: self.dst_access.to_legacy(),
+            old_layout: layout(self.old_layout).into(),
+            new_layout: layout(self.new_layout).into(),
+            src_queue_family_index: self.src_queue_family_index,
+            dst_queue_family_index: self.dst_queue_family_index,
+            #[cfg(target_pointer_width = "64")]
+            image: self.image.handle.as_ptr(),
+            #[cfg(not(target_pointer_width = "64"))]
+            image: self.image.handle.get(),
+            subresource_range: self.subresource_range.into(),
+        }
+    }
+}
+
+impl From<&ImageMemoryBarrier<'_>> for ffi::ImageMemoryBarrier2 {
+    fn from(barrier: &ImageMemoryBarrier) -> Self {
+        Self {
+            s_type: ffi::StructureType::ImageMemoryBarrier2,
+            p_next: std::ptr::null(),
+            src_stage_mask: barrier.src_stages.0,
+            src_access_mask: barrier.src_access.0,
+            dst_stage_mask: barrier.dst_stages.0,
+            dst_access_mask: barrier.dst_access.0,
+            old_layout: barrier.old_layout.into(),
+            new_layout: barrier.new_layout.into(),
+            src_queue_family_index: barrier.src_queue_family_index,
+            dst_queue_family_index: barrier.dst_queue_family_index,
+            #[cfg(target_pointer_width = "64")]
+            image: barrier.image.handle.as_ptr(),
+            #[cfg(not(target_pointer_width = "64"))]
+            image: barrier.image.handle.get(),
+            subresource_range: barrier.subresource_range.into(),
+        }
+    }
+}
+
+impl<'r> DependencyInfo<'r> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Makes the dependencies framebuffer-local, only between the same framebuffer regions.
+    pub fn with_by_region(mut self, by_region: bool) -> Self {
+        self.by_region = by_region;
+        self
+    }
+
+    pub fn with_memory_barrier(mut self, barrier: MemoryBarrier) -> Self {
+        self.memory_barriers.push(barrier);
+        self
+    }
+
+    pub fn with_buffer_barrier(mut self, barrier: BufferMemoryBarrier<'r>) -> Self {
+        self.buffer_barriers.push(barrier);
+        self
+    }
+
+    pub fn with_image_barrier(mut self, barrier: ImageMemoryBarrier<'r>) -> Self {
+        self.image_barriers.push(barrier);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.memory_barriers.is_empty()
+            && self.buffer_barriers.is_empty()
+            && self.image_barriers.is_empty()
+    }
+
+    pub fn memory_barriers(&self) -> &[MemoryBarrier] {
+        &self.memory_barriers
+    }
+
+    pub fn buffer_barriers(&self) -> &[BufferMemoryBarrier<'r>] {
+        &self.buffer_barriers
+    }
+
+    pub fn image_barriers(&self) -> &[ImageMemoryBarrier<'r>] {
+        &self.image_barriers
+    }
+
+    pub(crate) fn dependency_flags(&self) -> u32 {
+        if self.by_region {
+            ffi::DependencyFlagBits::ByRegionBit as u32
+        } else {
+            0
+        }
+    }
+
+    /// Source and destination stages of all barriers combined, for `vkCmdPipelineBarrier`.
+    /// Empty scopes become the top and bottom of the pipeline.
+    pub(crate) fn combined_stages(&self, features: &PhysicalDeviceFeatures) -> (u32, u32) {
+        let stages = self
+            .memory_barriers
+            .iter()
+            .map(|barrier| (barrier.src_stages, barrier.dst_stages))
+            .chain(
+                self.buffer_barriers
+                    .iter()
+                    .map(|barrier| (barrier.src_stages, barrier.dst_stages)),
+            )
+            .chain(
+                self.image_barriers
+                    .iter()
+                    .map(|barrier| (barrier.src_stages, barrier.dst_stages)),
+            );
+        let (src_stages, dst_stages) =
+            stages.fold((0, 0), |(src, dst), (barrier_src, barrier_dst)| {
+                (
+                    src | barrier_src.to_legacy(features),
+                    dst | barrier_dst.to_legacy(features),
+                )
+            });
+
+        (
+            if src_stages == 0 {
+                ffi::PipelineStageFlagBits::TopOfPipeBit as u32
+            } else {
+                src_stages
+            },
+            if dst_stages == 0 {
+                ffi::PipelineStageFlagBits::BottomOfPipeBit as u32
+            } else {
+                dst_stages
+            },
+        )
+    }
+}
@@ -51,6 +51,10 @@ pub struct SwapchainKhr<'a> {
     device: &'a Device<'a>,
     #[cfg(target_pointer_width = "64")]
     _marker: PhantomData<ffi::VkSwapchainKhr_T>,
+    pub(crate) image_format: Format,
+    pub(crate) image_extent: Extent2D,
+    pub(crate) image_array_layers: u32,
+    pub(crate) image_usage: ImageUsageFlags,
 }
 
 pub struct SwapchainBuilderKhr<'a, 'b> {
@@ -269,6 +273,10 @@ impl<'a, 'b> SwapchainBuilderKhr<'a, 'b> {
                 device,
                 #[cfg(target_pointer_width = "64")]
                 _marker: PhantomData,
+                image_format: self.image_format,
+                image_extent: self.image_extent,
+                image_array_layers: self.image_array_layers,
+                image_usage: self.image_usage,
             }),
             ffi::Result::ErrorOutOfHostMemory => Err(Error::OutOfHostMemory),
             ffi::Result::ErrorOutOfDeviceMemory => Err(Error::OutOfDeviceMemory),
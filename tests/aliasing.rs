@@ -0,0 +1,182 @@
+use vulkan_rs::format::Format;
+use vulkan_rs::graph::aliasing::AliasingPlan;
+use vulkan_rs::graph::{
+    BufferAccess, ImageAccess, ImageDesc, PassKind, RenderGraph, ResourceHandle,
+};
+use vulkan_rs::resource::{ImageLayout, MemoryRequirements};
+use vulkan_rs::sync::{Access, AccessFlags, PipelineStage, PipelineStageFlags};
+
+const MIB: u64 = 1024 * 1024;
+
+#[test]
+fn image_chain_aliases_dead_images() {
+    let mut graph = RenderGraph::new();
+    let gbuffer = graph.create_image(ImageDesc::new(Format::R8g8b8a8Unorm, (1280, 720, 1)));
+    let hdr = graph.create_image(ImageDesc::new(Format::R16g16b16a16Sfloat, (1280, 720, 1)));
+    let bloom = graph.create_image(ImageDesc::new(Format::R8g8b8a8Unorm, (640, 360, 1)));
+    let backbuffer = graph.import_image(ImageLayout::Undefined);
+    graph.export_image(backbuffer, ImageLayout::PresentSrcKhr);
+
+    graph
+        .add_pass("gbuffer", PassKind::Graphics)
+        .write_image(gbuffer, ImageAccess::ColorAttachment);
+    graph
+        .add_pass("lighting", PassKind::Graphics)
+        .read_image(gbuffer, ImageAccess::Sampled)
+        .write_image(hdr, ImageAccess::ColorAttachment);
+    graph
+        .add_pass("bloom", PassKind::Graphics)
+        .read_image(hdr, ImageAccess::Sampled)
+        .write_image(bloom, ImageAccess::ColorAttachment);
+    graph
+        .add_pass("tonemap", PassKind::Graphics)
+        .read_image(hdr, ImageAccess::Sampled)
+        .read_image(bloom, ImageAccess::Sampled)
+        .write_image(backbuffer, ImageAccess::ColorAttachment);
+
+    let compiled = graph.compile().unwrap();
+    assert_eq!(compiled.transients().len(), 3);
+
+    let plan = AliasingPlan::new(&compiled, 1, |resource| {
+        let size = match resource {
+            ResourceHandle::Image(image) if image == gbuffer => 8 * MIB,
+            ResourceHandle::Image(image) if image == hdr => 16 * MIB,
+            _ => 4 * MIB,
+        };
+        (MemoryRequirements::new(size, 64 * 1024, 1), 0)
+    });
+    let gbuffer_placement = plan.placement(ResourceHandle::Image(gbuffer)).unwrap();
+    let hdr_placement = plan.placement(ResourceHandle::Image(hdr)).unwrap();
+    let bloom_placement = plan.placement(ResourceHandle::Image(bloom)).unwrap();
+    assert_eq!(hdr_placement.offset(), 0);
+    assert_eq!(gbuffer_placement.offset(), 16 * MIB);
+    assert_eq!(bloom_placement.offset(), gbuffer_placement.offset());
+    assert!(plan.placement(ResourceHandle::Image(backbuffer)).is_none());
+
+    assert_eq!(plan.heaps().len(), 1);
+    assert_eq!(plan.heaps()[0].size(), 24 * MIB);
+    assert_eq!(plan.unaliased_size(), 28 * MIB);
+    assert_eq!(plan.aliased_size(), 24 * MIB);
+    assert_eq!(plan.saved_size(), 4 * MIB);
+
+    let before = compiled.passes()[2].barriers().image_barriers()[1];
+    assert_eq!(before.image(), bloom);
+    assert!(before.src_stages().is_empty());
+
+    let compiled = compiled.with_aliasing(&plan);
+    let passes = compiled.passes();
+    let barrier = passes[2].barriers().image_barriers()[1];
+    assert_eq!(barrier.image(), bloom);
+    assert_eq!(barrier.old_layout(), ImageLayout::Undefined);
+    // The gbuffer was last sampled after its transition, which already waited for the
+    // attachment write.
+    assert_eq!(
+        barrier.src_stages(),
+        PipelineStageFlags::new()
+            .with_stage(PipelineStage::PreRasterizationShaders)
+            .with_stage(PipelineStage::FragmentShader)
+    );
+    assert!(barrier.src_access().is_empty());
+
+    // The gbuffer and the hdr target share no memory with earlier resources.
+    assert!(passes[0].barriers().image_barriers()[0]
+        .src_stages()
+        .is_empty());
+    assert!(passes[1].barriers().image_barriers()[1]
+        .src_stages()
+        .is_empty());
+}
+
+#[test]
+fn buffer_chain_adds_aliasing_barriers() {
+    let mut graph = RenderGraph::new();
+    let first = graph.create_buffer(MIB);
+    let second = graph.create_buffer(MIB);
+    let first_output = graph.import_buffer();
+    let second_output = graph.import_buffer();
+
+    graph
+        .add_pass("produce first", PassKind::Compute)
+        .write_buffer(first, BufferAccess::Storage);
+    graph
+        .add_pass("consume first", PassKind::Compute)
+        .read_buffer(first, BufferAccess::Storage)
+        .write_buffer(first_output, BufferAccess::Storage);
+    graph
+        .add_pass("produce second", PassKind::Compute)
+        .read_buffer(first_output, BufferAccess::Storage)
+        .write_buffer(second, BufferAccess::Storage);
+    graph
+        .add_pass("consume second", PassKind::Compute)
+        .read_buffer(second, BufferAccess::Storage)
+        .write_buffer(second_output, BufferAccess::Storage);
+
+    let compiled = graph.compile().unwrap();
+    let plan = AliasingPlan::new(&compiled, 4096, |_| {
+        (MemoryRequirements::new(MIB, 256, 1), 0)
+    });
+    assert_eq!(
+        plan.placement(ResourceHandle::Buffer(second))
+            .unwrap()
+            .offset(),
+        0
+    );
+    assert_eq!(plan.aliased_size(), MIB);
+    assert_eq!(plan.saved_size(), MIB);
+
+    assert!(compiled.passes()[2]
+        .barriers()
+        .buffer_barriers()
+        .iter()
+        .all(|barrier| barrier.buffer() != second));
+
+    let compiled = compiled.with_aliasing(&plan);
+    let buffer_barriers = compiled.passes()[2].barriers().buffer_barriers();
+    let barrier = buffer_barriers
+        .iter()
+        .find(|barrier| barrier.buffer() == second)
+        .unwrap();
+    assert_eq!(barrier.src_stages(), PipelineStage::ComputeShader.into());
+    assert_eq!(
+        barrier.src_access(),
+        AccessFlags::from(Access::ShaderStorageWrite)
+    );
+    assert_eq!(barrier.dst_stages(), PipelineStage::ComputeShader.into());
+    assert_eq!(
+        barrier.dst_access(),
+        AccessFlags::from(Access::ShaderStorageWrite)
+    );
+}
+
+#[test]
+fn overlapping_lifetimes_do_not_alias() {
+    let mut graph = RenderGraph::new();
+    let vertices = graph.create_buffer(MIB);
+    let target = graph.create_image(ImageDesc::new(Format::R8g8b8a8Unorm, (256, 256, 1)));
+    let output = graph.import_image(ImageLayout::Undefined);
+
+    graph
+        .add_pass("skinning", PassKind::Compute)
+        .write_buffer(vertices, BufferAccess::Storage);
+    graph
+        .add_pass("draw", PassKind::Graphics)
+        .read_buffer(vertices, BufferAccess::Vertex)
+        .write_image(target, ImageAccess::ColorAttachment);
+    graph
+        .add_pass("copy", PassKind::Transfer)
+        .read_image(target, ImageAccess::Transfer)
+        .write_image(output, ImageAccess::Transfer);
+
+    let compiled = graph.compile().unwrap();
+    let plan = AliasingPlan::new(&compiled, 64 * 1024, |resource| match resource {
+        ResourceHandle::Image(_) => (MemoryRequirements::new(MIB, 4096, 1), 0),
+        ResourceHandle::Buffer(_) => (MemoryRequirements::new(1000, 16, 1), 0),
+    });
+    let image = plan.placement(ResourceHandle::Image(target)).unwrap();
+    let buffer = plan.placement(ResourceHandle::Buffer(vertices)).unwrap();
+    assert_eq!(image.offset(), 0);
+    // Buffers and images in one heap are kept on different granularity pages.
+    assert_eq!(buffer.offset(), MIB);
+    assert_eq!(plan.aliased_size(), MIB + 1000);
+    assert_eq!(plan.saved_size(), 0);
+}
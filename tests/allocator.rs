@@ -0,0 +1,506 @@
+use vulkan_rs::core::{Error, Result};
+use vulkan_rs::device::{MemoryHeap, MemoryType, PhysicalDeviceMemoryProperties};
+use vulkan_rs::resource::allocator::{
+    AllocationRequest, AllocationStrategy, AllocatorBuilder, AllocatorStatistics, Linear,
+    MemoryBackend, Pool, ResourceKind, Tlsf,
+};
+use vulkan_rs::resource::{
+    DedicatedResource, MemoryPropertyFlags, MemoryPropertyFlagsBuilder, MemoryRequirements,
+};
+
+const MIB: u64 = 1024 * 1024;
+
+/// Hands out memory until the heap of a memory type runs out.
+struct Simulated {
+    heap_budgets: Vec<u64>,
+    properties: PhysicalDeviceMemoryProperties,
+}
+
+#[derive(Debug)]
+struct SimulatedMemory {
+    size: u64,
+}
+
+impl MemoryBackend for Simulated {
+    type Memory = SimulatedMemory;
+
+    fn allocate(
+        &mut self,
+        size: u64,
+        memory_type_index: u32,
+        _dedicated: Option<DedicatedResource>,
+    ) -> Result<SimulatedMemory> {
+        let heap_index = self.properties.memory_types[memory_type_index as usize].heap_index;
+        let budget = &mut self.heap_budgets[heap_index as usize];
+        if *budget < size {
+            return Err(Error::OutOfDeviceMemory);
+        }
+        *budget -= size;
+        Ok(SimulatedMemory { size })
+    }
+}
+
+fn device_local() -> MemoryPropertyFlags {
+    MemoryPropertyFlagsBuilder::new().device_local(true).build()
+}
+
+fn host_visible() -> MemoryPropertyFlags {
+    MemoryPropertyFlagsBuilder::new()
+        .host_visible(true)
+        .host_coherent(true)
+        .build()
+}
+
+fn host_cached() -> MemoryPropertyFlags {
+    MemoryPropertyFlagsBuilder::new().host_cached(true).build()
+}
+
+/// A discrete GPU: device local memory, two host memory types and a small device local heap
+/// that the host can write to.
+fn memory_properties() -> PhysicalDeviceMemoryProperties {
+    let memory_type = |property_flags: &mut MemoryPropertyFlagsBuilder, heap_index| MemoryType {
+        property_flags: property_flags.build(),
+        heap_index,
+    };
+
+    PhysicalDeviceMemoryProperties {
+        memory_types: vec![
+            memory_type(MemoryPropertyFlagsBuilder::new().device_local(true), 0),
+            memory_type(
+                MemoryPropertyFlagsBuilder::new()
+                    .host_visible(true)
+                    .host_coherent(true),
+                1,
+            ),
+            memory_type(
+                MemoryPropertyFlagsBuilder::new()
+                    .host_visible(true)
+                    .host_coherent(true)
+                    .host_cached(true),
+                1,
+            ),
+            memory_type(
+                MemoryPropertyFlagsBuilder::new()
+                    .device_local(true)
+                    .host_visible(true)
+                    .host_coherent(true),
+                2,
+            ),
+        ],
+        memory_heaps: vec![
+            MemoryHeap {
+                size: 8192 * MIB,
+                device_local: true,
+            },
+            MemoryHeap {
+                size: 16384 * MIB,
+                device_local: false,
+            },
+            MemoryHeap {
+                size: 256 * MIB,
+                device_local: true,
+            },
+        ],
+    }
+}
+
+fn backend() -> Simulated {
+    let properties = memory_properties();
+    Simulated {
+        heap_budgets: properties
+            .memory_heaps
+            .iter()
+            .map(|heap| heap.size)
+            .collect(),
+        properties,
+    }
+}
+
+fn request(size: u64, alignment: u64) -> AllocationRequest<'static> {
+    AllocationRequest::new(MemoryRequirements::new(size, alignment, 0b1111))
+}
+
+#[test]
+fn memory_type_selection() {
+    let mut allocator = AllocatorBuilder::new(memory_properties()).build(backend());
+
+    let allocation = allocator
+        .allocate(&request(MIB, 256).with_required_properties(device_local()))
+        .unwrap();
+    assert_eq!(allocation.memory_type_index(), 0);
+
+    let allocation = allocator
+        .allocate(
+            &request(MIB, 256)
+                .with_required_properties(host_visible())
+                .with_preferred_properties(host_cached()),
+        )
+        .unwrap();
+    assert_eq!(allocation.memory_type_index(), 2);
+
+    let allocation = allocator
+        .allocate(
+            &AllocationRequest::new(MemoryRequirements::new(MIB, 256, 0b1011))
+                .with_required_properties(host_visible())
+                .with_preferred_properties(host_cached()),
+        )
+        .unwrap();
+    assert_eq!(allocation.memory_type_index(), 1);
+
+    assert_eq!(
+        allocator
+            .allocate(
+                &AllocationRequest::new(MemoryRequirements::new(MIB, 256, 0b0001))
+                    .with_required_properties(host_visible())
+            )
+            .unwrap_err(),
+        Error::NoSuitableMemoryType
+    );
+}
+
+#[test]
+fn allocations_share_blocks() {
+    let mut allocator = AllocatorBuilder::new(memory_properties())
+        .with_block_size(64 * MIB)
+        .build(backend());
+
+    let mut allocations: Vec<_> = (1..=16)
+        .map(|i| allocator.allocate(&request(i * 1000, 256)).unwrap())
+        .collect();
+    allocations.sort_by_key(|allocation| allocation.offset());
+    for pair in allocations.windows(2) {
+        assert_eq!(pair[0].offset() % 256, 0);
+        assert!(pair[0].offset() + pair[0].size() <= pair[1].offset());
+    }
+
+    assert_eq!(
+        allocator.statistics(),
+        AllocatorStatistics {
+            block_count: 1,
+            dedicated_allocation_count: 0,
+            allocation_count: 16,
+            block_bytes: 64 * MIB,
+            allocated_bytes: (1..=16).map(|i| i * 1000).sum(),
+        }
+    );
+    assert_eq!(allocator.memory(&allocations[0]).size, 64 * MIB);
+
+    for allocation in allocations {
+        allocator.free(allocation);
+    }
+    // The empty block is kept and can hold an allocation of its whole size again.
+    let allocation = allocator.allocate(&request(32 * MIB, 256)).unwrap();
+    assert_eq!(allocation.offset(), 0);
+    assert_eq!(allocator.statistics().block_count, 1);
+}
+
+#[test]
+fn empty_blocks_are_released() {
+    let mut allocator = AllocatorBuilder::new(memory_properties())
+        .with_block_size(16 * MIB)
+        .build(backend());
+
+    let allocations: Vec<_> = (0..4)
+        .map(|_| allocator.allocate(&request(6 * MIB, 256)).unwrap())
+        .collect();
+    assert_eq!(allocator.statistics().block_count, 2);
+
+    for allocation in allocations {
+        allocator.free(allocation);
+    }
+    assert_eq!(allocator.statistics().block_count, 1);
+    assert_eq!(allocator.statistics().allocated_bytes, 0);
+}
+
+#[test]
+fn granularity_separates_linear_and_optimal_resources() {
+    for (granularity, block_count) in [(1, 1), (1024, 2)] {
+        let mut allocator = AllocatorBuilder::new(memory_properties())
+            .with_buffer_image_granularity(granularity)
+            .build(backend());
+
+        allocator.allocate(&request(100, 4)).unwrap();
+        allocator
+            .allocate(&request(100, 4).with_kind(ResourceKind::Optimal))
+            .unwrap();
+        assert_eq!(allocator.statistics().block_count, block_count);
+    }
+}
+
+#[test]
+fn dedicated_allocations() {
+    let mut allocator = AllocatorBuilder::new(memory_properties())
+        .with_block_size(64 * MIB)
+        .build(backend());
+
+    let preferred = allocator
+        .allocate(&AllocationRequest::new(
+            MemoryRequirements::new(MIB, 256, 0b1111).with_prefers_dedicated_allocation(true),
+        ))
+        .unwrap();
+    assert!(preferred.is_dedicated());
+    assert_eq!(preferred.offset(), 0);
+
+    let large = allocator.allocate(&request(40 * MIB, 256)).unwrap();
+    assert!(large.is_dedicated());
+
+    let small = allocator.allocate(&request(MIB, 256)).unwrap();
+    assert!(!small.is_dedicated());
+
+    let statistics = allocator.statistics();
+    assert_eq!(statistics.block_count, 3);
+    assert_eq!(statistics.dedicated_allocation_count, 2);
+    assert_eq!(statistics.block_bytes, 41 * MIB + 64 * MIB);
+
+    allocator.free(preferred);
+    allocator.free(large);
+    assert_eq!(allocator.statistics().block_count, 1);
+}
+
+#[test]
+fn small_heaps_use_smaller_blocks() {
+    let mut allocator = AllocatorBuilder::new(memory_properties()).build(backend());
+
+    allocator
+        .allocate(
+            &request(MIB, 256).with_required_properties(
+                MemoryPropertyFlagsBuilder::new()
+                    .device_local(true)
+                    .host_visible(true)
+                    .build(),
+            ),
+        )
+        .unwrap();
+    assert_eq!(allocator.memory_type_statistics(3).block_bytes, 32 * MIB);
+}
+
+#[test]
+fn out_of_memory_falls_back() {
+    let mut backend = backend();
+    backend.heap_budgets[0] = 100 * MIB;
+    let mut allocator = AllocatorBuilder::new(memory_properties()).build(backend);
+
+    // 256 MiB and 128 MiB blocks do not fit in what is left of the heap.
+    let allocation = allocator
+        .allocate(&request(MIB, 256).with_preferred_properties(device_local()))
+        .unwrap();
+    assert_eq!(allocation.memory_type_index(), 0);
+    assert_eq!(allocator.memory_type_statistics(0).block_bytes, 64 * MIB);
+
+    // Device local memory with host access is tried next, then host memory.
+    let allocation = allocator
+        .allocate(&request(70 * MIB, 256).with_preferred_properties(device_local()))
+        .unwrap();
+    assert_eq!(allocation.memory_type_index(), 3);
+    let allocation = allocator
+        .allocate(&request(240 * MIB, 256).with_preferred_properties(device_local()))
+        .unwrap();
+    assert_eq!(allocation.memory_type_index(), 1);
+
+    assert_eq!(
+        allocator
+            .allocate(&request(240 * MIB, 256).with_required_properties(device_local()))
+            .unwrap_err(),
+        Error::OutOfDeviceMemory
+    );
+}
+
+#[test]
+fn memory_allocation_count_limit() {
+    let mut allocator = AllocatorBuilder::new(memory_properties())
+        .with_max_memory_allocation_count(2)
+        .build(backend());
+
+    let dedicated =
+        MemoryRequirements::new(MIB, 256, 0b1111).with_prefers_dedicated_allocation(true);
+    let allocation = allocator
+        .allocate(&AllocationRequest::new(dedicated))
+        .unwrap();
+    allocator
+        .allocate(&AllocationRequest::new(dedicated))
+        .unwrap();
+    assert_eq!(
+        allocator
+            .allocate(&AllocationRequest::new(dedicated))
+            .unwrap_err(),
+        Error::TooManyObjects
+    );
+
+    allocator.free(allocation);
+    allocator
+        .allocate(&AllocationRequest::new(dedicated))
+        .unwrap();
+}
+
+#[test]
+fn linear_strategy_resets_per_frame() {
+    let mut allocator = AllocatorBuilder::new(memory_properties())
+        .with_block_size(MIB)
+        .with_strategy(Linear::new)
+        .build(backend());
+
+    for _ in 0..3 {
+        let offsets: Vec<_> = (0..4)
+            .map(|_| allocator.allocate(&request(1000, 256)).unwrap().offset())
+            .collect();
+        assert_eq!(offsets, [0, 1024, 2048, 3072]);
+        allocator.reset();
+    }
+    assert_eq!(allocator.statistics().block_count, 1);
+    assert_eq!(allocator.statistics().allocation_count, 0);
+}
+
+#[test]
+fn pool_strategy_reuses_slots() {
+    let mut allocator = AllocatorBuilder::new(memory_properties())
+        .with_block_size(MIB)
+        .with_strategy(|capacity| Pool::new(capacity, 4096))
+        .build(backend());
+
+    let first = allocator.allocate(&request(4000, 256)).unwrap();
+    let second = allocator.allocate(&request(100, 256)).unwrap();
+    assert_eq!((first.offset(), second.offset()), (0, 4096));
+
+    allocator.free(first);
+    assert_eq!(allocator.allocate(&request(4096, 256)).unwrap().offset(), 0);
+
+    assert_eq!(
+        allocator.allocate(&request(5000, 256)).unwrap_err(),
+        Error::UnsupportedAllocation {
+            size: 5000,
+            alignment: 256
+        }
+    );
+}
+
+#[test]
+fn tlsf_alignment_and_exact_fit() {
+    let mut tlsf = Tlsf::new(4096);
+
+    assert_eq!(tlsf.allocate(4096, 4096), Some(0));
+    assert_eq!(tlsf.allocate(1, 1), None);
+    tlsf.free(0);
+
+    assert_eq!(tlsf.allocate(10, 1), Some(0));
+    assert_eq!(tlsf.allocate(100, 1024), Some(1024));
+    // The gap left by the alignment is used for later allocations.
+    assert_eq!(tlsf.allocate(1000, 8), Some(16));
+    assert_eq!(tlsf.allocate(3072, 1), None);
+    assert_eq!(tlsf.allocate(2944, 64), Some(1152));
+
+    tlsf.reset();
+    assert_eq!(tlsf.allocate(4096, 1), Some(0));
+}
+
+#[test]
+fn tlsf_random_allocations_do_not_overlap() {
+    const CAPACITY: u64 = 1 << 20;
+    let mut tlsf = Tlsf::new(CAPACITY);
+    let mut live: Vec<(u64, u64)> = Vec::new();
+
+    // Linear congruential generator, to stay deterministic without dependencies.
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut random = move |bound: u64| {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (state >> 33) % bound
+    };
+
+    for _ in 0..5000 {
+        if live.is_empty() || random(3) > 0 {
+            let size = random(16 * 1024) + 1;
+            let alignment = 1 << random(10);
+            if let Some(offset) = tlsf.allocate(size, alignment) {
+                assert_eq!(offset % alignment, 0);
+                assert!(offset + size <= CAPACITY);
+                assert!(live
+                    .iter()
+                    .all(|&(other, other_size)| offset + size <= other
+                        || other + other_size <= offset));
+                live.push((offset, size));
+            }
+        } else {
+            let (offset, _) = live.swap_remove(random(live.len() as u64) as usize);
+            tlsf.free(offset);
+        }
+    }
+
+    for (offset, _) in live.drain(..) {
+        tlsf.free(offset);
+    }
+    // Every range merged back into one.
+    assert_eq!(tlsf.allocate(CAPACITY, 1), Some(0));
+}
+
+#[test]
+fn reset_invalidates_allocations() {
+    let mut allocator = AllocatorBuilder::new(memory_properties())
+        .with_block_size(MIB)
+        .with_strategy(Linear::new)
+        .build(backend());
+
+    let stale = allocator.allocate(&request(1000, 256)).unwrap();
+    allocator.reset();
+    let live = allocator.allocate(&request(1000, 256)).unwrap();
+    assert_eq!(live.offset(), stale.offset());
+
+    // Freeing an allocation from before the reset neither underflows the counts nor frees
+    // the live allocation in the same range.
+    allocator.free(stale);
+    assert_eq!(allocator.statistics().allocation_count, 1);
+    assert_eq!(
+        allocator.allocate(&request(1000, 256)).unwrap().offset(),
+        1024
+    );
+
+    allocator.free(live);
+    assert_eq!(allocator.statistics().allocation_count, 1);
+}
+
+#[test]
+#[should_panic(expected = "allocation was invalidated by a reset")]
+fn memory_of_reset_allocation() {
+    let mut allocator = AllocatorBuilder::new(memory_properties())
+        .with_block_size(MIB)
+        .build(backend());
+
+    let stale = allocator.allocate(&request(1000, 256)).unwrap();
+    allocator.reset();
+    allocator.allocate(&request(1000, 256)).unwrap();
+    allocator.memory(&stale);
+}
+
+#[test]
+fn required_dedicated_allocation_needs_a_resource() {
+    let mut allocator = AllocatorBuilder::new(memory_properties()).build(backend());
+
+    assert_eq!(
+        allocator
+            .allocate(&AllocationRequest::new(
+                MemoryRequirements::new(MIB, 256, 0b1111).with_requires_dedicated_allocation(true),
+            ))
+            .unwrap_err(),
+        Error::DedicatedResourceRequired
+    );
+    assert_eq!(allocator.statistics().block_count, 0);
+}
+
+#[test]
+fn invalid_alignments() {
+    let mut allocator = AllocatorBuilder::new(memory_properties()).build(backend());
+    for alignment in [0, 3, 96] {
+        assert_eq!(
+            allocator.allocate(&request(1000, alignment)).unwrap_err(),
+            Error::UnsupportedAllocation {
+                size: 1000,
+                alignment
+            }
+        );
+    }
+
+    assert_eq!(Tlsf::new(4096).allocate(16, 0), None);
+    assert_eq!(Linear::new(4096).allocate(16, 0), None);
+    assert_eq!(Pool::new(4096, 256).allocate(16, 0), None);
+    assert_eq!(Tlsf::new(4096).allocate(16, 48), None);
+}
@@ -0,0 +1,225 @@
+use vulkan_rs::device::{PhysicalDeviceFeatures, QueueFamilyProperties};
+use vulkan_rs::resource::{ImageAspectFlagsBuilder, ImageLayout};
+use vulkan_rs::sync::{
+    layout_dst_scope, layout_dst_scope_for_queue, layout_src_scope, layout_src_scope_for_queue,
+    Access, AccessFlags, PipelineStage, PipelineStageFlags,
+};
+
+// Queue flag bits of `VkQueueFlagBits`.
+const GRAPHICS: u32 = 0x1;
+const COMPUTE: u32 = 0x2;
+const TRANSFER: u32 = 0x4;
+
+fn queue_family(queue_flags: u32) -> QueueFamilyProperties {
+    QueueFamilyProperties {
+        queue_flags,
+        queue_count: 1,
+    }
+}
+
+#[test]
+fn transfer_to_shader_read_scopes() {
+    let (src_stages, src_access) = layout_src_scope(ImageLayout::TransferDstOptimal);
+    assert!(src_stages.contains(PipelineStage::Transfer));
+    assert_eq!(src_access, AccessFlags::from(Access::TransferWrite));
+
+    let (dst_stages, dst_access) = layout_dst_scope(ImageLayout::ShaderReadOnlyOptimal);
+    assert!(dst_stages.contains(PipelineStage::FragmentShader));
+    assert!(dst_stages.contains(PipelineStage::ComputeShader));
+    assert!(dst_access.contains(Access::ShaderRead));
+    assert!(!dst_access.contains(Access::ShaderWrite));
+}
+
+#[test]
+fn source_scopes_only_contain_writes() {
+    let (_, access) = layout_src_scope(ImageLayout::ShaderReadOnlyOptimal);
+    assert!(access.is_empty());
+
+    let (_, access) = layout_src_scope(ImageLayout::ColorAttachmentOptimal);
+    assert_eq!(access, AccessFlags::from(Access::ColorAttachmentWrite));
+
+    let (stages, access) = layout_src_scope(ImageLayout::Undefined);
+    assert_eq!(stages, PipelineStage::TopOfPipe.into());
+    assert!(access.is_empty());
+}
+
+#[test]
+fn present_scopes() {
+    let (stages, access) = layout_dst_scope(ImageLayout::PresentSrcKhr);
+    assert_eq!(stages, PipelineStage::BottomOfPipe.into());
+    assert!(access.is_empty());
+
+    let (stages, _) = layout_src_scope(ImageLayout::PresentSrcKhr);
+    assert_eq!(stages, PipelineStage::ColorAttachmentOutput.into());
+}
+
+#[test]
+fn synchronization2_stages_and_accesses() {
+    let stages = PipelineStageFlags::new()
+        .with_stage(PipelineStage::Copy)
+        .with_stage(PipelineStage::Blit);
+    assert!(stages.contains(PipelineStage::Copy));
+    assert!(!stages.contains(PipelineStage::Transfer));
+
+    let access = AccessFlags::new()
+        .with_access(Access::ShaderSampledRead)
+        .with_access(Access::ShaderStorageWrite);
+    assert_eq!(
+        access.writes(),
+        AccessFlags::from(Access::ShaderStorageWrite)
+    );
+}
+
+#[test]
+fn legacy_layouts() {
+    let color = ImageAspectFlagsBuilder::new().color(true).build();
+    let depth = ImageAspectFlagsBuilder::new().depth(true).build();
+    let stencil = ImageAspectFlagsBuilder::new().stencil(true).build();
+
+    assert_eq!(
+        ImageLayout::ReadOnlyOptimalKhr.to_legacy(color),
+        ImageLayout::ShaderReadOnlyOptimal
+    );
+    assert_eq!(
+        ImageLayout::AttachmentOptimalKhr.to_legacy(color),
+        ImageLayout::ColorAttachmentOptimal
+    );
+    for aspect_mask in [depth, stencil] {
+        assert_eq!(
+            ImageLayout::ReadOnlyOptimalKhr.to_legacy(aspect_mask),
+            ImageLayout::DepthStencilReadOnlyOptimal
+        );
+        assert_eq!(
+            ImageLayout::AttachmentOptimalKhr.to_legacy(aspect_mask),
+            ImageLayout::DepthStencilAttachmentOptimal
+        );
+    }
+
+    // Layouts of earlier versions are kept.
+    assert_eq!(
+        ImageLayout::TransferDstOptimal.to_legacy(color),
+        ImageLayout::TransferDstOptimal
+    );
+    assert_eq!(
+        ImageLayout::DepthReadOnlyOptimal.to_legacy(depth),
+        ImageLayout::DepthReadOnlyOptimal
+    );
+}
+
+#[test]
+fn queue_scopes() {
+    let graphics = queue_family(GRAPHICS | COMPUTE | TRANSFER);
+    assert_eq!(
+        layout_dst_scope_for_queue(ImageLayout::ShaderReadOnlyOptimal, &graphics),
+        layout_dst_scope(ImageLayout::ShaderReadOnlyOptimal)
+    );
+
+    let compute = queue_family(COMPUTE | TRANSFER);
+    let (stages, access) = layout_dst_scope_for_queue(ImageLayout::ShaderReadOnlyOptimal, &compute);
+    assert_eq!(stages, PipelineStage::ComputeShader.into());
+    assert_eq!(access, AccessFlags::from(Access::ShaderRead));
+
+    let (stages, access) = layout_dst_scope_for_queue(ImageLayout::ReadOnlyOptimalKhr, &compute);
+    assert_eq!(stages, PipelineStage::ComputeShader.into());
+    assert_eq!(access, AccessFlags::from(Access::ShaderRead));
+
+    let (stages, access) = layout_src_scope_for_queue(ImageLayout::General, &compute);
+    assert_eq!(stages, PipelineStage::AllCommands.into());
+    assert_eq!(access, AccessFlags::from(Access::MemoryWrite));
+
+    // Stages a transfer queue does not have fall back to all commands.
+    let transfer = queue_family(TRANSFER);
+    let (stages, access) = layout_dst_scope_for_queue(ImageLayout::TransferDstOptimal, &transfer);
+    assert_eq!(stages, PipelineStage::Transfer.into());
+    assert_eq!(access, AccessFlags::from(Access::TransferWrite));
+
+    let (stages, access) =
+        layout_dst_scope_for_queue(ImageLayout::ShaderReadOnlyOptimal, &transfer);
+    assert_eq!(stages, PipelineStage::AllCommands.into());
+    assert!(access.is_empty());
+
+    let (stages, access) = layout_src_scope_for_queue(ImageLayout::Undefined, &transfer);
+    assert_eq!(stages, PipelineStage::TopOfPipe.into());
+    assert!(access.is_empty());
+}
+
+#[test]
+fn stages_supported_by_queues() {
+    let stages = PipelineStageFlags::new()
+        .with_stage(PipelineStage::DrawIndirect)
+        .with_stage(PipelineStage::FragmentShader)
+        .with_stage(PipelineStage::ComputeShader)
+        .with_stage(PipelineStage::Blit)
+        .with_stage(PipelineStage::Copy)
+        .with_stage(PipelineStage::Host);
+
+    assert_eq!(
+        stages.supported_by(&queue_family(GRAPHICS | COMPUTE)),
+        stages
+    );
+    assert_eq!(
+        stages.supported_by(&queue_family(COMPUTE)),
+        PipelineStageFlags::new()
+            .with_stage(PipelineStage::DrawIndirect)
+            .with_stage(PipelineStage::ComputeShader)
+            .with_stage(PipelineStage::Copy)
+            .with_stage(PipelineStage::Host)
+    );
+    assert_eq!(
+        stages.supported_by(&queue_family(TRANSFER)),
+        PipelineStageFlags::new()
+            .with_stage(PipelineStage::Copy)
+            .with_stage(PipelineStage::Host)
+    );
+
+    let access = AccessFlags::new()
+        .with_access(Access::InputAttachmentRead)
+        .with_access(Access::ShaderRead)
+        .with_access(Access::TransferWrite);
+    assert_eq!(
+        access.supported_by(PipelineStage::ComputeShader.into()),
+        AccessFlags::from(Access::ShaderRead)
+    );
+    assert_eq!(
+        access.supported_by(PipelineStage::AllCommands.into()),
+        access
+    );
+}
+
+#[test]
+fn legacy_wait_stages() {
+    const VERTEX_SHADER: u32 = 0x8;
+    const GEOMETRY_SHADER: u32 = 0x40;
+    const COMPUTE_SHADER: u32 = 0x800;
+    const TRANSFER: u32 = 0x1000;
+    const ALL_COMMANDS: u32 = 0x10000;
+    let features = PhysicalDeviceFeatures::default();
+
+    // vkQueueSubmit rejects an empty wait stage mask.
+    assert_eq!(
+        PipelineStageFlags::new().to_legacy_wait_stages(&features),
+        ALL_COMMANDS
+    );
+    assert_eq!(
+        PipelineStageFlags::new()
+            .with_stage(PipelineStage::ComputeShader)
+            .with_stage(PipelineStage::Copy)
+            .to_legacy_wait_stages(&features),
+        COMPUTE_SHADER | TRANSFER
+    );
+
+    let pre_rasterization =
+        PipelineStageFlags::new().with_stage(PipelineStage::PreRasterizationShaders);
+    assert_eq!(
+        pre_rasterization.to_legacy_wait_stages(&features),
+        VERTEX_SHADER
+    );
+    let features = PhysicalDeviceFeatures {
+        geometry_shader: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        pre_rasterization.to_legacy_wait_stages(&features),
+        VERTEX_SHADER | GEOMETRY_SHADER
+    );
+}
@@ -0,0 +1,123 @@
+use vulkan_rs::command_buffer::CommandBufferUsageFlags;
+use vulkan_rs::core::Error;
+use vulkan_rs::descriptor::{
+    DescriptorPoolBuilder, DescriptorSetLayoutBinding, DescriptorSetLayoutBuilder, DescriptorType,
+};
+use vulkan_rs::init::{ApiVersion, ApplicationInfo, Instance};
+use vulkan_rs::pipeline::{
+    PipelineBindPoint, PipelineLayoutBuilder, ShaderStage, ShaderStageFlags,
+};
+
+#[test]
+#[ignore = "requires a Vulkan device"]
+fn bind_descriptor_sets() {
+    let app_info = ApplicationInfo {
+        application_name: None,
+        application_version: Default::default(),
+        engine_name: None,
+        engine_version: Default::default(),
+        api_version: ApiVersion::V1_0,
+    };
+    let instance = Instance::builder()
+        .with_application_info(&app_info)
+        .build()
+        .unwrap();
+    let physical_device = instance.enumerate_physical_devices().next().unwrap();
+    let device = physical_device.create_device(&[0], &[&[1.0f32]], None, None);
+
+    let stages = ShaderStageFlags::new().with_stage(ShaderStage::Compute);
+    let set_layout = DescriptorSetLayoutBuilder::new()
+        .with_binding(DescriptorSetLayoutBinding::new(
+            0,
+            DescriptorType::UniformBufferDynamic,
+            2,
+            stages,
+        ))
+        .build(&device)
+        .unwrap();
+    let other_layout = DescriptorSetLayoutBuilder::new()
+        .with_binding(DescriptorSetLayoutBinding::new(
+            0,
+            DescriptorType::StorageBuffer,
+            1,
+            stages,
+        ))
+        .build(&device)
+        .unwrap();
+    let pipeline_layout = PipelineLayoutBuilder::new()
+        .with_set_layouts(&[&set_layout])
+        .build(&device)
+        .unwrap();
+
+    let pool = DescriptorPoolBuilder::new(2)
+        .with_pool_size(DescriptorType::UniformBufferDynamic, 2)
+        .with_pool_size(DescriptorType::StorageBuffer, 1)
+        .build(&device)
+        .unwrap();
+    let sets = pool.allocate(&[&set_layout, &other_layout]).unwrap();
+
+    let alignment = device.limits().min_uniform_buffer_offset_alignment as u32;
+    let command_pool = device.create_command_pool(0);
+    let mut command_buffer = command_pool.allocate_command_buffers(1).pop().unwrap();
+    command_buffer
+        .begin(CommandBufferUsageFlags::default())
+        .unwrap();
+
+    command_buffer
+        .bind_descriptor_sets(
+            PipelineBindPoint::Compute,
+            &pipeline_layout,
+            0,
+            &[&sets[0]],
+            &[0, alignment],
+        )
+        .unwrap();
+    assert_eq!(
+        command_buffer.bind_descriptor_sets(
+            PipelineBindPoint::Compute,
+            &pipeline_layout,
+            1,
+            &[&sets[0]],
+            &[0, alignment],
+        ),
+        Err(Error::IncompatibleDescriptorSet(1))
+    );
+    assert_eq!(
+        command_buffer.bind_descriptor_sets(
+            PipelineBindPoint::Compute,
+            &pipeline_layout,
+            0,
+            &[&sets[1]],
+            &[],
+        ),
+        Err(Error::IncompatibleDescriptorSet(0))
+    );
+    assert_eq!(
+        command_buffer.bind_descriptor_sets(
+            PipelineBindPoint::Compute,
+            &pipeline_layout,
+            0,
+            &[&sets[0]],
+            &[0],
+        ),
+        Err(Error::DynamicOffsetCountMismatch {
+            expected: 2,
+            actual: 1
+        })
+    );
+    if alignment > 1 {
+        assert_eq!(
+            command_buffer.bind_descriptor_sets(
+                PipelineBindPoint::Compute,
+                &pipeline_layout,
+                0,
+                &[&sets[0]],
+                &[0, 1],
+            ),
+            Err(Error::MisalignedMemoryOffset {
+                offset: 1,
+                alignment: alignment.into()
+            })
+        );
+    }
+}
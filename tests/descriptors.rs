@@ -0,0 +1,140 @@
+use vulkan_rs::descriptor::allocator::{DescriptorPoolSizing, DescriptorSetLayoutKey};
+use vulkan_rs::descriptor::{
+    DescriptorBindingFlagsBuilder, DescriptorPoolSize, DescriptorSetLayoutBinding,
+    DescriptorSetLayoutBuilder, DescriptorSetLayoutCreateFlagsBuilder, DescriptorType,
+};
+use vulkan_rs::pipeline::{ShaderStage, ShaderStageFlags};
+
+fn fragment() -> ShaderStageFlags {
+    ShaderStageFlags::new().with_stage(ShaderStage::Fragment)
+}
+
+fn key(builder: &DescriptorSetLayoutBuilder) -> DescriptorSetLayoutKey {
+    DescriptorSetLayoutKey::from(builder)
+}
+
+#[test]
+fn pools_double_up_to_the_maximum() {
+    let sizing = DescriptorPoolSizing::new();
+    let sets: Vec<_> = (0..8).map(|created| sizing.sets(created)).collect();
+    assert_eq!(sets, [64, 128, 256, 512, 1024, 2048, 4096, 4096]);
+    assert_eq!(sizing.sets(u32::MAX), 4096);
+
+    let sizing = DescriptorPoolSizing::new().with_sets_per_pool(100);
+    assert_eq!(sizing.sets(0), 100);
+    assert_eq!(sizing.sets(5), 3200);
+    assert_eq!(sizing.sets(6), 4096);
+
+    // First pools above the maximum keep their size, empty ones hold one set.
+    let sizing = DescriptorPoolSizing::new().with_sets_per_pool(10000);
+    assert_eq!(sizing.sets(3), 10000);
+    let sizing = DescriptorPoolSizing::new().with_sets_per_pool(0);
+    assert_eq!(sizing.sets(0), 1);
+}
+
+#[test]
+fn pool_sizes_scale_with_sets() {
+    let sizing = DescriptorPoolSizing::new().with_pool_sizes(&[
+        DescriptorPoolSize::new(DescriptorType::UniformBuffer, 2),
+        DescriptorPoolSize::new(DescriptorType::CombinedImageSampler, 3),
+    ]);
+    assert_eq!(
+        sizing.pool_sizes(64),
+        [
+            DescriptorPoolSize::new(DescriptorType::UniformBuffer, 128),
+            DescriptorPoolSize::new(DescriptorType::CombinedImageSampler, 192),
+        ]
+    );
+
+    let sizing = sizing.with_pool_sizes(&[DescriptorPoolSize::new(
+        DescriptorType::StorageBuffer,
+        u32::MAX,
+    )]);
+    assert_eq!(
+        sizing.pool_sizes(2),
+        [DescriptorPoolSize::new(
+            DescriptorType::StorageBuffer,
+            u32::MAX
+        )]
+    );
+}
+
+#[test]
+fn layout_keys_ignore_binding_order() {
+    let mut first = DescriptorSetLayoutBuilder::new();
+    first
+        .with_binding(DescriptorSetLayoutBinding::new(
+            0,
+            DescriptorType::UniformBuffer,
+            1,
+            fragment(),
+        ))
+        .with_binding(DescriptorSetLayoutBinding::new(
+            1,
+            DescriptorType::CombinedImageSampler,
+            4,
+            fragment(),
+        ));
+    let mut second = DescriptorSetLayoutBuilder::new();
+    second
+        .with_binding(DescriptorSetLayoutBinding::new(
+            1,
+            DescriptorType::CombinedImageSampler,
+            4,
+            fragment(),
+        ))
+        .with_binding(DescriptorSetLayoutBinding::new(
+            0,
+            DescriptorType::UniformBuffer,
+            1,
+            fragment(),
+        ));
+
+    assert_eq!(key(&first), key(&second));
+}
+
+#[test]
+fn layout_keys_differ_by_bindings_and_flags() {
+    let binding =
+        || DescriptorSetLayoutBinding::new(0, DescriptorType::StorageBuffer, 1, fragment());
+    let mut base = DescriptorSetLayoutBuilder::new();
+    base.with_binding(binding());
+
+    let mut stages = DescriptorSetLayoutBuilder::new();
+    stages.with_binding(DescriptorSetLayoutBinding::new(
+        0,
+        DescriptorType::StorageBuffer,
+        1,
+        fragment().with_stage(ShaderStage::Vertex),
+    ));
+    assert_ne!(key(&base), key(&stages));
+
+    let mut count = DescriptorSetLayoutBuilder::new();
+    count.with_binding(DescriptorSetLayoutBinding::new(
+        0,
+        DescriptorType::StorageBuffer,
+        2,
+        fragment(),
+    ));
+    assert_ne!(key(&base), key(&count));
+
+    let mut binding_flags = DescriptorSetLayoutBuilder::new();
+    binding_flags.with_binding(
+        binding().with_binding_flags(
+            DescriptorBindingFlagsBuilder::new()
+                .partially_bound(true)
+                .build(),
+        ),
+    );
+    assert_ne!(key(&base), key(&binding_flags));
+
+    let mut flags = DescriptorSetLayoutBuilder::new();
+    flags
+        .with_flags(
+            DescriptorSetLayoutCreateFlagsBuilder::new()
+                .push_descriptor_khr(true)
+                .build(),
+        )
+        .with_binding(binding());
+    assert_ne!(key(&base), key(&flags));
+}
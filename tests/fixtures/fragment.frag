@@ -0,0 +1,12 @@
+#version 450
+
+layout(location = 0) in vec2 inUv;
+layout(location = 1) flat in int inIndex;
+
+layout(location = 0) out vec4 outColor;
+layout(location = 1) out ivec2 outId;
+
+layout(input_attachment_index = 0, set = 0, binding = 2) uniform subpassInput gbuffer;
+
+void main() {
+}
@@ -0,0 +1,12 @@
+#version 450
+
+layout(location = 0) in vec3 position;
+layout(location = 1) in vec2 uv;
+layout(location = 2) in uvec4 joints;
+layout(location = 3) in mat4 model;
+
+layout(location = 0) out vec2 outUv;
+layout(location = 1) flat out int outIndex;
+
+void main() {
+}
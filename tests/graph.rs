@@ -0,0 +1,420 @@
+use vulkan_rs::command_buffer::CommandBufferUsageFlags;
+use vulkan_rs::core::Error;
+use vulkan_rs::format::Format;
+use vulkan_rs::graph::{
+    BufferAccess, GraphResources, ImageAccess, ImageDesc, PassKind, RenderGraph,
+};
+use vulkan_rs::init::{ApiVersion, ApplicationInfo, Instance};
+use vulkan_rs::resource::{ImageLayout, ImageUsage};
+use vulkan_rs::sync::{Access, AccessFlags, PipelineStage, PipelineStageFlags};
+
+fn graphics_shaders() -> PipelineStageFlags {
+    PipelineStageFlags::new()
+        .with_stage(PipelineStage::PreRasterizationShaders)
+        .with_stage(PipelineStage::FragmentShader)
+}
+
+fn fragment_tests() -> PipelineStageFlags {
+    PipelineStageFlags::new()
+        .with_stage(PipelineStage::EarlyFragmentTests)
+        .with_stage(PipelineStage::LateFragmentTests)
+}
+
+fn pass_names(graph: &RenderGraph) -> Vec<String> {
+    graph
+        .compile()
+        .unwrap()
+        .passes()
+        .iter()
+        .map(|pass| pass.name().to_owned())
+        .collect()
+}
+
+#[test]
+fn deferred_frame_barriers() {
+    let mut graph = RenderGraph::new();
+    let albedo = graph.create_image(ImageDesc::new(Format::R8g8b8a8Unorm, (1280, 720, 1)));
+    let depth = graph.create_image(ImageDesc::new(Format::D32Sfloat, (1280, 720, 1)));
+    let backbuffer = graph.import_image(ImageLayout::Undefined);
+    graph.export_image(backbuffer, ImageLayout::PresentSrcKhr);
+
+    graph
+        .add_pass("gbuffer", PassKind::Graphics)
+        .write_image(albedo, ImageAccess::ColorAttachment)
+        .write_image(depth, ImageAccess::DepthStencilAttachment);
+    graph
+        .add_pass("lighting", PassKind::Graphics)
+        .read_image(albedo, ImageAccess::Sampled)
+        .read_image(depth, ImageAccess::Sampled)
+        .write_image(backbuffer, ImageAccess::ColorAttachment);
+
+    let compiled = graph.compile().unwrap();
+    assert!(compiled.culled_passes().is_empty());
+    let passes = compiled.passes();
+    assert_eq!(passes.len(), 2);
+
+    let gbuffer = passes[0].barriers().image_barriers();
+    assert_eq!(passes[0].name(), "gbuffer");
+    assert_eq!(gbuffer.len(), 2);
+    assert_eq!(gbuffer[0].image(), albedo);
+    assert_eq!(gbuffer[0].old_layout(), ImageLayout::Undefined);
+    assert_eq!(gbuffer[0].new_layout(), ImageLayout::ColorAttachmentOptimal);
+    assert!(gbuffer[0].src_stages().is_empty());
+    assert_eq!(
+        gbuffer[0].dst_access(),
+        AccessFlags::from(Access::ColorAttachmentWrite)
+    );
+    assert_eq!(gbuffer[1].image(), depth);
+    assert_eq!(
+        gbuffer[1].new_layout(),
+        ImageLayout::DepthStencilAttachmentOptimal
+    );
+    assert_eq!(gbuffer[1].dst_stages(), fragment_tests());
+
+    let lighting = passes[1].barriers().image_barriers();
+    assert_eq!(lighting.len(), 3);
+    assert_eq!(lighting[0].image(), albedo);
+    assert_eq!(
+        lighting[0].old_layout(),
+        ImageLayout::ColorAttachmentOptimal
+    );
+    assert_eq!(lighting[0].new_layout(), ImageLayout::ShaderReadOnlyOptimal);
+    assert_eq!(
+        lighting[0].src_stages(),
+        PipelineStage::ColorAttachmentOutput.into()
+    );
+    assert_eq!(
+        lighting[0].src_access(),
+        AccessFlags::from(Access::ColorAttachmentWrite)
+    );
+    assert_eq!(lighting[0].dst_stages(), graphics_shaders());
+    assert_eq!(
+        lighting[0].dst_access(),
+        AccessFlags::from(Access::ShaderSampledRead)
+    );
+    assert_eq!(lighting[1].image(), depth);
+    assert_eq!(lighting[1].src_stages(), fragment_tests());
+    assert_eq!(
+        lighting[1].src_access(),
+        AccessFlags::from(Access::DepthStencilAttachmentWrite)
+    );
+    assert_eq!(lighting[2].image(), backbuffer);
+    assert_eq!(
+        lighting[2].new_layout(),
+        ImageLayout::ColorAttachmentOptimal
+    );
+
+    let present = compiled.final_barriers().image_barriers();
+    assert_eq!(present.len(), 1);
+    assert_eq!(present[0].image(), backbuffer);
+    assert_eq!(present[0].old_layout(), ImageLayout::ColorAttachmentOptimal);
+    assert_eq!(present[0].new_layout(), ImageLayout::PresentSrcKhr);
+    assert_eq!(
+        present[0].src_stages(),
+        PipelineStage::ColorAttachmentOutput.into()
+    );
+    assert_eq!(present[0].dst_stages(), PipelineStage::BottomOfPipe.into());
+    assert!(present[0].dst_access().is_empty());
+
+    let usage = graph.image_usage(albedo);
+    assert!(usage.contains(ImageUsage::ColorAttachment));
+    assert!(usage.contains(ImageUsage::Sampled));
+    assert!(!usage.contains(ImageUsage::Storage));
+}
+
+#[test]
+fn unused_passes_are_culled() {
+    let mut graph = RenderGraph::new();
+    let debug = graph.create_image(ImageDesc::new(Format::R8g8b8a8Unorm, (64, 64, 1)));
+    let histogram = graph.create_buffer(1024);
+    let output = graph.import_image(ImageLayout::Undefined);
+
+    graph
+        .add_pass("debug view", PassKind::Graphics)
+        .write_image(debug, ImageAccess::ColorAttachment);
+    graph
+        .add_pass("histogram", PassKind::Compute)
+        .read_image(debug, ImageAccess::Sampled)
+        .write_buffer(histogram, BufferAccess::Storage);
+    graph
+        .add_pass("clear", PassKind::Transfer)
+        .write_image(output, ImageAccess::Transfer);
+
+    let compiled = graph.compile().unwrap();
+    assert_eq!(compiled.culled_passes(), ["debug view", "histogram"]);
+    assert_eq!(compiled.passes().len(), 1);
+    assert_eq!(compiled.passes()[0].name(), "clear");
+    assert_eq!(compiled.passes()[0].index(), 2);
+}
+
+#[test]
+fn reads_share_barriers() {
+    let mut graph = RenderGraph::new();
+    let vertices = graph.create_buffer(4096);
+    let targets: Vec<_> = (0..3)
+        .map(|_| graph.import_image(ImageLayout::ColorAttachmentOptimal))
+        .collect();
+
+    graph
+        .add_pass("skinning", PassKind::Compute)
+        .write_buffer(vertices, BufferAccess::Storage);
+    graph
+        .add_pass("draw 0", PassKind::Graphics)
+        .read_buffer(vertices, BufferAccess::Vertex)
+        .write_image(targets[0], ImageAccess::ColorAttachment);
+    graph
+        .add_pass("draw 1", PassKind::Graphics)
+        .read_buffer(vertices, BufferAccess::Vertex)
+        .write_image(targets[1], ImageAccess::ColorAttachment);
+    graph
+        .add_pass("draw 2", PassKind::Graphics)
+        .read_buffer(vertices, BufferAccess::Uniform)
+        .write_image(targets[2], ImageAccess::ColorAttachment);
+
+    let compiled = graph.compile().unwrap();
+    let passes = compiled.passes();
+    assert_eq!(passes.len(), 4);
+    assert!(passes[0].barriers().is_empty());
+
+    let buffer_barriers = passes[1].barriers().buffer_barriers();
+    assert_eq!(buffer_barriers.len(), 1);
+    assert_eq!(buffer_barriers[0].buffer(), vertices);
+    assert_eq!(
+        buffer_barriers[0].src_stages(),
+        PipelineStage::ComputeShader.into()
+    );
+    assert_eq!(
+        buffer_barriers[0].src_access(),
+        AccessFlags::from(Access::ShaderStorageWrite)
+    );
+    assert_eq!(
+        buffer_barriers[0].dst_stages(),
+        PipelineStage::VertexAttributeInput.into()
+    );
+    assert_eq!(
+        buffer_barriers[0].dst_access(),
+        AccessFlags::from(Access::VertexAttributeRead)
+    );
+
+    // The write is already visible to vertex attribute reads.
+    assert!(passes[2].barriers().buffer_barriers().is_empty());
+
+    let buffer_barriers = passes[3].barriers().buffer_barriers();
+    assert_eq!(buffer_barriers.len(), 1);
+    assert_eq!(buffer_barriers[0].dst_stages(), graphics_shaders());
+    assert_eq!(
+        buffer_barriers[0].dst_access(),
+        AccessFlags::from(Access::UniformRead)
+    );
+}
+
+#[test]
+fn write_after_read_is_an_execution_dependency() {
+    let mut graph = RenderGraph::new();
+    let staging = graph.import_buffer();
+    let texture = graph.import_image(ImageLayout::Undefined);
+
+    graph
+        .add_pass("upload", PassKind::Transfer)
+        .read_buffer(staging, BufferAccess::Transfer)
+        .write_image(texture, ImageAccess::Transfer);
+    graph
+        .add_pass("refill", PassKind::Transfer)
+        .write_buffer(staging, BufferAccess::Transfer);
+
+    let compiled = graph.compile().unwrap();
+    let passes = compiled.passes();
+    assert_eq!(passes.len(), 2);
+    assert!(passes[0].barriers().buffer_barriers().is_empty());
+
+    let buffer_barriers = passes[1].barriers().buffer_barriers();
+    assert_eq!(buffer_barriers.len(), 1);
+    assert_eq!(
+        buffer_barriers[0].src_stages(),
+        PipelineStage::Transfer.into()
+    );
+    assert!(buffer_barriers[0].src_access().is_empty());
+    assert_eq!(
+        buffer_barriers[0].dst_access(),
+        AccessFlags::from(Access::TransferWrite)
+    );
+}
+
+#[test]
+fn independent_passes_fill_dependency_gaps() {
+    let mut graph = RenderGraph::new();
+    let occlusion = graph.create_image(ImageDesc::new(Format::R16g16b16a16Sfloat, (512, 512, 1)));
+    let lit = graph.import_buffer();
+    let particles = graph.import_buffer();
+
+    graph
+        .add_pass("ssao", PassKind::Compute)
+        .write_image(occlusion, ImageAccess::Storage);
+    graph
+        .add_pass("apply ssao", PassKind::Compute)
+        .read_image(occlusion, ImageAccess::Storage)
+        .write_buffer(lit, BufferAccess::Storage);
+    graph
+        .add_pass("particles", PassKind::Compute)
+        .write_buffer(particles, BufferAccess::Storage);
+
+    assert_eq!(pass_names(&graph), ["ssao", "particles", "apply ssao"]);
+}
+
+#[test]
+fn invalid_accesses() {
+    let mut graph = RenderGraph::new();
+    let image = graph.create_image(ImageDesc::new(Format::R8g8b8a8Unorm, (16, 16, 1)));
+    graph
+        .add_pass("sampled write", PassKind::Compute)
+        .write_image(image, ImageAccess::Sampled);
+    assert_eq!(
+        graph.compile().unwrap_err(),
+        Error::InvalidPassAccess { pass: 0 }
+    );
+
+    let mut graph = RenderGraph::new();
+    let image = graph.create_image(ImageDesc::new(Format::R8g8b8a8Unorm, (16, 16, 1)));
+    graph
+        .add_pass("compute attachment", PassKind::Compute)
+        .write_image(image, ImageAccess::ColorAttachment);
+    assert_eq!(
+        graph.compile().unwrap_err(),
+        Error::InvalidPassAccess { pass: 0 }
+    );
+
+    let mut graph = RenderGraph::new();
+    let image = graph.create_image(ImageDesc::new(Format::R8g8b8a8Unorm, (16, 16, 1)));
+    let output = graph.import_buffer();
+    graph
+        .add_pass("read before write", PassKind::Compute)
+        .read_image(image, ImageAccess::Sampled)
+        .write_buffer(output, BufferAccess::Storage);
+    assert_eq!(
+        graph.compile().unwrap_err(),
+        Error::UninitializedGraphResource { pass: 0 }
+    );
+}
+
+#[test]
+fn read_modify_write_is_followed_by_a_barrier() {
+    let mut graph = RenderGraph::new();
+    let particles = graph.import_buffer();
+    let field = graph.create_image(ImageDesc::new(Format::R32Sfloat, (64, 64, 1)));
+    let output = graph.import_buffer();
+
+    graph
+        .add_pass("clear field", PassKind::Compute)
+        .write_image(field, ImageAccess::Storage);
+    graph
+        .add_pass("simulate", PassKind::Compute)
+        .read_buffer(particles, BufferAccess::Storage)
+        .write_buffer(particles, BufferAccess::Storage)
+        .read_image(field, ImageAccess::Storage)
+        .write_image(field, ImageAccess::Storage);
+    graph
+        .add_pass("render", PassKind::Compute)
+        .read_buffer(particles, BufferAccess::Storage)
+        .read_image(field, ImageAccess::Storage)
+        .write_buffer(output, BufferAccess::Storage);
+
+    let compiled = graph.compile().unwrap();
+    let passes = compiled.passes();
+    assert_eq!(passes.len(), 3);
+    assert_eq!(passes[2].name(), "render");
+
+    let buffer_barriers = passes[2].barriers().buffer_barriers();
+    assert_eq!(buffer_barriers.len(), 1);
+    assert_eq!(buffer_barriers[0].buffer(), particles);
+    assert_eq!(
+        buffer_barriers[0].src_stages(),
+        PipelineStage::ComputeShader.into()
+    );
+    assert_eq!(
+        buffer_barriers[0].src_access(),
+        AccessFlags::from(Access::ShaderStorageWrite)
+    );
+    assert_eq!(
+        buffer_barriers[0].dst_access(),
+        AccessFlags::from(Access::ShaderStorageRead)
+    );
+
+    let image_barriers = passes[2].barriers().image_barriers();
+    assert_eq!(image_barriers.len(), 1);
+    assert_eq!(image_barriers[0].image(), field);
+    assert_eq!(image_barriers[0].old_layout(), ImageLayout::General);
+    assert_eq!(image_barriers[0].new_layout(), ImageLayout::General);
+    assert_eq!(
+        image_barriers[0].src_access(),
+        AccessFlags::from(Access::ShaderStorageWrite)
+    );
+}
+
+#[test]
+fn read_modify_write_of_uninitialized_resource() {
+    let mut graph = RenderGraph::new();
+    let accumulator = graph.create_buffer(256);
+    let output = graph.import_buffer();
+    graph
+        .add_pass("accumulate", PassKind::Compute)
+        .read_buffer(accumulator, BufferAccess::Storage)
+        .write_buffer(accumulator, BufferAccess::Storage);
+    graph
+        .add_pass("resolve", PassKind::Compute)
+        .read_buffer(accumulator, BufferAccess::Storage)
+        .write_buffer(output, BufferAccess::Storage);
+    assert_eq!(
+        graph.compile().unwrap_err(),
+        Error::UninitializedGraphResource { pass: 0 }
+    );
+}
+
+#[test]
+#[ignore = "requires a Vulkan device"]
+fn record_checks_the_compiled_graph() {
+    let app_info = ApplicationInfo {
+        application_name: None,
+        application_version: Default::default(),
+        engine_name: None,
+        engine_version: Default::default(),
+        api_version: ApiVersion::V1_0,
+    };
+    let instance = Instance::builder()
+        .with_application_info(&app_info)
+        .build()
+        .unwrap();
+    let physical_device = instance.enumerate_physical_devices().next().unwrap();
+    let device = physical_device.create_device(&[0], &[&[1.0f32]], None, None);
+    let command_pool = device.create_command_pool(0);
+    let mut command_buffer = command_pool.allocate_command_buffers(1).pop().unwrap();
+    command_buffer
+        .begin(CommandBufferUsageFlags::default())
+        .unwrap();
+
+    let resources = GraphResources::new();
+    let mut empty = RenderGraph::new();
+    let compiled = empty.compile().unwrap();
+    empty
+        .record(&compiled, &mut command_buffer, &resources)
+        .unwrap();
+
+    // A graph with the same passes is still another graph.
+    assert_eq!(
+        RenderGraph::new().record(&compiled, &mut command_buffer, &resources),
+        Err(Error::CompiledGraphMismatch)
+    );
+
+    let mut graph = RenderGraph::new();
+    let buffer = graph.import_buffer();
+    graph
+        .add_pass("simulate", PassKind::Compute)
+        .write_buffer(buffer, BufferAccess::Storage);
+    let compiled = graph.compile().unwrap();
+    graph
+        .add_pass("integrate", PassKind::Compute)
+        .write_buffer(buffer, BufferAccess::Storage);
+    assert_eq!(
+        graph.record(&compiled, &mut command_buffer, &resources),
+        Err(Error::CompiledGraphMismatch)
+    );
+}
@@ -0,0 +1,172 @@
+use vulkan_rs::core::Error;
+use vulkan_rs::format::Format;
+use vulkan_rs::pipeline::VertexInputAttributeDescription;
+use vulkan_rs::shaders::{EntryPoint, ShaderReflection};
+
+fn entry_point(reflection: &ShaderReflection) -> &EntryPoint {
+    reflection.entry_point("main").unwrap()
+}
+
+/// Assembles a module with a `main` entry point of `execution_model` whose interface is a
+/// `vec4` (or `mat4` if `matrix`) variable of `storage_class` at each of `locations`.
+fn interface_module(
+    execution_model: u32,
+    storage_class: u32,
+    matrix: bool,
+    locations: &[u32],
+) -> ShaderReflection {
+    let variables: Vec<u32> = (0..locations.len() as u32)
+        .map(|index| 20 + index)
+        .collect();
+    let mut instructions: Vec<(u32, Vec<u32>)> = Vec::new();
+    let mut entry_point = vec![execution_model, 10, u32::from_le_bytes(*b"main"), 0];
+    entry_point.extend(&variables);
+    instructions.push((15, entry_point));
+    for (&variable, &location) in variables.iter().zip(locations) {
+        instructions.push((71, vec![variable, 30, location]));
+    }
+    instructions.push((22, vec![1, 32]));
+    instructions.push((23, vec![2, 1, 4]));
+    instructions.push((24, vec![3, 2, 4]));
+    instructions.push((32, vec![4, storage_class, if matrix { 3 } else { 2 }]));
+    for &variable in &variables {
+        instructions.push((59, vec![4, variable, storage_class]));
+    }
+
+    let mut words = vec![0x0723_0203, 0x0001_0000, 0, 64, 0];
+    for (opcode, operands) in instructions {
+        words.push((operands.len() as u32 + 1) << 16 | opcode);
+        words.extend(operands);
+    }
+    let code: Vec<u8> = words.iter().flat_map(|word| word.to_ne_bytes()).collect();
+    ShaderReflection::new(&code).unwrap()
+}
+
+const VERTEX: u32 = 0;
+const FRAGMENT: u32 = 4;
+const INPUT: u32 = 1;
+const OUTPUT: u32 = 3;
+
+#[test]
+fn vertex_attributes() {
+    let reflection = ShaderReflection::new(include_bytes!("fixtures/vertex.spv")).unwrap();
+    let vertex = entry_point(&reflection);
+
+    let attribute = |location, format| VertexInputAttributeDescription::new(location, 0, format, 0);
+    let mut attributes = vec![
+        attribute(0, Format::R32g32b32Sfloat),
+        attribute(1, Format::R32g32Sfloat),
+        attribute(2, Format::R16g16b16a16Uint),
+    ];
+    attributes.extend((3..7).map(|location| attribute(location, Format::R32g32b32a32Sfloat)));
+    assert_eq!(vertex.validate_vertex_attributes(&attributes), Ok(()));
+
+    // Every column of the `mat4` at location 3 needs an attribute.
+    let without_column: Vec<_> = attributes
+        .iter()
+        .copied()
+        .filter(|attribute| attribute.location() != 5)
+        .collect();
+    assert_eq!(
+        vertex.validate_vertex_attributes(&without_column),
+        Err(Error::UnmatchedVertexInput(5))
+    );
+
+    attributes[2] = attribute(2, Format::R32g32b32a32Sfloat);
+    assert_eq!(
+        vertex.validate_vertex_attributes(&attributes),
+        Err(Error::IncompatibleVertexFormat {
+            location: 2,
+            format: Format::R32g32b32a32Sfloat
+        })
+    );
+    attributes[2] = attribute(2, Format::R64g64Uint);
+    assert_eq!(
+        vertex.validate_vertex_attributes(&attributes),
+        Err(Error::IncompatibleVertexFormat {
+            location: 2,
+            format: Format::R64g64Uint
+        })
+    );
+}
+
+#[test]
+fn fragment_inputs() {
+    let vertex = ShaderReflection::new(include_bytes!("fixtures/vertex.spv")).unwrap();
+    let fragment = ShaderReflection::new(include_bytes!("fixtures/fragment.spv")).unwrap();
+    assert_eq!(
+        entry_point(&fragment).validate_inputs_from(entry_point(&vertex)),
+        Ok(())
+    );
+
+    // The example vertex shader only writes a `vec3` at location 0.
+    let example = ShaderReflection::new(include_bytes!("../examples/vert.spv")).unwrap();
+    assert_eq!(
+        entry_point(&fragment).validate_inputs_from(entry_point(&example)),
+        Err(Error::UnmatchedFragmentInput(1))
+    );
+}
+
+#[test]
+fn multi_location_fragment_inputs() {
+    let fragment = interface_module(FRAGMENT, INPUT, true, &[2]);
+    let fragment = entry_point(&fragment);
+
+    let matrix = interface_module(VERTEX, OUTPUT, true, &[2]);
+    assert_eq!(fragment.validate_inputs_from(entry_point(&matrix)), Ok(()));
+    let columns = interface_module(VERTEX, OUTPUT, false, &[2, 3, 4, 5]);
+    assert_eq!(fragment.validate_inputs_from(entry_point(&columns)), Ok(()));
+
+    // Only the first column is written.
+    let first_column = interface_module(VERTEX, OUTPUT, false, &[2]);
+    assert_eq!(
+        fragment.validate_inputs_from(entry_point(&first_column)),
+        Err(Error::UnmatchedFragmentInput(2))
+    );
+    let missing_column = interface_module(VERTEX, OUTPUT, false, &[2, 3, 5]);
+    assert_eq!(
+        fragment.validate_inputs_from(entry_point(&missing_column)),
+        Err(Error::UnmatchedFragmentInput(2))
+    );
+}
+
+#[test]
+fn fragment_outputs() {
+    let fragment = ShaderReflection::new(include_bytes!("fixtures/fragment.spv")).unwrap();
+    let fragment = entry_point(&fragment);
+    assert_eq!(fragment.validate_color_attachments(2), Ok(()));
+    assert_eq!(
+        fragment.validate_color_attachments(1),
+        Err(Error::UnmatchedFragmentOutput(1))
+    );
+
+    let matrix = interface_module(FRAGMENT, OUTPUT, true, &[1]);
+    assert_eq!(entry_point(&matrix).validate_color_attachments(5), Ok(()));
+    assert_eq!(
+        entry_point(&matrix).validate_color_attachments(4),
+        Err(Error::UnmatchedFragmentOutput(1))
+    );
+}
+
+#[test]
+fn locations_past_the_last_one() {
+    let last = u32::MAX - 1;
+    let vertex = interface_module(VERTEX, INPUT, true, &[last]);
+    let vertex = entry_point(&vertex);
+    assert_eq!(vertex.inputs()[0].locations(), last as u64..last as u64 + 4);
+
+    let attributes = [
+        VertexInputAttributeDescription::new(last, 0, Format::R32g32b32a32Sfloat, 0),
+        VertexInputAttributeDescription::new(u32::MAX, 0, Format::R32g32b32a32Sfloat, 0),
+    ];
+    assert_eq!(
+        vertex.validate_vertex_attributes(&attributes),
+        Err(Error::UnmatchedVertexInput(last))
+    );
+
+    let fragment = interface_module(FRAGMENT, OUTPUT, true, &[last]);
+    assert_eq!(
+        entry_point(&fragment).validate_color_attachments(u32::MAX),
+        Err(Error::UnmatchedFragmentOutput(last))
+    );
+}
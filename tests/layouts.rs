@@ -0,0 +1,152 @@
+use vulkan_rs::format::Format;
+use vulkan_rs::resource::{
+    ImageAspectFlagsBuilder, ImageLayout, ImageSubresourceRange, LayoutTracker,
+};
+
+const ALL: u32 = u32::MAX;
+
+fn color(
+    base_mip_level: u32,
+    level_count: u32,
+    base_array_layer: u32,
+    layer_count: u32,
+) -> ImageSubresourceRange {
+    ImageSubresourceRange::new(
+        ImageAspectFlagsBuilder::new().color(true).build(),
+        base_mip_level,
+        level_count,
+        base_array_layer,
+        layer_count,
+    )
+}
+
+fn depth_stencil(depth: bool, stencil: bool) -> ImageSubresourceRange {
+    ImageSubresourceRange::new(
+        ImageAspectFlagsBuilder::new()
+            .depth(depth)
+            .stencil(stencil)
+            .build(),
+        0,
+        ALL,
+        0,
+        ALL,
+    )
+}
+
+#[test]
+fn mip_levels_and_array_layers() {
+    let tracker = LayoutTracker::new(Format::R8g8b8a8Unorm, 4, 2, ImageLayout::Undefined);
+    assert_eq!(
+        tracker.layout(&color(0, ALL, 0, ALL)),
+        Some(ImageLayout::Undefined)
+    );
+
+    tracker.record_transition(
+        &color(0, ALL, 0, ALL),
+        ImageLayout::Undefined,
+        ImageLayout::TransferDstOptimal,
+        false,
+    );
+    tracker.record_transition(
+        &color(1, 2, 1, 1),
+        ImageLayout::TransferDstOptimal,
+        ImageLayout::ShaderReadOnlyOptimal,
+        false,
+    );
+
+    assert_eq!(tracker.layout(&color(0, ALL, 0, ALL)), None);
+    assert_eq!(
+        tracker.layout(&color(1, 2, 1, 1)),
+        Some(ImageLayout::ShaderReadOnlyOptimal)
+    );
+    assert_eq!(
+        tracker.layout(&color(0, ALL, 0, 1)),
+        Some(ImageLayout::TransferDstOptimal)
+    );
+    assert_eq!(
+        tracker.layout(&color(3, 1, 1, 1)),
+        Some(ImageLayout::TransferDstOptimal)
+    );
+    // Ranges past the image select nothing.
+    assert_eq!(tracker.layout(&color(4, 1, 0, 1)), None);
+}
+
+#[test]
+fn depth_and_stencil_are_tracked_separately() {
+    let tracker = LayoutTracker::new(
+        Format::D24UnormS8Uint,
+        1,
+        1,
+        ImageLayout::DepthStencilAttachmentOptimal,
+    );
+    tracker.record_transition(
+        &depth_stencil(true, false),
+        ImageLayout::DepthStencilAttachmentOptimal,
+        ImageLayout::DepthReadOnlyStencilAttachmentOptimal,
+        false,
+    );
+
+    assert_eq!(
+        tracker.layout(&depth_stencil(true, false)),
+        Some(ImageLayout::DepthReadOnlyStencilAttachmentOptimal)
+    );
+    assert_eq!(
+        tracker.layout(&depth_stencil(false, true)),
+        Some(ImageLayout::DepthStencilAttachmentOptimal)
+    );
+    assert_eq!(tracker.layout(&depth_stencil(true, true)), None);
+
+    // Depth-only formats have a single aspect.
+    let tracker = LayoutTracker::new(Format::D32Sfloat, 1, 1, ImageLayout::Undefined);
+    tracker.record_transition(
+        &depth_stencil(true, false),
+        ImageLayout::Undefined,
+        ImageLayout::DepthAttachmentOptimal,
+        false,
+    );
+    assert_eq!(
+        tracker.layout(&depth_stencil(true, false)),
+        Some(ImageLayout::DepthAttachmentOptimal)
+    );
+}
+
+#[test]
+fn ownership_acquire_matches_the_released_layout() {
+    let tracker = LayoutTracker::new(Format::R8g8b8a8Unorm, 1, 1, ImageLayout::TransferDstOptimal);
+    tracker.record_transition(
+        &color(0, ALL, 0, ALL),
+        ImageLayout::TransferDstOptimal,
+        ImageLayout::ShaderReadOnlyOptimal,
+        true,
+    );
+    // The acquire repeats the transition the release already recorded.
+    tracker.record_transition(
+        &color(0, ALL, 0, ALL),
+        ImageLayout::TransferDstOptimal,
+        ImageLayout::ShaderReadOnlyOptimal,
+        true,
+    );
+    assert_eq!(
+        tracker.layout(&color(0, ALL, 0, ALL)),
+        Some(ImageLayout::ShaderReadOnlyOptimal)
+    );
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "image layout mismatch")]
+fn mismatched_old_layout() {
+    let tracker = LayoutTracker::new(Format::D32SfloatS8Uint, 1, 1, ImageLayout::Undefined);
+    tracker.record_transition(
+        &depth_stencil(false, true),
+        ImageLayout::Undefined,
+        ImageLayout::StencilAttachmentOptimal,
+        false,
+    );
+    tracker.record_transition(
+        &depth_stencil(true, false),
+        ImageLayout::StencilAttachmentOptimal,
+        ImageLayout::StencilReadOnlyOptimal,
+        false,
+    );
+}
@@ -0,0 +1,142 @@
+use vulkan_rs::core::Error;
+use vulkan_rs::device::{MemoryHeap, MemoryType, PhysicalDeviceMemoryProperties};
+use vulkan_rs::init::{ApiVersion, ApplicationInfo, Instance};
+use vulkan_rs::resource::{MemoryProperty, MemoryPropertyFlags, MemoryPropertyFlagsBuilder};
+
+fn properties() -> PhysicalDeviceMemoryProperties {
+    let memory_type = |property_flags: MemoryPropertyFlags, heap_index: u32| MemoryType {
+        property_flags,
+        heap_index,
+    };
+
+    PhysicalDeviceMemoryProperties {
+        memory_types: vec![
+            memory_type(
+                MemoryPropertyFlagsBuilder::new().device_local(true).build(),
+                0,
+            ),
+            memory_type(
+                MemoryPropertyFlagsBuilder::new()
+                    .host_visible(true)
+                    .host_coherent(true)
+                    .build(),
+                1,
+            ),
+            memory_type(
+                MemoryPropertyFlagsBuilder::new()
+                    .host_visible(true)
+                    .host_coherent(true)
+                    .host_cached(true)
+                    .build(),
+                1,
+            ),
+            memory_type(
+                MemoryPropertyFlagsBuilder::new()
+                    .device_local(true)
+                    .host_visible(true)
+                    .host_coherent(true)
+                    .build(),
+                0,
+            ),
+        ],
+        memory_heaps: vec![
+            MemoryHeap {
+                size: 8 << 30,
+                device_local: true,
+            },
+            MemoryHeap {
+                size: 16 << 30,
+                device_local: false,
+            },
+        ],
+    }
+}
+
+#[test]
+fn first_type_with_all_properties() {
+    let properties = properties();
+    let host_visible = MemoryPropertyFlagsBuilder::new().host_visible(true).build();
+    let host_cached = MemoryPropertyFlagsBuilder::new()
+        .host_visible(true)
+        .host_cached(true)
+        .build();
+    let device_local = MemoryPropertyFlagsBuilder::new().device_local(true).build();
+
+    assert_eq!(properties.find_memory_type(!0, host_visible), Some(1));
+    assert_eq!(properties.find_memory_type(!0, host_cached), Some(2));
+    assert_eq!(properties.find_memory_type(!0, device_local), Some(0));
+    assert_eq!(
+        properties.find_memory_type(!0, MemoryPropertyFlags::default()),
+        Some(0)
+    );
+}
+
+#[test]
+fn memory_type_bits_restrict_the_types() {
+    let properties = properties();
+    let host_visible = MemoryPropertyFlagsBuilder::new().host_visible(true).build();
+    let device_local = MemoryPropertyFlagsBuilder::new().device_local(true).build();
+
+    assert_eq!(properties.find_memory_type(0b1100, host_visible), Some(2));
+    assert_eq!(properties.find_memory_type(0b1110, device_local), Some(3));
+    assert_eq!(properties.find_memory_type(0b0110, device_local), None);
+    assert_eq!(
+        properties.find_memory_type(0, MemoryPropertyFlags::default()),
+        None
+    );
+}
+
+#[test]
+fn missing_properties() {
+    let properties = properties();
+    let lazily_allocated = MemoryPropertyFlagsBuilder::new()
+        .lazily_allocated(true)
+        .build();
+    assert_eq!(properties.find_memory_type(!0, lazily_allocated), None);
+
+    // Bits beyond the memory types select nothing.
+    let host_visible = MemoryPropertyFlagsBuilder::new().host_visible(true).build();
+    assert_eq!(properties.find_memory_type(1 << 31, host_visible), None);
+}
+
+#[test]
+#[ignore = "requires a Vulkan device"]
+fn empty_ranges_are_not_flushed() {
+    let app_info = ApplicationInfo {
+        application_name: None,
+        application_version: Default::default(),
+        engine_name: None,
+        engine_version: Default::default(),
+        api_version: ApiVersion::V1_0,
+    };
+    let instance = Instance::builder()
+        .with_application_info(&app_info)
+        .build()
+        .unwrap();
+    let physical_device = instance.enumerate_physical_devices().next().unwrap();
+    let device = physical_device.create_device(&[0], &[&[1.0f32]], None, None);
+
+    let memory_properties = device.memory_properties();
+    let host_visible = MemoryPropertyFlagsBuilder::new().host_visible(true).build();
+    let memory_type_index = (0..memory_properties.memory_types.len() as u32)
+        .find(|&index| {
+            let flags = memory_properties.memory_types[index as usize].property_flags;
+            flags.contains(MemoryProperty::HostVisible)
+                && !flags.contains(MemoryProperty::HostCoherent)
+        })
+        .or_else(|| memory_properties.find_memory_type(u32::MAX, host_visible))
+        .unwrap();
+    let mut memory = device.allocate_memory(256, memory_type_index).unwrap();
+    let mut mapped = memory.map(0, 256).unwrap();
+
+    mapped.flush(0, 0).unwrap();
+    mapped.flush(256, 0).unwrap();
+    mapped.invalidate(128, 0).unwrap();
+    assert_eq!(
+        mapped.flush(257, 0),
+        Err(Error::MemoryRangeOutOfBounds {
+            offset: 257,
+            size: 0
+        })
+    );
+}
@@ -0,0 +1,71 @@
+use vulkan_rs::pipeline::PipelineCacheHeader;
+
+const UUID: [u8; 16] = [
+    0x10, 0x32, 0x54, 0x76, 0x98, 0xba, 0xdc, 0xfe, 0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef,
+];
+
+/// Cache data with a `VK_PIPELINE_CACHE_HEADER_VERSION_ONE` header followed by `payload`.
+fn cache_data(header_size: u32, header_version: u32, payload: &[u8]) -> Vec<u8> {
+    let mut data = Vec::new();
+    data.extend_from_slice(&header_size.to_le_bytes());
+    data.extend_from_slice(&header_version.to_le_bytes());
+    data.extend_from_slice(&0x10de_u32.to_le_bytes());
+    data.extend_from_slice(&0x2684_u32.to_le_bytes());
+    data.extend_from_slice(&UUID);
+    data.extend_from_slice(payload);
+    data
+}
+
+#[test]
+fn valid_header() {
+    let expected = PipelineCacheHeader {
+        vendor_id: 0x10de,
+        device_id: 0x2684,
+        pipeline_cache_uuid: UUID,
+    };
+    assert_eq!(
+        PipelineCacheHeader::parse(&cache_data(32, 1, &[])),
+        Some(expected)
+    );
+    assert_eq!(
+        PipelineCacheHeader::parse(&cache_data(32, 1, &[0xff; 64])),
+        Some(expected)
+    );
+
+    // Larger headers of the same version keep the fields in place.
+    assert_eq!(
+        PipelineCacheHeader::parse(&cache_data(48, 1, &[0; 16])),
+        Some(expected)
+    );
+}
+
+#[test]
+fn truncated_header() {
+    let data = cache_data(32, 1, &[]);
+    assert_eq!(PipelineCacheHeader::parse(&[]), None);
+    assert_eq!(PipelineCacheHeader::parse(&data[..4]), None);
+    assert_eq!(PipelineCacheHeader::parse(&data[..31]), None);
+
+    // The header claims to be longer than the data.
+    assert_eq!(
+        PipelineCacheHeader::parse(&cache_data(48, 1, &[0; 15])),
+        None
+    );
+    assert_eq!(
+        PipelineCacheHeader::parse(&cache_data(u32::MAX, 1, &[])),
+        None
+    );
+}
+
+#[test]
+fn invalid_header() {
+    assert_eq!(PipelineCacheHeader::parse(&cache_data(16, 1, &[])), None);
+    assert_eq!(PipelineCacheHeader::parse(&cache_data(0, 1, &[])), None);
+    assert_eq!(PipelineCacheHeader::parse(&cache_data(32, 0, &[])), None);
+    assert_eq!(PipelineCacheHeader::parse(&cache_data(32, 2, &[])), None);
+
+    // The header is little-endian on every host.
+    let mut data = cache_data(32, 1, &[]);
+    data[..4].copy_from_slice(&32_u32.to_be_bytes());
+    assert_eq!(PipelineCacheHeader::parse(&data), None);
+}
@@ -0,0 +1,41 @@
+use vulkan_rs::pipeline::{PushConstantRange, ShaderStage, ShaderStageFlags};
+use vulkan_rs::resource::Pod;
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod)]
+struct Transform {
+    model: [[f32; 4]; 4],
+    tint: [f32; 4],
+    material: u32,
+    flags: u32,
+    _padding: [u32; 2],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod)]
+struct Scalars(f32, u32);
+
+fn assert_pod<T: Pod>() {}
+
+#[test]
+fn derived_types_are_pod() {
+    assert_pod::<Transform>();
+    assert_pod::<Scalars>();
+    assert_pod::<[Scalars; 4]>();
+}
+
+#[test]
+fn range_size_follows_type() {
+    let stages = ShaderStageFlags::new()
+        .with_stage(ShaderStage::Vertex)
+        .with_stage(ShaderStage::Fragment);
+
+    let range = PushConstantRange::of::<Transform>(stages, 0);
+    assert_eq!(range.offset(), 0);
+    assert_eq!(range.size(), 96);
+    assert_eq!(range.stage_flags(), stages);
+
+    let range = PushConstantRange::of::<Scalars>(stages, 96);
+    assert_eq!(range.offset(), 96);
+    assert_eq!(range.size(), 8);
+}
@@ -0,0 +1,200 @@
+use vulkan_rs::core::Error;
+use vulkan_rs::descriptor::DescriptorType;
+use vulkan_rs::pipeline::{ReflectedLayoutBuilder, ShaderStage, ShaderStageFlags};
+use vulkan_rs::shaders::ShaderReflection;
+
+fn reflect(code: &[u8]) -> ShaderReflection {
+    ShaderReflection::new(code).unwrap()
+}
+
+fn assemble(instructions: &[(u32, &[u32])]) -> ShaderReflection {
+    let mut words = vec![0x0723_0203, 0x0001_0000, 0, 64, 0];
+    for &(opcode, operands) in instructions {
+        words.push((operands.len() as u32 + 1) << 16 | opcode);
+        words.extend_from_slice(operands);
+    }
+    let code: Vec<u8> = words.iter().flat_map(|word| word.to_ne_bytes()).collect();
+    reflect(&code)
+}
+
+fn stages(stages: &[ShaderStage]) -> ShaderStageFlags {
+    stages
+        .iter()
+        .fold(ShaderStageFlags::new(), |flags, &stage| {
+            flags.with_stage(stage)
+        })
+}
+
+#[test]
+fn shared_bindings_merge_stages() {
+    let compute = reflect(include_bytes!("fixtures/compute.spv"));
+    let vertex_and_fragment = stages(&[ShaderStage::Vertex, ShaderStage::Fragment]);
+
+    let layout = ReflectedLayoutBuilder::from_reflections(&[
+        (ShaderStage::Vertex, &compute),
+        (ShaderStage::Fragment, &compute),
+    ])
+    .unwrap();
+    assert_eq!(layout.set_count(), 2);
+    assert_eq!(
+        layout.binding(0, 0),
+        Some((DescriptorType::UniformBuffer, 1, vertex_and_fragment))
+    );
+    assert_eq!(
+        layout.binding(1, 0),
+        Some((DescriptorType::CombinedImageSampler, 4, vertex_and_fragment))
+    );
+    assert_eq!(layout.binding(1, 2), None);
+    assert_eq!(layout.binding(2, 0), None);
+
+    // Identical push constant blocks share one range.
+    let ranges = layout.push_constant_ranges();
+    assert_eq!(ranges.len(), 1);
+    assert_eq!(ranges[0].stage_flags(), vertex_and_fragment);
+    assert_eq!((ranges[0].offset(), ranges[0].size()), (0, 16));
+}
+
+#[test]
+fn distinct_bindings_keep_their_stage() {
+    let compute = reflect(include_bytes!("fixtures/compute.spv"));
+    let fragment = reflect(include_bytes!("fixtures/fragment.spv"));
+
+    let mut layout = ReflectedLayoutBuilder::from_reflections(&[
+        (ShaderStage::Compute, &compute),
+        (ShaderStage::Fragment, &fragment),
+    ])
+    .unwrap();
+    assert_eq!(
+        layout.binding(0, 1),
+        Some((
+            DescriptorType::StorageBuffer,
+            1,
+            stages(&[ShaderStage::Compute])
+        ))
+    );
+    assert_eq!(
+        layout.binding(0, 2),
+        Some((
+            DescriptorType::InputAttachment,
+            1,
+            stages(&[ShaderStage::Fragment])
+        ))
+    );
+    assert_eq!(layout.push_constant_ranges().len(), 1);
+    assert_eq!(
+        layout.push_constant_ranges()[0].stage_flags(),
+        stages(&[ShaderStage::Compute])
+    );
+
+    layout.with_descriptor_count(1, 0, 16).unwrap();
+    assert_eq!(
+        layout.with_descriptor_count(3, 0, 8).unwrap_err(),
+        Error::UndeclaredBinding { set: 3, binding: 0 }
+    );
+    assert_eq!(
+        layout.with_descriptor_count(0, 7, 8).unwrap_err(),
+        Error::UndeclaredBinding { set: 0, binding: 7 }
+    );
+    assert_eq!(
+        layout.binding(1, 0),
+        Some((
+            DescriptorType::CombinedImageSampler,
+            16,
+            stages(&[ShaderStage::Compute])
+        ))
+    );
+    assert_eq!(layout.set_count(), 2);
+}
+
+#[test]
+fn incompatible_binding() {
+    let compute = reflect(include_bytes!("fixtures/compute.spv"));
+    // A sampler at set 0, binding 0, where the compute shader has a uniform buffer.
+    let sampler = assemble(&[
+        (71, &[3, 34, 0]),
+        (71, &[3, 33, 0]),
+        (26, &[1]),
+        (32, &[2, 0, 1]),
+        (59, &[2, 3, 0]),
+    ]);
+
+    assert_eq!(
+        ReflectedLayoutBuilder::from_reflections(&[
+            (ShaderStage::Compute, &compute),
+            (ShaderStage::Fragment, &sampler),
+        ])
+        .unwrap_err(),
+        Error::IncompatibleBinding { set: 0, binding: 0 }
+    );
+}
+
+#[test]
+fn push_constant_ranges_start_at_the_first_member() {
+    let compute = reflect(include_bytes!("fixtures/compute.spv"));
+    // A push constant block of type `struct { int a; int b; }` whose members are at offsets
+    // 18 and 20, after the bytes used by another stage.
+    let block = assemble(&[
+        (71, &[2, 2]),
+        (72, &[2, 0, 35, 18]),
+        (72, &[2, 1, 35, 20]),
+        (21, &[1, 32, 1]),
+        (30, &[2, 1, 1]),
+        (32, &[3, 9, 2]),
+        (59, &[3, 4, 9]),
+    ]);
+
+    let layout = ReflectedLayoutBuilder::from_reflections(&[
+        (ShaderStage::Vertex, &compute),
+        (ShaderStage::Fragment, &block),
+    ])
+    .unwrap();
+    let ranges: Vec<_> = layout
+        .push_constant_ranges()
+        .iter()
+        .map(|range| (range.stage_flags(), range.offset(), range.size()))
+        .collect();
+    // The range starts at the first member rounded down to a multiple of 4.
+    assert_eq!(
+        ranges,
+        [
+            (stages(&[ShaderStage::Vertex]), 0, 16),
+            (stages(&[ShaderStage::Fragment]), 16, 8),
+        ]
+    );
+}
+
+#[test]
+fn covered_push_constant_ranges_are_merged() {
+    let compute = reflect(include_bytes!("fixtures/compute.spv"));
+    // A push constant block of type `struct { int a; int b; }` whose members are at offsets
+    // 0 and 20, covering the 16 bytes used by another stage.
+    let block = assemble(&[
+        (71, &[2, 2]),
+        (72, &[2, 0, 35, 0]),
+        (72, &[2, 1, 35, 20]),
+        (21, &[1, 32, 1]),
+        (30, &[2, 1, 1]),
+        (32, &[3, 9, 2]),
+        (59, &[3, 4, 9]),
+    ]);
+    let all = stages(&[ShaderStage::Vertex, ShaderStage::Fragment]);
+
+    for reflections in [
+        [
+            (ShaderStage::Vertex, &compute),
+            (ShaderStage::Fragment, &block),
+        ],
+        [
+            (ShaderStage::Fragment, &block),
+            (ShaderStage::Vertex, &compute),
+        ],
+    ] {
+        let layout = ReflectedLayoutBuilder::from_reflections(&reflections).unwrap();
+        let ranges: Vec<_> = layout
+            .push_constant_ranges()
+            .iter()
+            .map(|range| (range.stage_flags(), range.offset(), range.size()))
+            .collect();
+        assert_eq!(ranges, [(all, 0, 24)]);
+    }
+}
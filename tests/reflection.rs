@@ -0,0 +1,290 @@
+use vulkan_rs::core::Error;
+use vulkan_rs::descriptor::DescriptorType;
+use vulkan_rs::pipeline::ShaderStage;
+use vulkan_rs::shaders::{NumericType, ShaderReflection, SpirvError};
+
+fn reflect(code: &[u8]) -> ShaderReflection {
+    ShaderReflection::new(code).unwrap()
+}
+
+#[test]
+fn compute_entry_point() {
+    let reflection = reflect(include_bytes!("fixtures/compute.spv"));
+
+    assert_eq!(reflection.entry_points().len(), 1);
+    let entry_point = reflection.entry_point("main").unwrap();
+    assert_eq!(entry_point.stage(), ShaderStage::Compute);
+    assert_eq!(entry_point.workgroup_size(), Some([64, 2, 1]));
+    assert!(entry_point.inputs().is_empty());
+    assert!(entry_point.outputs().is_empty());
+}
+
+#[test]
+fn descriptor_bindings() {
+    let reflection = reflect(include_bytes!("fixtures/compute.spv"));
+
+    let bindings: Vec<_> = reflection
+        .descriptor_bindings()
+        .iter()
+        .map(|binding| {
+            (
+                binding.set(),
+                binding.binding(),
+                binding.descriptor_type(),
+                binding.descriptor_count(),
+                binding.name(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        bindings,
+        [
+            (0, 0, DescriptorType::UniformBuffer, 1, Some("params")),
+            (0, 1, DescriptorType::StorageBuffer, 1, Some("data")),
+            (
+                1,
+                0,
+                DescriptorType::CombinedImageSampler,
+                4,
+                Some("textures")
+            ),
+            (1, 1, DescriptorType::StorageImage, 1, Some("target")),
+        ]
+    );
+}
+
+#[test]
+fn push_constant_block() {
+    let reflection = reflect(include_bytes!("fixtures/compute.spv"));
+
+    let block = reflection.push_constant_block().unwrap();
+    assert_eq!(block.size(), 16);
+    let members: Vec<_> = block
+        .members()
+        .iter()
+        .map(|member| (member.name(), member.offset(), member.size()))
+        .collect();
+    assert_eq!(
+        members,
+        [
+            (Some("offset"), 0, 4),
+            (Some("scale"), 4, 4),
+            (Some("extent"), 8, 8),
+        ]
+    );
+}
+
+#[test]
+fn specialization_constants() {
+    let reflection = reflect(include_bytes!("fixtures/compute.spv"));
+
+    let constants: Vec<_> = reflection
+        .specialization_constants()
+        .iter()
+        .map(|constant| (constant.constant_id(), constant.size(), constant.name()))
+        .collect();
+    assert_eq!(constants, [(0, 4, Some("USE_BIAS")), (1, 4, Some("SCALE"))]);
+}
+
+#[test]
+fn workgroup_size_from_specialization_constants() {
+    let reflection = reflect(include_bytes!("fixtures/workgroup.spv"));
+
+    let entry_point = reflection.entry_point("main").unwrap();
+    assert_eq!(entry_point.workgroup_size(), Some([32, 4, 1]));
+    let ids: Vec<_> = reflection
+        .specialization_constants()
+        .iter()
+        .map(|constant| constant.constant_id())
+        .collect();
+    assert_eq!(ids, [0, 1]);
+}
+
+#[test]
+fn vertex_inputs_and_outputs() {
+    let reflection = reflect(include_bytes!("fixtures/vertex.spv"));
+
+    let entry_point = reflection.entry_point("main").unwrap();
+    assert_eq!(entry_point.stage(), ShaderStage::Vertex);
+    assert_eq!(entry_point.workgroup_size(), None);
+
+    let inputs: Vec<_> = entry_point
+        .inputs()
+        .iter()
+        .map(|input| {
+            (
+                input.location(),
+                input.location_count(),
+                input.numeric_type(),
+                input.component_count(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        inputs,
+        [
+            (0, 1, NumericType::Float, 3),
+            (1, 1, NumericType::Float, 2),
+            (2, 1, NumericType::Uint, 4),
+            (3, 4, NumericType::Float, 4),
+        ]
+    );
+
+    // The built-in `gl_PerVertex` block has no location.
+    let outputs: Vec<_> = entry_point
+        .outputs()
+        .iter()
+        .map(|output| (output.location(), output.numeric_type(), output.name()))
+        .collect();
+    assert_eq!(
+        outputs,
+        [
+            (0, NumericType::Float, Some("outUv")),
+            (1, NumericType::Sint, Some("outIndex")),
+        ]
+    );
+}
+
+#[test]
+fn fragment_outputs_and_input_attachment() {
+    let reflection = reflect(include_bytes!("fixtures/fragment.spv"));
+
+    let entry_point = reflection.entry_point("main").unwrap();
+    assert_eq!(entry_point.stage(), ShaderStage::Fragment);
+    assert_eq!(entry_point.inputs().len(), 2);
+
+    let outputs: Vec<_> = entry_point
+        .outputs()
+        .iter()
+        .map(|output| {
+            (
+                output.location(),
+                output.numeric_type(),
+                output.component_count(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        outputs,
+        [(0, NumericType::Float, 4), (1, NumericType::Sint, 2)]
+    );
+
+    let binding = &reflection.descriptor_bindings()[0];
+    assert_eq!(binding.descriptor_type(), DescriptorType::InputAttachment);
+    assert_eq!((binding.set(), binding.binding()), (0, 2));
+}
+
+#[test]
+fn compiled_example_shaders() {
+    let vertex = reflect(include_bytes!("../examples/vert.spv"));
+    let entry_point = vertex.entry_point("main").unwrap();
+    assert_eq!(entry_point.stage(), ShaderStage::Vertex);
+    assert!(entry_point.inputs().is_empty());
+    assert_eq!(entry_point.outputs().len(), 1);
+    assert_eq!(entry_point.outputs()[0].component_count(), 3);
+
+    let fragment = reflect(include_bytes!("../examples/frag.spv"));
+    let entry_point = fragment.entry_point("main").unwrap();
+    assert_eq!(entry_point.stage(), ShaderStage::Fragment);
+    assert_eq!(entry_point.inputs()[0].component_count(), 3);
+    assert_eq!(entry_point.outputs()[0].component_count(), 4);
+    assert!(fragment.descriptor_bindings().is_empty());
+    assert!(fragment.push_constant_block().is_none());
+}
+
+#[test]
+fn malformed_code() {
+    let code = include_bytes!("fixtures/compute.spv");
+
+    assert_eq!(
+        ShaderReflection::new(&code[..code.len() - 2]).unwrap_err(),
+        Error::InvalidSpirv(SpirvError::InvalidLength(code.len() - 2))
+    );
+    assert_eq!(
+        ShaderReflection::new(&[0; 20]).unwrap_err(),
+        Error::InvalidSpirv(SpirvError::InvalidMagicNumber(0))
+    );
+
+    // Claims a second word for the final `OpFunctionEnd`.
+    let mut truncated = code.to_vec();
+    let len = truncated.len();
+    truncated[len - 4..].copy_from_slice(&(2 << 16 | 56u32).to_ne_bytes());
+    assert_eq!(
+        ShaderReflection::new(&truncated).unwrap_err(),
+        Error::InvalidSpirv(SpirvError::TruncatedInstruction(len / 4 - 1))
+    );
+}
+
+/// Assembles a SPIR-V 1.0 module from instructions given as opcode and operands.
+fn assemble(instructions: &[(u32, &[u32])]) -> Vec<u8> {
+    let mut words = vec![0x0723_0203, 0x0001_0000, 0, 64, 0];
+    for &(opcode, operands) in instructions {
+        words.push((operands.len() as u32 + 1) << 16 | opcode);
+        words.extend_from_slice(operands);
+    }
+    words.iter().flat_map(|word| word.to_ne_bytes()).collect()
+}
+
+#[test]
+fn overflowing_types() {
+    // A uniform constant of type `int[65536][65536]`.
+    let code = assemble(&[
+        (71, &[6, 33, 0]),
+        (21, &[1, 32, 1]),
+        (43, &[1, 2, 0x1_0000]),
+        (28, &[3, 1, 2]),
+        (28, &[4, 3, 2]),
+        (32, &[5, 0, 4]),
+        (59, &[5, 6, 0]),
+    ]);
+    assert_eq!(
+        ShaderReflection::new(&code).unwrap_err(),
+        Error::InvalidSpirv(SpirvError::TypeOverflow(4))
+    );
+
+    // A push constant block of type `struct { int[65536][65536] }`.
+    let code = assemble(&[
+        (21, &[1, 32, 1]),
+        (43, &[1, 2, 0x1_0000]),
+        (28, &[3, 1, 2]),
+        (28, &[4, 3, 2]),
+        (30, &[5, 4]),
+        (32, &[6, 9, 5]),
+        (59, &[6, 7, 9]),
+    ]);
+    assert_eq!(
+        ShaderReflection::new(&code).unwrap_err(),
+        Error::InvalidSpirv(SpirvError::TypeOverflow(4))
+    );
+}
+
+#[test]
+fn self_containing_types() {
+    // A push constant block with an array of itself, as only malformed code declares.
+    let code = assemble(&[
+        (21, &[1, 32, 1]),
+        (43, &[1, 2, 4]),
+        (28, &[3, 3, 2]),
+        (30, &[4, 3]),
+        (32, &[5, 9, 4]),
+        (59, &[5, 6, 9]),
+    ]);
+    assert_eq!(
+        ShaderReflection::new(&code).unwrap_err(),
+        Error::InvalidSpirv(SpirvError::TypeTooDeep(3))
+    );
+
+    // A uniform constant of such an array type with a single element.
+    let code = assemble(&[
+        (71, &[6, 33, 0]),
+        (21, &[1, 32, 1]),
+        (43, &[1, 2, 1]),
+        (28, &[3, 3, 2]),
+        (32, &[5, 0, 3]),
+        (59, &[5, 6, 0]),
+    ]);
+    assert_eq!(
+        ShaderReflection::new(&code).unwrap_err(),
+        Error::InvalidSpirv(SpirvError::TypeTooDeep(3))
+    );
+}
@@ -0,0 +1,72 @@
+use vulkan_rs::core::{Error, Result};
+use vulkan_rs::format::Format;
+use vulkan_rs::render_pass::{
+    AttachmentDescription, AttachmentReference, RenderPassBuilder, SubpassDescription,
+    ATTACHMENT_UNUSED,
+};
+use vulkan_rs::resource::ImageLayout;
+
+/// Validates a render pass of a color and a depth attachment and `subpass`.
+fn validate(subpass: SubpassDescription) -> Result<()> {
+    RenderPassBuilder::new()
+        .with_attachment(AttachmentDescription::new(Format::R8g8b8a8Unorm))
+        .with_attachment(AttachmentDescription::new(Format::D32Sfloat))
+        .with_subpass(subpass)
+        .validate()
+}
+
+#[test]
+fn attachment_references() {
+    let color = [AttachmentReference::new(
+        0,
+        ImageLayout::ColorAttachmentOptimal,
+    )];
+    let depth = AttachmentReference::new(1, ImageLayout::DepthStencilAttachmentOptimal);
+    let unused = [AttachmentReference::new(
+        ATTACHMENT_UNUSED,
+        ImageLayout::ColorAttachmentOptimal,
+    )];
+    let out_of_range = [AttachmentReference::new(
+        2,
+        ImageLayout::ShaderReadOnlyOptimal,
+    )];
+    let valid = SubpassDescription::new()
+        .with_color_attachments(&color)
+        .with_depth_stencil_attachment(depth)
+        .with_preserve_attachments(&[]);
+    assert_eq!(validate(valid), Ok(()));
+    assert_eq!(
+        validate(SubpassDescription::new().with_color_attachments(&unused)),
+        Ok(())
+    );
+
+    assert_eq!(
+        validate(SubpassDescription::new().with_input_attachments(&out_of_range)),
+        Err(Error::InvalidAttachmentReference(2))
+    );
+    assert_eq!(
+        validate(SubpassDescription::new().with_preserve_attachments(&[2])),
+        Err(Error::InvalidAttachmentReference(2))
+    );
+    assert_eq!(
+        validate(SubpassDescription::new().with_preserve_attachments(&[ATTACHMENT_UNUSED])),
+        Err(Error::InvalidAttachmentReference(ATTACHMENT_UNUSED))
+    );
+    assert_eq!(
+        validate(
+            SubpassDescription::new()
+                .with_color_attachments(&color)
+                .with_resolve_attachments(&[])
+        ),
+        Err(Error::ResolveAttachmentCountMismatch {
+            color_attachments: 1,
+            resolve_attachments: 0
+        })
+    );
+    assert_eq!(
+        validate(SubpassDescription::new().with_depth_stencil_attachment(
+            AttachmentReference::new(0, ImageLayout::DepthStencilAttachmentOptimal)
+        )),
+        Err(Error::InvalidDepthStencilFormat(Format::R8g8b8a8Unorm))
+    );
+}
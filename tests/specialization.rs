@@ -0,0 +1,120 @@
+use vulkan_rs::core::Error;
+use vulkan_rs::pipeline::{SpecializationInfo, SpecializationMapEntry};
+use vulkan_rs::resource::Pod;
+
+#[test]
+fn constants_within_data() {
+    let data = [0u8; 12];
+    let entries = [
+        SpecializationMapEntry::new(0, 0, 4),
+        SpecializationMapEntry::new(1, 4, 8),
+        SpecializationMapEntry::new(2, 12, 0),
+    ];
+    let info = SpecializationInfo::from_bytes(&data, &entries).unwrap();
+    assert_eq!(info.map_entries(), entries);
+    assert_eq!(info.data(), data);
+}
+
+#[test]
+fn constants_outside_data() {
+    let data = [0u8; 12];
+    assert_eq!(
+        SpecializationInfo::from_bytes(&data, &[SpecializationMapEntry::new(3, 8, 8)]).unwrap_err(),
+        Error::InvalidSpecializationConstant(3)
+    );
+    assert_eq!(
+        SpecializationInfo::from_bytes(&data, &[SpecializationMapEntry::new(4, 13, 0)])
+            .unwrap_err(),
+        Error::InvalidSpecializationConstant(4)
+    );
+
+    // The end of the constant overflows.
+    let entry = SpecializationMapEntry::new(5, 4, usize::MAX - 2);
+    assert_eq!(
+        SpecializationInfo::from_bytes(&data, &[entry]).unwrap_err(),
+        Error::InvalidSpecializationConstant(5)
+    );
+}
+
+#[test]
+fn constants_mapped_twice() {
+    let data = [0u8; 8];
+    let entries = [
+        SpecializationMapEntry::new(7, 0, 4),
+        SpecializationMapEntry::new(7, 4, 4),
+    ];
+    assert_eq!(
+        SpecializationInfo::from_bytes(&data, &entries).unwrap_err(),
+        Error::InvalidSpecializationConstant(7)
+    );
+}
+
+#[test]
+fn constants_replace_earlier_values() {
+    let info = SpecializationInfo::new()
+        .with_constant(0, 1.5f32)
+        .with_constant(1, true)
+        .with_constant(0, 2.0f64);
+
+    let entries: Vec<_> = info
+        .map_entries()
+        .iter()
+        .map(|entry| (entry.constant_id(), entry.offset(), entry.size()))
+        .collect();
+    assert_eq!(entries, [(1, 0, 4), (0, 4, 8)]);
+    assert_eq!(info.data().len(), 12);
+    assert_eq!(info.data()[..4], 1u32.to_ne_bytes());
+    assert_eq!(info.data()[4..], 2.0f64.to_ne_bytes());
+}
+
+#[test]
+fn constants_of_the_same_size_are_overwritten() {
+    let info = SpecializationInfo::new()
+        .with_constant(0, 1.5f32)
+        .with_constant(1, 7u32)
+        .with_constant(0, 2.5f32);
+    assert_eq!(info.map_entries().len(), 2);
+    assert_eq!(info.map_entries()[0].offset(), 0);
+    assert_eq!(info.data().len(), 8);
+    assert_eq!(info.data()[..4], 2.5f32.to_ne_bytes());
+
+    // Constants sharing bytes keep their value when one of them is replaced.
+    let data = 3u32.to_ne_bytes();
+    let entries = [
+        SpecializationMapEntry::new(0, 0, 4),
+        SpecializationMapEntry::new(1, 0, 4),
+    ];
+    let info = SpecializationInfo::from_bytes(&data, &entries)
+        .unwrap()
+        .with_constant(0, 4u32);
+    let entries: Vec<_> = info
+        .map_entries()
+        .iter()
+        .map(|entry| (entry.constant_id(), entry.offset(), entry.size()))
+        .collect();
+    assert_eq!(entries, [(1, 0, 4), (0, 4, 4)]);
+    assert_eq!(info.data()[..4], 3u32.to_ne_bytes());
+    assert_eq!(info.data()[4..], 4u32.to_ne_bytes());
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod)]
+struct Constants {
+    workgroup_size: u32,
+    scale: f32,
+}
+
+#[test]
+fn constants_from_struct() {
+    let constants = Constants {
+        workgroup_size: 64,
+        scale: 0.5,
+    };
+    let entries = [
+        SpecializationMapEntry::new(0, 0, 4),
+        SpecializationMapEntry::new(1, 4, 4),
+    ];
+    let info = SpecializationInfo::from_struct(&constants, &entries).unwrap();
+    assert_eq!(info.data()[..4], 64u32.to_ne_bytes());
+    assert_eq!(info.data()[4..], 0.5f32.to_ne_bytes());
+}
@@ -0,0 +1,46 @@
+use std::borrow::Cow;
+
+use vulkan_rs::core::Error;
+use vulkan_rs::shaders::{spirv_words, SpirvError};
+
+static CODE: &[u8] = vulkan_rs::include_spirv!("fixtures/compute.spv");
+
+#[test]
+fn aligned_code_is_borrowed() {
+    assert_eq!(CODE.as_ptr() as usize % 4, 0);
+    let words = spirv_words(CODE).unwrap();
+    assert!(matches!(words, Cow::Borrowed(_)));
+    assert_eq!(words[0], 0x0723_0203);
+}
+
+#[test]
+fn misaligned_code_is_copied() {
+    let mut buffer = vec![0u8; CODE.len() + 1];
+    buffer[1..].copy_from_slice(CODE);
+    let words = spirv_words(&buffer[1..]).unwrap();
+    assert!(matches!(words, Cow::Owned(_)));
+    assert_eq!(*words, *spirv_words(CODE).unwrap());
+}
+
+#[test]
+fn byte_swapped_code_is_converted() {
+    let swapped: Vec<u8> = CODE
+        .chunks_exact(4)
+        .flat_map(|word| [word[3], word[2], word[1], word[0]])
+        .collect();
+    assert_eq!(*spirv_words(&swapped).unwrap(), *spirv_words(CODE).unwrap());
+}
+
+#[test]
+fn malformed_header() {
+    let mut code = CODE.to_vec();
+    code[4..8].copy_from_slice(&0x0002_0000u32.to_ne_bytes());
+    assert_eq!(
+        spirv_words(&code).unwrap_err(),
+        Error::InvalidSpirv(SpirvError::UnsupportedVersion(0x0002_0000))
+    );
+    assert_eq!(
+        spirv_words(&CODE[..16]).unwrap_err(),
+        Error::InvalidSpirv(SpirvError::InvalidLength(16))
+    );
+}
@@ -0,0 +1,238 @@
+use vulkan_rs::core::{Error, Extent3D};
+use vulkan_rs::format::Format;
+use vulkan_rs::init::{ApiVersion, ApplicationInfo, Instance};
+use vulkan_rs::resource::upload::{ImageUploadRegion, SubresourceLayouts, Uploader};
+use vulkan_rs::resource::{
+    BufferBuilder, BufferUsage, BufferUsageFlagsBuilder, ImageAspectFlagsBuilder, ImageLayout,
+    ImageSubresourceRange,
+};
+
+#[test]
+fn uncompressed_region_size() {
+    let data = [0u8; 1];
+    let region = ImageUploadRegion::new(&data, Extent3D::new(64, 32, 1));
+    assert_eq!(
+        region.required_size(Format::R8g8b8a8Unorm),
+        Some(64 * 32 * 4)
+    );
+    assert_eq!(
+        region.required_size(Format::R32g32b32Sfloat),
+        Some(64 * 32 * 12)
+    );
+
+    let layers = region.with_array_layers(2, 6);
+    assert_eq!(layers.required_size(Format::R8Unorm), Some(64 * 32 * 6));
+
+    let volume = ImageUploadRegion::new(&data, Extent3D::new(16, 16, 8));
+    assert_eq!(
+        volume.required_size(Format::R16Sfloat),
+        Some(16 * 16 * 8 * 2)
+    );
+}
+
+#[test]
+fn compressed_region_size() {
+    let data = [0u8; 1];
+    let region = ImageUploadRegion::new(&data, Extent3D::new(64, 32, 1));
+    assert_eq!(
+        region.required_size(Format::Bc1RgbaUnormBlock),
+        Some(16 * 8 * 8)
+    );
+    assert_eq!(
+        region.required_size(Format::Bc7UnormBlock),
+        Some(16 * 8 * 16)
+    );
+
+    // Partial blocks at the edges of a mip level take a whole block.
+    let edge = ImageUploadRegion::new(&data, Extent3D::new(6, 2, 1));
+    assert_eq!(edge.required_size(Format::Bc3UnormBlock), Some(2 * 16));
+    assert_eq!(edge.required_size(Format::Astc5x4UnormBlock), Some(2 * 16));
+    assert_eq!(
+        edge.required_size(Format::G8b8g8r8_422Unorm),
+        Some(3 * 2 * 4)
+    );
+}
+
+#[test]
+fn depth_stencil_region_size() {
+    let data = [0u8; 1];
+    let depth = ImageUploadRegion::new(&data, Extent3D::new(8, 8, 1))
+        .with_aspect_mask(ImageAspectFlagsBuilder::new().depth(true).build());
+    let stencil = ImageUploadRegion::new(&data, Extent3D::new(8, 8, 1))
+        .with_aspect_mask(ImageAspectFlagsBuilder::new().stencil(true).build());
+    let both = ImageUploadRegion::new(&data, Extent3D::new(8, 8, 1)).with_aspect_mask(
+        ImageAspectFlagsBuilder::new()
+            .depth(true)
+            .stencil(true)
+            .build(),
+    );
+
+    assert_eq!(depth.required_size(Format::D32Sfloat), Some(8 * 8 * 4));
+    assert_eq!(depth.required_size(Format::D24UnormS8Uint), Some(8 * 8 * 4));
+    assert_eq!(depth.required_size(Format::D16UnormS8Uint), Some(8 * 8 * 2));
+    assert_eq!(stencil.required_size(Format::D32SfloatS8Uint), Some(8 * 8));
+    assert_eq!(both.required_size(Format::D24UnormS8Uint), None);
+}
+
+#[test]
+fn unknown_and_overflowing_sizes() {
+    let data = [0u8; 1];
+    let region = ImageUploadRegion::new(&data, Extent3D::new(64, 64, 1));
+    assert_eq!(region.required_size(Format::Undefined), None);
+    assert_eq!(region.required_size(Format::G8B8R8_3plane420Unorm), None);
+
+    let huge = ImageUploadRegion::new(&data, Extent3D::new(u32::MAX, u32::MAX, u32::MAX))
+        .with_array_layers(0, u32::MAX);
+    assert_eq!(huge.required_size(Format::R64g64b64a64Sfloat), None);
+}
+
+#[test]
+fn overlapping_ranges_are_merged() {
+    let color = ImageAspectFlagsBuilder::new().color(true).build();
+    let mut layouts = SubresourceLayouts::new();
+    layouts
+        .insert(
+            ImageSubresourceRange::new(color, 0, 1, 0, 4),
+            ImageLayout::ShaderReadOnlyOptimal,
+        )
+        .unwrap();
+    layouts
+        .insert(
+            ImageSubresourceRange::new(color, 0, 1, 2, 4),
+            ImageLayout::ShaderReadOnlyOptimal,
+        )
+        .unwrap();
+    layouts
+        .insert(
+            ImageSubresourceRange::new(color, 0, 1, 6, 2),
+            ImageLayout::ShaderReadOnlyOptimal,
+        )
+        .unwrap();
+    layouts
+        .insert(
+            ImageSubresourceRange::new(color, 1, 1, 0, 1),
+            ImageLayout::TransferSrcOptimal,
+        )
+        .unwrap();
+
+    assert_eq!(
+        layouts.ranges(),
+        [
+            (
+                ImageSubresourceRange::new(color, 0, 1, 0, 8),
+                ImageLayout::ShaderReadOnlyOptimal
+            ),
+            (
+                ImageSubresourceRange::new(color, 1, 1, 0, 1),
+                ImageLayout::TransferSrcOptimal
+            ),
+        ]
+    );
+}
+
+#[test]
+fn aspects_of_the_same_layers_share_a_range() {
+    let depth = ImageAspectFlagsBuilder::new().depth(true).build();
+    let stencil = ImageAspectFlagsBuilder::new().stencil(true).build();
+    let mut layouts = SubresourceLayouts::new();
+    layouts
+        .insert(
+            ImageSubresourceRange::new(depth, 0, 1, 0, 1),
+            ImageLayout::DepthStencilAttachmentOptimal,
+        )
+        .unwrap();
+    layouts
+        .insert(
+            ImageSubresourceRange::new(stencil, 0, 1, 0, 1),
+            ImageLayout::DepthStencilAttachmentOptimal,
+        )
+        .unwrap();
+
+    let both = ImageAspectFlagsBuilder::new()
+        .depth(true)
+        .stencil(true)
+        .build();
+    assert_eq!(
+        layouts.ranges(),
+        [(
+            ImageSubresourceRange::new(both, 0, 1, 0, 1),
+            ImageLayout::DepthStencilAttachmentOptimal
+        )]
+    );
+}
+
+#[test]
+fn conflicting_layouts_are_rejected() {
+    let color = ImageAspectFlagsBuilder::new().color(true).build();
+    let mut layouts = SubresourceLayouts::new();
+    layouts
+        .insert(
+            ImageSubresourceRange::new(color, 2, 1, 0, 4),
+            ImageLayout::ShaderReadOnlyOptimal,
+        )
+        .unwrap();
+
+    assert_eq!(
+        layouts.insert(
+            ImageSubresourceRange::new(color, 2, 1, 3, 2),
+            ImageLayout::TransferSrcOptimal,
+        ),
+        Err(Error::ConflictingImageLayouts {
+            mip_level: 2,
+            array_layer: 3
+        })
+    );
+    // Adjoining layers may have another layout, and the failed insert recorded nothing.
+    layouts
+        .insert(
+            ImageSubresourceRange::new(color, 2, 1, 4, 2),
+            ImageLayout::TransferSrcOptimal,
+        )
+        .unwrap();
+    assert_eq!(
+        layouts.ranges(),
+        [
+            (
+                ImageSubresourceRange::new(color, 2, 1, 0, 4),
+                ImageLayout::ShaderReadOnlyOptimal
+            ),
+            (
+                ImageSubresourceRange::new(color, 2, 1, 4, 2),
+                ImageLayout::TransferSrcOptimal
+            ),
+        ]
+    );
+}
+
+#[test]
+#[ignore = "requires a Vulkan device"]
+fn buffer_upload_needs_transfer_dst() {
+    let app_info = ApplicationInfo {
+        application_name: None,
+        application_version: Default::default(),
+        engine_name: None,
+        engine_version: Default::default(),
+        api_version: ApiVersion::V1_0,
+    };
+    let instance = Instance::builder()
+        .with_application_info(&app_info)
+        .build()
+        .unwrap();
+    let physical_device = instance.enumerate_physical_devices().next().unwrap();
+    let device = physical_device.create_device(&[0], &[&[1.0f32]], None, None);
+    let queue = device.get_queue(0, 0).unwrap();
+
+    let buffer = BufferBuilder::new(
+        64,
+        BufferUsageFlagsBuilder::new().uniform_buffer(true).build(),
+    )
+    .build(&device)
+    .unwrap();
+    let mut uploader = Uploader::new(&device, &queue, 0);
+    assert_eq!(
+        uploader.upload_buffer(&buffer, 0, &[0u32; 4]),
+        Err(Error::MissingBufferUsage(BufferUsage::TransferDst))
+    );
+    assert!(uploader.is_empty());
+    assert_eq!(uploader.staged_size(), 0);
+}
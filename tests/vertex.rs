@@ -0,0 +1,116 @@
+use vulkan_rs::format::Format;
+use vulkan_rs::pipeline::{
+    Vertex, VertexInputAttributeDescription, VertexInputDescriptions, VertexInputRate,
+};
+
+#[allow(dead_code)]
+#[repr(C)]
+#[derive(Vertex)]
+struct MeshVertex {
+    position: [f32; 3],
+    normal: [f32; 3],
+    uv: [f32; 2],
+    #[vertex(normalized)]
+    color: [u8; 4],
+    joints: [u16; 4],
+}
+
+#[allow(dead_code)]
+#[repr(C)]
+#[derive(Vertex)]
+struct Instance {
+    model: [[f32; 4]; 4],
+    #[vertex(format = R8g8b8a8Srgb)]
+    tint: [u8; 4],
+    #[vertex(skip)]
+    _padding: [u32; 3],
+    id: u32,
+}
+
+#[allow(dead_code)]
+#[repr(C)]
+#[derive(Vertex)]
+struct Precise([f64; 3], f32);
+
+fn attributes<V: Vertex>(binding: u32, first_location: u32) -> Vec<(u32, u32, Format, u32)> {
+    V::attribute_descriptions(binding, first_location)
+        .iter()
+        .map(|attribute| {
+            (
+                attribute.location(),
+                attribute.binding(),
+                attribute.format(),
+                attribute.offset(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn formats_and_offsets_follow_fields() {
+    assert_eq!(
+        attributes::<MeshVertex>(0, 0),
+        [
+            (0, 0, Format::R32g32b32Sfloat, 0),
+            (1, 0, Format::R32g32b32Sfloat, 12),
+            (2, 0, Format::R32g32Sfloat, 24),
+            (3, 0, Format::R8g8b8a8Unorm, 32),
+            (4, 0, Format::R16g16b16a16Uint, 36),
+        ]
+    );
+
+    let binding = MeshVertex::binding_description(0, VertexInputRate::Vertex);
+    assert_eq!(binding.stride(), 44);
+}
+
+#[test]
+fn matrices_take_a_location_per_column() {
+    assert_eq!(
+        attributes::<Instance>(1, 5),
+        [
+            (5, 1, Format::R32g32b32a32Sfloat, 0),
+            (6, 1, Format::R32g32b32a32Sfloat, 16),
+            (7, 1, Format::R32g32b32a32Sfloat, 32),
+            (8, 1, Format::R32g32b32a32Sfloat, 48),
+            (9, 1, Format::R8g8b8a8Srgb, 64),
+            (10, 1, Format::R32Uint, 80),
+        ]
+    );
+}
+
+#[test]
+fn wide_vectors_take_two_locations() {
+    assert_eq!(
+        attributes::<Precise>(0, 0),
+        [
+            (0, 0, Format::R64g64b64Sfloat, 0),
+            (2, 0, Format::R32Sfloat, 24),
+        ]
+    );
+}
+
+#[test]
+fn descriptions_chain_bindings_and_locations() {
+    let descriptions = VertexInputDescriptions::new()
+        .with_vertex::<MeshVertex>(VertexInputRate::Vertex)
+        .with_vertex::<Instance>(VertexInputRate::Instance);
+
+    let bindings: Vec<_> = descriptions
+        .bindings()
+        .iter()
+        .map(|binding| (binding.binding(), binding.stride(), binding.input_rate()))
+        .collect();
+    assert_eq!(
+        bindings,
+        [
+            (0, 44, VertexInputRate::Vertex),
+            (1, 84, VertexInputRate::Instance)
+        ]
+    );
+
+    assert_eq!(descriptions.attributes().len(), 11);
+    assert_eq!(
+        descriptions.attributes()[5],
+        VertexInputAttributeDescription::new(5, 1, Format::R32g32b32a32Sfloat, 0)
+    );
+}